@@ -24,6 +24,44 @@ impl fmt::Display for Error {
 
 impl core::error::Error for Error {}
 
+/// Error type which reports the position of the first invalid character.
+///
+/// Returned by [`mixed::decode_with_position`][crate::mixed::decode_with_position], e.g. for
+/// pointing users at a typo in a hex fingerprint.
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum PositionError {
+    /// Invalid character at the given byte index of the provided Base16 string.
+    InvalidCharacter {
+        /// Byte index of the first invalid character.
+        index: usize,
+    },
+
+    /// Odd-length Base16 string, or insufficient output buffer length.
+    InvalidLength,
+}
+
+impl fmt::Display for PositionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PositionError::InvalidCharacter { index } => {
+                write!(f, "invalid Base16 character at index {index}")
+            }
+            PositionError::InvalidLength => f.write_str("invalid Base16 length"),
+        }
+    }
+}
+
+impl core::error::Error for PositionError {}
+
+impl From<PositionError> for Error {
+    fn from(err: PositionError) -> Error {
+        match err {
+            PositionError::InvalidCharacter { .. } => Error::InvalidEncoding,
+            PositionError::InvalidLength => Error::InvalidLength,
+        }
+    }
+}
+
 impl From<Error> for fmt::Error {
     fn from(_: Error) -> fmt::Error {
         fmt::Error
@@ -55,6 +55,10 @@
 //! assert_eq!(res, raw);
 //! let res = base16ct::mixed::decode(mixed_hex_str, &mut buf).unwrap();
 //! assert_eq!(res, raw);
+//!
+//! // `decode_with_position` additionally reports where decoding failed
+//! let err = base16ct::mixed::decode_with_position("abCD12x4", &mut buf).unwrap_err();
+//! assert_eq!(err, base16ct::PositionError::InvalidCharacter { index: 6 });
 //! ```
 //!
 //! [RFC 4648]: https://tools.ietf.org/html/rfc4648
@@ -67,6 +71,9 @@ extern crate alloc;
 pub mod lower;
 /// Function for decoding mixed Base16 (hex)
 pub mod mixed;
+/// Serde helpers for hex-encoded byte fields
+#[cfg(feature = "serde")]
+pub mod serde;
 /// Function for decoding and encoding upper Base16 (hex)
 pub mod upper;
 
@@ -77,7 +84,7 @@ mod error;
 
 pub use crate::{
     display::HexDisplay,
-    error::{Error, Result},
+    error::{Error, PositionError, Result},
 };
 
 #[cfg(feature = "alloc")]
@@ -120,3 +127,43 @@ fn decode_inner<'a>(
         _ => Err(Error::InvalidEncoding),
     }
 }
+
+/// Like [`decode_inner`], but reports the index of the first invalid character on error.
+///
+/// The index is computed without data-dependent branches.
+fn decode_inner_with_position<'a>(
+    src: &[u8],
+    dst: &'a mut [u8],
+    decode_nibble: impl Fn(u8) -> u16,
+) -> core::result::Result<&'a [u8], PositionError> {
+    let dst = decoded_len(src)
+        .ok()
+        .and_then(|len| dst.get_mut(..len))
+        .ok_or(PositionError::InvalidLength)?;
+
+    let mut err: u16 = 0;
+    let mut index: usize = 0;
+    for (i, (src, dst)) in src.chunks_exact(2).zip(dst.iter_mut()).enumerate() {
+        let hi = decode_nibble(src[0]);
+        let lo = decode_nibble(src[1]);
+
+        // 1 if the nibble is invalid, 0 otherwise
+        let hi_err = hi >> 8 & 1;
+        let lo_err = lo >> 8 & 1;
+
+        // Record the index of the first invalid character only
+        let hi_first = usize::from(hi_err & !err).wrapping_neg();
+        err |= hi_err;
+        let lo_first = usize::from(lo_err & !err).wrapping_neg();
+        err |= lo_err;
+        index |= (2 * i) & hi_first;
+        index |= (2 * i + 1) & lo_first;
+
+        *dst = ((hi << 4) | lo) as u8;
+    }
+
+    match err {
+        0 => Ok(dst),
+        _ => Err(PositionError::InvalidCharacter { index }),
+    }
+}
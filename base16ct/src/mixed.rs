@@ -1,4 +1,4 @@
-use crate::{Error, decode_inner};
+use crate::{Error, PositionError, decode_inner, decode_inner_with_position};
 #[cfg(feature = "alloc")]
 use crate::{Vec, decoded_len};
 
@@ -15,6 +15,22 @@ pub fn decode_vec(input: impl AsRef<[u8]>) -> Result<Vec<u8>, Error> {
     Ok(output)
 }
 
+/// Decode a mixed Base16 (hex) string into the provided destination buffer, reporting the
+/// index of the first invalid character on error.
+pub fn decode_with_position(src: impl AsRef<[u8]>, dst: &mut [u8]) -> Result<&[u8], PositionError> {
+    decode_inner_with_position(src.as_ref(), dst, decode_nibble)
+}
+
+/// Decode a mixed Base16 (hex) string into a byte vector, reporting the index of the first
+/// invalid character on error.
+#[cfg(feature = "alloc")]
+pub fn decode_vec_with_position(input: impl AsRef<[u8]>) -> Result<Vec<u8>, PositionError> {
+    let len = decoded_len(input.as_ref()).map_err(|_| PositionError::InvalidLength)?;
+    let mut output = vec![0u8; len];
+    decode_with_position(input, &mut output)?;
+    Ok(output)
+}
+
 /// Decode a single nibble of lower hex
 #[inline(always)]
 fn decode_nibble(src: u8) -> u16 {
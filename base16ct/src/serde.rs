@@ -0,0 +1,196 @@
+//! Serde helpers for hex-encoded byte fields.
+//!
+//! The [`lower`] and [`upper`] modules can be used with `#[serde(with = "...")]`
+//! on byte fields, e.g. keys in a JSON configuration, which are serialized as
+//! a lower or upper case hex string respectively. The [`HexBytes`] wrapper
+//! does the same for fixed-size arrays without requiring the `alloc` feature.
+//!
+//! Encoding and decoding are constant-time with respect to the bytes, and
+//! invalid strings are reported without echoing their contents. Hex strings
+//! are decoded regardless of their case.
+//!
+//! ```
+//! # #[cfg(feature = "alloc")]
+//! # {
+//! use base16ct::serde::HexBytes;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Deserialize, Serialize)]
+//! struct Config {
+//!     #[serde(with = "base16ct::serde::lower")]
+//!     salt: Vec<u8>,
+//!     key: HexBytes<4>,
+//! }
+//! # }
+//! ```
+
+use core::fmt;
+
+use ::serde::{
+    Deserialize, Deserializer, Serialize, Serializer,
+    de::{Error, Unexpected, Visitor},
+};
+
+use crate::HexDisplay;
+
+/// Serialize and deserialize bytes as a lower case hex string.
+pub mod lower {
+    use ::serde::Serializer;
+
+    #[cfg(feature = "alloc")]
+    use {crate::Vec, ::serde::Deserializer};
+
+    /// Serialize the given bytes as a lower case hex string.
+    pub fn serialize<S, T>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: AsRef<[u8]> + ?Sized,
+    {
+        super::serialize_hex::<_, false>(value.as_ref(), serializer)
+    }
+
+    /// Deserialize bytes from a hex string, e.g. into a `Vec<u8>` or a `[u8; N]`.
+    #[cfg(feature = "alloc")]
+    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: TryFrom<Vec<u8>>,
+    {
+        super::deserialize_hex(deserializer)
+    }
+}
+
+/// Serialize and deserialize bytes as an upper case hex string.
+pub mod upper {
+    use ::serde::Serializer;
+
+    #[cfg(feature = "alloc")]
+    use {crate::Vec, ::serde::Deserializer};
+
+    /// Serialize the given bytes as an upper case hex string.
+    pub fn serialize<S, T>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: AsRef<[u8]> + ?Sized,
+    {
+        super::serialize_hex::<_, true>(value.as_ref(), serializer)
+    }
+
+    /// Deserialize bytes from a hex string, e.g. into a `Vec<u8>` or a `[u8; N]`.
+    #[cfg(feature = "alloc")]
+    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: TryFrom<Vec<u8>>,
+    {
+        super::deserialize_hex(deserializer)
+    }
+}
+
+/// Fixed-size array of bytes serialized as a lower case hex string.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct HexBytes<const N: usize>(pub [u8; N]);
+
+impl<const N: usize> AsRef<[u8]> for HexBytes<N> {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl<const N: usize> From<[u8; N]> for HexBytes<N> {
+    fn from(bytes: [u8; N]) -> Self {
+        Self(bytes)
+    }
+}
+
+impl<const N: usize> From<HexBytes<N>> for [u8; N] {
+    fn from(bytes: HexBytes<N>) -> [u8; N] {
+        bytes.0
+    }
+}
+
+impl<const N: usize> Serialize for HexBytes<N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_hex::<_, false>(&self.0, serializer)
+    }
+}
+
+impl<'de, const N: usize> Deserialize<'de> for HexBytes<N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(ArrayVisitor).map(Self)
+    }
+}
+
+/// Serialize `bytes` as a hex string, without allocating.
+fn serialize_hex<S: Serializer, const UPPERCASE: bool>(
+    bytes: &[u8],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    if UPPERCASE {
+        serializer.collect_str(&format_args!("{:X}", HexDisplay(bytes)))
+    } else {
+        serializer.collect_str(&format_args!("{:x}", HexDisplay(bytes)))
+    }
+}
+
+/// Deserialize a hex string into any type which can be built from its bytes.
+#[cfg(feature = "alloc")]
+fn deserialize_hex<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: TryFrom<crate::Vec<u8>>,
+{
+    let bytes = deserializer.deserialize_str(VecVisitor)?;
+    let len = bytes.len();
+    T::try_from(bytes).map_err(|_| D::Error::invalid_length(len, &"a valid number of bytes"))
+}
+
+/// Map a decoding error to a deserialization error, without revealing the
+/// possibly secret contents of `v`.
+fn decode_error<E: Error>(v: &str, err: crate::Error) -> E {
+    match err {
+        crate::Error::InvalidLength => E::invalid_length(v.len(), &"an even number of hex digits"),
+        crate::Error::InvalidEncoding => E::invalid_value(
+            Unexpected::Other("<potentially secret hex string>"),
+            &"a sequence of hex digits (0-9, a-f, A-F)",
+        ),
+    }
+}
+
+/// Visitor decoding a hex string of a fixed length.
+struct ArrayVisitor<const N: usize>;
+
+impl<const N: usize> Visitor<'_> for ArrayVisitor<N> {
+    type Value = [u8; N];
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "a string of {} hex digits", N * 2)
+    }
+
+    fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+        if v.len() != N * 2 {
+            return Err(E::invalid_length(v.len(), &self));
+        }
+
+        let mut bytes = [0u8; N];
+        crate::mixed::decode(v, &mut bytes).map_err(|err| decode_error(v, err))?;
+        Ok(bytes)
+    }
+}
+
+/// Visitor decoding a hex string of any length.
+#[cfg(feature = "alloc")]
+struct VecVisitor;
+
+#[cfg(feature = "alloc")]
+impl Visitor<'_> for VecVisitor {
+    type Value = crate::Vec<u8>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a string of hex digits")
+    }
+
+    fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+        crate::mixed::decode_vec(v).map_err(|err| decode_error(v, err))
+    }
+}
@@ -122,6 +122,43 @@ fn mixed_reject_odd_size_input() {
     )
 }
 
+#[test]
+fn mixed_decode_with_position() {
+    for vector in HEX_TEST_VECTORS {
+        let mut buf = [0u8; 5];
+        let out = base16ct::mixed::decode_with_position(vector.upper_hex, &mut buf).unwrap();
+        assert_eq!(vector.raw, out);
+        let out = base16ct::mixed::decode_with_position(vector.lower_hex, &mut buf).unwrap();
+        assert_eq!(vector.raw, out);
+    }
+
+    let mut buf = [0u8; 4];
+    for (input, index) in [
+        ("g0aB12cD", 0),
+        ("0gaB12cD", 1),
+        ("0aB12c D", 6),
+        ("0aB1:c D", 4),
+    ] {
+        assert_eq!(
+            base16ct::mixed::decode_with_position(input, &mut buf),
+            Err(base16ct::PositionError::InvalidCharacter { index })
+        );
+    }
+}
+
+#[test]
+fn mixed_with_position_reject_invalid_length() {
+    let mut out = [0u8; 3];
+    assert_eq!(
+        Err(base16ct::PositionError::InvalidLength),
+        base16ct::mixed::decode_with_position(b"12345", &mut out),
+    );
+    assert_eq!(
+        Err(base16ct::PositionError::InvalidLength),
+        base16ct::mixed::decode_with_position(b"12345678", &mut out),
+    );
+}
+
 #[test]
 #[cfg(feature = "alloc")]
 fn encode_and_decode_various_lengths() {
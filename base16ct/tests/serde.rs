@@ -0,0 +1,67 @@
+//! Serde helpers tests.
+
+#![cfg(feature = "serde")]
+
+use base16ct::serde::HexBytes;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize, Serialize, Eq, PartialEq)]
+struct Key {
+    id: HexBytes<4>,
+}
+
+#[test]
+fn hex_bytes_round_trip() {
+    let key = Key {
+        id: HexBytes([0xde, 0xad, 0xbe, 0xef]),
+    };
+
+    let json = serde_json::to_string(&key).unwrap();
+    assert_eq!(json, r#"{"id":"deadbeef"}"#);
+    assert_eq!(serde_json::from_str::<Key>(&json).unwrap(), key);
+    assert_eq!(
+        serde_json::from_str::<Key>(r#"{"id":"DEADbeef"}"#).unwrap(),
+        key
+    );
+}
+
+#[test]
+fn hex_bytes_invalid() {
+    assert!(serde_json::from_str::<Key>(r#"{"id":"deadbe"}"#).is_err());
+    assert!(serde_json::from_str::<Key>(r#"{"id":"deadbeef00"}"#).is_err());
+
+    let err = serde_json::from_str::<Key>(r#"{"id":"deadbeeg"}"#).unwrap_err();
+    assert!(!err.to_string().contains("deadbeeg"));
+}
+
+#[cfg(feature = "alloc")]
+mod with {
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Deserialize, Serialize, Eq, PartialEq)]
+    struct Config {
+        #[serde(with = "base16ct::serde::lower")]
+        salt: Vec<u8>,
+
+        #[serde(with = "base16ct::serde::upper")]
+        key: [u8; 2],
+    }
+
+    #[test]
+    fn round_trip() {
+        let config = Config {
+            salt: vec![0x01, 0xab, 0xcd],
+            key: [0xef, 0x10],
+        };
+
+        let json = serde_json::to_string(&config).unwrap();
+        assert_eq!(json, r#"{"salt":"01abcd","key":"EF10"}"#);
+        assert_eq!(serde_json::from_str::<Config>(&json).unwrap(), config);
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(serde_json::from_str::<Config>(r#"{"salt":"01a","key":"EF10"}"#).is_err());
+        assert!(serde_json::from_str::<Config>(r#"{"salt":"01","key":"EF1000"}"#).is_err());
+    }
+}
@@ -0,0 +1,272 @@
+//! Buffered Base32 decoder.
+
+use crate::{
+    Encoding,
+    Error::InvalidLength,
+    Result,
+    encoding::{decode_unpadded, decoded_len, remove_padding},
+};
+use core::{cmp, marker::PhantomData};
+
+#[cfg(feature = "alloc")]
+use {alloc::vec::Vec, core::iter};
+
+#[cfg(doc)]
+use crate::{Base32, Base32Unpadded};
+
+/// Stateful Base32 decoder with support for buffered, incremental decoding.
+///
+/// The `E` type parameter can be any type which impls [`Encoding`] such as
+/// [`Base32`] or [`Base32Unpadded`].
+///
+/// Input is decoded in 8-character blocks as output is requested, so only a
+/// partially consumed block is buffered between calls to [`Decoder::decode`].
+#[derive(Clone)]
+pub struct Decoder<'i, E: Encoding> {
+    /// Remaining Base32 input data, with any padding removed.
+    input: &'i [u8],
+
+    /// Length of the remaining data after Base32 decoding.
+    remaining_len: usize,
+
+    /// Block buffer used for non-block-aligned data.
+    block_buffer: BlockBuffer,
+
+    /// Phantom parameter for the Base32 encoding in use.
+    encoding: PhantomData<E>,
+}
+
+impl<'i, E: Encoding> Decoder<'i, E> {
+    /// Create a new decoder for a byte slice containing Base32-encoded data.
+    ///
+    /// # Returns
+    /// - `Ok(decoder)` on success.
+    /// - `Err(Error::InvalidLength)` if the input buffer is empty.
+    /// - `Err(Error::InvalidEncoding)` if the input is padded incorrectly.
+    pub fn new(input: &'i [u8]) -> Result<Self> {
+        if input.is_empty() {
+            return Err(InvalidLength);
+        }
+
+        let input = if E::PADDED {
+            remove_padding(input)?
+        } else {
+            input
+        };
+
+        Ok(Self {
+            input,
+            remaining_len: decoded_len(input.len()),
+            block_buffer: BlockBuffer::default(),
+            encoding: PhantomData,
+        })
+    }
+
+    /// Fill the provided buffer with data decoded from Base32.
+    ///
+    /// Enough Base32 input data must remain to fill the entire buffer.
+    ///
+    /// # Returns
+    /// - `Ok(bytes)` if the expected amount of data was read
+    /// - `Err(Error::InvalidLength)` if the exact amount of data couldn't be read
+    /// - `Err(Error::InvalidEncoding)` if the input contains invalid characters
+    pub fn decode<'o>(&mut self, out: &'o mut [u8]) -> Result<&'o [u8]> {
+        if out.len() > self.remaining_len {
+            return Err(InvalidLength);
+        }
+
+        let mut out_pos = 0;
+
+        while out_pos < out.len() {
+            // If there's data in the block buffer, use it
+            if !self.block_buffer.is_empty() {
+                let out_rem = out.len().checked_sub(out_pos).ok_or(InvalidLength)?;
+                let bytes = self.block_buffer.take(out_rem)?;
+                out[out_pos..][..bytes.len()].copy_from_slice(bytes);
+                out_pos = out_pos.checked_add(bytes.len()).ok_or(InvalidLength)?;
+            }
+
+            // Attempt to decode a stride of block-aligned data
+            let in_blocks = self.input.len() / 8;
+            let out_rem = out.len().checked_sub(out_pos).ok_or(InvalidLength)?;
+            let out_blocks = out_rem / BlockBuffer::SIZE;
+            let blocks = cmp::min(in_blocks, out_blocks);
+
+            if blocks > 0 {
+                let (in_aligned, in_rem) = self.input.split_at(blocks * 8);
+                let out_buf = &mut out[out_pos..][..blocks * BlockBuffer::SIZE];
+                let decoded_len = decode_unpadded::<E>(in_aligned, out_buf)?.len();
+                out_pos = out_pos.checked_add(decoded_len).ok_or(InvalidLength)?;
+                self.input = in_rem;
+            }
+
+            // If the input or output isn't block-aligned, decode the next
+            // block into the block buffer
+            if out_pos < out.len() {
+                if self.input.is_empty() {
+                    return Err(InvalidLength);
+                }
+
+                self.fill_block_buffer()?;
+            }
+        }
+
+        self.remaining_len = self
+            .remaining_len
+            .checked_sub(out.len())
+            .ok_or(InvalidLength)?;
+
+        Ok(out)
+    }
+
+    /// Decode all remaining Base32 data, placing the result into `buf`.
+    ///
+    /// If successful, this function will return the data decoded into `buf`.
+    #[cfg(feature = "alloc")]
+    pub fn decode_to_end<'o>(&mut self, buf: &'o mut Vec<u8>) -> Result<&'o [u8]> {
+        let start_len = buf.len();
+        let remaining_len = self.remaining_len();
+
+        // Append `remaining_len` zeroes to the vector
+        buf.extend(iter::repeat_n(0, remaining_len));
+        self.decode(&mut buf[start_len..])?;
+        Ok(&buf[start_len..])
+    }
+
+    /// Get the length of the remaining data after Base32 decoding.
+    ///
+    /// Decreases every time data is decoded.
+    pub fn remaining_len(&self) -> usize {
+        self.remaining_len
+    }
+
+    /// Has all of the input data been decoded?
+    pub fn is_finished(&self) -> bool {
+        self.remaining_len == 0
+    }
+
+    /// Decode the next block of input into the block buffer.
+    fn fill_block_buffer(&mut self) -> Result<()> {
+        let (block, rest) = self.input.split_at(cmp::min(self.input.len(), 8));
+        let mut buf = [0u8; BlockBuffer::SIZE];
+        let decoded = decode_unpadded::<E>(block, &mut buf)?;
+        self.block_buffer.fill(decoded)?;
+        self.input = rest;
+        Ok(())
+    }
+}
+
+/// Base32 decode buffer for a 1-block input.
+///
+/// This handles a partially decoded block of data, i.e. data which has been
+/// decoded but not read.
+#[derive(Clone, Default, Debug)]
+struct BlockBuffer {
+    /// 5 decoded bytes from an 8-byte Base32-encoded input.
+    decoded: [u8; Self::SIZE],
+
+    /// Length of the buffer.
+    length: usize,
+
+    /// Position within the buffer.
+    position: usize,
+}
+
+impl BlockBuffer {
+    /// Size of the buffer in bytes.
+    const SIZE: usize = 5;
+
+    /// Fill the buffer with up to 5 bytes of decoded Base32 input.
+    fn fill(&mut self, decoded_input: &[u8]) -> Result<()> {
+        debug_assert!(self.is_empty());
+
+        if decoded_input.len() > Self::SIZE {
+            return Err(InvalidLength);
+        }
+
+        self.position = 0;
+        self.length = decoded_input.len();
+        self.decoded[..decoded_input.len()].copy_from_slice(decoded_input);
+        Ok(())
+    }
+
+    /// Take a specified number of bytes from the buffer.
+    ///
+    /// Returns as many bytes as possible, or an empty slice if the buffer has
+    /// already been read to completion.
+    fn take(&mut self, nbytes: usize) -> Result<&[u8]> {
+        debug_assert!(self.position <= self.length);
+        let start_pos = self.position;
+        let remaining_len = self.length.checked_sub(start_pos).ok_or(InvalidLength)?;
+        let nbytes = cmp::min(nbytes, remaining_len);
+        self.position = self.position.checked_add(nbytes).ok_or(InvalidLength)?;
+        Ok(&self.decoded[start_pos..][..nbytes])
+    }
+
+    /// Have all of the bytes in this buffer been consumed?
+    fn is_empty(&self) -> bool {
+        self.position == self.length
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use crate::{Base32, Base32Unpadded, Base32Upper, Decoder, Encoding, Error};
+
+    /// Input which is neither block-aligned nor shorter than a block.
+    const INPUT: &[u8] = b"12345678901234567890123";
+
+    #[test]
+    fn decode_padded() {
+        decode_test::<Base32>(INPUT);
+        decode_test::<Base32Upper>(INPUT);
+    }
+
+    #[test]
+    fn decode_unpadded() {
+        decode_test::<Base32Unpadded>(INPUT);
+        decode_test::<Base32Unpadded>(&INPUT[..20]);
+    }
+
+    #[test]
+    fn decode_too_much() {
+        let mut decoder = Decoder::<Base32>::new(b"gezdgnbv").unwrap();
+        let mut buf = [0u8; 6];
+        assert_eq!(decoder.decode(&mut buf), Err(Error::InvalidLength));
+        assert_eq!(decoder.decode(&mut buf[..5]).unwrap(), b"12345");
+        assert!(decoder.is_finished());
+    }
+
+    #[test]
+    fn decode_invalid() {
+        let mut decoder = Decoder::<Base32Unpadded>::new(b"gezdgnbvgy3t!").unwrap();
+        let mut buf = [0u8; 8];
+        assert_eq!(decoder.decode(&mut buf[..5]).unwrap(), b"12345");
+        assert_eq!(decoder.decode(&mut buf[..3]), Err(Error::InvalidEncoding));
+        assert_eq!(
+            Decoder::<Base32>::new(b"gezdgnbvgy").err(),
+            Some(Error::InvalidEncoding)
+        );
+    }
+
+    /// Decode the encoded input in chunks of every size, checking the result
+    /// is the original input.
+    fn decode_test<E: Encoding>(input: &[u8]) {
+        let mut encoded = [0u8; 64];
+        let encoded = E::encode(input, &mut encoded).unwrap();
+        let mut buffer = [0u8; 64];
+
+        for chunk_size in 1..input.len() {
+            let mut decoder = Decoder::<E>::new(encoded.as_bytes()).unwrap();
+            assert_eq!(decoder.remaining_len(), input.len());
+
+            for chunk in buffer[..input.len()].chunks_mut(chunk_size) {
+                decoder.decode(chunk).unwrap();
+            }
+
+            assert!(decoder.is_finished());
+            assert_eq!(&buffer[..input.len()], input);
+        }
+    }
+}
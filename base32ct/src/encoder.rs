@@ -0,0 +1,226 @@
+//! Buffered Base32 encoder.
+
+use crate::{
+    Encoding,
+    Error::{self, InvalidLength},
+    Result,
+};
+use core::{cmp, marker::PhantomData, str};
+
+#[cfg(doc)]
+use crate::{Base32, Base32Unpadded};
+
+/// Stateful Base32 encoder with support for buffered, incremental encoding.
+///
+/// The `E` type parameter can be any type which impls [`Encoding`] such as
+/// [`Base32`] or [`Base32Unpadded`].
+///
+/// Input is encoded as soon as it forms complete 5-byte blocks, so only a
+/// partial block is buffered between calls to [`Encoder::encode`].
+pub struct Encoder<'o, E: Encoding> {
+    /// Output buffer.
+    output: &'o mut [u8],
+
+    /// Cursor within the output buffer.
+    position: usize,
+
+    /// Block buffer used for non-block-aligned data.
+    block_buffer: BlockBuffer,
+
+    /// Phantom parameter for the Base32 encoding in use.
+    encoding: PhantomData<E>,
+}
+
+impl<'o, E: Encoding> Encoder<'o, E> {
+    /// Create a new encoder which writes output to the given byte slice.
+    pub fn new(output: &'o mut [u8]) -> Result<Self> {
+        if output.is_empty() {
+            return Err(InvalidLength);
+        }
+
+        Ok(Self {
+            output,
+            position: 0,
+            block_buffer: BlockBuffer::default(),
+            encoding: PhantomData,
+        })
+    }
+
+    /// Encode the provided buffer as Base32, writing it to the output buffer.
+    ///
+    /// # Returns
+    /// - `Ok(())` if the expected amount of data was read
+    /// - `Err(Error::InvalidLength)` if there is insufficient space in the output buffer
+    pub fn encode(&mut self, mut input: &[u8]) -> Result<()> {
+        // If there's data in the block buffer, fill it
+        if !self.block_buffer.is_empty() {
+            self.process_buffer(&mut input)?;
+        }
+
+        while !input.is_empty() {
+            // Attempt to encode a stride of block-aligned data
+            let in_blocks = input.len() / BlockBuffer::SIZE;
+            let out_blocks = self.remaining().len() / 8;
+            let blocks = cmp::min(in_blocks, out_blocks);
+
+            if blocks > 0 {
+                let len = blocks.checked_mul(BlockBuffer::SIZE).ok_or(InvalidLength)?;
+                let (in_aligned, in_rem) = input.split_at(len);
+                input = in_rem;
+                self.perform_encode(in_aligned)?;
+            }
+
+            // If there's remaining non-aligned data, fill the block buffer
+            if !input.is_empty() {
+                self.process_buffer(&mut input)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Get the position inside of the output buffer where the write cursor
+    /// is currently located.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Finish encoding data, returning the resulting Base32 as a `str`.
+    pub fn finish(self) -> Result<&'o str> {
+        self.finish_with_remaining().map(|(base32, _)| base32)
+    }
+
+    /// Finish encoding data, returning the resulting Base32 as a `str`
+    /// along with the remaining space in the output buffer.
+    pub fn finish_with_remaining(mut self) -> Result<(&'o str, &'o mut [u8])> {
+        if !self.block_buffer.is_empty() {
+            let buffer_len = self.block_buffer.position;
+            let block = self.block_buffer.bytes;
+            self.perform_encode(&block[..buffer_len])?;
+        }
+
+        let (base32, remaining) = self.output.split_at_mut(self.position);
+        let base32 = str::from_utf8(base32).map_err(|_| Error::InvalidEncoding)?;
+        Ok((base32, remaining))
+    }
+
+    /// Borrow the remaining data in the buffer.
+    fn remaining(&mut self) -> &mut [u8] {
+        &mut self.output[self.position..]
+    }
+
+    /// Fill the block buffer with data, consuming and encoding it when the
+    /// buffer is full.
+    fn process_buffer(&mut self, input: &mut &[u8]) -> Result<()> {
+        self.block_buffer.fill(input)?;
+
+        if self.block_buffer.is_full() {
+            let block = self.block_buffer.take();
+            self.perform_encode(&block)?;
+        }
+
+        Ok(())
+    }
+
+    /// Perform Base32 encoding operation.
+    fn perform_encode(&mut self, input: &[u8]) -> Result<usize> {
+        let len = E::encode(input, self.remaining())?.len();
+        self.position = self.position.checked_add(len).ok_or(InvalidLength)?;
+        Ok(len)
+    }
+}
+
+/// Base32 encode buffer for a 1-block output.
+///
+/// This handles a partial block of data, i.e. data which hasn't been
+/// encoded yet because it doesn't form a complete block.
+#[derive(Clone, Default, Debug)]
+struct BlockBuffer {
+    /// 5 decoded bytes to be encoded to an 8-byte Base32-encoded output.
+    bytes: [u8; Self::SIZE],
+
+    /// Position within the buffer.
+    position: usize,
+}
+
+impl BlockBuffer {
+    /// Size of the buffer in bytes: 5-bytes of unencoded input which
+    /// Base32 encode to 8-bytes of output.
+    const SIZE: usize = 5;
+
+    /// Fill the remaining space in the buffer with the input data.
+    fn fill(&mut self, input: &mut &[u8]) -> Result<()> {
+        let remaining = Self::SIZE.checked_sub(self.position).ok_or(InvalidLength)?;
+        let len = cmp::min(input.len(), remaining);
+        self.bytes[self.position..][..len].copy_from_slice(&input[..len]);
+        self.position = self.position.checked_add(len).ok_or(InvalidLength)?;
+        *input = &input[len..];
+        Ok(())
+    }
+
+    /// Take the output buffer, resetting the position to 0.
+    fn take(&mut self) -> [u8; Self::SIZE] {
+        debug_assert!(self.is_full());
+        let result = self.bytes;
+        *self = Default::default();
+        result
+    }
+
+    /// Is the buffer empty?
+    fn is_empty(&self) -> bool {
+        self.position == 0
+    }
+
+    /// Is the buffer full?
+    fn is_full(&self) -> bool {
+        self.position == Self::SIZE
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use crate::{Base32, Base32Unpadded, Base32Upper, Encoder, Encoding, Error};
+
+    /// Input which is neither block-aligned nor shorter than a block.
+    const INPUT: &[u8] = b"12345678901234567890123";
+
+    #[test]
+    fn encode_padded() {
+        encode_test::<Base32>(INPUT);
+        encode_test::<Base32Upper>(INPUT);
+    }
+
+    #[test]
+    fn encode_unpadded() {
+        encode_test::<Base32Unpadded>(INPUT);
+        encode_test::<Base32Unpadded>(&INPUT[..20]);
+    }
+
+    #[test]
+    fn insufficient_output() {
+        let mut buffer = [0u8; 8];
+        let mut encoder = Encoder::<Base32>::new(&mut buffer).unwrap();
+        encoder.encode(b"12345").unwrap();
+        encoder.encode(b"6").unwrap();
+        assert_eq!(encoder.finish(), Err(Error::InvalidLength));
+    }
+
+    /// Encode the input in chunks of every size, checking the result is the
+    /// same as when encoding it at once.
+    fn encode_test<E: Encoding>(input: &[u8]) {
+        let mut expected = [0u8; 64];
+        let expected = E::encode(input, &mut expected).unwrap();
+        let mut buffer = [0u8; 64];
+
+        for chunk_size in 1..input.len() {
+            let mut encoder = Encoder::<E>::new(&mut buffer).unwrap();
+
+            for chunk in input.chunks(chunk_size) {
+                encoder.encode(chunk).unwrap();
+            }
+
+            assert_eq!(expected, encoder.finish().unwrap());
+        }
+    }
+}
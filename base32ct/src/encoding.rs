@@ -38,92 +38,7 @@ impl<T: Alphabet> Encoding for T {
             src.as_ref()
         };
 
-        if src.is_empty() {
-            return Ok(&[]);
-        }
-
-        let dlen = decoded_len(src.len());
-        let dst = dst.get_mut(..dlen).ok_or(Error::InvalidLength)?;
-
-        let mut src_chunks = src.chunks_exact(8);
-        let mut dst_chunks = dst.chunks_exact_mut(5);
-        let mut err = 0u8;
-
-        for (s, d) in (&mut src_chunks).zip(&mut dst_chunks) {
-            let c0 = Self::decode_5bits(s[0]);
-            let c1 = Self::decode_5bits(s[1]);
-            let c2 = Self::decode_5bits(s[2]);
-            let c3 = Self::decode_5bits(s[3]);
-            let c4 = Self::decode_5bits(s[4]);
-            let c5 = Self::decode_5bits(s[5]);
-            let c6 = Self::decode_5bits(s[6]);
-            let c7 = Self::decode_5bits(s[7]);
-
-            d[0] = (((c0 << 3) | (c1 >> 2)) & 0xff) as u8;
-            d[1] = (((c1 << 6) | (c2 << 1) | (c3 >> 4)) & 0xff) as u8;
-            d[2] = (((c3 << 4) | (c4 >> 1)) & 0xff) as u8;
-            d[3] = (((c4 << 7) | (c5 << 2) | (c6 >> 3)) & 0xff) as u8;
-            d[4] = (((c6 << 5) | (c7)) & 0xff) as u8;
-
-            err |= ((c0 | c1 | c2 | c3 | c4 | c5 | c6 | c7) >> 8) as u8;
-        }
-
-        // Handle last chunk if it's non-empty
-        let src_rem = src_chunks.remainder();
-        let dst_rem = dst_chunks.into_remainder();
-        let mut c = [0i16; 7];
-
-        if !src_rem.is_empty() {
-            c[0] = Self::decode_5bits(src_rem[0]);
-        }
-
-        if src_rem.len() >= 2 {
-            c[1] = Self::decode_5bits(src_rem[1]);
-        };
-
-        if src_rem.len() >= 3 {
-            c[2] = Self::decode_5bits(src_rem[2]);
-        }
-
-        if src_rem.len() >= 4 {
-            c[3] = Self::decode_5bits(src_rem[3]);
-        }
-
-        if src_rem.len() >= 5 {
-            c[4] = Self::decode_5bits(src_rem[4]);
-        }
-
-        if src_rem.len() >= 6 {
-            c[5] = Self::decode_5bits(src_rem[5]);
-        }
-
-        if src_rem.len() >= 7 {
-            c[6] = Self::decode_5bits(src_rem[6]);
-        };
-
-        if !src_rem.is_empty() {
-            dst_rem[0] = (((c[0] << 3) | (c[1] >> 2)) & 0xff) as u8;
-        }
-
-        if src_rem.len() >= 3 {
-            dst_rem[1] = (((c[1] << 6) | (c[2] << 1) | (c[3] >> 4)) & 0xff) as u8;
-        }
-
-        if src_rem.len() >= 5 {
-            dst_rem[2] = (((c[3] << 4) | (c[4] >> 1)) & 0xff) as u8;
-        }
-
-        if src_rem.len() >= 6 {
-            dst_rem[3] = (((c[4] << 7) | (c[5] << 2) | (c[6] >> 3)) & 0xff) as u8;
-        }
-
-        err |= ((c[0] | c[1] | c[2] | c[3] | c[4] | c[5] | c[6]) >> 8) as u8;
-
-        if err == 0 {
-            Ok(dst)
-        } else {
-            Err(Error::InvalidEncoding)
-        }
+        decode_unpadded::<Self>(src, dst)
     }
 
     #[cfg(feature = "alloc")]
@@ -226,6 +141,97 @@ impl<T: Alphabet> Encoding for T {
     }
 }
 
+/// Decode Base32 whose padding has already been removed, using the given
+/// alphabet.
+pub(crate) fn decode_unpadded<'o, T: Alphabet>(src: &[u8], dst: &'o mut [u8]) -> Result<&'o [u8]> {
+    if src.is_empty() {
+        return Ok(&[]);
+    }
+
+    let dlen = decoded_len(src.len());
+    let dst = dst.get_mut(..dlen).ok_or(Error::InvalidLength)?;
+
+    let mut src_chunks = src.chunks_exact(8);
+    let mut dst_chunks = dst.chunks_exact_mut(5);
+    let mut err = 0u8;
+
+    for (s, d) in (&mut src_chunks).zip(&mut dst_chunks) {
+        let c0 = T::decode_5bits(s[0]);
+        let c1 = T::decode_5bits(s[1]);
+        let c2 = T::decode_5bits(s[2]);
+        let c3 = T::decode_5bits(s[3]);
+        let c4 = T::decode_5bits(s[4]);
+        let c5 = T::decode_5bits(s[5]);
+        let c6 = T::decode_5bits(s[6]);
+        let c7 = T::decode_5bits(s[7]);
+
+        d[0] = (((c0 << 3) | (c1 >> 2)) & 0xff) as u8;
+        d[1] = (((c1 << 6) | (c2 << 1) | (c3 >> 4)) & 0xff) as u8;
+        d[2] = (((c3 << 4) | (c4 >> 1)) & 0xff) as u8;
+        d[3] = (((c4 << 7) | (c5 << 2) | (c6 >> 3)) & 0xff) as u8;
+        d[4] = (((c6 << 5) | (c7)) & 0xff) as u8;
+
+        err |= ((c0 | c1 | c2 | c3 | c4 | c5 | c6 | c7) >> 8) as u8;
+    }
+
+    // Handle last chunk if it's non-empty
+    let src_rem = src_chunks.remainder();
+    let dst_rem = dst_chunks.into_remainder();
+    let mut c = [0i16; 7];
+
+    if !src_rem.is_empty() {
+        c[0] = T::decode_5bits(src_rem[0]);
+    }
+
+    if src_rem.len() >= 2 {
+        c[1] = T::decode_5bits(src_rem[1]);
+    };
+
+    if src_rem.len() >= 3 {
+        c[2] = T::decode_5bits(src_rem[2]);
+    }
+
+    if src_rem.len() >= 4 {
+        c[3] = T::decode_5bits(src_rem[3]);
+    }
+
+    if src_rem.len() >= 5 {
+        c[4] = T::decode_5bits(src_rem[4]);
+    }
+
+    if src_rem.len() >= 6 {
+        c[5] = T::decode_5bits(src_rem[5]);
+    }
+
+    if src_rem.len() >= 7 {
+        c[6] = T::decode_5bits(src_rem[6]);
+    };
+
+    if !src_rem.is_empty() {
+        dst_rem[0] = (((c[0] << 3) | (c[1] >> 2)) & 0xff) as u8;
+    }
+
+    if src_rem.len() >= 3 {
+        dst_rem[1] = (((c[1] << 6) | (c[2] << 1) | (c[3] >> 4)) & 0xff) as u8;
+    }
+
+    if src_rem.len() >= 5 {
+        dst_rem[2] = (((c[3] << 4) | (c[4] >> 1)) & 0xff) as u8;
+    }
+
+    if src_rem.len() >= 6 {
+        dst_rem[3] = (((c[4] << 7) | (c[5] << 2) | (c[6] >> 3)) & 0xff) as u8;
+    }
+
+    err |= ((c[0] | c[1] | c[2] | c[3] | c[4] | c[5] | c[6]) >> 8) as u8;
+
+    if err == 0 {
+        Ok(dst)
+    } else {
+        Err(Error::InvalidEncoding)
+    }
+}
+
 /// Get the length of the output from decoding the provided *unpadded*
 /// Base32-encoded input.
 ///
@@ -233,12 +239,12 @@ impl<T: Alphabet> Encoding for T {
 /// and may return incorrect results for malformed Base32.
 // TODO(tarcieri): checked/overflow-proof arithmetic
 #[inline(always)]
-fn decoded_len(input_len: usize) -> usize {
+pub(crate) fn decoded_len(input_len: usize) -> usize {
     (input_len * 5) / 8
 }
 
 /// Remove padding from the provided input.
-fn remove_padding(mut input: &[u8]) -> Result<&[u8]> {
+pub(crate) fn remove_padding(mut input: &[u8]) -> Result<&[u8]> {
     // TODO(tarcieri): properly validate padding
     if input.len() % 8 != 0 {
         return Err(Error::InvalidEncoding);
@@ -52,11 +52,15 @@
 extern crate alloc;
 
 mod alphabet;
+mod decoder;
+mod encoder;
 mod encoding;
 mod error;
 
 pub use crate::{
     alphabet::rfc4648::{Base32, Base32Unpadded, Base32Upper, Base32UpperUnpadded},
+    decoder::Decoder,
+    encoder::Encoder,
     encoding::{Encoding, encoded_len},
     error::{Error, Result},
 };
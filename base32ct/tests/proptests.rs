@@ -3,7 +3,9 @@
 #![cfg(feature = "alloc")]
 
 use base32::Alphabet;
-use base32ct::{Base32 as Base32Ct, Base32Unpadded as Base32UnpaddedCt, Encoding};
+use base32ct::{
+    Base32 as Base32Ct, Base32Unpadded as Base32UnpaddedCt, Decoder, Encoder, Encoding,
+};
 use proptest::{prelude::*, string::*};
 
 const RFC4648_PADDED: Alphabet = Alphabet::Rfc4648 { padding: true };
@@ -34,4 +36,26 @@ proptest! {
         let expected = base32::encode(RFC4648_PADDED, &bytes).to_lowercase();
         prop_assert_eq!(actual, expected);
     }
+
+    /// Ensure the buffered `Encoder` and `Decoder` of `base32ct` are
+    /// equivalent to the `base32` ref crate when processing data in chunks.
+    #[test]
+    fn chunked_equiv_unpadded(bytes in bytes_regex(".{1,256}").unwrap(), chunk_size in 1usize..16) {
+        let expected = base32::encode(RFC4648_UNPADDED, &bytes).to_lowercase();
+
+        let mut buf = vec![0u8; expected.len()];
+        let mut encoder = Encoder::<Base32UnpaddedCt>::new(&mut buf).unwrap();
+        for chunk in bytes.chunks(chunk_size) {
+            encoder.encode(chunk).unwrap();
+        }
+        prop_assert_eq!(encoder.finish().unwrap(), &expected);
+
+        let mut decoder = Decoder::<Base32UnpaddedCt>::new(expected.as_bytes()).unwrap();
+        let mut decoded = vec![0u8; bytes.len()];
+        for chunk in decoded.chunks_mut(chunk_size) {
+            decoder.decode(chunk).unwrap();
+        }
+        prop_assert!(decoder.is_finished());
+        prop_assert_eq!(decoded, bytes);
+    }
 }
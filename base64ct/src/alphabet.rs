@@ -12,6 +12,74 @@ pub mod standard;
 pub mod url;
 
 /// Core encoder/decoder functions for a particular Base64 alphabet.
+///
+/// Every type implementing this trait gets an [`Encoding`][crate::Encoding]
+/// impl, so downstream crates can define custom alphabets which use the same
+/// constant-time core as the built-in ones.
+///
+/// Alphabets are described by the arithmetic steps mapping 6-bit values to
+/// characters and back, rather than by lookup tables:
+///
+/// - encoding starts from `value + BASE`, and each [`EncodeStep`] adds its
+///   offset to it if its condition holds, so the offsets move each contiguous
+///   range of values to the characters encoding it
+/// - decoding starts from `-1`, and each [`DecodeStep`] adds its offset (plus
+///   the character itself for [`DecodeStep::Range`]) if the character matches
+///   it, so exactly one step must match each valid character, and the result
+///   stays negative for invalid ones
+///
+/// For example, the alphabet `[0-9]`, `[A-Z]`, `[a-z]`, `+`, `/` with `.` as
+/// padding:
+///
+/// ```
+/// use base64ct::{Alphabet, DecodeStep, EncodeStep, Encoding};
+///
+/// #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+/// struct Base64Custom;
+///
+/// impl Alphabet for Base64Custom {
+///     const BASE: u8 = b'0';
+///     const DECODER: &'static [DecodeStep] = DECODER;
+///     const ENCODER: &'static [EncodeStep] = ENCODER;
+///     const PADDED: bool = true;
+///     const PAD: u8 = b'.';
+///     type Unpadded = Base64CustomUnpadded;
+/// }
+///
+/// #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+/// struct Base64CustomUnpadded;
+///
+/// impl Alphabet for Base64CustomUnpadded {
+///     const BASE: u8 = b'0';
+///     const DECODER: &'static [DecodeStep] = DECODER;
+///     const ENCODER: &'static [EncodeStep] = ENCODER;
+///     const PADDED: bool = false;
+///     type Unpadded = Self;
+/// }
+///
+/// const DECODER: &[DecodeStep] = &[
+///     DecodeStep::Range(b'0'..=b'9', -47),
+///     DecodeStep::Range(b'A'..=b'Z', -54),
+///     DecodeStep::Range(b'a'..=b'z', -60),
+///     DecodeStep::Eq(b'+', 63),
+///     DecodeStep::Eq(b'/', 64),
+/// ];
+///
+/// const ENCODER: &[EncodeStep] = &[
+///     EncodeStep::Apply(b'9', 7),
+///     EncodeStep::Apply(b'Z', 6),
+///     EncodeStep::Apply(b'z', b'+' as i16 - (b'z' as i16 + 1)),
+///     EncodeStep::Diff(62, b'/' as i16 - b'+' as i16 - 1),
+/// ];
+///
+/// let mut buf = [0u8; 8];
+/// assert_eq!(Base64Custom::encode(&[0x00, 0x10, 0x83, 0xFF], &mut buf)?, "0123/m..");
+/// assert_eq!(Base64CustomUnpadded::encode(&[0x00, 0x10, 0x83, 0xFF], &mut buf)?, "0123/m");
+/// # Ok::<(), base64ct::Error>(())
+/// ```
+///
+/// Custom alphabets should be checked against a reference encoding of every
+/// 6-bit value, e.g. using a test vector covering the whole alphabet.
 pub trait Alphabet: 'static + Copy + Debug + Eq + Send + Sized + Sync {
     /// First character in this Base64 alphabet.
     const BASE: u8;
@@ -25,6 +93,11 @@ pub trait Alphabet: 'static + Copy + Debug + Eq + Send + Sized + Sync {
     /// Is this encoding padded?
     const PADDED: bool;
 
+    /// Padding character of this encoding, if it's padded.
+    ///
+    /// Must not be a character of the alphabet.
+    const PAD: u8 = b'=';
+
     /// Unpadded equivalent of this alphabet.
     ///
     /// For alphabets that are unpadded to begin with, this should be `Self`.
@@ -103,22 +176,26 @@ pub trait Alphabet: 'static + Copy + Debug + Eq + Send + Sized + Sync {
     }
 }
 
-/// Constant-time decoder step.
+/// Constant-time decoder step of an [`Alphabet`].
 #[derive(Debug)]
 pub enum DecodeStep {
-    /// Match the given range, offsetting the input on match.
+    /// Match the given range, offsetting the input on match, i.e. adding
+    /// `input + offset` to the result.
     Range(RangeInclusive<u8>, i16),
 
-    /// Match the given value, returning the associated offset on match.
+    /// Match the given value, returning the associated offset on match, i.e.
+    /// adding `offset` to the result.
     Eq(u8, i16),
 }
 
-/// Constant-time encoder step.
+/// Constant-time encoder step of an [`Alphabet`].
 #[derive(Copy, Clone, Debug)]
 pub enum EncodeStep {
-    /// Apply the given offset to the cumulative result on match.
+    /// Apply the given offset to the cumulative result on match, i.e. if the
+    /// result so far is greater than the given character.
     Apply(u8, i16),
 
-    /// Compute a difference using the given offset on match.
+    /// Compute a difference using the given offset on match, i.e. if the
+    /// 6-bit value being encoded is greater than the given value.
     Diff(u8, i16),
 }
@@ -12,9 +12,6 @@ use alloc::{string::String, vec::Vec};
 #[cfg(doc)]
 use crate::{Base64, Base64Bcrypt, Base64Crypt, Base64Unpadded, Base64Url, Base64UrlUnpadded};
 
-/// Padding character
-const PAD: u8 = b'=';
-
 /// Base64 encoding trait.
 ///
 /// This trait must be imported to make use of any Base64 alphabet defined
@@ -34,8 +31,10 @@ pub trait Encoding: Alphabet {
 
     /// Decode a Base64 string in-place.
     ///
-    /// NOTE: this method does not (yet) validate that padding is well-formed,
-    /// if the given Base64 encoding is padded.
+    /// The decoded data overwrites the start of `buf`, which avoids the need
+    /// for a separate output buffer, e.g. on memory-constrained targets.
+    /// Input is validated exactly like with [`Encoding::decode`], including
+    /// the padding and trailing bits of the last block.
     fn decode_in_place(buf: &mut [u8]) -> Result<&[u8], InvalidEncodingError>;
 
     /// Decode a Base64 string into a byte vector.
@@ -64,7 +63,7 @@ pub trait Encoding: Alphabet {
 impl<T: Alphabet> Encoding for T {
     fn decode(src: impl AsRef<[u8]>, dst: &mut [u8]) -> Result<&[u8], Error> {
         let (src_unpadded, mut err) = if T::PADDED {
-            let (unpadded_len, e) = decode_padding(src.as_ref())?;
+            let (unpadded_len, e) = decode_padding::<T>(src.as_ref())?;
             (&src.as_ref()[..unpadded_len], e)
         } else {
             (src.as_ref(), 0)
@@ -78,8 +77,11 @@ impl<T: Alphabet> Encoding for T {
 
         let dst = &mut dst[..dlen];
 
-        let mut src_chunks = src_unpadded.chunks_exact(4);
-        let mut dst_chunks = dst.chunks_exact_mut(3);
+        let (src_rest, dst_rest, e) = decode_blocks::<T>(src_unpadded, dst);
+        err |= e;
+
+        let mut src_chunks = src_rest.chunks_exact(4);
+        let mut dst_chunks = dst_rest.chunks_exact_mut(3);
         for (s, d) in (&mut src_chunks).zip(&mut dst_chunks) {
             err |= Self::decode_3bytes(s, d);
         }
@@ -104,10 +106,17 @@ impl<T: Alphabet> Encoding for T {
     // TODO(tarcieri): explicitly checked/wrapped arithmetic
     #[allow(clippy::arithmetic_side_effects)]
     fn decode_in_place(mut buf: &mut [u8]) -> Result<&[u8], InvalidEncodingError> {
+        // The last block of the input may be overwritten while decoding, so
+        // keep a copy of it (including any padding) for the final validation
+        let last_block_start = (buf.len().saturating_sub(1) / 4) * 4;
+        let last_block_len = buf.len() - last_block_start;
+        let mut last_block = [0u8; 4];
+        last_block[..last_block_len].copy_from_slice(&buf[last_block_start..]);
+
         // TODO: eliminate unsafe code when LLVM12 is stable
         // See: https://github.com/rust-lang/rust/issues/80963
         let mut err = if T::PADDED {
-            let (unpadded_len, e) = decode_padding(buf)?;
+            let (unpadded_len, e) = decode_padding::<T>(buf)?;
             buf = &mut buf[..unpadded_len];
             e
         } else {
@@ -149,21 +158,24 @@ impl<T: Alphabet> Encoding for T {
 
         if err == 0 {
             // SAFETY: `dst_rem_len` is always smaller than 4, so we don't
-            // read outside of `tmp_out`, write and the final slicing never go
-            // outside of `buf`.
+            // read outside of `tmp_out`, and the write never goes outside of
+            // `buf`.
             #[allow(unsafe_code)]
             unsafe {
                 debug_assert!(dst_rem_pos + dst_rem_len <= buf.len());
                 debug_assert!(dst_rem_len <= tmp_out.len());
-                debug_assert!(dlen <= buf.len());
 
                 core::ptr::copy_nonoverlapping(
                     tmp_out.as_ptr(),
                     buf.as_mut_ptr().add(dst_rem_pos),
                     dst_rem_len,
                 );
-                Ok(buf.get_unchecked(..dlen))
             }
+
+            let decoded = &buf[..dlen];
+            validate_last_block::<T>(&last_block[..last_block_len], decoded)
+                .map_err(|_| InvalidEncodingError)?;
+            Ok(decoded)
         } else {
             Err(InvalidEncodingError)
         }
@@ -194,8 +206,9 @@ impl<T: Alphabet> Encoding for T {
 
         let dst = &mut dst[..elen];
 
-        let mut src_chunks = src.chunks_exact(3);
-        let mut dst_chunks = dst.chunks_exact_mut(4);
+        let (src_rest, dst_rest) = encode_blocks::<T>(src, dst);
+        let mut src_chunks = src_rest.chunks_exact(3);
+        let mut dst_chunks = dst_rest.chunks_exact_mut(4);
 
         for (s, d) in (&mut src_chunks).zip(&mut dst_chunks) {
             Self::encode_3bytes(s, d);
@@ -211,8 +224,8 @@ impl<T: Alphabet> Encoding for T {
 
                 let flag = src_rem.len() == 1;
                 let mask = (flag as u8).wrapping_sub(1);
-                dst_rem[2] = (dst_rem[2] & mask) | (PAD & !mask);
-                dst_rem[3] = PAD;
+                dst_rem[2] = (dst_rem[2] & mask) | (T::PAD & !mask);
+                dst_rem[3] = T::PAD;
             }
         } else {
             let dst_rem = dst_chunks.into_remainder();
@@ -252,6 +265,77 @@ impl<T: Alphabet> Encoding for T {
     }
 }
 
+/// Number of bytes encoded as a block of [`ENCODED_BLOCK_SIZE`] characters.
+///
+/// The bulk of the input of [`Encoding::encode`] and [`Encoding::decode`] is
+/// processed in blocks, whose fixed size lets the compiler unroll the loops
+/// and eliminate the per-chunk bounds checks of processing it 3 bytes at a
+/// time, which are costly e.g. on `wasm32` targets. Alphabets overriding
+/// [`Alphabet::encode_3bytes`] and [`Alphabet::decode_3bytes`] are still
+/// supported, since blocks are processed in terms of them.
+const DECODED_BLOCK_SIZE: usize = 48;
+
+/// Number of characters in a block of Base64 (i.e. a 64-column PEM line).
+const ENCODED_BLOCK_SIZE: usize = 64;
+
+/// Encode the whole blocks of [`DECODED_BLOCK_SIZE`] bytes at the start of
+/// `src` into `dst`, returning the remaining input and output.
+///
+/// `dst` must be large enough to hold the encoded blocks.
+// TODO(tarcieri): explicitly checked/wrapped arithmetic
+#[allow(clippy::arithmetic_side_effects)]
+#[inline(always)]
+fn encode_blocks<'s, 'd, T: Alphabet>(
+    src: &'s [u8],
+    dst: &'d mut [u8],
+) -> (&'s [u8], &'d mut [u8]) {
+    let blocks = src.len() / DECODED_BLOCK_SIZE;
+    let (src_blocks, src_rest) = src.split_at(blocks * DECODED_BLOCK_SIZE);
+    let (dst_blocks, dst_rest) = dst.split_at_mut(blocks * ENCODED_BLOCK_SIZE);
+
+    for (s, d) in src_blocks
+        .chunks_exact(DECODED_BLOCK_SIZE)
+        .zip(dst_blocks.chunks_exact_mut(ENCODED_BLOCK_SIZE))
+    {
+        // Fixed trip count, fully unrolled by the compiler
+        for (s, d) in s.chunks_exact(3).zip(d.chunks_exact_mut(4)) {
+            T::encode_3bytes(s, d);
+        }
+    }
+
+    (src_rest, dst_rest)
+}
+
+/// Decode the whole blocks of [`ENCODED_BLOCK_SIZE`] characters at the start
+/// of `src` into `dst`, returning the remaining input and output, along with
+/// an error flag to be combined with other encoding-related errors.
+///
+/// `dst` must be large enough to hold the decoded blocks.
+// TODO(tarcieri): explicitly checked/wrapped arithmetic
+#[allow(clippy::arithmetic_side_effects)]
+#[inline(always)]
+fn decode_blocks<'s, 'd, T: Alphabet>(
+    src: &'s [u8],
+    dst: &'d mut [u8],
+) -> (&'s [u8], &'d mut [u8], i16) {
+    let blocks = src.len() / ENCODED_BLOCK_SIZE;
+    let (src_blocks, src_rest) = src.split_at(blocks * ENCODED_BLOCK_SIZE);
+    let (dst_blocks, dst_rest) = dst.split_at_mut(blocks * DECODED_BLOCK_SIZE);
+    let mut err = 0;
+
+    for (s, d) in src_blocks
+        .chunks_exact(ENCODED_BLOCK_SIZE)
+        .zip(dst_blocks.chunks_exact_mut(DECODED_BLOCK_SIZE))
+    {
+        // Fixed trip count, fully unrolled by the compiler
+        for (s, d) in s.chunks_exact(4).zip(d.chunks_exact_mut(3)) {
+            err |= T::decode_3bytes(s, d);
+        }
+    }
+
+    (src_rest, dst_rest, err)
+}
+
 /// Validate padding is of the expected length compute unpadded length.
 ///
 /// Note that this method does not explicitly check that the padded data
@@ -262,14 +346,16 @@ impl<T: Alphabet> Encoding for T {
 /// errors (i.e. malformed padding bytes) as `i16` to be combined with other
 /// encoding-related errors prior to branching.
 #[inline(always)]
-pub(crate) fn decode_padding(input: &[u8]) -> Result<(usize, i16), InvalidEncodingError> {
+pub(crate) fn decode_padding<T: Alphabet>(
+    input: &[u8],
+) -> Result<(usize, i16), InvalidEncodingError> {
     if input.len() % 4 != 0 {
         return Err(InvalidEncodingError);
     }
 
     let unpadded_len = match *input {
-        [.., b0, b1] => is_pad_ct(b0)
-            .checked_add(is_pad_ct(b1))
+        [.., b0, b1] => is_pad_ct(T::PAD, b0)
+            .checked_add(is_pad_ct(T::PAD, b1))
             .and_then(|len| len.try_into().ok())
             .and_then(|len| input.len().checked_sub(len))
             .ok_or(InvalidEncodingError)?,
@@ -282,8 +368,8 @@ pub(crate) fn decode_padding(input: &[u8]) -> Result<(usize, i16), InvalidEncodi
         .ok_or(InvalidEncodingError)?;
 
     let err = match *input {
-        [.., b0] if padding_len == 1 => is_pad_ct(b0) ^ 1,
-        [.., b0, b1] if padding_len == 2 => (is_pad_ct(b0) & is_pad_ct(b1)) ^ 1,
+        [.., b0] if padding_len == 1 => is_pad_ct(T::PAD, b0) ^ 1,
+        [.., b0, b1] if padding_len == 2 => (is_pad_ct(T::PAD, b0) & is_pad_ct(T::PAD, b1)) ^ 1,
         _ => {
             if padding_len == 0 {
                 0
@@ -351,12 +437,12 @@ pub(crate) fn decoded_len(input_len: usize) -> usize {
     3 * k + (3 * l) / 4
 }
 
-/// Branchless match that a given byte is the `PAD` character
+/// Branchless match that a given byte is the `pad` character
 // TODO(tarcieri): explicitly checked/wrapped arithmetic
 #[allow(clippy::arithmetic_side_effects)]
 #[inline(always)]
-fn is_pad_ct(input: u8) -> i16 {
-    ((((PAD as i16 - 1) - input as i16) & (input as i16 - (PAD as i16 + 1))) >> 8) & 1
+fn is_pad_ct(pad: u8, input: u8) -> i16 {
+    ((((pad as i16 - 1) - input as i16) & (input as i16 - (pad as i16 + 1))) >> 8) & 1
 }
 
 // TODO(tarcieri): explicitly checked/wrapped arithmetic
@@ -88,6 +88,7 @@ mod test_vectors;
 
 pub use crate::{
     alphabet::{
+        Alphabet, DecodeStep, EncodeStep,
         bcrypt::Base64Bcrypt,
         crypt::Base64Crypt,
         shacrypt::Base64ShaCrypt,
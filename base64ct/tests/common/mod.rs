@@ -76,5 +76,34 @@ macro_rules! impl_tests {
                 }
             }
         }
+
+        #[test]
+        fn encode_and_decode_multiple_blocks() {
+            let mut data = [0u8; 200];
+            for (i, byte) in data.iter_mut().enumerate() {
+                *byte = (i * 37) as u8;
+            }
+
+            let mut inbuf = [0u8; 1024];
+            let mut outbuf = [0u8; 1024];
+
+            for i in 0..data.len() {
+                let encoded = <$encoding>::encode(&data[..i], &mut inbuf).unwrap();
+
+                // Encoding in blocks must match encoding the input piecewise
+                let split = i / 2 / 3 * 3;
+                let mut piecewise = [0u8; 1024];
+                let head_len = <$encoding>::encode(&data[..split], &mut piecewise)
+                    .unwrap()
+                    .len();
+                let tail_len = <$encoding>::encode(&data[split..i], &mut piecewise[head_len..])
+                    .unwrap()
+                    .len();
+                assert_eq!(encoded.as_bytes(), &piecewise[..head_len + tail_len]);
+
+                let decoded = <$encoding>::decode(encoded, &mut outbuf).unwrap();
+                assert_eq!(decoded, &data[..i]);
+            }
+        }
     };
 }
@@ -0,0 +1,106 @@
+//! Custom alphabet tests
+
+#[macro_use]
+mod common;
+
+use crate::common::*;
+use base64ct::{Alphabet, DecodeStep, EncodeStep};
+
+/// Custom alphabet with `.` padding.
+///
+/// ```text
+/// [0-9]      [A-Z]      [a-z]      +     /
+/// 0x30-0x39, 0x41-0x5a, 0x61-0x7a, 0x2b, 0x2f
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct Base64Custom;
+
+impl Alphabet for Base64Custom {
+    const BASE: u8 = b'0';
+    const DECODER: &'static [DecodeStep] = DECODER;
+    const ENCODER: &'static [EncodeStep] = ENCODER;
+    const PADDED: bool = true;
+    const PAD: u8 = b'.';
+    type Unpadded = Base64CustomUnpadded;
+}
+
+/// Custom alphabet *without* padding.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct Base64CustomUnpadded;
+
+impl Alphabet for Base64CustomUnpadded {
+    const BASE: u8 = b'0';
+    const DECODER: &'static [DecodeStep] = DECODER;
+    const ENCODER: &'static [EncodeStep] = ENCODER;
+    const PADDED: bool = false;
+    type Unpadded = Self;
+}
+
+const DECODER: &[DecodeStep] = &[
+    DecodeStep::Range(b'0'..=b'9', -47),
+    DecodeStep::Range(b'A'..=b'Z', -54),
+    DecodeStep::Range(b'a'..=b'z', -60),
+    DecodeStep::Eq(b'+', 63),
+    DecodeStep::Eq(b'/', 64),
+];
+
+const ENCODER: &[EncodeStep] = &[
+    EncodeStep::Apply(b'9', 7),
+    EncodeStep::Apply(b'Z', 6),
+    EncodeStep::Apply(b'z', b'+' as i16 - (b'z' as i16 + 1)),
+    EncodeStep::Diff(62, b'/' as i16 - b'+' as i16 - 1),
+];
+
+const TEST_VECTORS: &[TestVector] = &[
+    TestVector { raw: b"", b64: "" },
+    TestVector {
+        raw: b"\0",
+        b64: "00..",
+    },
+    TestVector {
+        raw: b"***",
+        b64: "AYeg",
+    },
+    TestVector {
+        raw: b"\x01\x02\x03\x04",
+        b64: "0G8310..",
+    },
+    TestVector {
+        raw: b"\xAD\xAD\xAD\xAD\xAD",
+        b64: "hQsjhQq.",
+    },
+    TestVector {
+        raw: b"\xFF\xEF\xFE\xFF\xEF\xFE",
+        b64: "/+/+/+/+",
+    },
+    TestVector {
+        raw: b"\x00\x10\x83\x10\x51\x87\x20\x92\x8B\x30\xD3\x8F\x41\x14\x93\x51\
+               \x55\x97\x61\x96\x9B\x71\xD7\x9F\x82\x18\xA3\x92\x59\xA7\xA2\x9A\
+               \xAB\xB2\xDB\xAF\xC3\x1C\xB3\xD3\x5D\xB7\xE3\x9E\xBB\xF3\xDF\xBF",
+        b64: "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz+/",
+    },
+];
+
+impl_tests!(Base64Custom);
+
+#[test]
+fn reject_standard_padding() {
+    let mut buf = [0u8; 16];
+    assert_eq!(
+        Base64Custom::decode("00==", &mut buf),
+        Err(Error::InvalidEncoding)
+    );
+}
+
+#[test]
+fn unpadded() {
+    let mut buf = [0u8; 16];
+    assert_eq!(
+        Base64CustomUnpadded::encode(b"\x01\x02\x03\x04", &mut buf).unwrap(),
+        "0G8310"
+    );
+    assert_eq!(
+        Base64CustomUnpadded::decode("0G8310", &mut buf).unwrap(),
+        b"\x01\x02\x03\x04"
+    );
+}
@@ -61,6 +61,9 @@ mod padded {
         let input = "AA/=";
         let mut buf = [0u8; 1024];
         assert_eq!(Base64::decode(input, &mut buf), Err(Error::InvalidEncoding));
+
+        buf[..input.len()].copy_from_slice(input.as_bytes());
+        assert!(Base64::decode_in_place(&mut buf[..input.len()]).is_err());
     }
 }
 
@@ -138,5 +141,8 @@ mod unpadded {
             Base64Unpadded::decode(input, &mut buf),
             Err(Error::InvalidEncoding)
         );
+
+        buf[..input.len()].copy_from_slice(input.as_bytes());
+        assert!(Base64Unpadded::decode_in_place(&mut buf[..input.len()]).is_err());
     }
 }
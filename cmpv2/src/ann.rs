@@ -1,9 +1,11 @@
 //! Announcement-related types
 
 use alloc::boxed::Box;
+use alloc::vec;
 use alloc::vec::Vec;
-use der::Sequence;
+use core::fmt;
 use der::asn1::GeneralizedTime;
+use der::{Encode, Sequence};
 
 use crmf::controls::CertId;
 use x509_cert::{crl::CertificateList, ext::Extensions};
@@ -70,3 +72,130 @@ pub struct RevAnnContent {
 ///
 /// [RFC 4210 Section 5.3.16]: https://www.rfc-editor.org/rfc/rfc4210#section-5.3.16
 pub type CrlAnnContent = Vec<CertificateList>;
+
+/// Announcement carried by a [`PkiBody`](crate::body::PkiBody), as returned by
+/// [`PkiBody::announcement`](crate::body::PkiBody::announcement).
+///
+/// Announcements are sent by a CA without a preceding request, e.g. to the
+/// subscribers of a repository, and aren't responded to.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Announcement<'a> {
+    /// CA key update announcement (`ckuann`).
+    CaKeyUpdate(&'a CaKeyUpdAnnContent),
+
+    /// Certificate announcement (`cann`).
+    Cert(&'a CertAnnContent),
+
+    /// Revocation announcement (`rann`).
+    Revocation(&'a RevAnnContent),
+
+    /// CRL announcement (`crlann`).
+    Crl(&'a CrlAnnContent),
+}
+
+impl<'a> Announcement<'a> {
+    /// Get the announced certificates, i.e. the `oldWithNew`, `newWithOld` and
+    /// `newWithNew` certificates of a `ckuann`, or the certificate of a `cann`.
+    ///
+    /// Returns an empty list for the other announcements.
+    pub fn certificates(self) -> Vec<&'a CmpCertificate> {
+        match self {
+            Self::CaKeyUpdate(content) => vec![
+                &*content.old_with_new,
+                &*content.new_with_old,
+                &*content.new_with_new,
+            ],
+            Self::Cert(cert) => vec![cert],
+            Self::Revocation(_) | Self::Crl(_) => Vec::new(),
+        }
+    }
+
+    /// Get the DER encodings of the announced certificates, see
+    /// [`Announcement::certificates`].
+    pub fn certificates_der(self) -> der::Result<Vec<Vec<u8>>> {
+        self.certificates()
+            .into_iter()
+            .map(Encode::to_der)
+            .collect()
+    }
+
+    /// Get the announced CRLs of a `crlann`.
+    ///
+    /// Returns an empty list for the other announcements.
+    pub fn crls(self) -> &'a [CertificateList] {
+        match self {
+            Self::Crl(crls) => crls,
+            _ => &[],
+        }
+    }
+
+    /// Get the DER encodings of the announced CRLs, see [`Announcement::crls`].
+    pub fn crls_der(self) -> der::Result<Vec<Vec<u8>>> {
+        self.crls().iter().map(Encode::to_der).collect()
+    }
+
+    /// Check the consistency of the announcement:
+    /// - the certificates of a `ckuann` must be self-issued, as specified in
+    ///   [RFC 4210 Section 4.4.1],
+    /// - the status of a `rann` must be `revocationWarning` or
+    ///   `revocationNotification`,
+    /// - a `crlann` must announce at least one CRL.
+    ///
+    /// The signatures of the certificates and CRLs aren't verified.
+    ///
+    /// [RFC 4210 Section 4.4.1]: https://www.rfc-editor.org/rfc/rfc4210#section-4.4.1
+    pub fn validate(self) -> Result<(), AnnError> {
+        match self {
+            Self::CaKeyUpdate(_) => {
+                let self_issued = self.certificates().iter().all(|cert| {
+                    cert.tbs_certificate().issuer() == cert.tbs_certificate().subject()
+                });
+
+                if !self_issued {
+                    return Err(AnnError::NotSelfIssued);
+                }
+            }
+            Self::Cert(_) => {}
+            Self::Revocation(content) => match content.status {
+                PkiStatus::RevocationWarning | PkiStatus::RevocationNotification => {}
+                status => return Err(AnnError::RevocationStatus(status)),
+            },
+            Self::Crl(crls) => {
+                if crls.is_empty() {
+                    return Err(AnnError::NoCrl);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Announcement validation error, see [`Announcement::validate`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum AnnError {
+    /// A certificate of a CA key update announcement isn't self-issued.
+    NotSelfIssued,
+
+    /// A revocation announcement has a status other than `revocationWarning`
+    /// or `revocationNotification`.
+    RevocationStatus(PkiStatus),
+
+    /// A CRL announcement doesn't contain any CRL.
+    NoCrl,
+}
+
+impl fmt::Display for AnnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotSelfIssued => f.write_str("CA key update certificate isn't self-issued"),
+            Self::RevocationStatus(status) => {
+                write!(f, "unexpected revocation announcement status: {status:?}")
+            }
+            Self::NoCrl => f.write_str("empty CRL announcement"),
+        }
+    }
+}
+
+impl core::error::Error for AnnError {}
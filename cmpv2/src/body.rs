@@ -1,15 +1,20 @@
 //! PKIBody type
 
-use der::Choice;
-use der::asn1::Null;
+use core::ops::{Deref, DerefMut};
+
+use der::asn1::{AnyRef, Null};
+use der::{
+    Choice, Decode, DecodeValue, EncodeValue, ErrorKind, FixedTag, Header, Length, Reader,
+    SliceReader, Tag, TagNumber, Tagged, Writer,
+};
 
 use crmf::request::CertReqMessages;
 use x509_cert::request::CertReq;
 
-use crate::ann::{CaKeyUpdAnnContent, CertAnnContent, CrlAnnContent, RevAnnContent};
+use crate::ann::{Announcement, CaKeyUpdAnnContent, CertAnnContent, CrlAnnContent, RevAnnContent};
 use crate::certified_key_pair::KeyRecRepContent;
 use crate::gp::{GenMsgContent, GenRepContent};
-use crate::message::PkiMessages;
+use crate::message::{PkiMessage, PkiMessages};
 use crate::poll::{PollRepContent, PollReqContent};
 use crate::pop::{PopoDecKeyChallContent, PopoDecKeyRespContent};
 use crate::response::CertRepMessage;
@@ -94,10 +99,8 @@ pub enum PkiBody<'a> {
     CrlAnn(CrlAnnContent),
     #[asn1(context_specific = "19", tag_mode = "EXPLICIT", constructed = "true")]
     PkiConf(PkiConfirmContent),
-
-    // TODO address recursion error
-    // #[asn1(context_specific = "20", tag_mode = "EXPLICIT", constructed = "true")]
-    // Nested(NestedMessageContent<'a>),
+    #[asn1(context_specific = "20", tag_mode = "EXPLICIT", constructed = "true")]
+    Nested(NestedMessageContent<'a>),
     #[asn1(context_specific = "21", tag_mode = "EXPLICIT", constructed = "true")]
     GenM(GenMsgContent),
     #[asn1(context_specific = "22", tag_mode = "EXPLICIT", constructed = "true")]
@@ -112,6 +115,33 @@ pub enum PkiBody<'a> {
     PollRep(PollRepContent<'a>),
 }
 
+impl<'a> PkiBody<'a> {
+    /// Get the inner messages of a `nested` body, e.g. a batch of requests
+    /// wrapped by an RA.
+    ///
+    /// Returns `None` for any other kind of body.
+    pub fn nested_messages(&self) -> Option<&[PkiMessage<'a>]> {
+        match self {
+            Self::Nested(messages) => Some(messages),
+            _ => None,
+        }
+    }
+
+    /// Get the announcement carried by a `ckuann`, `cann`, `rann` or `crlann`
+    /// body.
+    ///
+    /// Returns `None` for any other kind of body.
+    pub fn announcement(&self) -> Option<Announcement<'_>> {
+        match self {
+            Self::Ckuann(content) => Some(Announcement::CaKeyUpdate(content)),
+            Self::Cann(cert) => Some(Announcement::Cert(cert)),
+            Self::Rann(content) => Some(Announcement::Revocation(content)),
+            Self::CrlAnn(crls) => Some(Announcement::Crl(crls)),
+            _ => None,
+        }
+    }
+}
+
 /// The `PKIConfirmContent` type is defined in [RFC 4210 Section 5.3.17]
 ///
 /// ```text
@@ -121,11 +151,104 @@ pub enum PkiBody<'a> {
 /// [RFC 4210 Section 5.3.17]: https://www.rfc-editor.org/rfc/rfc4210#section-5.1.3.4
 pub type PkiConfirmContent = Null;
 
-/// The `PKIConfirmContent` type is defined in [RFC 4210 Section 5.3.17]
+/// The `NestedMessageContent` type is defined in [RFC 4210 Section 5.1.3.4]
 ///
 /// ```text
 ///  NestedMessageContent ::= PKIMessages
 /// ```
 ///
 /// [RFC 4210 Section 5.1.3.4]: https://www.rfc-editor.org/rfc/rfc4210#section-5.1.3.4
-pub type NestedMessageContent<'a> = PkiMessages<'a>;
+///
+/// Messages are rejected with [`ErrorKind::Overflow`] when decoding if their
+/// `nested` bodies are nested more than 8 levels deep, as decoding them would
+/// otherwise recurse as deep as the input allows.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct NestedMessageContent<'a>(pub PkiMessages<'a>);
+
+/// Maximum nesting depth of `nested` bodies.
+const MAX_NESTING_DEPTH: usize = 8;
+
+/// Tag of the `nested` alternative of [`PkiBody`].
+const NESTED_TAG: Tag = Tag::ContextSpecific {
+    constructed: true,
+    number: TagNumber(20),
+};
+
+impl<'a> DecodeValue<'a> for NestedMessageContent<'a> {
+    type Error = der::Error;
+
+    fn decode_value<R: Reader<'a>>(reader: &mut R, header: Header) -> der::Result<Self> {
+        let bytes = reader.read_slice(header.length())?;
+
+        if let Err(err) = check_nesting_depth(bytes, 0) {
+            return Err(reader.error(err.kind()));
+        }
+
+        let mut inner = SliceReader::new_with_encoding_rules(bytes, reader.encoding_rules())?;
+        let messages = PkiMessages::decode_value(&mut inner, header)?;
+        inner.finish()?;
+        Ok(Self(messages))
+    }
+}
+
+impl EncodeValue for NestedMessageContent<'_> {
+    fn value_len(&self) -> der::Result<Length> {
+        self.0.value_len()
+    }
+
+    fn encode_value(&self, writer: &mut impl Writer) -> der::Result<()> {
+        self.0.encode_value(writer)
+    }
+}
+
+impl FixedTag for NestedMessageContent<'_> {
+    const TAG: Tag = Tag::Sequence;
+}
+
+impl<'a> Deref for NestedMessageContent<'a> {
+    type Target = PkiMessages<'a>;
+
+    fn deref(&self) -> &PkiMessages<'a> {
+        &self.0
+    }
+}
+
+impl DerefMut for NestedMessageContent<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<'a> From<PkiMessages<'a>> for NestedMessageContent<'a> {
+    fn from(messages: PkiMessages<'a>) -> Self {
+        Self(messages)
+    }
+}
+
+/// Check that the `nested` bodies of the given DER-encoded `PKIMessages`
+/// aren't nested more than [`MAX_NESTING_DEPTH`] levels deep, without
+/// decoding the messages themselves.
+///
+/// Each level is checked before descending into it, so the recursion is
+/// bounded by [`MAX_NESTING_DEPTH`].
+fn check_nesting_depth(messages: &[u8], depth: usize) -> der::Result<()> {
+    if depth >= MAX_NESTING_DEPTH {
+        return Err(ErrorKind::Overflow.into());
+    }
+
+    let mut reader = SliceReader::new(messages)?;
+
+    while !reader.is_finished() {
+        let message = AnyRef::decode(&mut reader)?;
+        let mut fields = SliceReader::new(message.value())?;
+        let _header = AnyRef::decode(&mut fields)?;
+        let body = AnyRef::decode(&mut fields)?;
+
+        if body.tag() == NESTED_TAG {
+            let nested = AnyRef::from_der(body.value())?;
+            check_nesting_depth(nested.value(), depth + 1)?;
+        }
+    }
+
+    Ok(())
+}
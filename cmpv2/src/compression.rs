@@ -0,0 +1,249 @@
+//! Compression of PKIMessages for constrained transports.
+//!
+//! Messages with large bodies, e.g. `caPubs` carrying many certificates, can
+//! be too large for constrained transports such as CoAP. Parties supporting
+//! compression advertise the algorithms they can decompress in an
+//! `InfoTypeAndValue` of the `generalInfo` of their message headers, with a
+//! `SEQUENCE OF AlgorithmIdentifier` as value. No info type is registered for
+//! this purpose, so the OID identifying it must be agreed upon by the
+//! parties.
+//!
+//! Once a peer has advertised support for an algorithm, messages sent to it
+//! may be compressed using the CMS compressed-data convention of [RFC 3274]:
+//! the transport payload is then a `ContentInfo` containing a
+//! `CompressedData`, whose encapsulated content is the compressed DER
+//! encoding of the `PKIMessage`. The content encoding is implicit: receivers
+//! tell both kinds of payloads apart by their structure, so uncompressed
+//! messages are always accepted, see [`decode_payload`].
+//!
+//! As with message protection, this crate doesn't implement any compression
+//! algorithm itself, and relies on caller-provided functions instead.
+//!
+//! [RFC 3274]: https://www.rfc-editor.org/rfc/rfc3274
+
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+use cms::compressed_data::CompressedData;
+use cms::content_info::{CmsVersion, ContentInfo};
+use cms::signed_data::EncapsulatedContentInfo;
+use const_oid::db::rfc5911::ID_DATA;
+use core::fmt;
+use der::asn1::{Any, ObjectIdentifier, OctetString};
+use der::{Decode, Encode, Header, SliceReader, Tag};
+use spki::AlgorithmIdentifierOwned;
+
+use crate::gp::InfoTypeAndValue;
+use crate::header::PkiHeader;
+use crate::message::PkiMessage;
+
+/// `id-ct-compressedData` content type, as defined in [RFC 3274 Section 1.1].
+///
+/// [RFC 3274 Section 1.1]: https://www.rfc-editor.org/rfc/rfc3274#section-1.1
+pub use const_oid::db::rfc6268::ID_CT_COMPRESSED_DATA;
+
+/// Local compression settings.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CompressionConfig {
+    /// Info type under which support for compression is advertised.
+    pub info_type: ObjectIdentifier,
+
+    /// Supported compression algorithms, in order of preference.
+    pub algorithms: Vec<AlgorithmIdentifierOwned>,
+
+    /// Minimum length of the encoding of a message for it to be compressed.
+    pub min_len: usize,
+}
+
+impl CompressionConfig {
+    /// Advertise the supported compression algorithms in the `generalInfo`
+    /// of the given header, replacing any previous advertisement.
+    pub fn advertise(&self, header: &mut PkiHeader<'_>) -> der::Result<()> {
+        let value = Any::encode_from(&self.algorithms)?;
+        let general_info = header.general_info.get_or_insert_with(Vec::new);
+        general_info.retain(|info| info.oid != self.info_type);
+        general_info.push(InfoTypeAndValue {
+            oid: self.info_type,
+            value: Some(value),
+        });
+        Ok(())
+    }
+
+    /// Select the compression algorithm to use for messages sent to the
+    /// sender of the given message, i.e. the most preferred of the supported
+    /// algorithms which it advertised support for, if any.
+    pub fn negotiate(
+        &self,
+        peer: &PkiMessage<'_>,
+    ) -> Result<Option<&AlgorithmIdentifierOwned>, CompressionError> {
+        let Some(supported) = peer.compression_support(self.info_type)? else {
+            return Ok(None);
+        };
+
+        Ok(self.algorithms.iter().find(|alg| supported.contains(alg)))
+    }
+
+    /// Encode the given message as a transport payload, compressing it with
+    /// the given algorithm, as returned by [`CompressionConfig::negotiate`].
+    ///
+    /// Messages are sent uncompressed when no algorithm is given, when their
+    /// encoding is shorter than [`CompressionConfig::min_len`], or when
+    /// compressing them doesn't reduce the length of the payload.
+    pub fn encode_payload<E, F>(
+        &self,
+        message: &PkiMessage<'_>,
+        algorithm: Option<&AlgorithmIdentifierOwned>,
+        compress: F,
+    ) -> Result<Vec<u8>, E>
+    where
+        E: From<der::Error>,
+        F: FnOnce(&AlgorithmIdentifierOwned, &[u8]) -> Result<Vec<u8>, E>,
+    {
+        let message_der = message.to_der()?;
+
+        let Some(algorithm) = algorithm.filter(|_| message_der.len() >= self.min_len) else {
+            return Ok(message_der);
+        };
+
+        let compressed = compress(algorithm, &message_der)?;
+        let compressed_data = CompressedData {
+            version: CmsVersion::V0,
+            compression_alg: algorithm.clone(),
+            encap_content_info: EncapsulatedContentInfo {
+                econtent_type: ID_DATA,
+                econtent: Some(Any::encode_from(&OctetString::new(compressed)?)?),
+            },
+        };
+        let payload = ContentInfo {
+            content_type: ID_CT_COMPRESSED_DATA,
+            content: Any::encode_from(&compressed_data)?,
+        }
+        .to_der()?;
+
+        if payload.len() < message_der.len() {
+            Ok(payload)
+        } else {
+            Ok(message_der)
+        }
+    }
+}
+
+impl PkiMessage<'_> {
+    /// Get the compression algorithms advertised in the `generalInfo` of the
+    /// header of this message under the given info type, if any.
+    pub fn compression_support(
+        &self,
+        info_type: ObjectIdentifier,
+    ) -> Result<Option<Vec<AlgorithmIdentifierOwned>>, CompressionError> {
+        let mut entries = self
+            .header
+            .general_info
+            .iter()
+            .flatten()
+            .filter(|info| info.oid == info_type);
+
+        let Some(entry) = entries.next() else {
+            return Ok(None);
+        };
+
+        if entries.next().is_some() {
+            return Err(CompressionError::Malformed);
+        }
+
+        let value = entry.value.as_ref().ok_or(CompressionError::Malformed)?;
+        Ok(Some(value.decode_as()?))
+    }
+}
+
+/// Decode a transport payload into the DER encoding of a `PKIMessage`,
+/// decompressing it with the given function if it's compressed.
+///
+/// Uncompressed payloads are returned as-is. The decompression function is
+/// given the compression algorithm and the compressed data, and is
+/// responsible for rejecting unsupported algorithms, as well as for bounding
+/// the length of the decompressed data.
+pub fn decode_payload<'a, E, F>(payload: &'a [u8], decompress: F) -> Result<Cow<'a, [u8]>, E>
+where
+    E: From<CompressionError>,
+    F: FnOnce(&AlgorithmIdentifierOwned, &[u8]) -> Result<Vec<u8>, E>,
+{
+    if !is_content_info(payload).map_err(CompressionError::from)? {
+        return Ok(Cow::Borrowed(payload));
+    }
+
+    let content_info = ContentInfo::from_der(payload).map_err(CompressionError::from)?;
+    if content_info.content_type != ID_CT_COMPRESSED_DATA {
+        return Err(CompressionError::ContentType {
+            content_type: content_info.content_type,
+        }
+        .into());
+    }
+
+    let compressed_data: CompressedData = content_info
+        .content
+        .decode_as()
+        .map_err(CompressionError::from)?;
+    let encap = &compressed_data.encap_content_info;
+
+    if compressed_data.version != CmsVersion::V0 || encap.econtent_type != ID_DATA {
+        return Err(CompressionError::Malformed.into());
+    }
+
+    let compressed: OctetString = encap
+        .econtent
+        .as_ref()
+        .ok_or(CompressionError::Malformed)?
+        .decode_as()
+        .map_err(CompressionError::from)?;
+
+    decompress(&compressed_data.compression_alg, compressed.as_bytes()).map(Cow::Owned)
+}
+
+/// Is the given payload a `ContentInfo`, rather than a `PKIMessage`?
+///
+/// Both are `SEQUENCE`s, but the former begins with an `OBJECT IDENTIFIER`
+/// while the latter begins with the `SEQUENCE` of its header.
+fn is_content_info(payload: &[u8]) -> der::Result<bool> {
+    let mut reader = SliceReader::new(payload)?;
+    let header = Header::decode(&mut reader)?;
+    header.tag().assert_eq(Tag::Sequence)?;
+    Ok(Tag::peek(&reader)? == Tag::ObjectIdentifier)
+}
+
+/// Error negotiating or decoding compressed messages.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum CompressionError {
+    /// Error encoding or decoding a message or payload.
+    Der(der::Error),
+
+    /// The advertisement of supported algorithms is missing its value or
+    /// present more than once, or the compressed payload is malformed.
+    Malformed,
+
+    /// The payload is a `ContentInfo` of the given content type, rather than
+    /// of `id-ct-compressedData`.
+    ContentType {
+        /// Content type of the payload.
+        content_type: ObjectIdentifier,
+    },
+}
+
+impl fmt::Display for CompressionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Der(err) => write!(f, "error encoding or decoding payload: {err}"),
+            Self::Malformed => f.write_str("compression advertisement or payload is malformed"),
+            Self::ContentType { content_type } => {
+                write!(f, "unexpected payload content type: {content_type}")
+            }
+        }
+    }
+}
+
+impl core::error::Error for CompressionError {}
+
+impl From<der::Error> for CompressionError {
+    fn from(err: der::Error) -> Self {
+        Self::Der(err)
+    }
+}
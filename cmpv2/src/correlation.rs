@@ -0,0 +1,240 @@
+//! Correlation of responses with outstanding requests.
+//!
+//! Applications running many enrollments concurrently through a single
+//! transport connection need to route each response to the task awaiting it.
+//! A [`CorrelationMap`] maps the `transactionID`, and optionally the
+//! `certReqId`, of each outstanding request to a value such as a callback or
+//! the sending half of a channel, and looks it up from the header and body of
+//! the responses as they arrive.
+
+use alloc::vec::Vec;
+use core::{
+    fmt,
+    sync::atomic::{AtomicU32, Ordering},
+};
+use der::asn1::Int;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use alloc::collections::BTreeMap;
+
+use crate::{body::PkiBody, message::PkiMessage};
+
+/// Key identifying an outstanding request in a [`CorrelationMap`].
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+pub struct CorrelationKey {
+    /// `transactionID` of the request.
+    transaction_id: Vec<u8>,
+
+    /// `certReqId` of the request, if it's tracked individually.
+    cert_req_id: Option<Int>,
+}
+
+impl CorrelationKey {
+    /// Key matching any message of the transaction with the given
+    /// `transactionID`.
+    pub fn transaction(transaction_id: &[u8]) -> Self {
+        Self {
+            transaction_id: transaction_id.to_vec(),
+            cert_req_id: None,
+        }
+    }
+
+    /// Key matching the messages of the transaction with the given
+    /// `transactionID` which refer to the given `certReqId`.
+    pub fn cert_req(transaction_id: &[u8], cert_req_id: Int) -> Self {
+        Self {
+            transaction_id: transaction_id.to_vec(),
+            cert_req_id: Some(cert_req_id),
+        }
+    }
+
+    /// Get the `transactionID` of this key.
+    pub fn transaction_id(&self) -> &[u8] {
+        &self.transaction_id
+    }
+
+    /// Get the `certReqId` of this key, if any.
+    pub fn cert_req_id(&self) -> Option<&Int> {
+        self.cert_req_id.as_ref()
+    }
+}
+
+/// Concurrency-safe registry of outstanding requests, mapping them to a value
+/// used to route their responses, e.g. a callback or a channel.
+///
+/// A message is routed to the entry registered for its `transactionID` and
+/// the first of its `certReqId`s which has one, falling back to the entry
+/// registered for the `transactionID` alone.
+#[derive(Debug)]
+pub struct CorrelationMap<T> {
+    /// Outstanding requests.
+    entries: Mutex<BTreeMap<CorrelationKey, T>>,
+}
+
+impl<T> CorrelationMap<T> {
+    /// Create an empty [`CorrelationMap`].
+    pub fn new() -> Self {
+        Self {
+            entries: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Register an outstanding request with the given key.
+    ///
+    /// Returns [`CorrelationError::Duplicate`] if a request with the same key
+    /// is already outstanding.
+    pub fn register(&self, key: CorrelationKey, value: T) -> Result<(), CorrelationError> {
+        let mut entries = self.lock();
+
+        if entries.contains_key(&key) {
+            return Err(CorrelationError::Duplicate);
+        }
+
+        entries.insert(key, value);
+        Ok(())
+    }
+
+    /// Remove the request with the given key, e.g. once its transaction is
+    /// complete or has been abandoned, returning its value.
+    pub fn remove(&self, key: &CorrelationKey) -> Option<T> {
+        self.lock().remove(key)
+    }
+
+    /// Remove all requests of the transaction with the given `transactionID`.
+    pub fn remove_transaction(&self, transaction_id: &[u8]) -> Vec<T> {
+        let mut entries = self.lock();
+        let keys: Vec<_> = entries
+            .keys()
+            .filter(|key| key.transaction_id == transaction_id)
+            .cloned()
+            .collect();
+
+        keys.iter().filter_map(|key| entries.remove(key)).collect()
+    }
+
+    /// Get the number of outstanding requests.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Are there no outstanding requests?
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Get the value of the request the given message responds to.
+    ///
+    /// The value is cloned, so that the lock isn't held while the application
+    /// handles the message.
+    pub fn route(&self, message: &PkiMessage<'_>) -> Result<T, CorrelationError>
+    where
+        T: Clone,
+    {
+        let transaction_id = message
+            .header
+            .trans_id
+            .as_ref()
+            .ok_or(CorrelationError::MissingTransactionId)?
+            .as_bytes();
+
+        let entries = self.lock();
+        let mut key = CorrelationKey::transaction(transaction_id);
+
+        for cert_req_id in cert_req_ids(&message.body) {
+            key.cert_req_id = Some(cert_req_id.clone());
+
+            if let Some(value) = entries.get(&key) {
+                return Ok(value.clone());
+            }
+        }
+
+        key.cert_req_id = None;
+        entries.get(&key).cloned().ok_or(CorrelationError::Unknown)
+    }
+
+    /// Lock the entries, ignoring poisoning: entries are only modified by
+    /// single map operations, which can't leave them inconsistent.
+    fn lock(&self) -> MutexGuard<'_, BTreeMap<CorrelationKey, T>> {
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<T> Default for CorrelationMap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Get the `certReqId`s referred to by the given body, in order.
+///
+/// These are the IDs of the requests of `ir`/`cr`/`kur`/`krr`/`ccr` bodies,
+/// of the responses of `ip`/`cp`/`kup`/`ccp` bodies, and of `certConf`,
+/// `pollReq` and `pollRep` bodies. Other bodies don't refer to any.
+pub fn cert_req_ids<'a>(body: &'a PkiBody<'_>) -> Vec<&'a Int> {
+    match body {
+        PkiBody::Ir(reqs)
+        | PkiBody::Cr(reqs)
+        | PkiBody::Kur(reqs)
+        | PkiBody::Krr(reqs)
+        | PkiBody::Ccr(reqs) => reqs.iter().map(|req| &req.cert_req.cert_req_id).collect(),
+        PkiBody::Ip(rep) | PkiBody::Cp(rep) | PkiBody::Kup(rep) | PkiBody::Ccp(rep) => {
+            rep.response.iter().map(|rsp| &rsp.cert_req_id).collect()
+        }
+        PkiBody::CertConf(statuses) => statuses.iter().map(|status| &status.cert_req_id).collect(),
+        PkiBody::PollReq(poll_req) => poll_req.cert_req_ids.iter().collect(),
+        PkiBody::PollRep(poll_rep) => alloc::vec![&poll_rep.cert_req_id],
+        _ => Vec::new(),
+    }
+}
+
+/// Allocator of `certReqId`s which are unique among the requests sent by an
+/// application, so that they can be tracked individually in a
+/// [`CorrelationMap`] even when requests are batched.
+#[derive(Debug, Default)]
+pub struct CertReqIdAllocator {
+    /// Next `certReqId` to allocate.
+    next: AtomicU32,
+}
+
+impl CertReqIdAllocator {
+    /// Create an allocator starting at `certReqId` 0.
+    pub const fn new() -> Self {
+        Self {
+            next: AtomicU32::new(0),
+        }
+    }
+
+    /// Allocate a fresh `certReqId`.
+    ///
+    /// IDs wrap around once all 2^32 of them have been allocated.
+    pub fn allocate(&self) -> Int {
+        let id = self.next.fetch_add(1, Ordering::Relaxed);
+        Int::try_from(i64::from(id)).expect("u32 fits in an INTEGER")
+    }
+}
+
+/// Error correlating a message with an outstanding request.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum CorrelationError {
+    /// A request with the same key is already outstanding.
+    Duplicate,
+
+    /// The message has no `transactionID`.
+    MissingTransactionId,
+
+    /// The message doesn't correspond to any outstanding request.
+    Unknown,
+}
+
+impl fmt::Display for CorrelationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Duplicate => f.write_str("request already outstanding"),
+            Self::MissingTransactionId => f.write_str("message has no transactionID"),
+            Self::Unknown => f.write_str("message doesn't correspond to any outstanding request"),
+        }
+    }
+}
+
+impl core::error::Error for CorrelationError {}
@@ -0,0 +1,128 @@
+//! Cross-certification support.
+//!
+//! A CA requests a cross-certificate from another CA with a `ccr` body, which
+//! is answered with a `ccp` body. These use the same syntax as `cr` and `cp`
+//! bodies, with the restrictions of [RFC 4210 Section 5.3.11]:
+//!
+//! - the key pair MUST have been generated by the requesting CA, so the
+//!   private key MUST NOT be sent to the responding CA,
+//! - no encrypted private key can be sent in the response.
+//!
+//! [`ccr`] and [`ccp`] build the corresponding [`PkiBody`] after checking
+//! these restrictions, which [`validate_ccr`] and [`validate_ccp`] also check
+//! on received bodies.
+//!
+//! [RFC 4210 Section 5.3.11]: https://www.rfc-editor.org/rfc/rfc4210#section-5.3.11
+
+use core::fmt;
+
+use const_oid::db::rfc5912::ID_REG_CTRL_PKI_ARCHIVE_OPTIONS;
+use der::asn1::Int;
+
+use crmf::request::{CertReqMessages, CertReqMsg};
+
+use crate::body::PkiBody;
+use crate::certified_key_pair::CertOrEncCert;
+use crate::response::CertRepMessage;
+
+/// Build a cross-certification request (`ccr`) body from the given requests.
+pub fn ccr<'a>(content: CertReqMessages) -> Result<PkiBody<'a>, CrossCertError> {
+    validate_ccr(&content)?;
+    Ok(PkiBody::Ccr(content))
+}
+
+/// Build a cross-certification response (`ccp`) body from the given responses.
+pub fn ccp(content: CertRepMessage<'_>) -> Result<PkiBody<'_>, CrossCertError> {
+    validate_ccp(&content)?;
+    Ok(PkiBody::Ccp(content))
+}
+
+/// Check the requests of a `ccr` body against the restrictions of
+/// cross-certification requests.
+///
+/// Each request must include the public key of the requesting CA in its
+/// certificate template, and must not send a private key to be archived with
+/// a `pkiArchiveOptions` control.
+pub fn validate_ccr(content: &CertReqMessages) -> Result<(), CrossCertError> {
+    content.iter().try_for_each(validate_ccr_msg)
+}
+
+/// Check the responses of a `ccp` body against the restrictions of
+/// cross-certification responses.
+///
+/// Each response which carries a certified key pair must carry a plain
+/// certificate, i.e. neither an encrypted certificate nor a private key.
+pub fn validate_ccp(content: &CertRepMessage<'_>) -> Result<(), CrossCertError> {
+    for response in &content.response {
+        let Some(key_pair) = &response.certified_key_pair else {
+            continue;
+        };
+
+        let cert_req_id = || response.cert_req_id.clone();
+
+        if let CertOrEncCert::EncryptedCert(_) = key_pair.cert_or_enc_cert {
+            return Err(CrossCertError::EncryptedCert(cert_req_id()));
+        }
+
+        if key_pair.priv_key.is_some() {
+            return Err(CrossCertError::PrivateKey(cert_req_id()));
+        }
+    }
+
+    Ok(())
+}
+
+/// Check a single request of a `ccr` body.
+fn validate_ccr_msg(msg: &CertReqMsg) -> Result<(), CrossCertError> {
+    let req = &msg.cert_req;
+    let cert_req_id = || req.cert_req_id.clone();
+
+    if req.cert_template.subject_public_key_info.is_none() {
+        return Err(CrossCertError::MissingPublicKey(cert_req_id()));
+    }
+
+    let archives_private_key = req
+        .controls
+        .iter()
+        .flatten()
+        .any(|control| control.oid == ID_REG_CTRL_PKI_ARCHIVE_OPTIONS);
+
+    if archives_private_key {
+        return Err(CrossCertError::PrivateKey(cert_req_id()));
+    }
+
+    Ok(())
+}
+
+/// Error building or validating a cross-certification body.
+///
+/// Each variant holds the `certReqId` of the offending request or response.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum CrossCertError {
+    /// A request has no public key in its certificate template.
+    MissingPublicKey(Int),
+
+    /// A request or response sends a private key, e.g. in a
+    /// `pkiArchiveOptions` control of a request.
+    PrivateKey(Int),
+
+    /// A response carries an encrypted certificate.
+    EncryptedCert(Int),
+}
+
+impl fmt::Display for CrossCertError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingPublicKey(_) => {
+                f.write_str("cross-certification request without a public key")
+            }
+            Self::PrivateKey(_) => f.write_str("cross-certification message sends a private key"),
+            Self::EncryptedCert(_) => {
+                f.write_str("cross-certification response with an encrypted certificate")
+            }
+        }
+    }
+}
+
+impl core::error::Error for CrossCertError {}
@@ -1,8 +1,45 @@
 //! General purpose message-related types
 
-use der::{Sequence, ValueOrd};
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use der::asn1::{Any, ObjectIdentifier};
+use der::{Choice, DecodeValue, EncodeValue, Sequence, Tag, Tagged, ValueOrd};
+use spki::AlgorithmIdentifierOwned;
 use x509_cert::attr::{AttributeType, AttributeValue};
 
+use crate::header::CmpCertificate;
+
+/// `id-it-caProtEncCert` info type, as defined in [RFC 4210 Section 5.3.19.1].
+///
+/// [RFC 4210 Section 5.3.19.1]: https://www.rfc-editor.org/rfc/rfc4210#section-5.3.19.1
+pub use const_oid::db::rfc5912::ID_IT_CA_PROT_ENC_CERT;
+
+/// `id-it-signKeyPairTypes` info type, as defined in [RFC 4210 Section 5.3.19.2].
+///
+/// [RFC 4210 Section 5.3.19.2]: https://www.rfc-editor.org/rfc/rfc4210#section-5.3.19.2
+pub use const_oid::db::rfc5912::ID_IT_SIGN_KEY_PAIR_TYPES;
+
+/// `id-it-preferredSymmAlg` info type, as defined in [RFC 4210 Section 5.3.19.4].
+///
+/// [RFC 4210 Section 5.3.19.4]: https://www.rfc-editor.org/rfc/rfc4210#section-5.3.19.4
+pub use const_oid::db::rfc5912::ID_IT_PREFERRED_SYMM_ALG;
+
+/// `id-it-caCerts` info type, as defined in [RFC 9480 Section 2.3.1].
+///
+/// [RFC 9480 Section 2.3.1]: https://www.rfc-editor.org/rfc/rfc9480#section-2.3.1
+pub const ID_IT_CA_CERTS: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.6.1.5.5.7.4.17");
+
+/// `id-it-rootCaKeyUpdate` info type, as defined in [RFC 9480 Section 2.3.2].
+///
+/// [RFC 9480 Section 2.3.2]: https://www.rfc-editor.org/rfc/rfc9480#section-2.3.2
+pub const ID_IT_ROOT_CA_KEY_UPDATE: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.3.6.1.5.5.7.4.18");
+
+/// `id-it-rootCaCert` info type, as defined in [RFC 9480 Section 2.3.2].
+///
+/// [RFC 9480 Section 2.3.2]: https://www.rfc-editor.org/rfc/rfc9480#section-2.3.2
+pub const ID_IT_ROOT_CA_CERT: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.6.1.5.5.7.4.20");
+
 /// The `InfoTypeAndValue` type is defined in [RFC 4210 Section 5.3.19]
 ///
 /// ```text
@@ -21,6 +58,220 @@ pub struct InfoTypeAndValue {
     pub value: Option<AttributeValue>,
 }
 
+impl InfoTypeAndValue {
+    /// Create an `InfoTypeAndValue` without value, as used in `genm` requests
+    /// for information of the given type.
+    pub fn request(oid: ObjectIdentifier) -> Self {
+        Self { oid, value: None }
+    }
+
+    /// Decode the value of this `InfoTypeAndValue` according to its type.
+    pub fn to_info_value(&self) -> der::Result<InfoValue> {
+        self.try_into()
+    }
+}
+
+/// Typed value of an [`InfoTypeAndValue`], for the standard info types.
+///
+/// Values are absent in `genm` requests, and (except for `rootCaCert`)
+/// present in the corresponding `genp` responses. Absent values are
+/// represented as `None`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum InfoValue {
+    /// `id-it-caProtEncCert`: certificate of the CA to be used to protect
+    /// encrypted data, e.g. private keys for archival.
+    ///
+    /// ```text
+    ///  CAProtEncCertValue ::= CMPCertificate
+    /// ```
+    CaProtEncCert(Option<Box<CmpCertificate>>),
+
+    /// `id-it-signKeyPairTypes`: signature algorithms the CA supports for
+    /// certified public keys.
+    ///
+    /// ```text
+    ///  SignKeyPairTypesValue ::= SEQUENCE SIZE (1..MAX) OF
+    ///                  AlgorithmIdentifier{KEY-IDENTIFIER, {...}}
+    /// ```
+    SignKeyPairTypes(Option<Vec<AlgorithmIdentifierOwned>>),
+
+    /// `id-it-preferredSymmAlg`: symmetric algorithm the CA prefers for
+    /// encrypting data.
+    ///
+    /// ```text
+    ///  PreferredSymmAlgValue ::= AlgorithmIdentifier{ALGORITHM, {...}}
+    /// ```
+    PreferredSymmAlg(Option<AlgorithmIdentifierOwned>),
+
+    /// `id-it-caCerts`: CA certificates the PKI management entity recommends
+    /// for the end entity to trust.
+    ///
+    /// ```text
+    ///  CaCertsValue ::= SEQUENCE SIZE (1..MAX) OF CMPCertificate
+    /// ```
+    CaCerts(Option<Vec<CmpCertificate>>),
+
+    /// `id-it-rootCaCert`: root CA certificate for which an update is
+    /// requested with `id-it-rootCaKeyUpdate`, if any.
+    ///
+    /// ```text
+    ///  RootCaCertValue ::= CMPCertificate
+    /// ```
+    RootCaCert(Option<Box<CmpCertificate>>),
+
+    /// `id-it-rootCaKeyUpdate`: update of the root CA certificate, absent
+    /// from responses when no update is available.
+    ///
+    /// ```text
+    ///  RootCaKeyUpdateValue ::= RootCaKeyUpdateContent
+    /// ```
+    RootCaKeyUpdate(Option<Box<RootCaKeyUpdateContent>>),
+
+    /// Any other info type.
+    Other(InfoTypeAndValue),
+}
+
+impl InfoValue {
+    /// Get the info type of this value.
+    pub fn info_type(&self) -> ObjectIdentifier {
+        match self {
+            Self::CaProtEncCert(_) => ID_IT_CA_PROT_ENC_CERT,
+            Self::SignKeyPairTypes(_) => ID_IT_SIGN_KEY_PAIR_TYPES,
+            Self::PreferredSymmAlg(_) => ID_IT_PREFERRED_SYMM_ALG,
+            Self::CaCerts(_) => ID_IT_CA_CERTS,
+            Self::RootCaCert(_) => ID_IT_ROOT_CA_CERT,
+            Self::RootCaKeyUpdate(_) => ID_IT_ROOT_CA_KEY_UPDATE,
+            Self::Other(info) => info.oid,
+        }
+    }
+}
+
+impl TryFrom<&InfoTypeAndValue> for InfoValue {
+    type Error = der::Error;
+
+    fn try_from(info: &InfoTypeAndValue) -> der::Result<Self> {
+        let value = info.value.as_ref();
+
+        Ok(match info.oid {
+            ID_IT_CA_PROT_ENC_CERT => Self::CaProtEncCert(decode_value(value)?.map(Box::new)),
+            ID_IT_SIGN_KEY_PAIR_TYPES => Self::SignKeyPairTypes(decode_non_empty(value)?),
+            ID_IT_PREFERRED_SYMM_ALG => Self::PreferredSymmAlg(decode_value(value)?),
+            ID_IT_CA_CERTS => Self::CaCerts(decode_non_empty(value)?),
+            ID_IT_ROOT_CA_CERT => Self::RootCaCert(decode_value(value)?.map(Box::new)),
+            ID_IT_ROOT_CA_KEY_UPDATE => Self::RootCaKeyUpdate(decode_value(value)?.map(Box::new)),
+            _ => Self::Other(info.clone()),
+        })
+    }
+}
+
+impl TryFrom<InfoTypeAndValue> for InfoValue {
+    type Error = der::Error;
+
+    fn try_from(info: InfoTypeAndValue) -> der::Result<Self> {
+        Self::try_from(&info)
+    }
+}
+
+impl TryFrom<&InfoValue> for InfoTypeAndValue {
+    type Error = der::Error;
+
+    fn try_from(value: &InfoValue) -> der::Result<Self> {
+        let encoded = match value {
+            InfoValue::CaProtEncCert(cert) | InfoValue::RootCaCert(cert) => {
+                encode_value(cert.as_deref())?
+            }
+            InfoValue::SignKeyPairTypes(algs) => encode_non_empty(algs.as_ref())?,
+            InfoValue::PreferredSymmAlg(alg) => encode_value(alg.as_ref())?,
+            InfoValue::CaCerts(certs) => encode_non_empty(certs.as_ref())?,
+            InfoValue::RootCaKeyUpdate(content) => encode_value(content.as_deref())?,
+            InfoValue::Other(info) => return Ok(info.clone()),
+        };
+
+        Ok(Self {
+            oid: value.info_type(),
+            value: encoded,
+        })
+    }
+}
+
+impl TryFrom<InfoValue> for InfoTypeAndValue {
+    type Error = der::Error;
+
+    fn try_from(value: InfoValue) -> der::Result<Self> {
+        Self::try_from(&value)
+    }
+}
+
+/// The `RootCaKeyUpdateContent` type is defined in [RFC 9480 Section 2.3.2].
+///
+/// ```text
+///  RootCaKeyUpdateContent ::= SEQUENCE {
+///      newWithNew       CMPCertificate,
+///      newWithOld   [0] CMPCertificate OPTIONAL,
+///      oldWithNew   [1] CMPCertificate OPTIONAL
+///  }
+/// ```
+///
+/// [RFC 9480 Section 2.3.2]: https://www.rfc-editor.org/rfc/rfc9480#section-2.3.2
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+#[allow(missing_docs)]
+pub struct RootCaKeyUpdateContent {
+    pub new_with_new: CmpCertificate,
+    #[asn1(
+        context_specific = "0",
+        tag_mode = "EXPLICIT",
+        constructed = "true",
+        optional = "true"
+    )]
+    pub new_with_old: Option<CmpCertificate>,
+    #[asn1(
+        context_specific = "1",
+        tag_mode = "EXPLICIT",
+        constructed = "true",
+        optional = "true"
+    )]
+    pub old_with_new: Option<CmpCertificate>,
+}
+
+/// Decode the given optional `infoValue` as `T`.
+fn decode_value<T>(value: Option<&Any>) -> der::Result<Option<T>>
+where
+    T: for<'a> Choice<'a> + for<'a> DecodeValue<'a, Error = der::Error>,
+{
+    value.map(Any::decode_as).transpose()
+}
+
+/// Decode the given optional `infoValue` as a non-empty `SEQUENCE OF T`.
+fn decode_non_empty<T>(value: Option<&Any>) -> der::Result<Option<Vec<T>>>
+where
+    Vec<T>: for<'a> Choice<'a> + for<'a> DecodeValue<'a, Error = der::Error>,
+{
+    let values: Option<Vec<T>> = decode_value(value)?;
+
+    match values {
+        Some(values) if values.is_empty() => Err(Tag::Sequence.length_error().into()),
+        values => Ok(values),
+    }
+}
+
+/// Encode the given optional `infoValue`.
+fn encode_value<T: Tagged + EncodeValue>(value: Option<&T>) -> der::Result<Option<Any>> {
+    value.map(Any::encode_from).transpose()
+}
+
+/// Encode the given optional `infoValue` as a non-empty `SEQUENCE OF T`.
+fn encode_non_empty<T>(values: Option<&Vec<T>>) -> der::Result<Option<Any>>
+where
+    Vec<T>: Tagged + EncodeValue,
+{
+    if values.is_some_and(Vec::is_empty) {
+        return Err(Tag::Sequence.length_error().into());
+    }
+
+    encode_value(values)
+}
+
 /// The `GenMsgContent` type is defined in [RFC 4210 Section 5.3.19]
 ///
 /// ```text
@@ -28,7 +279,7 @@ pub struct InfoTypeAndValue {
 /// ```
 ///
 /// [RFC 4210 Section 5.3.19]: https://www.rfc-editor.org/rfc/rfc4210#section-5.3.19
-pub type GenMsgContent = alloc::vec::Vec<InfoTypeAndValue>;
+pub type GenMsgContent = Vec<InfoTypeAndValue>;
 
 /// The `GenRepContent` type is defined in [RFC 4210 Section 5.3.20]
 ///
@@ -37,4 +288,4 @@ pub type GenMsgContent = alloc::vec::Vec<InfoTypeAndValue>;
 /// ```
 ///
 /// [RFC 4210 Section 5.3.20]: https://www.rfc-editor.org/rfc/rfc4210#section-5.3.20
-pub type GenRepContent = alloc::vec::Vec<InfoTypeAndValue>;
+pub type GenRepContent = Vec<InfoTypeAndValue>;
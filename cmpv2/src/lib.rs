@@ -14,13 +14,17 @@
 )]
 
 extern crate alloc;
-
 #[cfg(feature = "std")]
 extern crate std;
 
 pub mod ann;
 pub mod body;
 pub mod certified_key_pair;
+#[cfg(feature = "compression")]
+pub mod compression;
+#[cfg(feature = "std")]
+pub mod correlation;
+pub mod cross_cert;
 pub mod gp;
 pub mod header;
 pub mod message;
@@ -28,6 +32,11 @@ pub mod oob;
 pub mod parameter;
 pub mod poll;
 pub mod pop;
+pub mod protection;
+pub mod replay;
 pub mod response;
 pub mod rev;
+pub mod server;
 pub mod status;
+pub mod transaction;
+pub mod transport;
@@ -1,11 +1,15 @@
 //! Polling-related types
 
 use alloc::vec::Vec;
+use core::{fmt, time::Duration};
 use der::asn1::Int;
 
 use der::Sequence;
 
+use crate::body::PkiBody;
 use crate::header::PkiFreeText;
+use crate::response::CertRepMessage;
+use crate::status::PkiStatus;
 
 /// The `PollReqContent` type is defined in [RFC 4210 Section 5.3.22].
 ///
@@ -45,3 +49,156 @@ pub struct PollRepContent<'a> {
     pub check_after: u64,
     pub reason: Option<PkiFreeText<'a>>,
 }
+
+impl PollRepContent<'_> {
+    /// Time to wait before polling again, from the `checkAfter` field.
+    pub fn check_after(&self) -> Duration {
+        Duration::from_secs(self.check_after)
+    }
+}
+
+/// Action to be taken by the application driving a [`Poller`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PollAction {
+    /// Wait for the given amount of time, then ask for the next action.
+    Wait(Duration),
+
+    /// Send a `pollReq` message with the given content, and pass the body of
+    /// the response to [`Poller::handle_body`].
+    SendPollReq(PollReqContent),
+
+    /// No certificate requests are waiting any more.
+    Done,
+}
+
+/// Sans-I/O state machine for polling the certificate requests of an
+/// `ip`/`cp`/`kup` response with `waiting` status, as described in
+/// [RFC 4210 Section 5.3.22].
+///
+/// The application repeatedly calls [`Poller::next_action`] and performs the
+/// returned [`PollAction`] until it returns [`PollAction::Done`]. The final
+/// responses to the polled requests are delivered in `ip`/`cp`/`kup` bodies,
+/// which the application should process as usual in addition to passing them
+/// to [`Poller::handle_body`].
+///
+/// [RFC 4210 Section 5.3.22]: https://www.rfc-editor.org/rfc/rfc4210#section-5.3.22
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Poller {
+    /// IDs of the certificate requests which are still waiting.
+    pending: Vec<PollReqContentId>,
+
+    /// Time to wait before sending the next `pollReq`, if any.
+    check_after: Option<Duration>,
+}
+
+impl Poller {
+    /// Create a [`Poller`] for the given waiting certificate requests.
+    pub fn new(cert_req_ids: Vec<PollReqContentId>) -> Self {
+        Self {
+            pending: cert_req_ids,
+            check_after: None,
+        }
+    }
+
+    /// Create a [`Poller`] for the certificate requests of the given response
+    /// which have `waiting` status.
+    pub fn from_response(response: &CertRepMessage<'_>) -> Self {
+        Self::new(waiting_cert_req_ids(response).cloned().collect())
+    }
+
+    /// IDs of the certificate requests which are still waiting.
+    pub fn pending(&self) -> &[PollReqContentId] {
+        &self.pending
+    }
+
+    /// Get the next action to be taken by the application.
+    pub fn next_action(&mut self) -> PollAction {
+        if self.pending.is_empty() {
+            return PollAction::Done;
+        }
+
+        match self.check_after.take() {
+            Some(check_after) => PollAction::Wait(check_after),
+            None => PollAction::SendPollReq(PollReqContent {
+                cert_req_ids: self.pending.clone(),
+            }),
+        }
+    }
+
+    /// Handle the body of the response to a `pollReq`.
+    pub fn handle_body(&mut self, body: &PkiBody<'_>) -> Result<(), PollError> {
+        match body {
+            PkiBody::PollRep(poll_rep) => self.handle_poll_rep(poll_rep),
+            PkiBody::Ip(response) | PkiBody::Cp(response) | PkiBody::Kup(response) => {
+                self.handle_response(response)
+            }
+            _ => Err(PollError::UnexpectedBody),
+        }
+    }
+
+    /// Handle a `pollRep`, indicating the given certificate request is still
+    /// waiting.
+    pub fn handle_poll_rep(&mut self, poll_rep: &PollRepContent<'_>) -> Result<(), PollError> {
+        if !self.pending.contains(&poll_rep.cert_req_id) {
+            return Err(PollError::UnknownCertReqId);
+        }
+
+        self.check_after = Some(poll_rep.check_after());
+        Ok(())
+    }
+
+    /// Handle an `ip`/`cp`/`kup` response, which completes the certificate
+    /// requests it contains unless they're still waiting.
+    pub fn handle_response(&mut self, response: &CertRepMessage<'_>) -> Result<(), PollError> {
+        if response
+            .response
+            .iter()
+            .any(|rsp| !self.pending.contains(&rsp.cert_req_id))
+        {
+            return Err(PollError::UnknownCertReqId);
+        }
+
+        self.pending.retain(|id| {
+            !response
+                .response
+                .iter()
+                .any(|rsp| &rsp.cert_req_id == id && rsp.status.status != PkiStatus::Waiting)
+        });
+
+        Ok(())
+    }
+}
+
+/// Iterate over the IDs of the certificate requests in the given response
+/// which have `waiting` status and hence need to be polled.
+pub fn waiting_cert_req_ids<'a>(
+    response: &'a CertRepMessage<'_>,
+) -> impl Iterator<Item = &'a PollReqContentId> {
+    response
+        .response
+        .iter()
+        .filter(|rsp| rsp.status.status == PkiStatus::Waiting)
+        .map(|rsp| &rsp.cert_req_id)
+}
+
+/// Error handling a response in a [`Poller`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum PollError {
+    /// The response refers to a certificate request which isn't being polled.
+    UnknownCertReqId,
+
+    /// The response body is neither `pollRep` nor `ip`/`cp`/`kup`.
+    UnexpectedBody,
+}
+
+impl fmt::Display for PollError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownCertReqId => f.write_str("response for unknown certReqId"),
+            Self::UnexpectedBody => f.write_str("unexpected response body while polling"),
+        }
+    }
+}
+
+impl core::error::Error for PollError {}
@@ -8,6 +8,9 @@ use der::asn1::{OctetString, UintRef};
 use spki::AlgorithmIdentifierOwned;
 use x509_cert::ext::pkix::name::GeneralName;
 
+#[cfg(feature = "builder")]
+pub use crmf::builder::CertReqMsgBuilder;
+
 /// The `POPODecKeyChallContent` type is defined in [RFC 4210 Section 5.2.8.3].
 ///
 /// ```text
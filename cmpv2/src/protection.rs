@@ -0,0 +1,423 @@
+//! PKIMessage protection verification helpers.
+//!
+//! This crate doesn't implement any cryptography itself. Instead, the helpers
+//! in this module locate the relevant parts of a [`PkiMessage`] and hand them
+//! to a caller-provided verification function, which can be implemented using
+//! e.g. the `signature` or `hmac` crates.
+//!
+//! # Dual protection
+//!
+//! Some RA-mediated flows require messages to carry both MAC-based and
+//! signature-based protection. In addition to the regular `protection`, such
+//! messages carry an [`AdditionalProtection`] in an `InfoTypeAndValue` of the
+//! `generalInfo` of their header. No info type is registered for this
+//! purpose, so the OID identifying it must be agreed upon by the parties.
+//!
+//! As the additional protection is part of the header, the regular
+//! protection covers it, while the additional protection covers the rest of
+//! the message: see [`protect_dual`] for the construction ordering rules.
+//! Either protection can be verified independently, with
+//! [`verify_with_rollover`] and [`verify_additional_with_rollover`]
+//! respectively, and flows requiring both should verify both.
+
+use alloc::vec::Vec;
+use core::fmt;
+use der::asn1::{Any, BitString, ObjectIdentifier};
+use der::{Encode, Header, Sequence, Tag};
+use spki::AlgorithmIdentifierOwned;
+
+use crate::gp::InfoTypeAndValue;
+use crate::header::PkiHeader;
+use crate::message::{PkiMessage, PkiProtection};
+
+impl PkiMessage<'_> {
+    /// Encode the `ProtectedPart` of this message, i.e. the DER encoding of
+    /// its header and body over which `protection` is computed.
+    ///
+    /// See [`ProtectedPart`][crate::message::ProtectedPart].
+    pub fn protected_part_der(&self) -> der::Result<Vec<u8>> {
+        let len = (self.header.encoded_len()? + self.body.encoded_len()?)?;
+        let mut buf = Vec::new();
+        Header::new(Tag::Sequence, len).encode_to_vec(&mut buf)?;
+        self.header.encode_to_vec(&mut buf)?;
+        self.body.encode_to_vec(&mut buf)?;
+        Ok(buf)
+    }
+
+    /// Get the [`AdditionalProtection`] carried in the `generalInfo` of the
+    /// header of this message under the given info type, if any.
+    pub fn additional_protection(
+        &self,
+        info_type: ObjectIdentifier,
+    ) -> Result<Option<AdditionalProtection>, ProtectionError> {
+        let mut entries = self
+            .header
+            .general_info
+            .iter()
+            .flatten()
+            .filter(|info| info.oid == info_type);
+
+        let Some(entry) = entries.next() else {
+            return Ok(None);
+        };
+
+        if entries.next().is_some() {
+            return Err(ProtectionError::Malformed);
+        }
+
+        let value = entry.value.as_ref().ok_or(ProtectionError::Malformed)?;
+        Ok(Some(value.decode_as()?))
+    }
+
+    /// Encode the part of this message covered by the additional protection
+    /// carried under the given info type, i.e. its `ProtectedPart` without
+    /// the additional protection itself.
+    ///
+    /// When no other `generalInfo` is present, the field is omitted
+    /// altogether.
+    pub fn additional_protected_part_der(
+        &self,
+        info_type: ObjectIdentifier,
+    ) -> der::Result<Vec<u8>> {
+        PkiMessage {
+            header: without_additional_protection(&self.header, info_type),
+            body: self.body.clone(),
+            protection: None,
+            extra_certs: None,
+        }
+        .protected_part_der()
+    }
+}
+
+/// Additional protection of a dual-protected [`PkiMessage`].
+///
+/// ```text
+/// AdditionalProtection ::= SEQUENCE {
+///     protectionAlg   AlgorithmIdentifier{ALGORITHM, {...}},
+///     protection      PKIProtection }
+/// ```
+///
+/// See the [module documentation](self) for details.
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+#[allow(missing_docs)]
+pub struct AdditionalProtection {
+    pub protection_alg: AlgorithmIdentifierOwned,
+    pub protection: PkiProtection,
+}
+
+/// Error verifying the protection of a [`PkiMessage`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ProtectionError {
+    /// Error encoding the protected part of the message.
+    Der(der::Error),
+
+    /// The message has no `protection`, or its header has no `protectionAlg`,
+    /// or no additional protection was found when one was expected.
+    Unprotected,
+
+    /// The `protection` is not octet-aligned, or the additional protection
+    /// is missing its value or present more than once.
+    Malformed,
+
+    /// The message body is not `nested`.
+    NotNested,
+
+    /// None of the candidate keys verified the message protection.
+    NoMatchingKey,
+
+    /// The message protection was verified by the candidate key at the given
+    /// index, but the key was rejected by the [`RolloverPolicy`].
+    Rejected {
+        /// Outcome of verifying the message protection.
+        verified: RolloverVerified,
+    },
+}
+
+impl fmt::Display for ProtectionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Der(err) => write!(f, "error encoding protected part: {err}"),
+            Self::Unprotected => f.write_str("message is not protected"),
+            Self::Malformed => f.write_str("message protection is malformed"),
+            Self::NotNested => f.write_str("message body is not nested"),
+            Self::NoMatchingKey => f.write_str("no candidate key verified message protection"),
+            Self::Rejected { verified } => write!(
+                f,
+                "message protection verified by candidate key {} which was rejected by policy",
+                verified.index
+            ),
+        }
+    }
+}
+
+impl core::error::Error for ProtectionError {}
+
+impl From<der::Error> for ProtectionError {
+    fn from(err: der::Error) -> Self {
+        Self::Der(err)
+    }
+}
+
+/// Outcome of verifying message protection against a set of candidate keys.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RolloverVerified {
+    /// Index of the candidate key which verified the message protection.
+    pub index: usize,
+
+    /// Is the candidate key which verified the message the newest one?
+    pub newest: bool,
+}
+
+/// Policy deciding whether a message verified by a particular candidate key
+/// is acceptable, e.g. during RA key rollover.
+///
+/// This trait is impl'd for closures, allowing arbitrary policies to be used.
+pub trait RolloverPolicy {
+    /// Should the message protection verified by the given key be accepted?
+    fn accept(&self, verified: &RolloverVerified) -> bool;
+}
+
+/// [`RolloverPolicy`] which accepts messages verified by any candidate key.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct AcceptAny;
+
+impl RolloverPolicy for AcceptAny {
+    fn accept(&self, _verified: &RolloverVerified) -> bool {
+        true
+    }
+}
+
+/// [`RolloverPolicy`] which only accepts messages verified by the newest
+/// candidate key, i.e. once key rollover is complete.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct RequireNewest;
+
+impl RolloverPolicy for RequireNewest {
+    fn accept(&self, verified: &RolloverVerified) -> bool {
+        verified.newest
+    }
+}
+
+impl<F> RolloverPolicy for F
+where
+    F: Fn(&RolloverVerified) -> bool,
+{
+    fn accept(&self, verified: &RolloverVerified) -> bool {
+        self(verified)
+    }
+}
+
+/// Verify the protection of a [`PkiMessage`] using a set of candidate keys,
+/// e.g. the old and new keys of an RA which is rolling over its credentials.
+///
+/// The `candidates` must be ordered from oldest to newest. They are tried
+/// starting with the newest, and `verify` is called with each candidate key,
+/// the `protectionAlg` from the message header, the DER encoding of the
+/// protected part of the message, and the protection bits, until one of them
+/// succeeds.
+///
+/// The candidate which verified the message is then checked against the
+/// provided [`RolloverPolicy`], and reported on success.
+pub fn verify_with_rollover<K, E, F>(
+    message: &PkiMessage<'_>,
+    candidates: &[K],
+    policy: impl RolloverPolicy,
+    verify: F,
+) -> Result<RolloverVerified, ProtectionError>
+where
+    F: FnMut(&K, &AlgorithmIdentifierOwned, &[u8], &[u8]) -> Result<(), E>,
+{
+    let alg = message
+        .header
+        .protection_alg
+        .as_ref()
+        .ok_or(ProtectionError::Unprotected)?;
+    let protection = message
+        .protection
+        .as_ref()
+        .ok_or(ProtectionError::Unprotected)?
+        .as_bytes()
+        .ok_or(ProtectionError::Malformed)?;
+    let protected_part = message.protected_part_der()?;
+
+    verify_candidates(alg, &protected_part, protection, candidates, policy, verify)
+}
+
+/// Verify the protection of a `nested` [`PkiMessage`], e.g. a batch of
+/// requests wrapped by an RA, and unwrap its inner messages.
+///
+/// Only the protection of the outer message is verified, as described for
+/// [`verify_with_rollover`]. Each inner message carries its own protection,
+/// typically applied by a different end entity, which should be verified
+/// separately, e.g.:
+///
+/// ```ignore
+/// for inner in unwrap_nested(&outer, &ra_keys, RequireNewest, verify)? {
+///     verify_with_rollover(inner, ee_keys(inner), AcceptAny, verify)?;
+/// }
+/// ```
+///
+/// Inner messages may themselves be `nested`, in which case they can be
+/// unwrapped in turn.
+pub fn unwrap_nested<'m, 'a, K, E, F>(
+    message: &'m PkiMessage<'a>,
+    candidates: &[K],
+    policy: impl RolloverPolicy,
+    verify: F,
+) -> Result<&'m [PkiMessage<'a>], ProtectionError>
+where
+    F: FnMut(&K, &AlgorithmIdentifierOwned, &[u8], &[u8]) -> Result<(), E>,
+{
+    let inner = message
+        .body
+        .nested_messages()
+        .ok_or(ProtectionError::NotNested)?;
+
+    verify_with_rollover(message, candidates, policy, verify)?;
+    Ok(inner)
+}
+
+/// Apply dual protection to a [`PkiMessage`], replacing any existing
+/// protection.
+///
+/// The protections are computed in the following order:
+///
+/// 1. The `protectionAlg` of the header is set to `protection_alg`.
+/// 2. `additional` is called with `additional_alg` and the DER encoding of the
+///    `ProtectedPart` of the message without the additional protection, and
+///    its result is stored as an [`AdditionalProtection`] in the
+///    `generalInfo` of the header under the given info type.
+/// 3. `protect` is called with `protection_alg` and the DER encoding of the
+///    `ProtectedPart` of the message, which now includes the additional
+///    protection, and its result is stored as the `protection`.
+///
+/// Typically, the additional protection is signature-based while the
+/// regular protection is MAC-based, although this isn't enforced.
+pub fn protect_dual<E, A, P>(
+    message: &mut PkiMessage<'_>,
+    info_type: ObjectIdentifier,
+    additional_alg: AlgorithmIdentifierOwned,
+    additional: A,
+    protection_alg: AlgorithmIdentifierOwned,
+    protect: P,
+) -> Result<(), E>
+where
+    E: From<der::Error>,
+    A: FnOnce(&AlgorithmIdentifierOwned, &[u8]) -> Result<Vec<u8>, E>,
+    P: FnOnce(&AlgorithmIdentifierOwned, &[u8]) -> Result<Vec<u8>, E>,
+{
+    message.protection = None;
+    message.header = without_additional_protection(&message.header, info_type);
+    message.header.protection_alg = Some(protection_alg.clone());
+
+    let part = message.protected_part_der()?;
+    let protection = additional(&additional_alg, &part)?;
+    let value = Any::encode_from(&AdditionalProtection {
+        protection_alg: additional_alg,
+        protection: BitString::from_bytes(&protection)?,
+    })?;
+
+    message
+        .header
+        .general_info
+        .get_or_insert_with(Vec::new)
+        .push(InfoTypeAndValue {
+            oid: info_type,
+            value: Some(value),
+        });
+
+    let part = message.protected_part_der()?;
+    let protection = protect(&protection_alg, &part)?;
+    message.protection = Some(BitString::from_bytes(&protection)?);
+    Ok(())
+}
+
+/// Verify the additional protection of a dual-protected [`PkiMessage`],
+/// carried under the given info type, using a set of candidate keys.
+///
+/// This works like [`verify_with_rollover`], except that `verify` is called
+/// with the `protectionAlg` of the additional protection, the DER encoding
+/// of the part of the message it covers, and its protection bits.
+///
+/// Only the additional protection is verified: use [`verify_with_rollover`]
+/// to verify the regular protection of the message.
+pub fn verify_additional_with_rollover<K, E, F>(
+    message: &PkiMessage<'_>,
+    info_type: ObjectIdentifier,
+    candidates: &[K],
+    policy: impl RolloverPolicy,
+    verify: F,
+) -> Result<RolloverVerified, ProtectionError>
+where
+    F: FnMut(&K, &AlgorithmIdentifierOwned, &[u8], &[u8]) -> Result<(), E>,
+{
+    let additional = message
+        .additional_protection(info_type)?
+        .ok_or(ProtectionError::Unprotected)?;
+    let protection = additional
+        .protection
+        .as_bytes()
+        .ok_or(ProtectionError::Malformed)?;
+    let protected_part = message.additional_protected_part_der(info_type)?;
+
+    verify_candidates(
+        &additional.protection_alg,
+        &protected_part,
+        protection,
+        candidates,
+        policy,
+        verify,
+    )
+}
+
+/// Try each candidate key, starting with the newest, and check the one which
+/// verified the protection against the policy.
+fn verify_candidates<K, E, F>(
+    alg: &AlgorithmIdentifierOwned,
+    protected_part: &[u8],
+    protection: &[u8],
+    candidates: &[K],
+    policy: impl RolloverPolicy,
+    mut verify: F,
+) -> Result<RolloverVerified, ProtectionError>
+where
+    F: FnMut(&K, &AlgorithmIdentifierOwned, &[u8], &[u8]) -> Result<(), E>,
+{
+    let (index, _) = candidates
+        .iter()
+        .enumerate()
+        .rev()
+        .find(|(_, key)| verify(key, alg, protected_part, protection).is_ok())
+        .ok_or(ProtectionError::NoMatchingKey)?;
+
+    let verified = RolloverVerified {
+        index,
+        newest: index + 1 == candidates.len(),
+    };
+
+    if policy.accept(&verified) {
+        Ok(verified)
+    } else {
+        Err(ProtectionError::Rejected { verified })
+    }
+}
+
+/// Copy the given header without the additional protection carried under
+/// the given info type.
+fn without_additional_protection<'a>(
+    header: &PkiHeader<'a>,
+    info_type: ObjectIdentifier,
+) -> PkiHeader<'a> {
+    let mut header = header.clone();
+
+    if let Some(general_info) = &mut header.general_info {
+        general_info.retain(|info| info.oid != info_type);
+
+        if general_info.is_empty() {
+            header.general_info = None;
+        }
+    }
+
+    header
+}
@@ -0,0 +1,190 @@
+//! Replay protection for servers.
+//!
+//! A protected message can be captured and resent by an attacker, and its
+//! protection will still verify. To detect this, servers can record the
+//! (`transactionID`, `senderNonce`) pair of every message they accept in a
+//! [`ReplayCache`] and reject messages whose pair was seen before. Entries
+//! only need to be retained for as long as a message would otherwise be
+//! accepted, e.g. based on its `messageTime`, so each entry has a TTL.
+
+use core::{fmt, time::Duration};
+use der::asn1::GeneralizedTime;
+
+use alloc::{collections::BTreeMap, vec::Vec};
+
+use crate::header::PkiHeader;
+
+/// Store of the (`transactionID`, `senderNonce`) pairs of previously accepted
+/// messages, e.g. in memory or in a database shared between server instances.
+pub trait ReplayCache {
+    /// Error type of the underlying store.
+    type Error;
+
+    /// Record the given pair as seen at `now`, expiring after `ttl`.
+    ///
+    /// Returns `false` if the pair was already recorded and hasn't expired
+    /// yet, i.e. if the message is a replay. Implementations backed by a
+    /// shared store must check and record the pair atomically.
+    fn insert(
+        &mut self,
+        transaction_id: &[u8],
+        sender_nonce: &[u8],
+        now: GeneralizedTime,
+        ttl: Duration,
+    ) -> Result<bool, Self::Error>;
+}
+
+/// Replay check for received messages, backed by a [`ReplayCache`].
+#[derive(Clone, Debug)]
+pub struct ReplayGuard<C> {
+    /// Cache of previously accepted messages.
+    cache: C,
+
+    /// Time after which recorded messages expire.
+    ttl: Duration,
+}
+
+impl<C: ReplayCache> ReplayGuard<C> {
+    /// Create a new [`ReplayGuard`] recording messages in the given cache for
+    /// the given amount of time.
+    pub fn new(cache: C, ttl: Duration) -> Self {
+        Self { cache, ttl }
+    }
+
+    /// Get the underlying cache.
+    pub fn cache(&self) -> &C {
+        &self.cache
+    }
+
+    /// Get the underlying cache mutably, e.g. to purge expired entries.
+    pub fn cache_mut(&mut self) -> &mut C {
+        &mut self.cache
+    }
+
+    /// Check that the message with the given header, received at `now`, isn't
+    /// a replay, and record it.
+    ///
+    /// This should only be called once the protection of the message has been
+    /// verified, since otherwise an attacker could fill the cache with
+    /// forged pairs to block legitimate messages.
+    pub fn check(
+        &mut self,
+        header: &PkiHeader<'_>,
+        now: GeneralizedTime,
+    ) -> Result<(), ReplayError<C::Error>> {
+        let transaction_id = header
+            .trans_id
+            .as_ref()
+            .ok_or(ReplayError::MissingTransactionId)?;
+
+        let sender_nonce = header
+            .sender_nonce
+            .as_ref()
+            .ok_or(ReplayError::MissingSenderNonce)?;
+
+        let fresh = self
+            .cache
+            .insert(
+                transaction_id.as_bytes(),
+                sender_nonce.as_bytes(),
+                now,
+                self.ttl,
+            )
+            .map_err(ReplayError::Cache)?;
+
+        if fresh {
+            Ok(())
+        } else {
+            Err(ReplayError::Replayed)
+        }
+    }
+}
+
+/// In-memory [`ReplayCache`], suitable for a single server instance.
+#[derive(Clone, Debug, Default)]
+pub struct MemoryReplayCache {
+    /// Expiry times of recorded pairs, as durations since the UNIX epoch.
+    entries: BTreeMap<(Vec<u8>, Vec<u8>), Duration>,
+}
+
+impl MemoryReplayCache {
+    /// Create a new, empty [`MemoryReplayCache`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of recorded pairs, including ones which have expired but
+    /// haven't been purged yet.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Is the cache empty?
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Remove the pairs which have expired at `now`.
+    pub fn purge(&mut self, now: GeneralizedTime) {
+        let now = now.to_unix_duration();
+        self.entries.retain(|_, expiry| *expiry > now);
+    }
+}
+
+impl ReplayCache for MemoryReplayCache {
+    type Error = core::convert::Infallible;
+
+    fn insert(
+        &mut self,
+        transaction_id: &[u8],
+        sender_nonce: &[u8],
+        now: GeneralizedTime,
+        ttl: Duration,
+    ) -> Result<bool, Self::Error> {
+        let key = (transaction_id.to_vec(), sender_nonce.to_vec());
+        let now = now.to_unix_duration();
+        let expiry = now.saturating_add(ttl);
+
+        match self.entries.get_mut(&key) {
+            Some(existing) if *existing > now => Ok(false),
+            Some(existing) => {
+                *existing = expiry;
+                Ok(true)
+            }
+            None => {
+                self.entries.insert(key, expiry);
+                Ok(true)
+            }
+        }
+    }
+}
+
+/// Error checking a message with a [`ReplayGuard`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ReplayError<E> {
+    /// The message has no `transactionID`.
+    MissingTransactionId,
+
+    /// The message has no `senderNonce`.
+    MissingSenderNonce,
+
+    /// The message was already received.
+    Replayed,
+
+    /// The [`ReplayCache`] failed.
+    Cache(E),
+}
+
+impl<E: fmt::Display> fmt::Display for ReplayError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingTransactionId => f.write_str("missing transactionID"),
+            Self::MissingSenderNonce => f.write_str("missing senderNonce"),
+            Self::Replayed => f.write_str("replayed message"),
+            Self::Cache(err) => write!(f, "replay cache error: {err}"),
+        }
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> core::error::Error for ReplayError<E> {}
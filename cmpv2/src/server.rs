@@ -0,0 +1,231 @@
+//! Server-side request dispatching.
+//!
+//! A CMP responder (CA or RA) receives a request, handles its body according
+//! to its type, and replies with a response whose header is tied to the
+//! request: the `transactionID` is the same, and the `recipNonce` echoes the
+//! `senderNonce` of the request. [`respond`] takes care of this bookkeeping,
+//! and dispatches the body of the request to the matching callback of a
+//! [`MessageHandler`].
+
+use der::asn1::{Null, OctetString};
+
+use crmf::request::CertReqMessages;
+use x509_cert::request::CertReq;
+
+use crate::body::PkiBody;
+use crate::cross_cert::ccp;
+use crate::gp::{GenMsgContent, GenRepContent};
+use crate::header::PkiHeader;
+use crate::message::PkiMessage;
+use crate::poll::{PollRepContent, PollReqContent};
+use crate::response::CertRepMessage;
+use crate::rev::{RevRepContent, RevReqContent};
+use crate::status::{
+    CertConfirmContent, ErrorMsgContent, PkiFailureInfoValues, PkiStatus, PkiStatusInfo,
+};
+
+/// Callbacks handling the bodies of the requests received by a CMP responder.
+///
+/// Each callback receives the header and body of a request, and returns the
+/// body of the response, which [`respond`] wraps in the corresponding
+/// [`PkiBody`] variant, e.g. the [`CertRepMessage`] returned by
+/// [`MessageHandler::ir`] is sent as an `ip`.
+///
+/// Callbacks return `Ok(None)` if the request type is not supported, in which
+/// case an `error` message with the `badRequest` failure info is sent. This
+/// is the behavior of the provided implementations, so handlers only need to
+/// implement the request types they support.
+///
+/// Requests which are rejected for other reasons should be answered with the
+/// appropriate status in the response body, e.g. a [`PkiStatus::Rejection`]
+/// in the [`CertRepMessage`]. [`MessageHandler::Error`] is meant for errors
+/// which prevent sending any response at all.
+#[allow(unused_variables)]
+pub trait MessageHandler {
+    /// Error type.
+    type Error;
+
+    /// Handle an initialization request (`ir`), responded to with an `ip`.
+    fn ir<'a>(
+        &mut self,
+        header: &PkiHeader<'a>,
+        content: &CertReqMessages,
+    ) -> Result<Option<CertRepMessage<'a>>, Self::Error> {
+        Ok(None)
+    }
+
+    /// Handle a certification request (`cr`), responded to with a `cp`.
+    fn cr<'a>(
+        &mut self,
+        header: &PkiHeader<'a>,
+        content: &CertReqMessages,
+    ) -> Result<Option<CertRepMessage<'a>>, Self::Error> {
+        Ok(None)
+    }
+
+    /// Handle a PKCS #10 certification request (`p10cr`), responded to with
+    /// a `cp`.
+    fn p10cr<'a>(
+        &mut self,
+        header: &PkiHeader<'a>,
+        content: &CertReq,
+    ) -> Result<Option<CertRepMessage<'a>>, Self::Error> {
+        Ok(None)
+    }
+
+    /// Handle a key update request (`kur`), responded to with a `kup`.
+    fn kur<'a>(
+        &mut self,
+        header: &PkiHeader<'a>,
+        content: &CertReqMessages,
+    ) -> Result<Option<CertRepMessage<'a>>, Self::Error> {
+        Ok(None)
+    }
+
+    /// Handle a cross-certification request (`ccr`), responded to with a
+    /// `ccp`.
+    ///
+    /// The response must only carry plain certificates, see
+    /// [`validate_ccp`](crate::cross_cert::validate_ccp). [`respond`] sends an
+    /// `error` message with the `systemFailure` failure info instead of a
+    /// response which doesn't.
+    fn ccr<'a>(
+        &mut self,
+        header: &PkiHeader<'a>,
+        content: &CertReqMessages,
+    ) -> Result<Option<CertRepMessage<'a>>, Self::Error> {
+        Ok(None)
+    }
+
+    /// Handle a revocation request (`rr`), responded to with a `rp`.
+    fn rr<'a>(
+        &mut self,
+        header: &PkiHeader<'a>,
+        content: &RevReqContent,
+    ) -> Result<Option<RevRepContent<'a>>, Self::Error> {
+        Ok(None)
+    }
+
+    /// Handle a general message (`genm`), responded to with a `genp`.
+    fn genm(
+        &mut self,
+        header: &PkiHeader<'_>,
+        content: &GenMsgContent,
+    ) -> Result<Option<GenRepContent>, Self::Error> {
+        Ok(None)
+    }
+
+    /// Handle a certificate confirmation (`certConf`), responded to with a
+    /// `pkiconf`.
+    fn cert_conf(
+        &mut self,
+        header: &PkiHeader<'_>,
+        content: &CertConfirmContent<'_>,
+    ) -> Result<Option<()>, Self::Error> {
+        Ok(None)
+    }
+
+    /// Handle a polling request (`pollReq`), responded to with a `pollRep`.
+    ///
+    /// Note that a `pollReq` for a request which is now complete must instead
+    /// be answered with the final response, which this trait does not
+    /// support.
+    fn poll_req<'a>(
+        &mut self,
+        header: &PkiHeader<'a>,
+        content: &PollReqContent,
+    ) -> Result<Option<PollRepContent<'a>>, Self::Error> {
+        Ok(None)
+    }
+
+    /// Handle an error message (`error`), responded to with a `pkiconf`.
+    fn error(
+        &mut self,
+        header: &PkiHeader<'_>,
+        content: &ErrorMsgContent<'_>,
+    ) -> Result<Option<()>, Self::Error> {
+        Ok(None)
+    }
+}
+
+/// Handle a request using the given [`MessageHandler`], and build the
+/// response.
+///
+/// The header of the response is derived from the header of the request:
+/// - `pvno` and `transactionID` are copied from the request,
+/// - `sender` and `recipient` are swapped,
+/// - `recipKID` is the `senderKID` of the request,
+/// - `recipNonce` is the `senderNonce` of the request,
+/// - `senderNonce` is set to the given (fresh) `sender_nonce`.
+///
+/// Other fields, e.g. `messageTime`, `protectionAlg` and `generalInfo`, are
+/// left unset, as is the `protection` of the response. These should be
+/// populated by the caller as needed before sending the response.
+pub fn respond<'a, H: MessageHandler>(
+    handler: &mut H,
+    request: &PkiMessage<'a>,
+    sender_nonce: OctetString,
+) -> Result<PkiMessage<'a>, H::Error> {
+    let header = &request.header;
+
+    let body = match &request.body {
+        PkiBody::Ir(content) => handler.ir(header, content)?.map(PkiBody::Ip),
+        PkiBody::Cr(content) => handler.cr(header, content)?.map(PkiBody::Cp),
+        PkiBody::P10cr(content) => handler.p10cr(header, content)?.map(PkiBody::Cp),
+        PkiBody::Kur(content) => handler.kur(header, content)?.map(PkiBody::Kup),
+        PkiBody::Ccr(content) => handler.ccr(header, content)?.map(|content| {
+            ccp(content).unwrap_or_else(|_| error_body(PkiFailureInfoValues::SystemFailure))
+        }),
+        PkiBody::Rr(content) => handler.rr(header, content)?.map(PkiBody::Rp),
+        PkiBody::GenM(content) => handler.genm(header, content)?.map(PkiBody::GenP),
+        PkiBody::CertConf(content) => handler
+            .cert_conf(header, content)?
+            .map(|()| PkiBody::PkiConf(Null)),
+        PkiBody::PollReq(content) => handler.poll_req(header, content)?.map(PkiBody::PollRep),
+        PkiBody::Error(content) => handler
+            .error(header, content)?
+            .map(|()| PkiBody::PkiConf(Null)),
+        _ => None,
+    };
+
+    Ok(PkiMessage {
+        header: response_header(header, sender_nonce),
+        body: body.unwrap_or_else(|| error_body(PkiFailureInfoValues::BadRequest)),
+        protection: None,
+        extra_certs: None,
+    })
+}
+
+/// Build the header of a response to a request with the given header.
+///
+/// See [`respond`] for how the fields are populated.
+pub fn response_header<'a>(request: &PkiHeader<'a>, sender_nonce: OctetString) -> PkiHeader<'a> {
+    PkiHeader {
+        pvno: request.pvno,
+        sender: request.recipient.clone(),
+        recipient: request.sender.clone(),
+        message_time: None,
+        protection_alg: None,
+        sender_kid: None,
+        recip_kid: request.sender_kid.clone(),
+        trans_id: request.trans_id.clone(),
+        sender_nonce: Some(sender_nonce),
+        recip_nonce: request.sender_nonce.clone(),
+        free_text: None,
+        general_info: None,
+    }
+}
+
+/// Body of an `error` response with the given failure info, e.g. for an
+/// unsupported request.
+fn error_body<'a>(fail_info: PkiFailureInfoValues) -> PkiBody<'a> {
+    PkiBody::Error(ErrorMsgContent {
+        pki_status_info: PkiStatusInfo {
+            status: PkiStatus::Rejection,
+            status_string: None,
+            fail_info: Some(fail_info.into()),
+        },
+        error_code: None,
+        error_details: None,
+    })
+}
@@ -2,7 +2,7 @@
 
 use crate::header::PkiFreeText;
 use alloc::vec::Vec;
-use der::asn1::{Int, OctetString};
+use der::asn1::{Int, OctetString, Utf8StringRef};
 use der::flagset::{FlagSet, flags};
 use der::{Enumerated, Sequence};
 
@@ -123,33 +123,33 @@ flags! {
     /// [RFC 4210 Section 5.2.3]: https://www.rfc-editor.org/rfc/rfc4210#section-5.2.3
     #[allow(missing_docs)]
     pub enum PkiFailureInfoValues: u32 {
-        BadAlg = 0,
-        BadMessageCheck = 1,
-        BadRequest      = 2,
-        BadTime         = 3,
-        BadCertId       = 4,
-        BadDataFormat   = 5,
-        WrongAuthority  = 6,
-        IncorrectData   = 7,
-        MissingTimeStamp = 8,
-        BadPOP           = 9,
-        CertRevoked      = 10,
-        CertConfirmed    = 11,
-        WrongIntegrity   = 12,
-        BadRecipientNonce = 13,
-        TimeNotAvailable  = 14,
-        UnacceptedPolicy  = 15,
-        UnacceptedExtension = 16,
-        AddInfoNotAvailable = 17,
-        BadSenderNonce      = 18,
-        BadCertTemplate     = 19,
-        SignerNotTrusted    = 20,
-        TransactionIdInUse  = 21,
-        UnsupportedVersion  = 22,
-        NotAuthorized       = 23,
-        SystemUnavail       = 24,
-        SystemFailure       = 25,
-        DuplicateCertReq    = 26,
+        BadAlg = 1 << 0,
+        BadMessageCheck = 1 << 1,
+        BadRequest = 1 << 2,
+        BadTime = 1 << 3,
+        BadCertId = 1 << 4,
+        BadDataFormat = 1 << 5,
+        WrongAuthority = 1 << 6,
+        IncorrectData = 1 << 7,
+        MissingTimeStamp = 1 << 8,
+        BadPOP = 1 << 9,
+        CertRevoked = 1 << 10,
+        CertConfirmed = 1 << 11,
+        WrongIntegrity = 1 << 12,
+        BadRecipientNonce = 1 << 13,
+        TimeNotAvailable = 1 << 14,
+        UnacceptedPolicy = 1 << 15,
+        UnacceptedExtension = 1 << 16,
+        AddInfoNotAvailable = 1 << 17,
+        BadSenderNonce = 1 << 18,
+        BadCertTemplate = 1 << 19,
+        SignerNotTrusted = 1 << 20,
+        TransactionIdInUse = 1 << 21,
+        UnsupportedVersion = 1 << 22,
+        NotAuthorized = 1 << 23,
+        SystemUnavail = 1 << 24,
+        SystemFailure = 1 << 25,
+        DuplicateCertReq = 1 << 26,
     }
 }
 
@@ -177,6 +177,36 @@ pub struct PkiStatusInfo<'a> {
     pub fail_info: Option<PkiFailureInfo>,
 }
 
+impl<'a> PkiStatusInfo<'a> {
+    /// Create a new [`PkiStatusInfo`] with the given status, failure
+    /// information and optional free text.
+    ///
+    /// `failInfo` is omitted if `fail_info` is empty.
+    pub fn new(
+        status: PkiStatus,
+        fail_info: impl Into<PkiFailureInfo>,
+        text: Option<&'a str>,
+    ) -> der::Result<Self> {
+        let fail_info = fail_info.into();
+
+        Ok(Self {
+            status,
+            status_string: text.map(free_text).transpose()?,
+            fail_info: (!fail_info.is_empty()).then_some(fail_info),
+        })
+    }
+
+    /// Get the failure information, which is empty if `failInfo` is absent.
+    pub fn failure_info(&self) -> PkiFailureInfo {
+        self.fail_info.unwrap_or_default()
+    }
+
+    /// Get the strings of `statusString`, if any.
+    pub fn status_text(&self) -> impl Iterator<Item = &'a str> {
+        free_text_strs(&self.status_string)
+    }
+}
+
 /// The `ErrorMsgContent` type is defined in [RFC 4210 Section 5.2.21].
 ///
 /// ```text
@@ -198,6 +228,82 @@ pub struct ErrorMsgContent<'a> {
     pub error_details: Option<PkiFreeText<'a>>,
 }
 
+impl<'a> ErrorMsgContent<'a> {
+    /// Create a new [`ErrorMsgContent`] reporting the given status, failure
+    /// information and optional free text, e.g.:
+    ///
+    /// ```
+    /// use cmpv2::status::{ErrorMsgContent, PkiFailureInfoValues, PkiStatus};
+    ///
+    /// let error = ErrorMsgContent::new(
+    ///     PkiStatus::Rejection,
+    ///     PkiFailureInfoValues::BadPOP | PkiFailureInfoValues::BadCertTemplate,
+    ///     Some("proof of possession failed"),
+    /// )?
+    /// .with_error_code(42);
+    ///
+    /// assert!(error.failure_info().contains(PkiFailureInfoValues::BadPOP));
+    /// assert!(error.status_text().eq(["proof of possession failed"]));
+    /// # Ok::<(), der::Error>(())
+    /// ```
+    ///
+    /// The text is used as the `statusString` of the `pKIStatusInfo`.
+    pub fn new(
+        status: PkiStatus,
+        fail_info: impl Into<PkiFailureInfo>,
+        text: Option<&'a str>,
+    ) -> der::Result<Self> {
+        Ok(Self {
+            pki_status_info: PkiStatusInfo::new(status, fail_info, text)?,
+            error_code: None,
+            error_details: None,
+        })
+    }
+
+    /// Set the implementation-specific `errorCode`.
+    pub fn with_error_code(mut self, error_code: u64) -> Self {
+        self.error_code = Some(error_code);
+        self
+    }
+
+    /// Set the implementation-specific `errorDetails` to the given text.
+    pub fn with_error_details(mut self, details: &'a str) -> der::Result<Self> {
+        self.error_details = Some(free_text(details)?);
+        Ok(self)
+    }
+
+    /// Get the status of the `pKIStatusInfo`.
+    pub fn status(&self) -> PkiStatus {
+        self.pki_status_info.status
+    }
+
+    /// Get the failure information of the `pKIStatusInfo`, which is empty if
+    /// `failInfo` is absent.
+    pub fn failure_info(&self) -> PkiFailureInfo {
+        self.pki_status_info.failure_info()
+    }
+
+    /// Get the strings of the `statusString` of the `pKIStatusInfo`, if any.
+    pub fn status_text(&self) -> impl Iterator<Item = &'a str> {
+        self.pki_status_info.status_text()
+    }
+
+    /// Get the strings of `errorDetails`, if any.
+    pub fn error_details_text(&self) -> impl Iterator<Item = &'a str> {
+        free_text_strs(&self.error_details)
+    }
+}
+
+/// Create a [`PkiFreeText`] containing the given text.
+fn free_text(text: &str) -> der::Result<PkiFreeText<'_>> {
+    Ok(alloc::vec![Utf8StringRef::new(text)?])
+}
+
+/// Iterate over the strings of an optional [`PkiFreeText`].
+fn free_text_strs<'a>(text: &Option<PkiFreeText<'a>>) -> impl Iterator<Item = &'a str> {
+    text.iter().flatten().map(Utf8StringRef::as_str)
+}
+
 /// The `CertConfirmContent` type is defined in [RFC 4210 Section 5.2.18].
 ///
 /// ```text
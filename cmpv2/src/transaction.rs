@@ -0,0 +1,191 @@
+//! Client transaction state persistence.
+//!
+//! Some CMP exchanges can span hours or days, e.g. when a device has to wait
+//! for an enrollment request to be manually approved. The types in this
+//! module allow a client to snapshot the state of such a transaction, persist
+//! it through a [`TransactionStore`], and resume the transaction after a
+//! restart rather than starting over.
+
+use alloc::vec::Vec;
+use core::fmt;
+use der::asn1::{GeneralizedTime, Int, OctetString};
+use der::{Decode, Encode, Sequence};
+
+use crate::header::PkiHeader;
+
+/// Snapshot of the client state of an in-progress CMP transaction.
+///
+/// Snapshots are serialized using DER, with the following schema:
+///
+/// ```text
+/// TransactionSnapshot ::= SEQUENCE {
+///     transactionID       OCTET STRING,
+///     senderNonce     [0] OCTET STRING OPTIONAL,
+///     -- senderNonce of the last request sent by the client
+///     recipNonce      [1] OCTET STRING OPTIONAL,
+///     -- senderNonce of the last response received by the client
+///     pendingCertConf     SEQUENCE OF PendingCertConf
+/// }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+#[allow(missing_docs)]
+pub struct TransactionSnapshot {
+    pub transaction_id: OctetString,
+    #[asn1(
+        context_specific = "0",
+        tag_mode = "EXPLICIT",
+        constructed = "false",
+        optional = "true"
+    )]
+    pub sender_nonce: Option<OctetString>,
+    #[asn1(
+        context_specific = "1",
+        tag_mode = "EXPLICIT",
+        constructed = "false",
+        optional = "true"
+    )]
+    pub recip_nonce: Option<OctetString>,
+    pub pending_cert_conf: Vec<PendingCertConf>,
+}
+
+impl TransactionSnapshot {
+    /// Create a snapshot for a new transaction with the given `transactionID`.
+    pub fn new(transaction_id: OctetString) -> Self {
+        Self {
+            transaction_id,
+            sender_nonce: None,
+            recip_nonce: None,
+            pending_cert_conf: Vec::new(),
+        }
+    }
+
+    /// Populate the `transactionID` and `recipNonce` of the header of the next
+    /// request in this transaction, and record its (fresh) `senderNonce`.
+    pub fn prepare_request(&mut self, header: &mut PkiHeader<'_>, sender_nonce: OctetString) {
+        header.trans_id = Some(self.transaction_id.clone());
+        header.recip_nonce = self.recip_nonce.clone();
+        header.sender_nonce = Some(sender_nonce.clone());
+        self.sender_nonce = Some(sender_nonce);
+    }
+
+    /// Record the header of a response received in this transaction, checking
+    /// its `transactionID` and that its `recipNonce` matches the `senderNonce`
+    /// of the last request.
+    pub fn record_response(&mut self, header: &PkiHeader<'_>) -> Result<(), TransactionError> {
+        if header.trans_id.as_ref() != Some(&self.transaction_id) {
+            return Err(TransactionError::TransactionId);
+        }
+
+        if header.recip_nonce != self.sender_nonce {
+            return Err(TransactionError::RecipNonce);
+        }
+
+        self.recip_nonce.clone_from(&header.sender_nonce);
+        Ok(())
+    }
+
+    /// Record that a `certConf` for the given certificate request must be sent
+    /// before the given deadline.
+    pub fn expect_cert_conf(&mut self, cert_req_id: Int, deadline: GeneralizedTime) {
+        self.pending_cert_conf
+            .retain(|pending| pending.cert_req_id != cert_req_id);
+        self.pending_cert_conf.push(PendingCertConf {
+            cert_req_id,
+            deadline,
+        });
+    }
+
+    /// Record that a `certConf` was sent for the given certificate request.
+    ///
+    /// Returns `false` if no `certConf` was pending for the request.
+    pub fn cert_confirmed(&mut self, cert_req_id: &Int) -> bool {
+        let len = self.pending_cert_conf.len();
+        self.pending_cert_conf
+            .retain(|pending| &pending.cert_req_id != cert_req_id);
+        self.pending_cert_conf.len() != len
+    }
+
+    /// Iterate over the pending `certConf`s whose deadline has passed at the
+    /// given time.
+    pub fn expired_cert_confs(
+        &self,
+        now: GeneralizedTime,
+    ) -> impl Iterator<Item = &PendingCertConf> {
+        self.pending_cert_conf
+            .iter()
+            .filter(move |pending| pending.deadline < now)
+    }
+
+    /// Save this snapshot using the given [`TransactionStore`].
+    pub fn save<S: TransactionStore>(&self, store: &mut S) -> Result<(), S::Error> {
+        store.save(self.transaction_id.as_bytes(), &self.to_der()?)
+    }
+
+    /// Restore the snapshot of the transaction with the given `transactionID`
+    /// from the given [`TransactionStore`], if present.
+    pub fn restore<S: TransactionStore>(
+        store: &mut S,
+        transaction_id: &[u8],
+    ) -> Result<Option<Self>, S::Error> {
+        match store.restore(transaction_id)? {
+            Some(der) => Ok(Some(Self::from_der(&der)?)),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Certificate confirmation which is pending for a certificate request.
+///
+/// ```text
+/// PendingCertConf ::= SEQUENCE {
+///     certReqId       INTEGER,
+///     deadline        GeneralizedTime
+/// }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+#[allow(missing_docs)]
+pub struct PendingCertConf {
+    pub cert_req_id: Int,
+    pub deadline: GeneralizedTime,
+}
+
+/// Callbacks for persisting [`TransactionSnapshot`]s, e.g. to flash storage
+/// or a database.
+///
+/// Snapshots are passed to the store in their DER-serialized form, keyed by
+/// their `transactionID`.
+pub trait TransactionStore {
+    /// Error type, which must be able to represent snapshot
+    /// (de)serialization errors.
+    type Error: From<der::Error>;
+
+    /// Save the serialized snapshot of the transaction with the given ID,
+    /// replacing any previously saved snapshot.
+    fn save(&mut self, transaction_id: &[u8], snapshot: &[u8]) -> Result<(), Self::Error>;
+
+    /// Restore the serialized snapshot of the transaction with the given ID.
+    fn restore(&mut self, transaction_id: &[u8]) -> Result<Option<Vec<u8>>, Self::Error>;
+}
+
+/// Error recording a message in a [`TransactionSnapshot`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum TransactionError {
+    /// The `transactionID` of the message doesn't match the transaction.
+    TransactionId,
+
+    /// The `recipNonce` of the message doesn't match the `senderNonce` of the
+    /// last request.
+    RecipNonce,
+}
+
+impl fmt::Display for TransactionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TransactionId => f.write_str("transactionID mismatch"),
+            Self::RecipNonce => f.write_str("recipNonce does not match last senderNonce"),
+        }
+    }
+}
+
+impl core::error::Error for TransactionError {}
@@ -0,0 +1,293 @@
+//! HTTP transfer of CMP messages, as described in [RFC 6712].
+//!
+//! CMP messages are sent as the body of HTTP `POST` requests and of their
+//! `200 OK` responses, with the `application/pkixcmp` content type. The body
+//! is the DER encoding of a single [`PkiMessage`], framed by the
+//! `Content-Length` header.
+//!
+//! This module is sans-I/O: [`HttpRequest`] and [`HttpResponse`] provide the
+//! method, headers and body to be passed to the HTTP client or server of the
+//! application, and [`decode_request`] and [`decode_response`] check the
+//! received HTTP messages before decoding their body.
+//!
+//! Blocking clients sending [`HttpRequest`]s and returning a
+//! [`ReceivedResponse`] are provided for [`ureq`](::ureq) and
+//! [`reqwest`](::reqwest) with the `ureq` and `reqwest` features:
+//!
+//! ```no_run
+//! # #[cfg(feature = "ureq")]
+//! # fn example(message: &cmpv2::message::PkiMessage<'_>) -> Result<(), Box<dyn std::error::Error>> {
+//! use cmpv2::transport::{self, HttpRequest};
+//!
+//! let agent = ureq::Agent::new_with_defaults();
+//! let request = HttpRequest::new("/.well-known/cmp", message)?;
+//! let response = transport::ureq::send(&agent, "http://ca.example.com", &request)?;
+//! let reply = response.decode()?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [RFC 6712]: https://www.rfc-editor.org/rfc/rfc6712
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
+use der::{Decode, Encode};
+
+use crate::message::PkiMessage;
+
+#[cfg(feature = "reqwest")]
+pub mod reqwest;
+#[cfg(feature = "ureq")]
+pub mod ureq;
+
+/// Media type of CMP messages, as registered in [RFC 6712 Section 3.4].
+///
+/// [RFC 6712 Section 3.4]: https://www.rfc-editor.org/rfc/rfc6712#section-3.4
+pub const CONTENT_TYPE: &str = "application/pkixcmp";
+
+/// HTTP method of the requests carrying CMP messages.
+pub const METHOD: &str = "POST";
+
+/// HTTP status code of the responses carrying CMP messages.
+pub const STATUS_OK: u16 = 200;
+
+/// HTTP request carrying a CMP request message, to be sent by a client.
+///
+/// ```
+/// # fn example(message: &cmpv2::message::PkiMessage<'_>) -> der::Result<()> {
+/// use cmpv2::transport::{CONTENT_TYPE, HttpRequest, METHOD};
+///
+/// let request = HttpRequest::new("/.well-known/cmp", message)?;
+/// assert_eq!(request.method(), METHOD);
+/// assert_eq!(request.headers()[0], ("Content-Type", CONTENT_TYPE.into()));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HttpRequest {
+    path: String,
+    body: Vec<u8>,
+}
+
+impl HttpRequest {
+    /// Create a request sending the given message to the given path of the
+    /// server, e.g. `/` or `/.well-known/cmp`.
+    pub fn new(path: impl Into<String>, message: &PkiMessage<'_>) -> der::Result<Self> {
+        Ok(Self {
+            path: path.into(),
+            body: message.to_der()?,
+        })
+    }
+
+    /// Get the HTTP method of the request, i.e. [`METHOD`].
+    pub fn method(&self) -> &'static str {
+        METHOD
+    }
+
+    /// Get the path the request is sent to.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Get the `Content-Type` and `Content-Length` headers of the request.
+    pub fn headers(&self) -> [(&'static str, String); 2] {
+        headers(&self.body)
+    }
+
+    /// Get the body of the request, i.e. the DER encoding of the message.
+    pub fn body(&self) -> &[u8] {
+        &self.body
+    }
+
+    /// Take the body of the request.
+    pub fn into_body(self) -> Vec<u8> {
+        self.body
+    }
+}
+
+/// HTTP response carrying a CMP response message, to be sent by a server.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HttpResponse {
+    body: Vec<u8>,
+}
+
+impl HttpResponse {
+    /// Create a response sending the given message.
+    pub fn new(message: &PkiMessage<'_>) -> der::Result<Self> {
+        Ok(Self {
+            body: message.to_der()?,
+        })
+    }
+
+    /// Get the HTTP status code of the response, i.e. [`STATUS_OK`].
+    ///
+    /// CMP-level errors are reported in `error` messages, which are sent with
+    /// this status code as well.
+    pub fn status(&self) -> u16 {
+        STATUS_OK
+    }
+
+    /// Get the `Content-Type` and `Content-Length` headers of the response.
+    pub fn headers(&self) -> [(&'static str, String); 2] {
+        headers(&self.body)
+    }
+
+    /// Get the body of the response, i.e. the DER encoding of the message.
+    pub fn body(&self) -> &[u8] {
+        &self.body
+    }
+
+    /// Take the body of the response.
+    pub fn into_body(self) -> Vec<u8> {
+        self.body
+    }
+}
+
+/// HTTP response received by a client, e.g. using one of the provided
+/// clients, holding the body until the CMP message it carries is decoded.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReceivedResponse {
+    status: u16,
+    content_type: Option<String>,
+    body: Vec<u8>,
+}
+
+impl ReceivedResponse {
+    /// Create a response with the given status code, `Content-Type` header
+    /// (if present) and body.
+    pub fn new(status: u16, content_type: Option<String>, body: Vec<u8>) -> Self {
+        Self {
+            status,
+            content_type,
+            body,
+        }
+    }
+
+    /// Get the HTTP status code of the response.
+    pub fn status(&self) -> u16 {
+        self.status
+    }
+
+    /// Get the value of the `Content-Type` header of the response, if present.
+    pub fn content_type(&self) -> Option<&str> {
+        self.content_type.as_deref()
+    }
+
+    /// Get the body of the response.
+    pub fn body(&self) -> &[u8] {
+        &self.body
+    }
+
+    /// Decode the CMP message carried by this response, see
+    /// [`decode_response`].
+    pub fn decode(&self) -> Result<PkiMessage<'_>, TransportError> {
+        decode_response(self.status, self.content_type(), &self.body)
+    }
+}
+
+/// Decode the CMP message carried by an HTTP request received by a server.
+///
+/// `content_type` is the value of the `Content-Type` header of the request, if
+/// present.
+///
+/// # Errors
+/// - [`TransportError::Method`] if the method isn't `POST`
+/// - [`TransportError::ContentType`] if the content type isn't `application/pkixcmp`
+/// - [`TransportError::Der`] if the body isn't a DER-encoded `PKIMessage`
+pub fn decode_request<'a>(
+    method: &str,
+    content_type: Option<&str>,
+    body: &'a [u8],
+) -> Result<PkiMessage<'a>, TransportError> {
+    if method != METHOD {
+        return Err(TransportError::Method);
+    }
+
+    decode_body(content_type, body)
+}
+
+/// Decode the CMP message carried by an HTTP response received by a client.
+///
+/// `content_type` is the value of the `Content-Type` header of the response,
+/// if present.
+///
+/// # Errors
+/// - [`TransportError::Status`] if the status code isn't `200`
+/// - [`TransportError::ContentType`] if the content type isn't `application/pkixcmp`
+/// - [`TransportError::Der`] if the body isn't a DER-encoded `PKIMessage`
+pub fn decode_response<'a>(
+    status: u16,
+    content_type: Option<&str>,
+    body: &'a [u8],
+) -> Result<PkiMessage<'a>, TransportError> {
+    if status != STATUS_OK {
+        return Err(TransportError::Status(status));
+    }
+
+    decode_body(content_type, body)
+}
+
+/// Check whether the value of a `Content-Type` header is [`CONTENT_TYPE`].
+///
+/// Media types are case-insensitive, and parameters are ignored.
+pub fn is_cmp_content_type(content_type: &str) -> bool {
+    let media_type = content_type.split(';').next().unwrap_or_default();
+    media_type.trim().eq_ignore_ascii_case(CONTENT_TYPE)
+}
+
+fn decode_body<'a>(
+    content_type: Option<&str>,
+    body: &'a [u8],
+) -> Result<PkiMessage<'a>, TransportError> {
+    if !content_type.is_some_and(is_cmp_content_type) {
+        return Err(TransportError::ContentType);
+    }
+
+    Ok(PkiMessage::from_der(body)?)
+}
+
+fn headers(body: &[u8]) -> [(&'static str, String); 2] {
+    [
+        ("Content-Type", CONTENT_TYPE.to_string()),
+        ("Content-Length", body.len().to_string()),
+    ]
+}
+
+/// Error checking an HTTP message carrying a CMP message.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum TransportError {
+    /// The request method isn't `POST`.
+    Method,
+
+    /// The response status code isn't `200`.
+    Status(u16),
+
+    /// The `Content-Type` header is missing or isn't `application/pkixcmp`.
+    ContentType,
+
+    /// The body isn't a DER-encoded `PKIMessage`.
+    Der(der::Error),
+}
+
+impl fmt::Display for TransportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Method => write!(f, "CMP request method is not {METHOD}"),
+            Self::Status(status) => write!(f, "unexpected HTTP status code {status}"),
+            Self::ContentType => write!(f, "content type is not {CONTENT_TYPE}"),
+            Self::Der(err) => write!(f, "error decoding CMP message: {err}"),
+        }
+    }
+}
+
+impl core::error::Error for TransportError {}
+
+impl From<der::Error> for TransportError {
+    fn from(err: der::Error) -> Self {
+        Self::Der(err)
+    }
+}
@@ -0,0 +1,32 @@
+//! Blocking HTTP client using [`reqwest`].
+
+use alloc::{format, string::String};
+
+use super::{CONTENT_TYPE, HttpRequest, ReceivedResponse};
+
+/// Send the given request to the CMP server at the given base URL, e.g.
+/// `https://ca.example.com`, to which the path of the request is appended.
+pub fn send(
+    client: &::reqwest::blocking::Client,
+    server: &str,
+    request: &HttpRequest,
+) -> Result<ReceivedResponse, ::reqwest::Error> {
+    let response = client
+        .post(format!("{server}{}", request.path()))
+        .header(::reqwest::header::CONTENT_TYPE, CONTENT_TYPE)
+        .body(request.body().to_vec())
+        .send()?;
+
+    let status = response.status().as_u16();
+    let content_type = response
+        .headers()
+        .get(::reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(String::from);
+
+    Ok(ReceivedResponse {
+        status,
+        content_type,
+        body: response.bytes()?.to_vec(),
+    })
+}
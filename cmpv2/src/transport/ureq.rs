@@ -0,0 +1,36 @@
+//! Blocking HTTP client using [`ureq`].
+
+use alloc::{format, string::String};
+
+use super::{CONTENT_TYPE, HttpRequest, ReceivedResponse};
+
+/// Send the given request to the CMP server at the given base URL, e.g.
+/// `https://ca.example.com`, to which the path of the request is appended.
+///
+/// Unexpected status codes aren't reported as errors here, but by
+/// [`ReceivedResponse::decode`], like for the other clients.
+pub fn send(
+    agent: &::ureq::Agent,
+    server: &str,
+    request: &HttpRequest,
+) -> Result<ReceivedResponse, ::ureq::Error> {
+    let mut response = agent
+        .post(format!("{server}{}", request.path()))
+        .config()
+        .http_status_as_error(false)
+        .build()
+        .header("Content-Type", CONTENT_TYPE)
+        .send(request.body())?;
+
+    let content_type = response
+        .headers()
+        .get("Content-Type")
+        .and_then(|value| value.to_str().ok())
+        .map(String::from);
+
+    Ok(ReceivedResponse {
+        status: response.status().as_u16(),
+        content_type,
+        body: response.body_mut().read_to_vec()?,
+    })
+}
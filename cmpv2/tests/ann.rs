@@ -0,0 +1,130 @@
+//! Announcement tests
+
+use cmpv2::ann::{AnnError, Announcement, CaKeyUpdAnnContent, RevAnnContent};
+use cmpv2::body::PkiBody;
+use cmpv2::certified_key_pair::CertOrEncCert;
+use cmpv2::header::CmpCertificate;
+use cmpv2::message::PkiMessage;
+use cmpv2::status::PkiStatus;
+use crmf::controls::CertId;
+use der::asn1::GeneralizedTime;
+use der::{Decode, Encode};
+use x509_cert::crl::CertificateList;
+use x509_cert::serial_number::SerialNumber;
+
+/// Get a self-signed certificate, and a certificate which isn't self-issued
+/// from the example `cp`.
+fn certs() -> (CmpCertificate, CmpCertificate) {
+    // openssl x509 -in ec384-server-key.crt -outform DER -out ec384-server-cert.der
+    let ca = CmpCertificate::from_der(include_bytes!("examples/ec384-server-cert.der")).unwrap();
+
+    let PkiBody::Cp(rep) = PkiMessage::from_der(include_bytes!("examples/cr_rsp_01.bin"))
+        .unwrap()
+        .body
+    else {
+        unreachable!()
+    };
+
+    let key_pair = rep.response[0].certified_key_pair.clone().unwrap();
+    let CertOrEncCert::Certificate(ee) = key_pair.cert_or_enc_cert else {
+        unreachable!()
+    };
+
+    (ca, *ee)
+}
+
+fn crl() -> CertificateList {
+    CertificateList::from_der(include_bytes!(
+        "../../x509-cert/tests/examples/GoodCACRL.crl"
+    ))
+    .unwrap()
+}
+
+#[test]
+fn cann() {
+    let (ca, _) = certs();
+    let body = PkiBody::Cann(ca.clone());
+
+    let ann = body.announcement().unwrap();
+    assert_eq!(ann, Announcement::Cert(&ca));
+    assert_eq!(ann.certificates(), [&ca]);
+    assert_eq!(ann.certificates_der().unwrap(), [ca.to_der().unwrap()]);
+    assert!(ann.crls().is_empty());
+    assert!(ann.validate().is_ok());
+
+    assert!(PkiBody::PkiConf(der::asn1::Null).announcement().is_none());
+}
+
+#[test]
+fn ckuann() {
+    let (ca, ee) = certs();
+    let mut content = CaKeyUpdAnnContent {
+        old_with_new: Box::new(ca.clone()),
+        new_with_old: Box::new(ca.clone()),
+        new_with_new: Box::new(ca.clone()),
+    };
+
+    let body = PkiBody::Ckuann(content.clone());
+    let ann = body.announcement().unwrap();
+    assert_eq!(ann.certificates().len(), 3);
+    assert_eq!(ann.certificates_der().unwrap()[2], ca.to_der().unwrap());
+    assert!(ann.validate().is_ok());
+
+    *content.new_with_old = ee;
+    assert_eq!(
+        Announcement::CaKeyUpdate(&content).validate(),
+        Err(AnnError::NotSelfIssued)
+    );
+}
+
+#[test]
+fn crlann() {
+    let crl = crl();
+    let body = PkiBody::CrlAnn(vec![crl.clone()]);
+
+    let ann = body.announcement().unwrap();
+    assert!(ann.certificates().is_empty());
+    assert_eq!(ann.crls(), core::slice::from_ref(&crl));
+    assert_eq!(
+        ann.crls_der().unwrap(),
+        [include_bytes!("../../x509-cert/tests/examples/GoodCACRL.crl").to_vec()]
+    );
+    assert!(ann.validate().is_ok());
+
+    assert_eq!(
+        Announcement::Crl(&Vec::new()).validate(),
+        Err(AnnError::NoCrl)
+    );
+}
+
+#[test]
+fn rann() {
+    let (ca, ee) = certs();
+    let time = GeneralizedTime::from_unix_duration(core::time::Duration::from_secs(1_700_000_000))
+        .unwrap();
+    let mut content = RevAnnContent {
+        status: PkiStatus::RevocationWarning,
+        cert_id: CertId {
+            issuer: x509_cert::ext::pkix::name::GeneralName::DirectoryName(
+                ca.tbs_certificate().subject().clone(),
+            ),
+            serial_number: SerialNumber::new(ee.tbs_certificate().serial_number().as_bytes())
+                .unwrap(),
+        },
+        will_be_revoked_at: time,
+        bad_since_date: time,
+        crl_details: None,
+    };
+
+    let body = PkiBody::Rann(content.clone());
+    let ann = body.announcement().unwrap();
+    assert!(ann.certificates().is_empty());
+    assert!(ann.crls().is_empty());
+    assert!(ann.validate().is_ok());
+
+    content.status = PkiStatus::Accepted;
+    assert_eq!(
+        Announcement::Revocation(&content).validate(),
+        Err(AnnError::RevocationStatus(PkiStatus::Accepted))
+    );
+}
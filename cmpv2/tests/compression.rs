@@ -0,0 +1,147 @@
+//! Message compression tests
+
+#![cfg(feature = "compression")]
+
+use cmpv2::compression::{CompressionConfig, CompressionError, decode_payload};
+use cmpv2::message::PkiMessage;
+use der::asn1::ObjectIdentifier;
+use der::{Decode, Encode};
+use spki::AlgorithmIdentifierOwned;
+
+/// Info type under which support for compression is advertised in tests.
+const INFO_TYPE: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.6.1.4.1.32473.3");
+
+/// `id-alg-zlibCompress` from RFC 3274.
+const ZLIB: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.9.16.3.8");
+
+/// Compressed data produced by the fake compression function.
+const COMPRESSED: &[u8] = &[0xAB; 4];
+
+fn alg(oid: ObjectIdentifier) -> AlgorithmIdentifierOwned {
+    AlgorithmIdentifierOwned {
+        oid,
+        parameters: None,
+    }
+}
+
+fn config(algorithms: Vec<AlgorithmIdentifierOwned>, min_len: usize) -> CompressionConfig {
+    CompressionConfig {
+        info_type: INFO_TYPE,
+        algorithms,
+        min_len,
+    }
+}
+
+#[test]
+fn negotiate() {
+    let mut peer = PkiMessage::from_der(include_bytes!("examples/cr_req_01.bin")).unwrap();
+    let other = alg(ObjectIdentifier::new_unwrap("1.3.6.1.4.1.32473.4"));
+    let ours = config(vec![other.clone(), alg(ZLIB)], 0);
+
+    // Peer didn't advertise support
+    assert_eq!(ours.negotiate(&peer), Ok(None));
+
+    config(vec![alg(ZLIB)], 0)
+        .advertise(&mut peer.header)
+        .unwrap();
+    let peer_der = peer.to_der().unwrap();
+    let peer = PkiMessage::from_der(&peer_der).unwrap();
+    assert_eq!(
+        peer.compression_support(INFO_TYPE).unwrap(),
+        Some(vec![alg(ZLIB)])
+    );
+    assert_eq!(ours.negotiate(&peer), Ok(Some(&alg(ZLIB))));
+
+    // No algorithm in common
+    assert_eq!(config(vec![other], 0).negotiate(&peer), Ok(None));
+}
+
+#[test]
+fn advertise_replaces_previous() {
+    let mut message = PkiMessage::from_der(include_bytes!("examples/cr_req_01.bin")).unwrap();
+    config(vec![alg(ZLIB)], 0)
+        .advertise(&mut message.header)
+        .unwrap();
+    config(vec![], 0).advertise(&mut message.header).unwrap();
+    assert_eq!(
+        message.compression_support(INFO_TYPE).unwrap(),
+        Some(vec![])
+    );
+
+    let general_info = message.header.general_info.as_mut().unwrap();
+    general_info.push(general_info.last().unwrap().clone());
+    assert_eq!(
+        message.compression_support(INFO_TYPE),
+        Err(CompressionError::Malformed)
+    );
+}
+
+#[test]
+fn round_trip() {
+    let message = PkiMessage::from_der(include_bytes!("examples/cr_rsp_01.bin")).unwrap();
+    let message_der = message.to_der().unwrap();
+    let config = config(vec![alg(ZLIB)], 64);
+
+    let payload = config
+        .encode_payload(&message, Some(&alg(ZLIB)), |algorithm, data| {
+            assert_eq!(algorithm, &alg(ZLIB));
+            assert_eq!(data, message_der);
+            Ok::<_, CompressionError>(COMPRESSED.to_vec())
+        })
+        .unwrap();
+    assert!(payload.len() < message_der.len());
+
+    let decoded = decode_payload(&payload, |algorithm, data| {
+        assert_eq!(algorithm, &alg(ZLIB));
+        assert_eq!(data, COMPRESSED);
+        Ok::<_, CompressionError>(message_der.clone())
+    })
+    .unwrap();
+    assert_eq!(decoded.as_ref(), message_der);
+    assert_eq!(PkiMessage::from_der(&decoded).unwrap(), message);
+}
+
+#[test]
+fn uncompressed() {
+    let message = PkiMessage::from_der(include_bytes!("examples/cr_rsp_01.bin")).unwrap();
+    let message_der = message.to_der().unwrap();
+    let no_compress =
+        |_: &AlgorithmIdentifierOwned, _: &[u8]| -> Result<Vec<u8>, CompressionError> {
+            panic!("unexpected compression")
+        };
+
+    // No algorithm negotiated
+    let payload = config(vec![alg(ZLIB)], 0)
+        .encode_payload(&message, None, no_compress)
+        .unwrap();
+    assert_eq!(payload, message_der);
+
+    // Message below the threshold
+    let payload = config(vec![alg(ZLIB)], message_der.len() + 1)
+        .encode_payload(&message, Some(&alg(ZLIB)), no_compress)
+        .unwrap();
+    assert_eq!(payload, message_der);
+
+    // Compression doesn't reduce the length
+    let payload = config(vec![alg(ZLIB)], 0)
+        .encode_payload(&message, Some(&alg(ZLIB)), |_, data| {
+            Ok::<_, CompressionError>(data.to_vec())
+        })
+        .unwrap();
+    assert_eq!(payload, message_der);
+
+    // Uncompressed payloads are accepted as-is
+    let decoded = decode_payload(&payload, no_compress).unwrap();
+    assert_eq!(decoded.as_ref(), message_der);
+}
+
+#[test]
+fn reject_other_content_type() {
+    // ContentInfo of `id-data`
+    let payload = [
+        0x30, 0x0F, 0x06, 0x09, 0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x07, 0x01, 0xA0, 0x02,
+        0x04, 0x00,
+    ];
+    let err = decode_payload(&payload, |_, _| Ok::<_, CompressionError>(vec![])).unwrap_err();
+    assert!(matches!(err, CompressionError::ContentType { .. }));
+}
@@ -0,0 +1,101 @@
+//! Request correlation tests
+
+#![cfg(feature = "std")]
+
+use std::sync::{Arc, mpsc};
+use std::thread;
+
+use cmpv2::body::PkiBody;
+use cmpv2::correlation::{
+    CertReqIdAllocator, CorrelationError, CorrelationKey, CorrelationMap, cert_req_ids,
+};
+use cmpv2::message::PkiMessage;
+use der::Decode;
+use der::asn1::Int;
+
+fn ir_req() -> PkiMessage<'static> {
+    PkiMessage::from_der(include_bytes!("examples/ir_req_01.bin")).unwrap()
+}
+
+fn ir_rsp() -> PkiMessage<'static> {
+    PkiMessage::from_der(include_bytes!("examples/ir_rsp_01.bin")).unwrap()
+}
+
+#[test]
+fn cert_req_ids_of_bodies() {
+    let zero = Int::new(&[0]).unwrap();
+    assert_eq!(cert_req_ids(&ir_req().body), [&zero]);
+    assert_eq!(cert_req_ids(&ir_rsp().body), [&zero]);
+    assert!(cert_req_ids(&PkiBody::PkiConf(der::asn1::Null)).is_empty());
+}
+
+#[test]
+fn route_by_transaction_id() {
+    let rsp = ir_rsp();
+    let transaction_id = rsp.header.trans_id.as_ref().unwrap().as_bytes();
+
+    let map = CorrelationMap::new();
+    map.register(CorrelationKey::transaction(transaction_id), "transaction")
+        .unwrap();
+    map.register(CorrelationKey::transaction(b"other"), "other")
+        .unwrap();
+    assert_eq!(map.route(&rsp), Ok("transaction"));
+
+    // Responses referring to a tracked certReqId are routed individually
+    let key = CorrelationKey::cert_req(transaction_id, Int::new(&[0]).unwrap());
+    map.register(key.clone(), "cert_req").unwrap();
+    assert_eq!(map.route(&rsp), Ok("cert_req"));
+
+    assert_eq!(
+        map.register(key.clone(), "duplicate"),
+        Err(CorrelationError::Duplicate)
+    );
+
+    assert_eq!(map.remove(&key), Some("cert_req"));
+    assert_eq!(map.route(&rsp), Ok("transaction"));
+
+    assert_eq!(map.remove_transaction(transaction_id), ["transaction"]);
+    assert_eq!(map.route(&rsp), Err(CorrelationError::Unknown));
+    assert_eq!(map.len(), 1);
+}
+
+#[test]
+fn route_without_transaction_id() {
+    let mut rsp = ir_rsp();
+    rsp.header.trans_id = None;
+
+    let map = CorrelationMap::<()>::new();
+    assert_eq!(map.route(&rsp), Err(CorrelationError::MissingTransactionId));
+}
+
+#[test]
+fn route_to_channels_concurrently() {
+    let rsp = ir_rsp();
+    let transaction_id = rsp.header.trans_id.clone().unwrap();
+    let map = Arc::new(CorrelationMap::new());
+
+    let (sender, receiver) = mpsc::channel();
+    map.register(
+        CorrelationKey::transaction(transaction_id.as_bytes()),
+        sender,
+    )
+    .unwrap();
+
+    let transport = {
+        let map = Arc::clone(&map);
+        thread::spawn(move || {
+            let rsp = ir_rsp();
+            map.route(&rsp).unwrap().send(rsp).unwrap();
+        })
+    };
+
+    transport.join().unwrap();
+    assert_eq!(receiver.recv().unwrap(), rsp);
+}
+
+#[test]
+fn allocate_cert_req_ids() {
+    let allocator = CertReqIdAllocator::new();
+    assert_eq!(allocator.allocate(), Int::new(&[0]).unwrap());
+    assert_eq!(allocator.allocate(), Int::new(&[1]).unwrap());
+}
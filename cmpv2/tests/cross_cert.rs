@@ -0,0 +1,172 @@
+//! Cross-certification tests
+
+use cmpv2::body::PkiBody;
+use cmpv2::certified_key_pair::CertOrEncCert;
+use cmpv2::cross_cert::{CrossCertError, ccp, ccr, validate_ccp, validate_ccr};
+use cmpv2::header::PkiHeader;
+use cmpv2::message::PkiMessage;
+use cmpv2::response::CertRepMessage;
+use cmpv2::server::{MessageHandler, respond};
+use cmpv2::status::PkiFailureInfoValues;
+use const_oid::db::rfc5912::ID_REG_CTRL_PKI_ARCHIVE_OPTIONS;
+use crmf::controls::EncryptedValue;
+use crmf::request::CertReqMessages;
+use der::asn1::{BitString, Int, OctetString};
+use der::{Any, Decode, Encode};
+use x509_cert::attr::AttributeTypeAndValue;
+
+fn cr_content() -> CertReqMessages {
+    match PkiMessage::from_der(include_bytes!("examples/cr_req_01.bin"))
+        .unwrap()
+        .body
+    {
+        PkiBody::Cr(content) => content,
+        _ => unreachable!(),
+    }
+}
+
+fn cp_content() -> CertRepMessage<'static> {
+    match PkiMessage::from_der(include_bytes!("examples/cr_rsp_01.bin"))
+        .unwrap()
+        .body
+    {
+        PkiBody::Cp(content) => content,
+        _ => unreachable!(),
+    }
+}
+
+fn encrypted_value() -> EncryptedValue {
+    EncryptedValue {
+        intended_alg: None,
+        sym_alg: None,
+        enc_sym_key: None,
+        key_alg: None,
+        value_hint: None,
+        enc_value: BitString::from_bytes(&[0x42; 16]).unwrap(),
+    }
+}
+
+fn first_id() -> Int {
+    Int::new(&[0]).unwrap()
+}
+
+#[test]
+fn ccr_round_trip() {
+    let body = ccr(cr_content()).unwrap();
+    let der = body.to_der().unwrap();
+
+    let decoded = PkiBody::from_der(&der).unwrap();
+    assert!(matches!(&decoded, PkiBody::Ccr(content) if *content == cr_content()));
+    assert_eq!(decoded, body);
+}
+
+#[test]
+fn ccr_missing_public_key() {
+    let mut content = cr_content();
+    content[0].cert_req.cert_template.subject_public_key_info = None;
+
+    assert_eq!(
+        validate_ccr(&content),
+        Err(CrossCertError::MissingPublicKey(first_id()))
+    );
+    assert!(ccr(content).is_err());
+}
+
+#[test]
+fn ccr_private_key_archive() {
+    let mut content = cr_content();
+    content[0]
+        .cert_req
+        .controls
+        .get_or_insert_with(Vec::new)
+        .push(AttributeTypeAndValue {
+            oid: ID_REG_CTRL_PKI_ARCHIVE_OPTIONS,
+            value: Any::from_der(&[0x01, 0x01, 0xff]).unwrap(),
+        });
+
+    assert_eq!(
+        validate_ccr(&content),
+        Err(CrossCertError::PrivateKey(first_id()))
+    );
+}
+
+#[test]
+fn ccp_round_trip() {
+    let body = ccp(cp_content()).unwrap();
+    let der = body.to_der().unwrap();
+
+    let decoded = PkiBody::from_der(&der).unwrap();
+    assert!(matches!(&decoded, PkiBody::Ccp(content) if *content == cp_content()));
+}
+
+#[test]
+fn ccp_encrypted_cert() {
+    let mut content = cp_content();
+    let key_pair = content.response[0].certified_key_pair.as_mut().unwrap();
+    key_pair.cert_or_enc_cert = CertOrEncCert::EncryptedCert(Box::new(encrypted_value()));
+
+    assert_eq!(
+        validate_ccp(&content),
+        Err(CrossCertError::EncryptedCert(first_id()))
+    );
+    assert!(ccp(content).is_err());
+}
+
+#[test]
+fn ccp_private_key() {
+    let mut content = cp_content();
+    let key_pair = content.response[0].certified_key_pair.as_mut().unwrap();
+    key_pair.priv_key = Some(encrypted_value());
+
+    assert_eq!(
+        validate_ccp(&content),
+        Err(CrossCertError::PrivateKey(first_id()))
+    );
+}
+
+/// Handler answering cross-certification requests with the given responses.
+struct CcrHandler(CertRepMessage<'static>);
+
+impl MessageHandler for CcrHandler {
+    type Error = CrossCertError;
+
+    fn ccr<'a>(
+        &mut self,
+        _header: &PkiHeader<'a>,
+        content: &CertReqMessages,
+    ) -> Result<Option<CertRepMessage<'a>>, CrossCertError> {
+        validate_ccr(content)?;
+        Ok(Some(self.0.clone()))
+    }
+}
+
+#[test]
+fn respond_ccr() {
+    let mut req = PkiMessage::from_der(include_bytes!("examples/cr_req_01.bin")).unwrap();
+    req.body = ccr(cr_content()).unwrap();
+
+    let mut handler = CcrHandler(cp_content());
+    let rsp = respond(&mut handler, &req, OctetString::new([0x42; 16]).unwrap()).unwrap();
+    assert_eq!(rsp.body, PkiBody::Ccp(cp_content()));
+    assert_eq!(rsp.header.trans_id, req.header.trans_id);
+}
+
+#[test]
+fn respond_ccr_invalid_ccp() {
+    let mut req = PkiMessage::from_der(include_bytes!("examples/cr_req_01.bin")).unwrap();
+    req.body = ccr(cr_content()).unwrap();
+
+    let mut content = cp_content();
+    let key_pair = content.response[0].certified_key_pair.as_mut().unwrap();
+    key_pair.priv_key = Some(encrypted_value());
+
+    let mut handler = CcrHandler(content);
+    let rsp = respond(&mut handler, &req, OctetString::new([0x42; 16]).unwrap()).unwrap();
+    match rsp.body {
+        PkiBody::Error(content) => assert_eq!(
+            content.pki_status_info.fail_info,
+            Some(PkiFailureInfoValues::SystemFailure.into())
+        ),
+        _ => panic!("expected an error body"),
+    }
+}
@@ -0,0 +1,75 @@
+//! Error message construction tests
+
+use cmpv2::body::PkiBody;
+use cmpv2::status::{ErrorMsgContent, PkiFailureInfo, PkiFailureInfoValues, PkiStatus};
+use der::{Decode, Encode};
+
+#[test]
+fn error_msg_round_trip() {
+    let error = ErrorMsgContent::new(
+        PkiStatus::Rejection,
+        PkiFailureInfoValues::BadMessageCheck | PkiFailureInfoValues::WrongIntegrity,
+        Some("protection did not verify"),
+    )
+    .unwrap()
+    .with_error_code(7)
+    .with_error_details("unknown MAC key")
+    .unwrap();
+
+    let der = PkiBody::Error(error.clone()).to_der().unwrap();
+    let PkiBody::Error(decoded) = PkiBody::from_der(&der).unwrap() else {
+        panic!("unexpected body");
+    };
+    assert_eq!(decoded, error);
+
+    assert_eq!(decoded.status(), PkiStatus::Rejection);
+    assert_eq!(
+        decoded.failure_info(),
+        PkiFailureInfoValues::BadMessageCheck | PkiFailureInfoValues::WrongIntegrity
+    );
+    assert!(
+        decoded
+            .failure_info()
+            .contains(PkiFailureInfoValues::WrongIntegrity)
+    );
+    assert!(
+        !decoded
+            .failure_info()
+            .contains(PkiFailureInfoValues::BadAlg)
+    );
+    assert!(decoded.status_text().eq(["protection did not verify"]));
+    assert_eq!(decoded.error_code, Some(7));
+    assert!(decoded.error_details_text().eq(["unknown MAC key"]));
+}
+
+#[test]
+fn error_msg_optional_fields() {
+    let error =
+        ErrorMsgContent::new(PkiStatus::Rejection, PkiFailureInfo::default(), None).unwrap();
+
+    assert_eq!(error.pki_status_info.fail_info, None);
+    assert_eq!(error.pki_status_info.status_string, None);
+    assert!(error.failure_info().is_empty());
+    assert_eq!(error.status_text().count(), 0);
+    assert_eq!(error.error_details_text().count(), 0);
+
+    // SEQUENCE { SEQUENCE { INTEGER 2 } }
+    assert_eq!(
+        error.to_der().unwrap(),
+        [0x30, 0x05, 0x30, 0x03, 0x02, 0x01, 0x02]
+    );
+}
+
+#[test]
+fn error_msg_single_failure() {
+    let error =
+        ErrorMsgContent::new(PkiStatus::Rejection, PkiFailureInfoValues::BadRequest, None).unwrap();
+
+    let der = error.to_der().unwrap();
+    let decoded = ErrorMsgContent::from_der(&der).unwrap();
+    assert_eq!(decoded.failure_info(), PkiFailureInfoValues::BadRequest);
+
+    // failInfo is BIT STRING { badRequest (2) }
+    let fail_info = error.pki_status_info.fail_info.unwrap();
+    assert_eq!(fail_info.to_der().unwrap(), [0x03, 0x02, 0x05, 0x20]);
+}
@@ -1,6 +1,12 @@
 use cmpv2::body::PkiBody;
-use cmpv2::header::{PkiHeader, Pvno};
+use cmpv2::certified_key_pair::CertOrEncCert;
+use cmpv2::gp::{
+    ID_IT_CA_CERTS, ID_IT_ROOT_CA_KEY_UPDATE, ID_IT_SIGN_KEY_PAIR_TYPES, InfoTypeAndValue,
+    InfoValue, RootCaKeyUpdateContent,
+};
+use cmpv2::header::{CmpCertificate, PkiHeader, Pvno};
 use cmpv2::message::PkiMessage;
+use der::asn1::{Any, ObjectIdentifier};
 use der::{Decode, Encode};
 
 #[test]
@@ -81,3 +87,83 @@ fn genm_rsp_message_test() {
     println!("Reencoded: {reencoded_req_01:02X?}");
     assert_eq!(req_01, reencoded_req_01.as_slice());
 }
+
+#[test]
+fn genm_req_info_value_test() {
+    let req_01 = include_bytes!("examples/genm_req_01.bin");
+    let message = PkiMessage::from_der(req_01).unwrap();
+    let PkiBody::GenM(content) = &message.body else {
+        panic!("unexpected body: {:?}", message.body);
+    };
+
+    assert_eq!(
+        content,
+        &[InfoTypeAndValue::request(ID_IT_SIGN_KEY_PAIR_TYPES)]
+    );
+    assert_eq!(
+        content[0].to_info_value().unwrap(),
+        InfoValue::SignKeyPairTypes(None)
+    );
+}
+
+#[test]
+fn info_value_round_trip_test() {
+    let rsp_01 = include_bytes!("examples/cr_rsp_01.bin");
+    let message = PkiMessage::from_der(rsp_01).unwrap();
+    let PkiBody::Cp(cp) = message.body else {
+        panic!("unexpected body: {:?}", message.body);
+    };
+    let key_pair = cp.response[0].certified_key_pair.as_ref().unwrap();
+    let CertOrEncCert::Certificate(cert) = &key_pair.cert_or_enc_cert else {
+        panic!("unexpected certificate: {:?}", key_pair.cert_or_enc_cert);
+    };
+    let cert = CmpCertificate::clone(cert);
+
+    let values = [
+        InfoValue::CaCerts(Some(vec![cert.clone()])),
+        InfoValue::RootCaKeyUpdate(Some(Box::new(RootCaKeyUpdateContent {
+            new_with_new: cert.clone(),
+            new_with_old: None,
+            old_with_new: Some(cert),
+        }))),
+        InfoValue::RootCaKeyUpdate(None),
+    ];
+
+    for value in values {
+        let info = InfoTypeAndValue::try_from(&value).unwrap();
+        assert_eq!(info.oid, value.info_type());
+
+        let decoded = InfoTypeAndValue::from_der(&info.to_der().unwrap()).unwrap();
+        assert_eq!(decoded.to_info_value().unwrap(), value);
+    }
+
+    assert_eq!(
+        InfoTypeAndValue::request(ID_IT_ROOT_CA_KEY_UPDATE)
+            .to_info_value()
+            .unwrap(),
+        InfoValue::RootCaKeyUpdate(None)
+    );
+}
+
+#[test]
+fn info_value_empty_sequence_test() {
+    assert!(InfoTypeAndValue::try_from(InfoValue::CaCerts(Some(Vec::new()))).is_err());
+
+    let info = InfoTypeAndValue {
+        oid: ID_IT_CA_CERTS,
+        value: Some(Any::encode_from(&Vec::<CmpCertificate>::new()).unwrap()),
+    };
+    assert!(info.to_info_value().is_err());
+}
+
+#[test]
+fn info_value_other_test() {
+    let info = InfoTypeAndValue {
+        oid: ObjectIdentifier::new_unwrap("1.2.3.4"),
+        value: Some(Any::encode_from(&56789u32).unwrap()),
+    };
+
+    let value = info.to_info_value().unwrap();
+    assert_eq!(value, InfoValue::Other(info.clone()));
+    assert_eq!(InfoTypeAndValue::try_from(value).unwrap(), info);
+}
@@ -0,0 +1,99 @@
+//! Polling tests
+
+use core::time::Duration;
+
+use cmpv2::body::PkiBody;
+use cmpv2::poll::{PollAction, PollError, PollRepContent, PollReqContent, Poller};
+use cmpv2::response::{CertRepMessage, CertResponse};
+use cmpv2::status::{PkiStatus, PkiStatusInfo};
+use der::asn1::Int;
+
+fn response(statuses: &[(u8, PkiStatus)]) -> CertRepMessage<'static> {
+    CertRepMessage {
+        ca_pubs: None,
+        response: statuses
+            .iter()
+            .map(|&(id, status)| CertResponse {
+                cert_req_id: Int::new(&[id]).unwrap(),
+                status: PkiStatusInfo {
+                    status,
+                    status_string: None,
+                    fail_info: None,
+                },
+                certified_key_pair: None,
+                rsp_info: None,
+            })
+            .collect(),
+    }
+}
+
+fn poll_rep(id: u8, check_after: u64) -> PkiBody<'static> {
+    PkiBody::PollRep(PollRepContent {
+        cert_req_id: Int::new(&[id]).unwrap(),
+        check_after,
+        reason: None,
+    })
+}
+
+#[test]
+fn poll_until_done() {
+    let initial = response(&[
+        (0, PkiStatus::Accepted),
+        (1, PkiStatus::Waiting),
+        (2, PkiStatus::Waiting),
+    ]);
+
+    let mut poller = Poller::from_response(&initial);
+    let ids = vec![Int::new(&[1]).unwrap(), Int::new(&[2]).unwrap()];
+    assert_eq!(poller.pending(), ids.as_slice());
+
+    assert_eq!(
+        poller.next_action(),
+        PollAction::SendPollReq(PollReqContent {
+            cert_req_ids: ids.clone()
+        })
+    );
+
+    poller.handle_body(&poll_rep(1, 60)).unwrap();
+    assert_eq!(
+        poller.next_action(),
+        PollAction::Wait(Duration::from_secs(60))
+    );
+    assert_eq!(
+        poller.next_action(),
+        PollAction::SendPollReq(PollReqContent { cert_req_ids: ids })
+    );
+
+    // Request 1 is still waiting, but request 2 was granted
+    poller
+        .handle_body(&PkiBody::Ip(response(&[
+            (1, PkiStatus::Waiting),
+            (2, PkiStatus::GrantedWithMods),
+        ])))
+        .unwrap();
+    assert_eq!(poller.pending(), &[Int::new(&[1]).unwrap()]);
+
+    poller
+        .handle_body(&PkiBody::Ip(response(&[(1, PkiStatus::Rejection)])))
+        .unwrap();
+    assert_eq!(poller.next_action(), PollAction::Done);
+}
+
+#[test]
+fn reject_unexpected_responses() {
+    let mut poller = Poller::from_response(&response(&[(1, PkiStatus::Waiting)]));
+
+    assert_eq!(
+        poller.handle_body(&poll_rep(2, 60)),
+        Err(PollError::UnknownCertReqId)
+    );
+    assert_eq!(
+        poller.handle_body(&PkiBody::Cp(response(&[(2, PkiStatus::Accepted)]))),
+        Err(PollError::UnknownCertReqId)
+    );
+    assert_eq!(
+        poller.handle_body(&PkiBody::PkiConf(der::asn1::Null)),
+        Err(PollError::UnexpectedBody)
+    );
+    assert_eq!(poller.pending(), &[Int::new(&[1]).unwrap()]);
+}
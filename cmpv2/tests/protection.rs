@@ -0,0 +1,252 @@
+//! Message protection verification tests
+
+use cmpv2::body::PkiBody;
+use cmpv2::message::{PkiMessage, ProtectedPart};
+use cmpv2::protection::{
+    AcceptAny, ProtectionError, RequireNewest, RolloverVerified, protect_dual, unwrap_nested,
+    verify_additional_with_rollover, verify_with_rollover,
+};
+use der::asn1::{BitString, ObjectIdentifier};
+use der::{Decode, Encode};
+use spki::AlgorithmIdentifierOwned;
+
+/// Fake verifier which accepts the protection when the candidate key matches
+/// the protection bits and the protected part was encoded as expected.
+fn verifier<'a>(
+    expected_part: &'a [u8],
+) -> impl FnMut(&&[u8], &spki::AlgorithmIdentifierOwned, &[u8], &[u8]) -> Result<(), ()> + 'a {
+    move |key, _alg, protected_part, protection| {
+        if protected_part == expected_part && *key == protection {
+            Ok(())
+        } else {
+            Err(())
+        }
+    }
+}
+
+#[test]
+fn protected_part_der() {
+    let message = PkiMessage::from_der(include_bytes!("examples/cr_req_01.bin")).unwrap();
+    let expected = ProtectedPart {
+        header: message.header.clone(),
+        body: message.body.clone(),
+    }
+    .to_der()
+    .unwrap();
+
+    assert_eq!(message.protected_part_der().unwrap(), expected);
+}
+
+#[test]
+fn verify_rollover() {
+    let message = PkiMessage::from_der(include_bytes!("examples/cr_req_01.bin")).unwrap();
+    let part = message.protected_part_der().unwrap();
+    let protection = message.protection.as_ref().unwrap().raw_bytes();
+    let other = [0u8; 4];
+
+    // Verified under the newest key
+    let candidates: [&[u8]; 2] = [&other, protection];
+    let verified =
+        verify_with_rollover(&message, &candidates, RequireNewest, verifier(&part)).unwrap();
+    assert_eq!(
+        verified,
+        RolloverVerified {
+            index: 1,
+            newest: true
+        }
+    );
+
+    // Verified under the old key
+    let candidates: [&[u8]; 2] = [protection, &other];
+    let verified = verify_with_rollover(&message, &candidates, AcceptAny, verifier(&part)).unwrap();
+    assert_eq!(verified.index, 0);
+    assert!(!verified.newest);
+
+    assert_eq!(
+        verify_with_rollover(&message, &candidates, RequireNewest, verifier(&part)),
+        Err(ProtectionError::Rejected { verified })
+    );
+
+    // Custom policy
+    let policy = |verified: &RolloverVerified| verified.index == 0;
+    assert!(verify_with_rollover(&message, &candidates, policy, verifier(&part)).is_ok());
+
+    // No matching key
+    let candidates: [&[u8]; 1] = [&other];
+    assert_eq!(
+        verify_with_rollover(&message, &candidates, AcceptAny, verifier(&part)),
+        Err(ProtectionError::NoMatchingKey)
+    );
+}
+
+#[test]
+fn verify_unprotected() {
+    let mut message = PkiMessage::from_der(include_bytes!("examples/cr_req_01.bin")).unwrap();
+    message.protection = None;
+
+    let candidates: [&[u8]; 0] = [];
+    assert_eq!(
+        verify_with_rollover(&message, &candidates, AcceptAny, verifier(&[])),
+        Err(ProtectionError::Unprotected)
+    );
+}
+
+#[test]
+fn nested_roundtrip_and_unwrap() {
+    let cr = PkiMessage::from_der(include_bytes!("examples/cr_req_01.bin")).unwrap();
+    let ir = PkiMessage::from_der(include_bytes!("examples/ir_req_01.bin")).unwrap();
+    let ra_key = [1u8, 2, 3, 4];
+
+    let outer = PkiMessage {
+        header: cr.header.clone(),
+        body: PkiBody::Nested(vec![cr.clone(), ir.clone()].into()),
+        protection: Some(BitString::from_bytes(&ra_key).unwrap()),
+        extra_certs: None,
+    };
+
+    let der = outer.to_der().unwrap();
+    let decoded = PkiMessage::from_der(&der).unwrap();
+    assert_eq!(decoded, outer);
+
+    let part = decoded.protected_part_der().unwrap();
+    let candidates: [&[u8]; 1] = [&ra_key];
+    let inner = unwrap_nested(&decoded, &candidates, RequireNewest, verifier(&part)).unwrap();
+    assert_eq!(inner, [cr, ir]);
+
+    // Inner messages carry their own protection
+    for message in inner {
+        let part = message.protected_part_der().unwrap();
+        let protection = message.protection.as_ref().unwrap().raw_bytes();
+        let candidates: [&[u8]; 1] = [protection];
+        assert!(verify_with_rollover(message, &candidates, AcceptAny, verifier(&part)).is_ok());
+    }
+
+    // Outer protection must verify
+    let candidates: [&[u8]; 1] = [&[0u8; 4]];
+    assert_eq!(
+        unwrap_nested(&decoded, &candidates, AcceptAny, verifier(&part)),
+        Err(ProtectionError::NoMatchingKey)
+    );
+}
+
+#[test]
+fn unwrap_not_nested() {
+    let message = PkiMessage::from_der(include_bytes!("examples/cr_req_01.bin")).unwrap();
+    let candidates: [&[u8]; 0] = [];
+    assert_eq!(
+        unwrap_nested(&message, &candidates, AcceptAny, verifier(&[])),
+        Err(ProtectionError::NotNested)
+    );
+}
+
+#[test]
+fn dual_protection() {
+    let mut message = PkiMessage::from_der(include_bytes!("examples/cr_req_01.bin")).unwrap();
+    let info_type = ObjectIdentifier::new_unwrap("1.3.6.1.4.1.22554.99.1");
+    let mac_alg = message.header.protection_alg.clone().unwrap();
+    let sig_alg = AlgorithmIdentifierOwned {
+        oid: ObjectIdentifier::new_unwrap("1.2.840.10045.4.3.2"),
+        parameters: None,
+    };
+    let (mac_key, sig_key) = ([1u8; 4], [2u8; 4]);
+
+    let mut signed_part = Vec::new();
+    protect_dual::<der::Error, _, _>(
+        &mut message,
+        info_type,
+        sig_alg.clone(),
+        |alg, part| {
+            assert_eq!(alg, &sig_alg);
+            signed_part = part.to_vec();
+            Ok(sig_key.to_vec())
+        },
+        mac_alg.clone(),
+        |alg, _part| {
+            assert_eq!(alg, &mac_alg);
+            Ok(mac_key.to_vec())
+        },
+    )
+    .unwrap();
+
+    let der = message.to_der().unwrap();
+    let message = PkiMessage::from_der(&der).unwrap();
+    assert_eq!(message.header.protection_alg, Some(mac_alg));
+
+    let additional = message.additional_protection(info_type).unwrap().unwrap();
+    assert_eq!(additional.protection_alg, sig_alg);
+    assert_eq!(additional.protection.raw_bytes(), sig_key);
+
+    // The additional protection covers the message without itself
+    assert_eq!(
+        message.additional_protected_part_der(info_type).unwrap(),
+        signed_part
+    );
+
+    // The regular protection covers the additional protection
+    let part = message.protected_part_der().unwrap();
+    assert_ne!(part, signed_part);
+
+    let candidates: [&[u8]; 1] = [&mac_key];
+    assert!(verify_with_rollover(&message, &candidates, AcceptAny, verifier(&part)).is_ok());
+
+    let candidates: [&[u8]; 1] = [&sig_key];
+    assert!(
+        verify_additional_with_rollover(
+            &message,
+            info_type,
+            &candidates,
+            AcceptAny,
+            verifier(&signed_part)
+        )
+        .is_ok()
+    );
+
+    let candidates: [&[u8]; 1] = [&mac_key];
+    assert_eq!(
+        verify_additional_with_rollover(
+            &message,
+            info_type,
+            &candidates,
+            AcceptAny,
+            verifier(&signed_part)
+        ),
+        Err(ProtectionError::NoMatchingKey)
+    );
+}
+
+#[test]
+fn dual_protection_missing() {
+    let message = PkiMessage::from_der(include_bytes!("examples/cr_req_01.bin")).unwrap();
+    let info_type = ObjectIdentifier::new_unwrap("1.3.6.1.4.1.22554.99.1");
+    assert_eq!(message.additional_protection(info_type), Ok(None));
+
+    let candidates: [&[u8]; 0] = [];
+    assert_eq!(
+        verify_additional_with_rollover(&message, info_type, &candidates, AcceptAny, verifier(&[])),
+        Err(ProtectionError::Unprotected)
+    );
+}
+
+#[test]
+fn nested_too_deep() {
+    let cr = PkiMessage::from_der(include_bytes!("examples/cr_req_01.bin")).unwrap();
+    let nest = |levels: usize| {
+        let mut message = cr.clone();
+        for _ in 0..levels {
+            message = PkiMessage {
+                header: cr.header.clone(),
+                body: PkiBody::Nested(vec![message].into()),
+                protection: None,
+                extra_certs: None,
+            };
+        }
+        message.to_der().unwrap()
+    };
+
+    let der = nest(8);
+    let decoded = PkiMessage::from_der(&der).unwrap();
+    assert_eq!(decoded.to_der().unwrap(), der);
+
+    let err = PkiMessage::from_der(&nest(9)).unwrap_err();
+    assert_eq!(err.kind(), der::ErrorKind::Overflow);
+}
@@ -0,0 +1,61 @@
+//! Replay protection tests
+
+use core::time::Duration;
+
+use cmpv2::message::PkiMessage;
+use cmpv2::replay::{MemoryReplayCache, ReplayError, ReplayGuard};
+use der::DateTime;
+use der::Decode;
+use der::asn1::{GeneralizedTime, OctetString};
+
+fn time(hour: u8) -> GeneralizedTime {
+    GeneralizedTime::from_date_time(DateTime::new(2030, 1, 1, hour, 0, 0).unwrap())
+}
+
+#[test]
+fn reject_replayed_message() {
+    let req = PkiMessage::from_der(include_bytes!("examples/ir_req_01.bin")).unwrap();
+    let mut guard = ReplayGuard::new(MemoryReplayCache::new(), Duration::from_secs(3600));
+
+    guard.check(&req.header, time(0)).unwrap();
+    assert_eq!(
+        guard.check(&req.header, time(0)),
+        Err(ReplayError::Replayed)
+    );
+
+    // A fresh senderNonce in the same transaction is accepted
+    let mut header = req.header.clone();
+    header.sender_nonce = Some(OctetString::new(*b"0123456789abcdef").unwrap());
+    guard.check(&header, time(0)).unwrap();
+    assert_eq!(guard.cache().len(), 2);
+
+    // Entries expire after the TTL
+    guard.check(&req.header, time(1)).unwrap();
+    assert_eq!(
+        guard.check(&req.header, time(1)),
+        Err(ReplayError::Replayed)
+    );
+
+    guard.cache_mut().purge(time(2));
+    assert!(guard.cache().is_empty());
+}
+
+#[test]
+fn reject_missing_fields() {
+    let req = PkiMessage::from_der(include_bytes!("examples/ir_req_01.bin")).unwrap();
+    let mut guard = ReplayGuard::new(MemoryReplayCache::new(), Duration::from_secs(3600));
+
+    let mut header = req.header.clone();
+    header.sender_nonce = None;
+    assert_eq!(
+        guard.check(&header, time(0)),
+        Err(ReplayError::MissingSenderNonce)
+    );
+
+    header.trans_id = None;
+    assert_eq!(
+        guard.check(&header, time(0)),
+        Err(ReplayError::MissingTransactionId)
+    );
+    assert!(guard.cache().is_empty());
+}
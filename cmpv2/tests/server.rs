@@ -0,0 +1,95 @@
+//! Server-side dispatching tests
+
+use cmpv2::body::PkiBody;
+use cmpv2::gp::{GenMsgContent, GenRepContent};
+use cmpv2::header::PkiHeader;
+use cmpv2::message::PkiMessage;
+use cmpv2::response::CertRepMessage;
+use cmpv2::server::{MessageHandler, respond};
+use cmpv2::status::{PkiFailureInfoValues, PkiStatus};
+use cmpv2::transaction::TransactionSnapshot;
+use crmf::request::CertReqMessages;
+use der::Decode;
+use der::asn1::OctetString;
+
+/// Handler replaying the response body of the example `ir` transaction.
+struct IrHandler(&'static [u8]);
+
+impl MessageHandler for IrHandler {
+    type Error = der::Error;
+
+    fn ir<'a>(
+        &mut self,
+        _header: &PkiHeader<'a>,
+        content: &CertReqMessages,
+    ) -> der::Result<Option<CertRepMessage<'a>>> {
+        assert_eq!(content.len(), 1);
+
+        match PkiBody::from_der(self.0)? {
+            PkiBody::Ip(rep) => Ok(Some(rep)),
+            _ => unreachable!(),
+        }
+    }
+
+    fn genm(
+        &mut self,
+        _header: &PkiHeader<'_>,
+        _content: &GenMsgContent,
+    ) -> der::Result<Option<GenRepContent>> {
+        Ok(Some(GenRepContent::new()))
+    }
+}
+
+fn nonce() -> OctetString {
+    OctetString::new([0x42; 16]).unwrap()
+}
+
+#[test]
+fn respond_ir() {
+    let req = PkiMessage::from_der(include_bytes!("examples/ir_req_01.bin")).unwrap();
+    let expected = PkiMessage::from_der(include_bytes!("examples/ir_rsp_01.bin")).unwrap();
+    let body = include_bytes!("examples/ir_rsp_body_01.bin");
+
+    let rsp = respond(&mut IrHandler(body), &req, nonce()).unwrap();
+    assert_eq!(rsp.body, expected.body);
+    assert!(rsp.protection.is_none());
+
+    let header = &rsp.header;
+    assert_eq!(header.pvno, req.header.pvno);
+    assert_eq!(header.sender, req.header.recipient);
+    assert_eq!(header.recipient, req.header.sender);
+    assert_eq!(header.recip_kid, req.header.sender_kid);
+    assert_eq!(header.sender_nonce, Some(nonce()));
+
+    // The response is accepted by the client side of the transaction
+    let mut snapshot = TransactionSnapshot::new(req.header.trans_id.clone().unwrap());
+    let mut req_header = req.header.clone();
+    snapshot.prepare_request(&mut req_header, req.header.sender_nonce.clone().unwrap());
+    snapshot.record_response(header).unwrap();
+}
+
+#[test]
+fn respond_genm() {
+    let req = PkiMessage::from_der(include_bytes!("examples/genm_req_01.bin")).unwrap();
+    let rsp = respond(&mut IrHandler(&[]), &req, nonce()).unwrap();
+    assert_eq!(rsp.body, PkiBody::GenP(GenRepContent::new()));
+    assert_eq!(rsp.header.trans_id, req.header.trans_id);
+    assert_eq!(rsp.header.recip_nonce, req.header.sender_nonce);
+}
+
+#[test]
+fn respond_unsupported() {
+    let req = PkiMessage::from_der(include_bytes!("examples/rr_req_01.bin")).unwrap();
+    let rsp = respond(&mut IrHandler(&[]), &req, nonce()).unwrap();
+    assert_eq!(rsp.header.trans_id, req.header.trans_id);
+    assert_eq!(rsp.header.recip_nonce, req.header.sender_nonce);
+
+    let PkiBody::Error(error) = rsp.body else {
+        panic!("expected an error response");
+    };
+    assert_eq!(error.pki_status_info.status, PkiStatus::Rejection);
+    assert_eq!(
+        error.pki_status_info.fail_info,
+        Some(PkiFailureInfoValues::BadRequest.into())
+    );
+}
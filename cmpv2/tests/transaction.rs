@@ -0,0 +1,92 @@
+//! Transaction persistence tests
+
+use std::collections::HashMap;
+
+use cmpv2::message::PkiMessage;
+use cmpv2::transaction::{TransactionError, TransactionSnapshot, TransactionStore};
+use der::DateTime;
+use der::Decode;
+use der::asn1::{GeneralizedTime, Int, OctetString};
+
+#[derive(Default)]
+struct MemoryStore(HashMap<Vec<u8>, Vec<u8>>);
+
+impl TransactionStore for MemoryStore {
+    type Error = der::Error;
+
+    fn save(&mut self, transaction_id: &[u8], snapshot: &[u8]) -> der::Result<()> {
+        self.0.insert(transaction_id.to_vec(), snapshot.to_vec());
+        Ok(())
+    }
+
+    fn restore(&mut self, transaction_id: &[u8]) -> der::Result<Option<Vec<u8>>> {
+        Ok(self.0.get(transaction_id).cloned())
+    }
+}
+
+fn time(year: u16, hour: u8) -> GeneralizedTime {
+    GeneralizedTime::from_date_time(DateTime::new(year, 1, 1, hour, 0, 0).unwrap())
+}
+
+#[test]
+fn resume_transaction() {
+    let req = PkiMessage::from_der(include_bytes!("examples/ir_req_01.bin")).unwrap();
+    let rsp = PkiMessage::from_der(include_bytes!("examples/ir_rsp_01.bin")).unwrap();
+
+    let mut snapshot = TransactionSnapshot::new(req.header.trans_id.clone().unwrap());
+    let mut header = req.header.clone();
+    snapshot.prepare_request(&mut header, req.header.sender_nonce.clone().unwrap());
+    assert_eq!(header, req.header);
+
+    snapshot.record_response(&rsp.header).unwrap();
+    assert_eq!(snapshot.recip_nonce, rsp.header.sender_nonce);
+
+    snapshot.expect_cert_conf(Int::new(&[0]).unwrap(), time(2030, 12));
+
+    // Persist and restore across a "reboot"
+    let mut store = MemoryStore::default();
+    snapshot.save(&mut store).unwrap();
+
+    let transaction_id = snapshot.transaction_id.as_bytes();
+    let mut restored = TransactionSnapshot::restore(&mut store, transaction_id)
+        .unwrap()
+        .unwrap();
+    assert_eq!(restored, snapshot);
+    assert!(
+        TransactionSnapshot::restore(&mut store, b"unknown")
+            .unwrap()
+            .is_none()
+    );
+
+    // Next request in the transaction echoes the response's senderNonce
+    let mut header = req.header.clone();
+    let nonce = OctetString::new(*b"0123456789abcdef").unwrap();
+    restored.prepare_request(&mut header, nonce.clone());
+    assert_eq!(header.recip_nonce, rsp.header.sender_nonce);
+    assert_eq!(header.sender_nonce, Some(nonce));
+
+    // Pending certConf deadlines
+    assert_eq!(restored.expired_cert_confs(time(2030, 11)).count(), 0);
+    assert_eq!(restored.expired_cert_confs(time(2030, 13)).count(), 1);
+    assert!(restored.cert_confirmed(&Int::new(&[0]).unwrap()));
+    assert!(!restored.cert_confirmed(&Int::new(&[0]).unwrap()));
+    assert_eq!(restored.expired_cert_confs(time(2030, 13)).count(), 0);
+}
+
+#[test]
+fn reject_mismatched_response() {
+    let req = PkiMessage::from_der(include_bytes!("examples/ir_req_01.bin")).unwrap();
+    let rsp = PkiMessage::from_der(include_bytes!("examples/ir_rsp_01.bin")).unwrap();
+
+    let mut snapshot = TransactionSnapshot::new(req.header.trans_id.clone().unwrap());
+    assert_eq!(
+        snapshot.record_response(&rsp.header),
+        Err(TransactionError::RecipNonce)
+    );
+
+    let mut snapshot = TransactionSnapshot::new(OctetString::new(*b"other").unwrap());
+    assert_eq!(
+        snapshot.record_response(&rsp.header),
+        Err(TransactionError::TransactionId)
+    );
+}
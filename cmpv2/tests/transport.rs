@@ -0,0 +1,165 @@
+//! HTTP transport tests
+
+use cmpv2::message::PkiMessage;
+use cmpv2::transport::{
+    CONTENT_TYPE, HttpRequest, HttpResponse, TransportError, decode_request, decode_response,
+    is_cmp_content_type,
+};
+use der::{Decode, Encode};
+
+const IR_REQ: &[u8] = include_bytes!("examples/ir_req_01.bin");
+const IR_RSP: &[u8] = include_bytes!("examples/ir_rsp_01.bin");
+
+#[test]
+fn request_round_trip() {
+    let message = PkiMessage::from_der(IR_REQ).unwrap();
+    let request = HttpRequest::new("/.well-known/cmp", &message).unwrap();
+
+    assert_eq!(request.method(), "POST");
+    assert_eq!(request.path(), "/.well-known/cmp");
+    assert_eq!(
+        request.headers(),
+        [
+            ("Content-Type", "application/pkixcmp".into()),
+            ("Content-Length", IR_REQ.len().to_string()),
+        ]
+    );
+    assert_eq!(request.body(), IR_REQ);
+
+    let decoded = decode_request("POST", Some(CONTENT_TYPE), request.body()).unwrap();
+    assert_eq!(decoded, message);
+
+    assert_eq!(
+        decode_request("GET", Some(CONTENT_TYPE), IR_REQ),
+        Err(TransportError::Method)
+    );
+}
+
+#[test]
+fn response_round_trip() {
+    let message = PkiMessage::from_der(IR_RSP).unwrap();
+    let response = HttpResponse::new(&message).unwrap();
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers()[1].1, IR_RSP.len().to_string());
+    assert_eq!(response.clone().into_body(), IR_RSP);
+
+    let decoded = decode_response(200, Some("Application/PKIXCMP"), response.body()).unwrap();
+    assert_eq!(decoded.to_der().unwrap(), IR_RSP);
+
+    assert_eq!(
+        decode_response(500, Some(CONTENT_TYPE), IR_RSP),
+        Err(TransportError::Status(500))
+    );
+}
+
+#[test]
+fn content_type() {
+    assert!(is_cmp_content_type("application/pkixcmp"));
+    assert!(is_cmp_content_type(" application/pkixcmp ; charset=binary"));
+    assert!(!is_cmp_content_type("application/pkix-cert"));
+    assert!(!is_cmp_content_type("application/pkixcmp-poll"));
+
+    assert_eq!(
+        decode_response(200, None, IR_RSP),
+        Err(TransportError::ContentType)
+    );
+    assert_eq!(
+        decode_response(200, Some("text/html"), IR_RSP),
+        Err(TransportError::ContentType)
+    );
+}
+
+#[test]
+fn trailing_data() {
+    let mut body = IR_RSP.to_vec();
+    body.push(0);
+
+    assert!(matches!(
+        decode_response(200, Some(CONTENT_TYPE), &body),
+        Err(TransportError::Der(_))
+    ));
+}
+
+/// Serve a single request on a local port, checking it's a CMP request and
+/// replying with `IR_RSP`.
+#[cfg(any(feature = "reqwest", feature = "ureq"))]
+fn serve_once() -> (String, std::thread::JoinHandle<()>) {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let server = format!("http://{}", listener.local_addr().unwrap());
+
+    let handle = std::thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream);
+
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        assert!(line.starts_with("POST /.well-known/cmp HTTP/1.1"));
+
+        let mut content_type = None;
+        let mut content_length = 0;
+        loop {
+            line.clear();
+            reader.read_line(&mut line).unwrap();
+            let Some((name, value)) = line.trim_end().split_once(':') else {
+                break;
+            };
+            if name.eq_ignore_ascii_case("content-type") {
+                content_type = Some(value.trim().to_string());
+            } else if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap();
+            }
+        }
+
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body).unwrap();
+        assert_eq!(
+            decode_request("POST", content_type.as_deref(), &body),
+            Ok(PkiMessage::from_der(IR_REQ).unwrap())
+        );
+
+        let mut stream = reader.into_inner();
+        write!(
+            stream,
+            "HTTP/1.1 200 OK\r\nContent-Type: {CONTENT_TYPE}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            IR_RSP.len()
+        )
+        .unwrap();
+        stream.write_all(IR_RSP).unwrap();
+    });
+
+    (server, handle)
+}
+
+#[cfg(feature = "reqwest")]
+#[test]
+fn reqwest_send() {
+    let (server, handle) = serve_once();
+    let message = PkiMessage::from_der(IR_REQ).unwrap();
+    let request = HttpRequest::new("/.well-known/cmp", &message).unwrap();
+
+    let client = reqwest::blocking::Client::new();
+    let response = cmpv2::transport::reqwest::send(&client, &server, &request).unwrap();
+    handle.join().unwrap();
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.decode().unwrap().to_der().unwrap(), IR_RSP);
+}
+
+#[cfg(feature = "ureq")]
+#[test]
+fn ureq_send() {
+    let (server, handle) = serve_once();
+    let message = PkiMessage::from_der(IR_REQ).unwrap();
+    let request = HttpRequest::new("/.well-known/cmp", &message).unwrap();
+
+    let agent = ureq::Agent::new_with_defaults();
+    let response = cmpv2::transport::ureq::send(&agent, &server, &request).unwrap();
+    handle.join().unwrap();
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.decode().unwrap().to_der().unwrap(), IR_RSP);
+}
@@ -27,7 +27,7 @@ impl Root {
 
         for (spec, s) in &self.0 {
             mods.extend(s.module(spec));
-            recs.extend(s.records(quote! { &#spec }));
+            recs.extend(s.records(spec));
         }
 
         quote! {
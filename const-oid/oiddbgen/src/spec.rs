@@ -13,13 +13,14 @@ impl Spec {
         self.0.insert(value)
     }
 
-    pub fn records(&self, path: TokenStream) -> TokenStream {
+    pub fn records(&self, spec: &Ident) -> TokenStream {
         let mut stream = TokenStream::default();
+        let source = spec.to_string();
 
         for n in &self.0 {
             let name = n.name();
             let symb = n.symbol();
-            stream.extend(quote! { (#path::#symb, #name), })
+            stream.extend(quote! { (&#spec::#symb, #name, #source), })
         }
 
         stream
@@ -1,9 +1,6 @@
 //! Arcs are integer values which exist within an OID's hierarchy.
 
-use crate::{Error, Result};
-
-#[cfg(doc)]
-use crate::ObjectIdentifier;
+use crate::{Error, ObjectIdentifier, Result};
 
 /// Type alias used to represent an "arc", i.e. integer identifier value, where an OID comprises a
 /// sequence of arcs.
@@ -42,14 +39,27 @@ pub struct Arcs<'a> {
 
     /// Current position within the serialized BER bytes of this OID.
     cursor: Option<usize>,
+
+    /// Is this a relative OID, i.e. one without root arcs?
+    relative: bool,
 }
 
 impl<'a> Arcs<'a> {
     /// Create a new iterator over an OID encoded as BER bytes.
-    pub(crate) fn new(bytes: &'a [u8]) -> Self {
+    pub(crate) const fn new(bytes: &'a [u8]) -> Self {
         Self {
             bytes,
             cursor: None,
+            relative: false,
+        }
+    }
+
+    /// Create a new iterator over a relative OID encoded as BER bytes.
+    pub(crate) const fn new_relative(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            cursor: Some(0),
+            relative: true,
         }
     }
 
@@ -57,17 +67,27 @@ impl<'a> Arcs<'a> {
     ///
     /// This method is fallible so it can be used as a first pass to determine
     /// that the arcs in the OID are well-formed.
-    pub(crate) fn try_next(&mut self) -> Result<Option<Arc>> {
+    pub(crate) const fn try_next(&mut self) -> Result<Option<Arc>> {
+        // TODO(tarcieri): use `?` when stable in `const fn`
         match self.cursor {
             // Indicates we're on the root arc
             None => {
-                let root_byte = *self.bytes.first().ok_or(Error::Empty)?;
-                let root = RootArcs::try_from(root_byte)?;
+                let root_byte = match self.bytes.first() {
+                    Some(&byte) => byte,
+                    None => return Err(Error::Empty),
+                };
+                let root = match RootArcs::from_byte(root_byte) {
+                    Ok(root) => root,
+                    Err(err) => return Err(err),
+                };
                 self.cursor = Some(0);
                 Ok(Some(root.first_arc()))
             }
-            Some(0) => {
-                let root = RootArcs::try_from(self.bytes[0])?;
+            Some(0) if !self.relative => {
+                let root = match RootArcs::from_byte(self.bytes[0]) {
+                    Ok(root) => root,
+                    Err(err) => return Err(err),
+                };
                 self.cursor = Some(1);
                 Ok(Some(root.second_arc()))
             }
@@ -78,12 +98,14 @@ impl<'a> Arcs<'a> {
                 loop {
                     let len = checked_add!(offset, arc_bytes);
 
-                    match self.bytes.get(len).cloned() {
+                    if len < self.bytes.len() {
+                        let byte = self.bytes[len];
+
                         // The arithmetic below includes advance checks
                         // against `ARC_MAX_BYTES` and `ARC_MAX_LAST_OCTET`
                         // which ensure the operations will not overflow.
                         #[allow(clippy::arithmetic_side_effects)]
-                        Some(byte) => {
+                        {
                             arc_bytes = checked_add!(arc_bytes, 1);
 
                             if (arc_bytes > ARC_MAX_BYTES) && (byte & ARC_MAX_LAST_OCTET != 0) {
@@ -91,19 +113,16 @@ impl<'a> Arcs<'a> {
                             }
 
                             result = (result << 7) | (byte & 0b1111111) as Arc;
-
-                            if byte & 0b10000000 == 0 {
-                                self.cursor = Some(checked_add!(offset, arc_bytes));
-                                return Ok(Some(result));
-                            }
                         }
-                        None => {
-                            if arc_bytes == 0 {
-                                return Ok(None);
-                            } else {
-                                return Err(Error::Base128);
-                            }
+
+                        if byte & 0b10000000 == 0 {
+                            self.cursor = Some(checked_add!(offset, arc_bytes));
+                            return Ok(Some(result));
                         }
+                    } else if arc_bytes == 0 {
+                        return Ok(None);
+                    } else {
+                        return Err(Error::Base128);
                     }
                 }
             }
@@ -120,6 +139,32 @@ impl Iterator for Arcs<'_> {
     }
 }
 
+/// [`Iterator`] over the parents of an [`ObjectIdentifier`], starting with its
+/// direct parent and ending with the OID comprising only its root arcs.
+///
+/// Returned by [`ObjectIdentifier::parent_arcs`].
+#[derive(Clone, Debug)]
+pub struct ParentArcs<const MAX_SIZE: usize> {
+    /// OID whose parent is returned next.
+    oid: Option<ObjectIdentifier<MAX_SIZE>>,
+}
+
+impl<const MAX_SIZE: usize> ParentArcs<MAX_SIZE> {
+    /// Create a new iterator over the parents of the given OID.
+    pub(crate) const fn new(oid: ObjectIdentifier<MAX_SIZE>) -> Self {
+        Self { oid: Some(oid) }
+    }
+}
+
+impl<const MAX_SIZE: usize> Iterator for ParentArcs<MAX_SIZE> {
+    type Item = ObjectIdentifier<MAX_SIZE>;
+
+    fn next(&mut self) -> Option<ObjectIdentifier<MAX_SIZE>> {
+        self.oid = self.oid?.parent();
+        self.oid
+    }
+}
+
 /// Byte containing the first and second arcs of an OID.
 ///
 /// This is represented this way in order to reduce the overall size of the
@@ -146,6 +191,17 @@ impl RootArcs {
         Ok(Self(byte))
     }
 
+    /// Parse [`RootArcs`] from the first byte of a BER-encoded OID.
+    // Ensured not to overflow by constructor invariants
+    #[allow(clippy::arithmetic_side_effects)]
+    pub(crate) const fn from_byte(octet: u8) -> Result<Self> {
+        let first = octet as Arc / (ARC_MAX_SECOND + 1);
+        let second = octet as Arc % (ARC_MAX_SECOND + 1);
+        let result = Self::new(first, second);
+        debug_assert!(matches!(result, Ok(RootArcs(byte)) if byte == octet));
+        result
+    }
+
     /// Get the value of the first arc
     #[allow(clippy::arithmetic_side_effects)]
     pub(crate) const fn first_arc(self) -> Arc {
@@ -162,14 +218,8 @@ impl RootArcs {
 impl TryFrom<u8> for RootArcs {
     type Error = Error;
 
-    // Ensured not to overflow by constructor invariants
-    #[allow(clippy::arithmetic_side_effects)]
     fn try_from(octet: u8) -> Result<Self> {
-        let first = octet as Arc / (ARC_MAX_SECOND + 1);
-        let second = octet as Arc % (ARC_MAX_SECOND + 1);
-        let result = Self::new(first, second)?;
-        debug_assert_eq!(octet, result.0);
-        Ok(result)
+        Self::from_byte(octet)
     }
 }
 
@@ -1,5 +1,7 @@
 //! Array-backed buffer for BER bytes.
 
+use crate::{Error, Result};
+
 /// Array-backed buffer for storing BER computed at compile-time.
 #[derive(Copy, Clone, Eq, Hash, PartialEq, PartialOrd, Ord)]
 pub struct Buffer<const SIZE: usize> {
@@ -11,6 +13,35 @@ pub struct Buffer<const SIZE: usize> {
 }
 
 impl<const SIZE: usize> Buffer<SIZE> {
+    /// Create a buffer containing the concatenation of the given byte slices.
+    ///
+    /// Returns `Err(Error::Length)` if they do not fit in `SIZE`.
+    pub(crate) const fn concat(a: &[u8], b: &[u8]) -> Result<Self> {
+        let length = checked_add!(a.len(), b.len());
+
+        if length > SIZE || length > u8::MAX as usize {
+            return Err(Error::Length);
+        }
+
+        let mut bytes = [0u8; SIZE];
+        let mut i = 0;
+
+        while i < length {
+            bytes[i] = if i < a.len() {
+                a[i]
+            } else {
+                b[checked_sub!(i, a.len())]
+            };
+
+            i = checked_add!(i, 1);
+        }
+
+        Ok(Self {
+            length: length as u8,
+            bytes,
+        })
+    }
+
     /// Borrow the inner byte slice.
     pub const fn as_bytes(&self) -> &[u8] {
         self.bytes.split_at(self.length as usize).0
@@ -37,7 +37,7 @@ const fn eq_case(lhs: &[u8], rhs: &[u8]) -> bool {
 
 /// A query interface for OIDs/Names.
 #[derive(Copy, Clone)]
-pub struct Database<'a>(&'a [(&'a ObjectIdentifier, &'a str)]);
+pub struct Database<'a>(&'a [(&'a ObjectIdentifier, &'a str, &'a str)]);
 
 impl<'a> Database<'a> {
     /// Looks up a name for an OID.
@@ -84,6 +84,49 @@ impl<'a> Database<'a> {
         None
     }
 
+    /// Iterate over all entries of the database.
+    ///
+    /// Entries are grouped by [`Entry::source`], which makes this suitable for
+    /// printing listings of the known OIDs:
+    ///
+    /// ```
+    /// use const_oid::db::DB;
+    ///
+    /// for entry in DB.iter().filter(|entry| entry.source() == "fips204") {
+    ///     println!("{} {}", entry.oid(), entry.name());
+    /// }
+    /// ```
+    pub fn iter(&self) -> Entries<'a> {
+        Entries(self.0.iter())
+    }
+
+    /// Iterate over the entries defined by the given source, e.g. `rfc5912`.
+    pub fn by_source<'b>(
+        &self,
+        source: &'b str,
+    ) -> impl Iterator<Item = Entry<'a>> + Clone + use<'a, 'b> {
+        self.iter()
+            .filter(move |entry| eq_case(entry.source.as_bytes(), source.as_bytes()))
+    }
+
+    /// Iterate over the entries whose OID is the given `arc` or is beneath it.
+    ///
+    /// For example, the ECDSA signature algorithms can be listed with:
+    ///
+    /// ```
+    /// use const_oid::{ObjectIdentifier, db::DB};
+    ///
+    /// const ECDSA_WITH_SHA2: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.4.3");
+    ///
+    /// assert!(DB.by_arc(ECDSA_WITH_SHA2).any(|entry| entry.name() == "ecdsa-with-SHA256"));
+    /// ```
+    pub fn by_arc(
+        &self,
+        arc: ObjectIdentifier,
+    ) -> impl Iterator<Item = Entry<'a>> + Clone + use<'a> {
+        self.iter().filter(move |entry| entry.oid.starts_with(arc))
+    }
+
     /// Return the list of matched name for the OID.
     pub const fn find_names_for_oid(&self, oid: ObjectIdentifier) -> Names<'a> {
         Names {
@@ -94,6 +137,52 @@ impl<'a> Database<'a> {
     }
 }
 
+/// Entry of a [`Database`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Entry<'a> {
+    oid: &'a ObjectIdentifier,
+    name: &'a str,
+    source: &'a str,
+}
+
+impl<'a> Entry<'a> {
+    /// Get the OID of this entry.
+    pub const fn oid(&self) -> &'a ObjectIdentifier {
+        self.oid
+    }
+
+    /// Get the name associated with the OID.
+    pub const fn name(&self) -> &'a str {
+        self.name
+    }
+
+    /// Get the source this entry was generated from, which is also the name of
+    /// the module containing its constant, e.g. `rfc5280` or `fips204`.
+    pub const fn source(&self) -> &'a str {
+        self.source
+    }
+}
+
+/// Iterator over all entries of a [`Database`].
+#[derive(Clone, Debug)]
+pub struct Entries<'a>(core::slice::Iter<'a, (&'a ObjectIdentifier, &'a str, &'a str)>);
+
+impl<'a> Iterator for Entries<'a> {
+    type Item = Entry<'a>;
+
+    fn next(&mut self) -> Option<Entry<'a>> {
+        self.0
+            .next()
+            .map(|&(oid, name, source)| Entry { oid, name, source })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl ExactSizeIterator for Entries<'_> {}
+
 /// Iterator returning the multiple names that may be associated with an OID.
 pub struct Names<'a> {
     database: Database<'a>,
@@ -126,7 +215,7 @@ impl<'a> Iterator for Names<'a> {
 mod tests {
     use crate::ObjectIdentifier;
 
-    use super::rfc4519::CN;
+    use super::{rfc4519::CN, rfc5912};
 
     #[test]
     fn by_oid() {
@@ -144,4 +233,45 @@ mod tests {
 
         assert_eq!(None, super::DB.by_name("purplePeopleEater"));
     }
+
+    #[test]
+    fn iter() {
+        assert!(super::DB.iter().len() > 1000);
+
+        let cn = super::DB
+            .iter()
+            .find(|entry| entry.oid() == &CN)
+            .expect("cn not found");
+        assert_eq!(cn.name(), "cn");
+        assert_eq!(cn.source(), "rfc4519");
+    }
+
+    #[test]
+    fn by_source() {
+        let mut entries = super::DB.by_source("RFC5912");
+        assert!(entries.all(|entry| entry.source() == "rfc5912"));
+
+        let sha256 = super::DB
+            .by_source("rfc5912")
+            .find(|entry| entry.name() == "id-sha256")
+            .expect("id-sha256 not found");
+        assert_eq!(sha256.oid(), &rfc5912::ID_SHA_256);
+
+        assert_eq!(super::DB.by_source("rfc0000").count(), 0);
+    }
+
+    #[test]
+    fn by_arc() {
+        let pkcs1 = super::DB.by_arc(rfc5912::PKCS_1);
+        assert!(
+            pkcs1
+                .clone()
+                .all(|entry| entry.oid().starts_with(rfc5912::PKCS_1))
+        );
+
+        let has_name = |name| pkcs1.clone().any(|entry| entry.name() == name);
+        assert!(has_name("pkcs-1"));
+        assert!(has_name("sha256WithRSAEncryption"));
+        assert!(!has_name("id-sha256"));
+    }
 }
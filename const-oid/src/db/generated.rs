@@ -3061,2555 +3061,4252 @@ pub mod tcgtpm {
         crate::ObjectIdentifier::new_unwrap("2.23.133.8.3");
 }
 pub const DB: super::Database<'static> = super::Database(&[
-    (&bake::BAKE, "bake"),
-    (&bake::BAKE_KDF, "bake-kdf"),
-    (&bake::BAKE_BMQV, "bake-bmqv"),
-    (&bake::BAKE_BSTS, "bake-bsts"),
-    (&bake::BAKE_KEYS, "bake-keys"),
-    (&bake::BAKE_PUBKEY, "bake-pubkey"),
-    (&bake::BAKE_SWU, "bake-swu"),
-    (&bake::BAKE_BPACE, "bake-bpace"),
-    (&bake::BAKE_DH, "bake-dh"),
-    (&bash::BASH, "bash"),
-    (&bash::BASH_F, "bash-f"),
-    (&bash::BASH_256, "bash256"),
-    (&bash::BASH_384, "bash384"),
-    (&bash::BASH_512, "bash512"),
-    (&bash::BASH_PRG_HASH_2561, "bash-prg-hash2561"),
-    (&bash::BASH_PRG_HASH_2562, "bash-prg-hash2562"),
-    (&bash::BASH_PRG_HASH_3841, "bash-prg-hash3841"),
-    (&bash::BASH_PRG_HASH_3842, "bash-prg-hash3842"),
-    (&bash::BASH_PRG_HASH_5121, "bash-prg-hash5121"),
-    (&bash::BASH_PRG_HASH_5122, "bash-prg-hash5122"),
-    (&bash::BASH_PRG_AE_1281, "bash-prg-ae1281"),
-    (&bash::BASH_PRG_AE_1282, "bash-prg-ae1282"),
-    (&bash::BASH_PRG_AE_1921, "bash-prg-ae1921"),
-    (&bash::BASH_PRG_AE_1922, "bash-prg-ae1922"),
-    (&bash::BASH_PRG_AE_2561, "bash-prg-ae2561"),
-    (&bash::BASH_PRG_AE_2562, "bash-prg-ae2562"),
-    (&belt::BELT, "belt"),
-    (&belt::BELT_KEYREP, "belt-keyrep"),
-    (&belt::BELT_ECB_128, "belt-ecb128"),
-    (&belt::BELT_BDE_128, "belt-bde128"),
-    (&belt::BELT_BDE_192, "belt-bde192"),
-    (&belt::BELT_BDE_256, "belt-bde256"),
-    (&belt::BELT_ECB_192, "belt-ecb192"),
-    (&belt::BELT_SDE_128, "belt-sde128"),
-    (&belt::BELT_SDE_192, "belt-sde192"),
-    (&belt::BELT_SDE_256, "belt-sde256"),
-    (&belt::BELT_ECB_256, "belt-ecb256"),
-    (&belt::BELT_FMT_128, "belt-fmt128"),
-    (&belt::BELT_FMT_192, "belt-fmt192"),
-    (&belt::BELT_FMT_256, "belt-fmt256"),
-    (&belt::BELT_CBC_128, "belt-cbc128"),
-    (&belt::BELT_CBC_192, "belt-cbc192"),
-    (&belt::BELT_CBC_256, "belt-cbc256"),
-    (&belt::BELT_BLOCK_128, "belt-block128"),
-    (&belt::BELT_CFB_128, "belt-cfb128"),
-    (&belt::BELT_CFB_192, "belt-cfb192"),
-    (&belt::BELT_CFB_256, "belt-cfb256"),
-    (&belt::BELT_BLOCK_192, "belt-block192"),
-    (&belt::BELT_CTR_128, "belt-ctr128"),
-    (&belt::BELT_CTR_192, "belt-ctr192"),
-    (&belt::BELT_CTR_256, "belt-ctr256"),
-    (&belt::BELT_BLOCK_256, "belt-block256"),
-    (&belt::BELT_MAC_128, "belt-mac128"),
-    (&belt::BELT_MAC_192, "belt-mac192"),
-    (&belt::BELT_MAC_256, "belt-mac256"),
-    (&belt::BELT_WBLOCK_128, "belt-wblock128"),
-    (&belt::BELT_DWP_128, "belt-dwp128"),
-    (&belt::BELT_DWP_192, "belt-dwp192"),
-    (&belt::BELT_DWP_256, "belt-dwp256"),
-    (&belt::BELT_CHE_128, "belt-che128"),
-    (&belt::BELT_CHE_192, "belt-che192"),
-    (&belt::BELT_CHE_256, "belt-che256"),
-    (&belt::BELT_WBLOCK_192, "belt-wblock192"),
-    (&belt::BELT_KWP_128, "belt-kwp128"),
-    (&belt::BELT_KWP_192, "belt-kwp192"),
-    (&belt::BELT_KWP_256, "belt-kwp256"),
-    (&belt::BELT_WBLOCK_256, "belt-wblock256"),
-    (&belt::BELT_HASH, "belt-hash"),
-    (&belt::BELT_COMPRESS, "belt-compress"),
-    (&belt::BELT_KEYEXPAND, "belt-keyexpand"),
-    (&bign::BIGN, "bign"),
-    (&bign::BIGN_WITH_HSPEC, "bign-with-hspec"),
-    (&bign::BIGN_WITH_HBELT, "bign-with-hbelt"),
-    (&bign::BIGN_KEYS, "bign-keys"),
-    (&bign::BIGN_PUBKEY, "bign-pubkey"),
-    (&bign::BIGN_GENEC, "bign-genec"),
-    (&bign::BIGN_VALEC, "bign-valec"),
-    (&bign::BIGN_CURVES, "bign-curves"),
-    (&bign::BIGN_CURVE_256_V_1, "bign-curve256v1"),
-    (&bign::BIGN_CURVE_384_V_1, "bign-curve384v1"),
-    (&bign::BIGN_CURVE_512_V_1, "bign-curve512v1"),
-    (&bign::BIGN_GENKEYPAIR, "bign-genkeypair"),
-    (&bign::BIGN_VALPUBKEY, "bign-valpubkey"),
-    (&bign::BIGN_FIELDS, "bign-fields"),
-    (&bign::BIGN_PRIMEFIELD, "bign-primefield"),
-    (&bign::BIGN_KEYTRANSPORT, "bign-keytransport"),
-    (&bign::BIGN_GENK, "bign-genk"),
-    (&bign::BIGN_IBS_WITH_HSPEC, "bign-ibs-with-hspec"),
-    (&bign::BIGN_IBS_WITH_HBELT, "bign-ibs-with-hbelt"),
-    (&bpki::BPKI, "bpki"),
-    (&bpki::BPKI_ROLE, "bpki-role"),
-    (&bpki::BPKI_ROLE_CA_0, "bpki-role-ca0"),
-    (&bpki::BPKI_ROLE_CA_1, "bpki-role-ca1"),
-    (&bpki::BPKI_ROLE_AA, "bpki-role-aa"),
-    (&bpki::BPKI_ROLE_CA_2, "bpki-role-ca2"),
-    (&bpki::BPKI_ROLE_RA, "bpki-role-ra"),
-    (&bpki::BPKI_ROLE_OCSP, "bpki-role-ocsp"),
-    (&bpki::BPKI_ROLE_TSA, "bpki-role-tsa"),
-    (&bpki::BPKI_ROLE_DVCS, "bpki-role-dvcs"),
-    (&bpki::BPKI_ROLE_IDS, "bpki-role-ids"),
-    (&bpki::BPKI_ROLE_TLS, "bpki-role-tls"),
-    (&bpki::BPKI_ROLE_NP, "bpki-role-np"),
-    (&bpki::BPKI_ROLE_FNP, "bpki-role-fnp"),
-    (&bpki::BPKI_ROLE_LR, "bpki-role-lr"),
-    (&bpki::BPKI_ROLE_ACD, "bpki-role-acd"),
-    (&bpki::BPKI_EKU, "bpki-eku"),
-    (&bpki::BPKI_EKU_SERVER_TM, "bpki-eku-serverTM"),
-    (&bpki::BPKI_EKU_CLIENT_TM, "bpki-eku-clientTM"),
-    (&bpki::BPKI_AT, "bpki-at"),
+    (&bake::BAKE, "bake", "bake"),
+    (&bake::BAKE_KDF, "bake-kdf", "bake"),
+    (&bake::BAKE_BMQV, "bake-bmqv", "bake"),
+    (&bake::BAKE_BSTS, "bake-bsts", "bake"),
+    (&bake::BAKE_KEYS, "bake-keys", "bake"),
+    (&bake::BAKE_PUBKEY, "bake-pubkey", "bake"),
+    (&bake::BAKE_SWU, "bake-swu", "bake"),
+    (&bake::BAKE_BPACE, "bake-bpace", "bake"),
+    (&bake::BAKE_DH, "bake-dh", "bake"),
+    (&bash::BASH, "bash", "bash"),
+    (&bash::BASH_F, "bash-f", "bash"),
+    (&bash::BASH_256, "bash256", "bash"),
+    (&bash::BASH_384, "bash384", "bash"),
+    (&bash::BASH_512, "bash512", "bash"),
+    (&bash::BASH_PRG_HASH_2561, "bash-prg-hash2561", "bash"),
+    (&bash::BASH_PRG_HASH_2562, "bash-prg-hash2562", "bash"),
+    (&bash::BASH_PRG_HASH_3841, "bash-prg-hash3841", "bash"),
+    (&bash::BASH_PRG_HASH_3842, "bash-prg-hash3842", "bash"),
+    (&bash::BASH_PRG_HASH_5121, "bash-prg-hash5121", "bash"),
+    (&bash::BASH_PRG_HASH_5122, "bash-prg-hash5122", "bash"),
+    (&bash::BASH_PRG_AE_1281, "bash-prg-ae1281", "bash"),
+    (&bash::BASH_PRG_AE_1282, "bash-prg-ae1282", "bash"),
+    (&bash::BASH_PRG_AE_1921, "bash-prg-ae1921", "bash"),
+    (&bash::BASH_PRG_AE_1922, "bash-prg-ae1922", "bash"),
+    (&bash::BASH_PRG_AE_2561, "bash-prg-ae2561", "bash"),
+    (&bash::BASH_PRG_AE_2562, "bash-prg-ae2562", "bash"),
+    (&belt::BELT, "belt", "belt"),
+    (&belt::BELT_KEYREP, "belt-keyrep", "belt"),
+    (&belt::BELT_ECB_128, "belt-ecb128", "belt"),
+    (&belt::BELT_BDE_128, "belt-bde128", "belt"),
+    (&belt::BELT_BDE_192, "belt-bde192", "belt"),
+    (&belt::BELT_BDE_256, "belt-bde256", "belt"),
+    (&belt::BELT_ECB_192, "belt-ecb192", "belt"),
+    (&belt::BELT_SDE_128, "belt-sde128", "belt"),
+    (&belt::BELT_SDE_192, "belt-sde192", "belt"),
+    (&belt::BELT_SDE_256, "belt-sde256", "belt"),
+    (&belt::BELT_ECB_256, "belt-ecb256", "belt"),
+    (&belt::BELT_FMT_128, "belt-fmt128", "belt"),
+    (&belt::BELT_FMT_192, "belt-fmt192", "belt"),
+    (&belt::BELT_FMT_256, "belt-fmt256", "belt"),
+    (&belt::BELT_CBC_128, "belt-cbc128", "belt"),
+    (&belt::BELT_CBC_192, "belt-cbc192", "belt"),
+    (&belt::BELT_CBC_256, "belt-cbc256", "belt"),
+    (&belt::BELT_BLOCK_128, "belt-block128", "belt"),
+    (&belt::BELT_CFB_128, "belt-cfb128", "belt"),
+    (&belt::BELT_CFB_192, "belt-cfb192", "belt"),
+    (&belt::BELT_CFB_256, "belt-cfb256", "belt"),
+    (&belt::BELT_BLOCK_192, "belt-block192", "belt"),
+    (&belt::BELT_CTR_128, "belt-ctr128", "belt"),
+    (&belt::BELT_CTR_192, "belt-ctr192", "belt"),
+    (&belt::BELT_CTR_256, "belt-ctr256", "belt"),
+    (&belt::BELT_BLOCK_256, "belt-block256", "belt"),
+    (&belt::BELT_MAC_128, "belt-mac128", "belt"),
+    (&belt::BELT_MAC_192, "belt-mac192", "belt"),
+    (&belt::BELT_MAC_256, "belt-mac256", "belt"),
+    (&belt::BELT_WBLOCK_128, "belt-wblock128", "belt"),
+    (&belt::BELT_DWP_128, "belt-dwp128", "belt"),
+    (&belt::BELT_DWP_192, "belt-dwp192", "belt"),
+    (&belt::BELT_DWP_256, "belt-dwp256", "belt"),
+    (&belt::BELT_CHE_128, "belt-che128", "belt"),
+    (&belt::BELT_CHE_192, "belt-che192", "belt"),
+    (&belt::BELT_CHE_256, "belt-che256", "belt"),
+    (&belt::BELT_WBLOCK_192, "belt-wblock192", "belt"),
+    (&belt::BELT_KWP_128, "belt-kwp128", "belt"),
+    (&belt::BELT_KWP_192, "belt-kwp192", "belt"),
+    (&belt::BELT_KWP_256, "belt-kwp256", "belt"),
+    (&belt::BELT_WBLOCK_256, "belt-wblock256", "belt"),
+    (&belt::BELT_HASH, "belt-hash", "belt"),
+    (&belt::BELT_COMPRESS, "belt-compress", "belt"),
+    (&belt::BELT_KEYEXPAND, "belt-keyexpand", "belt"),
+    (&bign::BIGN, "bign", "bign"),
+    (&bign::BIGN_WITH_HSPEC, "bign-with-hspec", "bign"),
+    (&bign::BIGN_WITH_HBELT, "bign-with-hbelt", "bign"),
+    (&bign::BIGN_KEYS, "bign-keys", "bign"),
+    (&bign::BIGN_PUBKEY, "bign-pubkey", "bign"),
+    (&bign::BIGN_GENEC, "bign-genec", "bign"),
+    (&bign::BIGN_VALEC, "bign-valec", "bign"),
+    (&bign::BIGN_CURVES, "bign-curves", "bign"),
+    (&bign::BIGN_CURVE_256_V_1, "bign-curve256v1", "bign"),
+    (&bign::BIGN_CURVE_384_V_1, "bign-curve384v1", "bign"),
+    (&bign::BIGN_CURVE_512_V_1, "bign-curve512v1", "bign"),
+    (&bign::BIGN_GENKEYPAIR, "bign-genkeypair", "bign"),
+    (&bign::BIGN_VALPUBKEY, "bign-valpubkey", "bign"),
+    (&bign::BIGN_FIELDS, "bign-fields", "bign"),
+    (&bign::BIGN_PRIMEFIELD, "bign-primefield", "bign"),
+    (&bign::BIGN_KEYTRANSPORT, "bign-keytransport", "bign"),
+    (&bign::BIGN_GENK, "bign-genk", "bign"),
+    (&bign::BIGN_IBS_WITH_HSPEC, "bign-ibs-with-hspec", "bign"),
+    (&bign::BIGN_IBS_WITH_HBELT, "bign-ibs-with-hbelt", "bign"),
+    (&bpki::BPKI, "bpki", "bpki"),
+    (&bpki::BPKI_ROLE, "bpki-role", "bpki"),
+    (&bpki::BPKI_ROLE_CA_0, "bpki-role-ca0", "bpki"),
+    (&bpki::BPKI_ROLE_CA_1, "bpki-role-ca1", "bpki"),
+    (&bpki::BPKI_ROLE_AA, "bpki-role-aa", "bpki"),
+    (&bpki::BPKI_ROLE_CA_2, "bpki-role-ca2", "bpki"),
+    (&bpki::BPKI_ROLE_RA, "bpki-role-ra", "bpki"),
+    (&bpki::BPKI_ROLE_OCSP, "bpki-role-ocsp", "bpki"),
+    (&bpki::BPKI_ROLE_TSA, "bpki-role-tsa", "bpki"),
+    (&bpki::BPKI_ROLE_DVCS, "bpki-role-dvcs", "bpki"),
+    (&bpki::BPKI_ROLE_IDS, "bpki-role-ids", "bpki"),
+    (&bpki::BPKI_ROLE_TLS, "bpki-role-tls", "bpki"),
+    (&bpki::BPKI_ROLE_NP, "bpki-role-np", "bpki"),
+    (&bpki::BPKI_ROLE_FNP, "bpki-role-fnp", "bpki"),
+    (&bpki::BPKI_ROLE_LR, "bpki-role-lr", "bpki"),
+    (&bpki::BPKI_ROLE_ACD, "bpki-role-acd", "bpki"),
+    (&bpki::BPKI_EKU, "bpki-eku", "bpki"),
+    (&bpki::BPKI_EKU_SERVER_TM, "bpki-eku-serverTM", "bpki"),
+    (&bpki::BPKI_EKU_CLIENT_TM, "bpki-eku-clientTM", "bpki"),
+    (&bpki::BPKI_AT, "bpki-at", "bpki"),
     (
         &bpki::BPKI_AT_CERTIFICATE_VALIDITY,
         "bpki-at-certificateValidity",
-    ),
-    (&bpki::BPKI_CT, "bpki-ct"),
-    (&bpki::BPKI_CT_ENROLL_1_REQ, "bpki-ct-enroll1-req"),
-    (&bpki::BPKI_CT_ENROLL_2_REQ, "bpki-ct-enroll2-req"),
-    (&bpki::BPKI_CT_REENROLL_REQ, "bpki-ct-reenroll-req"),
-    (&bpki::BPKI_CT_SPAWN_REQ, "bpki-ct-spawn-req"),
-    (&bpki::BPKI_CT_SETPWD_REQ, "bpki-ct-setpwd-req"),
-    (&bpki::BPKI_CT_REVOKE_REQ, "bpki-ct-revoke-req"),
-    (&bpki::BPKI_CT_RESP, "bpki-ct-resp"),
-    (&brng::BRNG, "brng"),
-    (&brng::HMAC_HSPEC, "hmac-hspec"),
-    (&brng::HOTP_HSPEC, "hotp-hspec"),
-    (&brng::HOTP_HBELT, "hotp-hbelt"),
-    (&brng::HMAC_HBELT, "hmac-hbelt"),
-    (&brng::TOTP_HSPEC, "totp-hspec"),
-    (&brng::TOTP_HBELT, "totp-hbelt"),
-    (&brng::OCRA_HSPEC, "ocra-hspec"),
-    (&brng::OCRA_HBELT, "ocra-hbelt"),
-    (&brng::BRNG_CTR_HSPEC, "brng-ctr-hspec"),
-    (&brng::BRNG_CTR_HBELT, "brng-ctr-hbelt"),
-    (&brng::BRNG_CTR_STB_11761, "brng-ctr-stb11761"),
-    (&brng::BRNG_HMAC_HSPEC, "brng-hmac-hspec"),
-    (&brng::BRNG_HMAC_HBELT, "brng-hmac-hbelt"),
-    (&btok::BTOK, "btok"),
-    (&btok::BTOK_BAUTH, "btok-bauth"),
-    (&btok::BTOK_BAUTH_1, "btok-bauth1"),
-    (&btok::BTOK_ATTRS, "btok-attrs"),
-    (&btok::ID_DOCUMENT_VALIDITY, "id-DocumentValidity"),
-    (&btok::ID_AGE_VERIFICATION, "id-AgeVerification"),
-    (&btok::ID_PLACE_VERIFICATION, "id-PlaceVerification"),
-    (&btok::BTOK_ACCESS, "btok-access"),
-    (&btok::ID_E_ID_ACCESS, "id-eIdAccess"),
-    (&btok::ID_E_SIGN_ACCESS, "id-eSignAccess"),
-    (&btok::BTOK_APPS, "btok-apps"),
-    (&btok::ID_EID, "id-eID"),
-    (&btok::ID_E_SIGN, "id-eSign"),
-    (&btok::BTOK_CVEXT, "btok-cvext"),
-    (&btok::ID_SIGN_AUTH_EXT, "id-SignAuthExt"),
-    (&fips202::NIST_ALGORITHMS, "nistAlgorithms"),
-    (&fips202::HASH_ALGS, "hashAlgs"),
-    (&fips202::ID_SHA_3_512, "id-sha3-512"),
-    (&fips202::ID_SHAKE_128, "id-shake128"),
-    (&fips202::ID_SHAKE_256, "id-shake256"),
-    (&fips202::ID_SHA_3_224, "id-sha3-224"),
-    (&fips202::ID_SHA_3_256, "id-sha3-256"),
-    (&fips202::ID_SHA_3_384, "id-sha3-384"),
-    (&fips203::NIST_ALGORITHMS, "nistAlgorithms"),
-    (&fips203::KEMS, "kems"),
-    (&fips203::ID_ALG_ML_KEM_512, "id-alg-ml-kem-512"),
-    (&fips203::ID_ALG_ML_KEM_768, "id-alg-ml-kem-768"),
-    (&fips203::ID_ALG_ML_KEM_1024, "id-alg-ml-kem-1024"),
-    (&fips204::NIST_ALGORITHMS, "nistAlgorithms"),
-    (&fips204::SIG_ALGS, "sigAlgs"),
-    (&fips204::ID_ML_DSA_44, "id-ml-dsa-44"),
-    (&fips204::ID_ML_DSA_65, "id-ml-dsa-65"),
-    (&fips204::ID_ML_DSA_87, "id-ml-dsa-87"),
+        "bpki",
+    ),
+    (&bpki::BPKI_CT, "bpki-ct", "bpki"),
+    (&bpki::BPKI_CT_ENROLL_1_REQ, "bpki-ct-enroll1-req", "bpki"),
+    (&bpki::BPKI_CT_ENROLL_2_REQ, "bpki-ct-enroll2-req", "bpki"),
+    (&bpki::BPKI_CT_REENROLL_REQ, "bpki-ct-reenroll-req", "bpki"),
+    (&bpki::BPKI_CT_SPAWN_REQ, "bpki-ct-spawn-req", "bpki"),
+    (&bpki::BPKI_CT_SETPWD_REQ, "bpki-ct-setpwd-req", "bpki"),
+    (&bpki::BPKI_CT_REVOKE_REQ, "bpki-ct-revoke-req", "bpki"),
+    (&bpki::BPKI_CT_RESP, "bpki-ct-resp", "bpki"),
+    (&brng::BRNG, "brng", "brng"),
+    (&brng::HMAC_HSPEC, "hmac-hspec", "brng"),
+    (&brng::HOTP_HSPEC, "hotp-hspec", "brng"),
+    (&brng::HOTP_HBELT, "hotp-hbelt", "brng"),
+    (&brng::HMAC_HBELT, "hmac-hbelt", "brng"),
+    (&brng::TOTP_HSPEC, "totp-hspec", "brng"),
+    (&brng::TOTP_HBELT, "totp-hbelt", "brng"),
+    (&brng::OCRA_HSPEC, "ocra-hspec", "brng"),
+    (&brng::OCRA_HBELT, "ocra-hbelt", "brng"),
+    (&brng::BRNG_CTR_HSPEC, "brng-ctr-hspec", "brng"),
+    (&brng::BRNG_CTR_HBELT, "brng-ctr-hbelt", "brng"),
+    (&brng::BRNG_CTR_STB_11761, "brng-ctr-stb11761", "brng"),
+    (&brng::BRNG_HMAC_HSPEC, "brng-hmac-hspec", "brng"),
+    (&brng::BRNG_HMAC_HBELT, "brng-hmac-hbelt", "brng"),
+    (&btok::BTOK, "btok", "btok"),
+    (&btok::BTOK_BAUTH, "btok-bauth", "btok"),
+    (&btok::BTOK_BAUTH_1, "btok-bauth1", "btok"),
+    (&btok::BTOK_ATTRS, "btok-attrs", "btok"),
+    (&btok::ID_DOCUMENT_VALIDITY, "id-DocumentValidity", "btok"),
+    (&btok::ID_AGE_VERIFICATION, "id-AgeVerification", "btok"),
+    (&btok::ID_PLACE_VERIFICATION, "id-PlaceVerification", "btok"),
+    (&btok::BTOK_ACCESS, "btok-access", "btok"),
+    (&btok::ID_E_ID_ACCESS, "id-eIdAccess", "btok"),
+    (&btok::ID_E_SIGN_ACCESS, "id-eSignAccess", "btok"),
+    (&btok::BTOK_APPS, "btok-apps", "btok"),
+    (&btok::ID_EID, "id-eID", "btok"),
+    (&btok::ID_E_SIGN, "id-eSign", "btok"),
+    (&btok::BTOK_CVEXT, "btok-cvext", "btok"),
+    (&btok::ID_SIGN_AUTH_EXT, "id-SignAuthExt", "btok"),
+    (&fips202::NIST_ALGORITHMS, "nistAlgorithms", "fips202"),
+    (&fips202::HASH_ALGS, "hashAlgs", "fips202"),
+    (&fips202::ID_SHA_3_512, "id-sha3-512", "fips202"),
+    (&fips202::ID_SHAKE_128, "id-shake128", "fips202"),
+    (&fips202::ID_SHAKE_256, "id-shake256", "fips202"),
+    (&fips202::ID_SHA_3_224, "id-sha3-224", "fips202"),
+    (&fips202::ID_SHA_3_256, "id-sha3-256", "fips202"),
+    (&fips202::ID_SHA_3_384, "id-sha3-384", "fips202"),
+    (&fips203::NIST_ALGORITHMS, "nistAlgorithms", "fips203"),
+    (&fips203::KEMS, "kems", "fips203"),
+    (&fips203::ID_ALG_ML_KEM_512, "id-alg-ml-kem-512", "fips203"),
+    (&fips203::ID_ALG_ML_KEM_768, "id-alg-ml-kem-768", "fips203"),
+    (
+        &fips203::ID_ALG_ML_KEM_1024,
+        "id-alg-ml-kem-1024",
+        "fips203",
+    ),
+    (&fips204::NIST_ALGORITHMS, "nistAlgorithms", "fips204"),
+    (&fips204::SIG_ALGS, "sigAlgs", "fips204"),
+    (&fips204::ID_ML_DSA_44, "id-ml-dsa-44", "fips204"),
+    (&fips204::ID_ML_DSA_65, "id-ml-dsa-65", "fips204"),
+    (&fips204::ID_ML_DSA_87, "id-ml-dsa-87", "fips204"),
     (
         &fips204::ID_HASH_ML_DSA_44_WITH_SHA_512,
         "id-hash-ml-dsa-44-with-sha512",
+        "fips204",
     ),
     (
         &fips204::ID_HASH_ML_DSA_65_WITH_SHA_512,
         "id-hash-ml-dsa-65-with-sha512",
+        "fips204",
     ),
     (
         &fips204::ID_HASH_ML_DSA_87_WITH_SHA_512,
         "id-hash-ml-dsa-87-with-sha512",
+        "fips204",
+    ),
+    (&fips205::NIST_ALGORITHMS, "nistAlgorithms", "fips205"),
+    (&fips205::SIG_ALGS, "sigAlgs", "fips205"),
+    (
+        &fips205::ID_SLH_DSA_SHA_2_128_S,
+        "id-slh-dsa-sha2-128s",
+        "fips205",
+    ),
+    (
+        &fips205::ID_SLH_DSA_SHA_2_128_F,
+        "id-slh-dsa-sha2-128f",
+        "fips205",
+    ),
+    (
+        &fips205::ID_SLH_DSA_SHA_2_192_S,
+        "id-slh-dsa-sha2-192s",
+        "fips205",
+    ),
+    (
+        &fips205::ID_SLH_DSA_SHA_2_192_F,
+        "id-slh-dsa-sha2-192f",
+        "fips205",
+    ),
+    (
+        &fips205::ID_SLH_DSA_SHA_2_256_S,
+        "id-slh-dsa-sha2-256s",
+        "fips205",
+    ),
+    (
+        &fips205::ID_SLH_DSA_SHA_2_256_F,
+        "id-slh-dsa-sha2-256f",
+        "fips205",
+    ),
+    (
+        &fips205::ID_SLH_DSA_SHAKE_128_S,
+        "id-slh-dsa-shake-128s",
+        "fips205",
+    ),
+    (
+        &fips205::ID_SLH_DSA_SHAKE_128_F,
+        "id-slh-dsa-shake-128f",
+        "fips205",
+    ),
+    (
+        &fips205::ID_SLH_DSA_SHAKE_192_S,
+        "id-slh-dsa-shake-192s",
+        "fips205",
+    ),
+    (
+        &fips205::ID_SLH_DSA_SHAKE_192_F,
+        "id-slh-dsa-shake-192f",
+        "fips205",
+    ),
+    (
+        &fips205::ID_SLH_DSA_SHAKE_256_S,
+        "id-slh-dsa-shake-256s",
+        "fips205",
+    ),
+    (
+        &fips205::ID_SLH_DSA_SHAKE_256_F,
+        "id-slh-dsa-shake-256f",
+        "fips205",
     ),
-    (&fips205::NIST_ALGORITHMS, "nistAlgorithms"),
-    (&fips205::SIG_ALGS, "sigAlgs"),
-    (&fips205::ID_SLH_DSA_SHA_2_128_S, "id-slh-dsa-sha2-128s"),
-    (&fips205::ID_SLH_DSA_SHA_2_128_F, "id-slh-dsa-sha2-128f"),
-    (&fips205::ID_SLH_DSA_SHA_2_192_S, "id-slh-dsa-sha2-192s"),
-    (&fips205::ID_SLH_DSA_SHA_2_192_F, "id-slh-dsa-sha2-192f"),
-    (&fips205::ID_SLH_DSA_SHA_2_256_S, "id-slh-dsa-sha2-256s"),
-    (&fips205::ID_SLH_DSA_SHA_2_256_F, "id-slh-dsa-sha2-256f"),
-    (&fips205::ID_SLH_DSA_SHAKE_128_S, "id-slh-dsa-shake-128s"),
-    (&fips205::ID_SLH_DSA_SHAKE_128_F, "id-slh-dsa-shake-128f"),
-    (&fips205::ID_SLH_DSA_SHAKE_192_S, "id-slh-dsa-shake-192s"),
-    (&fips205::ID_SLH_DSA_SHAKE_192_F, "id-slh-dsa-shake-192f"),
-    (&fips205::ID_SLH_DSA_SHAKE_256_S, "id-slh-dsa-shake-256s"),
-    (&fips205::ID_SLH_DSA_SHAKE_256_F, "id-slh-dsa-shake-256f"),
     (
         &fips205::ID_HASH_SLH_DSA_SHA_2_128_S_WITH_SHA_256,
         "id-hash-slh-dsa-sha2-128s-with-sha256",
+        "fips205",
     ),
     (
         &fips205::ID_HASH_SLH_DSA_SHA_2_128_F_WITH_SHA_256,
         "id-hash-slh-dsa-sha2-128f-with-sha256",
+        "fips205",
     ),
     (
         &fips205::ID_HASH_SLH_DSA_SHA_2_192_S_WITH_SHA_512,
         "id-hash-slh-dsa-sha2-192s-with-sha512",
+        "fips205",
     ),
     (
         &fips205::ID_HASH_SLH_DSA_SHA_2_192_F_WITH_SHA_512,
         "id-hash-slh-dsa-sha2-192f-with-sha512",
+        "fips205",
     ),
     (
         &fips205::ID_HASH_SLH_DSA_SHA_2_256_S_WITH_SHA_512,
         "id-hash-slh-dsa-sha2-256s-with-sha512",
+        "fips205",
     ),
     (
         &fips205::ID_HASH_SLH_DSA_SHA_2_256_F_WITH_SHA_512,
         "id-hash-slh-dsa-sha2-256f-with-sha512",
+        "fips205",
     ),
     (
         &fips205::ID_HASH_SLH_DSA_SHAKE_128_S_WITH_SHAKE_128,
         "id-hash-slh-dsa-shake-128s-with-shake128",
+        "fips205",
     ),
     (
         &fips205::ID_HASH_SLH_DSA_SHAKE_128_F_WITH_SHAKE_128,
         "id-hash-slh-dsa-shake-128f-with-shake128",
+        "fips205",
     ),
     (
         &fips205::ID_HASH_SLH_DSA_SHAKE_192_S_WITH_SHAKE_256,
         "id-hash-slh-dsa-shake-192s-with-shake256",
+        "fips205",
     ),
     (
         &fips205::ID_HASH_SLH_DSA_SHAKE_192_F_WITH_SHAKE_256,
         "id-hash-slh-dsa-shake-192f-with-shake256",
+        "fips205",
     ),
     (
         &fips205::ID_HASH_SLH_DSA_SHAKE_256_S_WITH_SHAKE_256,
         "id-hash-slh-dsa-shake-256s-with-shake256",
+        "fips205",
     ),
     (
         &fips205::ID_HASH_SLH_DSA_SHAKE_256_F_WITH_SHAKE_256,
         "id-hash-slh-dsa-shake-256f-with-shake256",
+        "fips205",
+    ),
+    (
+        &rfc1274::TEXT_ENCODED_OR_ADDRESS,
+        "textEncodedORAddress",
+        "rfc1274",
+    ),
+    (&rfc1274::OTHER_MAILBOX, "otherMailbox", "rfc1274"),
+    (&rfc1274::LAST_MODIFIED_TIME, "lastModifiedTime", "rfc1274"),
+    (&rfc1274::LAST_MODIFIED_BY, "lastModifiedBy", "rfc1274"),
+    (&rfc1274::A_RECORD, "aRecord", "rfc1274"),
+    (&rfc1274::MD_RECORD, "mDRecord", "rfc1274"),
+    (&rfc1274::MX_RECORD, "mXRecord", "rfc1274"),
+    (&rfc1274::NS_RECORD, "nSRecord", "rfc1274"),
+    (&rfc1274::SOA_RECORD, "sOARecord", "rfc1274"),
+    (&rfc1274::CNAME_RECORD, "cNAMERecord", "rfc1274"),
+    (&rfc1274::JANET_MAILBOX, "janetMailbox", "rfc1274"),
+    (
+        &rfc1274::MAIL_PREFERENCE_OPTION,
+        "mailPreferenceOption",
+        "rfc1274",
+    ),
+    (&rfc1274::DSA_QUALITY, "dSAQuality", "rfc1274"),
+    (
+        &rfc1274::SUBTREE_MINIMUM_QUALITY,
+        "subtreeMinimumQuality",
+        "rfc1274",
+    ),
+    (
+        &rfc1274::SUBTREE_MAXIMUM_QUALITY,
+        "subtreeMaximumQuality",
+        "rfc1274",
+    ),
+    (&rfc1274::PERSONAL_SIGNATURE, "personalSignature", "rfc1274"),
+    (&rfc1274::DIT_REDIRECT, "dITRedirect", "rfc1274"),
+    (&rfc1274::AUDIO, "audio", "rfc1274"),
+    (&rfc1274::PHOTO, "photo", "rfc1274"),
+    (&rfc1274::DNS_DOMAIN, "dNSDomain", "rfc1274"),
+    (&rfc1274::PILOT_ORGANIZATION, "pilotOrganization", "rfc1274"),
+    (&rfc1274::PILOT_DSA, "pilotDSA", "rfc1274"),
+    (
+        &rfc1274::QUALITY_LABELLED_DATA,
+        "qualityLabelledData",
+        "rfc1274",
+    ),
+    (&rfc1274::PILOT_OBJECT, "pilotObject", "rfc1274"),
+    (&rfc1274::PILOT_PERSON, "pilotPerson", "rfc1274"),
+    (&rfc2079::LABELED_URI, "labeledURI", "rfc2079"),
+    (&rfc2079::LABELED_URI_OBJECT, "labeledURIObject", "rfc2079"),
+    (
+        &rfc2164::RFC_822_TO_X_400_MAPPING,
+        "rFC822ToX400Mapping",
+        "rfc2164",
+    ),
+    (
+        &rfc2164::X_400_TO_RFC_822_MAPPING,
+        "x400ToRFC822Mapping",
+        "rfc2164",
     ),
-    (&rfc1274::TEXT_ENCODED_OR_ADDRESS, "textEncodedORAddress"),
-    (&rfc1274::OTHER_MAILBOX, "otherMailbox"),
-    (&rfc1274::LAST_MODIFIED_TIME, "lastModifiedTime"),
-    (&rfc1274::LAST_MODIFIED_BY, "lastModifiedBy"),
-    (&rfc1274::A_RECORD, "aRecord"),
-    (&rfc1274::MD_RECORD, "mDRecord"),
-    (&rfc1274::MX_RECORD, "mXRecord"),
-    (&rfc1274::NS_RECORD, "nSRecord"),
-    (&rfc1274::SOA_RECORD, "sOARecord"),
-    (&rfc1274::CNAME_RECORD, "cNAMERecord"),
-    (&rfc1274::JANET_MAILBOX, "janetMailbox"),
-    (&rfc1274::MAIL_PREFERENCE_OPTION, "mailPreferenceOption"),
-    (&rfc1274::DSA_QUALITY, "dSAQuality"),
-    (&rfc1274::SUBTREE_MINIMUM_QUALITY, "subtreeMinimumQuality"),
-    (&rfc1274::SUBTREE_MAXIMUM_QUALITY, "subtreeMaximumQuality"),
-    (&rfc1274::PERSONAL_SIGNATURE, "personalSignature"),
-    (&rfc1274::DIT_REDIRECT, "dITRedirect"),
-    (&rfc1274::AUDIO, "audio"),
-    (&rfc1274::PHOTO, "photo"),
-    (&rfc1274::DNS_DOMAIN, "dNSDomain"),
-    (&rfc1274::PILOT_ORGANIZATION, "pilotOrganization"),
-    (&rfc1274::PILOT_DSA, "pilotDSA"),
-    (&rfc1274::QUALITY_LABELLED_DATA, "qualityLabelledData"),
-    (&rfc1274::PILOT_OBJECT, "pilotObject"),
-    (&rfc1274::PILOT_PERSON, "pilotPerson"),
-    (&rfc2079::LABELED_URI, "labeledURI"),
-    (&rfc2079::LABELED_URI_OBJECT, "labeledURIObject"),
-    (&rfc2164::RFC_822_TO_X_400_MAPPING, "rFC822ToX400Mapping"),
-    (&rfc2164::X_400_TO_RFC_822_MAPPING, "x400ToRFC822Mapping"),
     (
         &rfc2164::OMITTED_OR_ADDRESS_COMPONENT,
         "omittedORAddressComponent",
+        "rfc2164",
+    ),
+    (&rfc2164::MIXER_GATEWAY, "mixerGateway", "rfc2164"),
+    (
+        &rfc2164::ASSOCIATED_X_400_GATEWAY,
+        "associatedX400Gateway",
+        "rfc2164",
+    ),
+    (
+        &rfc2164::ASSOCIATED_OR_ADDRESS,
+        "associatedORAddress",
+        "rfc2164",
     ),
-    (&rfc2164::MIXER_GATEWAY, "mixerGateway"),
-    (&rfc2164::ASSOCIATED_X_400_GATEWAY, "associatedX400Gateway"),
-    (&rfc2164::ASSOCIATED_OR_ADDRESS, "associatedORAddress"),
     (
         &rfc2164::OR_ADDRESS_COMPONENT_TYPE,
         "oRAddressComponentType",
+        "rfc2164",
     ),
     (
         &rfc2164::ASSOCIATED_INTERNET_GATEWAY,
         "associatedInternetGateway",
+        "rfc2164",
     ),
-    (&rfc2164::MCGAM_TABLES, "mcgamTables"),
-    (&rfc2247::DOMAIN_NAME_FORM, "domainNameForm"),
+    (&rfc2164::MCGAM_TABLES, "mcgamTables", "rfc2164"),
+    (&rfc2247::DOMAIN_NAME_FORM, "domainNameForm", "rfc2247"),
     (
         &rfc2252::PRESENTATION_ADDRESS_MATCH,
         "presentationAddressMatch",
+        "rfc2252",
     ),
     (
         &rfc2252::PROTOCOL_INFORMATION_MATCH,
         "protocolInformationMatch",
+        "rfc2252",
+    ),
+    (
+        &rfc2256::KNOWLEDGE_INFORMATION,
+        "knowledgeInformation",
+        "rfc2256",
+    ),
+    (
+        &rfc2256::PRESENTATION_ADDRESS,
+        "presentationAddress",
+        "rfc2256",
     ),
-    (&rfc2256::KNOWLEDGE_INFORMATION, "knowledgeInformation"),
-    (&rfc2256::PRESENTATION_ADDRESS, "presentationAddress"),
     (
         &rfc2256::SUPPORTED_APPLICATION_CONTEXT,
         "supportedApplicationContext",
+        "rfc2256",
+    ),
+    (
+        &rfc2256::PROTOCOL_INFORMATION,
+        "protocolInformation",
+        "rfc2256",
+    ),
+    (&rfc2256::DMD_NAME, "dmdName", "rfc2256"),
+    (
+        &rfc2256::STATE_OR_PROVINCE_NAME,
+        "stateOrProvinceName",
+        "rfc2256",
     ),
-    (&rfc2256::PROTOCOL_INFORMATION, "protocolInformation"),
-    (&rfc2256::DMD_NAME, "dmdName"),
-    (&rfc2256::STATE_OR_PROVINCE_NAME, "stateOrProvinceName"),
-    (&rfc2256::STREET_ADDRESS, "streetAddress"),
-    (&rfc2256::APPLICATION_ENTITY, "applicationEntity"),
-    (&rfc2256::DSA, "dSA"),
-    (&rfc2256::DMD, "dmd"),
-    (&rfc2293::SUBTREE, "subtree"),
-    (&rfc2293::TABLE, "table"),
-    (&rfc2293::TABLE_ENTRY, "tableEntry"),
-    (&rfc2293::TEXT_TABLE_ENTRY, "textTableEntry"),
+    (&rfc2256::STREET_ADDRESS, "streetAddress", "rfc2256"),
+    (&rfc2256::APPLICATION_ENTITY, "applicationEntity", "rfc2256"),
+    (&rfc2256::DSA, "dSA", "rfc2256"),
+    (&rfc2256::DMD, "dmd", "rfc2256"),
+    (&rfc2293::SUBTREE, "subtree", "rfc2293"),
+    (&rfc2293::TABLE, "table", "rfc2293"),
+    (&rfc2293::TABLE_ENTRY, "tableEntry", "rfc2293"),
+    (&rfc2293::TEXT_TABLE_ENTRY, "textTableEntry", "rfc2293"),
     (
         &rfc2293::DISTINGUISHED_NAME_TABLE_ENTRY,
         "distinguishedNameTableEntry",
+        "rfc2293",
     ),
-    (&rfc2293::TEXT_TABLE_KEY, "textTableKey"),
-    (&rfc2293::TEXT_TABLE_VALUE, "textTableValue"),
+    (&rfc2293::TEXT_TABLE_KEY, "textTableKey", "rfc2293"),
+    (&rfc2293::TEXT_TABLE_VALUE, "textTableValue", "rfc2293"),
     (
         &rfc2293::DISTINGUISHED_NAME_TABLE_KEY,
         "distinguishedNameTableKey",
+        "rfc2293",
+    ),
+    (&rfc2589::DYNAMIC_OBJECT, "dynamicObject", "rfc2589"),
+    (&rfc2589::ENTRY_TTL, "entryTtl", "rfc2589"),
+    (&rfc2589::DYNAMIC_SUBTREES, "dynamicSubtrees", "rfc2589"),
+    (&rfc2739::CAL_CAL_URI, "calCalURI", "rfc2739"),
+    (&rfc2739::CAL_FBURL, "calFBURL", "rfc2739"),
+    (&rfc2739::CAL_CAPURI, "calCAPURI", "rfc2739"),
+    (&rfc2739::CAL_CAL_ADR_URI, "calCalAdrURI", "rfc2739"),
+    (&rfc2739::CAL_OTHER_CAL_UR_IS, "calOtherCalURIs", "rfc2739"),
+    (&rfc2739::CAL_OTHER_FBUR_LS, "calOtherFBURLs", "rfc2739"),
+    (&rfc2739::CAL_OTHER_CAPUR_IS, "calOtherCAPURIs", "rfc2739"),
+    (
+        &rfc2739::CAL_OTHER_CAL_ADR_UR_IS,
+        "calOtherCalAdrURIs",
+        "rfc2739",
+    ),
+    (&rfc2739::CAL_ENTRY, "calEntry", "rfc2739"),
+    (&rfc2798::JPEG_PHOTO, "jpegPhoto", "rfc2798"),
+    (&rfc2798::CAR_LICENSE, "carLicense", "rfc2798"),
+    (&rfc2798::DEPARTMENT_NUMBER, "departmentNumber", "rfc2798"),
+    (&rfc2798::USER_PKCS_12, "userPKCS12", "rfc2798"),
+    (&rfc2798::DISPLAY_NAME, "displayName", "rfc2798"),
+    (&rfc2798::EMPLOYEE_NUMBER, "employeeNumber", "rfc2798"),
+    (&rfc2798::PREFERRED_LANGUAGE, "preferredLanguage", "rfc2798"),
+    (&rfc2798::EMPLOYEE_TYPE, "employeeType", "rfc2798"),
+    (
+        &rfc2798::USER_SMIME_CERTIFICATE,
+        "userSMIMECertificate",
+        "rfc2798",
+    ),
+    (&rfc2798::INET_ORG_PERSON, "inetOrgPerson", "rfc2798"),
+    (&rfc2985::PKCS_9, "pkcs-9", "rfc2985"),
+    (&rfc2985::PKCS_9_MO, "pkcs-9-mo", "rfc2985"),
+    (
+        &rfc2985::PKCS_9_AT_EMAIL_ADDRESS,
+        "pkcs-9-at-emailAddress",
+        "rfc2985",
     ),
-    (&rfc2589::DYNAMIC_OBJECT, "dynamicObject"),
-    (&rfc2589::ENTRY_TTL, "entryTtl"),
-    (&rfc2589::DYNAMIC_SUBTREES, "dynamicSubtrees"),
-    (&rfc2739::CAL_CAL_URI, "calCalURI"),
-    (&rfc2739::CAL_FBURL, "calFBURL"),
-    (&rfc2739::CAL_CAPURI, "calCAPURI"),
-    (&rfc2739::CAL_CAL_ADR_URI, "calCalAdrURI"),
-    (&rfc2739::CAL_OTHER_CAL_UR_IS, "calOtherCalURIs"),
-    (&rfc2739::CAL_OTHER_FBUR_LS, "calOtherFBURLs"),
-    (&rfc2739::CAL_OTHER_CAPUR_IS, "calOtherCAPURIs"),
-    (&rfc2739::CAL_OTHER_CAL_ADR_UR_IS, "calOtherCalAdrURIs"),
-    (&rfc2739::CAL_ENTRY, "calEntry"),
-    (&rfc2798::JPEG_PHOTO, "jpegPhoto"),
-    (&rfc2798::CAR_LICENSE, "carLicense"),
-    (&rfc2798::DEPARTMENT_NUMBER, "departmentNumber"),
-    (&rfc2798::USER_PKCS_12, "userPKCS12"),
-    (&rfc2798::DISPLAY_NAME, "displayName"),
-    (&rfc2798::EMPLOYEE_NUMBER, "employeeNumber"),
-    (&rfc2798::PREFERRED_LANGUAGE, "preferredLanguage"),
-    (&rfc2798::EMPLOYEE_TYPE, "employeeType"),
-    (&rfc2798::USER_SMIME_CERTIFICATE, "userSMIMECertificate"),
-    (&rfc2798::INET_ORG_PERSON, "inetOrgPerson"),
-    (&rfc2985::PKCS_9, "pkcs-9"),
-    (&rfc2985::PKCS_9_MO, "pkcs-9-mo"),
-    (&rfc2985::PKCS_9_AT_EMAIL_ADDRESS, "pkcs-9-at-emailAddress"),
     (
         &rfc2985::PKCS_9_AT_ISSUER_AND_SERIAL_NUMBER,
         "pkcs-9-at-issuerAndSerialNumber",
+        "rfc2985",
     ),
     (
         &rfc2985::PKCS_9_AT_PASSWORD_CHECK,
         "pkcs-9-at-passwordCheck",
+        "rfc2985",
+    ),
+    (
+        &rfc2985::PKCS_9_AT_PUBLIC_KEY,
+        "pkcs-9-at-publicKey",
+        "rfc2985",
     ),
-    (&rfc2985::PKCS_9_AT_PUBLIC_KEY, "pkcs-9-at-publicKey"),
     (
         &rfc2985::PKCS_9_AT_SIGNING_DESCRIPTION,
         "pkcs-9-at-signingDescription",
+        "rfc2985",
     ),
     (
         &rfc2985::PKCS_9_AT_EXTENSION_REQUEST,
         "pkcs-9-at-extensionRequest",
+        "rfc2985",
     ),
     (
         &rfc2985::PKCS_9_AT_SMIME_CAPABILITIES,
         "pkcs-9-at-smimeCapabilities",
+        "rfc2985",
     ),
-    (&rfc2985::SMIME, "smime"),
+    (&rfc2985::SMIME, "smime", "rfc2985"),
     (
         &rfc2985::PKCS_9_AT_UNSTRUCTURED_NAME,
         "pkcs-9-at-unstructuredName",
+        "rfc2985",
+    ),
+    (
+        &rfc2985::PKCS_9_AT_FRIENDLY_NAME,
+        "pkcs-9-at-friendlyName",
+        "rfc2985",
+    ),
+    (
+        &rfc2985::PKCS_9_AT_LOCAL_KEY_ID,
+        "pkcs-9-at-localKeyId",
+        "rfc2985",
+    ),
+    (&rfc2985::CERT_TYPES, "certTypes", "rfc2985"),
+    (&rfc2985::CRL_TYPES, "crlTypes", "rfc2985"),
+    (&rfc2985::PKCS_9_OC, "pkcs-9-oc", "rfc2985"),
+    (
+        &rfc2985::PKCS_9_OC_PKCS_ENTITY,
+        "pkcs-9-oc-pkcsEntity",
+        "rfc2985",
     ),
-    (&rfc2985::PKCS_9_AT_FRIENDLY_NAME, "pkcs-9-at-friendlyName"),
-    (&rfc2985::PKCS_9_AT_LOCAL_KEY_ID, "pkcs-9-at-localKeyId"),
-    (&rfc2985::CERT_TYPES, "certTypes"),
-    (&rfc2985::CRL_TYPES, "crlTypes"),
-    (&rfc2985::PKCS_9_OC, "pkcs-9-oc"),
-    (&rfc2985::PKCS_9_OC_PKCS_ENTITY, "pkcs-9-oc-pkcsEntity"),
     (
         &rfc2985::PKCS_9_OC_NATURAL_PERSON,
         "pkcs-9-oc-naturalPerson",
+        "rfc2985",
+    ),
+    (&rfc2985::PKCS_9_AT, "pkcs-9-at", "rfc2985"),
+    (
+        &rfc2985::PKCS_9_AT_PKCS_15_TOKEN,
+        "pkcs-9-at-pkcs15Token",
+        "rfc2985",
     ),
-    (&rfc2985::PKCS_9_AT, "pkcs-9-at"),
-    (&rfc2985::PKCS_9_AT_PKCS_15_TOKEN, "pkcs-9-at-pkcs15Token"),
     (
         &rfc2985::PKCS_9_AT_ENCRYPTED_PRIVATE_KEY_INFO,
         "pkcs-9-at-encryptedPrivateKeyInfo",
+        "rfc2985",
+    ),
+    (
+        &rfc2985::PKCS_9_AT_RANDOM_NONCE,
+        "pkcs-9-at-randomNonce",
+        "rfc2985",
     ),
-    (&rfc2985::PKCS_9_AT_RANDOM_NONCE, "pkcs-9-at-randomNonce"),
     (
         &rfc2985::PKCS_9_AT_SEQUENCE_NUMBER,
         "pkcs-9-at-sequenceNumber",
+        "rfc2985",
+    ),
+    (
+        &rfc2985::PKCS_9_AT_PKCS_7_PDU,
+        "pkcs-9-at-pkcs7PDU",
+        "rfc2985",
+    ),
+    (&rfc2985::PKCS_9_SX, "pkcs-9-sx", "rfc2985"),
+    (
+        &rfc2985::PKCS_9_SX_PKCS_9_STRING,
+        "pkcs-9-sx-pkcs9String",
+        "rfc2985",
+    ),
+    (
+        &rfc2985::PKCS_9_SX_SIGNING_TIME,
+        "pkcs-9-sx-signingTime",
+        "rfc2985",
     ),
-    (&rfc2985::PKCS_9_AT_PKCS_7_PDU, "pkcs-9-at-pkcs7PDU"),
-    (&rfc2985::PKCS_9_SX, "pkcs-9-sx"),
-    (&rfc2985::PKCS_9_SX_PKCS_9_STRING, "pkcs-9-sx-pkcs9String"),
-    (&rfc2985::PKCS_9_SX_SIGNING_TIME, "pkcs-9-sx-signingTime"),
-    (&rfc2985::PKCS_9_MR, "pkcs-9-mr"),
+    (&rfc2985::PKCS_9_MR, "pkcs-9-mr", "rfc2985"),
     (
         &rfc2985::PKCS_9_MR_CASE_IGNORE_MATCH,
         "pkcs-9-mr-caseIgnoreMatch",
+        "rfc2985",
     ),
     (
         &rfc2985::PKCS_9_MR_SIGNING_TIME_MATCH,
         "pkcs-9-mr-signingTimeMatch",
+        "rfc2985",
+    ),
+    (
+        &rfc2985::PKCS_9_AT_CONTENT_TYPE,
+        "pkcs-9-at-contentType",
+        "rfc2985",
     ),
-    (&rfc2985::PKCS_9_AT_CONTENT_TYPE, "pkcs-9-at-contentType"),
     (
         &rfc2985::PKCS_9_AT_MESSAGE_DIGEST,
         "pkcs-9-at-messageDigest",
+        "rfc2985",
+    ),
+    (
+        &rfc2985::PKCS_9_AT_SIGNING_TIME,
+        "pkcs-9-at-signingTime",
+        "rfc2985",
     ),
-    (&rfc2985::PKCS_9_AT_SIGNING_TIME, "pkcs-9-at-signingTime"),
     (
         &rfc2985::PKCS_9_AT_COUNTER_SIGNATURE,
         "pkcs-9-at-counterSignature",
+        "rfc2985",
     ),
     (
         &rfc2985::PKCS_9_AT_CHALLENGE_PASSWORD,
         "pkcs-9-at-challengePassword",
+        "rfc2985",
     ),
     (
         &rfc2985::PKCS_9_AT_UNSTRUCTURED_ADDRESS,
         "pkcs-9-at-unstructuredAddress",
+        "rfc2985",
     ),
     (
         &rfc2985::PKCS_9_AT_EXTENDED_CERTIFICATE_ATTRIBUTES,
         "pkcs-9-at-extendedCertificateAttributes",
+        "rfc2985",
+    ),
+    (&rfc2985::IETF_AT, "ietf-at", "rfc2985"),
+    (
+        &rfc2985::PKCS_9_AT_DATE_OF_BIRTH,
+        "pkcs-9-at-dateOfBirth",
+        "rfc2985",
+    ),
+    (
+        &rfc2985::PKCS_9_AT_PLACE_OF_BIRTH,
+        "pkcs-9-at-placeOfBirth",
+        "rfc2985",
     ),
-    (&rfc2985::IETF_AT, "ietf-at"),
-    (&rfc2985::PKCS_9_AT_DATE_OF_BIRTH, "pkcs-9-at-dateOfBirth"),
-    (&rfc2985::PKCS_9_AT_PLACE_OF_BIRTH, "pkcs-9-at-placeOfBirth"),
-    (&rfc2985::PKCS_9_AT_GENDER, "pkcs-9-at-gender"),
+    (&rfc2985::PKCS_9_AT_GENDER, "pkcs-9-at-gender", "rfc2985"),
     (
         &rfc2985::PKCS_9_AT_COUNTRY_OF_CITIZENSHIP,
         "pkcs-9-at-countryOfCitizenship",
+        "rfc2985",
     ),
     (
         &rfc2985::PKCS_9_AT_COUNTRY_OF_RESIDENCE,
         "pkcs-9-at-countryOfResidence",
+        "rfc2985",
+    ),
+    (
+        &rfc2985::PKCS_9_AT_USER_PKCS_12,
+        "pkcs-9-at-userPKCS12",
+        "rfc2985",
+    ),
+    (&rfc3161::ID_CT_TST_INFO, "id-ct-TSTInfo", "rfc3161"),
+    (
+        &rfc3161::ID_AA_TIME_STAMP_TOKEN,
+        "id-aa-timeStampToken",
+        "rfc3161",
     ),
-    (&rfc2985::PKCS_9_AT_USER_PKCS_12, "pkcs-9-at-userPKCS12"),
-    (&rfc3161::ID_CT_TST_INFO, "id-ct-TSTInfo"),
-    (&rfc3161::ID_AA_TIME_STAMP_TOKEN, "id-aa-timeStampToken"),
-    (&rfc3280::EMAIL, "email"),
-    (&rfc3280::EMAIL_ADDRESS, "emailAddress"),
-    (&rfc3280::PSEUDONYM, "pseudonym"),
-    (&rfc3296::REF, "ref"),
-    (&rfc3296::REFERRAL, "referral"),
+    (&rfc3280::EMAIL, "email", "rfc3280"),
+    (&rfc3280::EMAIL_ADDRESS, "emailAddress", "rfc3280"),
+    (&rfc3280::PSEUDONYM, "pseudonym", "rfc3280"),
+    (&rfc3296::REF, "ref", "rfc3296"),
+    (&rfc3296::REFERRAL, "referral", "rfc3296"),
     (
         &rfc3671::COLLECTIVE_ATTRIBUTE_SUBENTRIES,
         "collectiveAttributeSubentries",
+        "rfc3671",
+    ),
+    (
+        &rfc3671::COLLECTIVE_EXCLUSIONS,
+        "collectiveExclusions",
+        "rfc3671",
     ),
-    (&rfc3671::COLLECTIVE_EXCLUSIONS, "collectiveExclusions"),
     (
         &rfc3671::COLLECTIVE_ATTRIBUTE_SUBENTRY,
         "collectiveAttributeSubentry",
+        "rfc3671",
     ),
-    (&rfc3671::C_O, "c-o"),
-    (&rfc3671::C_OU, "c-ou"),
-    (&rfc3671::C_POSTAL_ADDRESS, "c-PostalAddress"),
-    (&rfc3671::C_POSTAL_CODE, "c-PostalCode"),
-    (&rfc3671::C_POST_OFFICE_BOX, "c-PostOfficeBox"),
+    (&rfc3671::C_O, "c-o", "rfc3671"),
+    (&rfc3671::C_OU, "c-ou", "rfc3671"),
+    (&rfc3671::C_POSTAL_ADDRESS, "c-PostalAddress", "rfc3671"),
+    (&rfc3671::C_POSTAL_CODE, "c-PostalCode", "rfc3671"),
+    (&rfc3671::C_POST_OFFICE_BOX, "c-PostOfficeBox", "rfc3671"),
     (
         &rfc3671::C_PHYSICAL_DELIVERY_OFFICE,
         "c-PhysicalDeliveryOffice",
+        "rfc3671",
     ),
-    (&rfc3671::C_TELEPHONE_NUMBER, "c-TelephoneNumber"),
-    (&rfc3671::C_TELEX_NUMBER, "c-TelexNumber"),
+    (&rfc3671::C_TELEPHONE_NUMBER, "c-TelephoneNumber", "rfc3671"),
+    (&rfc3671::C_TELEX_NUMBER, "c-TelexNumber", "rfc3671"),
     (
         &rfc3671::C_FACSIMILE_TELEPHONE_NUMBER,
         "c-FacsimileTelephoneNumber",
+        "rfc3671",
     ),
     (
         &rfc3671::C_INTERNATIONAL_ISDN_NUMBER,
         "c-InternationalISDNNumber",
+        "rfc3671",
+    ),
+    (&rfc3671::C_L, "c-l", "rfc3671"),
+    (&rfc3671::C_ST, "c-st", "rfc3671"),
+    (&rfc3671::C_STREET, "c-street", "rfc3671"),
+    (&rfc3672::SUBENTRY, "subentry", "rfc3672"),
+    (
+        &rfc3672::ADMINISTRATIVE_ROLE,
+        "administrativeRole",
+        "rfc3672",
+    ),
+    (
+        &rfc3672::SUBTREE_SPECIFICATION,
+        "subtreeSpecification",
+        "rfc3672",
     ),
-    (&rfc3671::C_L, "c-l"),
-    (&rfc3671::C_ST, "c-st"),
-    (&rfc3671::C_STREET, "c-street"),
-    (&rfc3672::SUBENTRY, "subentry"),
-    (&rfc3672::ADMINISTRATIVE_ROLE, "administrativeRole"),
-    (&rfc3672::SUBTREE_SPECIFICATION, "subtreeSpecification"),
-    (&rfc3672::AUTONOMOUS_AREA, "autonomousArea"),
+    (&rfc3672::AUTONOMOUS_AREA, "autonomousArea", "rfc3672"),
     (
         &rfc3672::ACCESS_CONTROL_SPECIFIC_AREA,
         "accessControlSpecificArea",
+        "rfc3672",
     ),
     (
         &rfc3672::ACCESS_CONTROL_INNER_AREA,
         "accessControlInnerArea",
+        "rfc3672",
     ),
     (
         &rfc3672::SUBSCHEMA_ADMIN_SPECIFIC_AREA,
         "subschemaAdminSpecificArea",
+        "rfc3672",
     ),
     (
         &rfc3672::COLLECTIVE_ATTRIBUTE_SPECIFIC_AREA,
         "collectiveAttributeSpecificArea",
+        "rfc3672",
     ),
     (
         &rfc3672::COLLECTIVE_ATTRIBUTE_INNER_AREA,
         "collectiveAttributeInnerArea",
+        "rfc3672",
+    ),
+    (
+        &rfc3687::COMPONENT_FILTER_MATCH,
+        "componentFilterMatch",
+        "rfc3687",
+    ),
+    (&rfc3687::RDN_MATCH, "rdnMatch", "rfc3687"),
+    (&rfc3687::PRESENT_MATCH, "presentMatch", "rfc3687"),
+    (
+        &rfc3687::ALL_COMPONENTS_MATCH,
+        "allComponentsMatch",
+        "rfc3687",
     ),
-    (&rfc3687::COMPONENT_FILTER_MATCH, "componentFilterMatch"),
-    (&rfc3687::RDN_MATCH, "rdnMatch"),
-    (&rfc3687::PRESENT_MATCH, "presentMatch"),
-    (&rfc3687::ALL_COMPONENTS_MATCH, "allComponentsMatch"),
     (
         &rfc3687::DIRECTORY_COMPONENTS_MATCH,
         "directoryComponentsMatch",
+        "rfc3687",
+    ),
+    (
+        &rfc3698::STORED_PREFIX_MATCH,
+        "storedPrefixMatch",
+        "rfc3698",
     ),
-    (&rfc3698::STORED_PREFIX_MATCH, "storedPrefixMatch"),
-    (&rfc3703::PCIM_POLICY, "pcimPolicy"),
+    (&rfc3703::PCIM_POLICY, "pcimPolicy", "rfc3703"),
     (
         &rfc3703::PCIM_RULE_ACTION_ASSOCIATION,
         "pcimRuleActionAssociation",
+        "rfc3703",
+    ),
+    (
+        &rfc3703::PCIM_CONDITION_AUX_CLASS,
+        "pcimConditionAuxClass",
+        "rfc3703",
     ),
-    (&rfc3703::PCIM_CONDITION_AUX_CLASS, "pcimConditionAuxClass"),
-    (&rfc3703::PCIM_TPC_AUX_CLASS, "pcimTPCAuxClass"),
+    (&rfc3703::PCIM_TPC_AUX_CLASS, "pcimTPCAuxClass", "rfc3703"),
     (
         &rfc3703::PCIM_CONDITION_VENDOR_AUX_CLASS,
         "pcimConditionVendorAuxClass",
+        "rfc3703",
+    ),
+    (
+        &rfc3703::PCIM_ACTION_AUX_CLASS,
+        "pcimActionAuxClass",
+        "rfc3703",
     ),
-    (&rfc3703::PCIM_ACTION_AUX_CLASS, "pcimActionAuxClass"),
     (
         &rfc3703::PCIM_ACTION_VENDOR_AUX_CLASS,
         "pcimActionVendorAuxClass",
+        "rfc3703",
+    ),
+    (
+        &rfc3703::PCIM_POLICY_INSTANCE,
+        "pcimPolicyInstance",
+        "rfc3703",
+    ),
+    (
+        &rfc3703::PCIM_ELEMENT_AUX_CLASS,
+        "pcimElementAuxClass",
+        "rfc3703",
     ),
-    (&rfc3703::PCIM_POLICY_INSTANCE, "pcimPolicyInstance"),
-    (&rfc3703::PCIM_ELEMENT_AUX_CLASS, "pcimElementAuxClass"),
-    (&rfc3703::PCIM_REPOSITORY, "pcimRepository"),
+    (&rfc3703::PCIM_REPOSITORY, "pcimRepository", "rfc3703"),
     (
         &rfc3703::PCIM_REPOSITORY_AUX_CLASS,
         "pcimRepositoryAuxClass",
+        "rfc3703",
+    ),
+    (&rfc3703::PCIM_GROUP, "pcimGroup", "rfc3703"),
+    (
+        &rfc3703::PCIM_REPOSITORY_INSTANCE,
+        "pcimRepositoryInstance",
+        "rfc3703",
     ),
-    (&rfc3703::PCIM_GROUP, "pcimGroup"),
-    (&rfc3703::PCIM_REPOSITORY_INSTANCE, "pcimRepositoryInstance"),
     (
         &rfc3703::PCIM_SUBTREES_PTR_AUX_CLASS,
         "pcimSubtreesPtrAuxClass",
+        "rfc3703",
     ),
     (
         &rfc3703::PCIM_GROUP_CONTAINMENT_AUX_CLASS,
         "pcimGroupContainmentAuxClass",
+        "rfc3703",
     ),
     (
         &rfc3703::PCIM_RULE_CONTAINMENT_AUX_CLASS,
         "pcimRuleContainmentAuxClass",
+        "rfc3703",
+    ),
+    (
+        &rfc3703::PCIM_GROUP_AUX_CLASS,
+        "pcimGroupAuxClass",
+        "rfc3703",
+    ),
+    (
+        &rfc3703::PCIM_GROUP_INSTANCE,
+        "pcimGroupInstance",
+        "rfc3703",
     ),
-    (&rfc3703::PCIM_GROUP_AUX_CLASS, "pcimGroupAuxClass"),
-    (&rfc3703::PCIM_GROUP_INSTANCE, "pcimGroupInstance"),
-    (&rfc3703::PCIM_RULE, "pcimRule"),
-    (&rfc3703::PCIM_RULE_AUX_CLASS, "pcimRuleAuxClass"),
-    (&rfc3703::PCIM_RULE_INSTANCE, "pcimRuleInstance"),
+    (&rfc3703::PCIM_RULE, "pcimRule", "rfc3703"),
+    (&rfc3703::PCIM_RULE_AUX_CLASS, "pcimRuleAuxClass", "rfc3703"),
+    (&rfc3703::PCIM_RULE_INSTANCE, "pcimRuleInstance", "rfc3703"),
     (
         &rfc3703::PCIM_RULE_CONDITION_ASSOCIATION,
         "pcimRuleConditionAssociation",
+        "rfc3703",
     ),
     (
         &rfc3703::PCIM_RULE_VALIDITY_ASSOCIATION,
         "pcimRuleValidityAssociation",
+        "rfc3703",
     ),
     (
         &rfc3703::PCIM_RULE_VALIDITY_PERIOD_LIST,
         "pcimRuleValidityPeriodList",
+        "rfc3703",
+    ),
+    (&rfc3703::PCIM_RULE_USAGE, "pcimRuleUsage", "rfc3703"),
+    (&rfc3703::PCIM_RULE_PRIORITY, "pcimRulePriority", "rfc3703"),
+    (
+        &rfc3703::PCIM_RULE_MANDATORY,
+        "pcimRuleMandatory",
+        "rfc3703",
     ),
-    (&rfc3703::PCIM_RULE_USAGE, "pcimRuleUsage"),
-    (&rfc3703::PCIM_RULE_PRIORITY, "pcimRulePriority"),
-    (&rfc3703::PCIM_RULE_MANDATORY, "pcimRuleMandatory"),
     (
         &rfc3703::PCIM_RULE_SEQUENCED_ACTIONS,
         "pcimRuleSequencedActions",
+        "rfc3703",
     ),
-    (&rfc3703::PCIM_ROLES, "pcimRoles"),
+    (&rfc3703::PCIM_ROLES, "pcimRoles", "rfc3703"),
     (
         &rfc3703::PCIM_CONDITION_GROUP_NUMBER,
         "pcimConditionGroupNumber",
+        "rfc3703",
+    ),
+    (
+        &rfc3703::PCIM_CONDITION_NEGATED,
+        "pcimConditionNegated",
+        "rfc3703",
+    ),
+    (
+        &rfc3703::PCIM_CONDITION_NAME,
+        "pcimConditionName",
+        "rfc3703",
     ),
-    (&rfc3703::PCIM_CONDITION_NEGATED, "pcimConditionNegated"),
-    (&rfc3703::PCIM_CONDITION_NAME, "pcimConditionName"),
-    (&rfc3703::PCIM_CONDITION_DN, "pcimConditionDN"),
+    (&rfc3703::PCIM_CONDITION_DN, "pcimConditionDN", "rfc3703"),
     (
         &rfc3703::PCIM_VALIDITY_CONDITION_NAME,
         "pcimValidityConditionName",
+        "rfc3703",
     ),
     (
         &rfc3703::PCIM_TIME_PERIOD_CONDITION_DN,
         "pcimTimePeriodConditionDN",
+        "rfc3703",
     ),
-    (&rfc3703::PCIM_ACTION_NAME, "pcimActionName"),
-    (&rfc3703::PCIM_ACTION_ORDER, "pcimActionOrder"),
-    (&rfc3703::PCIM_ACTION_DN, "pcimActionDN"),
-    (&rfc3703::PCIM_TPC_TIME, "pcimTPCTime"),
+    (&rfc3703::PCIM_ACTION_NAME, "pcimActionName", "rfc3703"),
+    (&rfc3703::PCIM_ACTION_ORDER, "pcimActionOrder", "rfc3703"),
+    (&rfc3703::PCIM_ACTION_DN, "pcimActionDN", "rfc3703"),
+    (&rfc3703::PCIM_TPC_TIME, "pcimTPCTime", "rfc3703"),
     (
         &rfc3703::PCIM_TPC_MONTH_OF_YEAR_MASK,
         "pcimTPCMonthOfYearMask",
+        "rfc3703",
     ),
     (
         &rfc3703::PCIM_TPC_DAY_OF_MONTH_MASK,
         "pcimTPCDayOfMonthMask",
+        "rfc3703",
+    ),
+    (
+        &rfc3703::PCIM_TPC_DAY_OF_WEEK_MASK,
+        "pcimTPCDayOfWeekMask",
+        "rfc3703",
+    ),
+    (
+        &rfc3703::PCIM_TPC_TIME_OF_DAY_MASK,
+        "pcimTPCTimeOfDayMask",
+        "rfc3703",
     ),
-    (&rfc3703::PCIM_TPC_DAY_OF_WEEK_MASK, "pcimTPCDayOfWeekMask"),
-    (&rfc3703::PCIM_TPC_TIME_OF_DAY_MASK, "pcimTPCTimeOfDayMask"),
-    (&rfc3703::PCIM_KEYWORDS, "pcimKeywords"),
+    (&rfc3703::PCIM_KEYWORDS, "pcimKeywords", "rfc3703"),
     (
         &rfc3703::PCIM_TPC_LOCAL_OR_UTC_TIME,
         "pcimTPCLocalOrUtcTime",
+        "rfc3703",
     ),
     (
         &rfc3703::PCIM_VENDOR_CONSTRAINT_DATA,
         "pcimVendorConstraintData",
+        "rfc3703",
     ),
     (
         &rfc3703::PCIM_VENDOR_CONSTRAINT_ENCODING,
         "pcimVendorConstraintEncoding",
+        "rfc3703",
+    ),
+    (
+        &rfc3703::PCIM_VENDOR_ACTION_DATA,
+        "pcimVendorActionData",
+        "rfc3703",
     ),
-    (&rfc3703::PCIM_VENDOR_ACTION_DATA, "pcimVendorActionData"),
     (
         &rfc3703::PCIM_VENDOR_ACTION_ENCODING,
         "pcimVendorActionEncoding",
+        "rfc3703",
     ),
     (
         &rfc3703::PCIM_POLICY_INSTANCE_NAME,
         "pcimPolicyInstanceName",
+        "rfc3703",
+    ),
+    (
+        &rfc3703::PCIM_REPOSITORY_NAME,
+        "pcimRepositoryName",
+        "rfc3703",
     ),
-    (&rfc3703::PCIM_REPOSITORY_NAME, "pcimRepositoryName"),
     (
         &rfc3703::PCIM_SUBTREES_AUX_CONTAINED_SET,
         "pcimSubtreesAuxContainedSet",
+        "rfc3703",
     ),
     (
         &rfc3703::PCIM_GROUPS_AUX_CONTAINED_SET,
         "pcimGroupsAuxContainedSet",
+        "rfc3703",
     ),
     (
         &rfc3703::PCIM_RULES_AUX_CONTAINED_SET,
         "pcimRulesAuxContainedSet",
+        "rfc3703",
     ),
-    (&rfc3703::PCIM_GROUP_NAME, "pcimGroupName"),
-    (&rfc3703::PCIM_RULE_NAME, "pcimRuleName"),
-    (&rfc3703::PCIM_RULE_ENABLED, "pcimRuleEnabled"),
+    (&rfc3703::PCIM_GROUP_NAME, "pcimGroupName", "rfc3703"),
+    (&rfc3703::PCIM_RULE_NAME, "pcimRuleName", "rfc3703"),
+    (&rfc3703::PCIM_RULE_ENABLED, "pcimRuleEnabled", "rfc3703"),
     (
         &rfc3703::PCIM_RULE_CONDITION_LIST_TYPE,
         "pcimRuleConditionListType",
+        "rfc3703",
+    ),
+    (
+        &rfc3703::PCIM_RULE_CONDITION_LIST,
+        "pcimRuleConditionList",
+        "rfc3703",
+    ),
+    (
+        &rfc3703::PCIM_RULE_ACTION_LIST,
+        "pcimRuleActionList",
+        "rfc3703",
+    ),
+    (
+        &rfc3712::PRINTER_XRI_SUPPORTED,
+        "printer-xri-supported",
+        "rfc3712",
     ),
-    (&rfc3703::PCIM_RULE_CONDITION_LIST, "pcimRuleConditionList"),
-    (&rfc3703::PCIM_RULE_ACTION_LIST, "pcimRuleActionList"),
-    (&rfc3712::PRINTER_XRI_SUPPORTED, "printer-xri-supported"),
-    (&rfc3712::PRINTER_ALIASES, "printer-aliases"),
+    (&rfc3712::PRINTER_ALIASES, "printer-aliases", "rfc3712"),
     (
         &rfc3712::PRINTER_CHARSET_CONFIGURED,
         "printer-charset-configured",
+        "rfc3712",
     ),
     (
         &rfc3712::PRINTER_JOB_PRIORITY_SUPPORTED,
         "printer-job-priority-supported",
+        "rfc3712",
     ),
     (
         &rfc3712::PRINTER_JOB_K_OCTETS_SUPPORTED,
         "printer-job-k-octets-supported",
+        "rfc3712",
     ),
     (
         &rfc3712::PRINTER_CURRENT_OPERATOR,
         "printer-current-operator",
+        "rfc3712",
+    ),
+    (
+        &rfc3712::PRINTER_SERVICE_PERSON,
+        "printer-service-person",
+        "rfc3712",
     ),
-    (&rfc3712::PRINTER_SERVICE_PERSON, "printer-service-person"),
     (
         &rfc3712::PRINTER_DELIVERY_ORIENTATION_SUPPORTED,
         "printer-delivery-orientation-supported",
+        "rfc3712",
     ),
     (
         &rfc3712::PRINTER_STACKING_ORDER_SUPPORTED,
         "printer-stacking-order-supported",
+        "rfc3712",
     ),
     (
         &rfc3712::PRINTER_OUTPUT_FEATURES_SUPPORTED,
         "printer-output-features-supported",
+        "rfc3712",
     ),
     (
         &rfc3712::PRINTER_MEDIA_LOCAL_SUPPORTED,
         "printer-media-local-supported",
+        "rfc3712",
     ),
     (
         &rfc3712::PRINTER_COPIES_SUPPORTED,
         "printer-copies-supported",
+        "rfc3712",
     ),
     (
         &rfc3712::PRINTER_NATURAL_LANGUAGE_CONFIGURED,
         "printer-natural-language-configured",
+        "rfc3712",
     ),
     (
         &rfc3712::PRINTER_PRINT_QUALITY_SUPPORTED,
         "printer-print-quality-supported",
+        "rfc3712",
     ),
     (
         &rfc3712::PRINTER_RESOLUTION_SUPPORTED,
         "printer-resolution-supported",
+        "rfc3712",
+    ),
+    (
+        &rfc3712::PRINTER_MEDIA_SUPPORTED,
+        "printer-media-supported",
+        "rfc3712",
+    ),
+    (
+        &rfc3712::PRINTER_SIDES_SUPPORTED,
+        "printer-sides-supported",
+        "rfc3712",
     ),
-    (&rfc3712::PRINTER_MEDIA_SUPPORTED, "printer-media-supported"),
-    (&rfc3712::PRINTER_SIDES_SUPPORTED, "printer-sides-supported"),
     (
         &rfc3712::PRINTER_NUMBER_UP_SUPPORTED,
         "printer-number-up-supported",
+        "rfc3712",
     ),
     (
         &rfc3712::PRINTER_FINISHINGS_SUPPORTED,
         "printer-finishings-supported",
+        "rfc3712",
     ),
     (
         &rfc3712::PRINTER_PAGES_PER_MINUTE_COLOR,
         "printer-pages-per-minute-color",
+        "rfc3712",
     ),
     (
         &rfc3712::PRINTER_PAGES_PER_MINUTE,
         "printer-pages-per-minute",
+        "rfc3712",
     ),
     (
         &rfc3712::PRINTER_COMPRESSION_SUPPORTED,
         "printer-compression-supported",
+        "rfc3712",
+    ),
+    (
+        &rfc3712::PRINTER_COLOR_SUPPORTED,
+        "printer-color-supported",
+        "rfc3712",
     ),
-    (&rfc3712::PRINTER_COLOR_SUPPORTED, "printer-color-supported"),
     (
         &rfc3712::PRINTER_DOCUMENT_FORMAT_SUPPORTED,
         "printer-document-format-supported",
+        "rfc3712",
     ),
     (
         &rfc3712::PRINTER_CHARSET_SUPPORTED,
         "printer-charset-supported",
+        "rfc3712",
     ),
     (
         &rfc3712::PRINTER_MULTIPLE_DOCUMENT_JOBS_SUPPORTED,
         "printer-multiple-document-jobs-supported",
+        "rfc3712",
     ),
     (
         &rfc3712::PRINTER_IPP_VERSIONS_SUPPORTED,
         "printer-ipp-versions-supported",
+        "rfc3712",
     ),
-    (&rfc3712::PRINTER_MORE_INFO, "printer-more-info"),
-    (&rfc3712::PRINTER_NAME, "printer-name"),
-    (&rfc3712::PRINTER_LOCATION, "printer-location"),
+    (&rfc3712::PRINTER_MORE_INFO, "printer-more-info", "rfc3712"),
+    (&rfc3712::PRINTER_NAME, "printer-name", "rfc3712"),
+    (&rfc3712::PRINTER_LOCATION, "printer-location", "rfc3712"),
     (
         &rfc3712::PRINTER_GENERATED_NATURAL_LANGUAGE_SUPPORTED,
         "printer-generated-natural-language-supported",
+        "rfc3712",
+    ),
+    (
+        &rfc3712::PRINTER_MAKE_AND_MODEL,
+        "printer-make-and-model",
+        "rfc3712",
+    ),
+    (&rfc3712::PRINTER_INFO, "printer-info", "rfc3712"),
+    (&rfc3712::PRINTER_URI, "printer-uri", "rfc3712"),
+    (&rfc3712::PRINTER_LPR, "printerLPR", "rfc3712"),
+    (
+        &rfc3712::SLP_SERVICE_PRINTER,
+        "slpServicePrinter",
+        "rfc3712",
     ),
-    (&rfc3712::PRINTER_MAKE_AND_MODEL, "printer-make-and-model"),
-    (&rfc3712::PRINTER_INFO, "printer-info"),
-    (&rfc3712::PRINTER_URI, "printer-uri"),
-    (&rfc3712::PRINTER_LPR, "printerLPR"),
-    (&rfc3712::SLP_SERVICE_PRINTER, "slpServicePrinter"),
-    (&rfc3712::PRINTER_SERVICE, "printerService"),
-    (&rfc3712::PRINTER_IPP, "printerIPP"),
+    (&rfc3712::PRINTER_SERVICE, "printerService", "rfc3712"),
+    (&rfc3712::PRINTER_IPP, "printerIPP", "rfc3712"),
     (
         &rfc3712::PRINTER_SERVICE_AUX_CLASS,
         "printerServiceAuxClass",
+        "rfc3712",
+    ),
+    (&rfc3712::PRINTER_ABSTRACT, "printerAbstract", "rfc3712"),
+    (&rfc4104::PCELS_POLICY_SET, "pcelsPolicySet", "rfc4104"),
+    (
+        &rfc4104::PCELS_ACTION_ASSOCIATION,
+        "pcelsActionAssociation",
+        "rfc4104",
     ),
-    (&rfc3712::PRINTER_ABSTRACT, "printerAbstract"),
-    (&rfc4104::PCELS_POLICY_SET, "pcelsPolicySet"),
-    (&rfc4104::PCELS_ACTION_ASSOCIATION, "pcelsActionAssociation"),
     (
         &rfc4104::PCELS_SIMPLE_CONDITION_AUX_CLASS,
         "pcelsSimpleConditionAuxClass",
+        "rfc4104",
     ),
     (
         &rfc4104::PCELS_COMPOUND_CONDITION_AUX_CLASS,
         "pcelsCompoundConditionAuxClass",
+        "rfc4104",
     ),
     (
         &rfc4104::PCELS_COMPOUND_FILTER_CONDITION_AUX_CLASS,
         "pcelsCompoundFilterConditionAuxClass",
+        "rfc4104",
     ),
     (
         &rfc4104::PCELS_SIMPLE_ACTION_AUX_CLASS,
         "pcelsSimpleActionAuxClass",
+        "rfc4104",
     ),
     (
         &rfc4104::PCELS_COMPOUND_ACTION_AUX_CLASS,
         "pcelsCompoundActionAuxClass",
+        "rfc4104",
     ),
-    (&rfc4104::PCELS_VARIABLE, "pcelsVariable"),
+    (&rfc4104::PCELS_VARIABLE, "pcelsVariable", "rfc4104"),
     (
         &rfc4104::PCELS_EXPLICIT_VARIABLE_AUX_CLASS,
         "pcelsExplicitVariableAuxClass",
+        "rfc4104",
     ),
     (
         &rfc4104::PCELS_IMPLICIT_VARIABLE_AUX_CLASS,
         "pcelsImplicitVariableAuxClass",
+        "rfc4104",
     ),
     (
         &rfc4104::PCELS_SOURCE_I_PV_4_VARIABLE_AUX_CLASS,
         "pcelsSourceIPv4VariableAuxClass",
+        "rfc4104",
     ),
     (
         &rfc4104::PCELS_POLICY_SET_ASSOCIATION,
         "pcelsPolicySetAssociation",
+        "rfc4104",
     ),
     (
         &rfc4104::PCELS_SOURCE_I_PV_6_VARIABLE_AUX_CLASS,
         "pcelsSourceIPv6VariableAuxClass",
+        "rfc4104",
     ),
     (
         &rfc4104::PCELS_DESTINATION_I_PV_4_VARIABLE_AUX_CLASS,
         "pcelsDestinationIPv4VariableAuxClass",
+        "rfc4104",
     ),
     (
         &rfc4104::PCELS_DESTINATION_I_PV_6_VARIABLE_AUX_CLASS,
         "pcelsDestinationIPv6VariableAuxClass",
+        "rfc4104",
     ),
     (
         &rfc4104::PCELS_SOURCE_PORT_VARIABLE_AUX_CLASS,
         "pcelsSourcePortVariableAuxClass",
+        "rfc4104",
     ),
     (
         &rfc4104::PCELS_DESTINATION_PORT_VARIABLE_AUX_CLASS,
         "pcelsDestinationPortVariableAuxClass",
+        "rfc4104",
     ),
     (
         &rfc4104::PCELS_IP_PROTOCOL_VARIABLE_AUX_CLASS,
         "pcelsIPProtocolVariableAuxClass",
+        "rfc4104",
     ),
     (
         &rfc4104::PCELS_IP_VERSION_VARIABLE_AUX_CLASS,
         "pcelsIPVersionVariableAuxClass",
+        "rfc4104",
     ),
     (
         &rfc4104::PCELS_IP_TO_S_VARIABLE_AUX_CLASS,
         "pcelsIPToSVariableAuxClass",
+        "rfc4104",
     ),
     (
         &rfc4104::PCELS_DSCP_VARIABLE_AUX_CLASS,
         "pcelsDSCPVariableAuxClass",
+        "rfc4104",
     ),
     (
         &rfc4104::PCELS_FLOW_ID_VARIABLE_AUX_CLASS,
         "pcelsFlowIdVariableAuxClass",
+        "rfc4104",
     ),
-    (&rfc4104::PCELS_GROUP, "pcelsGroup"),
+    (&rfc4104::PCELS_GROUP, "pcelsGroup", "rfc4104"),
     (
         &rfc4104::PCELS_SOURCE_MAC_VARIABLE_AUX_CLASS,
         "pcelsSourceMACVariableAuxClass",
+        "rfc4104",
     ),
     (
         &rfc4104::PCELS_DESTINATION_MAC_VARIABLE_AUX_CLASS,
         "pcelsDestinationMACVariableAuxClass",
+        "rfc4104",
     ),
     (
         &rfc4104::PCELS_VLAN_VARIABLE_AUX_CLASS,
         "pcelsVLANVariableAuxClass",
+        "rfc4104",
     ),
     (
         &rfc4104::PCELS_CO_S_VARIABLE_AUX_CLASS,
         "pcelsCoSVariableAuxClass",
+        "rfc4104",
     ),
     (
         &rfc4104::PCELS_ETHERTYPE_VARIABLE_AUX_CLASS,
         "pcelsEthertypeVariableAuxClass",
+        "rfc4104",
     ),
     (
         &rfc4104::PCELS_SOURCE_SAP_VARIABLE_AUX_CLASS,
         "pcelsSourceSAPVariableAuxClass",
+        "rfc4104",
     ),
     (
         &rfc4104::PCELS_DESTINATION_SAP_VARIABLE_AUX_CLASS,
         "pcelsDestinationSAPVariableAuxClass",
+        "rfc4104",
     ),
     (
         &rfc4104::PCELS_SNAPOUI_VARIABLE_AUX_CLASS,
         "pcelsSNAPOUIVariableAuxClass",
+        "rfc4104",
     ),
     (
         &rfc4104::PCELS_SNAP_TYPE_VARIABLE_AUX_CLASS,
         "pcelsSNAPTypeVariableAuxClass",
+        "rfc4104",
     ),
     (
         &rfc4104::PCELS_FLOW_DIRECTION_VARIABLE_AUX_CLASS,
         "pcelsFlowDirectionVariableAuxClass",
+        "rfc4104",
+    ),
+    (
+        &rfc4104::PCELS_GROUP_AUX_CLASS,
+        "pcelsGroupAuxClass",
+        "rfc4104",
+    ),
+    (
+        &rfc4104::PCELS_VALUE_AUX_CLASS,
+        "pcelsValueAuxClass",
+        "rfc4104",
     ),
-    (&rfc4104::PCELS_GROUP_AUX_CLASS, "pcelsGroupAuxClass"),
-    (&rfc4104::PCELS_VALUE_AUX_CLASS, "pcelsValueAuxClass"),
     (
         &rfc4104::PCELS_I_PV_4_ADDR_VALUE_AUX_CLASS,
         "pcelsIPv4AddrValueAuxClass",
+        "rfc4104",
     ),
     (
         &rfc4104::PCELS_I_PV_6_ADDR_VALUE_AUX_CLASS,
         "pcelsIPv6AddrValueAuxClass",
+        "rfc4104",
     ),
     (
         &rfc4104::PCELS_MAC_ADDR_VALUE_AUX_CLASS,
         "pcelsMACAddrValueAuxClass",
+        "rfc4104",
     ),
     (
         &rfc4104::PCELS_STRING_VALUE_AUX_CLASS,
         "pcelsStringValueAuxClass",
+        "rfc4104",
     ),
     (
         &rfc4104::PCELS_BIT_STRING_VALUE_AUX_CLASS,
         "pcelsBitStringValueAuxClass",
+        "rfc4104",
     ),
     (
         &rfc4104::PCELS_INTEGER_VALUE_AUX_CLASS,
         "pcelsIntegerValueAuxClass",
+        "rfc4104",
     ),
     (
         &rfc4104::PCELS_BOOLEAN_VALUE_AUX_CLASS,
         "pcelsBooleanValueAuxClass",
+        "rfc4104",
+    ),
+    (
+        &rfc4104::PCELS_REUSABLE_CONTAINER,
+        "pcelsReusableContainer",
+        "rfc4104",
     ),
-    (&rfc4104::PCELS_REUSABLE_CONTAINER, "pcelsReusableContainer"),
     (
         &rfc4104::PCELS_REUSABLE_CONTAINER_AUX_CLASS,
         "pcelsReusableContainerAuxClass",
+        "rfc4104",
+    ),
+    (
+        &rfc4104::PCELS_GROUP_INSTANCE,
+        "pcelsGroupInstance",
+        "rfc4104",
     ),
-    (&rfc4104::PCELS_GROUP_INSTANCE, "pcelsGroupInstance"),
     (
         &rfc4104::PCELS_REUSABLE_CONTAINER_INSTANCE,
         "pcelsReusableContainerInstance",
+        "rfc4104",
+    ),
+    (
+        &rfc4104::PCELS_ROLE_COLLECTION,
+        "pcelsRoleCollection",
+        "rfc4104",
+    ),
+    (
+        &rfc4104::PCELS_FILTER_ENTRY_BASE,
+        "pcelsFilterEntryBase",
+        "rfc4104",
+    ),
+    (
+        &rfc4104::PCELS_IP_HEADERS_FILTER,
+        "pcelsIPHeadersFilter",
+        "rfc4104",
     ),
-    (&rfc4104::PCELS_ROLE_COLLECTION, "pcelsRoleCollection"),
-    (&rfc4104::PCELS_FILTER_ENTRY_BASE, "pcelsFilterEntryBase"),
-    (&rfc4104::PCELS_IP_HEADERS_FILTER, "pcelsIPHeadersFilter"),
-    (&rfc4104::PCELS_8021_FILTER, "pcels8021Filter"),
+    (&rfc4104::PCELS_8021_FILTER, "pcels8021Filter", "rfc4104"),
     (
         &rfc4104::PCELS_FILTER_LIST_AUX_CLASS,
         "pcelsFilterListAuxClass",
+        "rfc4104",
     ),
     (
         &rfc4104::PCELS_VENDOR_VARIABLE_AUX_CLASS,
         "pcelsVendorVariableAuxClass",
+        "rfc4104",
     ),
     (
         &rfc4104::PCELS_VENDOR_VALUE_AUX_CLASS,
         "pcelsVendorValueAuxClass",
+        "rfc4104",
+    ),
+    (&rfc4104::PCELS_RULE, "pcelsRule", "rfc4104"),
+    (
+        &rfc4104::PCELS_RULE_AUX_CLASS,
+        "pcelsRuleAuxClass",
+        "rfc4104",
+    ),
+    (
+        &rfc4104::PCELS_RULE_INSTANCE,
+        "pcelsRuleInstance",
+        "rfc4104",
     ),
-    (&rfc4104::PCELS_RULE, "pcelsRule"),
-    (&rfc4104::PCELS_RULE_AUX_CLASS, "pcelsRuleAuxClass"),
-    (&rfc4104::PCELS_RULE_INSTANCE, "pcelsRuleInstance"),
     (
         &rfc4104::PCELS_CONDITION_ASSOCIATION,
         "pcelsConditionAssociation",
+        "rfc4104",
+    ),
+    (
+        &rfc4104::PCELS_POLICY_SET_NAME,
+        "pcelsPolicySetName",
+        "rfc4104",
+    ),
+    (
+        &rfc4104::PCELS_EXECUTION_STRATEGY,
+        "pcelsExecutionStrategy",
+        "rfc4104",
+    ),
+    (&rfc4104::PCELS_VARIABLE_DN, "pcelsVariableDN", "rfc4104"),
+    (&rfc4104::PCELS_VALUE_DN, "pcelsValueDN", "rfc4104"),
+    (&rfc4104::PCELS_IS_MIRRORED, "pcelsIsMirrored", "rfc4104"),
+    (
+        &rfc4104::PCELS_VARIABLE_NAME,
+        "pcelsVariableName",
+        "rfc4104",
     ),
-    (&rfc4104::PCELS_POLICY_SET_NAME, "pcelsPolicySetName"),
-    (&rfc4104::PCELS_EXECUTION_STRATEGY, "pcelsExecutionStrategy"),
-    (&rfc4104::PCELS_VARIABLE_DN, "pcelsVariableDN"),
-    (&rfc4104::PCELS_VALUE_DN, "pcelsValueDN"),
-    (&rfc4104::PCELS_IS_MIRRORED, "pcelsIsMirrored"),
-    (&rfc4104::PCELS_VARIABLE_NAME, "pcelsVariableName"),
     (
         &rfc4104::PCELS_EXPECTED_VALUE_LIST,
         "pcelsExpectedValueList",
+        "rfc4104",
     ),
     (
         &rfc4104::PCELS_VARIABLE_MODEL_CLASS,
         "pcelsVariableModelClass",
+        "rfc4104",
     ),
     (
         &rfc4104::PCELS_VARIABLE_MODEL_PROPERTY,
         "pcelsVariableModelProperty",
+        "rfc4104",
     ),
     (
         &rfc4104::PCELS_EXPECTED_VALUE_TYPES,
         "pcelsExpectedValueTypes",
+        "rfc4104",
+    ),
+    (&rfc4104::PCELS_VALUE_NAME, "pcelsValueName", "rfc4104"),
+    (
+        &rfc4104::PCELS_DECISION_STRATEGY,
+        "pcelsDecisionStrategy",
+        "rfc4104",
+    ),
+    (
+        &rfc4104::PCELS_I_PV_4_ADDR_LIST,
+        "pcelsIPv4AddrList",
+        "rfc4104",
+    ),
+    (
+        &rfc4104::PCELS_I_PV_6_ADDR_LIST,
+        "pcelsIPv6AddrList",
+        "rfc4104",
+    ),
+    (&rfc4104::PCELS_MAC_ADDR_LIST, "pcelsMACAddrList", "rfc4104"),
+    (&rfc4104::PCELS_STRING_LIST, "pcelsStringList", "rfc4104"),
+    (
+        &rfc4104::PCELS_BIT_STRING_LIST,
+        "pcelsBitStringList",
+        "rfc4104",
     ),
-    (&rfc4104::PCELS_VALUE_NAME, "pcelsValueName"),
-    (&rfc4104::PCELS_DECISION_STRATEGY, "pcelsDecisionStrategy"),
-    (&rfc4104::PCELS_I_PV_4_ADDR_LIST, "pcelsIPv4AddrList"),
-    (&rfc4104::PCELS_I_PV_6_ADDR_LIST, "pcelsIPv6AddrList"),
-    (&rfc4104::PCELS_MAC_ADDR_LIST, "pcelsMACAddrList"),
-    (&rfc4104::PCELS_STRING_LIST, "pcelsStringList"),
-    (&rfc4104::PCELS_BIT_STRING_LIST, "pcelsBitStringList"),
-    (&rfc4104::PCELS_INTEGER_LIST, "pcelsIntegerList"),
-    (&rfc4104::PCELS_BOOLEAN, "pcelsBoolean"),
+    (&rfc4104::PCELS_INTEGER_LIST, "pcelsIntegerList", "rfc4104"),
+    (&rfc4104::PCELS_BOOLEAN, "pcelsBoolean", "rfc4104"),
     (
         &rfc4104::PCELS_REUSABLE_CONTAINER_NAME,
         "pcelsReusableContainerName",
+        "rfc4104",
     ),
     (
         &rfc4104::PCELS_REUSABLE_CONTAINER_LIST,
         "pcelsReusableContainerList",
+        "rfc4104",
+    ),
+    (&rfc4104::PCELS_ROLE, "pcelsRole", "rfc4104"),
+    (
+        &rfc4104::PCELS_POLICY_SET_LIST,
+        "pcelsPolicySetList",
+        "rfc4104",
     ),
-    (&rfc4104::PCELS_ROLE, "pcelsRole"),
-    (&rfc4104::PCELS_POLICY_SET_LIST, "pcelsPolicySetList"),
     (
         &rfc4104::PCELS_ROLE_COLLECTION_NAME,
         "pcelsRoleCollectionName",
+        "rfc4104",
+    ),
+    (&rfc4104::PCELS_ELEMENT_LIST, "pcelsElementList", "rfc4104"),
+    (&rfc4104::PCELS_FILTER_NAME, "pcelsFilterName", "rfc4104"),
+    (
+        &rfc4104::PCELS_FILTER_IS_NEGATED,
+        "pcelsFilterIsNegated",
+        "rfc4104",
+    ),
+    (
+        &rfc4104::PCELS_IP_HDR_VERSION,
+        "pcelsIPHdrVersion",
+        "rfc4104",
     ),
-    (&rfc4104::PCELS_ELEMENT_LIST, "pcelsElementList"),
-    (&rfc4104::PCELS_FILTER_NAME, "pcelsFilterName"),
-    (&rfc4104::PCELS_FILTER_IS_NEGATED, "pcelsFilterIsNegated"),
-    (&rfc4104::PCELS_IP_HDR_VERSION, "pcelsIPHdrVersion"),
     (
         &rfc4104::PCELS_IP_HDR_SOURCE_ADDRESS,
         "pcelsIPHdrSourceAddress",
+        "rfc4104",
     ),
     (
         &rfc4104::PCELS_IP_HDR_SOURCE_ADDRESS_END_OF_RANGE,
         "pcelsIPHdrSourceAddressEndOfRange",
+        "rfc4104",
+    ),
+    (
+        &rfc4104::PCELS_IP_HDR_SOURCE_MASK,
+        "pcelsIPHdrSourceMask",
+        "rfc4104",
+    ),
+    (
+        &rfc4104::PCELS_IP_HDR_DEST_ADDRESS,
+        "pcelsIPHdrDestAddress",
+        "rfc4104",
     ),
-    (&rfc4104::PCELS_IP_HDR_SOURCE_MASK, "pcelsIPHdrSourceMask"),
-    (&rfc4104::PCELS_IP_HDR_DEST_ADDRESS, "pcelsIPHdrDestAddress"),
     (
         &rfc4104::PCELS_IP_HDR_DEST_ADDRESS_END_OF_RANGE,
         "pcelsIPHdrDestAddressEndOfRange",
+        "rfc4104",
+    ),
+    (&rfc4104::PCELS_PRIORITY, "pcelsPriority", "rfc4104"),
+    (
+        &rfc4104::PCELS_IP_HDR_DEST_MASK,
+        "pcelsIPHdrDestMask",
+        "rfc4104",
+    ),
+    (
+        &rfc4104::PCELS_IP_HDR_PROTOCOL_ID,
+        "pcelsIPHdrProtocolID",
+        "rfc4104",
     ),
-    (&rfc4104::PCELS_PRIORITY, "pcelsPriority"),
-    (&rfc4104::PCELS_IP_HDR_DEST_MASK, "pcelsIPHdrDestMask"),
-    (&rfc4104::PCELS_IP_HDR_PROTOCOL_ID, "pcelsIPHdrProtocolID"),
     (
         &rfc4104::PCELS_IP_HDR_SOURCE_PORT_START,
         "pcelsIPHdrSourcePortStart",
+        "rfc4104",
     ),
     (
         &rfc4104::PCELS_IP_HDR_SOURCE_PORT_END,
         "pcelsIPHdrSourcePortEnd",
+        "rfc4104",
     ),
     (
         &rfc4104::PCELS_IP_HDR_DEST_PORT_START,
         "pcelsIPHdrDestPortStart",
+        "rfc4104",
     ),
     (
         &rfc4104::PCELS_IP_HDR_DEST_PORT_END,
         "pcelsIPHdrDestPortEnd",
+        "rfc4104",
+    ),
+    (
+        &rfc4104::PCELS_IP_HDR_DSCP_LIST,
+        "pcelsIPHdrDSCPList",
+        "rfc4104",
+    ),
+    (
+        &rfc4104::PCELS_IP_HDR_FLOW_LABEL,
+        "pcelsIPHdrFlowLabel",
+        "rfc4104",
     ),
-    (&rfc4104::PCELS_IP_HDR_DSCP_LIST, "pcelsIPHdrDSCPList"),
-    (&rfc4104::PCELS_IP_HDR_FLOW_LABEL, "pcelsIPHdrFlowLabel"),
     (
         &rfc4104::PCELS_8021_HDR_SOURCE_MAC_ADDRESS,
         "pcels8021HdrSourceMACAddress",
+        "rfc4104",
     ),
     (
         &rfc4104::PCELS_8021_HDR_SOURCE_MAC_MASK,
         "pcels8021HdrSourceMACMask",
+        "rfc4104",
     ),
-    (&rfc4104::PCELS_POLICY_SET_DN, "pcelsPolicySetDN"),
+    (&rfc4104::PCELS_POLICY_SET_DN, "pcelsPolicySetDN", "rfc4104"),
     (
         &rfc4104::PCELS_8021_HDR_DEST_MAC_ADDRESS,
         "pcels8021HdrDestMACAddress",
+        "rfc4104",
     ),
     (
         &rfc4104::PCELS_8021_HDR_DEST_MAC_MASK,
         "pcels8021HdrDestMACMask",
+        "rfc4104",
     ),
     (
         &rfc4104::PCELS_8021_HDR_PROTOCOL_ID,
         "pcels8021HdrProtocolID",
+        "rfc4104",
+    ),
+    (
+        &rfc4104::PCELS_8021_HDR_PRIORITY,
+        "pcels8021HdrPriority",
+        "rfc4104",
+    ),
+    (
+        &rfc4104::PCELS_8021_HDR_VLANID,
+        "pcels8021HdrVLANID",
+        "rfc4104",
+    ),
+    (
+        &rfc4104::PCELS_FILTER_LIST_NAME,
+        "pcelsFilterListName",
+        "rfc4104",
+    ),
+    (
+        &rfc4104::PCELS_FILTER_DIRECTION,
+        "pcelsFilterDirection",
+        "rfc4104",
+    ),
+    (
+        &rfc4104::PCELS_FILTER_ENTRY_LIST,
+        "pcelsFilterEntryList",
+        "rfc4104",
     ),
-    (&rfc4104::PCELS_8021_HDR_PRIORITY, "pcels8021HdrPriority"),
-    (&rfc4104::PCELS_8021_HDR_VLANID, "pcels8021HdrVLANID"),
-    (&rfc4104::PCELS_FILTER_LIST_NAME, "pcelsFilterListName"),
-    (&rfc4104::PCELS_FILTER_DIRECTION, "pcelsFilterDirection"),
-    (&rfc4104::PCELS_FILTER_ENTRY_LIST, "pcelsFilterEntryList"),
     (
         &rfc4104::PCELS_VENDOR_VARIABLE_DATA,
         "pcelsVendorVariableData",
+        "rfc4104",
     ),
     (
         &rfc4104::PCELS_VENDOR_VARIABLE_ENCODING,
         "pcelsVendorVariableEncoding",
+        "rfc4104",
     ),
     (
         &rfc4104::PCELS_CONDITION_LIST_TYPE,
         "pcelsConditionListType",
+        "rfc4104",
+    ),
+    (
+        &rfc4104::PCELS_VENDOR_VALUE_DATA,
+        "pcelsVendorValueData",
+        "rfc4104",
     ),
-    (&rfc4104::PCELS_VENDOR_VALUE_DATA, "pcelsVendorValueData"),
     (
         &rfc4104::PCELS_VENDOR_VALUE_ENCODING,
         "pcelsVendorValueEncoding",
+        "rfc4104",
     ),
     (
         &rfc4104::PCELS_RULE_VALIDITY_PERIOD_LIST,
         "pcelsRuleValidityPeriodList",
+        "rfc4104",
+    ),
+    (
+        &rfc4104::PCELS_CONDITION_LIST,
+        "pcelsConditionList",
+        "rfc4104",
+    ),
+    (&rfc4104::PCELS_ACTION_LIST, "pcelsActionList", "rfc4104"),
+    (
+        &rfc4104::PCELS_SEQUENCED_ACTIONS,
+        "pcelsSequencedActions",
+        "rfc4104",
+    ),
+    (&rfc4237::VPIM_USER, "vPIMUser", "rfc4237"),
+    (
+        &rfc4237::VPIM_TELEPHONE_NUMBER,
+        "vPIMTelephoneNumber",
+        "rfc4237",
+    ),
+    (&rfc4237::VPIM_SUB_MAILBOXES, "vPIMSubMailboxes", "rfc4237"),
+    (
+        &rfc4237::VPIM_RFC_822_MAILBOX,
+        "vPIMRfc822Mailbox",
+        "rfc4237",
     ),
-    (&rfc4104::PCELS_CONDITION_LIST, "pcelsConditionList"),
-    (&rfc4104::PCELS_ACTION_LIST, "pcelsActionList"),
-    (&rfc4104::PCELS_SEQUENCED_ACTIONS, "pcelsSequencedActions"),
-    (&rfc4237::VPIM_USER, "vPIMUser"),
-    (&rfc4237::VPIM_TELEPHONE_NUMBER, "vPIMTelephoneNumber"),
-    (&rfc4237::VPIM_SUB_MAILBOXES, "vPIMSubMailboxes"),
-    (&rfc4237::VPIM_RFC_822_MAILBOX, "vPIMRfc822Mailbox"),
-    (&rfc4237::VPIM_SPOKEN_NAME, "vPIMSpokenName"),
+    (&rfc4237::VPIM_SPOKEN_NAME, "vPIMSpokenName", "rfc4237"),
     (
         &rfc4237::VPIM_SUPPORTED_UA_BEHAVIORS,
         "vPIMSupportedUABehaviors",
+        "rfc4237",
     ),
     (
         &rfc4237::VPIM_SUPPORTED_AUDIO_MEDIA_TYPES,
         "vPIMSupportedAudioMediaTypes",
+        "rfc4237",
     ),
     (
         &rfc4237::VPIM_SUPPORTED_MESSAGE_CONTEXT,
         "vPIMSupportedMessageContext",
+        "rfc4237",
     ),
-    (&rfc4237::VPIM_TEXT_NAME, "vPIMTextName"),
+    (&rfc4237::VPIM_TEXT_NAME, "vPIMTextName", "rfc4237"),
     (
         &rfc4237::VPIM_EXTENDED_ABSENCE_STATUS,
         "vPIMExtendedAbsenceStatus",
+        "rfc4237",
+    ),
+    (
+        &rfc4237::VPIM_MAX_MESSAGE_SIZE,
+        "vPIMMaxMessageSize",
+        "rfc4237",
     ),
-    (&rfc4237::VPIM_MAX_MESSAGE_SIZE, "vPIMMaxMessageSize"),
     (
         &rfc4403::UDDI_BUSINESS_ENTITY_NAME_FORM,
         "uddiBusinessEntityNameForm",
+        "rfc4403",
     ),
     (
         &rfc4403::UDDIV_3_ENTITY_OBITUARY_NAME_FORM,
         "uddiv3EntityObituaryNameForm",
+        "rfc4403",
+    ),
+    (
+        &rfc4403::UDDI_CONTACT_NAME_FORM,
+        "uddiContactNameForm",
+        "rfc4403",
+    ),
+    (
+        &rfc4403::UDDI_ADDRESS_NAME_FORM,
+        "uddiAddressNameForm",
+        "rfc4403",
     ),
-    (&rfc4403::UDDI_CONTACT_NAME_FORM, "uddiContactNameForm"),
-    (&rfc4403::UDDI_ADDRESS_NAME_FORM, "uddiAddressNameForm"),
     (
         &rfc4403::UDDI_BUSINESS_SERVICE_NAME_FORM,
         "uddiBusinessServiceNameForm",
+        "rfc4403",
     ),
     (
         &rfc4403::UDDI_BINDING_TEMPLATE_NAME_FORM,
         "uddiBindingTemplateNameForm",
+        "rfc4403",
     ),
     (
         &rfc4403::UDDI_T_MODEL_INSTANCE_INFO_NAME_FORM,
         "uddiTModelInstanceInfoNameForm",
+        "rfc4403",
+    ),
+    (
+        &rfc4403::UDDI_T_MODEL_NAME_FORM,
+        "uddiTModelNameForm",
+        "rfc4403",
     ),
-    (&rfc4403::UDDI_T_MODEL_NAME_FORM, "uddiTModelNameForm"),
     (
         &rfc4403::UDDI_PUBLISHER_ASSERTION_NAME_FORM,
         "uddiPublisherAssertionNameForm",
+        "rfc4403",
     ),
     (
         &rfc4403::UDDIV_3_SUBSCRIPTION_NAME_FORM,
         "uddiv3SubscriptionNameForm",
+        "rfc4403",
+    ),
+    (&rfc4403::UDDI_BUSINESS_KEY, "uddiBusinessKey", "rfc4403"),
+    (&rfc4403::UDDI_E_MAIL, "uddiEMail", "rfc4403"),
+    (&rfc4403::UDDI_SORT_CODE, "uddiSortCode", "rfc4403"),
+    (&rfc4403::UDDI_T_MODEL_KEY, "uddiTModelKey", "rfc4403"),
+    (&rfc4403::UDDI_ADDRESS_LINE, "uddiAddressLine", "rfc4403"),
+    (
+        &rfc4403::UDDI_IDENTIFIER_BAG,
+        "uddiIdentifierBag",
+        "rfc4403",
+    ),
+    (&rfc4403::UDDI_CATEGORY_BAG, "uddiCategoryBag", "rfc4403"),
+    (
+        &rfc4403::UDDI_KEYED_REFERENCE,
+        "uddiKeyedReference",
+        "rfc4403",
+    ),
+    (&rfc4403::UDDI_SERVICE_KEY, "uddiServiceKey", "rfc4403"),
+    (&rfc4403::UDDI_BINDING_KEY, "uddiBindingKey", "rfc4403"),
+    (&rfc4403::UDDI_ACCESS_POINT, "uddiAccessPoint", "rfc4403"),
+    (
+        &rfc4403::UDDI_AUTHORIZED_NAME,
+        "uddiAuthorizedName",
+        "rfc4403",
+    ),
+    (
+        &rfc4403::UDDI_HOSTING_REDIRECTOR,
+        "uddiHostingRedirector",
+        "rfc4403",
     ),
-    (&rfc4403::UDDI_BUSINESS_KEY, "uddiBusinessKey"),
-    (&rfc4403::UDDI_E_MAIL, "uddiEMail"),
-    (&rfc4403::UDDI_SORT_CODE, "uddiSortCode"),
-    (&rfc4403::UDDI_T_MODEL_KEY, "uddiTModelKey"),
-    (&rfc4403::UDDI_ADDRESS_LINE, "uddiAddressLine"),
-    (&rfc4403::UDDI_IDENTIFIER_BAG, "uddiIdentifierBag"),
-    (&rfc4403::UDDI_CATEGORY_BAG, "uddiCategoryBag"),
-    (&rfc4403::UDDI_KEYED_REFERENCE, "uddiKeyedReference"),
-    (&rfc4403::UDDI_SERVICE_KEY, "uddiServiceKey"),
-    (&rfc4403::UDDI_BINDING_KEY, "uddiBindingKey"),
-    (&rfc4403::UDDI_ACCESS_POINT, "uddiAccessPoint"),
-    (&rfc4403::UDDI_AUTHORIZED_NAME, "uddiAuthorizedName"),
-    (&rfc4403::UDDI_HOSTING_REDIRECTOR, "uddiHostingRedirector"),
     (
         &rfc4403::UDDI_INSTANCE_DESCRIPTION,
         "uddiInstanceDescription",
+        "rfc4403",
+    ),
+    (
+        &rfc4403::UDDI_INSTANCE_PARMS,
+        "uddiInstanceParms",
+        "rfc4403",
     ),
-    (&rfc4403::UDDI_INSTANCE_PARMS, "uddiInstanceParms"),
     (
         &rfc4403::UDDI_OVERVIEW_DESCRIPTION,
         "uddiOverviewDescription",
+        "rfc4403",
+    ),
+    (&rfc4403::UDDI_OVERVIEW_URL, "uddiOverviewURL", "rfc4403"),
+    (&rfc4403::UDDI_FROM_KEY, "uddiFromKey", "rfc4403"),
+    (&rfc4403::UDDI_TO_KEY, "uddiToKey", "rfc4403"),
+    (&rfc4403::UDDI_UUID, "uddiUUID", "rfc4403"),
+    (&rfc4403::UDDI_IS_HIDDEN, "uddiIsHidden", "rfc4403"),
+    (&rfc4403::UDDI_IS_PROJECTION, "uddiIsProjection", "rfc4403"),
+    (&rfc4403::UDDI_OPERATOR, "uddiOperator", "rfc4403"),
+    (&rfc4403::UDDI_LANG, "uddiLang", "rfc4403"),
+    (
+        &rfc4403::UDDIV_3_BUSINESS_KEY,
+        "uddiv3BusinessKey",
+        "rfc4403",
     ),
-    (&rfc4403::UDDI_OVERVIEW_URL, "uddiOverviewURL"),
-    (&rfc4403::UDDI_FROM_KEY, "uddiFromKey"),
-    (&rfc4403::UDDI_TO_KEY, "uddiToKey"),
-    (&rfc4403::UDDI_UUID, "uddiUUID"),
-    (&rfc4403::UDDI_IS_HIDDEN, "uddiIsHidden"),
-    (&rfc4403::UDDI_IS_PROJECTION, "uddiIsProjection"),
-    (&rfc4403::UDDI_OPERATOR, "uddiOperator"),
-    (&rfc4403::UDDI_LANG, "uddiLang"),
-    (&rfc4403::UDDIV_3_BUSINESS_KEY, "uddiv3BusinessKey"),
-    (&rfc4403::UDDIV_3_SERVICE_KEY, "uddiv3ServiceKey"),
-    (&rfc4403::UDDIV_3_BINDING_KEY, "uddiv3BindingKey"),
-    (&rfc4403::UDDIV_3_TMODEL_KEY, "uddiv3TmodelKey"),
+    (&rfc4403::UDDIV_3_SERVICE_KEY, "uddiv3ServiceKey", "rfc4403"),
+    (&rfc4403::UDDIV_3_BINDING_KEY, "uddiv3BindingKey", "rfc4403"),
+    (&rfc4403::UDDIV_3_TMODEL_KEY, "uddiv3TmodelKey", "rfc4403"),
     (
         &rfc4403::UDDIV_3_DIGITAL_SIGNATURE,
         "uddiv3DigitalSignature",
+        "rfc4403",
     ),
-    (&rfc4403::UDDIV_3_NODE_ID, "uddiv3NodeId"),
+    (&rfc4403::UDDIV_3_NODE_ID, "uddiv3NodeId", "rfc4403"),
     (
         &rfc4403::UDDIV_3_ENTITY_MODIFICATION_TIME,
         "uddiv3EntityModificationTime",
+        "rfc4403",
+    ),
+    (
+        &rfc4403::UDDIV_3_SUBSCRIPTION_KEY,
+        "uddiv3SubscriptionKey",
+        "rfc4403",
     ),
-    (&rfc4403::UDDIV_3_SUBSCRIPTION_KEY, "uddiv3SubscriptionKey"),
     (
         &rfc4403::UDDIV_3_SUBSCRIPTION_FILTER,
         "uddiv3SubscriptionFilter",
+        "rfc4403",
     ),
-    (&rfc4403::UDDI_NAME, "uddiName"),
+    (&rfc4403::UDDI_NAME, "uddiName", "rfc4403"),
     (
         &rfc4403::UDDIV_3_NOTIFICATION_INTERVAL,
         "uddiv3NotificationInterval",
+        "rfc4403",
+    ),
+    (
+        &rfc4403::UDDIV_3_MAX_ENTITIES,
+        "uddiv3MaxEntities",
+        "rfc4403",
+    ),
+    (
+        &rfc4403::UDDIV_3_EXPIRES_AFTER,
+        "uddiv3ExpiresAfter",
+        "rfc4403",
+    ),
+    (
+        &rfc4403::UDDIV_3_BRIEF_RESPONSE,
+        "uddiv3BriefResponse",
+        "rfc4403",
     ),
-    (&rfc4403::UDDIV_3_MAX_ENTITIES, "uddiv3MaxEntities"),
-    (&rfc4403::UDDIV_3_EXPIRES_AFTER, "uddiv3ExpiresAfter"),
-    (&rfc4403::UDDIV_3_BRIEF_RESPONSE, "uddiv3BriefResponse"),
-    (&rfc4403::UDDIV_3_ENTITY_KEY, "uddiv3EntityKey"),
+    (&rfc4403::UDDIV_3_ENTITY_KEY, "uddiv3EntityKey", "rfc4403"),
     (
         &rfc4403::UDDIV_3_ENTITY_CREATION_TIME,
         "uddiv3EntityCreationTime",
+        "rfc4403",
     ),
     (
         &rfc4403::UDDIV_3_ENTITY_DELETION_TIME,
         "uddiv3EntityDeletionTime",
+        "rfc4403",
     ),
-    (&rfc4403::UDDI_DESCRIPTION, "uddiDescription"),
-    (&rfc4403::UDDI_DISCOVERY_UR_LS, "uddiDiscoveryURLs"),
-    (&rfc4403::UDDI_USE_TYPE, "uddiUseType"),
-    (&rfc4403::UDDI_PERSON_NAME, "uddiPersonName"),
-    (&rfc4403::UDDI_PHONE, "uddiPhone"),
-    (&rfc4403::UDDI_BUSINESS_ENTITY, "uddiBusinessEntity"),
-    (&rfc4403::UDDIV_3_ENTITY_OBITUARY, "uddiv3EntityObituary"),
-    (&rfc4403::UDDI_CONTACT, "uddiContact"),
-    (&rfc4403::UDDI_ADDRESS, "uddiAddress"),
-    (&rfc4403::UDDI_BUSINESS_SERVICE, "uddiBusinessService"),
-    (&rfc4403::UDDI_BINDING_TEMPLATE, "uddiBindingTemplate"),
+    (&rfc4403::UDDI_DESCRIPTION, "uddiDescription", "rfc4403"),
     (
-        &rfc4403::UDDI_T_MODEL_INSTANCE_INFO,
-        "uddiTModelInstanceInfo",
+        &rfc4403::UDDI_DISCOVERY_UR_LS,
+        "uddiDiscoveryURLs",
+        "rfc4403",
     ),
-    (&rfc4403::UDDI_T_MODEL, "uddiTModel"),
-    (&rfc4403::UDDI_PUBLISHER_ASSERTION, "uddiPublisherAssertion"),
-    (&rfc4403::UDDIV_3_SUBSCRIPTION, "uddiv3Subscription"),
-    (&rfc4512::EXTENSIBLE_OBJECT, "extensibleObject"),
-    (&rfc4512::SUPPORTED_CONTROL, "supportedControl"),
+    (&rfc4403::UDDI_USE_TYPE, "uddiUseType", "rfc4403"),
+    (&rfc4403::UDDI_PERSON_NAME, "uddiPersonName", "rfc4403"),
+    (&rfc4403::UDDI_PHONE, "uddiPhone", "rfc4403"),
     (
-        &rfc4512::SUPPORTED_SASL_MECHANISMS,
-        "supportedSASLMechanisms",
+        &rfc4403::UDDI_BUSINESS_ENTITY,
+        "uddiBusinessEntity",
+        "rfc4403",
     ),
-    (&rfc4512::SUPPORTED_LDAP_VERSION, "supportedLDAPVersion"),
-    (&rfc4512::LDAP_SYNTAXES, "ldapSyntaxes"),
-    (&rfc4512::NAMING_CONTEXTS, "namingContexts"),
-    (&rfc4512::ALT_SERVER, "altServer"),
-    (&rfc4512::SUPPORTED_EXTENSION, "supportedExtension"),
-    (&rfc4512::SUPPORTED_FEATURES, "supportedFeatures"),
-    (&rfc4512::CREATE_TIMESTAMP, "createTimestamp"),
-    (&rfc4512::SUBSCHEMA_SUBENTRY, "subschemaSubentry"),
-    (&rfc4512::MODIFY_TIMESTAMP, "modifyTimestamp"),
-    (&rfc4512::CREATORS_NAME, "creatorsName"),
-    (&rfc4512::MODIFIERS_NAME, "modifiersName"),
-    (&rfc4512::SUBSCHEMA, "subschema"),
-    (&rfc4512::DIT_STRUCTURE_RULES, "dITStructureRules"),
-    (&rfc4512::GOVERNING_STRUCTURE_RULE, "governingStructureRule"),
-    (&rfc4512::DIT_CONTENT_RULES, "dITContentRules"),
-    (&rfc4512::MATCHING_RULES, "matchingRules"),
-    (&rfc4512::ATTRIBUTE_TYPES, "attributeTypes"),
-    (&rfc4512::OBJECT_CLASSES, "objectClasses"),
-    (&rfc4512::NAME_FORMS, "nameForms"),
-    (&rfc4512::MATCHING_RULE_USE, "matchingRuleUse"),
-    (&rfc4512::STRUCTURAL_OBJECT_CLASS, "structuralObjectClass"),
-    (&rfc4512::OBJECT_CLASS, "objectClass"),
-    (&rfc4512::ALIASED_OBJECT_NAME, "aliasedObjectName"),
-    (&rfc4512::TOP, "top"),
-    (&rfc4512::ALIAS, "alias"),
-    (&rfc4517::CASE_EXACT_IA_5_MATCH, "caseExactIA5Match"),
-    (&rfc4517::CASE_IGNORE_IA_5_MATCH, "caseIgnoreIA5Match"),
     (
-        &rfc4517::CASE_IGNORE_IA_5_SUBSTRINGS_MATCH,
-        "caseIgnoreIA5SubstringsMatch",
+        &rfc4403::UDDIV_3_ENTITY_OBITUARY,
+        "uddiv3EntityObituary",
+        "rfc4403",
     ),
-    (&rfc4517::OBJECT_IDENTIFIER_MATCH, "objectIdentifierMatch"),
-    (&rfc4517::DISTINGUISHED_NAME_MATCH, "distinguishedNameMatch"),
+    (&rfc4403::UDDI_CONTACT, "uddiContact", "rfc4403"),
+    (&rfc4403::UDDI_ADDRESS, "uddiAddress", "rfc4403"),
     (
-        &rfc4517::NUMERIC_STRING_SUBSTRINGS_MATCH,
-        "numericStringSubstringsMatch",
+        &rfc4403::UDDI_BUSINESS_SERVICE,
+        "uddiBusinessService",
+        "rfc4403",
     ),
-    (&rfc4517::CASE_IGNORE_LIST_MATCH, "caseIgnoreListMatch"),
     (
-        &rfc4517::CASE_IGNORE_LIST_SUBSTRINGS_MATCH,
-        "caseIgnoreListSubstringsMatch",
+        &rfc4403::UDDI_BINDING_TEMPLATE,
+        "uddiBindingTemplate",
+        "rfc4403",
     ),
-    (&rfc4517::BOOLEAN_MATCH, "booleanMatch"),
-    (&rfc4517::INTEGER_MATCH, "integerMatch"),
-    (&rfc4517::INTEGER_ORDERING_MATCH, "integerOrderingMatch"),
-    (&rfc4517::BIT_STRING_MATCH, "bitStringMatch"),
-    (&rfc4517::OCTET_STRING_MATCH, "octetStringMatch"),
     (
-        &rfc4517::OCTET_STRING_ORDERING_MATCH,
-        "octetStringOrderingMatch",
+        &rfc4403::UDDI_T_MODEL_INSTANCE_INFO,
+        "uddiTModelInstanceInfo",
+        "rfc4403",
     ),
-    (&rfc4517::CASE_IGNORE_MATCH, "caseIgnoreMatch"),
-    (&rfc4517::TELEPHONE_NUMBER_MATCH, "telephoneNumberMatch"),
+    (&rfc4403::UDDI_T_MODEL, "uddiTModel", "rfc4403"),
     (
-        &rfc4517::TELEPHONE_NUMBER_SUBSTRINGS_MATCH,
-        "telephoneNumberSubstringsMatch",
+        &rfc4403::UDDI_PUBLISHER_ASSERTION,
+        "uddiPublisherAssertion",
+        "rfc4403",
     ),
-    (&rfc4517::UNIQUE_MEMBER_MATCH, "uniqueMemberMatch"),
-    (&rfc4517::GENERALIZED_TIME_MATCH, "generalizedTimeMatch"),
     (
-        &rfc4517::GENERALIZED_TIME_ORDERING_MATCH,
+        &rfc4403::UDDIV_3_SUBSCRIPTION,
+        "uddiv3Subscription",
+        "rfc4403",
+    ),
+    (&rfc4512::EXTENSIBLE_OBJECT, "extensibleObject", "rfc4512"),
+    (&rfc4512::SUPPORTED_CONTROL, "supportedControl", "rfc4512"),
+    (
+        &rfc4512::SUPPORTED_SASL_MECHANISMS,
+        "supportedSASLMechanisms",
+        "rfc4512",
+    ),
+    (
+        &rfc4512::SUPPORTED_LDAP_VERSION,
+        "supportedLDAPVersion",
+        "rfc4512",
+    ),
+    (&rfc4512::LDAP_SYNTAXES, "ldapSyntaxes", "rfc4512"),
+    (&rfc4512::NAMING_CONTEXTS, "namingContexts", "rfc4512"),
+    (&rfc4512::ALT_SERVER, "altServer", "rfc4512"),
+    (
+        &rfc4512::SUPPORTED_EXTENSION,
+        "supportedExtension",
+        "rfc4512",
+    ),
+    (&rfc4512::SUPPORTED_FEATURES, "supportedFeatures", "rfc4512"),
+    (&rfc4512::CREATE_TIMESTAMP, "createTimestamp", "rfc4512"),
+    (&rfc4512::SUBSCHEMA_SUBENTRY, "subschemaSubentry", "rfc4512"),
+    (&rfc4512::MODIFY_TIMESTAMP, "modifyTimestamp", "rfc4512"),
+    (&rfc4512::CREATORS_NAME, "creatorsName", "rfc4512"),
+    (&rfc4512::MODIFIERS_NAME, "modifiersName", "rfc4512"),
+    (&rfc4512::SUBSCHEMA, "subschema", "rfc4512"),
+    (
+        &rfc4512::DIT_STRUCTURE_RULES,
+        "dITStructureRules",
+        "rfc4512",
+    ),
+    (
+        &rfc4512::GOVERNING_STRUCTURE_RULE,
+        "governingStructureRule",
+        "rfc4512",
+    ),
+    (&rfc4512::DIT_CONTENT_RULES, "dITContentRules", "rfc4512"),
+    (&rfc4512::MATCHING_RULES, "matchingRules", "rfc4512"),
+    (&rfc4512::ATTRIBUTE_TYPES, "attributeTypes", "rfc4512"),
+    (&rfc4512::OBJECT_CLASSES, "objectClasses", "rfc4512"),
+    (&rfc4512::NAME_FORMS, "nameForms", "rfc4512"),
+    (&rfc4512::MATCHING_RULE_USE, "matchingRuleUse", "rfc4512"),
+    (
+        &rfc4512::STRUCTURAL_OBJECT_CLASS,
+        "structuralObjectClass",
+        "rfc4512",
+    ),
+    (&rfc4512::OBJECT_CLASS, "objectClass", "rfc4512"),
+    (
+        &rfc4512::ALIASED_OBJECT_NAME,
+        "aliasedObjectName",
+        "rfc4512",
+    ),
+    (&rfc4512::TOP, "top", "rfc4512"),
+    (&rfc4512::ALIAS, "alias", "rfc4512"),
+    (
+        &rfc4517::CASE_EXACT_IA_5_MATCH,
+        "caseExactIA5Match",
+        "rfc4517",
+    ),
+    (
+        &rfc4517::CASE_IGNORE_IA_5_MATCH,
+        "caseIgnoreIA5Match",
+        "rfc4517",
+    ),
+    (
+        &rfc4517::CASE_IGNORE_IA_5_SUBSTRINGS_MATCH,
+        "caseIgnoreIA5SubstringsMatch",
+        "rfc4517",
+    ),
+    (
+        &rfc4517::OBJECT_IDENTIFIER_MATCH,
+        "objectIdentifierMatch",
+        "rfc4517",
+    ),
+    (
+        &rfc4517::DISTINGUISHED_NAME_MATCH,
+        "distinguishedNameMatch",
+        "rfc4517",
+    ),
+    (
+        &rfc4517::NUMERIC_STRING_SUBSTRINGS_MATCH,
+        "numericStringSubstringsMatch",
+        "rfc4517",
+    ),
+    (
+        &rfc4517::CASE_IGNORE_LIST_MATCH,
+        "caseIgnoreListMatch",
+        "rfc4517",
+    ),
+    (
+        &rfc4517::CASE_IGNORE_LIST_SUBSTRINGS_MATCH,
+        "caseIgnoreListSubstringsMatch",
+        "rfc4517",
+    ),
+    (&rfc4517::BOOLEAN_MATCH, "booleanMatch", "rfc4517"),
+    (&rfc4517::INTEGER_MATCH, "integerMatch", "rfc4517"),
+    (
+        &rfc4517::INTEGER_ORDERING_MATCH,
+        "integerOrderingMatch",
+        "rfc4517",
+    ),
+    (&rfc4517::BIT_STRING_MATCH, "bitStringMatch", "rfc4517"),
+    (&rfc4517::OCTET_STRING_MATCH, "octetStringMatch", "rfc4517"),
+    (
+        &rfc4517::OCTET_STRING_ORDERING_MATCH,
+        "octetStringOrderingMatch",
+        "rfc4517",
+    ),
+    (&rfc4517::CASE_IGNORE_MATCH, "caseIgnoreMatch", "rfc4517"),
+    (
+        &rfc4517::TELEPHONE_NUMBER_MATCH,
+        "telephoneNumberMatch",
+        "rfc4517",
+    ),
+    (
+        &rfc4517::TELEPHONE_NUMBER_SUBSTRINGS_MATCH,
+        "telephoneNumberSubstringsMatch",
+        "rfc4517",
+    ),
+    (
+        &rfc4517::UNIQUE_MEMBER_MATCH,
+        "uniqueMemberMatch",
+        "rfc4517",
+    ),
+    (
+        &rfc4517::GENERALIZED_TIME_MATCH,
+        "generalizedTimeMatch",
+        "rfc4517",
+    ),
+    (
+        &rfc4517::GENERALIZED_TIME_ORDERING_MATCH,
         "generalizedTimeOrderingMatch",
+        "rfc4517",
     ),
     (
         &rfc4517::INTEGER_FIRST_COMPONENT_MATCH,
         "integerFirstComponentMatch",
+        "rfc4517",
     ),
     (
         &rfc4517::CASE_IGNORE_ORDERING_MATCH,
         "caseIgnoreOrderingMatch",
+        "rfc4517",
     ),
     (
         &rfc4517::OBJECT_IDENTIFIER_FIRST_COMPONENT_MATCH,
         "objectIdentifierFirstComponentMatch",
+        "rfc4517",
     ),
     (
         &rfc4517::DIRECTORY_STRING_FIRST_COMPONENT_MATCH,
         "directoryStringFirstComponentMatch",
+        "rfc4517",
     ),
-    (&rfc4517::WORD_MATCH, "wordMatch"),
-    (&rfc4517::KEYWORD_MATCH, "keywordMatch"),
+    (&rfc4517::WORD_MATCH, "wordMatch", "rfc4517"),
+    (&rfc4517::KEYWORD_MATCH, "keywordMatch", "rfc4517"),
     (
         &rfc4517::CASE_IGNORE_SUBSTRINGS_MATCH,
         "caseIgnoreSubstringsMatch",
+        "rfc4517",
     ),
-    (&rfc4517::CASE_EXACT_MATCH, "caseExactMatch"),
+    (&rfc4517::CASE_EXACT_MATCH, "caseExactMatch", "rfc4517"),
     (
         &rfc4517::CASE_EXACT_ORDERING_MATCH,
         "caseExactOrderingMatch",
+        "rfc4517",
     ),
     (
         &rfc4517::CASE_EXACT_SUBSTRINGS_MATCH,
         "caseExactSubstringsMatch",
+        "rfc4517",
+    ),
+    (
+        &rfc4517::NUMERIC_STRING_MATCH,
+        "numericStringMatch",
+        "rfc4517",
     ),
-    (&rfc4517::NUMERIC_STRING_MATCH, "numericStringMatch"),
     (
         &rfc4517::NUMERIC_STRING_ORDERING_MATCH,
         "numericStringOrderingMatch",
-    ),
-    (&rfc4519::UID, "uid"),
-    (&rfc4519::USER_ID, "userId"),
-    (&rfc4519::DC, "DC"),
-    (&rfc4519::DOMAIN_COMPONENT, "domainComponent"),
-    (&rfc4519::UID_OBJECT, "uidObject"),
-    (&rfc4519::DC_OBJECT, "dcObject"),
-    (&rfc4519::O, "o"),
-    (&rfc4519::ORGANIZATION_NAME, "organizationName"),
-    (&rfc4519::OU, "ou"),
-    (&rfc4519::ORGANIZATIONAL_UNIT_NAME, "organizationalUnitName"),
-    (&rfc4519::TITLE, "title"),
-    (&rfc4519::DESCRIPTION, "description"),
-    (&rfc4519::SEARCH_GUIDE, "searchGuide"),
-    (&rfc4519::BUSINESS_CATEGORY, "businessCategory"),
-    (&rfc4519::POSTAL_ADDRESS, "postalAddress"),
-    (&rfc4519::POSTAL_CODE, "postalCode"),
-    (&rfc4519::POST_OFFICE_BOX, "postOfficeBox"),
+        "rfc4517",
+    ),
+    (&rfc4519::UID, "uid", "rfc4519"),
+    (&rfc4519::USER_ID, "userId", "rfc4519"),
+    (&rfc4519::DC, "DC", "rfc4519"),
+    (&rfc4519::DOMAIN_COMPONENT, "domainComponent", "rfc4519"),
+    (&rfc4519::UID_OBJECT, "uidObject", "rfc4519"),
+    (&rfc4519::DC_OBJECT, "dcObject", "rfc4519"),
+    (&rfc4519::O, "o", "rfc4519"),
+    (&rfc4519::ORGANIZATION_NAME, "organizationName", "rfc4519"),
+    (&rfc4519::OU, "ou", "rfc4519"),
+    (
+        &rfc4519::ORGANIZATIONAL_UNIT_NAME,
+        "organizationalUnitName",
+        "rfc4519",
+    ),
+    (&rfc4519::TITLE, "title", "rfc4519"),
+    (&rfc4519::DESCRIPTION, "description", "rfc4519"),
+    (&rfc4519::SEARCH_GUIDE, "searchGuide", "rfc4519"),
+    (&rfc4519::BUSINESS_CATEGORY, "businessCategory", "rfc4519"),
+    (&rfc4519::POSTAL_ADDRESS, "postalAddress", "rfc4519"),
+    (&rfc4519::POSTAL_CODE, "postalCode", "rfc4519"),
+    (&rfc4519::POST_OFFICE_BOX, "postOfficeBox", "rfc4519"),
     (
         &rfc4519::PHYSICAL_DELIVERY_OFFICE_NAME,
         "physicalDeliveryOfficeName",
+        "rfc4519",
     ),
-    (&rfc4519::TELEPHONE_NUMBER, "telephoneNumber"),
-    (&rfc4519::TELEX_NUMBER, "telexNumber"),
+    (&rfc4519::TELEPHONE_NUMBER, "telephoneNumber", "rfc4519"),
+    (&rfc4519::TELEX_NUMBER, "telexNumber", "rfc4519"),
     (
         &rfc4519::TELETEX_TERMINAL_IDENTIFIER,
         "teletexTerminalIdentifier",
+        "rfc4519",
     ),
     (
         &rfc4519::FACSIMILE_TELEPHONE_NUMBER,
         "facsimileTelephoneNumber",
+        "rfc4519",
     ),
-    (&rfc4519::X_121_ADDRESS, "x121Address"),
+    (&rfc4519::X_121_ADDRESS, "x121Address", "rfc4519"),
     (
         &rfc4519::INTERNATIONALI_SDN_NUMBER,
         "internationaliSDNNumber",
+        "rfc4519",
+    ),
+    (&rfc4519::REGISTERED_ADDRESS, "registeredAddress", "rfc4519"),
+    (
+        &rfc4519::DESTINATION_INDICATOR,
+        "destinationIndicator",
+        "rfc4519",
     ),
-    (&rfc4519::REGISTERED_ADDRESS, "registeredAddress"),
-    (&rfc4519::DESTINATION_INDICATOR, "destinationIndicator"),
     (
         &rfc4519::PREFERRED_DELIVERY_METHOD,
         "preferredDeliveryMethod",
+        "rfc4519",
+    ),
+    (&rfc4519::CN, "cn", "rfc4519"),
+    (&rfc4519::COMMON_NAME, "commonName", "rfc4519"),
+    (&rfc4519::MEMBER, "member", "rfc4519"),
+    (&rfc4519::OWNER, "owner", "rfc4519"),
+    (&rfc4519::ROLE_OCCUPANT, "roleOccupant", "rfc4519"),
+    (&rfc4519::SEE_ALSO, "seeAlso", "rfc4519"),
+    (&rfc4519::USER_PASSWORD, "userPassword", "rfc4519"),
+    (&rfc4519::SN, "sn", "rfc4519"),
+    (&rfc4519::SURNAME, "surname", "rfc4519"),
+    (&rfc4519::NAME, "name", "rfc4519"),
+    (&rfc4519::GN, "GN", "rfc4519"),
+    (&rfc4519::GIVEN_NAME, "givenName", "rfc4519"),
+    (&rfc4519::INITIALS, "initials", "rfc4519"),
+    (
+        &rfc4519::GENERATION_QUALIFIER,
+        "generationQualifier",
+        "rfc4519",
+    ),
+    (
+        &rfc4519::X_500_UNIQUE_IDENTIFIER,
+        "x500UniqueIdentifier",
+        "rfc4519",
+    ),
+    (&rfc4519::DN_QUALIFIER, "dnQualifier", "rfc4519"),
+    (
+        &rfc4519::ENHANCED_SEARCH_GUIDE,
+        "enhancedSearchGuide",
+        "rfc4519",
     ),
-    (&rfc4519::CN, "cn"),
-    (&rfc4519::COMMON_NAME, "commonName"),
-    (&rfc4519::MEMBER, "member"),
-    (&rfc4519::OWNER, "owner"),
-    (&rfc4519::ROLE_OCCUPANT, "roleOccupant"),
-    (&rfc4519::SEE_ALSO, "seeAlso"),
-    (&rfc4519::USER_PASSWORD, "userPassword"),
-    (&rfc4519::SN, "sn"),
-    (&rfc4519::SURNAME, "surname"),
-    (&rfc4519::NAME, "name"),
-    (&rfc4519::GN, "GN"),
-    (&rfc4519::GIVEN_NAME, "givenName"),
-    (&rfc4519::INITIALS, "initials"),
-    (&rfc4519::GENERATION_QUALIFIER, "generationQualifier"),
-    (&rfc4519::X_500_UNIQUE_IDENTIFIER, "x500UniqueIdentifier"),
-    (&rfc4519::DN_QUALIFIER, "dnQualifier"),
-    (&rfc4519::ENHANCED_SEARCH_GUIDE, "enhancedSearchGuide"),
-    (&rfc4519::DISTINGUISHED_NAME, "distinguishedName"),
-    (&rfc4519::SERIAL_NUMBER, "serialNumber"),
-    (&rfc4519::UNIQUE_MEMBER, "uniqueMember"),
-    (&rfc4519::HOUSE_IDENTIFIER, "houseIdentifier"),
-    (&rfc4519::C, "c"),
-    (&rfc4519::COUNTRY_NAME, "countryName"),
-    (&rfc4519::L, "L"),
-    (&rfc4519::LOCALITY_NAME, "localityName"),
-    (&rfc4519::ST, "st"),
-    (&rfc4519::STREET, "street"),
-    (&rfc4519::RESIDENTIAL_PERSON, "residentialPerson"),
-    (&rfc4519::APPLICATION_PROCESS, "applicationProcess"),
-    (&rfc4519::DEVICE, "device"),
-    (&rfc4519::GROUP_OF_UNIQUE_NAMES, "groupOfUniqueNames"),
-    (&rfc4519::COUNTRY, "country"),
-    (&rfc4519::LOCALITY, "locality"),
-    (&rfc4519::ORGANIZATION, "organization"),
-    (&rfc4519::ORGANIZATIONAL_UNIT, "organizationalUnit"),
-    (&rfc4519::PERSON, "person"),
-    (&rfc4519::ORGANIZATIONAL_PERSON, "organizationalPerson"),
-    (&rfc4519::ORGANIZATIONAL_ROLE, "organizationalRole"),
-    (&rfc4519::GROUP_OF_NAMES, "groupOfNames"),
-    (&rfc4523::CERTIFICATE_EXACT_MATCH, "certificateExactMatch"),
-    (&rfc4523::CERTIFICATE_MATCH, "certificateMatch"),
+    (&rfc4519::DISTINGUISHED_NAME, "distinguishedName", "rfc4519"),
+    (&rfc4519::SERIAL_NUMBER, "serialNumber", "rfc4519"),
+    (&rfc4519::UNIQUE_MEMBER, "uniqueMember", "rfc4519"),
+    (&rfc4519::HOUSE_IDENTIFIER, "houseIdentifier", "rfc4519"),
+    (&rfc4519::C, "c", "rfc4519"),
+    (&rfc4519::COUNTRY_NAME, "countryName", "rfc4519"),
+    (&rfc4519::L, "L", "rfc4519"),
+    (&rfc4519::LOCALITY_NAME, "localityName", "rfc4519"),
+    (&rfc4519::ST, "st", "rfc4519"),
+    (&rfc4519::STREET, "street", "rfc4519"),
+    (&rfc4519::RESIDENTIAL_PERSON, "residentialPerson", "rfc4519"),
+    (
+        &rfc4519::APPLICATION_PROCESS,
+        "applicationProcess",
+        "rfc4519",
+    ),
+    (&rfc4519::DEVICE, "device", "rfc4519"),
+    (
+        &rfc4519::GROUP_OF_UNIQUE_NAMES,
+        "groupOfUniqueNames",
+        "rfc4519",
+    ),
+    (&rfc4519::COUNTRY, "country", "rfc4519"),
+    (&rfc4519::LOCALITY, "locality", "rfc4519"),
+    (&rfc4519::ORGANIZATION, "organization", "rfc4519"),
+    (
+        &rfc4519::ORGANIZATIONAL_UNIT,
+        "organizationalUnit",
+        "rfc4519",
+    ),
+    (&rfc4519::PERSON, "person", "rfc4519"),
+    (
+        &rfc4519::ORGANIZATIONAL_PERSON,
+        "organizationalPerson",
+        "rfc4519",
+    ),
+    (
+        &rfc4519::ORGANIZATIONAL_ROLE,
+        "organizationalRole",
+        "rfc4519",
+    ),
+    (&rfc4519::GROUP_OF_NAMES, "groupOfNames", "rfc4519"),
+    (
+        &rfc4523::CERTIFICATE_EXACT_MATCH,
+        "certificateExactMatch",
+        "rfc4523",
+    ),
+    (&rfc4523::CERTIFICATE_MATCH, "certificateMatch", "rfc4523"),
     (
         &rfc4523::CERTIFICATE_PAIR_EXACT_MATCH,
         "certificatePairExactMatch",
+        "rfc4523",
+    ),
+    (
+        &rfc4523::CERTIFICATE_PAIR_MATCH,
+        "certificatePairMatch",
+        "rfc4523",
     ),
-    (&rfc4523::CERTIFICATE_PAIR_MATCH, "certificatePairMatch"),
     (
         &rfc4523::CERTIFICATE_LIST_EXACT_MATCH,
         "certificateListExactMatch",
+        "rfc4523",
+    ),
+    (
+        &rfc4523::CERTIFICATE_LIST_MATCH,
+        "certificateListMatch",
+        "rfc4523",
     ),
-    (&rfc4523::CERTIFICATE_LIST_MATCH, "certificateListMatch"),
     (
         &rfc4523::ALGORITHM_IDENTIFIER_MATCH,
         "algorithmIdentifierMatch",
+        "rfc4523",
     ),
-    (&rfc4523::USER_CERTIFICATE, "userCertificate"),
-    (&rfc4523::CA_CERTIFICATE, "cACertificate"),
+    (&rfc4523::USER_CERTIFICATE, "userCertificate", "rfc4523"),
+    (&rfc4523::CA_CERTIFICATE, "cACertificate", "rfc4523"),
     (
         &rfc4523::AUTHORITY_REVOCATION_LIST,
         "authorityRevocationList",
+        "rfc4523",
     ),
     (
         &rfc4523::CERTIFICATE_REVOCATION_LIST,
         "certificateRevocationList",
+        "rfc4523",
+    ),
+    (
+        &rfc4523::CROSS_CERTIFICATE_PAIR,
+        "crossCertificatePair",
+        "rfc4523",
+    ),
+    (
+        &rfc4523::SUPPORTED_ALGORITHMS,
+        "supportedAlgorithms",
+        "rfc4523",
+    ),
+    (
+        &rfc4523::DELTA_REVOCATION_LIST,
+        "deltaRevocationList",
+        "rfc4523",
     ),
-    (&rfc4523::CROSS_CERTIFICATE_PAIR, "crossCertificatePair"),
-    (&rfc4523::SUPPORTED_ALGORITHMS, "supportedAlgorithms"),
-    (&rfc4523::DELTA_REVOCATION_LIST, "deltaRevocationList"),
     (
         &rfc4523::STRONG_AUTHENTICATION_USER,
         "strongAuthenticationUser",
+        "rfc4523",
+    ),
+    (
+        &rfc4523::CERTIFICATION_AUTHORITY,
+        "certificationAuthority",
+        "rfc4523",
     ),
-    (&rfc4523::CERTIFICATION_AUTHORITY, "certificationAuthority"),
     (
         &rfc4523::CERTIFICATION_AUTHORITY_V_2,
         "certificationAuthority-V2",
+        "rfc4523",
     ),
     (
         &rfc4523::USER_SECURITY_INFORMATION,
         "userSecurityInformation",
+        "rfc4523",
+    ),
+    (
+        &rfc4523::CRL_DISTRIBUTION_POINT,
+        "cRLDistributionPoint",
+        "rfc4523",
+    ),
+    (&rfc4523::PKI_USER, "pkiUser", "rfc4523"),
+    (&rfc4523::PKI_CA, "pkiCA", "rfc4523"),
+    (&rfc4523::DELTA_CRL, "deltaCRL", "rfc4523"),
+    (&rfc4524::MANAGER, "manager", "rfc4524"),
+    (
+        &rfc4524::DOCUMENT_IDENTIFIER,
+        "documentIdentifier",
+        "rfc4524",
+    ),
+    (&rfc4524::DOCUMENT_TITLE, "documentTitle", "rfc4524"),
+    (&rfc4524::DOCUMENT_VERSION, "documentVersion", "rfc4524"),
+    (&rfc4524::DOCUMENT_AUTHOR, "documentAuthor", "rfc4524"),
+    (&rfc4524::DOCUMENT_LOCATION, "documentLocation", "rfc4524"),
+    (&rfc4524::HOME_PHONE, "homePhone", "rfc4524"),
+    (&rfc4524::HOME_TELEPHONE, "homeTelephone", "rfc4524"),
+    (&rfc4524::SECRETARY, "secretary", "rfc4524"),
+    (&rfc4524::MAIL, "mail", "rfc4524"),
+    (&rfc4524::RFC_822_MAILBOX, "RFC822Mailbox", "rfc4524"),
+    (&rfc4524::ASSOCIATED_DOMAIN, "associatedDomain", "rfc4524"),
+    (&rfc4524::ASSOCIATED_NAME, "associatedName", "rfc4524"),
+    (
+        &rfc4524::HOME_POSTAL_ADDRESS,
+        "homePostalAddress",
+        "rfc4524",
+    ),
+    (&rfc4524::INFO, "info", "rfc4524"),
+    (&rfc4524::PERSONAL_TITLE, "personalTitle", "rfc4524"),
+    (&rfc4524::MOBILE, "mobile", "rfc4524"),
+    (
+        &rfc4524::MOBILE_TELEPHONE_NUMBER,
+        "mobileTelephoneNumber",
+        "rfc4524",
+    ),
+    (&rfc4524::PAGER, "pager", "rfc4524"),
+    (
+        &rfc4524::PAGER_TELEPHONE_NUMBER,
+        "pagerTelephoneNumber",
+        "rfc4524",
+    ),
+    (&rfc4524::CO, "co", "rfc4524"),
+    (
+        &rfc4524::FRIENDLY_COUNTRY_NAME,
+        "friendlyCountryName",
+        "rfc4524",
+    ),
+    (&rfc4524::UNIQUE_IDENTIFIER, "uniqueIdentifier", "rfc4524"),
+    (
+        &rfc4524::ORGANIZATIONAL_STATUS,
+        "organizationalStatus",
+        "rfc4524",
+    ),
+    (&rfc4524::BUILDING_NAME, "buildingName", "rfc4524"),
+    (&rfc4524::DRINK, "drink", "rfc4524"),
+    (&rfc4524::FAVOURITE_DRINK, "favouriteDrink", "rfc4524"),
+    (
+        &rfc4524::SINGLE_LEVEL_QUALITY,
+        "singleLevelQuality",
+        "rfc4524",
+    ),
+    (&rfc4524::DOCUMENT_PUBLISHER, "documentPublisher", "rfc4524"),
+    (&rfc4524::ROOM_NUMBER, "roomNumber", "rfc4524"),
+    (&rfc4524::USER_CLASS, "userClass", "rfc4524"),
+    (&rfc4524::HOST, "host", "rfc4524"),
+    (&rfc4524::DOMAIN, "domain", "rfc4524"),
+    (&rfc4524::RFC_822_LOCAL_PART, "RFC822LocalPart", "rfc4524"),
+    (
+        &rfc4524::DOMAIN_RELATED_OBJECT,
+        "domainRelatedObject",
+        "rfc4524",
+    ),
+    (&rfc4524::FRIENDLY_COUNTRY, "friendlyCountry", "rfc4524"),
+    (
+        &rfc4524::SIMPLE_SECURITY_OBJECT,
+        "simpleSecurityObject",
+        "rfc4524",
+    ),
+    (&rfc4524::ACCOUNT, "account", "rfc4524"),
+    (&rfc4524::DOCUMENT, "document", "rfc4524"),
+    (&rfc4524::ROOM, "room", "rfc4524"),
+    (&rfc4524::DOCUMENT_SERIES, "documentSeries", "rfc4524"),
+    (&rfc4530::UUID_MATCH, "uuidMatch", "rfc4530"),
+    (
+        &rfc4530::UUID_ORDERING_MATCH,
+        "uuidOrderingMatch",
+        "rfc4530",
+    ),
+    (&rfc4530::ENTRY_UUID, "entryUUID", "rfc4530"),
+    (
+        &rfc4876::DEFAULT_SERVER_LIST,
+        "defaultServerList",
+        "rfc4876",
+    ),
+    (
+        &rfc4876::DEFAULT_SEARCH_BASE,
+        "defaultSearchBase",
+        "rfc4876",
+    ),
+    (&rfc4876::CREDENTIAL_LEVEL, "credentialLevel", "rfc4876"),
+    (&rfc4876::OBJECTCLASS_MAP, "objectclassMap", "rfc4876"),
+    (
+        &rfc4876::DEFAULT_SEARCH_SCOPE,
+        "defaultSearchScope",
+        "rfc4876",
+    ),
+    (
+        &rfc4876::SERVICE_CREDENTIAL_LEVEL,
+        "serviceCredentialLevel",
+        "rfc4876",
     ),
-    (&rfc4523::CRL_DISTRIBUTION_POINT, "cRLDistributionPoint"),
-    (&rfc4523::PKI_USER, "pkiUser"),
-    (&rfc4523::PKI_CA, "pkiCA"),
-    (&rfc4523::DELTA_CRL, "deltaCRL"),
-    (&rfc4524::MANAGER, "manager"),
-    (&rfc4524::DOCUMENT_IDENTIFIER, "documentIdentifier"),
-    (&rfc4524::DOCUMENT_TITLE, "documentTitle"),
-    (&rfc4524::DOCUMENT_VERSION, "documentVersion"),
-    (&rfc4524::DOCUMENT_AUTHOR, "documentAuthor"),
-    (&rfc4524::DOCUMENT_LOCATION, "documentLocation"),
-    (&rfc4524::HOME_PHONE, "homePhone"),
-    (&rfc4524::HOME_TELEPHONE, "homeTelephone"),
-    (&rfc4524::SECRETARY, "secretary"),
-    (&rfc4524::MAIL, "mail"),
-    (&rfc4524::RFC_822_MAILBOX, "RFC822Mailbox"),
-    (&rfc4524::ASSOCIATED_DOMAIN, "associatedDomain"),
-    (&rfc4524::ASSOCIATED_NAME, "associatedName"),
-    (&rfc4524::HOME_POSTAL_ADDRESS, "homePostalAddress"),
-    (&rfc4524::INFO, "info"),
-    (&rfc4524::PERSONAL_TITLE, "personalTitle"),
-    (&rfc4524::MOBILE, "mobile"),
-    (&rfc4524::MOBILE_TELEPHONE_NUMBER, "mobileTelephoneNumber"),
-    (&rfc4524::PAGER, "pager"),
-    (&rfc4524::PAGER_TELEPHONE_NUMBER, "pagerTelephoneNumber"),
-    (&rfc4524::CO, "co"),
-    (&rfc4524::FRIENDLY_COUNTRY_NAME, "friendlyCountryName"),
-    (&rfc4524::UNIQUE_IDENTIFIER, "uniqueIdentifier"),
-    (&rfc4524::ORGANIZATIONAL_STATUS, "organizationalStatus"),
-    (&rfc4524::BUILDING_NAME, "buildingName"),
-    (&rfc4524::DRINK, "drink"),
-    (&rfc4524::FAVOURITE_DRINK, "favouriteDrink"),
-    (&rfc4524::SINGLE_LEVEL_QUALITY, "singleLevelQuality"),
-    (&rfc4524::DOCUMENT_PUBLISHER, "documentPublisher"),
-    (&rfc4524::ROOM_NUMBER, "roomNumber"),
-    (&rfc4524::USER_CLASS, "userClass"),
-    (&rfc4524::HOST, "host"),
-    (&rfc4524::DOMAIN, "domain"),
-    (&rfc4524::RFC_822_LOCAL_PART, "RFC822LocalPart"),
-    (&rfc4524::DOMAIN_RELATED_OBJECT, "domainRelatedObject"),
-    (&rfc4524::FRIENDLY_COUNTRY, "friendlyCountry"),
-    (&rfc4524::SIMPLE_SECURITY_OBJECT, "simpleSecurityObject"),
-    (&rfc4524::ACCOUNT, "account"),
-    (&rfc4524::DOCUMENT, "document"),
-    (&rfc4524::ROOM, "room"),
-    (&rfc4524::DOCUMENT_SERIES, "documentSeries"),
-    (&rfc4530::UUID_MATCH, "uuidMatch"),
-    (&rfc4530::UUID_ORDERING_MATCH, "uuidOrderingMatch"),
-    (&rfc4530::ENTRY_UUID, "entryUUID"),
-    (&rfc4876::DEFAULT_SERVER_LIST, "defaultServerList"),
-    (&rfc4876::DEFAULT_SEARCH_BASE, "defaultSearchBase"),
-    (&rfc4876::CREDENTIAL_LEVEL, "credentialLevel"),
-    (&rfc4876::OBJECTCLASS_MAP, "objectclassMap"),
-    (&rfc4876::DEFAULT_SEARCH_SCOPE, "defaultSearchScope"),
-    (&rfc4876::SERVICE_CREDENTIAL_LEVEL, "serviceCredentialLevel"),
     (
         &rfc4876::SERVICE_SEARCH_DESCRIPTOR,
         "serviceSearchDescriptor",
+        "rfc4876",
     ),
     (
         &rfc4876::SERVICE_AUTHENTICATION_METHOD,
         "serviceAuthenticationMethod",
+        "rfc4876",
     ),
-    (&rfc4876::DEREFERENCE_ALIASES, "dereferenceAliases"),
-    (&rfc4876::PREFERRED_SERVER_LIST, "preferredServerList"),
-    (&rfc4876::SEARCH_TIME_LIMIT, "searchTimeLimit"),
-    (&rfc4876::BIND_TIME_LIMIT, "bindTimeLimit"),
-    (&rfc4876::FOLLOW_REFERRALS, "followReferrals"),
-    (&rfc4876::AUTHENTICATION_METHOD, "authenticationMethod"),
-    (&rfc4876::PROFILE_TTL, "profileTTL"),
-    (&rfc4876::ATTRIBUTE_MAP, "attributeMap"),
-    (&rfc4876::DUA_CONFIG_PROFILE, "DUAConfigProfile"),
-    (&rfc5020::ENTRY_DN, "entryDN"),
-    (&rfc5280::PKCS_9, "pkcs-9"),
-    (&rfc5280::ID_PKIX, "id-pkix"),
-    (&rfc5280::ID_PE, "id-pe"),
+    (
+        &rfc4876::DEREFERENCE_ALIASES,
+        "dereferenceAliases",
+        "rfc4876",
+    ),
+    (
+        &rfc4876::PREFERRED_SERVER_LIST,
+        "preferredServerList",
+        "rfc4876",
+    ),
+    (&rfc4876::SEARCH_TIME_LIMIT, "searchTimeLimit", "rfc4876"),
+    (&rfc4876::BIND_TIME_LIMIT, "bindTimeLimit", "rfc4876"),
+    (&rfc4876::FOLLOW_REFERRALS, "followReferrals", "rfc4876"),
+    (
+        &rfc4876::AUTHENTICATION_METHOD,
+        "authenticationMethod",
+        "rfc4876",
+    ),
+    (&rfc4876::PROFILE_TTL, "profileTTL", "rfc4876"),
+    (&rfc4876::ATTRIBUTE_MAP, "attributeMap", "rfc4876"),
+    (&rfc4876::DUA_CONFIG_PROFILE, "DUAConfigProfile", "rfc4876"),
+    (&rfc5020::ENTRY_DN, "entryDN", "rfc5020"),
+    (&rfc5280::PKCS_9, "pkcs-9", "rfc5280"),
+    (&rfc5280::ID_PKIX, "id-pkix", "rfc5280"),
+    (&rfc5280::ID_PE, "id-pe", "rfc5280"),
     (
         &rfc5280::ID_PE_AUTHORITY_INFO_ACCESS,
         "id-pe-authorityInfoAccess",
+        "rfc5280",
     ),
     (
         &rfc5280::ID_PE_SUBJECT_INFO_ACCESS,
         "id-pe-subjectInfoAccess",
+        "rfc5280",
+    ),
+    (&rfc5280::ID_QT, "id-qt", "rfc5280"),
+    (&rfc5280::ID_QT_CPS, "id-qt-cps", "rfc5280"),
+    (&rfc5280::ID_QT_UNOTICE, "id-qt-unotice", "rfc5280"),
+    (&rfc5280::ID_KP, "id-kp", "rfc5280"),
+    (&rfc5280::ID_KP_SERVER_AUTH, "id-kp-serverAuth", "rfc5280"),
+    (&rfc5280::ID_KP_CLIENT_AUTH, "id-kp-clientAuth", "rfc5280"),
+    (&rfc5280::ID_KP_CODE_SIGNING, "id-kp-codeSigning", "rfc5280"),
+    (
+        &rfc5280::ID_KP_EMAIL_PROTECTION,
+        "id-kp-emailProtection",
+        "rfc5280",
+    ),
+    (
+        &rfc5280::ID_KP_TIME_STAMPING,
+        "id-kp-timeStamping",
+        "rfc5280",
+    ),
+    (&rfc5280::ID_KP_OCSP_SIGNING, "id-kp-OCSPSigning", "rfc5280"),
+    (&rfc5280::ID_AD, "id-ad", "rfc5280"),
+    (&rfc5280::ID_AD_OCSP, "id-ad-ocsp", "rfc5280"),
+    (&rfc5280::ID_AD_CA_ISSUERS, "id-ad-caIssuers", "rfc5280"),
+    (
+        &rfc5280::ID_AD_TIME_STAMPING,
+        "id-ad-timeStamping",
+        "rfc5280",
+    ),
+    (
+        &rfc5280::ID_AD_CA_REPOSITORY,
+        "id-ad-caRepository",
+        "rfc5280",
+    ),
+    (&rfc5280::HOLD_INSTRUCTION, "holdInstruction", "rfc5280"),
+    (
+        &rfc5280::ID_HOLDINSTRUCTION_NONE,
+        "id-holdinstruction-none",
+        "rfc5280",
     ),
-    (&rfc5280::ID_QT, "id-qt"),
-    (&rfc5280::ID_QT_CPS, "id-qt-cps"),
-    (&rfc5280::ID_QT_UNOTICE, "id-qt-unotice"),
-    (&rfc5280::ID_KP, "id-kp"),
-    (&rfc5280::ID_KP_SERVER_AUTH, "id-kp-serverAuth"),
-    (&rfc5280::ID_KP_CLIENT_AUTH, "id-kp-clientAuth"),
-    (&rfc5280::ID_KP_CODE_SIGNING, "id-kp-codeSigning"),
-    (&rfc5280::ID_KP_EMAIL_PROTECTION, "id-kp-emailProtection"),
-    (&rfc5280::ID_KP_TIME_STAMPING, "id-kp-timeStamping"),
-    (&rfc5280::ID_KP_OCSP_SIGNING, "id-kp-OCSPSigning"),
-    (&rfc5280::ID_AD, "id-ad"),
-    (&rfc5280::ID_AD_OCSP, "id-ad-ocsp"),
-    (&rfc5280::ID_AD_CA_ISSUERS, "id-ad-caIssuers"),
-    (&rfc5280::ID_AD_TIME_STAMPING, "id-ad-timeStamping"),
-    (&rfc5280::ID_AD_CA_REPOSITORY, "id-ad-caRepository"),
-    (&rfc5280::HOLD_INSTRUCTION, "holdInstruction"),
-    (&rfc5280::ID_HOLDINSTRUCTION_NONE, "id-holdinstruction-none"),
     (
         &rfc5280::ID_HOLDINSTRUCTION_CALLISSUER,
         "id-holdinstruction-callissuer",
+        "rfc5280",
     ),
     (
         &rfc5280::ID_HOLDINSTRUCTION_REJECT,
         "id-holdinstruction-reject",
+        "rfc5280",
     ),
-    (&rfc5280::ID_CE, "id-ce"),
+    (&rfc5280::ID_CE, "id-ce", "rfc5280"),
     (
         &rfc5280::ID_CE_SUBJECT_KEY_IDENTIFIER,
         "id-ce-subjectKeyIdentifier",
+        "rfc5280",
     ),
-    (&rfc5280::ID_CE_KEY_USAGE, "id-ce-keyUsage"),
+    (&rfc5280::ID_CE_KEY_USAGE, "id-ce-keyUsage", "rfc5280"),
     (
         &rfc5280::ID_CE_PRIVATE_KEY_USAGE_PERIOD,
         "id-ce-privateKeyUsagePeriod",
+        "rfc5280",
+    ),
+    (
+        &rfc5280::ID_CE_SUBJECT_ALT_NAME,
+        "id-ce-subjectAltName",
+        "rfc5280",
+    ),
+    (
+        &rfc5280::ID_CE_ISSUER_ALT_NAME,
+        "id-ce-issuerAltName",
+        "rfc5280",
     ),
-    (&rfc5280::ID_CE_SUBJECT_ALT_NAME, "id-ce-subjectAltName"),
-    (&rfc5280::ID_CE_ISSUER_ALT_NAME, "id-ce-issuerAltName"),
-    (&rfc5280::ID_CE_BASIC_CONSTRAINTS, "id-ce-basicConstraints"),
-    (&rfc5280::ID_CE_CRL_NUMBER, "id-ce-cRLNumber"),
-    (&rfc5280::ID_CE_CRL_REASONS, "id-ce-cRLReasons"),
+    (
+        &rfc5280::ID_CE_BASIC_CONSTRAINTS,
+        "id-ce-basicConstraints",
+        "rfc5280",
+    ),
+    (&rfc5280::ID_CE_CRL_NUMBER, "id-ce-cRLNumber", "rfc5280"),
+    (&rfc5280::ID_CE_CRL_REASONS, "id-ce-cRLReasons", "rfc5280"),
     (
         &rfc5280::ID_CE_HOLD_INSTRUCTION_CODE,
         "id-ce-holdInstructionCode",
+        "rfc5280",
+    ),
+    (
+        &rfc5280::ID_CE_INVALIDITY_DATE,
+        "id-ce-invalidityDate",
+        "rfc5280",
     ),
-    (&rfc5280::ID_CE_INVALIDITY_DATE, "id-ce-invalidityDate"),
     (
         &rfc5280::ID_CE_DELTA_CRL_INDICATOR,
         "id-ce-deltaCRLIndicator",
+        "rfc5280",
     ),
     (
         &rfc5280::ID_CE_ISSUING_DISTRIBUTION_POINT,
         "id-ce-issuingDistributionPoint",
+        "rfc5280",
     ),
     (
         &rfc5280::ID_CE_CERTIFICATE_ISSUER,
         "id-ce-certificateIssuer",
+        "rfc5280",
+    ),
+    (
+        &rfc5280::ID_CE_NAME_CONSTRAINTS,
+        "id-ce-nameConstraints",
+        "rfc5280",
     ),
-    (&rfc5280::ID_CE_NAME_CONSTRAINTS, "id-ce-nameConstraints"),
     (
         &rfc5280::ID_CE_CRL_DISTRIBUTION_POINTS,
         "id-ce-cRLDistributionPoints",
+        "rfc5280",
     ),
     (
         &rfc5280::ID_CE_CERTIFICATE_POLICIES,
         "id-ce-certificatePolicies",
+        "rfc5280",
+    ),
+    (&rfc5280::ANY_POLICY, "anyPolicy", "rfc5280"),
+    (
+        &rfc5280::ID_CE_POLICY_MAPPINGS,
+        "id-ce-policyMappings",
+        "rfc5280",
     ),
-    (&rfc5280::ANY_POLICY, "anyPolicy"),
-    (&rfc5280::ID_CE_POLICY_MAPPINGS, "id-ce-policyMappings"),
     (
         &rfc5280::ID_CE_AUTHORITY_KEY_IDENTIFIER,
         "id-ce-authorityKeyIdentifier",
+        "rfc5280",
     ),
     (
         &rfc5280::ID_CE_POLICY_CONSTRAINTS,
         "id-ce-policyConstraints",
+        "rfc5280",
+    ),
+    (
+        &rfc5280::ID_CE_EXT_KEY_USAGE,
+        "id-ce-extKeyUsage",
+        "rfc5280",
+    ),
+    (
+        &rfc5280::ANY_EXTENDED_KEY_USAGE,
+        "anyExtendedKeyUsage",
+        "rfc5280",
+    ),
+    (&rfc5280::ID_CE_FRESHEST_CRL, "id-ce-freshestCRL", "rfc5280"),
+    (
+        &rfc5280::ID_CE_INHIBIT_ANY_POLICY,
+        "id-ce-inhibitAnyPolicy",
+        "rfc5280",
     ),
-    (&rfc5280::ID_CE_EXT_KEY_USAGE, "id-ce-extKeyUsage"),
-    (&rfc5280::ANY_EXTENDED_KEY_USAGE, "anyExtendedKeyUsage"),
-    (&rfc5280::ID_CE_FRESHEST_CRL, "id-ce-freshestCRL"),
-    (&rfc5280::ID_CE_INHIBIT_ANY_POLICY, "id-ce-inhibitAnyPolicy"),
     (
         &rfc5280::ID_CE_SUBJECT_DIRECTORY_ATTRIBUTES,
         "id-ce-subjectDirectoryAttributes",
+        "rfc5280",
+    ),
+    (&rfc5280::ID_AT, "id-at", "rfc5280"),
+    (&rfc5639::ELLIPTIC_CURVE, "ellipticCurve", "rfc5639"),
+    (&rfc5639::VERSION_ONE, "versionOne", "rfc5639"),
+    (&rfc5639::BRAINPOOL_P_160_R_1, "brainpoolP160r1", "rfc5639"),
+    (&rfc5639::BRAINPOOL_P_320_T_1, "brainpoolP320t1", "rfc5639"),
+    (&rfc5639::BRAINPOOL_P_384_R_1, "brainpoolP384r1", "rfc5639"),
+    (&rfc5639::BRAINPOOL_P_384_T_1, "brainpoolP384t1", "rfc5639"),
+    (&rfc5639::BRAINPOOL_P_512_R_1, "brainpoolP512r1", "rfc5639"),
+    (&rfc5639::BRAINPOOL_P_512_T_1, "brainpoolP512t1", "rfc5639"),
+    (&rfc5639::BRAINPOOL_P_160_T_1, "brainpoolP160t1", "rfc5639"),
+    (&rfc5639::BRAINPOOL_P_192_R_1, "brainpoolP192r1", "rfc5639"),
+    (&rfc5639::BRAINPOOL_P_192_T_1, "brainpoolP192t1", "rfc5639"),
+    (&rfc5639::BRAINPOOL_P_224_R_1, "brainpoolP224r1", "rfc5639"),
+    (&rfc5639::BRAINPOOL_P_224_T_1, "brainpoolP224t1", "rfc5639"),
+    (&rfc5639::BRAINPOOL_P_256_R_1, "brainpoolP256r1", "rfc5639"),
+    (&rfc5639::BRAINPOOL_P_256_T_1, "brainpoolP256t1", "rfc5639"),
+    (&rfc5639::BRAINPOOL_P_320_R_1, "brainpoolP320r1", "rfc5639"),
+    (&rfc5753::ANSI_X_9_62, "ansi-x9-62", "rfc5753"),
+    (&rfc5753::ID_EC_PUBLIC_KEY, "id-ecPublicKey", "rfc5753"),
+    (
+        &rfc5753::ID_HMAC_WITH_SHA_384,
+        "id-hmacWithSHA384",
+        "rfc5753",
+    ),
+    (
+        &rfc5753::ID_HMAC_WITH_SHA_512,
+        "id-hmacWithSHA512",
+        "rfc5753",
+    ),
+    (
+        &rfc5753::ID_HMAC_WITH_SHA_224,
+        "id-hmacWithSHA224",
+        "rfc5753",
     ),
-    (&rfc5280::ID_AT, "id-at"),
-    (&rfc5639::ELLIPTIC_CURVE, "ellipticCurve"),
-    (&rfc5639::VERSION_ONE, "versionOne"),
-    (&rfc5639::BRAINPOOL_P_160_R_1, "brainpoolP160r1"),
-    (&rfc5639::BRAINPOOL_P_320_T_1, "brainpoolP320t1"),
-    (&rfc5639::BRAINPOOL_P_384_R_1, "brainpoolP384r1"),
-    (&rfc5639::BRAINPOOL_P_384_T_1, "brainpoolP384t1"),
-    (&rfc5639::BRAINPOOL_P_512_R_1, "brainpoolP512r1"),
-    (&rfc5639::BRAINPOOL_P_512_T_1, "brainpoolP512t1"),
-    (&rfc5639::BRAINPOOL_P_160_T_1, "brainpoolP160t1"),
-    (&rfc5639::BRAINPOOL_P_192_R_1, "brainpoolP192r1"),
-    (&rfc5639::BRAINPOOL_P_192_T_1, "brainpoolP192t1"),
-    (&rfc5639::BRAINPOOL_P_224_R_1, "brainpoolP224r1"),
-    (&rfc5639::BRAINPOOL_P_224_T_1, "brainpoolP224t1"),
-    (&rfc5639::BRAINPOOL_P_256_R_1, "brainpoolP256r1"),
-    (&rfc5639::BRAINPOOL_P_256_T_1, "brainpoolP256t1"),
-    (&rfc5639::BRAINPOOL_P_320_R_1, "brainpoolP320r1"),
-    (&rfc5753::ANSI_X_9_62, "ansi-x9-62"),
-    (&rfc5753::ID_EC_PUBLIC_KEY, "id-ecPublicKey"),
-    (&rfc5753::ID_HMAC_WITH_SHA_384, "id-hmacWithSHA384"),
-    (&rfc5753::ID_HMAC_WITH_SHA_512, "id-hmacWithSHA512"),
-    (&rfc5753::ID_HMAC_WITH_SHA_224, "id-hmacWithSHA224"),
-    (&rfc5753::ID_HMAC_WITH_SHA_256, "id-hmacWithSHA256"),
-    (&rfc5753::SECG_SCHEME, "secg-scheme"),
+    (
+        &rfc5753::ID_HMAC_WITH_SHA_256,
+        "id-hmacWithSHA256",
+        "rfc5753",
+    ),
+    (&rfc5753::SECG_SCHEME, "secg-scheme", "rfc5753"),
     (
         &rfc5753::DH_SINGLE_PASS_STD_DH_SHA_224_KDF_SCHEME,
         "dhSinglePass-stdDH-sha224kdf-scheme",
+        "rfc5753",
     ),
     (
         &rfc5753::DH_SINGLE_PASS_STD_DH_SHA_256_KDF_SCHEME,
         "dhSinglePass-stdDH-sha256kdf-scheme",
+        "rfc5753",
     ),
     (
         &rfc5753::DH_SINGLE_PASS_STD_DH_SHA_384_KDF_SCHEME,
         "dhSinglePass-stdDH-sha384kdf-scheme",
+        "rfc5753",
     ),
     (
         &rfc5753::DH_SINGLE_PASS_STD_DH_SHA_512_KDF_SCHEME,
         "dhSinglePass-stdDH-sha512kdf-scheme",
+        "rfc5753",
     ),
     (
         &rfc5753::DH_SINGLE_PASS_COFACTOR_DH_SHA_224_KDF_SCHEME,
         "dhSinglePass-cofactorDH-sha224kdf-scheme",
+        "rfc5753",
     ),
     (
         &rfc5753::DH_SINGLE_PASS_COFACTOR_DH_SHA_256_KDF_SCHEME,
         "dhSinglePass-cofactorDH-sha256kdf-scheme",
+        "rfc5753",
     ),
     (
         &rfc5753::DH_SINGLE_PASS_COFACTOR_DH_SHA_384_KDF_SCHEME,
         "dhSinglePass-cofactorDH-sha384kdf-scheme",
+        "rfc5753",
     ),
     (
         &rfc5753::DH_SINGLE_PASS_COFACTOR_DH_SHA_512_KDF_SCHEME,
         "dhSinglePass-cofactorDH-sha512kdf-scheme",
+        "rfc5753",
     ),
     (
         &rfc5753::MQV_SINGLE_PASS_SHA_224_KDF_SCHEME,
         "mqvSinglePass-sha224kdf-scheme",
+        "rfc5753",
     ),
     (
         &rfc5753::MQV_SINGLE_PASS_SHA_256_KDF_SCHEME,
         "mqvSinglePass-sha256kdf-scheme",
+        "rfc5753",
     ),
     (
         &rfc5753::MQV_SINGLE_PASS_SHA_384_KDF_SCHEME,
         "mqvSinglePass-sha384kdf-scheme",
+        "rfc5753",
     ),
     (
         &rfc5753::MQV_SINGLE_PASS_SHA_512_KDF_SCHEME,
         "mqvSinglePass-sha512kdf-scheme",
+        "rfc5753",
     ),
-    (&rfc5753::X_9_63_SCHEME, "x9-63-scheme"),
+    (&rfc5753::X_9_63_SCHEME, "x9-63-scheme", "rfc5753"),
     (
         &rfc5753::MQV_SINGLE_PASS_SHA_1_KDF_SCHEME,
         "mqvSinglePass-sha1kdf-scheme",
+        "rfc5753",
     ),
     (
         &rfc5753::DH_SINGLE_PASS_STD_DH_SHA_1_KDF_SCHEME,
         "dhSinglePass-stdDH-sha1kdf-scheme",
+        "rfc5753",
     ),
     (
         &rfc5753::DH_SINGLE_PASS_COFACTOR_DH_SHA_1_KDF_SCHEME,
         "dhSinglePass-cofactorDH-sha1kdf-scheme",
+        "rfc5753",
+    ),
+    (&rfc5911::ID_PBKDF_2, "id-PBKDF2", "rfc5911"),
+    (&rfc5911::ID_DATA, "id-data", "rfc5911"),
+    (&rfc5911::ID_SIGNED_DATA, "id-signedData", "rfc5911"),
+    (&rfc5911::ID_ENVELOPED_DATA, "id-envelopedData", "rfc5911"),
+    (&rfc5911::ID_DIGESTED_DATA, "id-digestedData", "rfc5911"),
+    (&rfc5911::ID_ENCRYPTED_DATA, "id-encryptedData", "rfc5911"),
+    (&rfc5911::SMIME_CAPABILITIES, "smimeCapabilities", "rfc5911"),
+    (&rfc5911::ID_SMIME, "id-smime", "rfc5911"),
+    (&rfc5911::ID_CT_RECEIPT, "id-ct-receipt", "rfc5911"),
+    (
+        &rfc5911::ID_CT_FIRMWARE_PACKAGE,
+        "id-ct-firmwarePackage",
+        "rfc5911",
     ),
-    (&rfc5911::ID_PBKDF_2, "id-PBKDF2"),
-    (&rfc5911::ID_DATA, "id-data"),
-    (&rfc5911::ID_SIGNED_DATA, "id-signedData"),
-    (&rfc5911::ID_ENVELOPED_DATA, "id-envelopedData"),
-    (&rfc5911::ID_DIGESTED_DATA, "id-digestedData"),
-    (&rfc5911::ID_ENCRYPTED_DATA, "id-encryptedData"),
-    (&rfc5911::SMIME_CAPABILITIES, "smimeCapabilities"),
-    (&rfc5911::ID_SMIME, "id-smime"),
-    (&rfc5911::ID_CT_RECEIPT, "id-ct-receipt"),
-    (&rfc5911::ID_CT_FIRMWARE_PACKAGE, "id-ct-firmwarePackage"),
     (
         &rfc5911::ID_CT_FIRMWARE_LOAD_RECEIPT,
         "id-ct-firmwareLoadReceipt",
+        "rfc5911",
     ),
     (
         &rfc5911::ID_CT_FIRMWARE_LOAD_ERROR,
         "id-ct-firmwareLoadError",
+        "rfc5911",
     ),
-    (&rfc5911::ID_CT_AUTH_DATA, "id-ct-authData"),
+    (&rfc5911::ID_CT_AUTH_DATA, "id-ct-authData", "rfc5911"),
     (
         &rfc5911::ID_CT_AUTH_ENVELOPED_DATA,
         "id-ct-authEnvelopedData",
+        "rfc5911",
     ),
-    (&rfc5911::ID_CT_CONTENT_INFO, "id-ct-contentInfo"),
-    (&rfc5911::ID_CAP, "id-cap"),
+    (&rfc5911::ID_CT_CONTENT_INFO, "id-ct-contentInfo", "rfc5911"),
+    (&rfc5911::ID_CAP, "id-cap", "rfc5911"),
     (
         &rfc5911::ID_CAP_PREFER_BINARY_INSIDE,
         "id-cap-preferBinaryInside",
+        "rfc5911",
+    ),
+    (&rfc5911::ID_AA, "id-aa", "rfc5911"),
+    (
+        &rfc5911::ID_AA_RECEIPT_REQUEST,
+        "id-aa-receiptRequest",
+        "rfc5911",
+    ),
+    (
+        &rfc5911::ID_AA_CONTENT_REFERENCE,
+        "id-aa-contentReference",
+        "rfc5911",
+    ),
+    (
+        &rfc5911::ID_AA_ENCRYP_KEY_PREF,
+        "id-aa-encrypKeyPref",
+        "rfc5911",
     ),
-    (&rfc5911::ID_AA, "id-aa"),
-    (&rfc5911::ID_AA_RECEIPT_REQUEST, "id-aa-receiptRequest"),
-    (&rfc5911::ID_AA_CONTENT_REFERENCE, "id-aa-contentReference"),
-    (&rfc5911::ID_AA_ENCRYP_KEY_PREF, "id-aa-encrypKeyPref"),
     (
         &rfc5911::ID_AA_SIGNING_CERTIFICATE,
         "id-aa-signingCertificate",
+        "rfc5911",
+    ),
+    (
+        &rfc5911::ID_AA_SECURITY_LABEL,
+        "id-aa-securityLabel",
+        "rfc5911",
+    ),
+    (
+        &rfc5911::ID_AA_ML_EXPAND_HISTORY,
+        "id-aa-mlExpandHistory",
+        "rfc5911",
     ),
-    (&rfc5911::ID_AA_SECURITY_LABEL, "id-aa-securityLabel"),
-    (&rfc5911::ID_AA_ML_EXPAND_HISTORY, "id-aa-mlExpandHistory"),
     (
         &rfc5911::ID_AA_FIRMWARE_PACKAGE_ID,
         "id-aa-firmwarePackageID",
+        "rfc5911",
     ),
     (
         &rfc5911::ID_AA_TARGET_HARDWARE_I_DS,
         "id-aa-targetHardwareIDs",
+        "rfc5911",
+    ),
+    (
+        &rfc5911::ID_AA_DECRYPT_KEY_ID,
+        "id-aa-decryptKeyID",
+        "rfc5911",
+    ),
+    (
+        &rfc5911::ID_AA_IMPL_CRYPTO_ALGS,
+        "id-aa-implCryptoAlgs",
+        "rfc5911",
     ),
-    (&rfc5911::ID_AA_DECRYPT_KEY_ID, "id-aa-decryptKeyID"),
-    (&rfc5911::ID_AA_IMPL_CRYPTO_ALGS, "id-aa-implCryptoAlgs"),
     (
         &rfc5911::ID_AA_WRAPPED_FIRMWARE_KEY,
         "id-aa-wrappedFirmwareKey",
+        "rfc5911",
     ),
-    (&rfc5911::ID_AA_CONTENT_HINT, "id-aa-contentHint"),
+    (&rfc5911::ID_AA_CONTENT_HINT, "id-aa-contentHint", "rfc5911"),
     (
         &rfc5911::ID_AA_COMMUNITY_IDENTIFIERS,
         "id-aa-communityIdentifiers",
+        "rfc5911",
     ),
     (
         &rfc5911::ID_AA_FIRMWARE_PACKAGE_INFO,
         "id-aa-firmwarePackageInfo",
+        "rfc5911",
+    ),
+    (
+        &rfc5911::ID_AA_IMPL_COMPRESS_ALGS,
+        "id-aa-implCompressAlgs",
+        "rfc5911",
     ),
-    (&rfc5911::ID_AA_IMPL_COMPRESS_ALGS, "id-aa-implCompressAlgs"),
     (
         &rfc5911::ID_AA_SIGNING_CERTIFICATE_V_2,
         "id-aa-signingCertificateV2",
+        "rfc5911",
     ),
-    (&rfc5911::ID_AA_ER_INTERNAL, "id-aa-er-internal"),
-    (&rfc5911::ID_AA_MSG_SIG_DIGEST, "id-aa-msgSigDigest"),
-    (&rfc5911::ID_AA_ER_EXTERNAL, "id-aa-er-external"),
+    (&rfc5911::ID_AA_ER_INTERNAL, "id-aa-er-internal", "rfc5911"),
+    (
+        &rfc5911::ID_AA_MSG_SIG_DIGEST,
+        "id-aa-msgSigDigest",
+        "rfc5911",
+    ),
+    (&rfc5911::ID_AA_ER_EXTERNAL, "id-aa-er-external", "rfc5911"),
     (
         &rfc5911::ID_AA_CONTENT_IDENTIFIER,
         "id-aa-contentIdentifier",
+        "rfc5911",
+    ),
+    (
+        &rfc5911::ID_AA_EQUIVALENT_LABELS,
+        "id-aa-equivalentLabels",
+        "rfc5911",
+    ),
+    (&rfc5911::ID_ALG_SSDH, "id-alg-SSDH", "rfc5911"),
+    (&rfc5911::ID_ALG_ESDH, "id-alg-ESDH", "rfc5911"),
+    (
+        &rfc5911::ID_ALG_CMS_3_DE_SWRAP,
+        "id-alg-CMS3DESwrap",
+        "rfc5911",
+    ),
+    (
+        &rfc5911::ID_ALG_CMSRC_2_WRAP,
+        "id-alg-CMSRC2wrap",
+        "rfc5911",
+    ),
+    (&rfc5911::ID_SKD, "id-skd", "rfc5911"),
+    (&rfc5911::ID_SKD_GL_USE_KEK, "id-skd-glUseKEK", "rfc5911"),
+    (
+        &rfc5911::ID_SKD_GLA_QUERY_REQUEST,
+        "id-skd-glaQueryRequest",
+        "rfc5911",
     ),
-    (&rfc5911::ID_AA_EQUIVALENT_LABELS, "id-aa-equivalentLabels"),
-    (&rfc5911::ID_ALG_SSDH, "id-alg-SSDH"),
-    (&rfc5911::ID_ALG_ESDH, "id-alg-ESDH"),
-    (&rfc5911::ID_ALG_CMS_3_DE_SWRAP, "id-alg-CMS3DESwrap"),
-    (&rfc5911::ID_ALG_CMSRC_2_WRAP, "id-alg-CMSRC2wrap"),
-    (&rfc5911::ID_SKD, "id-skd"),
-    (&rfc5911::ID_SKD_GL_USE_KEK, "id-skd-glUseKEK"),
-    (&rfc5911::ID_SKD_GLA_QUERY_REQUEST, "id-skd-glaQueryRequest"),
     (
         &rfc5911::ID_SKD_GLA_QUERY_RESPONSE,
         "id-skd-glaQueryResponse",
+        "rfc5911",
+    ),
+    (
+        &rfc5911::ID_SKD_GL_PROVIDE_CERT,
+        "id-skd-glProvideCert",
+        "rfc5911",
+    ),
+    (
+        &rfc5911::ID_SKD_GL_MANAGE_CERT,
+        "id-skd-glManageCert",
+        "rfc5911",
+    ),
+    (&rfc5911::ID_SKD_GL_KEY, "id-skd-glKey", "rfc5911"),
+    (&rfc5911::ID_SKD_GL_DELETE, "id-skd-glDelete", "rfc5911"),
+    (
+        &rfc5911::ID_SKD_GL_ADD_MEMBER,
+        "id-skd-glAddMember",
+        "rfc5911",
+    ),
+    (
+        &rfc5911::ID_SKD_GL_DELETE_MEMBER,
+        "id-skd-glDeleteMember",
+        "rfc5911",
+    ),
+    (&rfc5911::ID_SKD_GL_REKEY, "id-skd-glRekey", "rfc5911"),
+    (
+        &rfc5911::ID_SKD_GL_ADD_OWNER,
+        "id-skd-glAddOwner",
+        "rfc5911",
     ),
-    (&rfc5911::ID_SKD_GL_PROVIDE_CERT, "id-skd-glProvideCert"),
-    (&rfc5911::ID_SKD_GL_MANAGE_CERT, "id-skd-glManageCert"),
-    (&rfc5911::ID_SKD_GL_KEY, "id-skd-glKey"),
-    (&rfc5911::ID_SKD_GL_DELETE, "id-skd-glDelete"),
-    (&rfc5911::ID_SKD_GL_ADD_MEMBER, "id-skd-glAddMember"),
-    (&rfc5911::ID_SKD_GL_DELETE_MEMBER, "id-skd-glDeleteMember"),
-    (&rfc5911::ID_SKD_GL_REKEY, "id-skd-glRekey"),
-    (&rfc5911::ID_SKD_GL_ADD_OWNER, "id-skd-glAddOwner"),
-    (&rfc5911::ID_SKD_GL_REMOVE_OWNER, "id-skd-glRemoveOwner"),
-    (&rfc5911::ID_SKD_GL_KEY_COMPROMISE, "id-skd-glKeyCompromise"),
-    (&rfc5911::ID_SKD_GLK_REFRESH, "id-skd-glkRefresh"),
-    (&rfc5911::ID_CONTENT_TYPE, "id-contentType"),
-    (&rfc5911::ID_MESSAGE_DIGEST, "id-messageDigest"),
-    (&rfc5911::ID_SIGNING_TIME, "id-signingTime"),
-    (&rfc5911::ID_COUNTERSIGNATURE, "id-countersignature"),
-    (&rfc5911::RC_2_CBC, "rc2-cbc"),
-    (&rfc5911::DES_EDE_3_CBC, "des-ede3-cbc"),
-    (&rfc5911::LTANS, "ltans"),
-    (&rfc5911::ID_CET_SKD_FAIL_INFO, "id-cet-skdFailInfo"),
-    (&rfc5911::ID_CMC_GLA_RR, "id-cmc-glaRR"),
+    (
+        &rfc5911::ID_SKD_GL_REMOVE_OWNER,
+        "id-skd-glRemoveOwner",
+        "rfc5911",
+    ),
+    (
+        &rfc5911::ID_SKD_GL_KEY_COMPROMISE,
+        "id-skd-glKeyCompromise",
+        "rfc5911",
+    ),
+    (&rfc5911::ID_SKD_GLK_REFRESH, "id-skd-glkRefresh", "rfc5911"),
+    (&rfc5911::ID_CONTENT_TYPE, "id-contentType", "rfc5911"),
+    (&rfc5911::ID_MESSAGE_DIGEST, "id-messageDigest", "rfc5911"),
+    (&rfc5911::ID_SIGNING_TIME, "id-signingTime", "rfc5911"),
+    (
+        &rfc5911::ID_COUNTERSIGNATURE,
+        "id-countersignature",
+        "rfc5911",
+    ),
+    (&rfc5911::RC_2_CBC, "rc2-cbc", "rfc5911"),
+    (&rfc5911::DES_EDE_3_CBC, "des-ede3-cbc", "rfc5911"),
+    (&rfc5911::LTANS, "ltans", "rfc5911"),
+    (
+        &rfc5911::ID_CET_SKD_FAIL_INFO,
+        "id-cet-skdFailInfo",
+        "rfc5911",
+    ),
+    (&rfc5911::ID_CMC_GLA_RR, "id-cmc-glaRR", "rfc5911"),
     (
         &rfc5911::ID_CMC_GLA_SKD_ALG_REQUEST,
         "id-cmc-gla-skdAlgRequest",
+        "rfc5911",
     ),
     (
         &rfc5911::ID_CMC_GLA_SKD_ALG_RESPONSE,
         "id-cmc-gla-skdAlgResponse",
+        "rfc5911",
     ),
     (
         &rfc5911::ID_ON_HARDWARE_MODULE_NAME,
         "id-on-hardwareModuleName",
-    ),
-    (&rfc5911::HMAC_SHA_1, "hMAC-SHA1"),
-    (&rfc5911::AES, "aes"),
-    (&rfc5911::ID_AES_128_CBC, "id-aes128-CBC"),
-    (&rfc5911::ID_AES_192_CBC, "id-aes192-CBC"),
-    (&rfc5911::ID_AES_192_WRAP, "id-aes192-wrap"),
-    (&rfc5911::ID_AES_192_GCM, "id-aes192-GCM"),
-    (&rfc5911::ID_AES_192_CCM, "id-aes192-CCM"),
-    (&rfc5911::ID_AES_256_CBC, "id-aes256-CBC"),
-    (&rfc5911::ID_AES_256_WRAP, "id-aes256-wrap"),
-    (&rfc5911::ID_AES_256_GCM, "id-aes256-GCM"),
-    (&rfc5911::ID_AES_256_CCM, "id-aes256-CCM"),
-    (&rfc5911::ID_AES_128_WRAP, "id-aes128-wrap"),
-    (&rfc5911::ID_AES_128_GCM, "id-aes128-GCM"),
-    (&rfc5911::ID_AES_128_CCM, "id-aes128-CCM"),
-    (&rfc5912::ID_DSA, "id-dsa"),
-    (&rfc5912::DSA_WITH_SHA_1, "dsa-with-sha1"),
-    (&rfc5912::ID_EC_PUBLIC_KEY, "id-ecPublicKey"),
-    (&rfc5912::SECP_256_R_1, "secp256r1"),
-    (&rfc5912::ECDSA_WITH_SHA_224, "ecdsa-with-SHA224"),
-    (&rfc5912::ECDSA_WITH_SHA_256, "ecdsa-with-SHA256"),
-    (&rfc5912::ECDSA_WITH_SHA_384, "ecdsa-with-SHA384"),
-    (&rfc5912::ECDSA_WITH_SHA_512, "ecdsa-with-SHA512"),
-    (&rfc5912::DHPUBLICNUMBER, "dhpublicnumber"),
-    (&rfc5912::ID_PASSWORD_BASED_MAC, "id-PasswordBasedMac"),
-    (&rfc5912::ID_DH_BASED_MAC, "id-DHBasedMac"),
-    (&rfc5912::PKCS_1, "pkcs-1"),
-    (&rfc5912::RSA_ENCRYPTION, "rsaEncryption"),
-    (&rfc5912::ID_RSASSA_PSS, "id-RSASSA-PSS"),
+        "rfc5911",
+    ),
+    (&rfc5911::HMAC_SHA_1, "hMAC-SHA1", "rfc5911"),
+    (&rfc5911::AES, "aes", "rfc5911"),
+    (&rfc5911::ID_AES_128_CBC, "id-aes128-CBC", "rfc5911"),
+    (&rfc5911::ID_AES_192_CBC, "id-aes192-CBC", "rfc5911"),
+    (&rfc5911::ID_AES_192_WRAP, "id-aes192-wrap", "rfc5911"),
+    (&rfc5911::ID_AES_192_GCM, "id-aes192-GCM", "rfc5911"),
+    (&rfc5911::ID_AES_192_CCM, "id-aes192-CCM", "rfc5911"),
+    (&rfc5911::ID_AES_256_CBC, "id-aes256-CBC", "rfc5911"),
+    (&rfc5911::ID_AES_256_WRAP, "id-aes256-wrap", "rfc5911"),
+    (&rfc5911::ID_AES_256_GCM, "id-aes256-GCM", "rfc5911"),
+    (&rfc5911::ID_AES_256_CCM, "id-aes256-CCM", "rfc5911"),
+    (&rfc5911::ID_AES_128_WRAP, "id-aes128-wrap", "rfc5911"),
+    (&rfc5911::ID_AES_128_GCM, "id-aes128-GCM", "rfc5911"),
+    (&rfc5911::ID_AES_128_CCM, "id-aes128-CCM", "rfc5911"),
+    (&rfc5912::ID_DSA, "id-dsa", "rfc5912"),
+    (&rfc5912::DSA_WITH_SHA_1, "dsa-with-sha1", "rfc5912"),
+    (&rfc5912::ID_EC_PUBLIC_KEY, "id-ecPublicKey", "rfc5912"),
+    (&rfc5912::SECP_256_R_1, "secp256r1", "rfc5912"),
+    (&rfc5912::ECDSA_WITH_SHA_224, "ecdsa-with-SHA224", "rfc5912"),
+    (&rfc5912::ECDSA_WITH_SHA_256, "ecdsa-with-SHA256", "rfc5912"),
+    (&rfc5912::ECDSA_WITH_SHA_384, "ecdsa-with-SHA384", "rfc5912"),
+    (&rfc5912::ECDSA_WITH_SHA_512, "ecdsa-with-SHA512", "rfc5912"),
+    (&rfc5912::DHPUBLICNUMBER, "dhpublicnumber", "rfc5912"),
+    (
+        &rfc5912::ID_PASSWORD_BASED_MAC,
+        "id-PasswordBasedMac",
+        "rfc5912",
+    ),
+    (&rfc5912::ID_DH_BASED_MAC, "id-DHBasedMac", "rfc5912"),
+    (&rfc5912::PKCS_1, "pkcs-1", "rfc5912"),
+    (&rfc5912::RSA_ENCRYPTION, "rsaEncryption", "rfc5912"),
+    (&rfc5912::ID_RSASSA_PSS, "id-RSASSA-PSS", "rfc5912"),
     (
         &rfc5912::SHA_256_WITH_RSA_ENCRYPTION,
         "sha256WithRSAEncryption",
+        "rfc5912",
     ),
     (
         &rfc5912::SHA_384_WITH_RSA_ENCRYPTION,
         "sha384WithRSAEncryption",
+        "rfc5912",
     ),
     (
         &rfc5912::SHA_512_WITH_RSA_ENCRYPTION,
         "sha512WithRSAEncryption",
+        "rfc5912",
     ),
     (
         &rfc5912::SHA_224_WITH_RSA_ENCRYPTION,
         "sha224WithRSAEncryption",
+        "rfc5912",
+    ),
+    (
+        &rfc5912::MD_2_WITH_RSA_ENCRYPTION,
+        "md2WithRSAEncryption",
+        "rfc5912",
+    ),
+    (
+        &rfc5912::MD_5_WITH_RSA_ENCRYPTION,
+        "md5WithRSAEncryption",
+        "rfc5912",
     ),
-    (&rfc5912::MD_2_WITH_RSA_ENCRYPTION, "md2WithRSAEncryption"),
-    (&rfc5912::MD_5_WITH_RSA_ENCRYPTION, "md5WithRSAEncryption"),
-    (&rfc5912::SHA_1_WITH_RSA_ENCRYPTION, "sha1WithRSAEncryption"),
-    (&rfc5912::ID_RSAES_OAEP, "id-RSAES-OAEP"),
-    (&rfc5912::ID_MGF_1, "id-mgf1"),
-    (&rfc5912::ID_P_SPECIFIED, "id-pSpecified"),
-    (&rfc5912::PKCS_9, "pkcs-9"),
-    (&rfc5912::ID_EXTENSION_REQ, "id-ExtensionReq"),
-    (&rfc5912::ID_SMIME, "id-smime"),
-    (&rfc5912::ID_CT, "id-ct"),
+    (
+        &rfc5912::SHA_1_WITH_RSA_ENCRYPTION,
+        "sha1WithRSAEncryption",
+        "rfc5912",
+    ),
+    (&rfc5912::ID_RSAES_OAEP, "id-RSAES-OAEP", "rfc5912"),
+    (&rfc5912::ID_MGF_1, "id-mgf1", "rfc5912"),
+    (&rfc5912::ID_P_SPECIFIED, "id-pSpecified", "rfc5912"),
+    (&rfc5912::PKCS_9, "pkcs-9", "rfc5912"),
+    (&rfc5912::ID_EXTENSION_REQ, "id-ExtensionReq", "rfc5912"),
+    (&rfc5912::ID_SMIME, "id-smime", "rfc5912"),
+    (&rfc5912::ID_CT, "id-ct", "rfc5912"),
     (
         &rfc5912::ID_CT_SCVP_CERT_VAL_REQUEST,
         "id-ct-scvp-certValRequest",
+        "rfc5912",
     ),
     (
         &rfc5912::ID_CT_SCVP_CERT_VAL_RESPONSE,
         "id-ct-scvp-certValResponse",
+        "rfc5912",
     ),
     (
         &rfc5912::ID_CT_SCVP_VAL_POL_REQUEST,
         "id-ct-scvp-valPolRequest",
+        "rfc5912",
     ),
     (
         &rfc5912::ID_CT_SCVP_VAL_POL_RESPONSE,
         "id-ct-scvp-valPolResponse",
-    ),
-    (&rfc5912::ID_CT_ENC_KEY_WITH_ID, "id-ct-encKeyWithID"),
-    (&rfc5912::ID_AA, "id-aa"),
-    (&rfc5912::ID_AA_CMC_UNSIGNED_DATA, "id-aa-cmc-unsignedData"),
-    (&rfc5912::ID_MD_2, "id-md2"),
-    (&rfc5912::ID_MD_5, "id-md5"),
-    (&rfc5912::SECT_163_K_1, "sect163k1"),
-    (&rfc5912::SECT_163_R_2, "sect163r2"),
-    (&rfc5912::SECT_283_K_1, "sect283k1"),
-    (&rfc5912::SECT_283_R_1, "sect283r1"),
-    (&rfc5912::SECT_233_K_1, "sect233k1"),
-    (&rfc5912::SECT_233_R_1, "sect233r1"),
-    (&rfc5912::SECP_224_R_1, "secp224r1"),
-    (&rfc5912::SECP_384_R_1, "secp384r1"),
-    (&rfc5912::SECP_521_R_1, "secp521r1"),
-    (&rfc5912::SECT_409_K_1, "sect409k1"),
-    (&rfc5912::SECT_409_R_1, "sect409r1"),
-    (&rfc5912::SECT_571_K_1, "sect571k1"),
-    (&rfc5912::SECT_571_R_1, "sect571r1"),
-    (&rfc5912::ID_EC_DH, "id-ecDH"),
-    (&rfc5912::ID_EC_MQV, "id-ecMQV"),
-    (&rfc5912::ID_SHA_1, "id-sha1"),
-    (&rfc5912::ID_PKIX, "id-pkix"),
-    (&rfc5912::ID_PE, "id-pe"),
+        "rfc5912",
+    ),
+    (
+        &rfc5912::ID_CT_ENC_KEY_WITH_ID,
+        "id-ct-encKeyWithID",
+        "rfc5912",
+    ),
+    (&rfc5912::ID_AA, "id-aa", "rfc5912"),
+    (
+        &rfc5912::ID_AA_CMC_UNSIGNED_DATA,
+        "id-aa-cmc-unsignedData",
+        "rfc5912",
+    ),
+    (&rfc5912::ID_MD_2, "id-md2", "rfc5912"),
+    (&rfc5912::ID_MD_5, "id-md5", "rfc5912"),
+    (&rfc5912::SECT_163_K_1, "sect163k1", "rfc5912"),
+    (&rfc5912::SECT_163_R_2, "sect163r2", "rfc5912"),
+    (&rfc5912::SECT_283_K_1, "sect283k1", "rfc5912"),
+    (&rfc5912::SECT_283_R_1, "sect283r1", "rfc5912"),
+    (&rfc5912::SECT_233_K_1, "sect233k1", "rfc5912"),
+    (&rfc5912::SECT_233_R_1, "sect233r1", "rfc5912"),
+    (&rfc5912::SECP_224_R_1, "secp224r1", "rfc5912"),
+    (&rfc5912::SECP_384_R_1, "secp384r1", "rfc5912"),
+    (&rfc5912::SECP_521_R_1, "secp521r1", "rfc5912"),
+    (&rfc5912::SECT_409_K_1, "sect409k1", "rfc5912"),
+    (&rfc5912::SECT_409_R_1, "sect409r1", "rfc5912"),
+    (&rfc5912::SECT_571_K_1, "sect571k1", "rfc5912"),
+    (&rfc5912::SECT_571_R_1, "sect571r1", "rfc5912"),
+    (&rfc5912::ID_EC_DH, "id-ecDH", "rfc5912"),
+    (&rfc5912::ID_EC_MQV, "id-ecMQV", "rfc5912"),
+    (&rfc5912::ID_SHA_1, "id-sha1", "rfc5912"),
+    (&rfc5912::ID_PKIX, "id-pkix", "rfc5912"),
+    (&rfc5912::ID_PE, "id-pe", "rfc5912"),
     (
         &rfc5912::ID_PE_AUTHORITY_INFO_ACCESS,
         "id-pe-authorityInfoAccess",
+        "rfc5912",
     ),
-    (&rfc5912::ID_PE_AC_PROXYING, "id-pe-ac-proxying"),
+    (&rfc5912::ID_PE_AC_PROXYING, "id-pe-ac-proxying", "rfc5912"),
     (
         &rfc5912::ID_PE_SUBJECT_INFO_ACCESS,
         "id-pe-subjectInfoAccess",
+        "rfc5912",
     ),
-    (&rfc5912::ID_PE_AC_AUDIT_IDENTITY, "id-pe-ac-auditIdentity"),
-    (&rfc5912::ID_PE_AA_CONTROLS, "id-pe-aaControls"),
-    (&rfc5912::ID_ACA, "id-aca"),
+    (
+        &rfc5912::ID_PE_AC_AUDIT_IDENTITY,
+        "id-pe-ac-auditIdentity",
+        "rfc5912",
+    ),
+    (&rfc5912::ID_PE_AA_CONTROLS, "id-pe-aaControls", "rfc5912"),
+    (&rfc5912::ID_ACA, "id-aca", "rfc5912"),
     (
         &rfc5912::ID_ACA_AUTHENTICATION_INFO,
         "id-aca-authenticationInfo",
+        "rfc5912",
+    ),
+    (
+        &rfc5912::ID_ACA_ACCESS_IDENTITY,
+        "id-aca-accessIdentity",
+        "rfc5912",
     ),
-    (&rfc5912::ID_ACA_ACCESS_IDENTITY, "id-aca-accessIdentity"),
     (
         &rfc5912::ID_ACA_CHARGING_IDENTITY,
         "id-aca-chargingIdentity",
+        "rfc5912",
+    ),
+    (&rfc5912::ID_ACA_GROUP, "id-aca-group", "rfc5912"),
+    (&rfc5912::ID_ACA_ENC_ATTRS, "id-aca-encAttrs", "rfc5912"),
+    (&rfc5912::ID_CCT, "id-cct", "rfc5912"),
+    (&rfc5912::ID_CCT_PKI_DATA, "id-cct-PKIData", "rfc5912"),
+    (
+        &rfc5912::ID_CCT_PKI_RESPONSE,
+        "id-cct-PKIResponse",
+        "rfc5912",
+    ),
+    (&rfc5912::ID_STC, "id-stc", "rfc5912"),
+    (
+        &rfc5912::ID_STC_BUILD_PKC_PATH,
+        "id-stc-build-pkc-path",
+        "rfc5912",
     ),
-    (&rfc5912::ID_ACA_GROUP, "id-aca-group"),
-    (&rfc5912::ID_ACA_ENC_ATTRS, "id-aca-encAttrs"),
-    (&rfc5912::ID_CCT, "id-cct"),
-    (&rfc5912::ID_CCT_PKI_DATA, "id-cct-PKIData"),
-    (&rfc5912::ID_CCT_PKI_RESPONSE, "id-cct-PKIResponse"),
-    (&rfc5912::ID_STC, "id-stc"),
-    (&rfc5912::ID_STC_BUILD_PKC_PATH, "id-stc-build-pkc-path"),
     (
         &rfc5912::ID_STC_BUILD_VALID_PKC_PATH,
         "id-stc-build-valid-pkc-path",
+        "rfc5912",
     ),
     (
         &rfc5912::ID_STC_BUILD_STATUS_CHECKED_PKC_PATH,
         "id-stc-build-status-checked-pkc-path",
+        "rfc5912",
+    ),
+    (
+        &rfc5912::ID_STC_BUILD_AA_PATH,
+        "id-stc-build-aa-path",
+        "rfc5912",
     ),
-    (&rfc5912::ID_STC_BUILD_AA_PATH, "id-stc-build-aa-path"),
     (
         &rfc5912::ID_STC_BUILD_VALID_AA_PATH,
         "id-stc-build-valid-aa-path",
+        "rfc5912",
     ),
     (
         &rfc5912::ID_STC_BUILD_STATUS_CHECKED_AA_PATH,
         "id-stc-build-status-checked-aa-path",
+        "rfc5912",
     ),
     (
         &rfc5912::ID_STC_STATUS_CHECK_AC_AND_BUILD_STATUS_CHECKED_AA_PATH,
         "id-stc-status-check-ac-and-build-status-checked-aa-path",
+        "rfc5912",
     ),
-    (&rfc5912::ID_SWB, "id-swb"),
+    (&rfc5912::ID_SWB, "id-swb", "rfc5912"),
     (
         &rfc5912::ID_SWB_PKC_BEST_CERT_PATH,
         "id-swb-pkc-best-cert-path",
+        "rfc5912",
     ),
-    (&rfc5912::ID_SWB_PKC_CERT, "id-swb-pkc-cert"),
-    (&rfc5912::ID_SWB_AC_CERT, "id-swb-ac-cert"),
+    (&rfc5912::ID_SWB_PKC_CERT, "id-swb-pkc-cert", "rfc5912"),
+    (&rfc5912::ID_SWB_AC_CERT, "id-swb-ac-cert", "rfc5912"),
     (
         &rfc5912::ID_SWB_PKC_ALL_CERT_PATHS,
         "id-swb-pkc-all-cert-paths",
+        "rfc5912",
     ),
     (
         &rfc5912::ID_SWB_PKC_EE_REVOCATION_INFO,
         "id-swb-pkc-ee-revocation-info",
+        "rfc5912",
     ),
     (
         &rfc5912::ID_SWB_PKC_C_AS_REVOCATION_INFO,
         "id-swb-pkc-CAs-revocation-info",
+        "rfc5912",
     ),
     (
         &rfc5912::ID_SWB_PKC_REVOCATION_INFO,
         "id-swb-pkc-revocation-info",
+        "rfc5912",
     ),
     (
         &rfc5912::ID_SWB_PKC_PUBLIC_KEY_INFO,
         "id-swb-pkc-public-key-info",
+        "rfc5912",
+    ),
+    (
+        &rfc5912::ID_SWB_AA_CERT_PATH,
+        "id-swb-aa-cert-path",
+        "rfc5912",
     ),
-    (&rfc5912::ID_SWB_AA_CERT_PATH, "id-swb-aa-cert-path"),
     (
         &rfc5912::ID_SWB_AA_REVOCATION_INFO,
         "id-swb-aa-revocation-info",
+        "rfc5912",
     ),
     (
         &rfc5912::ID_SWB_AC_REVOCATION_INFO,
         "id-swb-ac-revocation-info",
+        "rfc5912",
     ),
     (
         &rfc5912::ID_SWB_RELAYED_RESPONSES,
         "id-swb-relayed-responses",
+        "rfc5912",
     ),
-    (&rfc5912::ID_SVP, "id-svp"),
+    (&rfc5912::ID_SVP, "id-svp", "rfc5912"),
     (
         &rfc5912::ID_SVP_DEFAULT_VAL_POLICY,
         "id-svp-defaultValPolicy",
+        "rfc5912",
+    ),
+    (
+        &rfc5912::ID_SVP_NAME_VAL_ALG,
+        "id-svp-nameValAlg",
+        "rfc5912",
+    ),
+    (
+        &rfc5912::ID_SVP_BASIC_VAL_ALG,
+        "id-svp-basicValAlg",
+        "rfc5912",
+    ),
+    (&rfc5912::NAME_COMP_ALG_SET, "NameCompAlgSet", "rfc5912"),
+    (&rfc5912::ID_NVA_DN_COMP_ALG, "id-nva-dnCompAlg", "rfc5912"),
+    (&rfc5912::ID_QT, "id-qt", "rfc5912"),
+    (&rfc5912::ID_QT_CPS, "id-qt-cps", "rfc5912"),
+    (&rfc5912::ID_QT_UNOTICE, "id-qt-unotice", "rfc5912"),
+    (&rfc5912::ID_KP, "id-kp", "rfc5912"),
+    (&rfc5912::ID_KP_SERVER_AUTH, "id-kp-serverAuth", "rfc5912"),
+    (&rfc5912::ID_KP_SCVP_SERVER, "id-kp-scvpServer", "rfc5912"),
+    (&rfc5912::ID_KP_SCVP_CLIENT, "id-kp-scvpClient", "rfc5912"),
+    (&rfc5912::ID_KP_CLIENT_AUTH, "id-kp-clientAuth", "rfc5912"),
+    (&rfc5912::ID_KP_CODE_SIGNING, "id-kp-codeSigning", "rfc5912"),
+    (
+        &rfc5912::ID_KP_EMAIL_PROTECTION,
+        "id-kp-emailProtection",
+        "rfc5912",
+    ),
+    (
+        &rfc5912::ID_KP_TIME_STAMPING,
+        "id-kp-timeStamping",
+        "rfc5912",
+    ),
+    (&rfc5912::ID_KP_OCSP_SIGNING, "id-kp-OCSPSigning", "rfc5912"),
+    (&rfc5912::ID_IT, "id-it", "rfc5912"),
+    (
+        &rfc5912::ID_IT_CA_PROT_ENC_CERT,
+        "id-it-caProtEncCert",
+        "rfc5912",
+    ),
+    (
+        &rfc5912::ID_IT_KEY_PAIR_PARAM_REQ,
+        "id-it-keyPairParamReq",
+        "rfc5912",
+    ),
+    (
+        &rfc5912::ID_IT_KEY_PAIR_PARAM_REP,
+        "id-it-keyPairParamRep",
+        "rfc5912",
+    ),
+    (
+        &rfc5912::ID_IT_REV_PASSPHRASE,
+        "id-it-revPassphrase",
+        "rfc5912",
+    ),
+    (
+        &rfc5912::ID_IT_IMPLICIT_CONFIRM,
+        "id-it-implicitConfirm",
+        "rfc5912",
+    ),
+    (
+        &rfc5912::ID_IT_CONFIRM_WAIT_TIME,
+        "id-it-confirmWaitTime",
+        "rfc5912",
+    ),
+    (
+        &rfc5912::ID_IT_ORIG_PKI_MESSAGE,
+        "id-it-origPKIMessage",
+        "rfc5912",
+    ),
+    (
+        &rfc5912::ID_IT_SUPP_LANG_TAGS,
+        "id-it-suppLangTags",
+        "rfc5912",
     ),
-    (&rfc5912::ID_SVP_NAME_VAL_ALG, "id-svp-nameValAlg"),
-    (&rfc5912::ID_SVP_BASIC_VAL_ALG, "id-svp-basicValAlg"),
-    (&rfc5912::NAME_COMP_ALG_SET, "NameCompAlgSet"),
-    (&rfc5912::ID_NVA_DN_COMP_ALG, "id-nva-dnCompAlg"),
-    (&rfc5912::ID_QT, "id-qt"),
-    (&rfc5912::ID_QT_CPS, "id-qt-cps"),
-    (&rfc5912::ID_QT_UNOTICE, "id-qt-unotice"),
-    (&rfc5912::ID_KP, "id-kp"),
-    (&rfc5912::ID_KP_SERVER_AUTH, "id-kp-serverAuth"),
-    (&rfc5912::ID_KP_SCVP_SERVER, "id-kp-scvpServer"),
-    (&rfc5912::ID_KP_SCVP_CLIENT, "id-kp-scvpClient"),
-    (&rfc5912::ID_KP_CLIENT_AUTH, "id-kp-clientAuth"),
-    (&rfc5912::ID_KP_CODE_SIGNING, "id-kp-codeSigning"),
-    (&rfc5912::ID_KP_EMAIL_PROTECTION, "id-kp-emailProtection"),
-    (&rfc5912::ID_KP_TIME_STAMPING, "id-kp-timeStamping"),
-    (&rfc5912::ID_KP_OCSP_SIGNING, "id-kp-OCSPSigning"),
-    (&rfc5912::ID_IT, "id-it"),
-    (&rfc5912::ID_IT_CA_PROT_ENC_CERT, "id-it-caProtEncCert"),
-    (&rfc5912::ID_IT_KEY_PAIR_PARAM_REQ, "id-it-keyPairParamReq"),
-    (&rfc5912::ID_IT_KEY_PAIR_PARAM_REP, "id-it-keyPairParamRep"),
-    (&rfc5912::ID_IT_REV_PASSPHRASE, "id-it-revPassphrase"),
-    (&rfc5912::ID_IT_IMPLICIT_CONFIRM, "id-it-implicitConfirm"),
-    (&rfc5912::ID_IT_CONFIRM_WAIT_TIME, "id-it-confirmWaitTime"),
-    (&rfc5912::ID_IT_ORIG_PKI_MESSAGE, "id-it-origPKIMessage"),
-    (&rfc5912::ID_IT_SUPP_LANG_TAGS, "id-it-suppLangTags"),
     (
         &rfc5912::ID_IT_SIGN_KEY_PAIR_TYPES,
         "id-it-signKeyPairTypes",
+        "rfc5912",
+    ),
+    (
+        &rfc5912::ID_IT_ENC_KEY_PAIR_TYPES,
+        "id-it-encKeyPairTypes",
+        "rfc5912",
+    ),
+    (
+        &rfc5912::ID_IT_PREFERRED_SYMM_ALG,
+        "id-it-preferredSymmAlg",
+        "rfc5912",
+    ),
+    (
+        &rfc5912::ID_IT_CA_KEY_UPDATE_INFO,
+        "id-it-caKeyUpdateInfo",
+        "rfc5912",
+    ),
+    (&rfc5912::ID_IT_CURRENT_CRL, "id-it-currentCRL", "rfc5912"),
+    (
+        &rfc5912::ID_IT_UNSUPPORTED_OI_DS,
+        "id-it-unsupportedOIDs",
+        "rfc5912",
+    ),
+    (&rfc5912::ID_AD, "id-ad", "rfc5912"),
+    (&rfc5912::ID_AD_OCSP, "id-ad-ocsp", "rfc5912"),
+    (&rfc5912::ID_AD_CA_ISSUERS, "id-ad-caIssuers", "rfc5912"),
+    (
+        &rfc5912::ID_AD_TIME_STAMPING,
+        "id-ad-timeStamping",
+        "rfc5912",
+    ),
+    (
+        &rfc5912::ID_AD_CA_REPOSITORY,
+        "id-ad-caRepository",
+        "rfc5912",
+    ),
+    (&rfc5912::ID_PKIP, "id-pkip", "rfc5912"),
+    (&rfc5912::ID_REG_CTRL, "id-regCtrl", "rfc5912"),
+    (
+        &rfc5912::ID_REG_CTRL_REG_TOKEN,
+        "id-regCtrl-regToken",
+        "rfc5912",
     ),
-    (&rfc5912::ID_IT_ENC_KEY_PAIR_TYPES, "id-it-encKeyPairTypes"),
-    (&rfc5912::ID_IT_PREFERRED_SYMM_ALG, "id-it-preferredSymmAlg"),
-    (&rfc5912::ID_IT_CA_KEY_UPDATE_INFO, "id-it-caKeyUpdateInfo"),
-    (&rfc5912::ID_IT_CURRENT_CRL, "id-it-currentCRL"),
-    (&rfc5912::ID_IT_UNSUPPORTED_OI_DS, "id-it-unsupportedOIDs"),
-    (&rfc5912::ID_AD, "id-ad"),
-    (&rfc5912::ID_AD_OCSP, "id-ad-ocsp"),
-    (&rfc5912::ID_AD_CA_ISSUERS, "id-ad-caIssuers"),
-    (&rfc5912::ID_AD_TIME_STAMPING, "id-ad-timeStamping"),
-    (&rfc5912::ID_AD_CA_REPOSITORY, "id-ad-caRepository"),
-    (&rfc5912::ID_PKIP, "id-pkip"),
-    (&rfc5912::ID_REG_CTRL, "id-regCtrl"),
-    (&rfc5912::ID_REG_CTRL_REG_TOKEN, "id-regCtrl-regToken"),
     (
         &rfc5912::ID_REG_CTRL_AUTHENTICATOR,
         "id-regCtrl-authenticator",
+        "rfc5912",
     ),
     (
         &rfc5912::ID_REG_CTRL_PKI_PUBLICATION_INFO,
         "id-regCtrl-pkiPublicationInfo",
+        "rfc5912",
     ),
     (
         &rfc5912::ID_REG_CTRL_PKI_ARCHIVE_OPTIONS,
         "id-regCtrl-pkiArchiveOptions",
+        "rfc5912",
+    ),
+    (
+        &rfc5912::ID_REG_CTRL_OLD_CERT_ID,
+        "id-regCtrl-oldCertID",
+        "rfc5912",
     ),
-    (&rfc5912::ID_REG_CTRL_OLD_CERT_ID, "id-regCtrl-oldCertID"),
     (
         &rfc5912::ID_REG_CTRL_PROTOCOL_ENCR_KEY,
         "id-regCtrl-protocolEncrKey",
+        "rfc5912",
+    ),
+    (&rfc5912::ID_REG_INFO, "id-regInfo", "rfc5912"),
+    (
+        &rfc5912::ID_REG_INFO_UTF_8_PAIRS,
+        "id-regInfo-utf8Pairs",
+        "rfc5912",
+    ),
+    (
+        &rfc5912::ID_REG_INFO_CERT_REQ,
+        "id-regInfo-certReq",
+        "rfc5912",
+    ),
+    (
+        &rfc5912::ID_ALG_NO_SIGNATURE,
+        "id-alg-noSignature",
+        "rfc5912",
+    ),
+    (&rfc5912::ID_CMC, "id-cmc", "rfc5912"),
+    (&rfc5912::ID_CMC_STATUS_INFO, "id-cmc-statusInfo", "rfc5912"),
+    (
+        &rfc5912::ID_CMC_DECRYPTED_POP,
+        "id-cmc-decryptedPOP",
+        "rfc5912",
+    ),
+    (
+        &rfc5912::ID_CMC_LRA_POP_WITNESS,
+        "id-cmc-lraPOPWitness",
+        "rfc5912",
+    ),
+    (&rfc5912::ID_CMC_GET_CERT, "id-cmc-getCert", "rfc5912"),
+    (&rfc5912::ID_CMC_GET_CRL, "id-cmc-getCRL", "rfc5912"),
+    (
+        &rfc5912::ID_CMC_REVOKE_REQUEST,
+        "id-cmc-revokeRequest",
+        "rfc5912",
+    ),
+    (&rfc5912::ID_CMC_REG_INFO, "id-cmc-regInfo", "rfc5912"),
+    (
+        &rfc5912::ID_CMC_RESPONSE_INFO,
+        "id-cmc-responseInfo",
+        "rfc5912",
+    ),
+    (
+        &rfc5912::ID_CMC_IDENTIFICATION,
+        "id-cmc-identification",
+        "rfc5912",
+    ),
+    (
+        &rfc5912::ID_CMC_QUERY_PENDING,
+        "id-cmc-queryPending",
+        "rfc5912",
+    ),
+    (
+        &rfc5912::ID_CMC_POP_LINK_RANDOM,
+        "id-cmc-popLinkRandom",
+        "rfc5912",
+    ),
+    (
+        &rfc5912::ID_CMC_POP_LINK_WITNESS,
+        "id-cmc-popLinkWitness",
+        "rfc5912",
     ),
-    (&rfc5912::ID_REG_INFO, "id-regInfo"),
-    (&rfc5912::ID_REG_INFO_UTF_8_PAIRS, "id-regInfo-utf8Pairs"),
-    (&rfc5912::ID_REG_INFO_CERT_REQ, "id-regInfo-certReq"),
-    (&rfc5912::ID_ALG_NO_SIGNATURE, "id-alg-noSignature"),
-    (&rfc5912::ID_CMC, "id-cmc"),
-    (&rfc5912::ID_CMC_STATUS_INFO, "id-cmc-statusInfo"),
-    (&rfc5912::ID_CMC_DECRYPTED_POP, "id-cmc-decryptedPOP"),
-    (&rfc5912::ID_CMC_LRA_POP_WITNESS, "id-cmc-lraPOPWitness"),
-    (&rfc5912::ID_CMC_GET_CERT, "id-cmc-getCert"),
-    (&rfc5912::ID_CMC_GET_CRL, "id-cmc-getCRL"),
-    (&rfc5912::ID_CMC_REVOKE_REQUEST, "id-cmc-revokeRequest"),
-    (&rfc5912::ID_CMC_REG_INFO, "id-cmc-regInfo"),
-    (&rfc5912::ID_CMC_RESPONSE_INFO, "id-cmc-responseInfo"),
-    (&rfc5912::ID_CMC_IDENTIFICATION, "id-cmc-identification"),
-    (&rfc5912::ID_CMC_QUERY_PENDING, "id-cmc-queryPending"),
-    (&rfc5912::ID_CMC_POP_LINK_RANDOM, "id-cmc-popLinkRandom"),
-    (&rfc5912::ID_CMC_POP_LINK_WITNESS, "id-cmc-popLinkWitness"),
     (
         &rfc5912::ID_CMC_CONFIRM_CERT_ACCEPTANCE,
         "id-cmc-confirmCertAcceptance",
+        "rfc5912",
+    ),
+    (
+        &rfc5912::ID_CMC_STATUS_INFO_V_2,
+        "id-cmc-statusInfoV2",
+        "rfc5912",
+    ),
+    (
+        &rfc5912::ID_CMC_TRUSTED_ANCHORS,
+        "id-cmc-trustedAnchors",
+        "rfc5912",
+    ),
+    (&rfc5912::ID_CMC_AUTH_DATA, "id-cmc-authData", "rfc5912"),
+    (
+        &rfc5912::ID_CMC_BATCH_REQUESTS,
+        "id-cmc-batchRequests",
+        "rfc5912",
+    ),
+    (
+        &rfc5912::ID_CMC_BATCH_RESPONSES,
+        "id-cmc-batchResponses",
+        "rfc5912",
+    ),
+    (
+        &rfc5912::ID_CMC_IDENTITY_PROOF,
+        "id-cmc-identityProof",
+        "rfc5912",
+    ),
+    (
+        &rfc5912::ID_CMC_PUBLISH_CERT,
+        "id-cmc-publishCert",
+        "rfc5912",
+    ),
+    (
+        &rfc5912::ID_CMC_MOD_CERT_TEMPLATE,
+        "id-cmc-modCertTemplate",
+        "rfc5912",
     ),
-    (&rfc5912::ID_CMC_STATUS_INFO_V_2, "id-cmc-statusInfoV2"),
-    (&rfc5912::ID_CMC_TRUSTED_ANCHORS, "id-cmc-trustedAnchors"),
-    (&rfc5912::ID_CMC_AUTH_DATA, "id-cmc-authData"),
-    (&rfc5912::ID_CMC_BATCH_REQUESTS, "id-cmc-batchRequests"),
-    (&rfc5912::ID_CMC_BATCH_RESPONSES, "id-cmc-batchResponses"),
-    (&rfc5912::ID_CMC_IDENTITY_PROOF, "id-cmc-identityProof"),
-    (&rfc5912::ID_CMC_PUBLISH_CERT, "id-cmc-publishCert"),
-    (&rfc5912::ID_CMC_MOD_CERT_TEMPLATE, "id-cmc-modCertTemplate"),
     (
         &rfc5912::ID_CMC_CONTROL_PROCESSED,
         "id-cmc-controlProcessed",
+        "rfc5912",
     ),
     (
         &rfc5912::ID_CMC_IDENTITY_PROOF_V_2,
         "id-cmc-identityProofV2",
+        "rfc5912",
     ),
     (
         &rfc5912::ID_CMC_POP_LINK_WITNESS_V_2,
         "id-cmc-popLinkWitnessV2",
+        "rfc5912",
+    ),
+    (&rfc5912::ID_CMC_DATA_RETURN, "id-cmc-dataReturn", "rfc5912"),
+    (
+        &rfc5912::ID_CMC_TRANSACTION_ID,
+        "id-cmc-transactionId",
+        "rfc5912",
+    ),
+    (
+        &rfc5912::ID_CMC_SENDER_NONCE,
+        "id-cmc-senderNonce",
+        "rfc5912",
+    ),
+    (
+        &rfc5912::ID_CMC_RECIPIENT_NONCE,
+        "id-cmc-recipientNonce",
+        "rfc5912",
+    ),
+    (
+        &rfc5912::ID_CMC_ADD_EXTENSIONS,
+        "id-cmc-addExtensions",
+        "rfc5912",
+    ),
+    (
+        &rfc5912::ID_CMC_ENCRYPTED_POP,
+        "id-cmc-encryptedPOP",
+        "rfc5912",
     ),
-    (&rfc5912::ID_CMC_DATA_RETURN, "id-cmc-dataReturn"),
-    (&rfc5912::ID_CMC_TRANSACTION_ID, "id-cmc-transactionId"),
-    (&rfc5912::ID_CMC_SENDER_NONCE, "id-cmc-senderNonce"),
-    (&rfc5912::ID_CMC_RECIPIENT_NONCE, "id-cmc-recipientNonce"),
-    (&rfc5912::ID_CMC_ADD_EXTENSIONS, "id-cmc-addExtensions"),
-    (&rfc5912::ID_CMC_ENCRYPTED_POP, "id-cmc-encryptedPOP"),
     (
         &rfc5912::ID_KEY_EXCHANGE_ALGORITHM,
         "id-keyExchangeAlgorithm",
+        "rfc5912",
+    ),
+    (&rfc5912::ID_SHA_256, "id-sha256", "rfc5912"),
+    (&rfc5912::ID_SHA_384, "id-sha384", "rfc5912"),
+    (&rfc5912::ID_SHA_512, "id-sha512", "rfc5912"),
+    (&rfc5912::ID_SHA_224, "id-sha224", "rfc5912"),
+    (&rfc5912::DSA_WITH_SHA_224, "dsa-with-sha224", "rfc5912"),
+    (&rfc5912::DSA_WITH_SHA_256, "dsa-with-sha256", "rfc5912"),
+    (&rfc5912::HOLD_INSTRUCTION, "holdInstruction", "rfc5912"),
+    (
+        &rfc5912::ID_HOLDINSTRUCTION_NONE,
+        "id-holdinstruction-none",
+        "rfc5912",
     ),
-    (&rfc5912::ID_SHA_256, "id-sha256"),
-    (&rfc5912::ID_SHA_384, "id-sha384"),
-    (&rfc5912::ID_SHA_512, "id-sha512"),
-    (&rfc5912::ID_SHA_224, "id-sha224"),
-    (&rfc5912::DSA_WITH_SHA_224, "dsa-with-sha224"),
-    (&rfc5912::DSA_WITH_SHA_256, "dsa-with-sha256"),
-    (&rfc5912::HOLD_INSTRUCTION, "holdInstruction"),
-    (&rfc5912::ID_HOLDINSTRUCTION_NONE, "id-holdinstruction-none"),
     (
         &rfc5912::ID_HOLDINSTRUCTION_CALLISSUER,
         "id-holdinstruction-callissuer",
+        "rfc5912",
     ),
     (
         &rfc5912::ID_HOLDINSTRUCTION_REJECT,
         "id-holdinstruction-reject",
+        "rfc5912",
     ),
-    (&rfc5912::ID_CE, "id-ce"),
+    (&rfc5912::ID_CE, "id-ce", "rfc5912"),
     (
         &rfc5912::ID_CE_SUBJECT_KEY_IDENTIFIER,
         "id-ce-subjectKeyIdentifier",
+        "rfc5912",
     ),
-    (&rfc5912::ID_CE_KEY_USAGE, "id-ce-keyUsage"),
+    (&rfc5912::ID_CE_KEY_USAGE, "id-ce-keyUsage", "rfc5912"),
     (
         &rfc5912::ID_CE_PRIVATE_KEY_USAGE_PERIOD,
         "id-ce-privateKeyUsagePeriod",
+        "rfc5912",
+    ),
+    (
+        &rfc5912::ID_CE_SUBJECT_ALT_NAME,
+        "id-ce-subjectAltName",
+        "rfc5912",
+    ),
+    (
+        &rfc5912::ID_CE_ISSUER_ALT_NAME,
+        "id-ce-issuerAltName",
+        "rfc5912",
     ),
-    (&rfc5912::ID_CE_SUBJECT_ALT_NAME, "id-ce-subjectAltName"),
-    (&rfc5912::ID_CE_ISSUER_ALT_NAME, "id-ce-issuerAltName"),
-    (&rfc5912::ID_CE_BASIC_CONSTRAINTS, "id-ce-basicConstraints"),
-    (&rfc5912::ID_CE_CRL_NUMBER, "id-ce-cRLNumber"),
-    (&rfc5912::ID_CE_CRL_REASONS, "id-ce-cRLReasons"),
+    (
+        &rfc5912::ID_CE_BASIC_CONSTRAINTS,
+        "id-ce-basicConstraints",
+        "rfc5912",
+    ),
+    (&rfc5912::ID_CE_CRL_NUMBER, "id-ce-cRLNumber", "rfc5912"),
+    (&rfc5912::ID_CE_CRL_REASONS, "id-ce-cRLReasons", "rfc5912"),
     (
         &rfc5912::ID_CE_HOLD_INSTRUCTION_CODE,
         "id-ce-holdInstructionCode",
+        "rfc5912",
+    ),
+    (
+        &rfc5912::ID_CE_INVALIDITY_DATE,
+        "id-ce-invalidityDate",
+        "rfc5912",
     ),
-    (&rfc5912::ID_CE_INVALIDITY_DATE, "id-ce-invalidityDate"),
     (
         &rfc5912::ID_CE_DELTA_CRL_INDICATOR,
         "id-ce-deltaCRLIndicator",
+        "rfc5912",
     ),
     (
         &rfc5912::ID_CE_ISSUING_DISTRIBUTION_POINT,
         "id-ce-issuingDistributionPoint",
+        "rfc5912",
     ),
     (
         &rfc5912::ID_CE_CERTIFICATE_ISSUER,
         "id-ce-certificateIssuer",
+        "rfc5912",
+    ),
+    (
+        &rfc5912::ID_CE_NAME_CONSTRAINTS,
+        "id-ce-nameConstraints",
+        "rfc5912",
     ),
-    (&rfc5912::ID_CE_NAME_CONSTRAINTS, "id-ce-nameConstraints"),
     (
         &rfc5912::ID_CE_CRL_DISTRIBUTION_POINTS,
         "id-ce-cRLDistributionPoints",
+        "rfc5912",
     ),
     (
         &rfc5912::ID_CE_CERTIFICATE_POLICIES,
         "id-ce-certificatePolicies",
+        "rfc5912",
+    ),
+    (
+        &rfc5912::ID_CE_POLICY_MAPPINGS,
+        "id-ce-policyMappings",
+        "rfc5912",
     ),
-    (&rfc5912::ID_CE_POLICY_MAPPINGS, "id-ce-policyMappings"),
     (
         &rfc5912::ID_CE_AUTHORITY_KEY_IDENTIFIER,
         "id-ce-authorityKeyIdentifier",
+        "rfc5912",
     ),
     (
         &rfc5912::ID_CE_POLICY_CONSTRAINTS,
         "id-ce-policyConstraints",
+        "rfc5912",
+    ),
+    (
+        &rfc5912::ID_CE_EXT_KEY_USAGE,
+        "id-ce-extKeyUsage",
+        "rfc5912",
+    ),
+    (
+        &rfc5912::ANY_EXTENDED_KEY_USAGE,
+        "anyExtendedKeyUsage",
+        "rfc5912",
+    ),
+    (&rfc5912::ID_CE_FRESHEST_CRL, "id-ce-freshestCRL", "rfc5912"),
+    (
+        &rfc5912::ID_CE_INHIBIT_ANY_POLICY,
+        "id-ce-inhibitAnyPolicy",
+        "rfc5912",
     ),
-    (&rfc5912::ID_CE_EXT_KEY_USAGE, "id-ce-extKeyUsage"),
-    (&rfc5912::ANY_EXTENDED_KEY_USAGE, "anyExtendedKeyUsage"),
-    (&rfc5912::ID_CE_FRESHEST_CRL, "id-ce-freshestCRL"),
-    (&rfc5912::ID_CE_INHIBIT_ANY_POLICY, "id-ce-inhibitAnyPolicy"),
     (
         &rfc5912::ID_CE_TARGET_INFORMATION,
         "id-ce-targetInformation",
+        "rfc5912",
     ),
-    (&rfc5912::ID_CE_NO_REV_AVAIL, "id-ce-noRevAvail"),
+    (&rfc5912::ID_CE_NO_REV_AVAIL, "id-ce-noRevAvail", "rfc5912"),
     (
         &rfc5912::ID_CE_SUBJECT_DIRECTORY_ATTRIBUTES,
         "id-ce-subjectDirectoryAttributes",
+        "rfc5912",
     ),
-    (&rfc5912::ID_AT, "id-at"),
-    (&rfc5912::ID_AT_ROLE, "id-at-role"),
-    (&rfc6109::LDIF_LOCATION_URL_OBJECT, "LDIFLocationURLObject"),
-    (&rfc6109::PROVIDER, "provider"),
+    (&rfc5912::ID_AT, "id-at", "rfc5912"),
+    (&rfc5912::ID_AT_ROLE, "id-at-role", "rfc5912"),
+    (
+        &rfc6109::LDIF_LOCATION_URL_OBJECT,
+        "LDIFLocationURLObject",
+        "rfc6109",
+    ),
+    (&rfc6109::PROVIDER, "provider", "rfc6109"),
     (
         &rfc6109::PROVIDER_CERTIFICATE_HASH,
         "providerCertificateHash",
+        "rfc6109",
+    ),
+    (
+        &rfc6109::PROVIDER_CERTIFICATE,
+        "providerCertificate",
+        "rfc6109",
     ),
-    (&rfc6109::PROVIDER_CERTIFICATE, "providerCertificate"),
-    (&rfc6109::PROVIDER_NAME, "providerName"),
-    (&rfc6109::MAIL_RECEIPT, "mailReceipt"),
-    (&rfc6109::MANAGED_DOMAINS, "managedDomains"),
-    (&rfc6109::LDIF_LOCATION_URL, "LDIFLocationURL"),
-    (&rfc6109::PROVIDER_UNIT, "providerUnit"),
-    (&rfc6268::RSADSI, "rsadsi"),
-    (&rfc6268::ID_DATA, "id-data"),
-    (&rfc6268::ID_SIGNED_DATA, "id-signedData"),
-    (&rfc6268::ID_ENVELOPED_DATA, "id-envelopedData"),
-    (&rfc6268::ID_DIGESTED_DATA, "id-digestedData"),
-    (&rfc6268::ID_ENCRYPTED_DATA, "id-encryptedData"),
+    (&rfc6109::PROVIDER_NAME, "providerName", "rfc6109"),
+    (&rfc6109::MAIL_RECEIPT, "mailReceipt", "rfc6109"),
+    (&rfc6109::MANAGED_DOMAINS, "managedDomains", "rfc6109"),
+    (&rfc6109::LDIF_LOCATION_URL, "LDIFLocationURL", "rfc6109"),
+    (&rfc6109::PROVIDER_UNIT, "providerUnit", "rfc6109"),
+    (&rfc6268::RSADSI, "rsadsi", "rfc6268"),
+    (&rfc6268::ID_DATA, "id-data", "rfc6268"),
+    (&rfc6268::ID_SIGNED_DATA, "id-signedData", "rfc6268"),
+    (&rfc6268::ID_ENVELOPED_DATA, "id-envelopedData", "rfc6268"),
+    (&rfc6268::ID_DIGESTED_DATA, "id-digestedData", "rfc6268"),
+    (&rfc6268::ID_ENCRYPTED_DATA, "id-encryptedData", "rfc6268"),
     (
         &rfc6268::ID_CT_CONTENT_COLLECTION,
         "id-ct-contentCollection",
+        "rfc6268",
+    ),
+    (&rfc6268::ID_CT_AUTH_DATA, "id-ct-authData", "rfc6268"),
+    (
+        &rfc6268::ID_CT_CONTENT_WITH_ATTRS,
+        "id-ct-contentWithAttrs",
+        "rfc6268",
     ),
-    (&rfc6268::ID_CT_AUTH_DATA, "id-ct-authData"),
-    (&rfc6268::ID_CT_CONTENT_WITH_ATTRS, "id-ct-contentWithAttrs"),
     (
         &rfc6268::ID_CT_AUTH_ENVELOPED_DATA,
         "id-ct-authEnvelopedData",
+        "rfc6268",
+    ),
+    (&rfc6268::ID_CT_CONTENT_INFO, "id-ct-contentInfo", "rfc6268"),
+    (
+        &rfc6268::ID_CT_COMPRESSED_DATA,
+        "id-ct-compressedData",
+        "rfc6268",
     ),
-    (&rfc6268::ID_CT_CONTENT_INFO, "id-ct-contentInfo"),
-    (&rfc6268::ID_CT_COMPRESSED_DATA, "id-ct-compressedData"),
     (
         &rfc6268::ID_AA_BINARY_SIGNING_TIME,
         "id-aa-binarySigningTime",
+        "rfc6268",
+    ),
+    (
+        &rfc6268::ID_ALG_ZLIB_COMPRESS,
+        "id-alg-zlibCompress",
+        "rfc6268",
     ),
-    (&rfc6268::ID_ALG_ZLIB_COMPRESS, "id-alg-zlibCompress"),
     (
         &rfc6268::ID_AA_MULTIPLE_SIGNATURES,
         "id-aa-multipleSignatures",
+        "rfc6268",
+    ),
+    (&rfc6268::ID_CONTENT_TYPE, "id-contentType", "rfc6268"),
+    (&rfc6268::ID_MESSAGE_DIGEST, "id-messageDigest", "rfc6268"),
+    (&rfc6268::ID_SIGNING_TIME, "id-signingTime", "rfc6268"),
+    (
+        &rfc6268::ID_COUNTERSIGNATURE,
+        "id-countersignature",
+        "rfc6268",
+    ),
+    (&rfc6268::DIGEST_ALGORITHM, "digestAlgorithm", "rfc6268"),
+    (
+        &rfc6268::ID_HMAC_WITH_SHA_384,
+        "id-hmacWithSHA384",
+        "rfc6268",
+    ),
+    (
+        &rfc6268::ID_HMAC_WITH_SHA_512,
+        "id-hmacWithSHA512",
+        "rfc6268",
+    ),
+    (
+        &rfc6268::ID_HMAC_WITH_SHA_224,
+        "id-hmacWithSHA224",
+        "rfc6268",
+    ),
+    (
+        &rfc6268::ID_HMAC_WITH_SHA_256,
+        "id-hmacWithSHA256",
+        "rfc6268",
+    ),
+    (&rfc6960::ID_PKIX_OCSP, "id-pkix-ocsp", "rfc6960"),
+    (
+        &rfc6960::ID_PKIX_OCSP_BASIC,
+        "id-pkix-ocsp-basic",
+        "rfc6960",
+    ),
+    (
+        &rfc6960::ID_PKIX_OCSP_NONCE,
+        "id-pkix-ocsp-nonce",
+        "rfc6960",
+    ),
+    (&rfc6960::ID_PKIX_OCSP_CRL, "id-pkix-ocsp-crl", "rfc6960"),
+    (
+        &rfc6960::ID_PKIX_OCSP_RESPONSE,
+        "id-pkix-ocsp-response",
+        "rfc6960",
+    ),
+    (
+        &rfc6960::ID_PKIX_OCSP_NOCHECK,
+        "id-pkix-ocsp-nocheck",
+        "rfc6960",
     ),
-    (&rfc6268::ID_CONTENT_TYPE, "id-contentType"),
-    (&rfc6268::ID_MESSAGE_DIGEST, "id-messageDigest"),
-    (&rfc6268::ID_SIGNING_TIME, "id-signingTime"),
-    (&rfc6268::ID_COUNTERSIGNATURE, "id-countersignature"),
-    (&rfc6268::DIGEST_ALGORITHM, "digestAlgorithm"),
-    (&rfc6268::ID_HMAC_WITH_SHA_384, "id-hmacWithSHA384"),
-    (&rfc6268::ID_HMAC_WITH_SHA_512, "id-hmacWithSHA512"),
-    (&rfc6268::ID_HMAC_WITH_SHA_224, "id-hmacWithSHA224"),
-    (&rfc6268::ID_HMAC_WITH_SHA_256, "id-hmacWithSHA256"),
-    (&rfc6960::ID_PKIX_OCSP, "id-pkix-ocsp"),
-    (&rfc6960::ID_PKIX_OCSP_BASIC, "id-pkix-ocsp-basic"),
-    (&rfc6960::ID_PKIX_OCSP_NONCE, "id-pkix-ocsp-nonce"),
-    (&rfc6960::ID_PKIX_OCSP_CRL, "id-pkix-ocsp-crl"),
-    (&rfc6960::ID_PKIX_OCSP_RESPONSE, "id-pkix-ocsp-response"),
-    (&rfc6960::ID_PKIX_OCSP_NOCHECK, "id-pkix-ocsp-nocheck"),
     (
         &rfc6960::ID_PKIX_OCSP_ARCHIVE_CUTOFF,
         "id-pkix-ocsp-archive-cutoff",
+        "rfc6960",
     ),
     (
         &rfc6960::ID_PKIX_OCSP_SERVICE_LOCATOR,
         "id-pkix-ocsp-service-locator",
+        "rfc6960",
     ),
     (
         &rfc6960::ID_PKIX_OCSP_PREF_SIG_ALGS,
         "id-pkix-ocsp-pref-sig-algs",
+        "rfc6960",
     ),
     (
         &rfc6960::ID_PKIX_OCSP_EXTENDED_REVOKE,
         "id-pkix-ocsp-extended-revoke",
-    ),
-    (&rfc6962::GOOGLE, "google"),
-    (&rfc6962::CT_PRECERT_SCTS, "ct-precert-scts"),
-    (&rfc6962::CT_PRECERT_POISON, "ct-precert-poison"),
-    (&rfc6962::CT_PRECERT_SIGNING_CERT, "ct-precert-signing-cert"),
-    (&rfc7107::ID_SMIME, "id-smime"),
-    (&rfc7107::ID_MOD, "id-mod"),
-    (&rfc7107::ID_CT, "id-ct"),
-    (&rfc7107::ID_EIT, "id-eit"),
-    (&rfc7107::ID_CAP, "id-cap"),
-    (&rfc7107::ID_PSKC, "id-pskc"),
-    (&rfc7107::ID_AA, "id-aa"),
-    (&rfc7107::ID_ALG, "id-alg"),
-    (&rfc7107::ID_CD, "id-cd"),
-    (&rfc7107::ID_SPQ, "id-spq"),
-    (&rfc7107::ID_CTI, "id-cti"),
-    (&rfc7107::ID_TSP, "id-tsp"),
-    (&rfc7107::ID_SKD, "id-skd"),
-    (&rfc7107::ID_STI, "id-sti"),
-    (&rfc7292::RSADSI, "rsadsi"),
-    (&rfc7292::PKCS, "pkcs"),
-    (&rfc7292::PKCS_12, "pkcs-12"),
-    (&rfc7292::PKCS_12_PBE_IDS, "pkcs-12PbeIds"),
+        "rfc6960",
+    ),
+    (&rfc6962::GOOGLE, "google", "rfc6962"),
+    (&rfc6962::CT_PRECERT_SCTS, "ct-precert-scts", "rfc6962"),
+    (&rfc6962::CT_PRECERT_POISON, "ct-precert-poison", "rfc6962"),
+    (
+        &rfc6962::CT_PRECERT_SIGNING_CERT,
+        "ct-precert-signing-cert",
+        "rfc6962",
+    ),
+    (&rfc7107::ID_SMIME, "id-smime", "rfc7107"),
+    (&rfc7107::ID_MOD, "id-mod", "rfc7107"),
+    (&rfc7107::ID_CT, "id-ct", "rfc7107"),
+    (&rfc7107::ID_EIT, "id-eit", "rfc7107"),
+    (&rfc7107::ID_CAP, "id-cap", "rfc7107"),
+    (&rfc7107::ID_PSKC, "id-pskc", "rfc7107"),
+    (&rfc7107::ID_AA, "id-aa", "rfc7107"),
+    (&rfc7107::ID_ALG, "id-alg", "rfc7107"),
+    (&rfc7107::ID_CD, "id-cd", "rfc7107"),
+    (&rfc7107::ID_SPQ, "id-spq", "rfc7107"),
+    (&rfc7107::ID_CTI, "id-cti", "rfc7107"),
+    (&rfc7107::ID_TSP, "id-tsp", "rfc7107"),
+    (&rfc7107::ID_SKD, "id-skd", "rfc7107"),
+    (&rfc7107::ID_STI, "id-sti", "rfc7107"),
+    (&rfc7292::RSADSI, "rsadsi", "rfc7292"),
+    (&rfc7292::PKCS, "pkcs", "rfc7292"),
+    (&rfc7292::PKCS_12, "pkcs-12", "rfc7292"),
+    (&rfc7292::PKCS_12_PBE_IDS, "pkcs-12PbeIds", "rfc7292"),
     (
         &rfc7292::PBE_WITH_SHA_AND_128_BIT_RC_4,
         "pbeWithSHAAnd128BitRC4",
+        "rfc7292",
     ),
     (
         &rfc7292::PBE_WITH_SHA_AND_40_BIT_RC_4,
         "pbeWithSHAAnd40BitRC4",
+        "rfc7292",
     ),
     (
         &rfc7292::PBE_WITH_SHA_AND_3_KEY_TRIPLE_DES_CBC,
         "pbeWithSHAAnd3-KeyTripleDES-CBC",
+        "rfc7292",
     ),
     (
         &rfc7292::PBE_WITH_SHA_AND_2_KEY_TRIPLE_DES_CBC,
         "pbeWithSHAAnd2-KeyTripleDES-CBC",
+        "rfc7292",
     ),
     (
         &rfc7292::PBE_WITH_SHA_AND_128_BIT_RC_2_CBC,
         "pbeWithSHAAnd128BitRC2-CBC",
+        "rfc7292",
     ),
     (
         &rfc7292::PBEWITH_SHA_AND_40_BIT_RC_2_CBC,
         "pbewithSHAAnd40BitRC2-CBC",
-    ),
-    (&rfc7292::BAGTYPES, "bagtypes"),
-    (&rfc7299::ID_PKIX, "id-pkix"),
-    (&rfc7299::ID_MOD, "id-mod"),
-    (&rfc7299::ID_PE, "id-pe"),
-    (&rfc7299::ID_ACA, "id-aca"),
-    (&rfc7299::ID_QCS, "id-qcs"),
-    (&rfc7299::ID_CCT, "id-cct"),
-    (&rfc7299::ID_TEST, "id-TEST"),
-    (&rfc7299::ID_CP, "id-cp"),
-    (&rfc7299::ID_CET, "id-cet"),
-    (&rfc7299::ID_RI, "id-ri"),
-    (&rfc7299::ID_SCT, "id-sct"),
-    (&rfc7299::ID_SWB, "id-swb"),
-    (&rfc7299::ID_SVP, "id-svp"),
-    (&rfc7299::ID_NVAE, "id-nvae"),
-    (&rfc7299::ID_BVAE, "id-bvae"),
-    (&rfc7299::ID_DNVAE, "id-dnvae"),
-    (&rfc7299::ID_QT, "id-qt"),
-    (&rfc7299::ID_LOGO, "id-logo"),
-    (&rfc7299::ID_PPL, "id-ppl"),
-    (&rfc7299::ID_MR, "id-mr"),
-    (&rfc7299::ID_SKIS, "id-skis"),
-    (&rfc7299::ID_KP, "id-kp"),
-    (&rfc7299::ID_IT, "id-it"),
-    (&rfc7299::ID_AD, "id-ad"),
-    (&rfc7299::ID_PKIX_OCSP, "id-pkix-ocsp"),
-    (&rfc7299::ID_PKIP, "id-pkip"),
-    (&rfc7299::ID_REG_CTRL, "id-regCtrl"),
-    (&rfc7299::ID_REG_INFO, "id-regInfo"),
-    (&rfc7299::ID_ALG, "id-alg"),
-    (&rfc7299::ID_CMC, "id-cmc"),
-    (&rfc7299::ID_CMC_GLA_RR, "id-cmc-glaRR"),
-    (&rfc7299::ID_ON, "id-on"),
-    (&rfc7299::ID_PDA, "id-pda"),
-    (&rfc7532::FEDFS_UUID, "fedfsUuid"),
-    (&rfc7532::FEDFS_FSL_PORT, "fedfsFslPort"),
-    (&rfc7532::FEDFS_NFS_PATH, "fedfsNfsPath"),
+        "rfc7292",
+    ),
+    (&rfc7292::BAGTYPES, "bagtypes", "rfc7292"),
+    (&rfc7299::ID_PKIX, "id-pkix", "rfc7299"),
+    (&rfc7299::ID_MOD, "id-mod", "rfc7299"),
+    (&rfc7299::ID_PE, "id-pe", "rfc7299"),
+    (&rfc7299::ID_ACA, "id-aca", "rfc7299"),
+    (&rfc7299::ID_QCS, "id-qcs", "rfc7299"),
+    (&rfc7299::ID_CCT, "id-cct", "rfc7299"),
+    (&rfc7299::ID_TEST, "id-TEST", "rfc7299"),
+    (&rfc7299::ID_CP, "id-cp", "rfc7299"),
+    (&rfc7299::ID_CET, "id-cet", "rfc7299"),
+    (&rfc7299::ID_RI, "id-ri", "rfc7299"),
+    (&rfc7299::ID_SCT, "id-sct", "rfc7299"),
+    (&rfc7299::ID_SWB, "id-swb", "rfc7299"),
+    (&rfc7299::ID_SVP, "id-svp", "rfc7299"),
+    (&rfc7299::ID_NVAE, "id-nvae", "rfc7299"),
+    (&rfc7299::ID_BVAE, "id-bvae", "rfc7299"),
+    (&rfc7299::ID_DNVAE, "id-dnvae", "rfc7299"),
+    (&rfc7299::ID_QT, "id-qt", "rfc7299"),
+    (&rfc7299::ID_LOGO, "id-logo", "rfc7299"),
+    (&rfc7299::ID_PPL, "id-ppl", "rfc7299"),
+    (&rfc7299::ID_MR, "id-mr", "rfc7299"),
+    (&rfc7299::ID_SKIS, "id-skis", "rfc7299"),
+    (&rfc7299::ID_KP, "id-kp", "rfc7299"),
+    (&rfc7299::ID_IT, "id-it", "rfc7299"),
+    (&rfc7299::ID_AD, "id-ad", "rfc7299"),
+    (&rfc7299::ID_PKIX_OCSP, "id-pkix-ocsp", "rfc7299"),
+    (&rfc7299::ID_PKIP, "id-pkip", "rfc7299"),
+    (&rfc7299::ID_REG_CTRL, "id-regCtrl", "rfc7299"),
+    (&rfc7299::ID_REG_INFO, "id-regInfo", "rfc7299"),
+    (&rfc7299::ID_ALG, "id-alg", "rfc7299"),
+    (&rfc7299::ID_CMC, "id-cmc", "rfc7299"),
+    (&rfc7299::ID_CMC_GLA_RR, "id-cmc-glaRR", "rfc7299"),
+    (&rfc7299::ID_ON, "id-on", "rfc7299"),
+    (&rfc7299::ID_PDA, "id-pda", "rfc7299"),
+    (&rfc7532::FEDFS_UUID, "fedfsUuid", "rfc7532"),
+    (&rfc7532::FEDFS_FSL_PORT, "fedfsFslPort", "rfc7532"),
+    (&rfc7532::FEDFS_NFS_PATH, "fedfsNfsPath", "rfc7532"),
     (
         &rfc7532::FEDFS_NSDB_CONTAINER_INFO,
         "fedfsNsdbContainerInfo",
+        "rfc7532",
     ),
-    (&rfc7532::FEDFS_FSN, "fedfsFsn"),
-    (&rfc7532::FEDFS_FSL, "fedfsFsl"),
-    (&rfc7532::FEDFS_NFS_FSL, "fedfsNfsFsl"),
-    (&rfc7532::FEDFS_NFS_MAJOR_VER, "fedfsNfsMajorVer"),
-    (&rfc7532::FEDFS_NFS_MINOR_VER, "fedfsNfsMinorVer"),
-    (&rfc7532::FEDFS_NFS_CURRENCY, "fedfsNfsCurrency"),
+    (&rfc7532::FEDFS_FSN, "fedfsFsn", "rfc7532"),
+    (&rfc7532::FEDFS_FSL, "fedfsFsl", "rfc7532"),
+    (&rfc7532::FEDFS_NFS_FSL, "fedfsNfsFsl", "rfc7532"),
+    (&rfc7532::FEDFS_NFS_MAJOR_VER, "fedfsNfsMajorVer", "rfc7532"),
+    (&rfc7532::FEDFS_NFS_MINOR_VER, "fedfsNfsMinorVer", "rfc7532"),
+    (&rfc7532::FEDFS_NFS_CURRENCY, "fedfsNfsCurrency", "rfc7532"),
     (
         &rfc7532::FEDFS_NFS_GEN_FLAG_WRITABLE,
         "fedfsNfsGenFlagWritable",
+        "rfc7532",
+    ),
+    (
+        &rfc7532::FEDFS_NFS_GEN_FLAG_GOING,
+        "fedfsNfsGenFlagGoing",
+        "rfc7532",
+    ),
+    (
+        &rfc7532::FEDFS_NFS_GEN_FLAG_SPLIT,
+        "fedfsNfsGenFlagSplit",
+        "rfc7532",
+    ),
+    (
+        &rfc7532::FEDFS_NFS_TRANS_FLAG_RDMA,
+        "fedfsNfsTransFlagRdma",
+        "rfc7532",
+    ),
+    (
+        &rfc7532::FEDFS_NFS_CLASS_SIMUL,
+        "fedfsNfsClassSimul",
+        "rfc7532",
+    ),
+    (
+        &rfc7532::FEDFS_NFS_CLASS_HANDLE,
+        "fedfsNfsClassHandle",
+        "rfc7532",
     ),
-    (&rfc7532::FEDFS_NFS_GEN_FLAG_GOING, "fedfsNfsGenFlagGoing"),
-    (&rfc7532::FEDFS_NFS_GEN_FLAG_SPLIT, "fedfsNfsGenFlagSplit"),
-    (&rfc7532::FEDFS_NFS_TRANS_FLAG_RDMA, "fedfsNfsTransFlagRdma"),
-    (&rfc7532::FEDFS_NFS_CLASS_SIMUL, "fedfsNfsClassSimul"),
-    (&rfc7532::FEDFS_NFS_CLASS_HANDLE, "fedfsNfsClassHandle"),
-    (&rfc7532::FEDFS_FSL_TTL, "fedfsFslTTL"),
-    (&rfc7532::FEDFS_NFS_CLASS_FILEID, "fedfsNfsClassFileid"),
-    (&rfc7532::FEDFS_NFS_CLASS_WRITEVER, "fedfsNfsClassWritever"),
-    (&rfc7532::FEDFS_NFS_CLASS_CHANGE, "fedfsNfsClassChange"),
-    (&rfc7532::FEDFS_NFS_CLASS_READDIR, "fedfsNfsClassReaddir"),
-    (&rfc7532::FEDFS_NFS_READ_RANK, "fedfsNfsReadRank"),
-    (&rfc7532::FEDFS_NFS_READ_ORDER, "fedfsNfsReadOrder"),
-    (&rfc7532::FEDFS_NFS_WRITE_RANK, "fedfsNfsWriteRank"),
-    (&rfc7532::FEDFS_NFS_WRITE_ORDER, "fedfsNfsWriteOrder"),
-    (&rfc7532::FEDFS_NFS_VAR_SUB, "fedfsNfsVarSub"),
-    (&rfc7532::FEDFS_NFS_VALID_FOR, "fedfsNfsValidFor"),
-    (&rfc7532::FEDFS_ANNOTATION, "fedfsAnnotation"),
-    (&rfc7532::FEDFS_NFS_URI, "fedfsNfsURI"),
-    (&rfc7532::FEDFS_DESCR, "fedfsDescr"),
-    (&rfc7532::FEDFS_NCE_DN, "fedfsNceDN"),
-    (&rfc7532::FEDFS_FSN_TTL, "fedfsFsnTTL"),
-    (&rfc7532::FEDFS_NET_ADDR, "fedfsNetAddr"),
-    (&rfc7532::FEDFS_NET_PORT, "fedfsNetPort"),
-    (&rfc7532::FEDFS_FSN_UUID, "fedfsFsnUuid"),
-    (&rfc7532::FEDFS_NSDB_NAME, "fedfsNsdbName"),
-    (&rfc7532::FEDFS_NSDB_PORT, "fedfsNsdbPort"),
-    (&rfc7532::FEDFS_NCE_PREFIX, "fedfsNcePrefix"),
-    (&rfc7532::FEDFS_FSL_UUID, "fedfsFslUuid"),
-    (&rfc7532::FEDFS_FSL_HOST, "fedfsFslHost"),
-    (&rfc7612::PRINTER_DEVICE_ID, "printer-device-id"),
+    (&rfc7532::FEDFS_FSL_TTL, "fedfsFslTTL", "rfc7532"),
+    (
+        &rfc7532::FEDFS_NFS_CLASS_FILEID,
+        "fedfsNfsClassFileid",
+        "rfc7532",
+    ),
+    (
+        &rfc7532::FEDFS_NFS_CLASS_WRITEVER,
+        "fedfsNfsClassWritever",
+        "rfc7532",
+    ),
+    (
+        &rfc7532::FEDFS_NFS_CLASS_CHANGE,
+        "fedfsNfsClassChange",
+        "rfc7532",
+    ),
+    (
+        &rfc7532::FEDFS_NFS_CLASS_READDIR,
+        "fedfsNfsClassReaddir",
+        "rfc7532",
+    ),
+    (&rfc7532::FEDFS_NFS_READ_RANK, "fedfsNfsReadRank", "rfc7532"),
+    (
+        &rfc7532::FEDFS_NFS_READ_ORDER,
+        "fedfsNfsReadOrder",
+        "rfc7532",
+    ),
+    (
+        &rfc7532::FEDFS_NFS_WRITE_RANK,
+        "fedfsNfsWriteRank",
+        "rfc7532",
+    ),
+    (
+        &rfc7532::FEDFS_NFS_WRITE_ORDER,
+        "fedfsNfsWriteOrder",
+        "rfc7532",
+    ),
+    (&rfc7532::FEDFS_NFS_VAR_SUB, "fedfsNfsVarSub", "rfc7532"),
+    (&rfc7532::FEDFS_NFS_VALID_FOR, "fedfsNfsValidFor", "rfc7532"),
+    (&rfc7532::FEDFS_ANNOTATION, "fedfsAnnotation", "rfc7532"),
+    (&rfc7532::FEDFS_NFS_URI, "fedfsNfsURI", "rfc7532"),
+    (&rfc7532::FEDFS_DESCR, "fedfsDescr", "rfc7532"),
+    (&rfc7532::FEDFS_NCE_DN, "fedfsNceDN", "rfc7532"),
+    (&rfc7532::FEDFS_FSN_TTL, "fedfsFsnTTL", "rfc7532"),
+    (&rfc7532::FEDFS_NET_ADDR, "fedfsNetAddr", "rfc7532"),
+    (&rfc7532::FEDFS_NET_PORT, "fedfsNetPort", "rfc7532"),
+    (&rfc7532::FEDFS_FSN_UUID, "fedfsFsnUuid", "rfc7532"),
+    (&rfc7532::FEDFS_NSDB_NAME, "fedfsNsdbName", "rfc7532"),
+    (&rfc7532::FEDFS_NSDB_PORT, "fedfsNsdbPort", "rfc7532"),
+    (&rfc7532::FEDFS_NCE_PREFIX, "fedfsNcePrefix", "rfc7532"),
+    (&rfc7532::FEDFS_FSL_UUID, "fedfsFslUuid", "rfc7532"),
+    (&rfc7532::FEDFS_FSL_HOST, "fedfsFslHost", "rfc7532"),
+    (&rfc7612::PRINTER_DEVICE_ID, "printer-device-id", "rfc7612"),
     (
         &rfc7612::PRINTER_DEVICE_SERVICE_COUNT,
         "printer-device-service-count",
+        "rfc7612",
+    ),
+    (&rfc7612::PRINTER_UUID, "printer-uuid", "rfc7612"),
+    (
+        &rfc7612::PRINTER_CHARGE_INFO,
+        "printer-charge-info",
+        "rfc7612",
+    ),
+    (
+        &rfc7612::PRINTER_CHARGE_INFO_URI,
+        "printer-charge-info-uri",
+        "rfc7612",
+    ),
+    (
+        &rfc7612::PRINTER_GEO_LOCATION,
+        "printer-geo-location",
+        "rfc7612",
     ),
-    (&rfc7612::PRINTER_UUID, "printer-uuid"),
-    (&rfc7612::PRINTER_CHARGE_INFO, "printer-charge-info"),
-    (&rfc7612::PRINTER_CHARGE_INFO_URI, "printer-charge-info-uri"),
-    (&rfc7612::PRINTER_GEO_LOCATION, "printer-geo-location"),
     (
         &rfc7612::PRINTER_IPP_FEATURES_SUPPORTED,
         "printer-ipp-features-supported",
-    ),
-    (&rfc7693::HASH_ALGS, "hashAlgs"),
-    (&rfc7693::BLAKE_2_B, "blake2b"),
-    (&rfc7693::ID_BLAKE_2_B_384, "id-blake2b384"),
-    (&rfc7693::ID_BLAKE_2_B_512, "id-blake2b512"),
-    (&rfc7693::ID_BLAKE_2_B_160, "id-blake2b160"),
-    (&rfc7693::ID_BLAKE_2_B_256, "id-blake2b256"),
-    (&rfc7693::BLAKE_2_S, "blake2s"),
-    (&rfc7693::ID_BLAKE_2_S_128, "id-blake2s128"),
-    (&rfc7693::ID_BLAKE_2_S_160, "id-blake2s160"),
-    (&rfc7693::ID_BLAKE_2_S_224, "id-blake2s224"),
-    (&rfc7693::ID_BLAKE_2_S_256, "id-blake2s256"),
-    (&rfc7693::MAC_ALGS, "macAlgs"),
-    (&rfc8284::JID_OBJECT, "JIDObject"),
-    (&rfc8284::JID, "jid"),
-    (&rfc8410::ID_EDWARDS_CURVE_ALGS, "id-edwards-curve-algs"),
-    (&rfc8410::ID_X_25519, "id-X25519"),
-    (&rfc8410::ID_X_448, "id-X448"),
-    (&rfc8410::ID_ED_25519, "id-Ed25519"),
-    (&rfc8410::ID_ED_448, "id-Ed448"),
-    (&rfc8894::ID_PKIX, "id-pkix"),
-    (&rfc8894::ID_SCEP, "id-scep"),
-    (&rfc8894::ID_SCEP_FAIL_INFO_TEXT, "id-scep-failInfoText"),
-    (&rfc8894::ID_VERI_SIGN, "id-VeriSign"),
-    (&rfc8894::ID_PKI, "id-pki"),
-    (&rfc8894::ID_ATTRIBUTES, "id-attributes"),
-    (&rfc8894::ID_MESSAGE_TYPE, "id-messageType"),
-    (&rfc8894::ID_PKI_STATUS, "id-pkiStatus"),
-    (&rfc8894::ID_FAIL_INFO, "id-failInfo"),
-    (&rfc8894::ID_SENDER_NONCE, "id-senderNonce"),
-    (&rfc8894::ID_RECIPIENT_NONCE, "id-recipientNonce"),
-    (&rfc8894::ID_TRANSACTION_ID, "id-transactionID"),
-    (&rfc9688::RSA_ENCRYPTION, "rsaEncryption"),
-    (&rfc9688::ID_MOD_SHA_3_OIDS_2023, "id-mod-sha3-oids-2023"),
-    (&rfc9688::ID_ALG, "id-alg"),
+        "rfc7612",
+    ),
+    (&rfc7693::HASH_ALGS, "hashAlgs", "rfc7693"),
+    (&rfc7693::BLAKE_2_B, "blake2b", "rfc7693"),
+    (&rfc7693::ID_BLAKE_2_B_384, "id-blake2b384", "rfc7693"),
+    (&rfc7693::ID_BLAKE_2_B_512, "id-blake2b512", "rfc7693"),
+    (&rfc7693::ID_BLAKE_2_B_160, "id-blake2b160", "rfc7693"),
+    (&rfc7693::ID_BLAKE_2_B_256, "id-blake2b256", "rfc7693"),
+    (&rfc7693::BLAKE_2_S, "blake2s", "rfc7693"),
+    (&rfc7693::ID_BLAKE_2_S_128, "id-blake2s128", "rfc7693"),
+    (&rfc7693::ID_BLAKE_2_S_160, "id-blake2s160", "rfc7693"),
+    (&rfc7693::ID_BLAKE_2_S_224, "id-blake2s224", "rfc7693"),
+    (&rfc7693::ID_BLAKE_2_S_256, "id-blake2s256", "rfc7693"),
+    (&rfc7693::MAC_ALGS, "macAlgs", "rfc7693"),
+    (&rfc8284::JID_OBJECT, "JIDObject", "rfc8284"),
+    (&rfc8284::JID, "jid", "rfc8284"),
+    (
+        &rfc8410::ID_EDWARDS_CURVE_ALGS,
+        "id-edwards-curve-algs",
+        "rfc8410",
+    ),
+    (&rfc8410::ID_X_25519, "id-X25519", "rfc8410"),
+    (&rfc8410::ID_X_448, "id-X448", "rfc8410"),
+    (&rfc8410::ID_ED_25519, "id-Ed25519", "rfc8410"),
+    (&rfc8410::ID_ED_448, "id-Ed448", "rfc8410"),
+    (&rfc8894::ID_PKIX, "id-pkix", "rfc8894"),
+    (&rfc8894::ID_SCEP, "id-scep", "rfc8894"),
+    (
+        &rfc8894::ID_SCEP_FAIL_INFO_TEXT,
+        "id-scep-failInfoText",
+        "rfc8894",
+    ),
+    (&rfc8894::ID_VERI_SIGN, "id-VeriSign", "rfc8894"),
+    (&rfc8894::ID_PKI, "id-pki", "rfc8894"),
+    (&rfc8894::ID_ATTRIBUTES, "id-attributes", "rfc8894"),
+    (&rfc8894::ID_MESSAGE_TYPE, "id-messageType", "rfc8894"),
+    (&rfc8894::ID_PKI_STATUS, "id-pkiStatus", "rfc8894"),
+    (&rfc8894::ID_FAIL_INFO, "id-failInfo", "rfc8894"),
+    (&rfc8894::ID_SENDER_NONCE, "id-senderNonce", "rfc8894"),
+    (&rfc8894::ID_RECIPIENT_NONCE, "id-recipientNonce", "rfc8894"),
+    (&rfc8894::ID_TRANSACTION_ID, "id-transactionID", "rfc8894"),
+    (&rfc9688::RSA_ENCRYPTION, "rsaEncryption", "rfc9688"),
+    (
+        &rfc9688::ID_MOD_SHA_3_OIDS_2023,
+        "id-mod-sha3-oids-2023",
+        "rfc9688",
+    ),
+    (&rfc9688::ID_ALG, "id-alg", "rfc9688"),
     (
         &rfc9688::ID_ALG_HKDF_WITH_SHA_3_224,
         "id-alg-hkdf-with-sha3-224",
+        "rfc9688",
     ),
     (
         &rfc9688::ID_ALG_HKDF_WITH_SHA_3_256,
         "id-alg-hkdf-with-sha3-256",
+        "rfc9688",
     ),
     (
         &rfc9688::ID_ALG_HKDF_WITH_SHA_3_384,
         "id-alg-hkdf-with-sha3-384",
+        "rfc9688",
     ),
     (
         &rfc9688::ID_ALG_HKDF_WITH_SHA_3_512,
         "id-alg-hkdf-with-sha3-512",
+        "rfc9688",
+    ),
+    (&rfc9688::X_9_44, "x9-44", "rfc9688"),
+    (&rfc9688::X_9_44_COMPONENTS, "x9-44-components", "rfc9688"),
+    (&rfc9688::ID_KDF_KDF_2, "id-kdf-kdf2", "rfc9688"),
+    (&rfc9688::ID_KDF_KDF_3, "id-kdf-kdf3", "rfc9688"),
+    (&rfc9688::HASH_ALGS, "hashAlgs", "rfc9688"),
+    (&rfc9688::ID_SHA_3_512, "id-sha3-512", "rfc9688"),
+    (
+        &rfc9688::ID_HMAC_WITH_SHA_3_224,
+        "id-hmacWithSHA3-224",
+        "rfc9688",
+    ),
+    (
+        &rfc9688::ID_HMAC_WITH_SHA_3_256,
+        "id-hmacWithSHA3-256",
+        "rfc9688",
+    ),
+    (
+        &rfc9688::ID_HMAC_WITH_SHA_3_384,
+        "id-hmacWithSHA3-384",
+        "rfc9688",
+    ),
+    (
+        &rfc9688::ID_HMAC_WITH_SHA_3_512,
+        "id-hmacWithSHA3-512",
+        "rfc9688",
+    ),
+    (&rfc9688::ID_KMAC_128, "id-kmac128", "rfc9688"),
+    (&rfc9688::ID_KMAC_256, "id-kmac256", "rfc9688"),
+    (&rfc9688::ID_SHA_3_224, "id-sha3-224", "rfc9688"),
+    (&rfc9688::ID_SHA_3_256, "id-sha3-256", "rfc9688"),
+    (&rfc9688::ID_SHA_3_384, "id-sha3-384", "rfc9688"),
+    (&rfc9688::SIG_ALGS, "sigAlgs", "rfc9688"),
+    (
+        &rfc9688::ID_ECDSA_WITH_SHA_3_256,
+        "id-ecdsa-with-sha3-256",
+        "rfc9688",
+    ),
+    (
+        &rfc9688::ID_ECDSA_WITH_SHA_3_384,
+        "id-ecdsa-with-sha3-384",
+        "rfc9688",
+    ),
+    (
+        &rfc9688::ID_ECDSA_WITH_SHA_3_512,
+        "id-ecdsa-with-sha3-512",
+        "rfc9688",
+    ),
+    (
+        &rfc9688::ID_ECDSA_WITH_SHA_3_224,
+        "id-ecdsa-with-sha3-224",
+        "rfc9688",
+    ),
+    (&tcgtpm::TCG_ORGANIZATION, "tcgOrganization", "tcgtpm"),
+    (
+        &tcgtpm::TCG_TCPA_SPEC_VERSION,
+        "tcg-tcpaSpecVersion",
+        "tcgtpm",
+    ),
+    (&tcgtpm::TCG_SV_TPM_12, "tcg-sv-tpm12", "tcgtpm"),
+    (&tcgtpm::TCG_SV_TPM_20, "tcg-sv-tpm20", "tcgtpm"),
+    (&tcgtpm::TCG_ATTRIBUTE, "tcg-attribute", "tcgtpm"),
+    (
+        &tcgtpm::TCG_AT_TPM_MANUFACTURER,
+        "tcg-at-tpmManufacturer",
+        "tcgtpm",
     ),
-    (&rfc9688::X_9_44, "x9-44"),
-    (&rfc9688::X_9_44_COMPONENTS, "x9-44-components"),
-    (&rfc9688::ID_KDF_KDF_2, "id-kdf-kdf2"),
-    (&rfc9688::ID_KDF_KDF_3, "id-kdf-kdf3"),
-    (&rfc9688::HASH_ALGS, "hashAlgs"),
-    (&rfc9688::ID_SHA_3_512, "id-sha3-512"),
-    (&rfc9688::ID_HMAC_WITH_SHA_3_224, "id-hmacWithSHA3-224"),
-    (&rfc9688::ID_HMAC_WITH_SHA_3_256, "id-hmacWithSHA3-256"),
-    (&rfc9688::ID_HMAC_WITH_SHA_3_384, "id-hmacWithSHA3-384"),
-    (&rfc9688::ID_HMAC_WITH_SHA_3_512, "id-hmacWithSHA3-512"),
-    (&rfc9688::ID_KMAC_128, "id-kmac128"),
-    (&rfc9688::ID_KMAC_256, "id-kmac256"),
-    (&rfc9688::ID_SHA_3_224, "id-sha3-224"),
-    (&rfc9688::ID_SHA_3_256, "id-sha3-256"),
-    (&rfc9688::ID_SHA_3_384, "id-sha3-384"),
-    (&rfc9688::SIG_ALGS, "sigAlgs"),
-    (&rfc9688::ID_ECDSA_WITH_SHA_3_256, "id-ecdsa-with-sha3-256"),
-    (&rfc9688::ID_ECDSA_WITH_SHA_3_384, "id-ecdsa-with-sha3-384"),
-    (&rfc9688::ID_ECDSA_WITH_SHA_3_512, "id-ecdsa-with-sha3-512"),
-    (&rfc9688::ID_ECDSA_WITH_SHA_3_224, "id-ecdsa-with-sha3-224"),
-    (&tcgtpm::TCG_ORGANIZATION, "tcgOrganization"),
-    (&tcgtpm::TCG_TCPA_SPEC_VERSION, "tcg-tcpaSpecVersion"),
-    (&tcgtpm::TCG_SV_TPM_12, "tcg-sv-tpm12"),
-    (&tcgtpm::TCG_SV_TPM_20, "tcg-sv-tpm20"),
-    (&tcgtpm::TCG_ATTRIBUTE, "tcg-attribute"),
-    (&tcgtpm::TCG_AT_TPM_MANUFACTURER, "tcg-at-tpmManufacturer"),
     (
         &tcgtpm::TCG_AT_SECURITY_QUALITIES,
         "tcg-at-securityQualities",
+        "tcgtpm",
     ),
     (
         &tcgtpm::TCG_AT_TPM_PROTECTION_PROFILE,
         "tcg-at-tpmProtectionProfile",
+        "tcgtpm",
     ),
     (
         &tcgtpm::TCG_AT_TPM_SECURITY_TARGET,
         "tcg-at-tpmSecurityTarget",
+        "tcgtpm",
     ),
     (
         &tcgtpm::TCG_AT_TBB_PROTECTION_PROFILE,
         "tcg-at-tbbProtectionProfile",
+        "tcgtpm",
     ),
     (
         &tcgtpm::TCG_AT_TBB_SECURITY_TARGET,
         "tcg-at-tbbSecurityTarget",
+        "tcgtpm",
+    ),
+    (&tcgtpm::TCG_AT_TPM_ID_LABEL, "tcg-at-tpmIdLabel", "tcgtpm"),
+    (
+        &tcgtpm::TCG_AT_TPM_SPECIFICATION,
+        "tcg-at-tpmSpecification",
+        "tcgtpm",
     ),
-    (&tcgtpm::TCG_AT_TPM_ID_LABEL, "tcg-at-tpmIdLabel"),
-    (&tcgtpm::TCG_AT_TPM_SPECIFICATION, "tcg-at-tpmSpecification"),
     (
         &tcgtpm::TCG_AT_TCG_PLATFORM_SPECIFICATION,
         "tcg-at-tcgPlatformSpecification",
+        "tcgtpm",
     ),
     (
         &tcgtpm::TCG_AT_TPM_SECURITY_ASSERTIONS,
         "tcg-at-tpmSecurityAssertions",
+        "tcgtpm",
     ),
     (
         &tcgtpm::TCG_AT_TBB_SECURITY_ASSERTIONS,
         "tcg-at-tbbSecurityAssertions",
+        "tcgtpm",
     ),
-    (&tcgtpm::TCG_AT_TPM_MODEL, "tcg-at-tpmModel"),
-    (&tcgtpm::TCG_AT_TPM_VERSION, "tcg-at-tpmVersion"),
+    (&tcgtpm::TCG_AT_TPM_MODEL, "tcg-at-tpmModel", "tcgtpm"),
+    (&tcgtpm::TCG_AT_TPM_VERSION, "tcg-at-tpmVersion", "tcgtpm"),
     (
         &tcgtpm::TCG_AT_PLATFORM_MANUFACTURER,
         "tcg-at-platformManufacturer",
+        "tcgtpm",
+    ),
+    (
+        &tcgtpm::TCG_AT_PLATFORM_MODEL,
+        "tcg-at-platformModel",
+        "tcgtpm",
+    ),
+    (
+        &tcgtpm::TCG_AT_PLATFORM_VERSION,
+        "tcg-at-platformVersion",
+        "tcgtpm",
     ),
-    (&tcgtpm::TCG_AT_PLATFORM_MODEL, "tcg-at-platformModel"),
-    (&tcgtpm::TCG_AT_PLATFORM_VERSION, "tcg-at-platformVersion"),
-    (&tcgtpm::TCG_PROTOCOL, "tcg-protocol"),
-    (&tcgtpm::TCG_PRT_TPM_ID_PROTOCOL, "tcg-prt-tpmIdProtocol"),
-    (&tcgtpm::TCG_ALGORITHM, "tcg-algorithm"),
-    (&tcgtpm::TCG_ALGORITHM_NULL, "tcg-algorithm-null"),
-    (&tcgtpm::TCG_CE, "tcg-ce"),
+    (&tcgtpm::TCG_PROTOCOL, "tcg-protocol", "tcgtpm"),
+    (
+        &tcgtpm::TCG_PRT_TPM_ID_PROTOCOL,
+        "tcg-prt-tpmIdProtocol",
+        "tcgtpm",
+    ),
+    (&tcgtpm::TCG_ALGORITHM, "tcg-algorithm", "tcgtpm"),
+    (&tcgtpm::TCG_ALGORITHM_NULL, "tcg-algorithm-null", "tcgtpm"),
+    (&tcgtpm::TCG_CE, "tcg-ce", "tcgtpm"),
     (
         &tcgtpm::TCG_CE_RELEVANT_CREDENTIALS,
         "tcg-ce-relevantCredentials",
+        "tcgtpm",
     ),
     (
         &tcgtpm::TCG_CE_RELEVANT_MANIFESTS,
         "tcg-ce-relevantManifests",
+        "tcgtpm",
     ),
     (
         &tcgtpm::TCG_CE_VIRTUAL_PLATFORM_ATTESTATION_SERVICE,
         "tcg-ce-virtualPlatformAttestationService",
+        "tcgtpm",
     ),
     (
         &tcgtpm::TCG_CE_MIGRATION_CONTROLLER_ATTESTATION_SERVICE,
         "tcg-ce-migrationControllerAttestationService",
+        "tcgtpm",
     ),
     (
         &tcgtpm::TCG_CE_MIGRATION_CONTROLLER_REGISTRATION_SERVICE,
         "tcg-ce-migrationControllerRegistrationService",
+        "tcgtpm",
     ),
     (
         &tcgtpm::TCG_CE_VIRTUAL_PLATFORM_BACKUP_SERVICE,
         "tcg-ce-virtualPlatformBackupService",
+        "tcgtpm",
+    ),
+    (&tcgtpm::TCG_KP, "tcg-kp", "tcgtpm"),
+    (
+        &tcgtpm::TCG_KP_EK_CERTIFICATE,
+        "tcg-kp-EKCertificate",
+        "tcgtpm",
     ),
-    (&tcgtpm::TCG_KP, "tcg-kp"),
-    (&tcgtpm::TCG_KP_EK_CERTIFICATE, "tcg-kp-EKCertificate"),
     (
         &tcgtpm::TCG_KP_PLATFORM_CERTIFICATE,
         "tcg-kp-PlatformCertificate",
+        "tcgtpm",
+    ),
+    (
+        &tcgtpm::TCG_KP_AIK_CERTIFICATE,
+        "tcg-kp-AIKCertificate",
+        "tcgtpm",
     ),
-    (&tcgtpm::TCG_KP_AIK_CERTIFICATE, "tcg-kp-AIKCertificate"),
 ]);
@@ -87,3 +87,72 @@ impl fmt::Display for Error {
 }
 
 impl core::error::Error for Error {}
+
+/// Error parsing the dotted string form of an OID, locating the [`Error`] in
+/// the input.
+///
+/// Returned by [`ObjectIdentifier::try_parse_dotted`][`crate::ObjectIdentifier::try_parse_dotted`],
+/// e.g. to point out invalid OIDs in configuration files.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+pub struct ParseError {
+    /// Reason the input was rejected.
+    error: Error,
+
+    /// Position of the offending character in the input.
+    position: usize,
+
+    /// Index of the offending arc.
+    arc_index: usize,
+}
+
+impl ParseError {
+    /// Create a new parse error.
+    pub(crate) const fn new(error: Error, position: usize, arc_index: usize) -> Self {
+        Self {
+            error,
+            position,
+            arc_index,
+        }
+    }
+
+    /// Get the reason the input was rejected.
+    pub const fn error(&self) -> Error {
+        self.error
+    }
+
+    /// Get the byte position in the input of the offending character, or of
+    /// the start of the offending arc if its value was rejected.
+    ///
+    /// For errors about the input as a whole, e.g. [`Error::Length`], this
+    /// is the length of the input.
+    pub const fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Get the zero-based index of the offending arc.
+    pub const fn arc_index(&self) -> usize {
+        self.arc_index
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} (at position {}, arc {})",
+            self.error, self.position, self.arc_index
+        )
+    }
+}
+
+impl core::error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(err: ParseError) -> Error {
+        err.error
+    }
+}
@@ -26,16 +26,19 @@ mod arcs;
 mod buffer;
 mod encoder;
 mod error;
+mod macros;
 mod parser;
+mod relative;
 mod traits;
 
 #[cfg(feature = "db")]
 pub mod db;
 
 pub use crate::{
-    arcs::{Arc, Arcs},
+    arcs::{Arc, Arcs, ParentArcs},
     buffer::Buffer,
-    error::{Error, Result},
+    error::{Error, ParseError, Result},
+    relative::RelativeObjectIdentifier,
     traits::{AssociatedOid, DynAssociatedOid},
 };
 
@@ -106,6 +109,28 @@ impl ObjectIdentifier {
         }
     }
 
+    /// Parse an [`ObjectIdentifier`] from the dot-delimited string form,
+    /// reporting exactly where and why invalid input was rejected.
+    ///
+    /// This never panics, and is meant for validating user-provided input,
+    /// e.g. configuration files:
+    ///
+    /// ```
+    /// use const_oid::{Error, ObjectIdentifier};
+    ///
+    /// let err = ObjectIdentifier::try_parse_dotted("1.2.840.1x3549").unwrap_err();
+    /// assert_eq!(err.error(), Error::DigitExpected { actual: b'x' });
+    /// assert_eq!(err.position(), 9);
+    /// assert_eq!(err.arc_index(), 3);
+    /// ```
+    pub const fn try_parse_dotted(s: &str) -> core::result::Result<Self, ParseError> {
+        // TODO(tarcieri): use `?` when stable in `const fn`
+        match parser::Parser::parse_detailed(s) {
+            Ok(parser) => parser.finish_detailed(),
+            Err(err) => Err(err),
+        }
+    }
+
     /// Parse an OID from a slice of [`Arc`] values (i.e. integers).
     pub fn from_arcs(arcs: impl IntoIterator<Item = Arc>) -> Result<Self> {
         let mut encoder = Encoder::new();
@@ -118,7 +143,7 @@ impl ObjectIdentifier {
     }
 
     /// Parse an OID from from its BER/DER encoding.
-    pub fn from_bytes(ber_bytes: &[u8]) -> Result<Self> {
+    pub const fn from_bytes(ber_bytes: &[u8]) -> Result<Self> {
         Self::from_bytes_sized(ber_bytes)
     }
 }
@@ -127,8 +152,44 @@ impl<const MAX_SIZE: usize> ObjectIdentifier<MAX_SIZE> {
     /// Parse an OID from from its BER/DER encoding.
     ///
     /// Returns `Err(Error::Length)` if bytes do not fit in `MAX_SIZE`.
-    pub fn from_bytes_sized(ber_bytes: &[u8]) -> Result<Self> {
-        ObjectIdentifierRef::from_bytes(ber_bytes)?.try_into()
+    pub const fn from_bytes_sized(ber_bytes: &[u8]) -> Result<Self> {
+        // TODO(tarcieri): use `?` when stable in `const fn`
+        match ObjectIdentifierRef::from_bytes(ber_bytes) {
+            Ok(oid_ref) => Self::from_oid_ref(oid_ref),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Copy the BER/DER encoding of the given [`ObjectIdentifierRef`] into a
+    /// new [`ObjectIdentifier`].
+    ///
+    /// Returns `Err(Error::Length)` if bytes do not fit in `MAX_SIZE`.
+    const fn from_oid_ref(oid_ref: &ObjectIdentifierRef) -> Result<Self> {
+        let len = oid_ref.as_bytes().len();
+
+        if len > MAX_SIZE {
+            return Err(Error::Length);
+        }
+
+        let mut bytes = [0u8; MAX_SIZE];
+        let mut i = 0;
+
+        while i < len {
+            bytes[i] = oid_ref.as_bytes()[i];
+
+            // Won't overflow due to `i < len` check above
+            #[allow(clippy::arithmetic_side_effects)]
+            {
+                i += 1;
+            }
+        }
+
+        let ber = Buffer {
+            bytes,
+            length: len as u8,
+        };
+
+        Ok(Self { ber })
     }
 
     /// Get the BER/DER serialization of this OID as bytes.
@@ -145,18 +206,79 @@ impl<const MAX_SIZE: usize> ObjectIdentifier<MAX_SIZE> {
     }
 
     /// Get the parent OID of this one (if applicable).
-    pub fn parent(&self) -> Option<Self> {
-        let num_arcs = self.len().checked_sub(1)?;
-        let mut encoder = Encoder::new();
+    ///
+    /// Returns `None` if this OID has only two arcs (i.e. the root arcs).
+    ///
+    /// ```
+    /// use const_oid::ObjectIdentifier;
+    ///
+    /// const CHILD: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.6.1.4.1.32473.1");
+    /// const PARENT: Option<ObjectIdentifier> = CHILD.parent();
+    ///
+    /// assert_eq!(PARENT, Some(ObjectIdentifier::new_unwrap("1.3.6.1.4.1.32473")));
+    /// ```
+    pub const fn parent(&self) -> Option<Self> {
+        let mut ber = self.ber;
+
+        // The first byte encodes the two root arcs, which can't be removed
+        if ber.length <= 1 {
+            return None;
+        }
 
-        for arc in self.arcs().take(num_arcs) {
-            encoder = encoder.arc(arc).ok()?;
+        // The last byte of an arc has its high bit clear, whereas the other
+        // bytes of the arc have it set. Walk back over the last arc, clearing
+        // its bytes so the result compares equal to an OID parsed directly.
+        //
+        // Won't overflow due to `ber.length > 1` checks
+        #[allow(clippy::arithmetic_side_effects)]
+        loop {
+            ber.length -= 1;
+            ber.bytes[ber.length as usize] = 0;
+
+            if ber.length == 1 || ber.bytes[ber.length as usize - 1] & 0b10000000 == 0 {
+                break;
+            }
         }
 
-        encoder.finish().ok()
+        Some(Self { ber })
+    }
+
+    /// Iterate over the parent OIDs of this one, from its direct parent up to
+    /// the OID comprising only the root arcs.
+    ///
+    /// This can be used to look up the closest registered arc of an OID
+    /// without allocating:
+    ///
+    /// ```
+    /// use const_oid::ObjectIdentifier;
+    ///
+    /// const OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.6.1.4.1.32473.1");
+    ///
+    /// let parents: Vec<_> = OID.parent_arcs().map(|oid| oid.to_string()).collect();
+    /// assert_eq!(
+    ///     parents,
+    ///     ["1.3.6.1.4.1.32473", "1.3.6.1.4.1", "1.3.6.1.4", "1.3.6.1", "1.3.6", "1.3"]
+    /// );
+    /// ```
+    pub const fn parent_arcs(&self) -> ParentArcs<MAX_SIZE> {
+        ParentArcs::new(*self)
     }
 
     /// Push an additional arc onto this OID, returning the child OID.
+    ///
+    /// This can be used to derive OIDs from a base OID at compile time:
+    ///
+    /// ```
+    /// use const_oid::ObjectIdentifier;
+    ///
+    /// const ENTERPRISE: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.6.1.4.1.32473");
+    /// const CHILD: ObjectIdentifier = match ENTERPRISE.push_arc(1) {
+    ///     Ok(oid) => oid,
+    ///     Err(_) => panic!("OID too long"),
+    /// };
+    ///
+    /// assert_eq!(CHILD, ObjectIdentifier::new_unwrap("1.3.6.1.4.1.32473.1"));
+    /// ```
     pub const fn push_arc(self, arc: Arc) -> Result<Self> {
         // TODO(tarcieri): use `?` when stable in `const fn`
         match Encoder::extend(self).arc(arc) {
@@ -166,6 +288,20 @@ impl<const MAX_SIZE: usize> ObjectIdentifier<MAX_SIZE> {
     }
 
     /// Does this OID start with the other OID?
+    ///
+    /// This is the case when this OID is equal to `other`, or is located under
+    /// it in the OID hierarchy, e.g. to match any OID under a private
+    /// enterprise number built with [`arc_prefix!`]:
+    ///
+    /// ```
+    /// use const_oid::{ObjectIdentifier, arc_prefix, oid};
+    ///
+    /// const PEN: u32 = 32473;
+    /// const OUR_ARC: ObjectIdentifier = arc_prefix!(oid!("1.3.6.1.4.1"), PEN);
+    ///
+    /// assert!(oid!("1.3.6.1.4.1.32473.1.2").starts_with(OUR_ARC));
+    /// assert!(!oid!("1.3.6.1.4.1.324731").starts_with(OUR_ARC));
+    /// ```
     pub const fn starts_with<const SIZE: usize>(&self, other: ObjectIdentifier<SIZE>) -> bool {
         let len = other.as_bytes().len();
 
@@ -187,6 +323,42 @@ impl<const MAX_SIZE: usize> ObjectIdentifier<MAX_SIZE> {
 
         true
     }
+
+    /// Get the arcs of this OID following the given base OID, as a
+    /// [`RelativeObjectIdentifier`].
+    ///
+    /// Returns `None` if this OID doesn't start with `base`, or has no arcs
+    /// following it.
+    ///
+    /// ```
+    /// use const_oid::{ObjectIdentifier, RelativeObjectIdentifier};
+    ///
+    /// const BASE: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.6.1.4.1");
+    /// const OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.6.1.4.1.32473.1");
+    ///
+    /// assert_eq!(
+    ///     OID.relative_to(BASE),
+    ///     Some(RelativeObjectIdentifier::new_unwrap("32473.1"))
+    /// );
+    /// assert_eq!(BASE.relative_to(OID), None);
+    /// ```
+    pub const fn relative_to<const SIZE: usize>(
+        &self,
+        base: ObjectIdentifier<SIZE>,
+    ) -> Option<RelativeObjectIdentifier<MAX_SIZE>> {
+        // The last byte of an arc has its high bit clear, so the bytes of
+        // `base` always end on an arc boundary of this OID
+        if !self.starts_with(base) {
+            return None;
+        }
+
+        let (_, relative) = self.as_bytes().split_at(base.as_bytes().len());
+
+        match Buffer::concat(relative, &[]) {
+            Ok(ber) if ber.len() > 0 => Some(RelativeObjectIdentifier { ber }),
+            _ => None,
+        }
+    }
 }
 
 impl<const MAX_SIZE: usize> AsRef<[u8]> for ObjectIdentifier<MAX_SIZE> {
@@ -235,21 +407,7 @@ impl<const MAX_SIZE: usize> TryFrom<&ObjectIdentifierRef> for ObjectIdentifier<M
     type Error = Error;
 
     fn try_from(oid_ref: &ObjectIdentifierRef) -> Result<Self> {
-        let len = oid_ref.as_bytes().len();
-
-        if len > MAX_SIZE {
-            return Err(Error::Length);
-        }
-
-        let mut bytes = [0u8; MAX_SIZE];
-        bytes[..len].copy_from_slice(oid_ref.as_bytes());
-
-        let ber = Buffer {
-            bytes,
-            length: len as u8,
-        };
-
-        Ok(Self { ber })
+        Self::from_oid_ref(oid_ref)
     }
 }
 
@@ -302,12 +460,18 @@ pub struct ObjectIdentifierRef {
 impl ObjectIdentifierRef {
     /// Create an [`ObjectIdentifierRef`], validating that the provided byte slice contains a valid
     /// BER/DER encoding.
-    // TODO(tarcieri): `const fn` support
-    pub fn from_bytes(ber: &[u8]) -> Result<&Self> {
+    pub const fn from_bytes(ber: &[u8]) -> Result<&Self> {
         // Ensure arcs are well-formed
         let mut arcs = Arcs::new(ber);
-        while arcs.try_next()?.is_some() {}
-        Ok(Self::from_bytes_unchecked(ber))
+
+        // TODO(tarcieri): use `?` when stable in `const fn`
+        loop {
+            match arcs.try_next() {
+                Ok(Some(_)) => (),
+                Ok(None) => return Ok(Self::from_bytes_unchecked(ber)),
+                Err(err) => return Err(err),
+            }
+        }
     }
 
     /// Create an [`ObjectIdentifierRef`] from the given byte slice without first checking that it
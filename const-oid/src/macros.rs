@@ -0,0 +1,69 @@
+//! Macros.
+
+/// Parse an [`ObjectIdentifier`][crate::ObjectIdentifier] from its
+/// dot-delimited string form at compile time.
+///
+/// The OID is always evaluated in a `const` context, including when the macro
+/// is used in an expression which is otherwise evaluated at runtime, so an
+/// invalid OID is a build error rather than a runtime panic:
+///
+/// ```
+/// use const_oid::{ObjectIdentifier, oid};
+///
+/// const SHA256_WITH_RSA: ObjectIdentifier = oid!("1.2.840.113549.1.1.11");
+///
+/// let oid = oid!("1.2.840.113549.1.1.11");
+/// assert_eq!(oid, SHA256_WITH_RSA);
+/// ```
+///
+/// ```compile_fail
+/// // The second arc of an OID with a root arc of `1` must be within 0-39
+/// let oid = const_oid::oid!("1.40.1");
+/// ```
+#[macro_export]
+macro_rules! oid {
+    ($oid:expr) => {
+        const { $crate::ObjectIdentifier::new_unwrap($oid) }
+    };
+}
+
+/// Build an [`ObjectIdentifier`][crate::ObjectIdentifier] prefix by pushing
+/// arcs onto a base OID at compile time.
+///
+/// Unlike [`oid!`], the arcs can be constants, e.g. a private enterprise
+/// number, avoiding the need to repeat them in a dot-delimited string. The
+/// result can be matched against with
+/// [`ObjectIdentifier::starts_with`][crate::ObjectIdentifier::starts_with]:
+///
+/// ```
+/// use const_oid::{ObjectIdentifier, arc_prefix, oid};
+///
+/// const ENTERPRISES: ObjectIdentifier = oid!("1.3.6.1.4.1");
+/// const PEN: u32 = 32473;
+/// const POLICIES: ObjectIdentifier = arc_prefix!(ENTERPRISES, PEN, 1);
+///
+/// assert_eq!(POLICIES, oid!("1.3.6.1.4.1.32473.1"));
+/// assert!(oid!("1.3.6.1.4.1.32473.1.5").starts_with(POLICIES));
+/// ```
+///
+/// ```compile_fail
+/// // The resulting OID exceeds the maximum length
+/// let oid = const_oid::arc_prefix!(const_oid::oid!("1.2"), 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12,
+///     13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35,
+///     36, 37, 38, 39, 40);
+/// ```
+#[macro_export]
+macro_rules! arc_prefix {
+    ($base:expr $(, $arc:expr)+ $(,)?) => {
+        const {
+            let oid: $crate::ObjectIdentifier = $base;
+            $(
+                let oid = match oid.push_arc($arc) {
+                    Ok(oid) => oid,
+                    Err(_) => panic!("OID too long"),
+                };
+            )+
+            oid
+        }
+    };
+}
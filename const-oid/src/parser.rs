@@ -1,6 +1,8 @@
 //! OID string parser with `const` support.
 
-use crate::{Arc, Error, ObjectIdentifier, Result, encoder::Encoder};
+use crate::{
+    Arc, Error, ObjectIdentifier, ParseError, RelativeObjectIdentifier, Result, encoder::Encoder,
+};
 
 /// Const-friendly OID string parser.
 ///
@@ -12,24 +14,60 @@ pub(crate) struct Parser {
 
     /// BER/DER encoder
     encoder: Encoder<{ ObjectIdentifier::MAX_SIZE }>,
+
+    /// Position of the next byte of input
+    position: usize,
+
+    /// Position of the first byte of the current arc
+    arc_start: usize,
+
+    /// Index of the current arc
+    arc_index: usize,
 }
 
 impl Parser {
     /// Parse an OID from a dot-delimited string e.g. `1.2.840.113549.1.1.1`
     pub(crate) const fn parse(s: &str) -> Result<Self> {
+        match Self::parse_with(s, Encoder::new()) {
+            Ok(parser) => Ok(parser),
+            Err(err) => Err(err.error()),
+        }
+    }
+
+    /// Parse an OID like [`Parser::parse`], reporting the location of errors
+    pub(crate) const fn parse_detailed(s: &str) -> core::result::Result<Self, ParseError> {
+        Self::parse_with(s, Encoder::new())
+    }
+
+    /// Parse a relative OID from a dot-delimited string e.g. `8571.3.2`
+    pub(crate) const fn parse_relative(s: &str) -> Result<Self> {
+        match Self::parse_with(s, Encoder::new_relative()) {
+            Ok(parser) => Ok(parser),
+            Err(err) => Err(err.error()),
+        }
+    }
+
+    /// Parse a dot-delimited string using the given encoder.
+    const fn parse_with(
+        s: &str,
+        encoder: Encoder<{ ObjectIdentifier::MAX_SIZE }>,
+    ) -> core::result::Result<Self, ParseError> {
         let bytes = s.as_bytes();
+        let parser = Self {
+            current_arc: None,
+            encoder,
+            position: 0,
+            arc_start: 0,
+            arc_index: 0,
+        };
 
         if bytes.is_empty() {
-            return Err(Error::Empty);
+            return Err(parser.error(Error::Empty));
         }
 
         match bytes[0] {
-            b'0'..=b'9' => Self {
-                current_arc: None,
-                encoder: Encoder::new(),
-            }
-            .parse_bytes(bytes),
-            actual => Err(Error::DigitExpected { actual }),
+            b'0'..=b'9' => parser.parse_bytes(bytes),
+            actual => Err(parser.error(Error::DigitExpected { actual })),
         }
     }
 
@@ -38,8 +76,30 @@ impl Parser {
         self.encoder.finish()
     }
 
+    /// Finish parsing like [`Parser::finish`], reporting the location of errors
+    pub(crate) const fn finish_detailed(
+        self,
+    ) -> core::result::Result<ObjectIdentifier, ParseError> {
+        let (position, arc_index) = (self.position, self.arc_index);
+
+        match self.encoder.finish() {
+            Ok(oid) => Ok(oid),
+            Err(error) => Err(ParseError::new(error, position, arc_index)),
+        }
+    }
+
+    /// Finish parsing a relative OID, returning the result
+    pub(crate) const fn finish_relative(self) -> Result<RelativeObjectIdentifier> {
+        self.encoder.finish_relative()
+    }
+
+    /// Locate an error at the current position
+    const fn error(&self, error: Error) -> ParseError {
+        ParseError::new(error, self.position, self.arc_index)
+    }
+
     /// Parse the remaining bytes
-    const fn parse_bytes(mut self, bytes: &[u8]) -> Result<Self> {
+    const fn parse_bytes(mut self, bytes: &[u8]) -> core::result::Result<Self, ParseError> {
         match bytes {
             // TODO(tarcieri): use `?` when stable in `const fn`
             [] => match self.current_arc {
@@ -48,9 +108,9 @@ impl Parser {
                         self.encoder = encoder;
                         Ok(self)
                     }
-                    Err(err) => Err(err),
+                    Err(err) => Err(ParseError::new(err, self.arc_start, self.arc_index)),
                 },
-                None => Err(Error::TrailingDot),
+                None => Err(self.error(Error::TrailingDot)),
             },
             [byte @ b'0'..=b'9', remaining @ ..] => {
                 let digit = byte.saturating_sub(b'0');
@@ -62,34 +122,39 @@ impl Parser {
                 // TODO(tarcieri): use `and_then` when const traits are stable
                 self.current_arc = match arc.checked_mul(10) {
                     Some(arc) => match arc.checked_add(digit as Arc) {
-                        None => return Err(Error::ArcTooBig),
+                        None => return Err(self.error(Error::ArcTooBig)),
                         Some(arc) => Some(arc),
                     },
-                    None => return Err(Error::ArcTooBig),
+                    None => return Err(self.error(Error::ArcTooBig)),
                 };
+                self.position = self.position.saturating_add(1);
                 self.parse_bytes(remaining)
             }
             [b'.', remaining @ ..] => {
                 match self.current_arc {
                     Some(arc) => {
                         if remaining.is_empty() {
-                            return Err(Error::TrailingDot);
+                            return Err(self.error(Error::TrailingDot));
                         }
 
                         // TODO(tarcieri): use `?` when stable in `const fn`
+                        // Errors rejecting the value of an arc are located at its start
                         match self.encoder.arc(arc) {
                             Ok(encoder) => {
                                 self.encoder = encoder;
                                 self.current_arc = None;
+                                self.position = self.position.saturating_add(1);
+                                self.arc_start = self.position;
+                                self.arc_index = self.arc_index.saturating_add(1);
                                 self.parse_bytes(remaining)
                             }
-                            Err(err) => Err(err),
+                            Err(err) => Err(ParseError::new(err, self.arc_start, self.arc_index)),
                         }
                     }
-                    None => Err(Error::RepeatedDot),
+                    None => Err(self.error(Error::RepeatedDot)),
                 }
             }
-            [byte, ..] => Err(Error::DigitExpected { actual: *byte }),
+            [byte, ..] => Err(self.error(Error::DigitExpected { actual: *byte })),
         }
     }
 }
@@ -106,6 +171,15 @@ mod tests {
         assert_eq!(oid, "1.23.456".parse().unwrap());
     }
 
+    #[test]
+    fn parse_relative() {
+        let oid = Parser::parse_relative("8571.3.2")
+            .unwrap()
+            .finish_relative()
+            .unwrap();
+        assert_eq!(oid.as_bytes(), &[0xC2, 0x7B, 0x03, 0x02]);
+    }
+
     #[test]
     fn reject_empty_string() {
         assert_eq!(Parser::parse("").err().unwrap(), Error::Empty);
@@ -128,4 +202,24 @@ mod tests {
     fn reject_trailing_dot() {
         assert_eq!(Parser::parse("1.23.").err().unwrap(), Error::TrailingDot);
     }
+
+    #[test]
+    fn error_location() {
+        let location = |s| {
+            let err = Parser::parse_detailed(s).err().unwrap();
+            (err.error(), err.position(), err.arc_index())
+        };
+
+        assert_eq!(location(""), (Error::Empty, 0, 0));
+        assert_eq!(location("X"), (Error::DigitExpected { actual: b'X' }, 0, 0));
+        assert_eq!(
+            location("1.2.3X"),
+            (Error::DigitExpected { actual: b'X' }, 5, 2)
+        );
+        assert_eq!(location("1.2..3"), (Error::RepeatedDot, 4, 2));
+        assert_eq!(location("1.23."), (Error::TrailingDot, 4, 1));
+        assert_eq!(location("1.2.99999999999"), (Error::ArcTooBig, 13, 2));
+        assert_eq!(location("1.40.1"), (Error::ArcInvalid { arc: 40 }, 2, 1));
+        assert_eq!(location("3.1"), (Error::ArcInvalid { arc: 3 }, 0, 0));
+    }
 }
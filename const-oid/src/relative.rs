@@ -0,0 +1,213 @@
+//! Relative object identifiers, i.e. ASN.1 `RELATIVE-OID`.
+
+use crate::{
+    Arc, Arcs, Buffer, DEFAULT_MAX_SIZE, Error, ObjectIdentifier, Result, encoder::Encoder,
+    parser::Parser,
+};
+use core::{fmt, str::FromStr};
+
+/// Relative object identifier (`RELATIVE-OID`).
+///
+/// Relative OIDs identify an object relative to a base OID known from the
+/// context, e.g. `32473.1` relative to `1.3.6.1.4.1`, and are encoded with
+/// BER/DER tag 13. Unlike [`ObjectIdentifier`] they have no root arcs, so any
+/// non-empty sequence of arcs is valid.
+///
+/// ```
+/// use const_oid::{ObjectIdentifier, RelativeObjectIdentifier};
+///
+/// const BASE: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.6.1.4.1");
+/// const RELATIVE: RelativeObjectIdentifier = RelativeObjectIdentifier::new_unwrap("32473.1");
+///
+/// let oid = RELATIVE.to_absolute(BASE)?;
+/// assert_eq!(oid, ObjectIdentifier::new_unwrap("1.3.6.1.4.1.32473.1"));
+/// assert_eq!(oid.relative_to(BASE), Some(RELATIVE));
+/// # Ok::<(), const_oid::Error>(())
+/// ```
+///
+/// # Validity
+///
+/// In order for a relative OID to be considered valid by this library, it
+/// must have at least one arc, and its BER/DER encoding must be shorter than
+/// [`RelativeObjectIdentifier::MAX_SIZE`].
+#[derive(Clone, Copy, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub struct RelativeObjectIdentifier<const MAX_SIZE: usize = DEFAULT_MAX_SIZE> {
+    /// Buffer containing BER/DER-serialized bytes (sans ASN.1 tag/length)
+    pub(crate) ber: Buffer<MAX_SIZE>,
+}
+
+impl RelativeObjectIdentifier {
+    /// Maximum size of a BER/DER-encoded relative OID in bytes.
+    pub const MAX_SIZE: usize = DEFAULT_MAX_SIZE;
+
+    /// Parse a [`RelativeObjectIdentifier`] from the dot-delimited string
+    /// form, panicking on parse errors.
+    ///
+    /// Use [`RelativeObjectIdentifier::new`] for fallible parsing.
+    // TODO(tarcieri): remove this when `Result::unwrap` is `const fn`
+    pub const fn new_unwrap(s: &str) -> Self {
+        match Self::new(s) {
+            Ok(oid) => oid,
+            Err(err) => err.panic(),
+        }
+    }
+
+    /// Parse a [`RelativeObjectIdentifier`] from the dot-delimited string
+    /// form, e.g. `8571.3.2`.
+    pub const fn new(s: &str) -> Result<Self> {
+        // TODO(tarcieri): use `?` when stable in `const fn`
+        match Parser::parse_relative(s) {
+            Ok(parser) => parser.finish_relative(),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Parse a relative OID from a slice of [`Arc`] values (i.e. integers).
+    pub fn from_arcs(arcs: impl IntoIterator<Item = Arc>) -> Result<Self> {
+        let mut encoder = Encoder::new_relative();
+
+        for arc in arcs {
+            encoder = encoder.arc(arc)?;
+        }
+
+        encoder.finish_relative()
+    }
+
+    /// Parse a relative OID from its BER/DER encoding.
+    pub const fn from_bytes(ber_bytes: &[u8]) -> Result<Self> {
+        Self::from_bytes_sized(ber_bytes)
+    }
+}
+
+impl<const MAX_SIZE: usize> RelativeObjectIdentifier<MAX_SIZE> {
+    /// Parse a relative OID from its BER/DER encoding.
+    ///
+    /// Returns `Err(Error::Length)` if bytes do not fit in `MAX_SIZE`.
+    pub const fn from_bytes_sized(ber_bytes: &[u8]) -> Result<Self> {
+        if ber_bytes.is_empty() {
+            return Err(Error::Empty);
+        }
+
+        // Ensure arcs are well-formed
+        let mut arcs = Arcs::new_relative(ber_bytes);
+
+        // TODO(tarcieri): use `?` when stable in `const fn`
+        loop {
+            match arcs.try_next() {
+                Ok(Some(_)) => (),
+                Ok(None) => break,
+                Err(err) => return Err(err),
+            }
+        }
+
+        match Buffer::concat(ber_bytes, &[]) {
+            Ok(ber) => Ok(Self { ber }),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Get the BER/DER serialization of this relative OID as bytes.
+    ///
+    /// Note that this encoding omits the ASN.1 tag/length, and only contains
+    /// the value portion of the encoded relative OID.
+    pub const fn as_bytes(&self) -> &[u8] {
+        self.ber.as_bytes()
+    }
+
+    /// Return the arc with the given index, if it exists.
+    pub fn arc(&self, index: usize) -> Option<Arc> {
+        self.arcs().nth(index)
+    }
+
+    /// Iterate over the arcs of this relative OID.
+    pub fn arcs(&self) -> Arcs<'_> {
+        Arcs::new_relative(self.as_bytes())
+    }
+
+    /// Get the length of this relative OID in arcs.
+    pub fn len(&self) -> usize {
+        self.arcs().count()
+    }
+
+    /// Resolve this relative OID against the given base OID, i.e. append its
+    /// arcs to the arcs of `base`.
+    ///
+    /// Returns `Err(Error::Length)` if the result does not fit in `SIZE`.
+    pub const fn to_absolute<const SIZE: usize>(
+        &self,
+        base: ObjectIdentifier<SIZE>,
+    ) -> Result<ObjectIdentifier<SIZE>> {
+        // TODO(tarcieri): use `?` when stable in `const fn`
+        match Buffer::concat(base.as_bytes(), self.as_bytes()) {
+            Ok(ber) => Ok(ObjectIdentifier { ber }),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+impl<const MAX_SIZE: usize> AsRef<[u8]> for RelativeObjectIdentifier<MAX_SIZE> {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl FromStr for RelativeObjectIdentifier {
+    type Err = Error;
+
+    fn from_str(string: &str) -> Result<Self> {
+        Self::new(string)
+    }
+}
+
+impl TryFrom<&[u8]> for RelativeObjectIdentifier {
+    type Error = Error;
+
+    fn try_from(ber_bytes: &[u8]) -> Result<Self> {
+        Self::from_bytes(ber_bytes)
+    }
+}
+
+impl<const MAX_SIZE: usize> fmt::Debug for RelativeObjectIdentifier<MAX_SIZE> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "RelativeObjectIdentifier({self})")
+    }
+}
+
+impl<const MAX_SIZE: usize> fmt::Display for RelativeObjectIdentifier<MAX_SIZE> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, arc) in self.arcs().enumerate() {
+            if i > 0 {
+                write!(f, ".")?;
+            }
+
+            write!(f, "{arc}")?;
+        }
+
+        Ok(())
+    }
+}
+
+// Implement by hand because the derive would create invalid values.
+// Use the encoder to create a valid relative OID with at least 1 arc.
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for RelativeObjectIdentifier {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let mut encoder = Encoder::new_relative()
+            .arc(u.arbitrary()?)
+            .map_err(|_| arbitrary::Error::IncorrectFormat)?;
+
+        for arc in u.arbitrary_iter()? {
+            encoder = encoder
+                .arc(arc?)
+                .map_err(|_| arbitrary::Error::IncorrectFormat)?;
+        }
+
+        encoder
+            .finish_relative()
+            .map_err(|_| arbitrary::Error::IncorrectFormat)
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        (Arc::size_hint(depth).0, None)
+    }
+}
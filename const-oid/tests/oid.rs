@@ -259,6 +259,37 @@ fn parse_invalid_repeat_dots() {
     assert_eq!(ObjectIdentifier::new("1.2..3.4"), Err(Error::RepeatedDot))
 }
 
+#[test]
+fn try_parse_dotted() {
+    assert_eq!(
+        ObjectIdentifier::try_parse_dotted(EXAMPLE_OID_1_STR),
+        Ok(EXAMPLE_OID_1)
+    );
+
+    let err = ObjectIdentifier::try_parse_dotted("1.2.840..3").unwrap_err();
+    assert_eq!(err.error(), Error::RepeatedDot);
+    assert_eq!((err.position(), err.arc_index()), (8, 3));
+    assert_eq!(
+        err.to_string(),
+        "repeated consecutive '..' characters in OID (at position 8, arc 3)"
+    );
+
+    // Overflowing arcs are located at the first digit which doesn't fit
+    let err = ObjectIdentifier::try_parse_dotted("1.2.840.99999999999").unwrap_err();
+    assert_eq!(err.error(), Error::ArcTooBig);
+    assert_eq!((err.position(), err.arc_index()), (17, 3));
+
+    // Invalid arc values are located at the start of the arc
+    let err = ObjectIdentifier::try_parse_dotted("2.41.1").unwrap_err();
+    assert_eq!(err.error(), Error::ArcInvalid { arc: 41 });
+    assert_eq!((err.position(), err.arc_index()), (2, 1));
+
+    // Errors about the whole input are located at its end
+    let err = ObjectIdentifier::try_parse_dotted("1").unwrap_err();
+    assert_eq!(Error::from(err), ObjectIdentifier::new("1").unwrap_err());
+    assert_eq!((err.position(), err.arc_index()), (1, 0));
+}
+
 #[test]
 fn parent() {
     let child = oid("1.2.3.4");
@@ -270,6 +301,43 @@ fn parent() {
     assert_eq!(parent.parent(), None);
 }
 
+#[test]
+fn parent_multibyte_arc() {
+    let child = oid("1.2.840.113549");
+    assert_eq!(child.parent().unwrap(), oid("1.2.840"));
+    assert_eq!(child.parent().unwrap().parent().unwrap(), oid("1.2"));
+}
+
+#[test]
+fn parent_arcs() {
+    let child = oid("1.2.840.113549.1");
+    let parents: Vec<_> = child.parent_arcs().collect();
+    assert_eq!(parents, [oid("1.2.840.113549"), oid("1.2.840"), oid("1.2")]);
+
+    assert_eq!(oid("1.2").parent_arcs().next(), None);
+    assert!(child.parent_arcs().all(|parent| child.starts_with(parent)));
+}
+
+#[test]
+fn const_navigation() {
+    const BASE: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.6.1.4.1.32473");
+    const CHILD: ObjectIdentifier = match BASE.push_arc(100_000) {
+        Ok(oid) => oid,
+        Err(_) => panic!("push_arc failed"),
+    };
+    const PARENT: Option<ObjectIdentifier> = CHILD.parent();
+
+    assert_eq!(CHILD, oid("1.3.6.1.4.1.32473.100000"));
+    assert_eq!(PARENT, Some(BASE));
+}
+
+#[test]
+fn oid_macro() {
+    const SHA256_WITH_RSA: ObjectIdentifier = const_oid::oid!("1.2.840.113549.1.1.11");
+    assert_eq!(SHA256_WITH_RSA, oid("1.2.840.113549.1.1.11"));
+    assert_eq!(const_oid::oid!(EXAMPLE_OID_2_STR), EXAMPLE_OID_2);
+}
+
 #[test]
 fn push_arc() {
     let parent = oid("1.2.3");
@@ -287,3 +355,21 @@ fn starts_with() {
     assert!(!child.starts_with(oid("2.2.3")));
     assert!(!child.starts_with(oid("1.2.3.4.5.6")));
 }
+
+#[test]
+fn arc_prefix_macro() {
+    const PEN: u32 = 32473;
+    const PREFIX: ObjectIdentifier = const_oid::arc_prefix!(const_oid::oid!("1.3.6.1.4.1"), PEN);
+
+    assert_eq!(PREFIX, oid("1.3.6.1.4.1.32473"));
+    assert!(oid("1.3.6.1.4.1.32473").starts_with(PREFIX));
+    assert!(oid("1.3.6.1.4.1.32473.1.2").starts_with(PREFIX));
+
+    // Arcs sharing leading digits aren't under the prefix
+    assert!(!oid("1.3.6.1.4.1.324731").starts_with(PREFIX));
+    assert!(!oid("1.3.6.1.4.1.3247").starts_with(PREFIX));
+    assert!(!oid("1.3.6.1.4.1").starts_with(PREFIX));
+
+    let nested = const_oid::arc_prefix!(PREFIX, 1, 2,);
+    assert_eq!(nested, oid("1.3.6.1.4.1.32473.1.2"));
+}
@@ -0,0 +1,122 @@
+//! Tests for `RelativeObjectIdentifier`.
+
+use const_oid::{Error, ObjectIdentifier, RelativeObjectIdentifier};
+use hex_literal::hex;
+use std::string::ToString;
+
+/// Example relative OID with a multi-byte arc.
+const EXAMPLE_REL_OID_STR: &str = "8571.3.2";
+const EXAMPLE_REL_OID_BER: &[u8] = &hex!("C27B0302");
+const EXAMPLE_REL_OID: RelativeObjectIdentifier =
+    RelativeObjectIdentifier::new_unwrap(EXAMPLE_REL_OID_STR);
+
+/// Example base OID.
+const EXAMPLE_BASE: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045");
+
+#[test]
+fn from_bytes() {
+    let oid = RelativeObjectIdentifier::from_bytes(EXAMPLE_REL_OID_BER).unwrap();
+    assert_eq!(oid, EXAMPLE_REL_OID);
+    assert_eq!(oid.arc(0).unwrap(), 8571);
+    assert_eq!(oid.arc(1).unwrap(), 3);
+    assert_eq!(oid.arc(2).unwrap(), 2);
+    assert_eq!(oid.arc(3), None);
+    assert_eq!(oid.len(), 3);
+}
+
+#[test]
+fn from_bytes_single_arc() {
+    // Unlike absolute OIDs, the first byte isn't a pair of root arcs
+    let oid = RelativeObjectIdentifier::from_bytes(&[0x78]).unwrap();
+    assert_eq!(oid.arcs().collect::<Vec<_>>(), [120]);
+}
+
+#[test]
+fn from_bytes_invalid() {
+    assert_eq!(RelativeObjectIdentifier::from_bytes(&[]), Err(Error::Empty));
+    assert_eq!(
+        RelativeObjectIdentifier::from_bytes(&hex!("C27B83")),
+        Err(Error::Base128)
+    );
+    assert_eq!(
+        RelativeObjectIdentifier::<2>::from_bytes_sized(EXAMPLE_REL_OID_BER),
+        Err(Error::Length)
+    );
+}
+
+#[test]
+fn from_str() {
+    let oid = EXAMPLE_REL_OID_STR
+        .parse::<RelativeObjectIdentifier>()
+        .unwrap();
+    assert_eq!(oid.as_bytes(), EXAMPLE_REL_OID_BER);
+    assert_eq!(
+        "0".parse::<RelativeObjectIdentifier>().unwrap().as_bytes(),
+        &[0]
+    );
+
+    // The first arcs aren't constrained like those of absolute OIDs
+    let oid = "300.7".parse::<RelativeObjectIdentifier>().unwrap();
+    assert_eq!(oid.as_bytes(), &hex!("822C07"));
+}
+
+#[test]
+fn parse_invalid() {
+    assert_eq!(
+        "".parse::<RelativeObjectIdentifier>().err().unwrap(),
+        Error::Empty
+    );
+    assert_eq!(
+        "1..2".parse::<RelativeObjectIdentifier>().err().unwrap(),
+        Error::RepeatedDot
+    );
+    assert_eq!(
+        "1.2.".parse::<RelativeObjectIdentifier>().err().unwrap(),
+        Error::TrailingDot
+    );
+    assert_eq!(
+        "1.x".parse::<RelativeObjectIdentifier>().err().unwrap(),
+        Error::DigitExpected { actual: b'x' }
+    );
+}
+
+#[test]
+fn from_arcs() {
+    let oid = RelativeObjectIdentifier::from_arcs([8571, 3, 2]).unwrap();
+    assert_eq!(oid, EXAMPLE_REL_OID);
+    assert_eq!(
+        RelativeObjectIdentifier::from_arcs([]).err().unwrap(),
+        Error::Empty
+    );
+}
+
+#[test]
+fn display() {
+    assert_eq!(EXAMPLE_REL_OID.to_string(), EXAMPLE_REL_OID_STR);
+    assert_eq!(
+        format!("{EXAMPLE_REL_OID:?}"),
+        "RelativeObjectIdentifier(8571.3.2)"
+    );
+}
+
+#[test]
+fn to_absolute() {
+    let oid = EXAMPLE_REL_OID.to_absolute(EXAMPLE_BASE).unwrap();
+    assert_eq!(oid, ObjectIdentifier::new_unwrap("1.2.840.10045.8571.3.2"));
+    assert_eq!(
+        EXAMPLE_REL_OID
+            .to_absolute(ObjectIdentifier::<7>::from_bytes_sized(&hex!("2A8648CE3D")).unwrap()),
+        Err(Error::Length)
+    );
+}
+
+#[test]
+fn relative_to() {
+    let oid = ObjectIdentifier::new_unwrap("1.2.840.10045.8571.3.2");
+    assert_eq!(oid.relative_to(EXAMPLE_BASE), Some(EXAMPLE_REL_OID));
+    assert_eq!(EXAMPLE_BASE.relative_to(EXAMPLE_BASE), None);
+    assert_eq!(
+        oid.relative_to(ObjectIdentifier::new_unwrap("1.2.841")),
+        None
+    );
+}
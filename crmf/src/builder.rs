@@ -0,0 +1,146 @@
+//! Builder for certificate request messages with proof of possession
+
+use alloc::{boxed::Box, vec::Vec};
+
+use der::{
+    Encode,
+    asn1::{BitString, Int},
+};
+use signature::Keypair;
+use spki::{DynSignatureAlgorithmIdentifier, EncodePublicKey, SubjectPublicKeyInfo};
+use x509_cert::{
+    builder::{Builder, Error, Result},
+    ext::{AsExtension, Extensions},
+    name::Name,
+};
+
+use crate::{
+    pop::{PopoSigningKey, ProofOfPossession},
+    request::{CertReqMsg, CertRequest, CertTemplate},
+};
+
+/// Builder for a [`CertReqMsg`] whose proof of possession is a signature by
+/// the key being certified, as described in [RFC 4211 Section 4.1].
+///
+/// The template is populated with the given subject and extensions, and with
+/// the public key of the signer. As the template then contains both the
+/// subject and the public key, the `POPOSigningKey` is computed over the DER
+/// encoding of the `CertRequest`, and `poposkInput` is omitted.
+///
+/// ```
+/// # use p256::{pkcs8::DecodePrivateKey, NistP256, ecdsa::DerSignature};
+/// # const PKCS8_PRIVATE_KEY_DER: &[u8] = include_bytes!("../tests/examples/p256-priv.der");
+/// # fn ecdsa_signer() -> ecdsa::SigningKey<NistP256> {
+/// #     let secret_key = p256::SecretKey::from_pkcs8_der(PKCS8_PRIVATE_KEY_DER).unwrap();
+/// #     ecdsa::SigningKey::from(secret_key)
+/// # }
+/// use crmf::builder::CertReqMsgBuilder;
+/// use der::asn1::Int;
+/// use std::str::FromStr;
+/// use x509_cert::{builder::Builder, name::Name};
+///
+/// let subject = Name::from_str("CN=service.domination.world").unwrap();
+/// let builder = CertReqMsgBuilder::new(Int::new(&[0]).unwrap(), subject);
+///
+/// let signer = ecdsa_signer();
+/// let cert_req_msg = builder.build::<_, DerSignature>(&signer).unwrap();
+/// assert!(cert_req_msg.popo.is_some());
+/// ```
+///
+/// [RFC 4211 Section 4.1]: https://www.rfc-editor.org/rfc/rfc4211#section-4.1
+pub struct CertReqMsgBuilder {
+    cert_req: CertRequest,
+    extensions: Extensions,
+}
+
+impl CertReqMsgBuilder {
+    /// Creates a new builder for a request with the given `certReqId` and
+    /// subject.
+    pub fn new(cert_req_id: Int, subject: Name) -> Self {
+        let cert_template = CertTemplate {
+            version: None,
+            serial_number: None,
+            signature: None,
+            issuer: None,
+            validity: None,
+            subject: Some(subject),
+            subject_public_key_info: None,
+            issuer_unique_id: None,
+            subject_unique_id: None,
+            extensions: None,
+        };
+
+        Self {
+            cert_req: CertRequest {
+                cert_req_id,
+                cert_template,
+                controls: None,
+            },
+            extensions: Vec::new(),
+        }
+    }
+
+    /// Add an extension to the template of this request
+    ///
+    /// Extensions need to implement [`AsExtension`], see
+    /// [`x509_cert::request::RequestBuilder::add_extension`].
+    pub fn add_extension<E: AsExtension>(&mut self, extension: &E) -> Result<()> {
+        let subject = self
+            .cert_req
+            .cert_template
+            .subject
+            .as_ref()
+            .ok_or(Error::MissingAttributes)?;
+
+        let ext = extension.to_extension(subject, &self.extensions)?;
+        self.extensions.push(ext);
+        Ok(())
+    }
+
+    /// Get the template of this request, e.g. to set a requested validity.
+    ///
+    /// The public key and extensions of the template are overwritten when
+    /// the request is built.
+    pub fn cert_template_mut(&mut self) -> &mut CertTemplate {
+        &mut self.cert_req.cert_template
+    }
+}
+
+impl Builder for CertReqMsgBuilder {
+    type Output = CertReqMsg;
+
+    fn finalize<S>(&mut self, signer: &S) -> Result<Vec<u8>>
+    where
+        S: Keypair + DynSignatureAlgorithmIdentifier,
+        S::VerifyingKey: EncodePublicKey,
+    {
+        let template = &mut self.cert_req.cert_template;
+        template.subject_public_key_info =
+            Some(SubjectPublicKeyInfo::from_key(&signer.verifying_key())?);
+        template.extensions = if self.extensions.is_empty() {
+            None
+        } else {
+            Some(self.extensions.clone())
+        };
+
+        self.cert_req.to_der().map_err(Error::from)
+    }
+
+    fn assemble<S>(self, signature: BitString, signer: &S) -> Result<Self::Output>
+    where
+        S: Keypair + DynSignatureAlgorithmIdentifier,
+        S::VerifyingKey: EncodePublicKey,
+    {
+        let popo = PopoSigningKey {
+            poposk_input: None,
+            alg_id: signer.signature_algorithm_identifier()?,
+            signature,
+        };
+
+        Ok(CertReqMsg {
+            cert_req: self.cert_req,
+            popo: Some(ProofOfPossession::Signature(Box::new(popo))),
+            reg_info: None,
+        })
+    }
+}
@@ -18,6 +18,8 @@ extern crate alloc;
 #[cfg(feature = "std")]
 extern crate std;
 
+#[cfg(feature = "builder")]
+pub mod builder;
 pub mod controls;
 pub mod pop;
 pub mod reg_info;
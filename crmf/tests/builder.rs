@@ -0,0 +1,73 @@
+#![cfg(feature = "builder")]
+
+use crmf::{builder::CertReqMsgBuilder, pop::ProofOfPossession, request::CertReqMsg};
+use der::{
+    Decode, Encode,
+    asn1::{Ia5String, Int},
+};
+use ecdsa::signature::Verifier;
+use p256::{
+    NistP256,
+    ecdsa::{DerSignature, SigningKey, VerifyingKey},
+    pkcs8::DecodePrivateKey,
+};
+use std::str::FromStr;
+use x509_cert::{
+    builder::Builder,
+    ext::pkix::{SubjectAltName, name::GeneralName},
+    name::Name,
+};
+
+const PKCS8_PRIVATE_KEY_DER: &[u8] = include_bytes!("examples/p256-priv.der");
+
+fn ecdsa_signer() -> SigningKey {
+    let secret_key = p256::SecretKey::from_pkcs8_der(PKCS8_PRIVATE_KEY_DER).unwrap();
+    ecdsa::SigningKey::<NistP256>::from(secret_key)
+}
+
+#[test]
+fn build_with_signature_pop() {
+    let subject = Name::from_str("CN=service.domination.world").unwrap();
+    let mut builder = CertReqMsgBuilder::new(Int::new(&[7]).unwrap(), subject.clone());
+    builder
+        .add_extension(&SubjectAltName(vec![GeneralName::DnsName(
+            Ia5String::new("service.domination.world").unwrap(),
+        )]))
+        .unwrap();
+
+    let signer = ecdsa_signer();
+    let cert_req_msg = builder.build::<_, DerSignature>(&signer).unwrap();
+
+    // Round trip the message
+    let der = cert_req_msg.to_der().unwrap();
+    let cert_req_msg = CertReqMsg::from_der(&der).unwrap();
+
+    let template = &cert_req_msg.cert_req.cert_template;
+    assert_eq!(template.subject, Some(subject));
+    assert_eq!(template.extensions.as_ref().unwrap().len(), 1);
+    assert_eq!(
+        template.subject_public_key_info,
+        Some(spki::SubjectPublicKeyInfo::from_key(signer.verifying_key()).unwrap())
+    );
+
+    // The signature covers the DER encoding of the `CertRequest`
+    let Some(ProofOfPossession::Signature(popo)) = &cert_req_msg.popo else {
+        panic!("missing signature POP");
+    };
+    assert!(popo.poposk_input.is_none());
+    assert_eq!(popo.alg_id.oid, const_oid::db::rfc5912::ECDSA_WITH_SHA_256);
+
+    let signature = DerSignature::try_from(popo.signature.raw_bytes()).unwrap();
+    let verifying_key = VerifyingKey::from(&signer);
+    verifying_key
+        .verify(&cert_req_msg.cert_req.to_der().unwrap(), &signature)
+        .unwrap();
+}
+
+#[test]
+fn build_without_extensions() {
+    let subject = Name::from_str("CN=example").unwrap();
+    let builder = CertReqMsgBuilder::new(Int::new(&[0]).unwrap(), subject);
+    let cert_req_msg = builder.build::<_, DerSignature>(&ecdsa_signer()).unwrap();
+    assert!(cert_req_msg.cert_req.cert_template.extensions.is_none());
+}
@@ -63,6 +63,30 @@ impl<T, const N: usize> ArrayVec<T, N> {
         self.length.checked_sub(1).and_then(|n| self.get(n))
     }
 
+    /// Remove the consecutive elements which `same` considers equal to the
+    /// element preceding them.
+    pub fn dedup_by(&mut self, mut same: impl FnMut(&T, &T) -> bool) {
+        let mut length = 0usize;
+
+        for i in 0..self.length {
+            let Some(item) = self.elements[i].take() else {
+                continue;
+            };
+
+            let duplicate = match length.checked_sub(1).map(|n| &self.elements[n]) {
+                Some(Some(prev)) => same(prev, &item),
+                _ => false,
+            };
+
+            if !duplicate {
+                self.elements[length] = Some(item);
+                length += 1;
+            }
+        }
+
+        self.length = length;
+    }
+
     /// Extract the inner array.
     pub fn into_array(self) -> [Option<T>; N] {
         self.elements
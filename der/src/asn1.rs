@@ -10,13 +10,18 @@ pub(crate) mod bit_string;
 #[cfg(feature = "alloc")]
 mod bmp_string;
 mod boolean;
+pub mod charset;
 mod choice;
 mod context_specific;
+mod duration;
 mod general_string;
 mod generalized_time;
 mod ia5_string;
 mod integer;
+mod little_endian;
+pub mod matching;
 mod null;
+mod numeric_string;
 mod octet_string;
 #[cfg(feature = "oid")]
 mod oid;
@@ -29,6 +34,7 @@ mod sequence;
 mod sequence_of;
 mod set_of;
 mod teletex_string;
+mod time_of_day;
 mod utc_time;
 mod utf8_string;
 mod videotex_string;
@@ -39,18 +45,22 @@ pub use self::{
     bit_string::{BitStringIter, BitStringRef},
     choice::Choice,
     context_specific::{ContextSpecific, ContextSpecificRef},
+    duration::Duration,
     general_string::GeneralStringRef,
     generalized_time::GeneralizedTime,
     ia5_string::Ia5StringRef,
     integer::{int::IntRef, uint::UintRef},
+    little_endian::{LeU32, LeU64},
     null::Null,
-    octet_string::OctetStringRef,
+    numeric_string::NumericStringRef,
+    octet_string::{OctetStringArray, OctetStringRef},
     printable_string::PrintableStringRef,
     private::{Private, PrivateRef},
     sequence::{Sequence, SequenceRef},
     sequence_of::{SequenceOf, SequenceOfIter},
     set_of::{SetOf, SetOfIter},
     teletex_string::TeletexStringRef,
+    time_of_day::TimeOfDay,
     utc_time::UtcTime,
     utf8_string::Utf8StringRef,
     videotex_string::VideotexStringRef,
@@ -70,4 +80,7 @@ pub use self::{
 };
 
 #[cfg(feature = "oid")]
-pub use const_oid::ObjectIdentifier;
+pub use {
+    self::oid::ObjectIdentifierRef,
+    const_oid::{ObjectIdentifier, RelativeObjectIdentifier},
+};
@@ -175,6 +175,8 @@ mod allocating {
     use super::*;
     use crate::{BytesOwned, encode::encode_value_to_slice, reader::read_value, referenced::*};
     use alloc::boxed::Box;
+    #[cfg(feature = "zeroize")]
+    use zeroize::Zeroize;
 
     /// ASN.1 `ANY`: represents any explicitly tagged ASN.1 value.
     ///
@@ -264,6 +266,13 @@ mod allocating {
         }
     }
 
+    #[cfg(feature = "zeroize")]
+    impl Zeroize for Any {
+        fn zeroize(&mut self) {
+            self.value.zeroize();
+        }
+    }
+
     impl Choice<'_> for Any {
         fn can_decode(_: Tag) -> bool {
             true
@@ -131,6 +131,13 @@ impl<'a> BitStringRef<'a> {
         let bitmask = 1u8 << (7 - (position % 8));
         Some(byte & bitmask != 0)
     }
+
+    /// Iterator over the positions of the bits which are set to 1.
+    pub fn set_bit_positions(self) -> impl Iterator<Item = usize> + 'a {
+        self.bits()
+            .enumerate()
+            .filter_map(|(position, bit)| bit.then_some(position))
+    }
 }
 
 impl_any_conversions!(BitStringRef<'a>, 'a);
@@ -248,6 +255,8 @@ mod allocating {
     use super::*;
     use crate::referenced::*;
     use alloc::vec::Vec;
+    #[cfg(feature = "zeroize")]
+    use zeroize::Zeroize;
 
     /// Owned form of ASN.1 `BIT STRING` type.
     ///
@@ -343,6 +352,98 @@ mod allocating {
         pub fn get(&self, position: usize) -> Option<bool> {
             BitStringRef::from(self).get(position)
         }
+
+        /// Iterator over the positions of the bits which are set to 1.
+        pub fn set_bit_positions(&self) -> impl Iterator<Item = usize> + '_ {
+            BitStringRef::from(self).set_bit_positions()
+        }
+
+        /// Set the bit at the given position to the given value.
+        ///
+        /// If `position` is past the end of this `BIT STRING`, it is extended
+        /// with zero bits so that `position` becomes its final bit.
+        ///
+        /// Returns [`ErrorKind::Overflow`] if the extended `BIT STRING` would be
+        /// too long to be encoded, i.e. if its value, including the initial
+        /// octet giving the number of unused bits, would exceed [`Length::MAX`].
+        pub fn set_bit(&mut self, position: usize, value: bool) -> Result<()> {
+            if position >= self.bit_length {
+                // Check before allocating that the value will fit in a `Length`
+                (Length::try_from(position / 8)? + Length::new(2))?;
+                self.resize(position.checked_add(1).ok_or(ErrorKind::Overflow)?);
+            }
+
+            let byte = self
+                .inner
+                .get_mut(position / 8)
+                .ok_or(ErrorKind::Overflow)?;
+            let bitmask = 1u8 << (7 - (position % 8));
+
+            if value {
+                *byte |= bitmask;
+            } else {
+                *byte &= !bitmask;
+            }
+
+            Ok(())
+        }
+
+        /// Collect the bits of this `BIT STRING` into a vector of booleans.
+        pub fn to_bools(&self) -> Vec<bool> {
+            self.bits().collect()
+        }
+
+        /// Resize this `BIT STRING` to the given length in bits, filling any
+        /// new bits with zeroes.
+        fn resize(&mut self, bit_length: usize) {
+            // Clear any unused bits so they don't become part of the value
+            if let Some(last) = self.inner.last_mut() {
+                *last &= 0xFF << self.unused_bits;
+            }
+
+            let byte_length = bit_length.div_ceil(8);
+            self.inner.resize(byte_length, 0);
+            self.unused_bits = u8::try_from(byte_length * 8 - bit_length)
+                .expect("unused bits should be less than 8");
+            self.bit_length = bit_length;
+        }
+    }
+
+    #[cfg(feature = "zeroize")]
+    impl Zeroize for BitString {
+        fn zeroize(&mut self) {
+            self.inner.zeroize();
+            self.unused_bits = 0;
+            self.bit_length = 0;
+        }
+    }
+
+    impl From<&[bool]> for BitString {
+        fn from(bits: &[bool]) -> BitString {
+            bits.iter().copied().collect()
+        }
+    }
+
+    impl FromIterator<bool> for BitString {
+        fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> BitString {
+            let mut bit_string = BitString {
+                unused_bits: 0,
+                bit_length: 0,
+                inner: Vec::new(),
+            };
+
+            for bit in iter {
+                // Only overflows if the bit length exceeds `usize::MAX`
+                let position = bit_string.bit_length;
+                bit_string.resize(position + 1);
+
+                if bit {
+                    bit_string.inner[position / 8] |= 1u8 << (7 - (position % 8));
+                }
+            }
+
+            bit_string
+        }
     }
 
     impl_any_conversions!(BitString);
@@ -614,6 +715,63 @@ mod tests {
         assert_eq!(bits.next(), None);
     }
 
+    #[test]
+    fn set_bit_positions() {
+        let bs = parse_bitstring(&hex!("066e5dc0")).unwrap();
+        let positions: [usize; 12] = [1, 2, 4, 5, 6, 9, 11, 12, 13, 15, 16, 17];
+        assert!(bs.set_bit_positions().eq(positions));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn bitstring_set_bit() {
+        use super::BitString;
+        use crate::ErrorKind;
+
+        // KeyUsage: digitalSignature (0) + keyCertSign (5)
+        let mut bs = BitString::from_bytes(&[]).unwrap();
+        bs.set_bit(0, true).unwrap();
+        bs.set_bit(5, true).unwrap();
+        assert_eq!(bs.bit_len(), 6);
+        assert_eq!(bs.unused_bits(), 2);
+        assert_eq!(bs.raw_bytes(), &[0b1000_0100]);
+        assert_eq!(bs.get(5), Some(true));
+        assert_eq!(bs.get(6), None);
+
+        bs.set_bit(0, false).unwrap();
+        bs.set_bit(8, true).unwrap();
+        assert_eq!(bs.bit_len(), 9);
+        assert_eq!(bs.unused_bits(), 7);
+        assert_eq!(bs.raw_bytes(), &[0b0000_0100, 0b1000_0000]);
+        assert!(bs.set_bit_positions().eq([5, 8]));
+
+        // The value would be one octet longer than `Length::MAX`, which can't
+        // be reached on 32-bit targets
+        let position = usize::try_from((u64::from(u32::MAX) - 1) * 8).unwrap_or(usize::MAX);
+        assert_eq!(
+            bs.set_bit(position, true).unwrap_err().kind(),
+            ErrorKind::Overflow
+        );
+        assert_eq!(bs.bit_len(), 9);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn bitstring_bools_round_trip() {
+        use super::BitString;
+
+        let bools = [true, false, true, true, false, false, false, false, true];
+        let bs = BitString::from(&bools[..]);
+        assert_eq!(bs.bit_len(), 9);
+        assert_eq!(bs.unused_bits(), 7);
+        assert_eq!(bs.raw_bytes(), &[0b1011_0000, 0b1000_0000]);
+        assert_eq!(bs.to_bools(), bools);
+
+        let empty = BitString::from(&[][..]);
+        assert!(empty.is_empty());
+        assert_eq!(empty.bit_len(), 0);
+    }
+
     #[test]
     fn reject_unused_bits_in_empty_string() {
         assert_eq!(
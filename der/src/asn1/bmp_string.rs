@@ -7,6 +7,9 @@ use crate::{
 use alloc::{boxed::Box, vec::Vec};
 use core::{fmt, str::FromStr};
 
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
 /// ASN.1 `BMPString` type.
 ///
 /// Encodes Basic Multilingual Plane (BMP) subset of Unicode (ISO 10646),
@@ -83,6 +86,13 @@ impl BmpString {
     }
 }
 
+#[cfg(feature = "zeroize")]
+impl Zeroize for BmpString {
+    fn zeroize(&mut self) {
+        self.bytes.zeroize();
+    }
+}
+
 impl AsRef<[u8]> for BmpString {
     fn as_ref(&self) -> &[u8] {
         self.as_bytes()
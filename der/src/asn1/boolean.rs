@@ -1,8 +1,8 @@
 //! ASN.1 `BOOLEAN` support.
 
 use crate::{
-    DecodeValue, EncodeValue, Error, ErrorKind, FixedTag, Header, Length, Reader, Result, Tag,
-    Writer, asn1::AnyRef, ord::OrdIsValueOrd,
+    DecodeValue, EncodeFixed, EncodeValue, Error, ErrorKind, FixedTag, Header, Length, Reader,
+    Result, Tag, Writer, asn1::AnyRef, encode_fixed::max_tlv_len, ord::OrdIsValueOrd,
 };
 
 /// Byte used to encode `true` in ASN.1 DER. From X.690 Section 11.1:
@@ -44,6 +44,10 @@ impl FixedTag for bool {
     const TAG: Tag = Tag::Boolean;
 }
 
+impl EncodeFixed for bool {
+    const MAX_ENCODED_LEN: usize = max_tlv_len(Self::TAG, 1);
+}
+
 impl OrdIsValueOrd for bool {}
 
 impl TryFrom<AnyRef<'_>> for bool {
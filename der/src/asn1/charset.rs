@@ -0,0 +1,128 @@
+//! Character sets of the ASN.1 restricted string types.
+//!
+//! These tables and checks are used when decoding the corresponding string
+//! types, and are also usable on their own, e.g. to pre-validate user input
+//! before picking which string type to encode it as.
+
+use crate::Tag;
+
+/// Build a lookup table of the bytes matching the given pattern.
+macro_rules! table {
+    ($allowed:pat) => {{
+        let mut table = [false; 256];
+        let mut c = 0u8;
+
+        loop {
+            table[c as usize] = matches!(c, $allowed);
+
+            if c == u8::MAX {
+                break table;
+            }
+
+            c += 1;
+        }
+    }};
+}
+
+/// Lookup table of the bytes allowed in a `NumericString`: `0..9` and space.
+pub const NUMERIC_STRING: [bool; 256] = table!(b'0'..=b'9' | b' ');
+
+/// Lookup table of the bytes allowed in a `PrintableString`.
+///
+/// See [`PrintableStringRef`][`crate::asn1::PrintableStringRef`] for the
+/// supported characters.
+pub const PRINTABLE_STRING: [bool; 256] = table!(
+    b'A'..=b'Z'
+        | b'a'..=b'z'
+        | b'0'..=b'9'
+        | b' '
+        | b'\''
+        | b'('
+        | b')'
+        | b'+'
+        | b','
+        | b'-'
+        | b'.'
+        | b'/'
+        | b':'
+        | b'='
+        | b'?'
+);
+
+/// Lookup table of the bytes allowed in an `IA5String`, i.e. 7-bit ASCII.
+pub const IA5_STRING: [bool; 256] = table!(0..=0x7F);
+
+/// Is every byte of `bytes` allowed in a `NumericString`?
+pub const fn is_valid_numeric(bytes: &[u8]) -> bool {
+    all_in(&NUMERIC_STRING, bytes)
+}
+
+/// Is every byte of `bytes` allowed in a `PrintableString`?
+pub const fn is_valid_printable(bytes: &[u8]) -> bool {
+    all_in(&PRINTABLE_STRING, bytes)
+}
+
+/// Is every byte of `bytes` allowed in an `IA5String`?
+pub const fn is_valid_ia5(bytes: &[u8]) -> bool {
+    all_in(&IA5_STRING, bytes)
+}
+
+/// Get the tag of the most restrictive string type which can represent `s`
+/// among `PrintableString` and `UTF8String`.
+///
+/// This is the choice recommended by [RFC 5280 Section 4.1.2.4] for the
+/// `DirectoryString` attribute values of distinguished names.
+///
+/// [RFC 5280 Section 4.1.2.4]: https://datatracker.ietf.org/doc/html/rfc5280#section-4.1.2.4
+pub const fn printable_or_utf8(s: &str) -> Tag {
+    if is_valid_printable(s.as_bytes()) {
+        Tag::PrintableString
+    } else {
+        Tag::Utf8String
+    }
+}
+
+/// Are all `bytes` allowed by the given lookup table?
+const fn all_in(table: &[bool; 256], bytes: &[u8]) -> bool {
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if !table[bytes[i] as usize] {
+            return false;
+        }
+
+        i += 1;
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_valid_ia5, is_valid_numeric, is_valid_printable, printable_or_utf8};
+    use crate::Tag;
+
+    #[test]
+    fn charsets() {
+        assert!(is_valid_numeric(b"0123 456"));
+        assert!(!is_valid_numeric(b"12a"));
+
+        assert!(is_valid_printable(b"Test User 1 (o'Neil) +=?,-./:"));
+        assert!(!is_valid_printable(b"user@example.com"));
+        assert!(!is_valid_printable(b"*"));
+
+        assert!(is_valid_ia5(b"user@example.com"));
+        assert!(!is_valid_ia5("caf\u{e9}".as_bytes()));
+
+        for empty in [is_valid_numeric, is_valid_printable, is_valid_ia5] {
+            assert!(empty(b""));
+        }
+    }
+
+    #[test]
+    fn pick_string_type() {
+        assert_eq!(printable_or_utf8("Example Org"), Tag::PrintableString);
+        assert_eq!(printable_or_utf8("Example & Co"), Tag::Utf8String);
+        assert_eq!(printable_or_utf8("M\u{fc}nchen"), Tag::Utf8String);
+    }
+}
@@ -28,8 +28,8 @@ impl_custom_class_ref!(
 mod tests {
     use super::ContextSpecific;
     use crate::{
-        Decode, Encode, SliceReader, TagMode, TagNumber,
-        asn1::{BitStringRef, ContextSpecificRef, SetOf, Utf8StringRef},
+        Decode, Encode, ErrorKind, Reader, SliceReader, TagMode, TagNumber,
+        asn1::{BitStringRef, ContextSpecificRef, SequenceOf, SetOf, Utf8StringRef},
     };
     use hex_literal::hex;
 
@@ -194,4 +194,138 @@ mod tests {
         assert_eq!(field.value.get(0).cloned(), Some(hello));
         assert_eq!(field.value.get(1).cloned(), Some(world));
     }
+
+    #[test]
+    fn context_specific_implicit_sequence_of() {
+        const EXAMPLE_BYTES: &[u8] = &hex!("A009 020107 020108 020109");
+        let elements = [7u16, 8, 9];
+
+        // Encode from a slice
+        let field = ContextSpecificRef::<[u16]> {
+            value: &elements[..],
+            tag_number: TagNumber(0),
+            tag_mode: TagMode::Implicit,
+        };
+
+        let mut buf = [0u8; 16];
+        assert_eq!(field.encode_to_slice(&mut buf).unwrap(), EXAMPLE_BYTES);
+
+        // Decode to a collection
+        let mut reader = SliceReader::new(EXAMPLE_BYTES).unwrap();
+        let field =
+            ContextSpecific::<SequenceOf<u16, 3>>::decode_implicit(&mut reader, TagNumber(0))
+                .unwrap()
+                .unwrap();
+
+        assert!(field.value.iter().eq(&elements));
+        assert_eq!(field.encode_to_slice(&mut buf).unwrap(), EXAMPLE_BYTES);
+
+        let mut reader = SliceReader::new(EXAMPLE_BYTES).unwrap();
+        let field = ContextSpecific::<[u16; 3]>::decode_implicit(&mut reader, TagNumber(0))
+            .unwrap()
+            .unwrap();
+        assert_eq!(field.value, elements);
+    }
+
+    #[test]
+    fn context_specific_explicit_sequence_of() {
+        let elements = [true, false];
+
+        let field = ContextSpecificRef::<[bool]> {
+            value: &elements[..],
+            tag_number: TagNumber(1),
+            tag_mode: TagMode::Explicit,
+        };
+
+        let mut buf = [0u8; 16];
+        let encoded = field.encode_to_slice(&mut buf).unwrap();
+        assert_eq!(encoded, &hex!("A108 3006 0101FF 010100"));
+
+        let mut reader = SliceReader::new(encoded).unwrap();
+        let field = ContextSpecific::<[bool; 2]>::decode_explicit(&mut reader, TagNumber(1))
+            .unwrap()
+            .unwrap();
+        assert_eq!(field.value, elements);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn context_specific_implicit_sequence_of_vec() {
+        use crate::asn1::SetOfVec;
+        use alloc::vec;
+
+        let mut reader = SliceReader::new(&hex!("A206 020102 020101")).unwrap();
+        let field = ContextSpecific::<vec::Vec<u8>>::decode_implicit(&mut reader, TagNumber(2))
+            .unwrap()
+            .unwrap();
+        assert_eq!(field.value, vec![2, 1]);
+
+        let mut reader = SliceReader::new(&hex!("A206 020101 020102")).unwrap();
+        let field = ContextSpecific::<SetOfVec<u8>>::decode_implicit(&mut reader, TagNumber(2))
+            .unwrap()
+            .unwrap();
+        assert_eq!(field.value.as_slice(), &[1, 2]);
+    }
+
+    #[test]
+    fn reader_context_specific_or() {
+        let mut reader = SliceReader::new(&hex!("A003020107 810109")).unwrap();
+        assert_eq!(
+            reader.context_specific_or(TagNumber(0), TagMode::Explicit, 5u8),
+            Ok(7)
+        );
+        assert_eq!(
+            reader.context_specific_or(TagNumber(0), TagMode::Explicit, 5u8),
+            Ok(5)
+        );
+        assert_eq!(
+            reader.context_specific_or(TagNumber(1), TagMode::Implicit, 5u8),
+            Ok(9)
+        );
+        assert!(reader.is_finished());
+    }
+
+    #[test]
+    fn reader_context_specific_required() {
+        const BYTES: &[u8] = &hex!("A103020107 810109");
+
+        let mut reader = SliceReader::new(BYTES).unwrap();
+        assert_eq!(
+            reader.context_specific_required(TagNumber(1), TagMode::Explicit),
+            Ok(7u8)
+        );
+        assert_eq!(
+            reader.context_specific_required(TagNumber(1), TagMode::Implicit),
+            Ok(9u8)
+        );
+
+        let err = reader
+            .context_specific_required::<u8>(TagNumber(2), TagMode::Implicit)
+            .unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::Incomplete { .. }));
+
+        let mut reader = SliceReader::new(BYTES).unwrap();
+        let err = reader
+            .context_specific_required::<u8>(TagNumber(0), TagMode::Explicit)
+            .unwrap_err();
+        assert_eq!(
+            err.kind(),
+            ErrorKind::TagUnexpected {
+                expected: Some(TagNumber(0).context_specific(true)),
+                actual: TagNumber(1).context_specific(true),
+            }
+        );
+
+        let mut reader = SliceReader::new(&BYTES[5..]).unwrap();
+        let err = reader
+            .context_specific_required::<u8>(TagNumber(0), TagMode::Implicit)
+            .unwrap_err();
+        assert_eq!(
+            err.kind(),
+            ErrorKind::TagUnexpected {
+                expected: Some(TagNumber(0).context_specific(false)),
+                actual: TagNumber(1).context_specific(false),
+            }
+        );
+    }
 }
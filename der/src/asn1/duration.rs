@@ -0,0 +1,456 @@
+//! ASN.1 `DURATION` support.
+
+use crate::{
+    DecodeValue, EncodeValue, Error, ErrorKind, FixedTag, Header, Length, Reader, Result,
+    SliceWriter, Tag, ValueOrd, Writer,
+};
+use core::{
+    cmp::Ordering,
+    fmt::{self, Write},
+    str::FromStr,
+};
+
+/// ASN.1 `DURATION` type.
+///
+/// Durations are ISO 8601 durations with integer components, either in the
+/// `PnYnMnDTnHnMnS` form (with any zero components omitted), or in the `PnW`
+/// form. Fractional components are not supported.
+///
+/// As specified in X.690, the encoding of a `DURATION` is the ISO 8601
+/// representation without its leading `P`, e.g. `P1Y2M3DT4H5M6S` is encoded
+/// as `1Y2M3DT4H5M6S`. Decoding rejects encodings with zero-valued
+/// components or leading zeros, other than `0D` for a zero duration.
+///
+/// ```
+/// use der::{Decode, Encode, asn1::Duration};
+///
+/// let duration: Duration = "P1Y2M3DT4H5M6S".parse()?;
+/// assert_eq!(duration.months(), 2);
+///
+/// let mut buf = [0u8; 32];
+/// let der = duration.encode_to_slice(&mut buf)?;
+/// assert_eq!(&der[3..], b"1Y2M3DT4H5M6S");
+/// assert_eq!(Duration::from_der(der)?, duration);
+/// # Ok::<(), der::Error>(())
+/// ```
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Duration {
+    years: u32,
+    months: u32,
+    weeks: u32,
+    days: u32,
+    hours: u32,
+    minutes: u32,
+    seconds: u32,
+}
+
+impl Duration {
+    /// Maximum length of the encoding of a [`Duration`]: six components of up
+    /// to 10 digits with their designator, and the `T` separator.
+    const MAX_LENGTH: usize = 6 * 11 + 1;
+
+    /// Create a new [`Duration`] in the `PnYnMnDTnHnMnS` form.
+    pub fn new(years: u32, months: u32, days: u32, hours: u32, minutes: u32, seconds: u32) -> Self {
+        Self {
+            years,
+            months,
+            weeks: 0,
+            days,
+            hours,
+            minutes,
+            seconds,
+        }
+    }
+
+    /// Create a new [`Duration`] in the `PnW` form.
+    pub fn from_weeks(weeks: u32) -> Self {
+        Self {
+            weeks,
+            ..Self::default()
+        }
+    }
+
+    /// Get the number of years.
+    pub fn years(&self) -> u32 {
+        self.years
+    }
+
+    /// Get the number of months.
+    pub fn months(&self) -> u32 {
+        self.months
+    }
+
+    /// Get the number of weeks.
+    pub fn weeks(&self) -> u32 {
+        self.weeks
+    }
+
+    /// Get the number of days.
+    pub fn days(&self) -> u32 {
+        self.days
+    }
+
+    /// Get the number of hours.
+    pub fn hours(&self) -> u32 {
+        self.hours
+    }
+
+    /// Get the number of minutes.
+    pub fn minutes(&self) -> u32 {
+        self.minutes
+    }
+
+    /// Get the number of seconds.
+    pub fn seconds(&self) -> u32 {
+        self.seconds
+    }
+
+    /// Parse the ISO 8601 representation of a duration without its leading
+    /// `P`, i.e. the contents octets of a DER-encoded `DURATION`.
+    fn parse(bytes: &[u8]) -> Option<Self> {
+        let mut duration = Self::default();
+        let mut designators: &[u8] = b"YMWD";
+        let mut value: Option<u32> = None;
+        let mut in_time = false;
+        let mut components = 0;
+        let mut time_components = 0;
+
+        for &byte in bytes {
+            if byte.is_ascii_digit() {
+                let digit = u32::from(byte - b'0');
+                value = Some(value.unwrap_or(0).checked_mul(10)?.checked_add(digit)?);
+                continue;
+            }
+
+            if byte == b'T' && !in_time && value.is_none() {
+                in_time = true;
+                designators = b"HMS";
+                continue;
+            }
+
+            // Components must appear in order, and at most once
+            let position = designators.iter().position(|&d| d == byte)?;
+            designators = designators.get(position + 1..)?;
+
+            let component = match (in_time, byte) {
+                (false, b'Y') => &mut duration.years,
+                (false, b'M') => &mut duration.months,
+                (false, b'W') => &mut duration.weeks,
+                (false, b'D') => &mut duration.days,
+                (true, b'H') => &mut duration.hours,
+                (true, b'M') => &mut duration.minutes,
+                (true, b'S') => &mut duration.seconds,
+                _ => return None,
+            };
+
+            *component = value.take()?;
+            components += 1;
+
+            if in_time {
+                time_components += 1;
+            }
+
+            // The `PnW` form can't be combined with other components
+            if byte == b'W' {
+                designators = b"";
+            }
+        }
+
+        let weeks_form = components == 1 && bytes.last() == Some(&b'W');
+
+        if value.is_some()
+            || components == 0
+            || (in_time && time_components == 0)
+            || (duration.weeks != 0 && !weeks_form)
+        {
+            return None;
+        }
+
+        Some(duration)
+    }
+
+    /// Encode the contents octets of this duration into the given buffer.
+    fn encode_contents<'b>(&self, buf: &'b mut [u8; Self::MAX_LENGTH]) -> Result<&'b [u8]> {
+        let mut writer = SliceWriter::new(buf);
+        self.encode_value(&mut writer)?;
+        writer.finish()
+    }
+
+    /// Iterate over the components of this duration, as `(value, designator)`
+    /// pairs, where the `T` separator has no value.
+    fn components(&self) -> impl Iterator<Item = (Option<u32>, u8)> {
+        let date = [
+            (self.years, b'Y'),
+            (self.months, b'M'),
+            (self.weeks, b'W'),
+            (self.days, b'D'),
+        ];
+        let time = [
+            (self.hours, b'H'),
+            (self.minutes, b'M'),
+            (self.seconds, b'S'),
+        ];
+
+        let has_time = time.iter().any(|&(value, _)| value != 0);
+        let is_zero = !has_time && date.iter().all(|&(value, _)| value == 0);
+
+        let non_zero =
+            |(value, designator): (u32, u8)| (value != 0).then_some((Some(value), designator));
+
+        date.into_iter()
+            .filter_map(non_zero)
+            .chain(is_zero.then_some((Some(0), b'D')))
+            .chain(has_time.then_some((None, b'T')))
+            .chain(time.into_iter().filter_map(non_zero))
+    }
+}
+
+impl_any_conversions!(Duration);
+
+impl<'a> DecodeValue<'a> for Duration {
+    type Error = Error;
+
+    fn decode_value<R: Reader<'a>>(reader: &mut R, header: Header) -> Result<Self> {
+        let len = usize::try_from(header.length())?;
+
+        if len > Self::MAX_LENGTH {
+            return Err(reader.error(Self::TAG.value_error()));
+        }
+
+        let mut bytes = [0u8; Self::MAX_LENGTH];
+        let bytes = &mut bytes[..len];
+        reader.read_into(bytes)?;
+
+        let duration = Self::parse(bytes).ok_or_else(|| reader.error(Self::TAG.value_error()))?;
+
+        // Zero-valued components and leading zeros aren't allowed in DER, so
+        // the only valid encoding is the one we'd produce ourselves
+        let mut buf = [0u8; Self::MAX_LENGTH];
+        if duration.encode_contents(&mut buf)? != bytes {
+            return Err(reader.error(Self::TAG.non_canonical_error()));
+        }
+
+        Ok(duration)
+    }
+}
+
+impl EncodeValue for Duration {
+    fn value_len(&self) -> Result<Length> {
+        self.components()
+            .map(|(value, _)| {
+                let digits = value.map_or(0, |n| n.checked_ilog10().unwrap_or(0) + 1);
+                Length::new(digits + 1)
+            })
+            .try_fold(Length::ZERO, |len, component| len + component)
+    }
+
+    fn encode_value(&self, writer: &mut impl Writer) -> Result<()> {
+        let mut digits = [0u8; 10];
+
+        for (value, designator) in self.components() {
+            if let Some(mut n) = value {
+                let mut i = digits.len();
+
+                loop {
+                    i -= 1;
+                    digits[i] = b'0' + u8::try_from(n % 10)?;
+                    n /= 10;
+
+                    if n == 0 {
+                        break;
+                    }
+                }
+
+                writer.write(&digits[i..])?;
+            }
+
+            writer.write_byte(designator)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FixedTag for Duration {
+    const TAG: Tag = Tag::Duration;
+}
+
+impl ValueOrd for Duration {
+    fn value_cmp(&self, other: &Self) -> Result<Ordering> {
+        let mut buf1 = [0u8; Self::MAX_LENGTH];
+        let mut buf2 = [0u8; Self::MAX_LENGTH];
+        Ok(self
+            .encode_contents(&mut buf1)?
+            .cmp(other.encode_contents(&mut buf2)?))
+    }
+}
+
+impl FromStr for Duration {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        s.strip_prefix('P')
+            .and_then(|s| Self::parse(s.as_bytes()))
+            .ok_or_else(|| Self::TAG.value_error().into())
+    }
+}
+
+/// Formats the [`Duration`] in the ISO 8601 representation, e.g. `P1DT12H`.
+impl fmt::Display for Duration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("P")?;
+
+        for (value, designator) in self.components() {
+            if let Some(value) = value {
+                write!(f, "{value}")?;
+            }
+
+            f.write_char(char::from(designator))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Converts durations which don't have years or months, whose length varies.
+impl TryFrom<Duration> for core::time::Duration {
+    type Error = Error;
+
+    fn try_from(duration: Duration) -> Result<Self> {
+        if duration.years != 0 || duration.months != 0 {
+            return Err(ErrorKind::DateTime.into());
+        }
+
+        let days = u64::from(duration.weeks) * 7 + u64::from(duration.days);
+        let hours = days * 24 + u64::from(duration.hours);
+        let minutes = hours * 60 + u64::from(duration.minutes);
+        let seconds = minutes * 60 + u64::from(duration.seconds);
+        Ok(Self::from_secs(seconds))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::Duration;
+    use crate::{Decode, DerOrd, Encode, Tag};
+    use core::cmp::Ordering;
+    use hex_literal::hex;
+
+    #[test]
+    fn round_trip() {
+        let der = hex!("1F 22 0D 31 59 32 4D 33 44 54 34 48 35 4D 36 53");
+        let duration = Duration::from_der(&der).unwrap();
+        assert_eq!(duration, Duration::new(1, 2, 3, 4, 5, 6));
+
+        let mut buf = [0u8; 32];
+        assert_eq!(duration.encode_to_slice(&mut buf).unwrap(), der);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn parse_and_display() {
+        use alloc::string::ToString;
+
+        for s in [
+            "P1Y",
+            "P2M",
+            "P3W",
+            "P10D",
+            "PT1H30M",
+            "P1DT12H",
+            "PT4294967295S",
+            "P0D",
+        ] {
+            assert_eq!(s.parse::<Duration>().unwrap().to_string(), s);
+        }
+
+        assert_eq!("P1M".parse::<Duration>().unwrap().months(), 1);
+        assert_eq!("PT1M".parse::<Duration>().unwrap().minutes(), 1);
+        assert_eq!("P3W".parse::<Duration>().unwrap().weeks(), 3);
+
+        // Zero components are omitted when encoding
+        assert_eq!("P0Y1D".parse::<Duration>().unwrap().to_string(), "P1D");
+        assert_eq!(
+            Duration::default().to_der().unwrap(),
+            hex!("1F 22 02 30 44")
+        );
+    }
+
+    #[test]
+    fn reject_invalid() {
+        for s in [
+            "",
+            "P",
+            "PT",
+            "1D",
+            "P1",
+            "P1D2Y",
+            "P1Y1Y",
+            "P1H",
+            "PT1D",
+            "P1W1D",
+            "P1.5D",
+            "P4294967296D",
+            "P-1D",
+            "P1DT",
+        ] {
+            assert!(s.parse::<Duration>().is_err(), "{s}");
+        }
+
+        let err = Duration::from_der(&hex!("1F 22 02 31 50")).unwrap_err();
+        assert_eq!(err.kind(), Tag::Duration.value_error());
+    }
+
+    #[test]
+    fn reject_noncanonical() {
+        for der in [
+            // P0Y1D
+            &hex!("1F 22 04 30 59 31 44")[..],
+            // P01D
+            &hex!("1F 22 03 30 31 44")[..],
+            // PT0H1M
+            &hex!("1F 22 05 54 30 48 31 4D")[..],
+            // P0W
+            &hex!("1F 22 02 30 57")[..],
+        ] {
+            let err = Duration::from_der(der).unwrap_err();
+            assert_eq!(err.kind(), Tag::Duration.non_canonical_error());
+        }
+
+        // P0D is the canonical encoding of a zero duration
+        assert_eq!(
+            Duration::from_der(&hex!("1F 22 02 30 44")).unwrap(),
+            Duration::default()
+        );
+    }
+
+    #[test]
+    fn der_ord() {
+        let one_day = Duration::new(0, 0, 1, 0, 0, 0);
+        let ten_days = Duration::new(0, 0, 10, 0, 0, 0);
+        let one_year = Duration::new(1, 0, 0, 0, 0, 0);
+
+        // Shorter encodings sort first, then the contents octets are compared
+        assert_eq!(one_day.der_cmp(&ten_days).unwrap(), Ordering::Less);
+        assert_eq!(one_year.der_cmp(&one_day).unwrap(), Ordering::Greater);
+        assert_eq!(one_day.der_cmp(&one_day).unwrap(), Ordering::Equal);
+    }
+
+    #[test]
+    fn to_core_duration() {
+        let duration: Duration = "P1DT1H1M1S".parse().unwrap();
+        assert_eq!(
+            core::time::Duration::try_from(duration).unwrap().as_secs(),
+            90061
+        );
+
+        let weeks = Duration::from_weeks(2);
+        assert_eq!(
+            core::time::Duration::try_from(weeks).unwrap().as_secs(),
+            14 * 86400
+        );
+
+        assert!(core::time::Duration::try_from(Duration::new(0, 1, 0, 0, 0, 0)).is_err());
+    }
+}
@@ -2,9 +2,10 @@
 #![cfg_attr(feature = "arbitrary", allow(clippy::arithmetic_side_effects))]
 
 use crate::{
-    DecodeValue, EncodeValue, Error, ErrorKind, FixedTag, Header, Length, Reader, Result, Tag,
-    Writer,
-    datetime::{self, DateTime},
+    DecodeValue, EncodeFixed, EncodeValue, Error, ErrorKind, FixedTag, Header, Length, Reader,
+    Result, Tag, Writer,
+    datetime::{self, DateTime, OutOfRange},
+    encode_fixed::max_tlv_len,
     ord::OrdIsValueOrd,
 };
 use core::time::Duration;
@@ -52,6 +53,17 @@ impl GeneralizedTime {
             .map_err(|_| Self::TAG.value_error().into())
     }
 
+    /// Create a new [`GeneralizedTime`] given the number of seconds since
+    /// `UNIX_EPOCH`, handling timestamps before 1970 or after 9999 according
+    /// to the given [`OutOfRange`] policy.
+    ///
+    /// See [`DateTime::from_unix_secs`] for more information.
+    pub fn from_unix_secs(secs: i64, out_of_range: OutOfRange) -> Result<Self> {
+        DateTime::from_unix_secs(secs, out_of_range)
+            .map(Into::into)
+            .map_err(|_| Self::TAG.value_error().into())
+    }
+
     /// Get the duration of this timestamp since `UNIX_EPOCH`.
     pub fn to_unix_duration(&self) -> Duration {
         self.0.unix_duration()
@@ -149,6 +161,10 @@ impl FixedTag for GeneralizedTime {
     const TAG: Tag = Tag::GeneralizedTime;
 }
 
+impl EncodeFixed for GeneralizedTime {
+    const MAX_ENCODED_LEN: usize = max_tlv_len(Self::TAG, Self::LENGTH);
+}
+
 impl OrdIsValueOrd for GeneralizedTime {}
 
 impl From<&GeneralizedTime> for GeneralizedTime {
@@ -333,7 +349,7 @@ impl TryFrom<GeneralizedTime> for PrimitiveDateTime {
 #[allow(clippy::unwrap_used)]
 mod tests {
     use super::GeneralizedTime;
-    use crate::{Decode, Encode, SliceWriter};
+    use crate::{DateTime, Decode, Encode, OutOfRange, SliceWriter};
     use hex_literal::hex;
 
     #[test]
@@ -395,4 +411,21 @@ mod tests {
         let example_bytes = "\x18\x0f99991231235960Z".as_bytes();
         assert!(GeneralizedTime::from_der(example_bytes).is_err());
     }
+
+    #[test]
+    fn from_unix_secs() {
+        let time = GeneralizedTime::from_unix_secs(2_524_608_000, OutOfRange::Reject).unwrap();
+        assert_eq!(
+            time.to_date_time(),
+            DateTime::new(2050, 1, 1, 0, 0, 0).unwrap()
+        );
+
+        assert!(GeneralizedTime::from_unix_secs(i64::MAX, OutOfRange::Reject).is_err());
+        assert_eq!(
+            GeneralizedTime::from_unix_secs(i64::MAX, OutOfRange::Clamp)
+                .unwrap()
+                .to_date_time(),
+            DateTime::INFINITY
+        );
+    }
 }
@@ -1,6 +1,6 @@
 //! ASN.1 `IA5String` support.
 
-use crate::{FixedTag, Result, StringRef, Tag, asn1::AnyRef};
+use crate::{FixedTag, Result, StringRef, Tag, asn1::AnyRef, asn1::charset};
 use core::{fmt, ops::Deref};
 
 macro_rules! impl_ia5_string {
@@ -49,7 +49,7 @@ impl<'a> Ia5StringRef<'a> {
         let input = input.as_ref();
 
         // Validate all characters are within IA5String's allowed set
-        if input.iter().any(|&c| c > 0x7F) {
+        if !charset::is_valid_ia5(input) {
             return Err(Self::TAG.value_error().into());
         }
 
@@ -99,6 +99,8 @@ mod allocation {
     };
     use alloc::{borrow::ToOwned, string::String};
     use core::{fmt, ops::Deref};
+    #[cfg(feature = "zeroize")]
+    use zeroize::Zeroize;
 
     /// ASN.1 `IA5String` type.
     ///
@@ -141,6 +143,13 @@ mod allocation {
         }
     }
 
+    #[cfg(feature = "zeroize")]
+    impl Zeroize for Ia5String {
+        fn zeroize(&mut self) {
+            self.inner.zeroize();
+        }
+    }
+
     impl<'a> From<Ia5StringRef<'a>> for Ia5String {
         fn from(ia5_string: Ia5StringRef<'a>) -> Ia5String {
             Self {
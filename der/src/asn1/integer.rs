@@ -3,9 +3,79 @@
 pub(super) mod int;
 pub(super) mod uint;
 
-use core::{cmp::Ordering, mem::size_of};
+use core::{
+    cmp::Ordering,
+    mem::size_of,
+    num::{
+        NonZeroI8, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI128, NonZeroU8, NonZeroU16,
+        NonZeroU32, NonZeroU64, NonZeroU128,
+    },
+};
 
-use crate::{EncodeValue, Result, encode::encode_value_to_slice};
+use crate::{
+    AnyRef, DecodeValue, EncodeFixed, EncodeValue, Error, ErrorKind, FixedTag, Header, Length,
+    Reader, Result, Tag, ValueOrd, Writer, encode::encode_value_to_slice,
+};
+
+macro_rules! impl_non_zero {
+    ($($non_zero:ty => $int:ty),+) => {
+        $(
+            impl<'a> DecodeValue<'a> for $non_zero {
+                type Error = Error;
+
+                fn decode_value<R: Reader<'a>>(reader: &mut R, header: Header) -> Result<Self> {
+                    let value = <$int>::decode_value(reader, header)?;
+                    Self::new(value).ok_or_else(|| reader.error(ErrorKind::Value { tag: Self::TAG }))
+                }
+            }
+
+            impl EncodeValue for $non_zero {
+                fn value_len(&self) -> Result<Length> {
+                    self.get().value_len()
+                }
+
+                fn encode_value(&self, writer: &mut impl Writer) -> Result<()> {
+                    self.get().encode_value(writer)
+                }
+            }
+
+            impl FixedTag for $non_zero {
+                const TAG: Tag = Tag::Integer;
+            }
+
+            impl EncodeFixed for $non_zero {
+                const MAX_ENCODED_LEN: usize = <$int>::MAX_ENCODED_LEN;
+            }
+
+            impl ValueOrd for $non_zero {
+                fn value_cmp(&self, other: &Self) -> Result<Ordering> {
+                    value_cmp(self.get(), other.get())
+                }
+            }
+
+            impl TryFrom<AnyRef<'_>> for $non_zero {
+                type Error = Error;
+
+                fn try_from(any: AnyRef<'_>) -> Result<Self> {
+                    any.decode_as()
+                }
+            }
+        )+
+    };
+}
+
+impl_non_zero!(
+    NonZeroU8 => u8,
+    NonZeroU16 => u16,
+    NonZeroU32 => u32,
+    NonZeroU64 => u64,
+    NonZeroU128 => u128,
+    NonZeroI8 => i8,
+    NonZeroI16 => i16,
+    NonZeroI32 => i32,
+    NonZeroI64 => i64,
+    NonZeroI128 => i128
+);
 
 /// Is the highest bit of the first byte in the slice set to `1`? (if present)
 #[inline]
@@ -171,4 +241,45 @@ pub(crate) mod tests {
         assert!(u8::from_der(&[0x02, 0x02, 0x00, 0x00]).is_err());
         assert!(u16::from_der(&[0x02, 0x02, 0x00, 0x00]).is_err());
     }
+
+    #[test]
+    fn round_trip_128_bit() {
+        let mut buffer = [0u8; 19];
+
+        let encoded = u128::MAX.encode_to_slice(&mut buffer).unwrap();
+        assert_eq!(&encoded[..3], &[0x02, 0x11, 0x00]);
+        assert_eq!(&encoded[3..], &[0xFF; 16]);
+        assert_eq!(u128::MAX, u128::from_der(encoded).unwrap());
+
+        let encoded = i128::MIN.encode_to_slice(&mut buffer).unwrap();
+        assert_eq!(&encoded[..3], &[0x02, 0x10, 0x80]);
+        assert_eq!(&encoded[3..], &[0x00; 15]);
+        assert_eq!(i128::MIN, i128::from_der(encoded).unwrap());
+
+        assert_eq!(255, u128::from_der(I255_BYTES).unwrap());
+        assert_eq!(-129, i128::from_der(INEG129_BYTES).unwrap());
+    }
+
+    #[test]
+    fn non_zero() {
+        use core::num::{NonZeroI16, NonZeroU64, NonZeroU128};
+
+        let mut buffer = [0u8; 19];
+
+        let value = NonZeroU64::new(255).unwrap();
+        assert_eq!(I255_BYTES, value.encode_to_slice(&mut buffer).unwrap());
+        assert_eq!(value, NonZeroU64::from_der(I255_BYTES).unwrap());
+
+        let value = NonZeroI16::new(-129).unwrap();
+        assert_eq!(INEG129_BYTES, value.encode_to_slice(&mut buffer).unwrap());
+        assert_eq!(value, NonZeroI16::from_der(INEG129_BYTES).unwrap());
+
+        let value = NonZeroU128::MAX;
+        let encoded = value.encode_to_slice(&mut buffer).unwrap();
+        assert_eq!(value, NonZeroU128::from_der(encoded).unwrap());
+
+        let err = NonZeroU64::from_der(I0_BYTES).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Value { tag: Tag::Integer });
+        assert!(NonZeroI16::from_der(I0_BYTES).is_err());
+    }
 }
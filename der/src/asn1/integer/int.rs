@@ -2,8 +2,8 @@
 
 use super::{is_highest_bit_set, uint, value_cmp};
 use crate::{
-    AnyRef, BytesRef, DecodeValue, EncodeValue, Error, ErrorKind, FixedTag, Header, Length, Reader,
-    Result, Tag, ValueOrd, Writer, ord::OrdIsValueOrd,
+    AnyRef, BytesRef, DecodeValue, EncodeFixed, EncodeValue, Error, ErrorKind, FixedTag, Header,
+    Length, Reader, Result, Tag, ValueOrd, Writer, encode_fixed::max_tlv_len, ord::OrdIsValueOrd,
 };
 use core::cmp::Ordering;
 
@@ -73,6 +73,10 @@ macro_rules! impl_encoding_traits {
                 const TAG: Tag = Tag::Integer;
             }
 
+            impl EncodeFixed for $int {
+                const MAX_ENCODED_LEN: usize = max_tlv_len(Self::TAG, Self::BITS as usize / 8);
+            }
+
             impl ValueOrd for $int {
                 fn value_cmp(&self, other: &Self) -> Result<Ordering> {
                     value_cmp(*self, *other)
@@ -185,6 +189,8 @@ mod allocating {
         referenced::{OwnedToRef, RefToOwned},
     };
     use alloc::{borrow::ToOwned, vec::Vec};
+    #[cfg(feature = "zeroize")]
+    use zeroize::Zeroize;
 
     /// Signed arbitrary precision ASN.1 `INTEGER` type.
     ///
@@ -225,6 +231,13 @@ mod allocating {
         }
     }
 
+    #[cfg(feature = "zeroize")]
+    impl Zeroize for Int {
+        fn zeroize(&mut self) {
+            self.inner.zeroize();
+        }
+    }
+
     impl_any_conversions!(Int);
 
     impl<'a> DecodeValue<'a> for Int {
@@ -2,8 +2,8 @@
 
 use super::value_cmp;
 use crate::{
-    AnyRef, BytesRef, DecodeValue, EncodeValue, Error, ErrorKind, FixedTag, Header, Length, Reader,
-    Result, Tag, ValueOrd, Writer, ord::OrdIsValueOrd,
+    AnyRef, BytesRef, DecodeValue, EncodeFixed, EncodeValue, Error, ErrorKind, FixedTag, Header,
+    Length, Reader, Result, Tag, ValueOrd, Writer, encode_fixed::max_tlv_len, ord::OrdIsValueOrd,
 };
 use core::cmp::Ordering;
 
@@ -58,6 +58,11 @@ macro_rules! impl_encoding_traits {
                 const TAG: Tag = Tag::Integer;
             }
 
+            impl EncodeFixed for $uint {
+                // Values with the highest bit set are prefixed with a zero byte
+                const MAX_ENCODED_LEN: usize = max_tlv_len(Self::TAG, Self::BITS as usize / 8 + 1);
+            }
+
             impl ValueOrd for $uint {
                 fn value_cmp(&self, other: &Self) -> Result<Ordering> {
                     value_cmp(*self, *other)
@@ -84,6 +89,12 @@ impl_encoding_traits!(u8, u16, u32, u64, u128);
 ///
 /// Intended for use cases like very large integers that are used in
 /// cryptographic applications (e.g. keys, signatures).
+///
+/// To decode directly into a fixed-size or boxed big integer, enable the `der`
+/// feature of [`crypto-bigint`], which impls [`Decode`][`crate::Decode`] and
+/// [`Encode`][`crate::Encode`] for its `Uint<LIMBS>` and `BoxedUint` types.
+///
+/// [`crypto-bigint`]: https://docs.rs/crypto-bigint
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 pub struct UintRef<'a> {
     /// Inner value
@@ -171,6 +182,8 @@ mod allocating {
         referenced::{OwnedToRef, RefToOwned},
     };
     use alloc::borrow::ToOwned;
+    #[cfg(feature = "zeroize")]
+    use zeroize::Zeroize;
 
     /// Unsigned arbitrary precision ASN.1 `INTEGER` type.
     ///
@@ -211,6 +224,13 @@ mod allocating {
         }
     }
 
+    #[cfg(feature = "zeroize")]
+    impl Zeroize for Uint {
+        fn zeroize(&mut self) {
+            self.inner.zeroize();
+        }
+    }
+
     impl_any_conversions!(Uint);
 
     impl<'a> DecodeValue<'a> for Uint {
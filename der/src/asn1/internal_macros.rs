@@ -35,6 +35,15 @@ macro_rules! impl_string_type {
             };
             use core::{fmt, str};
 
+            impl<$($li),*> $type {
+                /// Does this string match `other` according to X.520 `caseIgnoreMatch`?
+                ///
+                /// See [`asn1::matching`][`crate::asn1::matching`] for details.
+                pub fn case_ignore_eq(&self, other: impl AsRef<str>) -> bool {
+                    $crate::asn1::matching::case_ignore_eq(self.as_str(), other.as_ref())
+                }
+            }
+
             impl<$($li),*> AsRef<str> for $type {
                 fn as_ref(&self) -> &str {
                     self.as_str()
@@ -132,6 +141,11 @@ macro_rules! impl_custom_class {
             /// Differences from `EXPLICIT`:
             /// - Returns [`ErrorKind::Noncanonical`] if constructed bit
             ///   does not match constructed bit of the base encoding.
+            ///
+            /// Repeated fields such as `[0] IMPLICIT SEQUENCE OF T` are decoded by
+            /// using a collection as `T`, e.g. [`SequenceOf`][`crate::asn1::SequenceOf`],
+            /// [`SetOf`][`crate::asn1::SetOf`], or `Vec` and `SetOfVec` with the
+            /// `alloc` feature.
             pub fn decode_implicit<'a, R: Reader<'a>>(
                 reader: &mut R,
                 tag_number: TagNumber,
@@ -289,8 +303,12 @@ macro_rules! impl_custom_class_ref {
         ///
         /// This type encodes a field which is specific to a particular context
         /// and is identified by a [`TagNumber`].
+        ///
+        /// The referenced value may be a slice, which is encoded as a `SEQUENCE OF`,
+        /// e.g. to encode `[0] IMPLICIT SEQUENCE OF T` fields without copying their
+        /// elements to a collection.
         #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
-        pub struct $ref_class_type_name<'a, T> {
+        pub struct $ref_class_type_name<'a, T: ?Sized> {
             #[doc = concat!("`", $asn1_class_name, "` tag number sans the leading `", $class_bits_str, "` class")]
             /// identifier bit and `0b100000` constructed flag.
             pub tag_number: TagNumber,
@@ -302,7 +320,7 @@ macro_rules! impl_custom_class_ref {
             pub value: &'a T,
         }
 
-        impl<'a, T> $ref_class_type_name<'a, T> {
+        impl<'a, T: ?Sized> $ref_class_type_name<'a, T> {
             /// Convert to a [`EncodeValue`] object using [`EncodeValueRef`].
             fn encoder(&self) -> $class_type_name<EncodeValueRef<'a, T>> {
                 $class_type_name {
@@ -315,7 +333,7 @@ macro_rules! impl_custom_class_ref {
 
         impl<T> EncodeValue for $ref_class_type_name<'_, T>
         where
-            T: EncodeValue + Tagged,
+            T: EncodeValue + Tagged + ?Sized,
         {
             fn value_len(&self) -> Result<Length, Error> {
                 self.encoder().value_len()
@@ -328,7 +346,7 @@ macro_rules! impl_custom_class_ref {
 
         impl<T> Tagged for $ref_class_type_name<'_, T>
         where
-            T: Tagged,
+            T: Tagged + ?Sized,
         {
             fn tag(&self) -> Tag {
                 self.encoder().tag()
@@ -0,0 +1,142 @@
+//! Little-endian integers embedded in ASN.1 `OCTET STRING`s.
+//!
+//! These aren't ASN.1 types: some proprietary structures (e.g. device
+//! attestation formats) store fixed-size little-endian counters as the
+//! contents of an `OCTET STRING`, which these types encode and decode.
+
+use crate::{
+    DecodeValue, EncodeValue, Error, FixedTag, Header, Length, Reader, Result, Tag, ValueOrd,
+    Writer,
+};
+use core::cmp::Ordering;
+
+macro_rules! impl_little_endian {
+    ($($name:ident($int:ty, $asn1:expr)),+) => {
+        $(
+            #[doc = concat!("`", stringify!($int), "` encoded as the contents of an ASN.1 `OCTET STRING` ")]
+            #[doc = concat!("in little-endian byte order, i.e. as an `OCTET STRING (SIZE (", stringify!($asn1), "))`.")]
+            #[derive(Copy, Clone, Debug, Default, Eq, Hash, PartialEq, PartialOrd, Ord)]
+            pub struct $name(pub $int);
+
+            impl $name {
+                /// Length of the encoded value in bytes.
+                const LEN: usize = size_of::<$int>();
+
+                /// Create a new value.
+                pub const fn new(value: $int) -> Self {
+                    Self(value)
+                }
+
+                /// Get the inner integer value.
+                pub const fn get(self) -> $int {
+                    self.0
+                }
+
+                /// Encode this value as little-endian bytes.
+                pub const fn to_le_bytes(self) -> [u8; size_of::<$int>()] {
+                    self.0.to_le_bytes()
+                }
+            }
+
+            impl_any_conversions!($name);
+
+            impl<'a> DecodeValue<'a> for $name {
+                type Error = Error;
+
+                fn decode_value<R: Reader<'a>>(reader: &mut R, header: Header) -> Result<Self> {
+                    if usize::try_from(header.length())? != Self::LEN {
+                        return Err(reader.error(Self::TAG.length_error()));
+                    }
+
+                    let mut bytes = [0u8; Self::LEN];
+                    reader.read_into(&mut bytes)?;
+                    Ok(Self(<$int>::from_le_bytes(bytes)))
+                }
+            }
+
+            impl EncodeValue for $name {
+                fn value_len(&self) -> Result<Length> {
+                    Length::try_from(Self::LEN)
+                }
+
+                fn encode_value(&self, writer: &mut impl Writer) -> Result<()> {
+                    writer.write(&self.to_le_bytes())
+                }
+            }
+
+            impl FixedTag for $name {
+                const TAG: Tag = Tag::OctetString;
+            }
+
+            impl ValueOrd for $name {
+                fn value_cmp(&self, other: &Self) -> Result<Ordering> {
+                    // DER orders values by their encoding, not numerically
+                    Ok(self.to_le_bytes().cmp(&other.to_le_bytes()))
+                }
+            }
+
+            impl From<$int> for $name {
+                fn from(value: $int) -> Self {
+                    Self(value)
+                }
+            }
+
+            impl From<$name> for $int {
+                fn from(value: $name) -> $int {
+                    value.0
+                }
+            }
+        )+
+    };
+}
+
+impl_little_endian!(LeU32(u32, 4), LeU64(u64, 8));
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::{LeU32, LeU64};
+    use crate::{Decode, DerOrd, Encode, ErrorKind, Tag};
+    use core::cmp::Ordering;
+    use hex_literal::hex;
+
+    #[test]
+    fn round_trip() {
+        let mut buf = [0u8; 16];
+
+        let der = hex!("04 04 78 56 34 12");
+        assert_eq!(LeU32::from_der(&der).unwrap(), LeU32(0x1234_5678));
+        assert_eq!(LeU32(0x1234_5678).encode_to_slice(&mut buf).unwrap(), der);
+
+        let der = hex!("04 08 08 07 06 05 04 03 02 01");
+        assert_eq!(LeU64::from_der(&der).unwrap(), LeU64(0x0102_0304_0506_0708));
+        assert_eq!(
+            LeU64(0x0102_0304_0506_0708)
+                .encode_to_slice(&mut buf)
+                .unwrap(),
+            der
+        );
+    }
+
+    #[test]
+    fn reject_wrong_size() {
+        for der in [&hex!("04 03 01 02 03")[..], &hex!("04 05 01 02 03 04 05")] {
+            let err = LeU32::from_der(der).unwrap_err();
+            assert_eq!(
+                err.kind(),
+                ErrorKind::Length {
+                    tag: Tag::OctetString
+                }
+            );
+        }
+
+        assert!(LeU64::from_der(&hex!("04 04 01 02 03 04")).is_err());
+        assert!(LeU32::from_der(&hex!("02 04 01 02 03 04")).is_err());
+    }
+
+    #[test]
+    fn der_ord() {
+        // 0x0100 is encoded as `00 01 00 00`, which sorts before `01 00 00 00`
+        assert_eq!(LeU32(0x100).der_cmp(&LeU32(1)).unwrap(), Ordering::Less);
+    }
+}
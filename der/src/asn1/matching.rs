@@ -0,0 +1,130 @@
+//! X.520 matching rules for the ASN.1 restricted string types.
+//!
+//! These implement the `caseIgnoreMatch` and `caseIgnoreOrderingMatch` rules
+//! used to compare the `DirectoryString` attribute values of distinguished
+//! names, e.g. when chaining certificates by matching the issuer of one with
+//! the subject of another as described in [RFC 5280 Section 7.1].
+//!
+//! Values are prepared following the steps of [RFC 4518] which don't require
+//! Unicode tables beyond the ones in `core`:
+//!
+//! - case is folded by mapping every character to lowercase,
+//! - leading and trailing whitespace is removed,
+//! - runs of internal whitespace are collapsed into a single space.
+//!
+//! Unicode normalization and the removal of prohibited or unassigned code
+//! points aren't performed, so strings which only differ by those are
+//! considered different.
+//!
+//! [RFC 4518]: https://datatracker.ietf.org/doc/html/rfc4518
+//! [RFC 5280 Section 7.1]: https://datatracker.ietf.org/doc/html/rfc5280#section-7.1
+
+use core::{char::ToLowercase, cmp::Ordering, str::Chars};
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+/// Iterator over the characters of a string prepared for `caseIgnoreMatch`.
+///
+/// Returned by [`case_ignore_chars`].
+#[derive(Clone, Debug)]
+pub struct CaseIgnoreChars<'a> {
+    /// Remaining characters of the string, without trailing whitespace.
+    chars: Chars<'a>,
+
+    /// Remaining characters of the lowercase mapping of the current character.
+    lowercase: Option<ToLowercase>,
+}
+
+impl Iterator for CaseIgnoreChars<'_> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        if let Some(c) = self.lowercase.as_mut().and_then(Iterator::next) {
+            return Some(c);
+        }
+
+        let c = self.chars.next()?;
+
+        if c.is_whitespace() {
+            // Collapse the run of whitespace into a single space
+            self.chars = self.chars.as_str().trim_start().chars();
+            self.lowercase = None;
+            return Some(' ');
+        }
+
+        let mut lowercase = c.to_lowercase();
+        let c = lowercase.next();
+        self.lowercase = Some(lowercase);
+        c
+    }
+}
+
+/// Iterate over the characters of `s` prepared for `caseIgnoreMatch`, i.e.
+/// folded to lowercase, with leading and trailing whitespace removed and
+/// runs of internal whitespace collapsed into a single space.
+pub fn case_ignore_chars(s: &str) -> CaseIgnoreChars<'_> {
+    CaseIgnoreChars {
+        chars: s.trim().chars(),
+        lowercase: None,
+    }
+}
+
+/// Do `a` and `b` match according to X.520 `caseIgnoreMatch`?
+///
+/// ```
+/// use der::asn1::matching::case_ignore_eq;
+///
+/// assert!(case_ignore_eq("Example  CA ", "example ca"));
+/// assert!(!case_ignore_eq("Example CA", "ExampleCA"));
+/// ```
+pub fn case_ignore_eq(a: &str, b: &str) -> bool {
+    case_ignore_chars(a).eq(case_ignore_chars(b))
+}
+
+/// Compare `a` and `b` according to X.520 `caseIgnoreOrderingMatch`.
+pub fn case_ignore_cmp(a: &str, b: &str) -> Ordering {
+    case_ignore_chars(a).cmp(case_ignore_chars(b))
+}
+
+/// Prepare `s` for `caseIgnoreMatch`, e.g. to use it as the key of a map of
+/// names.
+#[cfg(feature = "alloc")]
+pub fn case_ignore_string(s: &str) -> String {
+    case_ignore_chars(s).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{case_ignore_chars, case_ignore_cmp, case_ignore_eq};
+    use core::cmp::Ordering;
+
+    #[test]
+    fn case_folding() {
+        assert!(case_ignore_eq("Example CA", "EXAMPLE ca"));
+        assert!(case_ignore_eq("Stra\u{df}e", "STRA\u{df}E"));
+        assert!(case_ignore_eq("\u{130}stanbul", "i\u{307}stanbul"));
+        assert!(!case_ignore_eq("Example CA", "Example CB"));
+    }
+
+    #[test]
+    fn insignificant_spaces() {
+        assert!(case_ignore_eq("  Example \t\n CA  ", "Example CA"));
+        assert!(case_ignore_eq("", "   "));
+        assert!(!case_ignore_eq("Example CA", "ExampleCA"));
+        assert!(case_ignore_chars(" a  B ").eq("a b".chars()));
+    }
+
+    #[test]
+    fn ordering() {
+        assert_eq!(case_ignore_cmp("abc", " ABC"), Ordering::Equal);
+        assert_eq!(case_ignore_cmp("ABC", "abd"), Ordering::Less);
+        assert_eq!(case_ignore_cmp("a  b", "ab"), Ordering::Less);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn prepared_string() {
+        assert_eq!(super::case_ignore_string(" Example\t CA "), "example ca");
+    }
+}
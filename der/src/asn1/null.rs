@@ -1,8 +1,8 @@
 //! ASN.1 `NULL` support.
 
 use crate::{
-    BytesRef, DecodeValue, EncodeValue, Error, ErrorKind, FixedTag, Header, Length, Reader, Result,
-    Tag, Writer, asn1::AnyRef, ord::OrdIsValueOrd,
+    BytesRef, DecodeValue, EncodeFixed, EncodeValue, Error, ErrorKind, FixedTag, Header, Length,
+    Reader, Result, Tag, Writer, asn1::AnyRef, encode_fixed::max_tlv_len, ord::OrdIsValueOrd,
 };
 
 /// ASN.1 `NULL` type.
@@ -37,6 +37,10 @@ impl FixedTag for Null {
     const TAG: Tag = Tag::Null;
 }
 
+impl EncodeFixed for Null {
+    const MAX_ENCODED_LEN: usize = max_tlv_len(Self::TAG, 0);
+}
+
 impl OrdIsValueOrd for Null {}
 
 impl<'a> From<Null> for AnyRef<'a> {
@@ -82,6 +86,10 @@ impl FixedTag for () {
     const TAG: Tag = Tag::Null;
 }
 
+impl EncodeFixed for () {
+    const MAX_ENCODED_LEN: usize = max_tlv_len(Self::TAG, 0);
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 mod tests {
@@ -0,0 +1,99 @@
+//! ASN.1 `NumericString` support.
+
+use crate::{FixedTag, Result, StringRef, Tag, asn1::AnyRef, asn1::charset};
+use core::{fmt, ops::Deref};
+
+/// ASN.1 `NumericString` type.
+///
+/// Supports the digits `0..9` and space.
+///
+/// For a larger subset of ASCII, use
+/// [`PrintableStringRef`][`crate::asn1::PrintableStringRef`] instead.
+///
+/// This is a zero-copy reference type which borrows from the input data.
+#[derive(Copy, Clone, Eq, PartialEq, PartialOrd, Ord)]
+pub struct NumericStringRef<'a> {
+    /// Inner value
+    inner: &'a StringRef,
+}
+
+impl<'a> NumericStringRef<'a> {
+    /// Create a new ASN.1 `NumericString`.
+    pub fn new<T>(input: &'a T) -> Result<Self>
+    where
+        T: AsRef<[u8]> + ?Sized,
+    {
+        let input = input.as_ref();
+
+        // Validate all characters are within NumericString's allowed set
+        if !charset::is_valid_numeric(input) {
+            return Err(Self::TAG.value_error().into());
+        }
+
+        StringRef::from_bytes(input)
+            .map(|inner| Self { inner })
+            .map_err(|_| Self::TAG.value_error().into())
+    }
+
+    /// Borrow the inner `str`.
+    pub fn as_str(&self) -> &'a str {
+        self.inner.as_str()
+    }
+}
+
+impl_string_type!(NumericStringRef<'a>, 'a);
+
+impl FixedTag for NumericStringRef<'_> {
+    const TAG: Tag = Tag::NumericString;
+}
+
+impl fmt::Debug for NumericStringRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "NumericString({:?})", self.as_str())
+    }
+}
+
+impl<'a> Deref for NumericStringRef<'a> {
+    type Target = StringRef;
+
+    fn deref(&self) -> &Self::Target {
+        self.inner
+    }
+}
+
+impl<'a> From<&NumericStringRef<'a>> for NumericStringRef<'a> {
+    fn from(value: &NumericStringRef<'a>) -> NumericStringRef<'a> {
+        *value
+    }
+}
+
+impl<'a> From<NumericStringRef<'a>> for AnyRef<'a> {
+    fn from(numeric_string: NumericStringRef<'a>) -> AnyRef<'a> {
+        AnyRef::from_tag_and_value(Tag::NumericString, numeric_string.inner.as_ref())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::NumericStringRef;
+    use crate::{Decode, Encode, Tag};
+    use hex_literal::hex;
+
+    #[test]
+    fn round_trip() {
+        let der = hex!("12 08 30 31 32 33 20 34 35 36");
+        let numeric_string = NumericStringRef::from_der(&der).unwrap();
+        assert_eq!(numeric_string.as_str(), "0123 456");
+
+        let mut buf = [0u8; 16];
+        assert_eq!(numeric_string.encode_to_slice(&mut buf).unwrap(), der);
+    }
+
+    #[test]
+    fn reject_invalid() {
+        let err = NumericStringRef::from_der(&hex!("12 03 31 32 61")).unwrap_err();
+        assert_eq!(err.kind(), Tag::NumericString.value_error());
+        assert!(NumericStringRef::new("-1").is_err());
+    }
+}
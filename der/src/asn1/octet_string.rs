@@ -1,9 +1,10 @@
 //! ASN.1 `OCTET STRING` support.
 
 use crate::{
-    BytesRef, Decode, DecodeValue, EncodeValue, Error, ErrorKind, FixedTag, Header, Length, Reader,
-    Tag, Writer, asn1::AnyRef, ord::OrdIsValueOrd,
+    BytesRef, Decode, DecodeValue, EncodeFixed, EncodeValue, Error, ErrorKind, FixedTag, Header,
+    Length, Reader, Size, Tag, Writer, asn1::AnyRef, encode_fixed::max_tlv_len, ord::OrdIsValueOrd,
 };
+use core::ops::Deref;
 
 /// ASN.1 `OCTET STRING` type: borrowed form.
 ///
@@ -92,6 +93,12 @@ impl FixedTag for &OctetStringRef {
 
 impl OrdIsValueOrd for &OctetStringRef {}
 
+impl Size for OctetStringRef {
+    fn size(&self) -> usize {
+        self.as_bytes().len()
+    }
+}
+
 impl<'a> From<&'a OctetStringRef> for AnyRef<'a> {
     fn from(octet_string: &'a OctetStringRef) -> AnyRef<'a> {
         AnyRef::from_tag_and_value(Tag::OctetString, &octet_string.inner)
@@ -161,18 +168,128 @@ impl<'a, const N: usize> TryFrom<&'a heapless::Vec<u8, N>> for &'a OctetStringRe
     }
 }
 
+/// ASN.1 `OCTET STRING` type: fixed-size array form.
+///
+/// Octet strings of exactly `N` bytes, e.g. salts, nonces or keys of a given
+/// size. Decoding fails with [`ErrorKind::Length`] if the length of the
+/// `OCTET STRING` is not `N`.
+///
+/// This is a newtype rather than an impl on `[u8; N]`, since arrays are
+/// encoded as `SEQUENCE OF`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub struct OctetStringArray<const N: usize>(pub [u8; N]);
+
+impl<const N: usize> OctetStringArray<N> {
+    /// Borrow the inner byte array.
+    pub fn as_bytes(&self) -> &[u8; N] {
+        &self.0
+    }
+
+    /// Get the inner byte array.
+    pub fn into_bytes(self) -> [u8; N] {
+        self.0
+    }
+}
+
+impl<const N: usize> AsRef<[u8]> for OctetStringArray<N> {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl<const N: usize> Deref for OctetStringArray<N> {
+    type Target = [u8; N];
+
+    fn deref(&self) -> &[u8; N] {
+        &self.0
+    }
+}
+
+impl<'a, const N: usize> DecodeValue<'a> for OctetStringArray<N> {
+    type Error = Error;
+
+    fn decode_value<R: Reader<'a>>(reader: &mut R, header: Header) -> Result<Self, Error> {
+        if usize::try_from(header.length())? != N {
+            return Err(reader.error(Self::TAG.length_error()));
+        }
+
+        let mut bytes = [0u8; N];
+        reader.read_into(&mut bytes)?;
+        Ok(Self(bytes))
+    }
+}
+
+impl<const N: usize> EncodeValue for OctetStringArray<N> {
+    fn value_len(&self) -> Result<Length, Error> {
+        Length::try_from(N)
+    }
+
+    fn encode_value(&self, writer: &mut impl Writer) -> Result<(), Error> {
+        writer.write(&self.0)
+    }
+}
+
+impl<const N: usize> FixedTag for OctetStringArray<N> {
+    const TAG: Tag = Tag::OctetString;
+}
+
+impl<const N: usize> EncodeFixed for OctetStringArray<N> {
+    const MAX_ENCODED_LEN: usize = max_tlv_len(Self::TAG, N);
+}
+
+impl<const N: usize> OrdIsValueOrd for OctetStringArray<N> {}
+
+impl<const N: usize> Size for OctetStringArray<N> {
+    fn size(&self) -> usize {
+        N
+    }
+}
+
+impl<const N: usize> From<[u8; N]> for OctetStringArray<N> {
+    fn from(bytes: [u8; N]) -> Self {
+        Self(bytes)
+    }
+}
+
+impl<const N: usize> From<OctetStringArray<N>> for [u8; N] {
+    fn from(octet_string: OctetStringArray<N>) -> [u8; N] {
+        octet_string.0
+    }
+}
+
+impl<const N: usize> TryFrom<AnyRef<'_>> for OctetStringArray<N> {
+    type Error = Error;
+
+    fn try_from(any: AnyRef<'_>) -> Result<Self, Error> {
+        any.decode_as()
+    }
+}
+
+impl<const N: usize> TryFrom<&OctetStringRef> for OctetStringArray<N> {
+    type Error = Error;
+
+    fn try_from(octet_string: &OctetStringRef) -> Result<Self, Error> {
+        octet_string.try_into().map(Self)
+    }
+}
+
 #[cfg(feature = "alloc")]
 pub use self::allocating::OctetString;
 
 #[cfg(feature = "alloc")]
 mod allocating {
     use super::*;
-    use crate::BytesOwned;
+    use crate::{
+        BytesOwned,
+        referenced::{OwnedToRef, RefToOwned},
+    };
     use alloc::{
         borrow::{Borrow, Cow, ToOwned},
         boxed::Box,
         vec::Vec,
     };
+    #[cfg(feature = "zeroize")]
+    use zeroize::Zeroize;
 
     /// ASN.1 `OCTET STRING` type: owned form.
     ///
@@ -218,6 +335,13 @@ mod allocating {
         }
     }
 
+    #[cfg(feature = "zeroize")]
+    impl Zeroize for OctetString {
+        fn zeroize(&mut self) {
+            self.inner.zeroize();
+        }
+    }
+
     impl_any_conversions!(OctetString);
 
     impl AsRef<[u8]> for OctetString {
@@ -255,6 +379,12 @@ mod allocating {
         const TAG: Tag = Tag::OctetString;
     }
 
+    impl Size for OctetString {
+        fn size(&self) -> usize {
+            self.as_bytes().len()
+        }
+    }
+
     impl OrdIsValueOrd for OctetString {}
 
     impl<'a> From<&'a OctetString> for &'a OctetStringRef {
@@ -271,6 +401,22 @@ mod allocating {
         }
     }
 
+    impl<'a> RefToOwned<'a> for &'a OctetStringRef {
+        type Owned = OctetString;
+
+        fn ref_to_owned(&self) -> Self::Owned {
+            OctetString::from(*self)
+        }
+    }
+
+    impl OwnedToRef for OctetString {
+        type Borrowed<'a> = &'a OctetStringRef;
+
+        fn owned_to_ref(&self) -> Self::Borrowed<'_> {
+            self.into()
+        }
+    }
+
     impl From<&OctetStringRef> for Vec<u8> {
         fn from(octet_string: &OctetStringRef) -> Vec<u8> {
             Vec::from(octet_string.as_bytes())
@@ -374,12 +520,152 @@ mod bytes {
     }
 }
 
+#[cfg(feature = "net")]
+mod net {
+    use super::OctetStringRef;
+    use crate::{
+        DecodeValue, EncodeValue, Error, FixedTag, Header, Length, Reader, Result, Tag, Writer,
+        ord::OrdIsValueOrd,
+    };
+    use core::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+    /// Length of an IPv4 address in bytes.
+    const IPV4_LEN: Length = Length::new(4);
+
+    /// Length of an IPv6 address in bytes.
+    const IPV6_LEN: Length = Length::new(16);
+
+    impl<'a> DecodeValue<'a> for Ipv4Addr {
+        type Error = Error;
+
+        fn decode_value<R: Reader<'a>>(reader: &mut R, header: Header) -> Result<Self> {
+            if header.length() != IPV4_LEN {
+                return Err(reader.error(Self::TAG.length_error()));
+            }
+
+            let mut octets = [0u8; 4];
+            reader.read_into(&mut octets)?;
+            Ok(octets.into())
+        }
+    }
+
+    impl EncodeValue for Ipv4Addr {
+        fn value_len(&self) -> Result<Length> {
+            Ok(IPV4_LEN)
+        }
+
+        fn encode_value(&self, writer: &mut impl Writer) -> Result<()> {
+            writer.write(&self.octets())
+        }
+    }
+
+    impl FixedTag for Ipv4Addr {
+        const TAG: Tag = Tag::OctetString;
+    }
+
+    impl OrdIsValueOrd for Ipv4Addr {}
+
+    impl TryFrom<&OctetStringRef> for Ipv4Addr {
+        type Error = Error;
+
+        fn try_from(octet_string: &OctetStringRef) -> Result<Self> {
+            <[u8; 4]>::try_from(octet_string).map(Into::into)
+        }
+    }
+
+    impl<'a> DecodeValue<'a> for Ipv6Addr {
+        type Error = Error;
+
+        fn decode_value<R: Reader<'a>>(reader: &mut R, header: Header) -> Result<Self> {
+            if header.length() != IPV6_LEN {
+                return Err(reader.error(Self::TAG.length_error()));
+            }
+
+            let mut octets = [0u8; 16];
+            reader.read_into(&mut octets)?;
+            Ok(octets.into())
+        }
+    }
+
+    impl EncodeValue for Ipv6Addr {
+        fn value_len(&self) -> Result<Length> {
+            Ok(IPV6_LEN)
+        }
+
+        fn encode_value(&self, writer: &mut impl Writer) -> Result<()> {
+            writer.write(&self.octets())
+        }
+    }
+
+    impl FixedTag for Ipv6Addr {
+        const TAG: Tag = Tag::OctetString;
+    }
+
+    impl OrdIsValueOrd for Ipv6Addr {}
+
+    impl TryFrom<&OctetStringRef> for Ipv6Addr {
+        type Error = Error;
+
+        fn try_from(octet_string: &OctetStringRef) -> Result<Self> {
+            <[u8; 16]>::try_from(octet_string).map(Into::into)
+        }
+    }
+
+    impl<'a> DecodeValue<'a> for IpAddr {
+        type Error = Error;
+
+        fn decode_value<R: Reader<'a>>(reader: &mut R, header: Header) -> Result<Self> {
+            match header.length() {
+                IPV4_LEN => Ipv4Addr::decode_value(reader, header).map(Into::into),
+                IPV6_LEN => Ipv6Addr::decode_value(reader, header).map(Into::into),
+                _ => Err(reader.error(Self::TAG.length_error())),
+            }
+        }
+    }
+
+    impl EncodeValue for IpAddr {
+        fn value_len(&self) -> Result<Length> {
+            match self {
+                IpAddr::V4(addr) => addr.value_len(),
+                IpAddr::V6(addr) => addr.value_len(),
+            }
+        }
+
+        fn encode_value(&self, writer: &mut impl Writer) -> Result<()> {
+            match self {
+                IpAddr::V4(addr) => addr.encode_value(writer),
+                IpAddr::V6(addr) => addr.encode_value(writer),
+            }
+        }
+    }
+
+    impl FixedTag for IpAddr {
+        const TAG: Tag = Tag::OctetString;
+    }
+
+    // IPv4 addresses sort before IPv6 addresses, matching the DER ordering of
+    // their (shorter) encodings.
+    impl OrdIsValueOrd for IpAddr {}
+
+    impl TryFrom<&OctetStringRef> for IpAddr {
+        type Error = Error;
+
+        fn try_from(octet_string: &OctetStringRef) -> Result<Self> {
+            match octet_string.len() {
+                IPV4_LEN => Ipv4Addr::try_from(octet_string).map(Into::into),
+                IPV6_LEN => Ipv6Addr::try_from(octet_string).map(Into::into),
+                _ => Err(Tag::OctetString.length_error().into()),
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 mod tests {
     use crate::{
-        Decode,
-        asn1::{OctetStringRef, PrintableStringRef},
+        Decode, Encode, EncodeFixed, ErrorKind, Tag,
+        asn1::{AnyRef, OctetStringArray, OctetStringRef, PrintableStringRef},
     };
     use hex_literal::hex;
 
@@ -405,6 +691,42 @@ mod tests {
         assert_eq!(AsRef::<str>::as_ref(&res), "hi");
     }
 
+    #[test]
+    fn octet_string_array() {
+        const EXAMPLE: &[u8] = &hex!("0404 DEADBEEF");
+
+        let decoded = OctetStringArray::<4>::from_der(EXAMPLE).unwrap();
+        assert_eq!(decoded.into_bytes(), hex!("DEADBEEF"));
+
+        let mut buf = [0u8; OctetStringArray::<4>::MAX_ENCODED_LEN];
+        assert_eq!(decoded.encode_to_slice(&mut buf).unwrap(), EXAMPLE);
+
+        let any = AnyRef::from_der(EXAMPLE).unwrap();
+        assert_eq!(OctetStringArray::<4>::try_from(any).unwrap(), decoded);
+
+        let octet_string = OctetStringRef::new(&EXAMPLE[2..]).unwrap();
+        assert_eq!(
+            OctetStringArray::<4>::try_from(octet_string).unwrap(),
+            decoded
+        );
+    }
+
+    #[test]
+    fn octet_string_array_wrong_length() {
+        let length_error = ErrorKind::Length {
+            tag: Tag::OctetString,
+        };
+
+        let err = OctetStringArray::<4>::from_der(&hex!("0403 DEADBE")).unwrap_err();
+        assert_eq!(err.kind(), length_error);
+
+        let err = OctetStringArray::<4>::from_der(&hex!("0405 DEADBEEF00")).unwrap_err();
+        assert_eq!(err.kind(), length_error);
+
+        let octet_string = OctetStringRef::new(&[0; 5]).unwrap();
+        assert!(OctetStringArray::<4>::try_from(octet_string).is_err());
+    }
+
     #[test]
     #[cfg(all(feature = "alloc", feature = "ber"))]
     fn decode_ber_primitive_definite() {
@@ -543,3 +865,68 @@ mod tests {
         assert_eq!(expected, err);
     }
 }
+
+#[cfg(all(test, feature = "net"))]
+#[allow(clippy::unwrap_used)]
+mod net_tests {
+    use crate::{Decode, Encode, ErrorKind, Tag, asn1::OctetStringRef};
+    use core::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+    use hex_literal::hex;
+
+    const IPV4_DER: &[u8] = &hex!("0404C0000201");
+    const IPV6_DER: &[u8] = &hex!("041020010DB8000000000000000000000001");
+
+    #[test]
+    fn ipv4_round_trip() {
+        let addr = Ipv4Addr::new(192, 0, 2, 1);
+        assert_eq!(Ipv4Addr::from_der(IPV4_DER).unwrap(), addr);
+        assert_eq!(IpAddr::from_der(IPV4_DER).unwrap(), IpAddr::V4(addr));
+
+        let mut buf = [0u8; 6];
+        assert_eq!(addr.encode_to_slice(&mut buf).unwrap(), IPV4_DER);
+        assert_eq!(
+            IpAddr::V4(addr).encode_to_slice(&mut buf).unwrap(),
+            IPV4_DER
+        );
+    }
+
+    #[test]
+    fn ipv6_round_trip() {
+        let addr = Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1);
+        assert_eq!(Ipv6Addr::from_der(IPV6_DER).unwrap(), addr);
+        assert_eq!(IpAddr::from_der(IPV6_DER).unwrap(), IpAddr::V6(addr));
+
+        let mut buf = [0u8; 18];
+        assert_eq!(addr.encode_to_slice(&mut buf).unwrap(), IPV6_DER);
+        assert_eq!(
+            IpAddr::V6(addr).encode_to_slice(&mut buf).unwrap(),
+            IPV6_DER
+        );
+    }
+
+    #[test]
+    fn reject_wrong_length() {
+        let length_error = ErrorKind::Length {
+            tag: Tag::OctetString,
+        };
+
+        let err = Ipv4Addr::from_der(IPV6_DER).unwrap_err();
+        assert_eq!(err.kind(), length_error);
+
+        let err = Ipv6Addr::from_der(IPV4_DER).unwrap_err();
+        assert_eq!(err.kind(), length_error);
+
+        let err = IpAddr::from_der(&hex!("0405C000020100")).unwrap_err();
+        assert_eq!(err.kind(), length_error);
+    }
+
+    #[test]
+    fn from_octet_string() {
+        let octet_string = OctetStringRef::new(&[192, 0, 2, 1]).unwrap();
+        assert_eq!(
+            IpAddr::try_from(octet_string).unwrap(),
+            IpAddr::V4(Ipv4Addr::new(192, 0, 2, 1))
+        );
+        assert!(Ipv6Addr::try_from(octet_string).is_err());
+    }
+}
@@ -1,10 +1,12 @@
-//! ASN.1 `OBJECT IDENTIFIER`
+//! ASN.1 `OBJECT IDENTIFIER` and `RELATIVE-OID`
 
 use crate::{
-    DecodeValue, EncodeValue, Error, FixedTag, Header, Length, Reader, Result, Tag, Tagged, Writer,
-    asn1::AnyRef, ord::OrdIsValueOrd,
+    BytesRef, DecodeValue, EncodeFixed, EncodeValue, Error, ErrorKind, FixedTag, Header, Length,
+    Reader, Result, Tag, Tagged, Writer, asn1::AnyRef, encode_fixed::max_tlv_len,
+    ord::OrdIsValueOrd,
 };
-use const_oid::{ObjectIdentifier, ObjectIdentifierRef};
+use const_oid::{ObjectIdentifier, RelativeObjectIdentifier};
+use core::fmt;
 
 #[cfg(feature = "alloc")]
 use super::Any;
@@ -20,7 +22,7 @@ impl<'a, const MAX_SIZE: usize> DecodeValue<'a> for ObjectIdentifier<MAX_SIZE> {
 
         let actual_len = reader.read_into(slice)?.len();
         debug_assert_eq!(actual_len, header.length().try_into()?);
-        Ok(ObjectIdentifierRef::from_bytes(slice)?.try_into()?)
+        Ok(const_oid::ObjectIdentifierRef::from_bytes(slice)?.try_into()?)
     }
 }
 
@@ -38,6 +40,10 @@ impl<const MAX_SIZE: usize> FixedTag for ObjectIdentifier<MAX_SIZE> {
     const TAG: Tag = Tag::ObjectIdentifier;
 }
 
+impl<const MAX_SIZE: usize> EncodeFixed for ObjectIdentifier<MAX_SIZE> {
+    const MAX_ENCODED_LEN: usize = max_tlv_len(Self::TAG, MAX_SIZE);
+}
+
 impl<const MAX_SIZE: usize> OrdIsValueOrd for ObjectIdentifier<MAX_SIZE> {}
 
 impl<'a, const MAX_SIZE: usize> From<&'a ObjectIdentifier<MAX_SIZE>> for AnyRef<'a> {
@@ -67,15 +73,222 @@ impl<const MAX_SIZE: usize> TryFrom<AnyRef<'_>> for ObjectIdentifier<MAX_SIZE> {
 
     fn try_from(any: AnyRef<'_>) -> Result<ObjectIdentifier<MAX_SIZE>> {
         any.tag().assert_eq(Tag::ObjectIdentifier)?;
-        Ok(ObjectIdentifierRef::from_bytes(any.value())?.try_into()?)
+        Ok(const_oid::ObjectIdentifierRef::from_bytes(any.value())?.try_into()?)
+    }
+}
+
+impl<'a, const MAX_SIZE: usize> DecodeValue<'a> for RelativeObjectIdentifier<MAX_SIZE> {
+    type Error = Error;
+
+    fn decode_value<R: Reader<'a>>(reader: &mut R, header: Header) -> Result<Self> {
+        let mut buf = [0u8; MAX_SIZE];
+        let slice = buf
+            .get_mut(..header.length().try_into()?)
+            .ok_or_else(|| Self::TAG.length_error())?;
+
+        let actual_len = reader.read_into(slice)?.len();
+        debug_assert_eq!(actual_len, header.length().try_into()?);
+        Ok(Self::from_bytes_sized(slice)?)
+    }
+}
+
+impl<const MAX_SIZE: usize> EncodeValue for RelativeObjectIdentifier<MAX_SIZE> {
+    fn value_len(&self) -> Result<Length> {
+        Length::try_from(self.as_bytes().len())
+    }
+
+    fn encode_value(&self, writer: &mut impl Writer) -> Result<()> {
+        writer.write(self.as_bytes())
+    }
+}
+
+impl<const MAX_SIZE: usize> FixedTag for RelativeObjectIdentifier<MAX_SIZE> {
+    const TAG: Tag = Tag::RelativeOid;
+}
+
+impl<const MAX_SIZE: usize> EncodeFixed for RelativeObjectIdentifier<MAX_SIZE> {
+    const MAX_ENCODED_LEN: usize = max_tlv_len(Self::TAG, MAX_SIZE);
+}
+
+impl<const MAX_SIZE: usize> OrdIsValueOrd for RelativeObjectIdentifier<MAX_SIZE> {}
+
+impl<'a, const MAX_SIZE: usize> From<&'a RelativeObjectIdentifier<MAX_SIZE>> for AnyRef<'a> {
+    fn from(oid: &'a RelativeObjectIdentifier<MAX_SIZE>) -> AnyRef<'a> {
+        // Relative OIDs are bounded by the same `MAX_SIZE` as OIDs, see the
+        // `length()` test below.
+        let value = oid
+            .as_bytes()
+            .try_into()
+            .expect("OID length invariant violated");
+
+        AnyRef::from_tag_and_value(Tag::RelativeOid, value)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const MAX_SIZE: usize> From<RelativeObjectIdentifier<MAX_SIZE>> for Any {
+    fn from(oid: RelativeObjectIdentifier<MAX_SIZE>) -> Any {
+        AnyRef::from(&oid).into()
+    }
+}
+
+impl<const MAX_SIZE: usize> TryFrom<AnyRef<'_>> for RelativeObjectIdentifier<MAX_SIZE> {
+    type Error = Error;
+
+    fn try_from(any: AnyRef<'_>) -> Result<RelativeObjectIdentifier<MAX_SIZE>> {
+        any.tag().assert_eq(Tag::RelativeOid)?;
+        Ok(Self::from_bytes_sized(any.value())?)
+    }
+}
+
+/// Borrowed ASN.1 `OBJECT IDENTIFIER`.
+///
+/// Unlike [`ObjectIdentifier`], decoding this type doesn't copy the encoded
+/// OID nor decode its arcs: it only checks that the value is a well-formed
+/// sequence of base 128 subidentifiers, which makes it suitable for hot paths
+/// which merely compare OIDs against constants:
+///
+/// ```
+/// use der::{Decode, asn1::{ObjectIdentifier, ObjectIdentifierRef}};
+///
+/// const RSA_ENCRYPTION: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.1");
+///
+/// let der = [0x06, 0x09, 0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x01, 0x01];
+/// let oid = ObjectIdentifierRef::from_der(&der)?;
+/// assert_eq!(oid, RSA_ENCRYPTION);
+/// assert_eq!(oid.to_oid()?, RSA_ENCRYPTION);
+/// # Ok::<(), der::Error>(())
+/// ```
+///
+/// Arcs are only decoded when converting to an [`ObjectIdentifier`], which
+/// fails if they aren't supported by `const-oid`, e.g. if they exceed 32 bits.
+#[derive(Copy, Clone, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub struct ObjectIdentifierRef<'a> {
+    /// BER/DER-serialized bytes (sans ASN.1 tag/length).
+    inner: &'a BytesRef,
+}
+
+impl<'a> ObjectIdentifierRef<'a> {
+    /// Create a new [`ObjectIdentifierRef`] from the given BER/DER-serialized
+    /// bytes (sans ASN.1 tag/length).
+    ///
+    /// Returns an error if the bytes aren't a non-empty sequence of minimally
+    /// encoded base 128 subidentifiers.
+    pub fn new(bytes: &'a [u8]) -> Result<Self> {
+        let inner = BytesRef::new(bytes).map_err(|_| Self::TAG.length_error())?;
+        validate_subidentifiers(bytes)?;
+        Ok(Self { inner })
+    }
+
+    /// Borrow the BER/DER-serialized bytes of this OID (sans ASN.1 tag/length).
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.inner.as_slice()
+    }
+
+    /// Decode the arcs of this OID into an owned [`ObjectIdentifier`].
+    pub fn to_oid(&self) -> Result<ObjectIdentifier> {
+        (*self).try_into()
+    }
+}
+
+impl_any_conversions!(ObjectIdentifierRef<'a>, 'a);
+
+impl<'a> DecodeValue<'a> for ObjectIdentifierRef<'a> {
+    type Error = Error;
+
+    fn decode_value<R: Reader<'a>>(reader: &mut R, header: Header) -> Result<Self> {
+        let bytes = <&'a BytesRef>::decode_value(reader, header)?;
+        Self::new(bytes.as_slice()).map_err(|err| reader.error(err.kind()))
+    }
+}
+
+impl EncodeValue for ObjectIdentifierRef<'_> {
+    fn value_len(&self) -> Result<Length> {
+        Ok(self.inner.len())
+    }
+
+    fn encode_value(&self, writer: &mut impl Writer) -> Result<()> {
+        writer.write(self.as_bytes())
+    }
+}
+
+impl FixedTag for ObjectIdentifierRef<'_> {
+    const TAG: Tag = Tag::ObjectIdentifier;
+}
+
+impl OrdIsValueOrd for ObjectIdentifierRef<'_> {}
+
+impl<'a> From<ObjectIdentifierRef<'a>> for AnyRef<'a> {
+    fn from(oid: ObjectIdentifierRef<'a>) -> AnyRef<'a> {
+        AnyRef::from_tag_and_value(Tag::ObjectIdentifier, oid.inner)
+    }
+}
+
+impl<'a, const MAX_SIZE: usize> From<&'a ObjectIdentifier<MAX_SIZE>> for ObjectIdentifierRef<'a> {
+    fn from(oid: &'a ObjectIdentifier<MAX_SIZE>) -> ObjectIdentifierRef<'a> {
+        // `ObjectIdentifier` only holds valid OIDs, whose length is bounded
+        // by `const_oid::MAX_LEN <= Length::max()` (see the `length()` test)
+        let inner = BytesRef::new(oid.as_bytes()).expect("OID length invariant violated");
+        ObjectIdentifierRef { inner }
+    }
+}
+
+impl<const MAX_SIZE: usize> TryFrom<ObjectIdentifierRef<'_>> for ObjectIdentifier<MAX_SIZE> {
+    type Error = Error;
+
+    fn try_from(oid: ObjectIdentifierRef<'_>) -> Result<ObjectIdentifier<MAX_SIZE>> {
+        Ok(ObjectIdentifier::from_bytes_sized(oid.as_bytes())?)
+    }
+}
+
+impl<const MAX_SIZE: usize> PartialEq<ObjectIdentifier<MAX_SIZE>> for ObjectIdentifierRef<'_> {
+    fn eq(&self, other: &ObjectIdentifier<MAX_SIZE>) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl fmt::Debug for ObjectIdentifierRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ObjectIdentifierRef({self})")
+    }
+}
+
+impl fmt::Display for ObjectIdentifierRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match const_oid::ObjectIdentifierRef::from_bytes(self.as_bytes()) {
+            Ok(oid) => fmt::Display::fmt(oid, f),
+            // Arcs unsupported by `const-oid`
+            Err(_) => write!(f, "{:02X?}", self.as_bytes()),
+        }
+    }
+}
+
+/// Ensure the given bytes are a non-empty sequence of minimally encoded base
+/// 128 subidentifiers, without decoding them.
+fn validate_subidentifiers(bytes: &[u8]) -> Result<()> {
+    let mut subidentifier_start = true;
+
+    for &byte in bytes {
+        // Leading `0x80` bytes are non-minimal padding (X.690 Section 8.19.2)
+        if subidentifier_start && byte == 0x80 {
+            return Err(ErrorKind::OidMalformed.into());
+        }
+
+        subidentifier_start = byte & 0x80 == 0;
+    }
+
+    if subidentifier_start && !bytes.is_empty() {
+        Ok(())
+    } else {
+        Err(ErrorKind::OidMalformed.into())
     }
 }
 
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 mod tests {
-    use super::ObjectIdentifier;
-    use crate::{Decode, Encode, Length};
+    use super::{ObjectIdentifier, ObjectIdentifierRef, RelativeObjectIdentifier};
+    use crate::{Decode, Encode, ErrorKind, Length, Tag, Tagged, asn1::AnyRef};
 
     const EXAMPLE_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549");
     const EXAMPLE_OID_BYTES: &[u8; 8] = &[0x06, 0x06, 0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d];
@@ -99,5 +312,56 @@ mod tests {
     fn length() {
         // Ensure an infallible `From` conversion to `Any` will never panic
         assert!(ObjectIdentifier::MAX_SIZE <= Length::MAX.try_into().unwrap());
+        assert!(RelativeObjectIdentifier::MAX_SIZE <= Length::MAX.try_into().unwrap());
+    }
+
+    #[test]
+    fn relative_oid_round_trip() {
+        // X.690 § 8.20.5: RELATIVE-OID { 8571 3 2 }
+        let der = &[0x0D, 0x04, 0xC2, 0x7B, 0x03, 0x02];
+        let oid = RelativeObjectIdentifier::from_der(der).unwrap();
+        assert_eq!(oid, RelativeObjectIdentifier::new_unwrap("8571.3.2"));
+        assert_eq!(oid.to_der().unwrap(), der);
+        assert_eq!(AnyRef::from(&oid).tag(), Tag::RelativeOid);
+
+        // An OBJECT IDENTIFIER with the same value isn't a RELATIVE-OID
+        let err = RelativeObjectIdentifier::<4>::from_der(&[0x06, 0x02, 0x2A, 0x03]).unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::TagUnexpected { .. }));
+    }
+
+    #[test]
+    fn decode_ref() {
+        let oid = ObjectIdentifierRef::from_der(EXAMPLE_OID_BYTES).unwrap();
+        assert_eq!(oid.as_bytes(), &EXAMPLE_OID_BYTES[2..]);
+        assert_eq!(oid, EXAMPLE_OID);
+        assert_eq!(oid.to_oid().unwrap(), EXAMPLE_OID);
+    }
+
+    #[test]
+    fn encode_ref() {
+        let mut buffer = [0u8; 8];
+        let oid = ObjectIdentifierRef::from(&EXAMPLE_OID);
+        assert_eq!(EXAMPLE_OID_BYTES, oid.encode_to_slice(&mut buffer).unwrap());
+    }
+
+    #[test]
+    fn decode_ref_malformed() {
+        for der in [
+            &[0x06, 0x00][..],
+            &[0x06, 0x02, 0x2A, 0x86],
+            &[0x06, 0x03, 0x2A, 0x80, 0x01],
+        ] {
+            let err = ObjectIdentifierRef::from_der(der).unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::OidMalformed, "{der:02X?}");
+        }
+    }
+
+    #[test]
+    fn decode_ref_large_arc() {
+        // The last arc doesn't fit in 32 bits
+        let der = [0x06, 0x07, 0x2A, 0x81, 0x80, 0x80, 0x80, 0x80, 0x7F];
+        let oid = ObjectIdentifierRef::from_der(&der).unwrap();
+        assert_eq!(oid.to_der().unwrap(), der);
+        assert!(oid.to_oid().is_err());
     }
 }
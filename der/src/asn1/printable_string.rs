@@ -1,6 +1,6 @@
 //! ASN.1 `PrintableString` support.
 
-use crate::{FixedTag, Result, StringRef, Tag, asn1::AnyRef};
+use crate::{FixedTag, Result, StringRef, Tag, asn1::AnyRef, asn1::charset};
 use core::{fmt, ops::Deref};
 
 macro_rules! impl_printable_string {
@@ -66,25 +66,8 @@ impl<'a> PrintableStringRef<'a> {
         let input = input.as_ref();
 
         // Validate all characters are within PrintableString's allowed set
-        for &c in input.iter() {
-            match c {
-                b'A'..=b'Z'
-                | b'a'..=b'z'
-                | b'0'..=b'9'
-                | b' '
-                | b'\''
-                | b'('
-                | b')'
-                | b'+'
-                | b','
-                | b'-'
-                | b'.'
-                | b'/'
-                | b':'
-                | b'='
-                | b'?' => (),
-                _ => return Err(Self::TAG.value_error().into()),
-            }
+        if !charset::is_valid_printable(input) {
+            return Err(Self::TAG.value_error().into());
         }
 
         StringRef::from_bytes(input)
@@ -133,6 +116,8 @@ mod allocation {
     };
     use alloc::{borrow::ToOwned, string::String};
     use core::{fmt, ops::Deref};
+    #[cfg(feature = "zeroize")]
+    use zeroize::Zeroize;
 
     /// ASN.1 `PrintableString` type.
     ///
@@ -192,6 +177,13 @@ mod allocation {
         }
     }
 
+    #[cfg(feature = "zeroize")]
+    impl Zeroize for PrintableString {
+        fn zeroize(&mut self) {
+            self.inner.zeroize();
+        }
+    }
+
     impl<'a> From<PrintableStringRef<'a>> for PrintableString {
         fn from(value: PrintableStringRef<'a>) -> PrintableString {
             let inner =
@@ -261,4 +253,15 @@ mod tests {
         let printable_string = PrintableStringRef::from_der(example_bytes).unwrap();
         assert_eq!(printable_string.as_str(), "Test User 1");
     }
+
+    #[test]
+    fn case_ignore_eq() {
+        let printable_string = PrintableStringRef::new("Test  User 1").unwrap();
+        assert!(printable_string.case_ignore_eq("test user 1 "));
+        assert!(
+            printable_string
+                .case_ignore_eq(crate::asn1::Utf8StringRef::new("TEST USER 1").unwrap())
+        );
+        assert!(!printable_string.case_ignore_eq("Test User 2"));
+    }
 }
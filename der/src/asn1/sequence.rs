@@ -2,8 +2,8 @@
 //! `SEQUENCE`s to Rust structs.
 
 use crate::{
-    BytesRef, DecodeValue, EncodeValue, Error, FixedTag, Header, Length, Reader, Result, Tag,
-    Writer,
+    BytesRef, Decode, DecodeValue, Encode, EncodeValue, Error, FixedTag, Header, Length, Reader,
+    Result, Tag, Writer,
 };
 
 #[cfg(feature = "alloc")]
@@ -67,3 +67,135 @@ impl EncodeValue for SequenceRef<'_> {
 }
 
 impl<'a> Sequence<'a> for SequenceRef<'a> {}
+
+/// Implement [`Sequence`] for tuples, which are encoded as an anonymous
+/// `SEQUENCE` of their components, for arities 1 to 12.
+macro_rules! impl_tuple_sequence {
+    ($($t:ident),+) => {
+        impl<'a, $($t),+> DecodeValue<'a> for ($($t,)+)
+        where
+            $($t: Decode<'a>,)+
+            $(Error: From<$t::Error>,)+
+        {
+            type Error = Error;
+
+            fn decode_value<R: Reader<'a>>(reader: &mut R, _header: Header) -> Result<Self> {
+                Ok(($($t::decode(reader)?,)+))
+            }
+        }
+
+        impl<$($t),+> EncodeValue for ($($t,)+)
+        where
+            $($t: Encode,)+
+        {
+            #[allow(non_snake_case)]
+            fn value_len(&self) -> Result<Length> {
+                let ($($t,)+) = self;
+                let mut len = Length::ZERO;
+                $(len = (len + $t.encoded_len()?)?;)+
+                Ok(len)
+            }
+
+            #[allow(non_snake_case)]
+            fn encode_value(&self, writer: &mut impl Writer) -> Result<()> {
+                let ($($t,)+) = self;
+                $($t.encode(writer)?;)+
+                Ok(())
+            }
+        }
+
+        impl<'a, $($t),+> Sequence<'a> for ($($t,)+)
+        where
+            $($t: Decode<'a> + Encode,)+
+            $(Error: From<$t::Error>,)+
+        {
+        }
+    };
+}
+
+impl_tuple_sequence!(A);
+impl_tuple_sequence!(A, B);
+impl_tuple_sequence!(A, B, C);
+impl_tuple_sequence!(A, B, C, D);
+impl_tuple_sequence!(A, B, C, D, E);
+impl_tuple_sequence!(A, B, C, D, E, F);
+impl_tuple_sequence!(A, B, C, D, E, F, G);
+impl_tuple_sequence!(A, B, C, D, E, F, G, H);
+impl_tuple_sequence!(A, B, C, D, E, F, G, H, I);
+impl_tuple_sequence!(A, B, C, D, E, F, G, H, I, J);
+impl_tuple_sequence!(A, B, C, D, E, F, G, H, I, J, K);
+impl_tuple_sequence!(A, B, C, D, E, F, G, H, I, J, K, L);
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use crate::{Decode, Encode, ErrorKind, Tag, asn1::OctetStringRef};
+    use hex_literal::hex;
+
+    // SEQUENCE { INTEGER 42, BOOLEAN TRUE, OCTET STRING "hi" }
+    const EXAMPLE: &[u8] = &hex!("300A 02012A 0101FF 04026869");
+
+    #[test]
+    fn tuple_round_trip() {
+        let value = <(u8, bool, &OctetStringRef)>::from_der(EXAMPLE).unwrap();
+        assert_eq!(value.0, 42);
+        assert!(value.1);
+        assert_eq!(value.2.as_bytes(), b"hi");
+
+        let mut buf = [0u8; 12];
+        assert_eq!(value.encode_to_slice(&mut buf).unwrap(), EXAMPLE);
+    }
+
+    #[test]
+    fn tuple_nested() {
+        // SEQUENCE { INTEGER 1, SEQUENCE { INTEGER 2, INTEGER 3 } }
+        const NESTED: &[u8] = &hex!("300B 020101 3006 020102 020103");
+
+        let value = <(u8, (u8, u8))>::from_der(NESTED).unwrap();
+        assert_eq!(value, (1, (2, 3)));
+
+        let mut buf = [0u8; 13];
+        assert_eq!(value.encode_to_slice(&mut buf).unwrap(), NESTED);
+    }
+
+    #[test]
+    fn tuple_arities() {
+        // SEQUENCE { INTEGER 7 }
+        const SINGLE: &[u8] = &hex!("3003 020107");
+        assert_eq!(<(u8,)>::from_der(SINGLE).unwrap(), (7,));
+        let mut buf = [0u8; 38];
+        assert_eq!((7u8,).encode_to_slice(&mut buf).unwrap(), SINGLE);
+
+        // SEQUENCE { INTEGER 0, ..., INTEGER 11 }
+        let mut twelve = hex!(
+            "3024 020100 020101 020102 020103 020104 020105 020106 020107 020108 020109 02010A 02010B"
+        );
+        let value = <(u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8)>::from_der(&twelve).unwrap();
+        assert_eq!(value, (0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11));
+        assert_eq!(value.encode_to_slice(&mut buf).unwrap(), twelve);
+
+        // Missing last component
+        twelve[1] -= 3;
+        assert!(
+            <(u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8)>::from_der(
+                &twelve[..twelve.len() - 3]
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn tuple_trailing_data() {
+        let err = <(u8, bool)>::from_der(EXAMPLE).unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::TrailingData { .. }));
+
+        let err = <(bool, u8)>::from_der(EXAMPLE).unwrap_err();
+        assert_eq!(
+            err.kind(),
+            ErrorKind::TagUnexpected {
+                expected: Some(Tag::Boolean),
+                actual: Tag::Integer
+            }
+        );
+    }
+}
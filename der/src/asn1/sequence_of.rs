@@ -1,8 +1,9 @@
 //! ASN.1 `SEQUENCE OF` support.
 
 use crate::{
-    ArrayVec, Decode, DecodeValue, DerOrd, Encode, EncodeValue, Error, FixedTag, Header, Length,
-    Reader, Tag, ValueOrd, Writer, arrayvec, ord::iter_cmp,
+    ArrayVec, Decode, DecodeValue, DerOrd, Encode, EncodeFixed, EncodeValue, Error, FixedTag,
+    Header, Length, Reader, Size, Tag, ValueOrd, Writer, arrayvec, encode_fixed::max_tlv_len,
+    ord::iter_cmp,
 };
 use core::cmp::Ordering;
 
@@ -101,6 +102,16 @@ impl<T, const N: usize> FixedTag for SequenceOf<T, N> {
     const TAG: Tag = Tag::Sequence;
 }
 
+impl<T: EncodeFixed, const N: usize> EncodeFixed for SequenceOf<T, N> {
+    const MAX_ENCODED_LEN: usize = max_tlv_len(Self::TAG, N * T::MAX_ENCODED_LEN);
+}
+
+impl<T, const N: usize> Size for SequenceOf<T, N> {
+    fn size(&self) -> usize {
+        self.len()
+    }
+}
+
 impl<T, const N: usize> ValueOrd for SequenceOf<T, N>
 where
     T: DerOrd,
@@ -146,6 +157,10 @@ impl<T: Encode> EncodeValue for [T] {
     }
 }
 
+impl<T> FixedTag for [T] {
+    const TAG: Tag = Tag::Sequence;
+}
+
 impl<'a, T, const N: usize> DecodeValue<'a> for [T; N]
 where
     T: Decode<'a>,
@@ -185,6 +200,16 @@ impl<T, const N: usize> FixedTag for [T; N] {
     const TAG: Tag = Tag::Sequence;
 }
 
+impl<T: EncodeFixed, const N: usize> EncodeFixed for [T; N] {
+    const MAX_ENCODED_LEN: usize = max_tlv_len(Self::TAG, N * T::MAX_ENCODED_LEN);
+}
+
+impl<T, const N: usize> Size for [T; N] {
+    fn size(&self) -> usize {
+        N
+    }
+}
+
 impl<T, const N: usize> ValueOrd for [T; N]
 where
     T: DerOrd,
@@ -232,6 +257,13 @@ impl<T> FixedTag for Vec<T> {
     const TAG: Tag = Tag::Sequence;
 }
 
+#[cfg(feature = "alloc")]
+impl<T> Size for Vec<T> {
+    fn size(&self) -> usize {
+        self.len()
+    }
+}
+
 #[cfg(feature = "alloc")]
 impl<T> ValueOrd for Vec<T>
 where
@@ -9,12 +9,28 @@
 //!
 //! However, all types in this module sort elements of a set at decode-time,
 //! ensuring they'll be in the proper order if reserialized.
+//!
+//! When this happens the violation is recorded, and can be queried using
+//! [`SetOf::is_reordered`] or [`SetOfVec::is_reordered`]. This allows callers
+//! to detect input from legacy toolkits which did not sort their output, e.g.
+//! so they can verify signatures over the original encoding rather than the
+//! re-sorted one.
+//!
+//! Duplicate elements are handled according to the [`DuplicatePolicy`] of the
+//! reader's [`ReaderOptions`], and rejected by default as required by DER.
+//!
+//! [`DuplicatePolicy`]: crate::DuplicatePolicy
+//! [`ReaderOptions`]: crate::ReaderOptions
 
 use crate::{
-    ArrayVec, Decode, DecodeValue, DerOrd, Encode, EncodeValue, Error, ErrorKind, FixedTag, Header,
-    Length, Reader, Tag, ValueOrd, Writer, arrayvec, ord::iter_cmp,
+    ArrayVec, Decode, DecodeValue, DerOrd, DuplicatePolicy, Encode, EncodeFixed, EncodeValue,
+    Error, ErrorKind, FixedTag, Header, Length, Reader, Size, Tag, ValueOrd, Writer, arrayvec,
+    encode_fixed::max_tlv_len, ord::iter_cmp,
+};
+use core::{
+    cmp::Ordering,
+    hash::{Hash, Hasher},
 };
-use core::cmp::Ordering;
 
 #[cfg(feature = "alloc")]
 use {alloc::vec::Vec, core::slice};
@@ -25,12 +41,15 @@ use {alloc::vec::Vec, core::slice};
 /// and does not depend on `alloc` support.
 // TODO(tarcieri): use `ArrayVec` when/if it's merged into `core`
 // See: https://github.com/rust-lang/rfcs/pull/2990
-#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord, Hash)]
+#[derive(Clone, Debug)]
 pub struct SetOf<T, const N: usize>
 where
     T: DerOrd,
 {
     inner: ArrayVec<T, N>,
+
+    /// Were the elements reordered at decode-time?
+    reordered: bool,
 }
 
 impl<T, const N: usize> SetOf<T, N>
@@ -41,6 +60,7 @@ where
     pub fn new() -> Self {
         Self {
             inner: ArrayVec::default(),
+            reordered: false,
         }
     }
 
@@ -93,6 +113,15 @@ where
     pub fn len(&self) -> usize {
         self.inner.len()
     }
+
+    /// Was this [`SetOf`] decoded from input whose elements were not in DER
+    /// order?
+    ///
+    /// If so, the elements were sorted at decode-time, and will be re-encoded
+    /// in DER order rather than their original order.
+    pub fn is_reordered(&self) -> bool {
+        self.reordered
+    }
 }
 
 impl<T, const N: usize> Default for SetOf<T, N>
@@ -118,7 +147,14 @@ where
         }
 
         // Ensure elements of the `SetOf` are sorted and will serialize as valid DER
-        der_sort(result.inner.as_mut())?;
+        let policy = reader.options().duplicate_policy();
+        result.reordered = der_sort_reordered(result.inner.as_mut(), policy)
+            .map_err(|e| reader.error(e.kind()))?;
+
+        if policy == DuplicatePolicy::Dedupe {
+            result.inner.dedup_by(is_duplicate);
+        }
+
         Ok(result)
     }
 }
@@ -148,6 +184,61 @@ where
     const TAG: Tag = Tag::Set;
 }
 
+impl<T, const N: usize> EncodeFixed for SetOf<T, N>
+where
+    T: EncodeFixed + DerOrd,
+{
+    const MAX_ENCODED_LEN: usize = max_tlv_len(Self::TAG, N * T::MAX_ENCODED_LEN);
+}
+
+impl<T, const N: usize> Size for SetOf<T, N>
+where
+    T: DerOrd,
+{
+    fn size(&self) -> usize {
+        self.len()
+    }
+}
+
+// Implement comparisons by hand to ignore whether the set was reordered.
+impl<T, const N: usize> PartialEq for SetOf<T, N>
+where
+    T: DerOrd + PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+}
+
+impl<T, const N: usize> Eq for SetOf<T, N> where T: DerOrd + Eq {}
+
+impl<T, const N: usize> PartialOrd for SetOf<T, N>
+where
+    T: DerOrd + PartialOrd,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.inner.partial_cmp(&other.inner)
+    }
+}
+
+impl<T, const N: usize> Ord for SetOf<T, N>
+where
+    T: DerOrd + Ord,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.inner.cmp(&other.inner)
+    }
+}
+
+impl<T, const N: usize> Hash for SetOf<T, N>
+where
+    T: DerOrd + Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.inner.hash(state)
+    }
+}
+
 impl<T, const N: usize> TryFrom<[T; N]> for SetOf<T, N>
 where
     T: DerOrd,
@@ -202,12 +293,15 @@ impl<T> ExactSizeIterator for SetOfIter<'_, T> {}
 /// This type implements an append-only `SET OF` type which is heap-backed
 /// and depends on `alloc` support.
 #[cfg(feature = "alloc")]
-#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord, Hash)]
+#[derive(Clone, Debug)]
 pub struct SetOfVec<T>
 where
     T: DerOrd,
 {
     inner: Vec<T>,
+
+    /// Were the elements reordered at decode-time?
+    reordered: bool,
 }
 
 #[cfg(feature = "alloc")]
@@ -215,6 +309,7 @@ impl<T: DerOrd> Default for SetOfVec<T> {
     fn default() -> Self {
         Self {
             inner: Default::default(),
+            reordered: false,
         }
     }
 }
@@ -228,6 +323,7 @@ where
     pub fn new() -> Self {
         Self {
             inner: Vec::default(),
+            reordered: false,
         }
     }
 
@@ -313,6 +409,15 @@ where
     pub fn len(&self) -> usize {
         self.inner.len()
     }
+
+    /// Was this [`SetOfVec`] decoded from input whose elements were not in
+    /// DER order?
+    ///
+    /// If so, the elements were sorted at decode-time, and will be re-encoded
+    /// in DER order rather than their original order.
+    pub fn is_reordered(&self) -> bool {
+        self.reordered
+    }
 }
 
 #[cfg(feature = "alloc")]
@@ -339,8 +444,15 @@ where
             inner.push(T::decode(reader)?);
         }
 
-        der_sort(inner.as_mut())?;
-        Ok(Self { inner })
+        let policy = reader.options().duplicate_policy();
+        let reordered =
+            der_sort_reordered(inner.as_mut(), policy).map_err(|e| reader.error(e.kind()))?;
+
+        if policy == DuplicatePolicy::Dedupe {
+            inner.dedup_by(|a, b| is_duplicate(a, b));
+        }
+
+        Ok(Self { inner, reordered })
     }
 }
 
@@ -371,6 +483,60 @@ where
     const TAG: Tag = Tag::Set;
 }
 
+#[cfg(feature = "alloc")]
+impl<T> Size for SetOfVec<T>
+where
+    T: DerOrd,
+{
+    fn size(&self) -> usize {
+        self.len()
+    }
+}
+
+// Implement comparisons by hand to ignore whether the set was reordered.
+#[cfg(feature = "alloc")]
+impl<T> PartialEq for SetOfVec<T>
+where
+    T: DerOrd + PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Eq for SetOfVec<T> where T: DerOrd + Eq {}
+
+#[cfg(feature = "alloc")]
+impl<T> PartialOrd for SetOfVec<T>
+where
+    T: DerOrd + PartialOrd,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.inner.partial_cmp(&other.inner)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Ord for SetOfVec<T>
+where
+    T: DerOrd + Ord,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.inner.cmp(&other.inner)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Hash for SetOfVec<T>
+where
+    T: DerOrd + Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.inner.hash(state)
+    }
+}
+
 #[cfg(feature = "alloc")]
 impl<T> From<SetOfVec<T>> for Vec<T>
 where
@@ -391,7 +557,10 @@ where
     fn try_from(mut vec: Vec<T>) -> Result<SetOfVec<T>, Error> {
         // TODO(tarcieri): use `[T]::sort_by` here?
         der_sort(vec.as_mut_slice())?;
-        Ok(SetOfVec { inner: vec })
+        Ok(SetOfVec {
+            inner: vec,
+            reordered: false,
+        })
     }
 }
 
@@ -452,24 +621,44 @@ fn check_der_ordering<T: DerOrd>(a: &T, b: &T) -> Result<(), Error> {
 /// This function is used rather than Rust's built-in `[T]::sort_by` in order
 /// to support heapless `no_std` targets as well as to enable bubbling up
 /// sorting errors.
-#[allow(clippy::arithmetic_side_effects)]
 fn der_sort<T: DerOrd>(slice: &mut [T]) -> Result<(), Error> {
+    der_sort_reordered(slice, DuplicatePolicy::Reject).map(|_| ())
+}
+
+/// Sort a mut slice according to its [`DerOrd`] as in [`der_sort`], returning
+/// whether any elements were out of order.
+///
+/// Duplicates are rejected unless `policy` allows them, in which case they're
+/// kept in their original order, next to each other.
+#[allow(clippy::arithmetic_side_effects)]
+fn der_sort_reordered<T: DerOrd>(slice: &mut [T], policy: DuplicatePolicy) -> Result<bool, Error> {
+    let mut reordered = false;
+
     for i in 0..slice.len() {
         let mut j = i;
 
         while j > 0 {
             match slice[j - 1].der_cmp(&slice[j])? {
                 Ordering::Less => break,
-                Ordering::Equal => return Err(ErrorKind::SetDuplicate.into()),
+                Ordering::Equal if policy == DuplicatePolicy::Reject => {
+                    return Err(ErrorKind::SetDuplicate.into());
+                }
+                Ordering::Equal => break,
                 Ordering::Greater => {
                     slice.swap(j - 1, j);
+                    reordered = true;
                     j -= 1;
                 }
             }
         }
     }
 
-    Ok(())
+    Ok(reordered)
+}
+
+/// Are the given set elements duplicates of each other?
+fn is_duplicate<T: DerOrd>(a: &T, b: &T) -> bool {
+    a.der_cmp(b) == Ok(Ordering::Equal)
 }
 
 #[cfg(test)]
@@ -478,7 +667,20 @@ mod tests {
     use super::SetOf;
     #[cfg(feature = "alloc")]
     use super::SetOfVec;
-    use crate::{DerOrd, ErrorKind};
+    use crate::{Decode, DerOrd, DuplicatePolicy, ErrorKind, ReaderOptions, SliceReader};
+    use hex_literal::hex;
+
+    /// `SET OF { INTEGER 2, INTEGER 1, INTEGER 2 }`
+    const DUPLICATES_MSG: &[u8] = &hex!("3109020102020101020102");
+
+    /// Decode [`DUPLICATES_MSG`] as `T` using the given policy.
+    fn decode_duplicates<T: Decode<'static, Error = crate::Error>>(
+        policy: DuplicatePolicy,
+    ) -> crate::Result<T> {
+        let options = ReaderOptions::new().with_duplicate_policy(policy);
+        let mut reader = SliceReader::new(DUPLICATES_MSG)?.with_options(options)?;
+        T::decode(&mut reader)
+    }
 
     #[test]
     fn setof_tryfrom_array() {
@@ -528,4 +730,31 @@ mod tests {
         let err = SetOfVec::try_from(vec).err().unwrap();
         assert_eq!(err.kind(), ErrorKind::SetDuplicate);
     }
+
+    #[test]
+    fn setof_decode_duplicates() {
+        let err = decode_duplicates::<SetOf<u8, 3>>(DuplicatePolicy::Reject).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::SetDuplicate);
+        assert_eq!(err.position(), Some(11u8.into()));
+
+        let set = decode_duplicates::<SetOf<u8, 3>>(DuplicatePolicy::Dedupe).unwrap();
+        assert!(set.iter().copied().eq([1, 2]));
+        assert!(set.is_reordered());
+
+        let set = decode_duplicates::<SetOf<u8, 3>>(DuplicatePolicy::Allow).unwrap();
+        assert!(set.iter().copied().eq([1, 2, 2]));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn setofvec_decode_duplicates() {
+        let err = decode_duplicates::<SetOfVec<u8>>(DuplicatePolicy::Reject).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::SetDuplicate);
+
+        let set = decode_duplicates::<SetOfVec<u8>>(DuplicatePolicy::Dedupe).unwrap();
+        assert_eq!(set.as_slice(), &[1, 2]);
+
+        let set = decode_duplicates::<SetOfVec<u8>>(DuplicatePolicy::Allow).unwrap();
+        assert_eq!(set.as_slice(), &[1, 2, 2]);
+    }
 }
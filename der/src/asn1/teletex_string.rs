@@ -107,6 +107,8 @@ mod allocation {
     };
     use alloc::{borrow::ToOwned, string::String};
     use core::{fmt, ops::Deref};
+    #[cfg(feature = "zeroize")]
+    use zeroize::Zeroize;
 
     /// ASN.1 `TeletexString` type.
     ///
@@ -154,6 +156,13 @@ mod allocation {
         }
     }
 
+    #[cfg(feature = "zeroize")]
+    impl Zeroize for TeletexString {
+        fn zeroize(&mut self) {
+            self.inner.zeroize();
+        }
+    }
+
     impl<'a> From<TeletexStringRef<'a>> for TeletexString {
         fn from(value: TeletexStringRef<'a>) -> TeletexString {
             let inner =
@@ -0,0 +1,165 @@
+//! ASN.1 `TIME-OF-DAY` support.
+
+use crate::{
+    DecodeValue, EncodeValue, Error, ErrorKind, FixedTag, Header, Length, Reader, Result, Tag,
+    Writer, datetime, ord::OrdIsValueOrd,
+};
+use core::{fmt, str::FromStr};
+
+/// ASN.1 `TIME-OF-DAY` type.
+///
+/// Local time of day with a resolution of one second, encoded as `HHMMSS`.
+///
+/// ```
+/// use der::{Decode, asn1::TimeOfDay};
+///
+/// let time = TimeOfDay::from_der(&[0x1F, 0x20, 0x06, b'1', b'3', b'0', b'5', b'5', b'9'])?;
+/// assert_eq!(time, TimeOfDay::new(13, 5, 59)?);
+/// assert_eq!(time.minutes(), 5);
+/// # Ok::<(), der::Error>(())
+/// ```
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub struct TimeOfDay {
+    hour: u8,
+    minutes: u8,
+    seconds: u8,
+}
+
+impl TimeOfDay {
+    /// Length of an ASN.1 DER-encoded [`TimeOfDay`].
+    pub const LENGTH: usize = 6;
+
+    /// Create a new [`TimeOfDay`] from the given hour, minutes and seconds.
+    pub fn new(hour: u8, minutes: u8, seconds: u8) -> Result<Self> {
+        if hour > 23 || minutes > 59 || seconds > 59 {
+            return Err(ErrorKind::DateTime.into());
+        }
+
+        Ok(Self {
+            hour,
+            minutes,
+            seconds,
+        })
+    }
+
+    /// Get the hour (0-23).
+    pub fn hour(&self) -> u8 {
+        self.hour
+    }
+
+    /// Get the minutes (0-59).
+    pub fn minutes(&self) -> u8 {
+        self.minutes
+    }
+
+    /// Get the seconds (0-59).
+    pub fn seconds(&self) -> u8 {
+        self.seconds
+    }
+}
+
+impl_any_conversions!(TimeOfDay);
+
+impl<'a> DecodeValue<'a> for TimeOfDay {
+    type Error = Error;
+
+    fn decode_value<R: Reader<'a>>(reader: &mut R, header: Header) -> Result<Self> {
+        if Self::LENGTH != usize::try_from(header.length())? {
+            return Err(reader.error(Self::TAG.value_error()));
+        }
+
+        let mut bytes = [0u8; Self::LENGTH];
+        reader.read_into(&mut bytes)?;
+
+        let [hour1, hour2, min1, min2, sec1, sec2] = bytes;
+        let hour = datetime::decode_decimal(Self::TAG, hour1, hour2)?;
+        let minutes = datetime::decode_decimal(Self::TAG, min1, min2)?;
+        let seconds = datetime::decode_decimal(Self::TAG, sec1, sec2)?;
+
+        Self::new(hour, minutes, seconds).map_err(|_| reader.error(Self::TAG.value_error()))
+    }
+}
+
+impl EncodeValue for TimeOfDay {
+    fn value_len(&self) -> Result<Length> {
+        Self::LENGTH.try_into()
+    }
+
+    fn encode_value(&self, writer: &mut impl Writer) -> Result<()> {
+        datetime::encode_decimal(writer, Self::TAG, self.hour)?;
+        datetime::encode_decimal(writer, Self::TAG, self.minutes)?;
+        datetime::encode_decimal(writer, Self::TAG, self.seconds)
+    }
+}
+
+impl FixedTag for TimeOfDay {
+    const TAG: Tag = Tag::TimeOfDay;
+}
+
+impl OrdIsValueOrd for TimeOfDay {}
+
+impl FromStr for TimeOfDay {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match *s.as_bytes() {
+            [hour1, hour2, b':', min1, min2, b':', sec1, sec2] => {
+                let decimal = |hi, lo| {
+                    datetime::decode_decimal(Self::TAG, hi, lo).map_err(|_| ErrorKind::DateTime)
+                };
+                Self::new(
+                    decimal(hour1, hour2)?,
+                    decimal(min1, min2)?,
+                    decimal(sec1, sec2)?,
+                )
+            }
+            _ => Err(ErrorKind::DateTime.into()),
+        }
+    }
+}
+
+/// Formats the [`TimeOfDay`] in the ISO 8601 extended format, e.g. `13:05:59`.
+impl fmt::Display for TimeOfDay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:02}:{:02}:{:02}",
+            self.hour, self.minutes, self.seconds
+        )
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::TimeOfDay;
+    use crate::{Decode, Encode, Tag};
+    use hex_literal::hex;
+
+    #[test]
+    fn round_trip() {
+        let der = hex!("1F 20 06 32 33 35 39 30 30");
+        let time = TimeOfDay::from_der(&der).unwrap();
+        assert_eq!((time.hour(), time.minutes(), time.seconds()), (23, 59, 0));
+
+        let mut buf = [0u8; 16];
+        assert_eq!(time.encode_to_slice(&mut buf).unwrap(), der);
+    }
+
+    #[test]
+    fn from_str() {
+        let time = "07:30:15".parse::<TimeOfDay>().unwrap();
+        assert_eq!(time, TimeOfDay::new(7, 30, 15).unwrap());
+        assert!("24:00:00".parse::<TimeOfDay>().is_err());
+        assert!("7:30:15".parse::<TimeOfDay>().is_err());
+    }
+
+    #[test]
+    fn reject_invalid() {
+        let err = TimeOfDay::from_der(&hex!("1F 20 06 32 34 30 30 30 30")).unwrap_err();
+        assert_eq!(err.kind(), Tag::TimeOfDay.value_error());
+
+        let err = TimeOfDay::from_der(&hex!("1F 20 04 32 33 35 39")).unwrap_err();
+        assert_eq!(err.kind(), Tag::TimeOfDay.value_error());
+    }
+}
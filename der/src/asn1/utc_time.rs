@@ -1,9 +1,10 @@
 //! ASN.1 `UTCTime` support.
 
 use crate::{
-    DecodeValue, EncodeValue, Error, ErrorKind, FixedTag, Header, Length, Reader, Result, Tag,
-    Writer,
-    datetime::{self, DateTime},
+    DecodeValue, EncodeFixed, EncodeValue, Error, ErrorKind, FixedTag, Header, Length, Reader,
+    Result, Tag, Writer,
+    datetime::{self, DateTime, OutOfRange},
+    encode_fixed::max_tlv_len,
     ord::OrdIsValueOrd,
 };
 use core::time::Duration;
@@ -11,6 +12,10 @@ use core::time::Duration;
 #[cfg(feature = "std")]
 use std::time::SystemTime;
 
+/// Maximum duration since `UNIX_EPOCH` which can be represented as a
+/// [`UtcTime`], i.e. 2049-12-31T23:59:59Z.
+const MAX_UNIX_DURATION: Duration = Duration::from_secs(2_524_607_999);
+
 /// ASN.1 `UTCTime` type.
 ///
 /// This type implements the validity requirements specified in
@@ -59,6 +64,18 @@ impl UtcTime {
         DateTime::from_unix_duration(unix_duration)?.try_into()
     }
 
+    /// Create a new [`UtcTime`] given the number of seconds since `UNIX_EPOCH`,
+    /// handling timestamps before 1970 or after 2049 according to the given
+    /// [`OutOfRange`] policy.
+    ///
+    /// See [`DateTime::from_unix_secs`] for more information.
+    pub fn from_unix_secs(secs: i64, out_of_range: OutOfRange) -> Result<Self> {
+        out_of_range
+            .unix_duration(secs, MAX_UNIX_DURATION)
+            .ok_or_else(|| Self::TAG.value_error().into())
+            .and_then(Self::from_unix_duration)
+    }
+
     /// Get the duration of this timestamp since `UNIX_EPOCH`.
     pub fn to_unix_duration(&self) -> Duration {
         self.0.unix_duration()
@@ -161,6 +178,10 @@ impl FixedTag for UtcTime {
     const TAG: Tag = Tag::UtcTime;
 }
 
+impl EncodeFixed for UtcTime {
+    const MAX_ENCODED_LEN: usize = max_tlv_len(Self::TAG, Self::LENGTH);
+}
+
 impl OrdIsValueOrd for UtcTime {}
 
 impl From<&UtcTime> for UtcTime {
@@ -242,7 +263,7 @@ impl<'a> arbitrary::Arbitrary<'a> for UtcTime {
 #[allow(clippy::unwrap_used)]
 mod tests {
     use super::UtcTime;
-    use crate::{Decode, Encode, SliceWriter};
+    use crate::{DateTime, Decode, Encode, OutOfRange, SliceWriter};
     use hex_literal::hex;
 
     #[test]
@@ -256,4 +277,32 @@ mod tests {
         utc_time.encode(&mut encoder).unwrap();
         assert_eq!(example_bytes, encoder.finish().unwrap());
     }
+
+    #[test]
+    fn from_unix_secs() {
+        let utc_time = UtcTime::from_unix_secs(673573540, OutOfRange::Reject).unwrap();
+        assert_eq!(
+            utc_time,
+            UtcTime::from_der(&hex!("17 0d 39 31 30 35 30 36 32 33 34 35 34 30 5a")).unwrap()
+        );
+
+        // 2050-01-01T00:00:00Z can't be represented as a `UTCTime`
+        assert!(UtcTime::from_unix_secs(2_524_608_000, OutOfRange::Reject).is_err());
+        assert_eq!(
+            UtcTime::from_unix_secs(2_524_608_000, OutOfRange::Clamp)
+                .unwrap()
+                .to_date_time(),
+            DateTime::new(2049, 12, 31, 23, 59, 59).unwrap()
+        );
+        assert!(UtcTime::from_unix_secs(2_524_607_999, OutOfRange::Reject).is_ok());
+
+        assert!(UtcTime::from_unix_secs(-1, OutOfRange::Reject).is_err());
+        assert_eq!(
+            UtcTime::from_unix_secs(-1, OutOfRange::Clamp)
+                .unwrap()
+                .to_unix_duration()
+                .as_secs(),
+            0
+        );
+    }
 }
@@ -1,7 +1,7 @@
 //! ASN.1 `UTF8String` support.
 
 use crate::{
-    EncodeValue, Error, FixedTag, Length, Result, StringRef, Tag, Writer, asn1::AnyRef,
+    EncodeValue, Error, FixedTag, Length, Result, Size, StringRef, Tag, Writer, asn1::AnyRef,
     ord::OrdIsValueOrd,
 };
 use core::{fmt, ops::Deref, str};
@@ -61,6 +61,12 @@ impl FixedTag for Utf8StringRef<'_> {
     const TAG: Tag = Tag::Utf8String;
 }
 
+impl Size for Utf8StringRef<'_> {
+    fn size(&self) -> usize {
+        self.as_str().size()
+    }
+}
+
 impl<'a> From<&Utf8StringRef<'a>> for Utf8StringRef<'a> {
     fn from(value: &Utf8StringRef<'a>) -> Utf8StringRef<'a> {
         *value
@@ -111,6 +117,13 @@ impl FixedTag for str {
 
 impl OrdIsValueOrd for str {}
 
+/// Size of a `UTF8String` is its number of characters.
+impl Size for str {
+    fn size(&self) -> usize {
+        self.chars().count()
+    }
+}
+
 #[cfg(feature = "alloc")]
 impl<'a> From<Utf8StringRef<'a>> for String {
     fn from(s: Utf8StringRef<'a>) -> String {
@@ -164,6 +177,13 @@ impl FixedTag for String {
 #[cfg(feature = "alloc")]
 impl OrdIsValueOrd for String {}
 
+#[cfg(feature = "alloc")]
+impl Size for String {
+    fn size(&self) -> usize {
+        self.as_str().size()
+    }
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 mod tests {
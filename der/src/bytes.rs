@@ -131,6 +131,8 @@ pub(crate) mod allocating {
 
     use alloc::{borrow::ToOwned, boxed::Box, vec::Vec};
     use core::{borrow::Borrow, cmp::Ordering, ops::Deref};
+    #[cfg(feature = "zeroize")]
+    use zeroize::Zeroize;
 
     /// Byte slice newtype which respects the `Length::max()` limit.
     #[derive(Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
@@ -218,6 +220,13 @@ pub(crate) mod allocating {
         }
     }
 
+    #[cfg(feature = "zeroize")]
+    impl Zeroize for BytesOwned {
+        fn zeroize(&mut self) {
+            self.inner.zeroize();
+        }
+    }
+
     impl<'a> DecodeValue<'a> for BytesOwned {
         type Error = Error;
 
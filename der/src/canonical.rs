@@ -0,0 +1,436 @@
+//! Canonicalization of BER-encoded documents into DER.
+
+use crate::{
+    Decode, Encode, EncodingRules, Error, ErrorKind, Header, Length, Reader, Result, SliceReader,
+    Tag, document::Document, reader::read_value,
+};
+use alloc::vec::Vec;
+use core::fmt::{self, Debug};
+
+#[cfg(feature = "ber")]
+use crate::{DecodeValue, asn1::OctetString};
+
+/// Maximum nesting depth of constructed values which will be canonicalized.
+const MAX_DEPTH: usize = 64;
+
+/// Document encoded under the Basic Encoding Rules (BER).
+///
+/// The subset of BER supported by this crate is described in [`EncodingRules`]:
+/// in addition to DER, it allows indefinite lengths and constructed
+/// `OCTET STRING`s.
+///
+/// Such documents are typically converted into a [`DerDocument`] by
+/// [`BerDocument::to_der`] as soon as they're received, which gives
+/// applications an explicit boundary between the BER they accept and the DER
+/// they process, along with a [`Canonicalization`] report of the changes
+/// needed to cross it:
+///
+/// ```
+/// use der::BerDocument;
+///
+/// // SEQUENCE { BOOLEAN TRUE } with an indefinite length and a non-canonical BOOLEAN
+/// let ber = BerDocument::try_from(&[0x30, 0x80, 0x01, 0x01, 0x01, 0x00, 0x00][..])?;
+/// let (der, report) = ber.to_der()?;
+///
+/// assert_eq!(der.as_bytes(), &[0x30, 0x03, 0x01, 0x01, 0xFF]);
+/// assert_eq!(report.lengths_rewritten(), 1);
+/// assert_eq!(report.booleans_normalized(), 1);
+/// # Ok::<(), der::Error>(())
+/// ```
+#[cfg(feature = "ber")]
+#[derive(Clone, Eq, PartialEq)]
+pub struct BerDocument {
+    /// ASN.1 BER encoded bytes.
+    ber_bytes: Vec<u8>,
+}
+
+#[cfg(feature = "ber")]
+impl BerDocument {
+    /// Get the ASN.1 BER-encoded bytes of this document.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.ber_bytes.as_slice()
+    }
+
+    /// Convert to an ASN.1 BER-encoded byte vector.
+    pub fn into_vec(self) -> Vec<u8> {
+        self.ber_bytes
+    }
+
+    /// Try to decode the inner ASN.1 BER message contained in this
+    /// [`BerDocument`] as the given type.
+    pub fn decode_msg<'a, T: Decode<'a>>(&'a self) -> core::result::Result<T, T::Error> {
+        T::from_ber(self.as_bytes())
+    }
+
+    /// Convert this document into DER, reporting the changes which were
+    /// needed to do so.
+    ///
+    /// The conversion is lossy in that the original encoding can't be
+    /// recovered from the result, e.g. the original order of the elements
+    /// of a `SET` is lost once they're sorted.
+    ///
+    /// Only universal types are canonicalized, as the encoding of values
+    /// with `APPLICATION`, `CONTEXT-SPECIFIC` or `PRIVATE` tags can't be
+    /// interpreted without a schema.
+    pub fn to_der(&self) -> Result<(DerDocument, Canonicalization)> {
+        let mut canonicalizer = Canonicalizer::default();
+        let der_bytes = canonicalizer.canonicalize_document(self.as_bytes(), EncodingRules::Ber)?;
+        Ok((DerDocument { der_bytes }, canonicalizer.report))
+    }
+}
+
+#[cfg(feature = "ber")]
+impl AsRef<[u8]> for BerDocument {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+#[cfg(feature = "ber")]
+impl Debug for BerDocument {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("BerDocument(")?;
+
+        for byte in self.as_bytes() {
+            write!(f, "{byte:02X}")?;
+        }
+
+        f.write_str(")")
+    }
+}
+
+#[cfg(feature = "ber")]
+impl From<DerDocument> for BerDocument {
+    fn from(doc: DerDocument) -> BerDocument {
+        Self {
+            ber_bytes: doc.der_bytes,
+        }
+    }
+}
+
+#[cfg(feature = "ber")]
+impl TryFrom<&[u8]> for BerDocument {
+    type Error = Error;
+
+    fn try_from(ber_bytes: &[u8]) -> Result<Self> {
+        ber_bytes.to_vec().try_into()
+    }
+}
+
+#[cfg(feature = "ber")]
+impl TryFrom<Vec<u8>> for BerDocument {
+    type Error = Error;
+
+    fn try_from(ber_bytes: Vec<u8>) -> Result<Self> {
+        let mut reader = SliceReader::new_with_encoding_rules(&ber_bytes, EncodingRules::Ber)?;
+        reader.skip_tlv()?;
+        reader.finish()?;
+        Ok(Self { ber_bytes })
+    }
+}
+
+/// Document encoded under the Distinguished Encoding Rules (DER).
+///
+/// Unlike [`Document`], which only checks the outer `SEQUENCE` of a
+/// document, the structure of every TLV of a [`DerDocument`] is checked:
+///
+/// - tags and lengths are encoded in their minimal, definite form,
+/// - values of primitive universal types are primitive,
+/// - the elements of each `SET` or `SET OF` are sorted by their encoding,
+/// - `BOOLEAN`s are encoded as `0x00` or `0xFF`.
+///
+/// The contents of other primitive values aren't checked, e.g. `INTEGER`s
+/// may have redundant leading octets, so these are still only validated
+/// when decoding the document as a given type. Like
+/// [`BerDocument::to_der`], only universal types are checked.
+#[derive(Clone, Eq, PartialEq)]
+pub struct DerDocument {
+    /// ASN.1 DER encoded bytes.
+    der_bytes: Vec<u8>,
+}
+
+impl DerDocument {
+    /// Get the ASN.1 DER-encoded bytes of this document.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.der_bytes.as_slice()
+    }
+
+    /// Convert to an ASN.1 DER-encoded byte vector.
+    pub fn into_vec(self) -> Vec<u8> {
+        self.der_bytes
+    }
+
+    /// Try to decode the inner ASN.1 DER message contained in this
+    /// [`DerDocument`] as the given type.
+    pub fn decode_msg<'a, T: Decode<'a>>(&'a self) -> core::result::Result<T, T::Error> {
+        T::from_der(self.as_bytes())
+    }
+
+    /// Encode the provided type as ASN.1 DER, storing the resulting encoded DER
+    /// as a [`DerDocument`].
+    pub fn encode_msg<T: Encode>(msg: &T) -> Result<Self> {
+        msg.to_der()?.try_into()
+    }
+}
+
+impl AsRef<[u8]> for DerDocument {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl Debug for DerDocument {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("DerDocument(")?;
+
+        for byte in self.as_bytes() {
+            write!(f, "{byte:02X}")?;
+        }
+
+        f.write_str(")")
+    }
+}
+
+impl TryFrom<&[u8]> for DerDocument {
+    type Error = Error;
+
+    fn try_from(der_bytes: &[u8]) -> Result<Self> {
+        der_bytes.to_vec().try_into()
+    }
+}
+
+impl TryFrom<Vec<u8>> for DerDocument {
+    type Error = Error;
+
+    fn try_from(der_bytes: Vec<u8>) -> Result<Self> {
+        let mut canonicalizer = Canonicalizer {
+            strict: true,
+            ..Canonicalizer::default()
+        };
+        canonicalizer.canonicalize_document(&der_bytes, EncodingRules::Der)?;
+        Ok(Self { der_bytes })
+    }
+}
+
+impl TryFrom<DerDocument> for Document {
+    type Error = Error;
+
+    fn try_from(doc: DerDocument) -> Result<Document> {
+        doc.der_bytes.try_into()
+    }
+}
+
+/// Report of the changes applied when converting a [`BerDocument`] into a
+/// [`DerDocument`].
+///
+/// Each count is the number of TLVs which were changed in a given way.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Canonicalization {
+    lengths_rewritten: usize,
+    strings_merged: usize,
+    sets_sorted: usize,
+    booleans_normalized: usize,
+}
+
+impl Canonicalization {
+    /// Number of indefinite lengths rewritten as definite lengths.
+    pub fn lengths_rewritten(&self) -> usize {
+        self.lengths_rewritten
+    }
+
+    /// Number of constructed strings merged into primitive ones.
+    pub fn strings_merged(&self) -> usize {
+        self.strings_merged
+    }
+
+    /// Number of `SET`s whose elements were sorted.
+    pub fn sets_sorted(&self) -> usize {
+        self.sets_sorted
+    }
+
+    /// Number of `BOOLEAN`s whose `TRUE` value wasn't encoded as `0xFF`.
+    pub fn booleans_normalized(&self) -> usize {
+        self.booleans_normalized
+    }
+
+    /// Was the document already encoded as DER, i.e. were no changes needed?
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl fmt::Display for Canonicalization {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} lengths rewritten, {} strings merged, {} sets sorted, {} booleans normalized",
+            self.lengths_rewritten, self.strings_merged, self.sets_sorted, self.booleans_normalized
+        )
+    }
+}
+
+/// Walker re-encoding each TLV of a document as DER.
+#[derive(Debug, Default)]
+struct Canonicalizer {
+    /// Report of the changes applied so far.
+    report: Canonicalization,
+
+    /// Should non-canonical encodings be rejected rather than changed?
+    strict: bool,
+}
+
+impl Canonicalizer {
+    /// Canonicalize a document consisting of a single TLV.
+    fn canonicalize_document(&mut self, bytes: &[u8], rules: EncodingRules) -> Result<Vec<u8>> {
+        let mut reader = SliceReader::new_with_encoding_rules(bytes, rules)?;
+        let der_bytes = self.canonicalize_tlv(&mut reader, 0)?;
+        reader.finish()?;
+        Ok(der_bytes)
+    }
+
+    /// Canonicalize the next TLV of the given reader.
+    fn canonicalize_tlv<'a, R: Reader<'a>>(
+        &mut self,
+        reader: &mut R,
+        depth: usize,
+    ) -> Result<Vec<u8>> {
+        let header = Header::decode(reader)?;
+        let tag = header.tag();
+
+        if depth > MAX_DEPTH {
+            return Err(reader.error(ErrorKind::Overflow));
+        }
+
+        #[cfg(feature = "ber")]
+        if header.length().is_indefinite() {
+            self.report.lengths_rewritten += 1;
+        }
+
+        let value = if header.is_constructed() && !tag.is_constructed() {
+            // Only constructed `OCTET STRING`s are supported by the BER reader
+            #[cfg(feature = "ber")]
+            {
+                self.report.strings_merged += 1;
+                let octet_string = read_value(reader, header, OctetString::decode_value)?;
+                octet_string.into_bytes().into_vec()
+            }
+
+            #[cfg(not(feature = "ber"))]
+            return Err(reader.error(tag.non_canonical_error()));
+        } else if header.is_constructed() {
+            let mut elements = read_value(reader, header, |reader, _| {
+                let mut elements = Vec::new();
+
+                while !reader.is_finished() {
+                    elements.push(self.canonicalize_tlv(reader, depth + 1)?);
+                }
+
+                Ok::<_, Error>(elements)
+            })?;
+
+            if tag == Tag::Set && !elements.is_sorted() {
+                if self.strict {
+                    return Err(reader.error(ErrorKind::SetOrdering));
+                }
+
+                self.report.sets_sorted += 1;
+                elements.sort();
+            }
+
+            elements.concat()
+        } else {
+            let value = read_value(reader, header, |reader, _| {
+                reader.read_slice(reader.remaining_len())
+            })?;
+
+            match value {
+                [byte] if tag == Tag::Boolean && *byte != 0x00 && *byte != 0xFF => {
+                    if self.strict {
+                        return Err(reader.error(tag.non_canonical_error()));
+                    }
+
+                    self.report.booleans_normalized += 1;
+                    Vec::from([0xFF])
+                }
+                _ => value.to_vec(),
+            }
+        };
+
+        let mut tlv = Vec::new();
+        Header::new(tag, Length::try_from(value.len())?).encode_to_vec(&mut tlv)?;
+        tlv.extend_from_slice(&value);
+        Ok(tlv)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::DerDocument;
+    use crate::{ErrorKind, Tag};
+    use hex_literal::hex;
+
+    #[cfg(feature = "ber")]
+    use super::{BerDocument, Canonicalization};
+
+    #[test]
+    #[cfg(feature = "ber")]
+    fn ber_to_der() {
+        // SEQUENCE (indefinite) {
+        //   OCTET STRING (constructed, indefinite) { "ab", "c" },
+        //   SET { INTEGER 2, INTEGER 1 },
+        //   BOOLEAN 0x01
+        // }
+        let ber = BerDocument::try_from(
+            &hex!(
+                "30 80
+                   24 80 04 02 61 62 04 01 63 00 00
+                   31 06 02 01 02 02 01 01
+                   01 01 01
+                 00 00"
+            )[..],
+        )
+        .unwrap();
+
+        let (der, report) = ber.to_der().unwrap();
+        assert_eq!(
+            der.as_bytes(),
+            hex!("30 10 04 03 61 62 63 31 06 02 01 01 02 01 02 01 01 FF")
+        );
+        assert_eq!(report.lengths_rewritten(), 2);
+        assert_eq!(report.strings_merged(), 1);
+        assert_eq!(report.sets_sorted(), 1);
+        assert_eq!(report.booleans_normalized(), 1);
+        assert!(!report.is_empty());
+
+        // Canonicalization is idempotent
+        let (again, report) = BerDocument::from(der.clone()).to_der().unwrap();
+        assert_eq!(again, der);
+        assert_eq!(report, Canonicalization::default());
+        assert!(report.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "ber")]
+    fn ber_malformed() {
+        assert!(BerDocument::try_from(&hex!("30 80 02 01 01")[..]).is_err());
+        assert!(BerDocument::try_from(&hex!("30 03 02 01 01 00")[..]).is_err());
+    }
+
+    #[test]
+    fn der_strict() {
+        let doc = DerDocument::try_from(&hex!("30 07 31 05 01 01 00 05 00")[..]).unwrap();
+        assert_eq!(doc.as_bytes(), hex!("30 07 31 05 01 01 00 05 00"));
+
+        let err = DerDocument::try_from(&hex!("31 06 02 01 02 02 01 01")[..]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::SetOrdering);
+
+        let err = DerDocument::try_from(&hex!("30 03 01 01 01")[..]).unwrap_err();
+        assert_eq!(err.kind(), Tag::Boolean.non_canonical_error());
+
+        assert!(DerDocument::try_from(&hex!("30 80 05 00 00 00")[..]).is_err());
+        assert!(DerDocument::try_from(&hex!("24 03 04 01 61")[..]).is_err());
+        assert!(DerDocument::try_from(&hex!("2C 03 0C 01 61")[..]).is_err());
+        assert!(DerDocument::try_from(&hex!("30 81 02 05 00")[..]).is_err());
+    }
+}
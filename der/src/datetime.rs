@@ -240,6 +240,101 @@ impl DateTime {
         )
     }
 
+    /// Parse a [`DateTime`] from an [RFC 3339] timestamp, e.g.
+    /// `2001-01-02T12:13:14Z` or `2001-01-02 14:13:14.000+02:00`.
+    ///
+    /// Unlike the [`FromStr`] impl, which only accepts the canonical form
+    /// produced by the [`fmt::Display`] impl, this accepts:
+    ///
+    /// - lowercase `t`/`z` and a space as the date/time separator
+    /// - fractional seconds, provided they're zero (since [`DateTime`] has
+    ///   a precision of one second)
+    /// - numeric UTC offsets, which are normalized to UTC
+    ///
+    /// Leap seconds are not supported.
+    ///
+    /// [RFC 3339]: https://datatracker.ietf.org/doc/html/rfc3339#section-5.6
+    pub fn from_rfc3339_str(s: &str) -> Result<Self> {
+        let (head, mut rest) = s
+            .as_bytes()
+            .split_at_checked(19)
+            .ok_or(ErrorKind::DateTime)?;
+
+        let [
+            year1,
+            year2,
+            year3,
+            year4,
+            b'-',
+            month1,
+            month2,
+            b'-',
+            day1,
+            day2,
+            b'T' | b't' | b' ',
+            hour1,
+            hour2,
+            b':',
+            min1,
+            min2,
+            b':',
+            sec1,
+            sec2,
+        ] = *head
+        else {
+            return Err(ErrorKind::DateTime.into());
+        };
+
+        // Fractional seconds
+        if let [b'.', frac @ ..] = rest {
+            let ndigits = frac.iter().take_while(|c| c.is_ascii_digit()).count();
+            let (digits, remaining) = frac.split_at(ndigits);
+
+            if digits.is_empty() || digits.iter().any(|&c| c != b'0') {
+                return Err(ErrorKind::DateTime.into());
+            }
+
+            rest = remaining;
+        }
+
+        let offset_secs = match *rest {
+            [b'Z' | b'z'] => 0,
+            [sign @ (b'+' | b'-'), hour1, hour2, b':', min1, min2] => {
+                let hours = decode_rfc3339_decimal(hour1, hour2)?;
+                let minutes = decode_rfc3339_decimal(min1, min2)?;
+
+                if hours > 23 || minutes > 59 {
+                    return Err(ErrorKind::DateTime.into());
+                }
+
+                let secs = i64::from(hours) * 3600 + i64::from(minutes) * 60;
+
+                if sign == b'-' { -secs } else { secs }
+            }
+            _ => return Err(ErrorKind::DateTime.into()),
+        };
+
+        let local = Self::new(
+            decode_year(&[year1, year2, year3, year4])?,
+            decode_rfc3339_decimal(month1, month2)?,
+            decode_rfc3339_decimal(day1, day2)?,
+            decode_rfc3339_decimal(hour1, hour2)?,
+            decode_rfc3339_decimal(min1, min2)?,
+            decode_rfc3339_decimal(sec1, sec2)?,
+        )?;
+
+        if offset_secs == 0 {
+            return Ok(local);
+        }
+
+        let utc_secs = i64::try_from(local.unix_duration.as_secs())?
+            .checked_sub(offset_secs)
+            .and_then(|secs| u64::try_from(secs).ok())
+            .ok_or(ErrorKind::DateTime)?;
+
+        Self::from_unix_duration(Duration::from_secs(utc_secs))
+    }
+
     /// Get the year.
     pub fn year(&self) -> u16 {
         self.year
@@ -275,6 +370,19 @@ impl DateTime {
         self.unix_duration
     }
 
+    /// Create a [`DateTime`] from the given number of seconds since `UNIX_EPOCH`
+    /// (a.k.a. "Unix time"), handling timestamps before 1970 or after 9999
+    /// according to the given [`OutOfRange`] policy.
+    ///
+    /// Unix time doesn't count leap seconds, so timestamps never denote a
+    /// 60th second, which can't be represented by a [`DateTime`] either.
+    pub fn from_unix_secs(secs: i64, out_of_range: OutOfRange) -> Result<Self> {
+        out_of_range
+            .unix_duration(secs, MAX_UNIX_DURATION)
+            .ok_or_else(|| ErrorKind::DateTime.into())
+            .and_then(Self::from_unix_duration)
+    }
+
     /// Instantiate from [`SystemTime`].
     #[cfg(feature = "std")]
     pub fn from_system_time(time: SystemTime) -> Result<Self> {
@@ -290,6 +398,39 @@ impl DateTime {
     }
 }
 
+/// Policy for handling timestamps outside of the range supported by a time type,
+/// e.g. when calling [`DateTime::from_unix_secs`].
+///
+/// All time types only support times from the Unix epoch onwards, so this also
+/// applies to the years 1950-1969 which RFC 5280 allows for `UTCTime`.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum OutOfRange {
+    /// Return an error.
+    #[default]
+    Reject,
+
+    /// Clamp the timestamp to the nearest supported time, e.g. to the Unix epoch
+    /// or [`DateTime::INFINITY`].
+    ///
+    /// This suits e.g. the `notAfter` time of a certificate, where a time beyond
+    /// the supported range means the certificate has no well-defined expiration.
+    Clamp,
+}
+
+impl OutOfRange {
+    /// Convert the given Unix timestamp to a [`Duration`] since `UNIX_EPOCH`
+    /// of at most `max`, returning `None` if it's rejected.
+    pub(crate) fn unix_duration(self, secs: i64, max: Duration) -> Option<Duration> {
+        match u64::try_from(secs) {
+            Ok(secs) if secs <= max.as_secs() => Some(Duration::from_secs(secs)),
+            _ if self == Self::Reject => None,
+            Ok(_) => Some(max),
+            Err(_) => Some(Duration::ZERO),
+        }
+    }
+}
+
 impl FromStr for DateTime {
     type Err = Error;
 
@@ -331,6 +472,10 @@ impl FromStr for DateTime {
     }
 }
 
+/// Formats the [`DateTime`] as an [RFC 3339] timestamp in UTC, e.g.
+/// `2001-01-02T12:13:14Z`, without allocating.
+///
+/// [RFC 3339]: https://datatracker.ietf.org/doc/html/rfc3339#section-5.6
 impl fmt::Display for DateTime {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -445,6 +590,11 @@ where
     writer.write_byte(b'0'.checked_add(value % 10).ok_or(ErrorKind::Overflow)?)
 }
 
+/// Decode 2-digit decimal value within an RFC 3339 timestamp.
+fn decode_rfc3339_decimal(hi: u8, lo: u8) -> Result<u8> {
+    decode_decimal(Tag::GeneralizedTime, hi, lo).map_err(|_| ErrorKind::DateTime.into())
+}
+
 /// Decode 4-digit year.
 // TODO(tarcieri): checked arithmetic
 #[allow(clippy::arithmetic_side_effects)]
@@ -468,13 +618,32 @@ mod const_range {
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 mod tests {
-    use super::DateTime;
+    use super::{DateTime, OutOfRange};
 
     /// Ensure a day is OK
     fn is_date_valid(year: u16, month: u8, day: u8, hour: u8, minute: u8, second: u8) -> bool {
         DateTime::new(year, month, day, hour, minute, second).is_ok()
     }
 
+    #[test]
+    fn from_unix_secs() {
+        let datetime = DateTime::from_unix_secs(673573540, OutOfRange::Reject).unwrap();
+        assert_eq!(datetime, DateTime::new(1991, 5, 6, 23, 45, 40).unwrap());
+
+        for secs in [-1, i64::MIN, 253_402_300_800, i64::MAX] {
+            assert!(DateTime::from_unix_secs(secs, OutOfRange::Reject).is_err());
+        }
+
+        assert_eq!(
+            DateTime::from_unix_secs(-1, OutOfRange::Clamp).unwrap(),
+            DateTime::new(1970, 1, 1, 0, 0, 0).unwrap()
+        );
+        assert_eq!(
+            DateTime::from_unix_secs(i64::MAX, OutOfRange::Clamp).unwrap(),
+            DateTime::INFINITY
+        );
+    }
+
     #[test]
     fn feb_leap_year_handling() {
         assert!(is_date_valid(2000, 2, 29, 0, 0, 0));
@@ -516,6 +685,69 @@ mod tests {
         assert_eq!(datetime.seconds(), 14);
     }
 
+    #[test]
+    fn from_rfc3339_str() {
+        let expected = DateTime::new(2001, 1, 2, 12, 13, 14).unwrap();
+
+        for s in [
+            "2001-01-02T12:13:14Z",
+            "2001-01-02t12:13:14z",
+            "2001-01-02 12:13:14Z",
+            "2001-01-02T12:13:14.000Z",
+            "2001-01-02T14:43:14+02:30",
+            "2001-01-02T07:13:14-05:00",
+            "2001-01-01T23:13:14-13:00",
+        ] {
+            assert_eq!(DateTime::from_rfc3339_str(s).unwrap(), expected, "{s}");
+        }
+    }
+
+    #[test]
+    fn from_rfc3339_str_invalid() {
+        for s in [
+            "",
+            "2001-01-02T12:13:14",
+            "2001-01-02T12:13:14.Z",
+            "2001-01-02T12:13:14.5Z",
+            "2001-01-02T12:13:14+02",
+            "2001-01-02T12:13:14+24:00",
+            "2001-01-02T12:13:60Z",
+            "2001-01-02X12:13:14Z",
+            "2001-13-02T12:13:14Z",
+            "1970-01-01T00:00:00+00:01",
+        ] {
+            assert!(DateTime::from_rfc3339_str(s).is_err(), "{s}");
+        }
+    }
+
+    #[test]
+    fn display_no_alloc() {
+        use core::fmt::Write;
+
+        struct Buf([u8; 20], usize);
+
+        impl Write for Buf {
+            fn write_str(&mut self, s: &str) -> core::fmt::Result {
+                let end = self.1 + s.len();
+                self.0
+                    .get_mut(self.1..end)
+                    .ok_or(core::fmt::Error)?
+                    .copy_from_slice(s.as_bytes());
+                self.1 = end;
+                Ok(())
+            }
+        }
+
+        let datetime = DateTime::new(2001, 1, 2, 12, 13, 14).unwrap();
+        let mut buf = Buf([0; 20], 0);
+        write!(buf, "{datetime}").unwrap();
+        assert_eq!(&buf.0, b"2001-01-02T12:13:14Z");
+        assert_eq!(
+            DateTime::from_rfc3339_str(core::str::from_utf8(&buf.0).unwrap()).unwrap(),
+            datetime
+        );
+    }
+
     #[cfg(feature = "alloc")]
     #[test]
     fn display() {
@@ -1,6 +1,6 @@
 //! Trait definition for [`Decode`].
 
-use crate::{Error, FixedTag, Header, Reader, SliceReader, reader::read_value};
+use crate::{Error, FixedTag, Header, Reader, SliceReader, reader::read_value, trace};
 
 use core::marker::PhantomData;
 
@@ -72,6 +72,7 @@ where
 
     fn decode<R: Reader<'a>>(reader: &mut R) -> Result<T, <T as DecodeValue<'a>>::Error> {
         let header = Header::decode(reader)?;
+        let _span = trace::decode_span::<T>(&header);
         header.tag().assert_eq(T::TAG)?;
         read_value(reader, header, T::decode_value)
     }
@@ -108,7 +109,11 @@ impl<T> DecodeOwned for T where T: for<'a> Decode<'a> {}
 /// PEM decoding trait.
 ///
 /// This trait is automatically impl'd for any type which impls both
-/// [`DecodeOwned`] and [`PemLabel`].
+/// [`DecodeOwned`] and [`PemLabel`], so a format crate only needs to impl
+/// [`PemLabel`] for its document types to get [`DecodePem::from_pem`].
+///
+/// The document type may use its own [`Decode::Error`], as long as it is the
+/// same for all lifetimes.
 #[cfg(feature = "pem")]
 #[diagnostic::on_unimplemented(
     note = "`DecodePem` is auto-impl'd for all lifetime-free types which impl both `Decode` and `PemLabel`"
@@ -119,8 +124,12 @@ pub trait DecodePem: DecodeOwned + PemLabel {
 }
 
 #[cfg(feature = "pem")]
-impl<T: DecodeOwned<Error = Error> + PemLabel> DecodePem for T {
-    fn from_pem(pem: impl AsRef<[u8]>) -> Result<T, Error> {
+impl<T, E> DecodePem for T
+where
+    T: for<'a> Decode<'a, Error = E> + PemLabel,
+    E: From<Error> + 'static,
+{
+    fn from_pem(pem: impl AsRef<[u8]>) -> Result<T, E> {
         let mut reader = PemReader::new(pem.as_ref())?;
         Self::validate_pem_label(reader.type_label()).map_err(Error::from)?;
         T::decode(&mut reader)
@@ -234,9 +234,14 @@ impl SecretDocument {
     }
 
     /// Decode ASN.1 DER document from PEM.
+    ///
+    /// The PEM is decoded using [`pem::decode_secret_vec`], which is constant-time with respect
+    /// to the encapsulated text, and the decoded bytes are zeroized once copied into the
+    /// document, or if they aren't a valid DER document.
     #[cfg(feature = "pem")]
     pub fn from_pem(pem: &str) -> Result<(&str, Self), Error> {
-        Document::from_pem(pem).map(|(label, doc)| (label, Self(doc)))
+        let (label, der_bytes) = pem::decode_secret_vec(pem.as_bytes())?;
+        Ok((label, Self(Document::try_from(der_bytes.as_slice())?)))
     }
 
     /// Encode ASN.1 DER document as a PEM string.
@@ -264,7 +269,8 @@ impl SecretDocument {
     /// Read PEM-encoded ASN.1 DER document from a file.
     #[cfg(all(feature = "pem", feature = "std"))]
     pub fn read_pem_file(path: impl AsRef<Path>) -> Result<(String, Self), Error> {
-        Document::read_pem_file(path).map(|(label, doc)| (label, Self(doc)))
+        let pem = Zeroizing::new(fs::read_to_string(path)?);
+        Self::from_pem(&pem).map(|(label, doc)| (label.to_owned(), doc))
     }
 
     /// Write PEM-encoded ASN.1 DER document to a file.
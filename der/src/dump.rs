@@ -0,0 +1,268 @@
+//! Human-readable dumps of DER documents, in the style of `openssl asn1parse`.
+
+use crate::{Decode, Header, Reader, SliceReader, Tag};
+use core::fmt;
+
+/// Maximum number of bytes of a primitive value shown in its preview.
+const PREVIEW_LEN: usize = 32;
+
+/// Maximum nesting depth of constructed values which will be dumped.
+const MAX_DEPTH: usize = 64;
+
+/// Display adapter which renders arbitrary DER bytes as a tree of TLVs, e.g.
+/// for debugging decoding failures without external tools.
+///
+/// Each line contains the offset of a TLV, its nesting depth, header and value
+/// lengths, whether it's primitive or constructed, its tag, and a preview of
+/// primitive values:
+///
+/// ```
+/// use der::dump::Dump;
+///
+/// let der = [0x30, 0x08, 0x02, 0x01, 0x2A, 0x0C, 0x03, 0x66, 0x6F, 0x6F];
+///
+/// assert_eq!(
+///     Dump::new(&der).to_string(),
+///     "    0:d=0  hl=2 l=   8 cons: SEQUENCE\n\
+///     \x20   2:d=1  hl=2 l=   1 prim: INTEGER           :2A\n\
+///     \x20   5:d=1  hl=2 l=   3 prim: UTF8String        :foo\n"
+/// );
+/// ```
+///
+/// Malformed input doesn't cause formatting to fail: instead, an `error` line
+/// is written at the offset of the first malformed TLV and dumping stops.
+#[derive(Clone, Copy, Debug)]
+pub struct Dump<'a> {
+    /// DER bytes to be dumped.
+    bytes: &'a [u8],
+}
+
+impl<'a> Dump<'a> {
+    /// Create a new [`Dump`] of the given DER bytes.
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    /// Dump the TLVs in `bytes`, located at `offset` in the document, returning
+    /// `false` if dumping was aborted due to an error.
+    fn dump(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        bytes: &[u8],
+        offset: usize,
+        depth: usize,
+    ) -> Result<bool, fmt::Error> {
+        let mut pos = 0;
+
+        while pos < bytes.len() {
+            let tlv_offset = offset + pos;
+
+            if depth > MAX_DEPTH {
+                writeln!(f, "{tlv_offset:>5}:error: maximum nesting depth exceeded")?;
+                return Ok(false);
+            }
+
+            let (header, header_len) = match decode_header(&bytes[pos..]) {
+                Ok(header) => header,
+                Err(err) => {
+                    // The error's position is relative to `bytes`, so only write its kind
+                    writeln!(f, "{tlv_offset:>5}:error: {}", err.kind())?;
+                    return Ok(false);
+                }
+            };
+
+            let value_len = usize::try_from(header.length()).map_err(|_| fmt::Error)?;
+            let value_start = pos + header_len;
+            let value = &bytes[value_start..value_start + value_len];
+            let tag = header.tag();
+
+            write!(
+                f,
+                "{tlv_offset:>5}:d={depth:<2} hl={header_len} l={value_len:>4} {}: ",
+                if tag.is_constructed() { "cons" } else { "prim" }
+            )?;
+
+            if tag.is_constructed() {
+                writeln!(f, "{tag}")?;
+
+                if !self.dump(f, value, offset + value_start, depth + 1)? {
+                    return Ok(false);
+                }
+            } else {
+                write_padded(f, tag, 18)?;
+                write_preview(f, tag, value)?;
+                writeln!(f)?;
+            }
+
+            pos = value_start + value_len;
+        }
+
+        Ok(true)
+    }
+}
+
+impl fmt::Display for Dump<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.dump(f, self.bytes, 0, 0).map(|_| ())
+    }
+}
+
+/// Decode the header at the start of `bytes`, returning it along with its
+/// encoded length.
+///
+/// The header's length is guaranteed to fit in the remaining bytes.
+fn decode_header(bytes: &[u8]) -> crate::Result<(Header, usize)> {
+    let mut reader = SliceReader::new(bytes)?;
+    let header = Header::decode(&mut reader)?;
+    let header_len = usize::try_from(reader.position())?;
+
+    if reader.remaining_len() < header.length() {
+        return Err(reader.error(header.tag().length_error()));
+    }
+
+    Ok((header, header_len))
+}
+
+/// Write a preview of a primitive value.
+fn write_preview(f: &mut fmt::Formatter<'_>, tag: Tag, value: &[u8]) -> fmt::Result {
+    match tag {
+        Tag::Null => Ok(()),
+        Tag::Boolean => match value {
+            [0] => f.write_str(":FALSE"),
+            [_] => f.write_str(":TRUE"),
+            _ => write_hex(f, value),
+        },
+        #[cfg(feature = "oid")]
+        Tag::ObjectIdentifier => match crate::asn1::ObjectIdentifier::from_bytes(value) {
+            Ok(oid) => write!(f, ":{oid}"),
+            Err(_) => write_hex(f, value),
+        },
+        Tag::Utf8String
+        | Tag::PrintableString
+        | Tag::Ia5String
+        | Tag::NumericString
+        | Tag::VisibleString
+        | Tag::TeletexString
+        | Tag::UtcTime
+        | Tag::GeneralizedTime
+        | Tag::TimeOfDay
+        | Tag::Duration => match core::str::from_utf8(value) {
+            Ok(s) => write_str(f, s),
+            Err(_) => write_hex(f, value),
+        },
+        _ => write_hex(f, value),
+    }
+}
+
+/// Write a preview of a string value, escaping control characters.
+fn write_str(f: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+    f.write_str(":")?;
+
+    for (i, c) in s.chars().enumerate() {
+        if i == PREVIEW_LEN {
+            return f.write_str("...");
+        }
+
+        if c.is_control() {
+            write!(f, "{}", c.escape_default())?;
+        } else {
+            write!(f, "{c}")?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Write a preview of a value as upper case hex.
+fn write_hex(f: &mut fmt::Formatter<'_>, value: &[u8]) -> fmt::Result {
+    if value.is_empty() {
+        return Ok(());
+    }
+
+    f.write_str(":")?;
+
+    for byte in value.iter().take(PREVIEW_LEN) {
+        write!(f, "{byte:02X}")?;
+    }
+
+    if value.len() > PREVIEW_LEN {
+        f.write_str("...")?;
+    }
+
+    Ok(())
+}
+
+/// Write the given tag, padded with spaces to the given width.
+fn write_padded(f: &mut fmt::Formatter<'_>, tag: Tag, width: usize) -> fmt::Result {
+    // `Tag`'s `Display` impl writes its parts separately, which ignores padding
+    let mut counter = Counter(0);
+    fmt::write(&mut counter, format_args!("{tag}"))?;
+    write!(f, "{tag}")?;
+
+    for _ in counter.0..width {
+        f.write_str(" ")?;
+    }
+
+    Ok(())
+}
+
+/// Writer which counts the number of characters written to it.
+struct Counter(usize);
+
+impl fmt::Write for Counter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0 += s.chars().count();
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::Dump;
+    use alloc::string::ToString;
+
+    #[test]
+    fn nested() {
+        let der = [
+            0x30, 0x0A, 0x31, 0x05, 0x01, 0x01, 0xFF, 0x05, 0x00, 0x04, 0x01, 0xAB,
+        ];
+
+        assert_eq!(
+            Dump::new(&der[..])
+                .to_string()
+                .lines()
+                .collect::<alloc::vec::Vec<_>>(),
+            [
+                "    0:d=0  hl=2 l=  10 cons: SEQUENCE",
+                "    2:d=1  hl=2 l=   5 cons: SET",
+                "    4:d=2  hl=2 l=   1 prim: BOOLEAN           :TRUE",
+                "    7:d=2  hl=2 l=   0 prim: NULL              ",
+                "    9:d=1  hl=2 l=   1 prim: OCTET STRING      :AB",
+            ]
+        );
+    }
+
+    #[test]
+    fn truncated() {
+        let der = [0x30, 0x06, 0x02, 0x01, 0x2A, 0x02, 0x05, 0x00];
+
+        assert_eq!(
+            Dump::new(&der).to_string(),
+            "    0:d=0  hl=2 l=   6 cons: SEQUENCE\n\
+             \x20   2:d=1  hl=2 l=   1 prim: INTEGER           :2A\n\
+             \x20   5:error: incorrect length for INTEGER\n"
+        );
+    }
+
+    #[cfg(feature = "oid")]
+    #[test]
+    fn oid() {
+        let der = [0x06, 0x03, 0x55, 0x04, 0x03];
+
+        assert_eq!(
+            Dump::new(&der).to_string(),
+            "    0:d=0  hl=2 l=   3 prim: OBJECT IDENTIFIER :2.5.4.3\n"
+        );
+    }
+}
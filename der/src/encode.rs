@@ -1,6 +1,6 @@
 //! Trait definition for [`Encode`].
 
-use crate::{Header, Length, Result, SliceWriter, Tagged, Writer};
+use crate::{Header, Length, Result, SliceWriter, Tagged, Writer, trace};
 use core::marker::PhantomData;
 
 #[cfg(feature = "alloc")]
@@ -38,6 +38,18 @@ pub trait Encode {
         writer.finish()
     }
 
+    /// Encode this value to the provided byte slice with deferred lengths,
+    /// returning a sub-slice containing the encoded message.
+    ///
+    /// Rather than computing the length of each nested value before encoding
+    /// it, lengths are inserted after each value is written. See
+    /// [`SliceWriter::new_deferred_length`] for more information.
+    fn encode_to_slice_deferred_length<'a>(&self, buf: &'a mut [u8]) -> Result<&'a [u8]> {
+        let mut writer = SliceWriter::new_deferred_length(buf);
+        self.encode(&mut writer)?;
+        writer.finish()
+    }
+
     /// Encode this message as ASN.1 DER, appending it to the provided
     /// byte vector.
     #[cfg(feature = "alloc")]
@@ -67,6 +79,19 @@ pub trait Encode {
         self.encode_to_vec(&mut buf)?;
         Ok(buf)
     }
+
+    /// Encode this type as DER, returning a byte vector.
+    ///
+    /// Like [`Encode::to_der`], but only computes [`Encode::encoded_len`] once,
+    /// for the whole message, to size the output buffer, encoding the message
+    /// itself using [`Encode::encode_to_slice_deferred_length`].
+    #[cfg(feature = "alloc")]
+    fn to_der_deferred_length(&self) -> Result<Vec<u8>> {
+        let mut buf = vec![0u8; usize::try_from(self.encoded_len()?)?];
+        let actual_len = self.encode_to_slice_deferred_length(&mut buf)?.len();
+        buf.truncate(actual_len);
+        Ok(buf)
+    }
 }
 
 impl<T> Encode for T
@@ -80,7 +105,23 @@ where
 
     /// Encode this value as ASN.1 DER using the provided [`Writer`].
     fn encode(&self, writer: &mut impl Writer) -> Result<()> {
-        self.header()?.encode(writer)?;
+        let tag = self.tag();
+
+        // Insert the length after the fact if supported by the writer
+        if let Some(start) = writer.begin_deferred_length() {
+            let _span = trace::encode_span::<Self>(tag, None);
+            tag.encode(writer)?;
+            let length_start = (start + tag.encoded_len()?)?;
+
+            // Placeholder for the length, which is enough for the short form
+            writer.write_byte(0)?;
+            self.encode_value(writer)?;
+            return writer.finish_deferred_length(length_start);
+        }
+
+        let header = self.header()?;
+        let _span = trace::encode_span::<Self>(tag, Some(header.length()));
+        header.encode(writer)?;
         self.encode_value(writer)
     }
 }
@@ -0,0 +1,182 @@
+//! Encoding of values whose length is statically bounded to stack-allocated arrays.
+
+use crate::{Encode, Length, Result, Tag};
+use core::fmt;
+
+/// Encoding of types whose DER encoding has a statically known maximum length,
+/// allowing them to be encoded without heap allocation, e.g. on embedded
+/// targets.
+///
+/// The size of the output array is checked against
+/// [`EncodeFixed::MAX_ENCODED_LEN`] at compile time:
+///
+/// ```
+/// use der::{EncodeFixed, Tag, encode_fixed::max_tlv_len};
+///
+/// assert_eq!(u8::MAX_ENCODED_LEN, 4);
+/// assert_eq!(<[u8; 2]>::MAX_ENCODED_LEN, max_tlv_len(Tag::Sequence, 8));
+///
+/// let encoded = 255u8.encode_to_array::<{ u8::MAX_ENCODED_LEN }>()?;
+/// assert_eq!(encoded.as_bytes(), &[0x02, 0x02, 0x00, 0xFF]);
+/// # Ok::<(), der::Error>(())
+/// ```
+///
+/// It can be derived on structs with the `Sequence` custom derive using the
+/// `#[asn1(encode_fixed)]` attribute, provided all of their fields impl
+/// [`EncodeFixed`].
+pub trait EncodeFixed: Encode {
+    /// Maximum length of the DER encoding of any value of this type in bytes,
+    /// including its tag and length.
+    const MAX_ENCODED_LEN: usize;
+
+    /// Encode this value as ASN.1 DER to an array of `N` bytes.
+    ///
+    /// Fails to compile if `N` is smaller than [`EncodeFixed::MAX_ENCODED_LEN`]:
+    ///
+    /// ```compile_fail
+    /// use der::EncodeFixed;
+    ///
+    /// let encoded = 255u8.encode_to_array::<3>();
+    /// ```
+    fn encode_to_array<const N: usize>(&self) -> Result<EncodedArray<N>> {
+        const {
+            assert!(
+                N >= Self::MAX_ENCODED_LEN,
+                "array is smaller than the maximum encoded length of the type"
+            )
+        };
+
+        let mut bytes = [0u8; N];
+        let len = Length::try_from(self.encode_to_slice(&mut bytes)?.len())?;
+        Ok(EncodedArray { bytes, len })
+    }
+}
+
+impl<T: EncodeFixed> EncodeFixed for Option<T> {
+    const MAX_ENCODED_LEN: usize = T::MAX_ENCODED_LEN;
+}
+
+/// DER encoding of a value stored in an array of `N` bytes, returned by
+/// [`EncodeFixed::encode_to_array`].
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+pub struct EncodedArray<const N: usize> {
+    /// Array containing the encoding, followed by zeroes.
+    bytes: [u8; N],
+
+    /// Length of the encoding.
+    len: Length,
+}
+
+impl<const N: usize> EncodedArray<N> {
+    /// Get the DER encoding as bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        let len = u32::from(self.len) as usize;
+        &self.bytes[..len]
+    }
+
+    /// Get the length of the DER encoding.
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> Length {
+        self.len
+    }
+}
+
+impl<const N: usize> AsRef<[u8]> for EncodedArray<N> {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl<const N: usize> fmt::Debug for EncodedArray<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("EncodedArray")
+            .field(&self.as_bytes())
+            .finish()
+    }
+}
+
+/// Compute the maximum length of the TLV encoding of a value with the given
+/// [`Tag`] whose value is at most `max_value_len` bytes long.
+pub const fn max_tlv_len(tag: Tag, max_value_len: usize) -> usize {
+    tag_len(tag) + length_len(max_value_len) + max_value_len
+}
+
+/// Compute the maximum length of the encoding of a value IMPLICITly tagged
+/// with the given [`Tag`], whose untagged encoding is at most
+/// `max_inner_len` bytes long.
+pub const fn max_implicit_len(tag: Tag, max_inner_len: usize) -> usize {
+    // The replaced tag of the inner encoding is at least 1 byte long
+    tag_len(tag) + max_inner_len.saturating_sub(1)
+}
+
+/// Length of the encoding of the given [`Tag`].
+const fn tag_len(tag: Tag) -> usize {
+    match tag.number().0 {
+        0..=30 => 1,
+        number => number.ilog2() as usize / 7 + 2,
+    }
+}
+
+/// Length of the DER encoding of the given length.
+const fn length_len(len: usize) -> usize {
+    match len {
+        0..=0x7F => 1,
+        0x80..=0xFF => 2,
+        0x100..=0xFFFF => 3,
+        0x10000..=0xFFFFFF => 4,
+        _ => 5,
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::{EncodeFixed, max_implicit_len, max_tlv_len};
+    use crate::{Encode, Length, Tag, TagNumber};
+
+    #[test]
+    fn integers() {
+        assert_eq!(
+            u8::MAX_ENCODED_LEN,
+            u8::MAX.encoded_len().unwrap().try_into().unwrap()
+        );
+        assert_eq!(
+            i64::MAX_ENCODED_LEN,
+            i64::MIN.encoded_len().unwrap().try_into().unwrap()
+        );
+        assert_eq!(
+            u128::MAX_ENCODED_LEN,
+            u128::MAX.encoded_len().unwrap().try_into().unwrap()
+        );
+
+        let encoded = 0u32.encode_to_array::<{ u32::MAX_ENCODED_LEN }>().unwrap();
+        assert_eq!(encoded.as_bytes(), &[0x02, 0x01, 0x00]);
+        assert_eq!(encoded.len(), Length::new(3));
+    }
+
+    #[test]
+    fn sequence_of() {
+        let array = [u16::MAX; 40];
+        let mut buf = [0u8; 256];
+        let encoded = array.encode_to_array::<{ <[u16; 40]>::MAX_ENCODED_LEN }>();
+        assert_eq!(
+            encoded.unwrap().as_bytes(),
+            array.encode_to_slice(&mut buf).unwrap()
+        );
+        assert_eq!(<[u16; 40]>::MAX_ENCODED_LEN, 3 + 40 * 5);
+    }
+
+    #[test]
+    fn tag_lengths() {
+        let tag = |number| Tag::ContextSpecific {
+            constructed: true,
+            number: TagNumber(number),
+        };
+
+        assert_eq!(max_tlv_len(tag(30), 0x7F), 0x81);
+        assert_eq!(max_tlv_len(tag(31), 0x80), 0x84);
+        assert_eq!(max_tlv_len(tag(0x4000), 0x100), 0x104 + 3);
+        assert_eq!(max_implicit_len(tag(0), 4), 4);
+        assert_eq!(max_implicit_len(tag(0x80), 4), 6);
+    }
+}
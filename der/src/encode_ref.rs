@@ -31,9 +31,9 @@ where
 /// for any reference type which impls the same.
 ///
 /// By virtue of the blanket impl, this type also impls `Encode`.
-pub struct EncodeValueRef<'a, T>(pub &'a T);
+pub struct EncodeValueRef<'a, T: ?Sized>(pub &'a T);
 
-impl<T> AsRef<T> for EncodeValueRef<'_, T> {
+impl<T: ?Sized> AsRef<T> for EncodeValueRef<'_, T> {
     fn as_ref(&self) -> &T {
         self.0
     }
@@ -41,7 +41,7 @@ impl<T> AsRef<T> for EncodeValueRef<'_, T> {
 
 impl<T> EncodeValue for EncodeValueRef<'_, T>
 where
-    T: EncodeValue,
+    T: EncodeValue + ?Sized,
 {
     fn value_len(&self) -> Result<Length> {
         self.0.value_len()
@@ -54,7 +54,7 @@ where
 
 impl<T> Tagged for EncodeValueRef<'_, T>
 where
-    T: Tagged,
+    T: Tagged + ?Sized,
 {
     fn tag(&self) -> Tag {
         self.0.tag()
@@ -63,7 +63,7 @@ where
 
 impl<T> ValueOrd for EncodeValueRef<'_, T>
 where
-    T: ValueOrd,
+    T: ValueOrd + ?Sized,
 {
     fn value_cmp(&self, other: &Self) -> Result<Ordering> {
         self.0.value_cmp(other.0)
@@ -204,6 +204,16 @@ pub enum ErrorKind {
     /// Indefinite length disallowed (or malformed when decoding BER)
     IndefiniteLength,
 
+    /// Length exceeds a limit configured with
+    /// [`ReaderOptions`][crate::ReaderOptions].
+    LengthLimit {
+        /// Length of the input or value.
+        length: Length,
+
+        /// Configured limit.
+        limit: Length,
+    },
+
     /// Incorrect length for a given field.
     Length {
         /// Tag of the value being decoded.
@@ -233,6 +243,23 @@ pub enum ErrorKind {
         oid: ObjectIdentifier,
     },
 
+    /// OID differs from the one expected.
+    ///
+    /// This error is intended to be used by libraries which expect a specific
+    /// OID, e.g. the algorithm of an `AlgorithmIdentifier`, so the caller can
+    /// tell which OID was found instead of the expected one.
+    ///
+    /// Holding two OIDs nearly doubles the size of [`ErrorKind`], so this
+    /// variant requires the `oid-unexpected` feature.
+    #[cfg(feature = "oid-unexpected")]
+    OidUnexpected {
+        /// OID value that was expected.
+        expected: ObjectIdentifier,
+
+        /// OID value that was actually encountered.
+        actual: ObjectIdentifier,
+    },
+
     /// `SET` cannot contain duplicates.
     SetDuplicate,
 
@@ -335,6 +362,9 @@ impl fmt::Display for ErrorKind {
             ErrorKind::Io(err) => write!(f, "I/O error: {err:?}"),
             ErrorKind::IndefiniteLength => write!(f, "indefinite length disallowed/malformed"),
             ErrorKind::Length { tag } => write!(f, "incorrect length for {tag}"),
+            ErrorKind::LengthLimit { length, limit } => {
+                write!(f, "length {length} exceeds configured limit of {limit}")
+            }
             ErrorKind::Noncanonical { tag } => {
                 write!(f, "ASN.1 {tag} not canonically encoded as DER")
             }
@@ -343,6 +373,10 @@ impl fmt::Display for ErrorKind {
             ErrorKind::OidUnknown { oid } => {
                 write!(f, "unknown/unsupported OID: {oid}")
             }
+            #[cfg(feature = "oid-unexpected")]
+            ErrorKind::OidUnexpected { expected, actual } => {
+                write!(f, "unexpected OID: expected {expected}, got {actual}")
+            }
             ErrorKind::SetDuplicate => write!(f, "SET OF contains duplicate"),
             ErrorKind::SetOrdering => write!(f, "SET OF ordering error"),
             ErrorKind::Overflow => write!(f, "integer overflow"),
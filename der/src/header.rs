@@ -2,7 +2,10 @@
 
 #[cfg(feature = "ber")]
 use crate::EncodingRules;
-use crate::{Decode, DerOrd, Encode, Error, ErrorKind, Length, Reader, Result, Tag, Writer};
+use crate::{
+    Decode, DerOrd, Encode, Error, ErrorKind, Length, Reader, Result, SliceReader, Tag, Writer,
+    reader::peek::PeekReader,
+};
 
 use core::cmp::Ordering;
 
@@ -20,6 +23,9 @@ pub struct Header {
 }
 
 impl Header {
+    /// Maximum number of octets in a DER encoded header: tag + 5-octet length.
+    pub(crate) const MAX_SIZE: usize = Tag::MAX_SIZE + 5;
+
     /// Create a new [`Header`] from a [`Tag`] and a [`Length`].
     pub fn new(tag: Tag, length: Length) -> Self {
         #[cfg(feature = "ber")]
@@ -59,12 +65,61 @@ impl Header {
 
     /// Peek forward in the reader, attempting to decode a [`Header`] at the current position.
     ///
-    /// Does not modify the reader's state.
+    /// Does not modify the reader's state. The input is obtained using
+    /// [`Reader::peek_into`].
     pub fn peek<'a>(reader: &impl Reader<'a>) -> Result<Self> {
-        Header::decode(&mut reader.clone())
+        Header::decode(&mut PeekReader::new(reader))
+    }
+
+    /// Peek forward in the reader like [`Header::peek`], also returning the
+    /// length of the header as it's encoded in the input.
+    ///
+    /// This differs from [`Encode::encoded_len`] for headers decoded from BER
+    /// with an indefinite length.
+    pub(crate) fn peek_with_len<'a>(reader: &impl Reader<'a>) -> Result<(Self, Length)> {
+        let mut peek = PeekReader::new(reader);
+        let header = Header::decode(&mut peek)?;
+        let len = (peek.position() - reader.position())?;
+        Ok((header, len))
     }
 }
 
+/// Get the total length of the TLV-encoded value at the start of `bytes`,
+/// i.e. the length of its header plus the length of its value, by decoding
+/// only its header.
+///
+/// This is intended for framing DER messages received over a stream, e.g. a
+/// TCP socket, before decoding them:
+///
+/// ```
+/// // SEQUENCE { INTEGER 42 }, received in two parts
+/// let mut buf = vec![0x30];
+/// assert_eq!(der::length_of_tlv(&buf)?, None);
+///
+/// buf.extend_from_slice(&[0x03, 0x02, 0x01]);
+/// assert_eq!(der::length_of_tlv(&buf)?, Some(5));
+/// # Ok::<(), der::Error>(())
+/// ```
+///
+/// # Returns
+/// - `Ok(Some(len))` once the header is complete: `bytes` must contain at
+///   least `len` bytes for the whole value to be available
+/// - `Ok(None)` if more bytes are needed to decode the header
+/// - `Err(_)` if the header is malformed, or has an indefinite length
+pub fn length_of_tlv(bytes: &[u8]) -> Result<Option<usize>> {
+    let bytes = bytes.get(..Header::MAX_SIZE).unwrap_or(bytes);
+    let mut reader = SliceReader::new(bytes)?;
+
+    let header = match Header::decode(&mut reader) {
+        Ok(header) => header,
+        Err(err) if matches!(err.kind(), ErrorKind::Incomplete { .. }) => return Ok(None),
+        Err(err) => return Err(err),
+    };
+
+    let len = (reader.position() + header.length())?;
+    usize::try_from(len).map(Some)
+}
+
 impl<'a> Decode<'a> for Header {
     type Error = Error;
 
@@ -79,6 +134,11 @@ impl<'a> Decode<'a> for Header {
             }
         })?;
 
+        reader
+            .options()
+            .check_value_len(length)
+            .map_err(|e| reader.error(e.kind()))?;
+
         #[cfg(feature = "ber")]
         if length.is_indefinite() && !is_constructed {
             debug_assert_eq!(reader.encoding_rules(), EncodingRules::Ber);
@@ -117,9 +177,10 @@ impl DerOrd for Header {
 }
 
 #[cfg(test)]
+#[allow(clippy::unwrap_used)]
 mod tests {
-    use super::Header;
-    use crate::{Encode, Length, Reader, SliceReader, Tag, TagNumber};
+    use super::{Header, length_of_tlv};
+    use crate::{Encode, ErrorKind, Length, Reader, SliceReader, Tag, TagNumber};
     use hex_literal::hex;
 
     #[test]
@@ -163,4 +224,49 @@ mod tests {
         // Should not decode
         Header::peek(&reader).expect_err("overlength error");
     }
+
+    #[test]
+    fn tlv_length() {
+        // OCTET STRING with a long-form length of 256
+        let mut msg = hex!("04820100").to_vec();
+        msg.resize(4 + 256, 0xAA);
+
+        assert_eq!(length_of_tlv(&[]).unwrap(), None);
+        assert_eq!(length_of_tlv(&msg[..1]).unwrap(), None);
+        assert_eq!(length_of_tlv(&msg[..3]).unwrap(), None);
+        assert_eq!(length_of_tlv(&msg[..4]).unwrap(), Some(260));
+        assert_eq!(length_of_tlv(&msg[..100]).unwrap(), Some(260));
+        assert_eq!(length_of_tlv(&msg).unwrap(), Some(260));
+
+        // Trailing data is not part of the TLV
+        msg.extend_from_slice(&hex!("0500"));
+        assert_eq!(length_of_tlv(&msg).unwrap(), Some(260));
+        assert_eq!(length_of_tlv(&msg[260..]).unwrap(), Some(2));
+    }
+
+    #[test]
+    fn tlv_length_max_header() {
+        let msg = hex!("BF8FFFFFFF7F 847FFFFFFF");
+        assert_eq!(length_of_tlv(&msg[..10]).unwrap(), None);
+        assert_eq!(length_of_tlv(&msg).unwrap(), Some(11 + 0x7FFFFFFF));
+
+        // Total length exceeding `Length::MAX`
+        let err = length_of_tlv(&hex!("BF8FFFFFFF7F 84FFFFFFFF")).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Overflow);
+    }
+
+    #[test]
+    fn tlv_length_malformed() {
+        // Non-canonical length
+        let err = length_of_tlv(&hex!("048105")).unwrap_err();
+        assert_eq!(
+            err.kind(),
+            ErrorKind::Length {
+                tag: Tag::OctetString
+            }
+        );
+
+        // Indefinite length
+        assert!(length_of_tlv(&hex!("3080")).is_err());
+    }
 }
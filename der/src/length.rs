@@ -285,8 +285,8 @@ impl<'a> Decode<'a> for Length {
                 let length = Length::from(decoded_len);
 
                 // X.690 Section 10.1: DER lengths must be encoded with a minimum
-                // number of octets
-                if length.initial_octet() == Some(tag) {
+                // number of octets, which BER doesn't require (Section 8.1.3.5)
+                if length.initial_octet() == Some(tag) || !reader.encoding_rules().is_der() {
                     Ok(length)
                 } else {
                     Err(reader.error(ErrorKind::Overlength))
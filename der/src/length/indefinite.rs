@@ -176,4 +176,17 @@ mod tests {
         assert!(length.is_indefinite());
         assert_eq!(usize::try_from(length).unwrap(), 20);
     }
+
+    #[test]
+    fn skip_tlv() {
+        // SEQUENCE (indefinite) { OCTET STRING "hi", [0] (indefinite) { NULL } }, BOOLEAN TRUE
+        let message = hex!("30 80 04 02 68 69 A0 80 05 00 00 00 00 00 01 01 FF");
+        let mut reader =
+            SliceReader::new_with_encoding_rules(&message, EncodingRules::Ber).unwrap();
+
+        let header = reader.skip_tlv().unwrap();
+        assert_eq!(header.tag(), Tag::Sequence);
+        assert_eq!(bool::decode(&mut reader), Ok(true));
+        assert!(reader.is_finished());
+    }
 }
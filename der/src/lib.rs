@@ -38,23 +38,31 @@
 //! - [`i8`], [`i16`], [`i32`], [`i64`], [`i128`]: ASN.1 `INTEGER`.
 //! - [`u8`], [`u16`], [`u32`], [`u64`], [`u128`]: ASN.1 `INTEGER`.
 //! - [`f64`]: ASN.1 `REAL` (gated on `real` crate feature)
+//! - [`IpAddr`][`core::net::IpAddr`], [`Ipv4Addr`][`core::net::Ipv4Addr`],
+//!   [`Ipv6Addr`][`core::net::Ipv6Addr`]: ASN.1 `OCTET STRING` containing the
+//!   4 or 16-byte address (gated on `net` crate feature)
 //! - [`str`], [`String`][`alloc::string::String`]: ASN.1 `UTF8String`.
 //!   `String` requires `alloc` feature. See also [`Utf8StringRef`].
 //! - [`Option`]: ASN.1 `OPTIONAL`.
 //! - [`SystemTime`][`std::time::SystemTime`]: ASN.1 `GeneralizedTime`. Requires `std` feature.
 //! - [`Vec`][`alloc::vec::Vec`]: ASN.1 `SEQUENCE OF`. Requires `alloc` feature.
 //! - `[T; N]`: ASN.1 `SEQUENCE OF`. See also [`SequenceOf`].
+//! - `(A, B)`, `(A, B, C)`, `(A, B, C, D)`: ASN.1 `SEQUENCE` of the components.
 //!
 //! The following ASN.1 types provided by this crate also impl these traits:
 //! - [`Any`], [`AnyRef`]: ASN.1 `ANY`.
 //! - [`BitString`], [`BitStringRef`]: ASN.1 `BIT STRING`
+//! - [`Duration`]: ASN.1 `DURATION`.
 //! - [`GeneralizedTime`]: ASN.1 `GeneralizedTime`.
 //! - [`Ia5StringRef`]: ASN.1 `IA5String`.
+//! - [`LeU32`], [`LeU64`]: little-endian integers embedded in an ASN.1 `OCTET STRING`.
 //! - [`Null`]: ASN.1 `NULL`.
-//! - [`ObjectIdentifier`]: ASN.1 `OBJECT IDENTIFIER`.
+//! - [`NumericStringRef`]: ASN.1 `NumericString` (digits and space).
+//! - [`ObjectIdentifier`], [`ObjectIdentifierRef`]: ASN.1 `OBJECT IDENTIFIER`.
 //! - [`OctetString`], [`OctetStringRef`]: ASN.1 `OCTET STRING`.
 //! - [`PrintableStringRef`]: ASN.1 `PrintableString` (ASCII subset).
 //! - [`TeletexStringRef`]: ASN.1 `TeletexString`.
+//! - [`TimeOfDay`]: ASN.1 `TIME-OF-DAY`.
 //! - [`VideotexStringRef`]: ASN.1 `VideotexString`.
 //! - [`SequenceOf`]: ASN.1 `SEQUENCE OF`.
 //! - [`SetOf`], [`SetOfVec`]: ASN.1 `SET OF`.
@@ -62,6 +70,12 @@
 //! - [`UtcTime`]: ASN.1 `UTCTime`.
 //! - [`Utf8StringRef`]: ASN.1 `UTF8String`.
 //!
+//! The fixed-size `Uint<LIMBS>` and heap-allocated `BoxedUint` types of
+//! [`crypto-bigint`] impl these traits as ASN.1 `INTEGER` when its `der`
+//! feature is enabled, and can be used directly as [`Sequence`] fields, e.g.
+//! for RSA moduli and exponents. These impls are provided by `crypto-bigint`
+//! rather than this crate, since impls in both crates would conflict.
+//!
 //! Context specific fields can be modeled using these generic types:
 //! - [`ContextSpecific`]: decoder/encoder for owned context-specific fields
 //! - [`ContextSpecificRef`]: encode-only type for references to context-specific fields
@@ -212,6 +226,7 @@
 //!
 //! - [`Choice`]: derive for `CHOICE` enum (see [`der_derive::Choice`])
 //! - [`Enumerated`]: derive for `ENUMERATED` enum (see [`der_derive::Enumerated`])
+//! - [`OidChoice`]: derive for OID-keyed `SEQUENCE` enum (see [`der_derive::OidChoice`])
 //! - [`Sequence`]: derive for `SEQUENCE` struct (see [`der_derive::Sequence`])
 //!
 //! ### Derive [`Sequence`] for struct
@@ -307,20 +322,27 @@
 //! [A Layman's Guide to a Subset of ASN.1, BER, and DER]: https://luca.ntop.org/Teaching/Appunti/asn1.html
 //! [A Warm Welcome to ASN.1 and DER]: https://letsencrypt.org/docs/a-warm-welcome-to-asn1-and-der/
 //!
+//! [`crypto-bigint`]: https://docs.rs/crypto-bigint
 //! [`Any`]: asn1::Any
 //! [`AnyRef`]: asn1::AnyRef
 //! [`ContextSpecific`]: asn1::ContextSpecific
 //! [`ContextSpecificRef`]: asn1::ContextSpecificRef
 //! [`BitString`]: asn1::BitString
 //! [`BitStringRef`]: asn1::BitStringRef
+//! [`Duration`]: asn1::Duration
 //! [`GeneralizedTime`]: asn1::GeneralizedTime
 //! [`Ia5StringRef`]: asn1::Ia5StringRef
+//! [`LeU32`]: asn1::LeU32
+//! [`LeU64`]: asn1::LeU64
 //! [`Null`]: asn1::Null
+//! [`NumericStringRef`]: asn1::NumericStringRef
 //! [`ObjectIdentifier`]: asn1::ObjectIdentifier
+//! [`ObjectIdentifierRef`]: asn1::ObjectIdentifierRef
 //! [`OctetString`]: asn1::OctetString
 //! [`OctetStringRef`]: asn1::OctetStringRef
 //! [`PrintableStringRef`]: asn1::PrintableStringRef
 //! [`TeletexStringRef`]: asn1::TeletexStringRef
+//! [`TimeOfDay`]: asn1::TimeOfDay
 //! [`VideotexStringRef`]: asn1::VideotexStringRef
 //! [`SequenceOf`]: asn1::SequenceOf
 //! [`SetOf`]: asn1::SetOf
@@ -339,7 +361,9 @@ extern crate alloc;
 extern crate std;
 
 pub mod asn1;
+pub mod encode_fixed;
 pub mod referenced;
+pub mod structural;
 
 pub(crate) mod arrayvec;
 mod bytes;
@@ -353,35 +377,74 @@ mod header;
 mod length;
 mod ord;
 mod reader;
+mod size;
 mod string;
 mod tag;
+mod tlv;
 mod writer;
 
+#[doc(hidden)]
+pub mod trace;
+
+#[cfg(feature = "dump")]
+pub mod dump;
+#[cfg(feature = "schema")]
+pub mod schema;
+#[cfg(feature = "zeroize")]
+pub mod secret;
+#[cfg(feature = "test-vectors")]
+pub mod test_vectors;
+
+#[cfg(feature = "alloc")]
+mod canonical;
 #[cfg(feature = "alloc")]
 mod document;
 
 pub use crate::{
     asn1::bit_string::allowed_len_bit_string::AllowedLenBitString,
     asn1::{AnyRef, Choice, Sequence},
-    datetime::DateTime,
+    datetime::{DateTime, OutOfRange},
     decode::{Decode, DecodeOwned, DecodeValue},
     encode::{Encode, EncodeValue},
+    encode_fixed::{EncodeFixed, EncodedArray},
     encode_ref::{EncodeRef, EncodeValueRef},
     encoding_rules::EncodingRules,
     error::{Error, ErrorKind, Result},
-    header::Header,
+    header::{Header, length_of_tlv},
     length::Length,
     ord::{DerOrd, ValueOrd},
-    reader::{Reader, slice::SliceReader},
-    tag::{Class, FixedTag, IsConstructed, Tag, TagMode, TagNumber, Tagged},
+    reader::{
+        Reader,
+        const_reader::ConstReader,
+        options::{DuplicatePolicy, ReaderOptions},
+        slice::SliceReader,
+        unknown::{DecodeUnknown, HookedReader},
+    },
+    size::Size,
+    tag::{
+        Class, ContextSpecificRange, FixedTag, IsConstructed, Tag, TagMode, TagNumber, TagSet,
+        Tagged,
+    },
+    tlv::TlvRef,
     writer::{Writer, slice::SliceWriter},
 };
 
+#[cfg(feature = "async")]
+pub use crate::reader::async_reader::AsyncReader;
 #[cfg(feature = "alloc")]
-pub use crate::{asn1::Any, document::Document};
+pub use crate::{
+    asn1::Any,
+    canonical::{Canonicalization, DerDocument},
+    document::Document,
+};
+
+#[cfg(all(feature = "alloc", feature = "ber"))]
+pub use crate::canonical::BerDocument;
 
 #[cfg(feature = "derive")]
-pub use der_derive::{BitString, Choice, DecodeValue, EncodeValue, Enumerated, Sequence, ValueOrd};
+pub use der_derive::{
+    BitString, Choice, DecodeValue, EncodeValue, Enumerated, OidChoice, Sequence, ValueOrd,
+};
 
 #[cfg(feature = "flagset")]
 pub use flagset;
@@ -391,7 +454,12 @@ pub use const_oid as oid;
 
 #[cfg(feature = "pem")]
 pub use {
-    crate::{decode::DecodePem, encode::EncodePem, reader::pem::PemReader, writer::pem::PemWriter},
+    crate::{
+        decode::DecodePem,
+        encode::EncodePem,
+        reader::pem::{PemBlocks, PemReader},
+        writer::pem::PemWriter,
+    },
     pem_rfc7468 as pem,
 };
 
@@ -1,15 +1,21 @@
 //! Reader trait.
 
+#[cfg(feature = "async")]
+pub(crate) mod async_reader;
+pub(crate) mod const_reader;
+pub(crate) mod options;
+pub(crate) mod peek;
 #[cfg(feature = "pem")]
 pub(crate) mod pem;
 pub(crate) mod slice;
+pub(crate) mod unknown;
 
 #[cfg(feature = "pem")]
 mod position;
 
 use crate::{
-    Decode, DecodeValue, Encode, EncodingRules, Error, ErrorKind, FixedTag, Header, Length, Tag,
-    TagMode, TagNumber, asn1::ContextSpecific,
+    AnyRef, Decode, DecodeValue, EncodingRules, Error, ErrorKind, FixedTag, Header, Length, Tag,
+    TagMode, TagNumber, asn1::ContextSpecific, reader::options::ReaderOptions,
 };
 
 #[cfg(feature = "alloc")]
@@ -23,6 +29,11 @@ pub trait Reader<'r>: Clone {
     /// Get the [`EncodingRules`] which should be applied when decoding the input.
     fn encoding_rules(&self) -> EncodingRules;
 
+    /// Get the [`ReaderOptions`] limiting the input accepted by this reader.
+    fn options(&self) -> ReaderOptions {
+        ReaderOptions::default()
+    }
+
     /// Get the length of the input.
     fn input_len(&self) -> Length;
 
@@ -61,6 +72,57 @@ pub trait Reader<'r>: Clone {
         .map(|field| field.value))
     }
 
+    /// Attempt to decode an ASN.1 `CONTEXT-SPECIFIC` field with the
+    /// provided [`TagNumber`], returning `default` if it's absent.
+    ///
+    /// This is a shorthand for fields declared with `DEFAULT` in the ASN.1
+    /// module, e.g. `[0] EXPLICIT INTEGER DEFAULT 20`.
+    fn context_specific_or<T>(
+        &mut self,
+        tag_number: TagNumber,
+        tag_mode: TagMode,
+        default: T,
+    ) -> Result<T, <T as DecodeValue<'r>>::Error>
+    where
+        T: DecodeValue<'r> + FixedTag + 'r,
+    {
+        self.context_specific(tag_number, tag_mode)
+            .map(|value| value.unwrap_or(default))
+    }
+
+    /// Decode a required ASN.1 `CONTEXT-SPECIFIC` field with the provided
+    /// [`TagNumber`].
+    ///
+    /// Returns [`ErrorKind::TagUnexpected`] if the next field has a different
+    /// tag, or [`ErrorKind::Incomplete`] if there is no next field.
+    fn context_specific_required<T>(
+        &mut self,
+        tag_number: TagNumber,
+        tag_mode: TagMode,
+    ) -> Result<T, <T as DecodeValue<'r>>::Error>
+    where
+        T: DecodeValue<'r> + FixedTag + 'r,
+    {
+        match self.context_specific(tag_number, tag_mode) {
+            Ok(Some(value)) => return Ok(value),
+            Ok(None) => (),
+            Err(err) => return Err(err),
+        }
+
+        let expected = Tag::ContextSpecific {
+            constructed: tag_mode == TagMode::Explicit || T::TAG.is_constructed(),
+            number: tag_number,
+        };
+
+        let actual = Tag::peek(self)?;
+        Err(self
+            .error(ErrorKind::TagUnexpected {
+                expected: Some(expected),
+                actual,
+            })
+            .into())
+    }
+
     /// Decode a value which impls the [`Decode`] trait.
     fn decode<T: Decode<'r>>(&mut self) -> Result<T, T::Error> {
         T::decode(self)
@@ -132,6 +194,12 @@ pub trait Reader<'r>: Clone {
     /// output buffer.
     ///
     /// Attempts to fill the entire buffer, returning an error if there is not enough data.
+    ///
+    /// This is how [`Header::peek`], [`Tag::peek`], and the decoders of `OPTIONAL` and `CHOICE`
+    /// values look ahead in the input. The default implementation reads from a clone of this
+    /// reader, which readers that can't cheaply be cloned, e.g. streaming readers, should
+    /// override, e.g. using a lookahead buffer. Peeks are at most 16 bytes long, except when
+    /// computing BER indefinite lengths, which always reads from a clone.
     fn peek_into(&self, buf: &mut [u8]) -> Result<(), Error> {
         let mut reader = self.clone();
         reader.read_into(buf)?;
@@ -198,10 +266,84 @@ pub trait Reader<'r>: Clone {
         read_value(self, header, |r, _| f(r))
     }
 
+    /// Skip over a complete TLV production without decoding or allocating,
+    /// returning its [`Header`].
+    ///
+    /// This is useful for writing forward-compatible decoders which need to
+    /// ignore unrecognized fields. Values with indefinite lengths (when
+    /// decoding BER) are skipped in their entirety, including their
+    /// end-of-contents marker.
+    fn skip_tlv(&mut self) -> Result<Header, Error> {
+        let header = Header::decode(self)?;
+        read_value(self, header, |r, _| r.drain(r.remaining_len()))?;
+        Ok(header)
+    }
+
+    /// Skip all of the remaining TLV productions in this reader, e.g. the
+    /// unrecognized trailing fields of an extensible `SEQUENCE`.
+    ///
+    /// Each skipped production must be well-formed.
+    fn skip_remaining(&mut self) -> Result<(), Error> {
+        while !self.is_finished() {
+            self.skip_tlv()?;
+        }
+
+        Ok(())
+    }
+
+    /// Handle the next TLV, which the decoder can't place.
+    ///
+    /// Returns `Ok(true)` if the TLV was consumed, and decoding can continue.
+    /// By default, the TLV is left in place so that the decoder rejects it, i.e.
+    /// `Ok(false)` is returned. [`HookedReader`] passes it to a
+    /// [`DecodeUnknown`] hook instead.
+    ///
+    /// [`HookedReader`]: crate::HookedReader
+    /// [`DecodeUnknown`]: crate::DecodeUnknown
+    fn decode_unknown(&mut self) -> Result<bool, Error> {
+        Ok(false)
+    }
+
+    /// Handle a TLV which isn't one of the known alternatives of an extensible
+    /// `CHOICE`.
+    ///
+    /// Returns `Ok(true)` if the TLV is accepted as an unknown alternative. By
+    /// default, it's rejected, i.e. `Ok(false)` is returned. [`HookedReader`]
+    /// passes it to a [`DecodeUnknown`] hook instead.
+    ///
+    /// [`HookedReader`]: crate::HookedReader
+    /// [`DecodeUnknown`]: crate::DecodeUnknown
+    fn decode_unknown_alternative(&mut self, _tlv: AnyRef<'r>) -> Result<bool, Error> {
+        Ok(false)
+    }
+
+    /// Handle the unknown extensions of an extensible `SEQUENCE` using
+    /// [`Reader::decode_unknown`].
+    ///
+    /// These are the TLVs preceding the next known field with the given tag,
+    /// i.e. of the same class with a lower tag number, or all remaining TLVs
+    /// if `next` is `None`. Stops at the first TLV which isn't consumed.
+    fn decode_unknown_extensions(&mut self, next: Option<Tag>) -> Result<(), Error> {
+        while !self.is_finished() {
+            if let Some(next) = next {
+                let tag = Tag::peek(self)?;
+
+                if tag.class() != next.class() || tag.number() >= next.number() {
+                    break;
+                }
+            }
+
+            if !self.decode_unknown()? {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
     /// Obtain a slice of bytes containing a complete TLV production suitable for parsing later.
     fn tlv_bytes(&mut self) -> Result<&'r [u8], Error> {
-        let header = Header::peek(self)?;
-        let header_len = header.encoded_len()?;
+        let (header, header_len) = Header::peek_with_len(self)?;
         self.read_slice((header_len + header.length())?)
     }
 }
@@ -0,0 +1,264 @@
+//! Reader pulling DER-encoded TLVs from an asynchronous stream.
+
+use crate::{Decode, Error, ErrorKind, Header, ReaderOptions};
+use alloc::vec::Vec;
+use std::io;
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+/// Maximum number of octets of a tag number encoded in the high tag number
+/// form, i.e. enough for a `u32`.
+const MAX_TAG_NUMBER_LEN: usize = 5;
+
+/// Maximum number of octets of a length encoded in the long form, i.e. enough
+/// for a `u32`.
+const MAX_LENGTH_LEN: usize = 4;
+
+/// Reader pulling DER-encoded TLVs from an [`AsyncRead`] stream.
+///
+/// Only the TLV currently being read is buffered, so protocols which embed a
+/// sequence of DER messages in a stream (e.g. LDAP) can decode each message
+/// as soon as it has been received, without reading the whole stream first.
+/// The header is read a few bytes at a time, so streams with expensive reads
+/// should be wrapped in a [`tokio::io::BufReader`].
+///
+/// The buffer only grows as octets of the TLV are received, so a bogus
+/// length doesn't cause a large allocation by itself. The length of the
+/// buffered TLVs is otherwise only limited by
+/// [`ReaderOptions::with_max_value_len`], which should be set when reading
+/// untrusted input.
+///
+/// ```
+/// use der::{AsyncReader, asn1::OctetStringRef};
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> der::Result<()> {
+/// // OCTET STRING followed by NULL
+/// let stream: &[u8] = &[0x04, 0x02, 0xAB, 0xCD, 0x05, 0x00];
+/// let mut reader = AsyncReader::new(stream);
+///
+/// let octet_string = reader.decode::<&OctetStringRef>().await?.unwrap();
+/// assert_eq!(octet_string.as_bytes(), &[0xAB, 0xCD]);
+///
+/// let header = reader.read_header().await?.unwrap();
+/// assert!(reader.read_value(&header).await?.is_empty());
+/// assert!(reader.read_header().await?.is_none());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct AsyncReader<R> {
+    /// Underlying stream.
+    inner: R,
+
+    /// TLV being read.
+    buf: Vec<u8>,
+
+    /// Length of the header of the TLV being read.
+    header_len: usize,
+
+    /// Options limiting the accepted input.
+    options: ReaderOptions,
+}
+
+impl<R: AsyncRead + Unpin> AsyncReader<R> {
+    /// Create a new reader pulling TLVs from the given stream.
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            buf: Vec::new(),
+            header_len: 0,
+            options: ReaderOptions::default(),
+        }
+    }
+
+    /// Limit the input accepted by this reader.
+    ///
+    /// The maximum value length applies to each TLV, whereas the maximum
+    /// input length is ignored since streams have no predetermined length.
+    pub fn with_options(mut self, options: ReaderOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Get the underlying stream.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Get the underlying stream.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Read the header of the next TLV.
+    ///
+    /// Returns `Ok(None)` if the stream ended before the first octet of the
+    /// TLV. The value is left in the stream, to be read using
+    /// [`AsyncReader::read_value`].
+    pub async fn read_header(&mut self) -> Result<Option<Header>, Error> {
+        self.buf.clear();
+        self.header_len = 0;
+
+        let first = match self.inner.read_u8().await {
+            Ok(byte) => byte,
+            Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+        self.buf.push(first);
+
+        // High tag number form
+        if first & 0x1F == 0x1F {
+            loop {
+                let byte = self.inner.read_u8().await?;
+                self.buf.push(byte);
+
+                if byte & 0x80 == 0 || self.buf.len() > MAX_TAG_NUMBER_LEN {
+                    break;
+                }
+            }
+        }
+
+        let length = self.inner.read_u8().await?;
+        self.buf.push(length);
+
+        // Long form length
+        if length > 0x80 {
+            let len = usize::from(length & 0x7F);
+
+            if len > MAX_LENGTH_LEN {
+                return Err(ErrorKind::Overflow.into());
+            }
+
+            self.read_to_buf(len).await?;
+        }
+
+        let header = Header::from_der(&self.buf)?;
+        self.options.check_value_len(header.length())?;
+        self.header_len = self.buf.len();
+        Ok(Some(header))
+    }
+
+    /// Read the value of the TLV with the given header, which must have just
+    /// been read using [`AsyncReader::read_header`].
+    pub async fn read_value(&mut self, header: &Header) -> Result<&[u8], Error> {
+        self.buf.truncate(self.header_len);
+        self.read_to_buf(usize::try_from(header.length())?).await?;
+        Ok(&self.buf[self.header_len..])
+    }
+
+    /// Read the next TLV, returning its encoding.
+    ///
+    /// Returns `Ok(None)` if the stream ended before the first octet of the
+    /// TLV.
+    pub async fn read_tlv(&mut self) -> Result<Option<&[u8]>, Error> {
+        let Some(header) = self.read_header().await? else {
+            return Ok(None);
+        };
+
+        self.read_value(&header).await?;
+        Ok(Some(&self.buf))
+    }
+
+    /// Read the next TLV and decode it as `T`, which may borrow from the
+    /// buffered TLV.
+    ///
+    /// Returns `Ok(None)` if the stream ended before the first octet of the
+    /// TLV.
+    pub async fn decode<'a, T: Decode<'a>>(&'a mut self) -> Result<Option<T>, T::Error> {
+        match self.read_tlv().await? {
+            Some(tlv) => T::from_der(tlv).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Read `len` octets from the stream, appending them to the buffer.
+    ///
+    /// The buffer grows as octets are received rather than being allocated
+    /// upfront, since `len` comes from the input and may be bogus.
+    async fn read_to_buf(&mut self, len: usize) -> Result<(), Error> {
+        let limit = u64::try_from(len).map_err(|_| ErrorKind::Overflow)?;
+        let read = (&mut self.inner)
+            .take(limit)
+            .read_to_end(&mut self.buf)
+            .await?;
+
+        if read < len {
+            return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::AsyncReader;
+    use crate::{
+        Encode, ErrorKind, Length, ReaderOptions, Tag,
+        asn1::{OctetStringRef, Utf8StringRef},
+    };
+    use alloc::vec::Vec;
+    use hex_literal::hex;
+
+    #[tokio::test]
+    async fn read_tlvs() {
+        let mut stream = Vec::new();
+        Utf8StringRef::new("hello")
+            .unwrap()
+            .encode_to_vec(&mut stream)
+            .unwrap();
+        OctetStringRef::new(&[0xAB; 200])
+            .unwrap()
+            .encode_to_vec(&mut stream)
+            .unwrap();
+
+        let mut reader = AsyncReader::new(stream.as_slice());
+        assert_eq!(
+            reader.decode::<Utf8StringRef<'_>>().await.unwrap().unwrap(),
+            Utf8StringRef::new("hello").unwrap()
+        );
+
+        let header = reader.read_header().await.unwrap().unwrap();
+        assert_eq!(header.tag(), Tag::OctetString);
+        assert_eq!(header.length(), Length::new(200));
+        assert_eq!(reader.read_value(&header).await.unwrap(), &[0xAB; 200]);
+
+        assert!(reader.read_tlv().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn read_high_tag_number() {
+        // [APPLICATION 300] { NULL }
+        let stream: &[u8] = &hex!("7F822C020500");
+        let mut reader = AsyncReader::new(stream);
+        assert_eq!(reader.read_tlv().await.unwrap().unwrap(), stream);
+    }
+
+    #[tokio::test]
+    async fn read_truncated() {
+        let stream: &[u8] = &hex!("0403ABCD");
+        let mut reader = AsyncReader::new(stream);
+        let err = reader.read_tlv().await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Io(std::io::ErrorKind::UnexpectedEof));
+    }
+
+    #[tokio::test]
+    async fn read_truncated_huge_length() {
+        // OCTET STRING claiming a length of 4 GiB, without a length limit
+        let stream: &[u8] = &hex!("0484FFFFFFFF");
+        let mut reader = AsyncReader::new(stream);
+        let err = reader.read_tlv().await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Io(std::io::ErrorKind::UnexpectedEof));
+    }
+
+    #[tokio::test]
+    async fn read_max_value_len() {
+        // OCTET STRING claiming a length of 4 GiB
+        let stream: &[u8] = &hex!("0484FFFFFFFF");
+        let options = ReaderOptions::new().with_max_value_len(Length::new(1024));
+        let mut reader = AsyncReader::new(stream).with_options(options);
+        let err = reader.read_header().await.unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::LengthLimit { .. }));
+    }
+}
@@ -0,0 +1,445 @@
+//! `const fn` reader.
+
+use crate::{Error, ErrorKind, Length, Tag, TagNumber};
+
+#[cfg(feature = "oid")]
+use const_oid::ObjectIdentifier;
+
+/// `INTEGER` tag octet.
+const INTEGER: u8 = 0x02;
+
+/// `OCTET STRING` tag octet.
+const OCTET_STRING: u8 = 0x04;
+
+/// `OBJECT IDENTIFIER` tag octet.
+#[cfg(feature = "oid")]
+const OBJECT_IDENTIFIER: u8 = 0x06;
+
+/// `SEQUENCE` tag octet.
+const SEQUENCE: u8 = 0x30;
+
+/// Reader which decodes a limited subset of DER in `const fn` contexts.
+///
+/// This makes it possible to embed DER-encoded constants (e.g. firmware
+/// configuration) and destructure them at compile time, such that malformed
+/// constants fail the build rather than being detected at runtime.
+///
+/// Only `INTEGER`, `OCTET STRING`, `OBJECT IDENTIFIER` (with the `oid`
+/// feature), and `SEQUENCE` values are supported.
+///
+/// ```
+/// use der::ConstReader;
+///
+/// // SEQUENCE { INTEGER 1000, OCTET STRING 0xCAFE }
+/// const CONFIG: &[u8] = &[0x30, 0x08, 0x02, 0x02, 0x03, 0xE8, 0x04, 0x02, 0xCA, 0xFE];
+///
+/// const PARSED: (u64, &[u8]) = {
+///     let mut reader = ConstReader::new(CONFIG);
+///     let mut seq = match reader.decode_sequence() {
+///         Ok(seq) => seq,
+///         Err(_) => panic!("expected SEQUENCE"),
+///     };
+///     let timeout = match seq.decode_u64() {
+///         Ok(n) => n,
+///         Err(_) => panic!("expected INTEGER"),
+///     };
+///     let key = match seq.decode_octet_string() {
+///         Ok(bytes) => bytes,
+///         Err(_) => panic!("expected OCTET STRING"),
+///     };
+///     assert!(seq.finish().is_ok() && reader.finish().is_ok());
+///     (timeout, key)
+/// };
+///
+/// assert_eq!(PARSED, (1000, &[0xCA, 0xFE][..]));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct ConstReader<'a> {
+    /// Byte slice being decoded.
+    bytes: &'a [u8],
+
+    /// Position within the decoded slice.
+    position: usize,
+
+    /// End of the region of the slice this reader is allowed to consume.
+    end: usize,
+}
+
+impl<'a> ConstReader<'a> {
+    /// Create a new reader for the given byte slice.
+    pub const fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            position: 0,
+            end: bytes.len(),
+        }
+    }
+
+    /// Have all of the bytes in this reader been consumed?
+    pub const fn is_finished(&self) -> bool {
+        self.position >= self.end
+    }
+
+    /// Finish decoding, returning an error if there is trailing data.
+    pub const fn finish(self) -> Result<(), Error> {
+        if self.is_finished() {
+            Ok(())
+        } else {
+            Err(self.error(ErrorKind::TrailingData {
+                decoded: length(self.position),
+                remaining: length(self.end.saturating_sub(self.position)),
+            }))
+        }
+    }
+
+    /// Decode a `SEQUENCE`, returning a reader for its contents.
+    pub const fn decode_sequence(&mut self) -> Result<ConstReader<'a>, Error> {
+        match self.read_value(SEQUENCE, Tag::Sequence) {
+            Ok(value) => Ok(Self {
+                bytes: self.bytes,
+                position: self.position.saturating_sub(value.len()),
+                end: self.position,
+            }),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Decode an `OCTET STRING`, returning its contents.
+    pub const fn decode_octet_string(&mut self) -> Result<&'a [u8], Error> {
+        self.read_value(OCTET_STRING, Tag::OctetString)
+    }
+
+    /// Decode an `INTEGER` which fits in a [`u64`].
+    pub const fn decode_u64(&mut self) -> Result<u64, Error> {
+        let bytes = match self.read_integer() {
+            Ok(bytes) => bytes,
+            Err(err) => return Err(err),
+        };
+
+        let bytes = match bytes {
+            [byte, ..] if *byte & 0x80 != 0 => {
+                return Err(self.error(ErrorKind::Value { tag: Tag::Integer }));
+            }
+            [0, rest @ ..] if !rest.is_empty() => rest,
+            _ => bytes,
+        };
+
+        if bytes.len() > 8 {
+            return Err(self.error(ErrorKind::Length { tag: Tag::Integer }));
+        }
+
+        let mut result = 0u64;
+        let mut i = 0;
+
+        while i < bytes.len() {
+            result = (result << 8) | bytes[i] as u64;
+            i += 1;
+        }
+
+        Ok(result)
+    }
+
+    /// Decode an `INTEGER` which fits in an [`i64`].
+    pub const fn decode_i64(&mut self) -> Result<i64, Error> {
+        let bytes = match self.read_integer() {
+            Ok(bytes) => bytes,
+            Err(err) => return Err(err),
+        };
+
+        if bytes.len() > 8 {
+            return Err(self.error(ErrorKind::Length { tag: Tag::Integer }));
+        }
+
+        // Sign-extend the value
+        let mut result = if bytes[0] & 0x80 != 0 { -1i64 } else { 0 };
+        let mut i = 0;
+
+        while i < bytes.len() {
+            result = (result << 8) | bytes[i] as i64;
+            i += 1;
+        }
+
+        Ok(result)
+    }
+
+    /// Decode an `OBJECT IDENTIFIER`.
+    #[cfg(feature = "oid")]
+    pub const fn decode_oid(&mut self) -> Result<ObjectIdentifier, Error> {
+        let bytes = match self.read_value(OBJECT_IDENTIFIER, Tag::ObjectIdentifier) {
+            Ok(bytes) => bytes,
+            Err(err) => return Err(err),
+        };
+
+        match ObjectIdentifier::from_bytes(bytes) {
+            Ok(oid) => Ok(oid),
+            Err(_) => Err(self.error(ErrorKind::OidMalformed)),
+        }
+    }
+
+    /// Read the contents of a canonically encoded `INTEGER`.
+    const fn read_integer(&mut self) -> Result<&'a [u8], Error> {
+        let bytes = match self.read_value(INTEGER, Tag::Integer) {
+            Ok(bytes) => bytes,
+            Err(err) => return Err(err),
+        };
+
+        match bytes {
+            [] => Err(self.error(ErrorKind::Length { tag: Tag::Integer })),
+            [0x00, next, ..] if *next & 0x80 == 0 => {
+                Err(self.error(ErrorKind::Noncanonical { tag: Tag::Integer }))
+            }
+            [0xFF, next, ..] if *next & 0x80 != 0 => {
+                Err(self.error(ErrorKind::Noncanonical { tag: Tag::Integer }))
+            }
+            _ => Ok(bytes),
+        }
+    }
+
+    /// Read a TLV with the given tag octet, returning its value.
+    const fn read_value(&mut self, tag_octet: u8, tag: Tag) -> Result<&'a [u8], Error> {
+        let actual = match self.read_byte() {
+            Ok(byte) => byte,
+            Err(err) => return Err(err),
+        };
+
+        if actual != tag_octet {
+            let kind = match decode_tag(actual) {
+                Some(actual) => ErrorKind::TagUnexpected {
+                    expected: Some(tag),
+                    actual,
+                },
+                None => ErrorKind::TagUnknown { byte: actual },
+            };
+
+            return Err(self.error(kind));
+        }
+
+        let len = match self.read_length(tag) {
+            Ok(len) => len,
+            Err(err) => return Err(err),
+        };
+
+        if len > self.end.saturating_sub(self.position) {
+            return Err(self.error(ErrorKind::Incomplete {
+                expected_len: length(self.position.saturating_add(len)),
+                actual_len: length(self.end),
+            }));
+        }
+
+        let (_, rest) = self.bytes.split_at(self.position);
+        let (value, _) = rest.split_at(len);
+        self.position += len;
+        Ok(value)
+    }
+
+    /// Read a DER-encoded length.
+    const fn read_length(&mut self, tag: Tag) -> Result<usize, Error> {
+        let first = match self.read_byte() {
+            Ok(byte) => byte,
+            Err(err) => return Err(err),
+        };
+
+        let nbytes = match first {
+            len @ 0x00..=0x7F => return Ok(len as usize),
+            0x80 => return Err(self.error(ErrorKind::IndefiniteLength)),
+            0x81..=0x84 => (first & 0x7F) as usize,
+            _ => return Err(self.error(ErrorKind::Overlength)),
+        };
+
+        let mut len = 0usize;
+        let mut i = 0;
+
+        while i < nbytes {
+            let byte = match self.read_byte() {
+                Ok(byte) => byte,
+                Err(err) => return Err(err),
+            };
+
+            // X.690 Section 10.1: DER lengths must be encoded with a minimum
+            // number of octets
+            if i == 0 && byte == 0 {
+                return Err(self.error(ErrorKind::Overlength));
+            }
+
+            len = (len << 8) | byte as usize;
+            i += 1;
+        }
+
+        if len < 0x80 {
+            return Err(self.error(ErrorKind::Length { tag }));
+        }
+
+        Ok(len)
+    }
+
+    /// Read a single byte.
+    const fn read_byte(&mut self) -> Result<u8, Error> {
+        if self.position >= self.end {
+            return Err(Error::new(
+                ErrorKind::Incomplete {
+                    expected_len: length(self.end.saturating_add(1)),
+                    actual_len: length(self.end),
+                },
+                length(self.end),
+            ));
+        }
+
+        let byte = self.bytes[self.position];
+        self.position += 1;
+        Ok(byte)
+    }
+
+    /// Return an error with the given [`ErrorKind`] annotated with the
+    /// current position.
+    const fn error(&self, kind: ErrorKind) -> Error {
+        Error::new(kind, length(self.position))
+    }
+}
+
+/// Convert a position within the input into a [`Length`], saturating at
+/// [`Length::MAX`] for (absurdly) large inputs.
+const fn length(n: usize) -> Length {
+    if n > u32::MAX as usize {
+        Length::MAX
+    } else {
+        #[allow(clippy::cast_possible_truncation)]
+        Length::new(n as u32)
+    }
+}
+
+/// Decode a single-octet tag, if it's one supported by this crate.
+const fn decode_tag(byte: u8) -> Option<Tag> {
+    let constructed = byte & 0x20 != 0;
+    let number = TagNumber((byte & 0x1F) as u32);
+
+    let tag = match byte {
+        0x01 => Tag::Boolean,
+        0x02 => Tag::Integer,
+        0x03 => Tag::BitString,
+        0x04 => Tag::OctetString,
+        0x05 => Tag::Null,
+        0x06 => Tag::ObjectIdentifier,
+        0x09 => Tag::Real,
+        0x0A => Tag::Enumerated,
+        0x0C => Tag::Utf8String,
+        0x0D => Tag::RelativeOid,
+        0x12 => Tag::NumericString,
+        0x13 => Tag::PrintableString,
+        0x14 => Tag::TeletexString,
+        0x15 => Tag::VideotexString,
+        0x16 => Tag::Ia5String,
+        0x17 => Tag::UtcTime,
+        0x18 => Tag::GeneralizedTime,
+        0x1A => Tag::VisibleString,
+        0x1B => Tag::GeneralString,
+        0x1E => Tag::BmpString,
+        0x30 => Tag::Sequence,
+        0x31 => Tag::Set,
+        _ if byte & 0x1F == 0x1F => return None,
+        0x40..=0x7F => Tag::Application {
+            constructed,
+            number,
+        },
+        0x80..=0xBF => Tag::ContextSpecific {
+            constructed,
+            number,
+        },
+        0xC0..=0xFF => Tag::Private {
+            constructed,
+            number,
+        },
+        _ => return None,
+    };
+
+    Some(tag)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::ConstReader;
+    use crate::{ErrorKind, Length, Tag};
+    use hex_literal::hex;
+
+    #[test]
+    fn decode_integers() {
+        assert_eq!(ConstReader::new(&hex!("020100")).decode_u64(), Ok(0));
+        assert_eq!(ConstReader::new(&hex!("02020080")).decode_u64(), Ok(128));
+        assert_eq!(
+            ConstReader::new(&hex!("020900FFFFFFFFFFFFFFFF")).decode_u64(),
+            Ok(u64::MAX)
+        );
+        assert_eq!(ConstReader::new(&hex!("0201FF")).decode_i64(), Ok(-1));
+        assert_eq!(ConstReader::new(&hex!("0202FF7F")).decode_i64(), Ok(-129));
+        assert_eq!(
+            ConstReader::new(&hex!("02087FFFFFFFFFFFFFFF")).decode_i64(),
+            Ok(i64::MAX)
+        );
+    }
+
+    #[test]
+    fn reject_bad_integers() {
+        let err = ConstReader::new(&hex!("02020001"))
+            .decode_u64()
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Noncanonical { tag: Tag::Integer });
+
+        let err = ConstReader::new(&hex!("0201FF")).decode_u64().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Value { tag: Tag::Integer });
+
+        let err = ConstReader::new(&hex!("0200")).decode_i64().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Length { tag: Tag::Integer });
+
+        let err = ConstReader::new(&hex!("0209010000000000000000"))
+            .decode_i64()
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Length { tag: Tag::Integer });
+    }
+
+    #[test]
+    fn decode_sequence() {
+        let mut reader = ConstReader::new(&hex!("3006040101040102 0500"));
+        let mut seq = reader.decode_sequence().unwrap();
+        assert_eq!(seq.decode_octet_string(), Ok(&[1u8][..]));
+        assert_eq!(seq.decode_octet_string(), Ok(&[2u8][..]));
+        assert!(seq.finish().is_ok());
+
+        let err = reader.finish().unwrap_err();
+        assert_eq!(err.position(), Some(Length::new(8)));
+    }
+
+    #[test]
+    fn reject_unexpected_tag() {
+        let err = ConstReader::new(&hex!("0401FF")).decode_u64().unwrap_err();
+        assert_eq!(
+            err.kind(),
+            ErrorKind::TagUnexpected {
+                expected: Some(Tag::Integer),
+                actual: Tag::OctetString
+            }
+        );
+    }
+
+    #[test]
+    fn reject_truncated() {
+        let err = ConstReader::new(&hex!("300504"))
+            .decode_sequence()
+            .unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::Incomplete { .. }));
+    }
+
+    #[cfg(feature = "oid")]
+    #[test]
+    fn decode_oid() {
+        const OID: const_oid::ObjectIdentifier =
+            match ConstReader::new(&hex!("06092A864886F70D010101")).decode_oid() {
+                Ok(oid) => oid,
+                Err(_) => panic!("malformed OID"),
+            };
+
+        assert_eq!(
+            OID,
+            const_oid::ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.1")
+        );
+    }
+}
@@ -0,0 +1,140 @@
+//! Reader options.
+
+use crate::{Error, ErrorKind, Length};
+
+/// Options limiting the input accepted by a [`Reader`][crate::Reader].
+///
+/// By default no limits are applied beyond those of this library, i.e. input
+/// is only limited by [`Length::MAX`]. Services decoding untrusted input can
+/// configure limits so that oversized input is rejected up front, and a TLV
+/// claiming an oversized length is rejected as soon as its header is decoded,
+/// with [`ErrorKind::LengthLimit`]:
+///
+/// ```
+/// use der::{Decode, ErrorKind, Length, ReaderOptions, SliceReader, asn1::OctetStringRef};
+///
+/// let options = ReaderOptions::new().with_max_value_len(Length::new(4));
+///
+/// let mut reader = SliceReader::new(&[0x04, 0x02, 0xAB, 0xCD])?.with_options(options)?;
+/// assert_eq!(<&OctetStringRef>::decode(&mut reader)?.as_bytes(), &[0xAB, 0xCD]);
+///
+/// // OCTET STRING claiming a length of 4 GiB
+/// let mut reader = SliceReader::new(&[0x04, 0x84, 0xFF, 0xFF, 0xFF, 0xFF])?.with_options(options)?;
+/// let err = <&OctetStringRef>::decode(&mut reader).unwrap_err();
+/// assert!(matches!(err.kind(), ErrorKind::LengthLimit { .. }));
+/// # Ok::<(), der::Error>(())
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ReaderOptions {
+    /// Maximum length of the input.
+    max_input_len: Option<Length>,
+
+    /// Maximum length of the value of a single TLV.
+    max_value_len: Option<Length>,
+
+    /// Handling of duplicate elements in a `SET OF`.
+    duplicate_policy: DuplicatePolicy,
+}
+
+impl ReaderOptions {
+    /// Create options which don't apply any limits.
+    pub const fn new() -> Self {
+        Self {
+            max_input_len: None,
+            max_value_len: None,
+            duplicate_policy: DuplicatePolicy::Reject,
+        }
+    }
+
+    /// Limit the length of the input.
+    pub const fn with_max_input_len(mut self, len: Length) -> Self {
+        self.max_input_len = Some(len);
+        self
+    }
+
+    /// Limit the length of the value of any single TLV in the input.
+    pub const fn with_max_value_len(mut self, len: Length) -> Self {
+        self.max_value_len = Some(len);
+        self
+    }
+
+    /// Set the handling of duplicate elements when decoding a `SET OF`.
+    pub const fn with_duplicate_policy(mut self, policy: DuplicatePolicy) -> Self {
+        self.duplicate_policy = policy;
+        self
+    }
+
+    /// Get the maximum length of the input, if any.
+    pub const fn max_input_len(&self) -> Option<Length> {
+        self.max_input_len
+    }
+
+    /// Get the maximum length of the value of a single TLV, if any.
+    pub const fn max_value_len(&self) -> Option<Length> {
+        self.max_value_len
+    }
+
+    /// Get the handling of duplicate elements when decoding a `SET OF`.
+    pub const fn duplicate_policy(&self) -> DuplicatePolicy {
+        self.duplicate_policy
+    }
+
+    /// Check the length of the input against the configured limit.
+    pub(crate) fn check_input_len(&self, length: Length) -> Result<(), Error> {
+        check_limit(length, self.max_input_len)
+    }
+
+    /// Check the length of a TLV value against the configured limit.
+    pub(crate) fn check_value_len(&self, length: Length) -> Result<(), Error> {
+        check_limit(length, self.max_value_len)
+    }
+}
+
+/// Handling of duplicate elements when decoding a `SET OF`, i.e. elements
+/// whose encodings are identical.
+///
+/// DER requires the elements of a `SET OF` to be unique, so duplicates are
+/// rejected with [`ErrorKind::SetDuplicate`] by default. Applications
+/// interoperating with encoders which don't enforce this can relax it:
+///
+/// ```
+/// # #[cfg(feature = "alloc")]
+/// # {
+/// use der::{Decode, DuplicatePolicy, ErrorKind, ReaderOptions, SliceReader, asn1::SetOfVec};
+///
+/// // SET OF { INTEGER 1, INTEGER 1 }
+/// let der = [0x31, 0x06, 0x02, 0x01, 0x01, 0x02, 0x01, 0x01];
+///
+/// let err = SetOfVec::<u8>::from_der(&der).unwrap_err();
+/// assert_eq!(err.kind(), ErrorKind::SetDuplicate);
+///
+/// let options = ReaderOptions::new().with_duplicate_policy(DuplicatePolicy::Dedupe);
+/// let mut reader = SliceReader::new(&der)?.with_options(options)?;
+/// assert_eq!(SetOfVec::<u8>::decode(&mut reader)?.as_slice(), &[1]);
+/// # }
+/// # Ok::<(), der::Error>(())
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum DuplicatePolicy {
+    /// Reject duplicates with [`ErrorKind::SetDuplicate`], as required by DER.
+    #[default]
+    Reject,
+
+    /// Keep only the first of the duplicate elements, so that the decoded
+    /// set re-encodes as valid DER.
+    Dedupe,
+
+    /// Keep all the duplicate elements.
+    ///
+    /// Note that the decoded set then doesn't re-encode as valid DER.
+    Allow,
+}
+
+/// Ensure the given length doesn't exceed the given limit, if any.
+fn check_limit(length: Length, limit: Option<Length>) -> Result<(), Error> {
+    match limit {
+        Some(limit) if length > limit => Err(ErrorKind::LengthLimit { length, limit }.into()),
+        _ => Ok(()),
+    }
+}
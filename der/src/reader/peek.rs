@@ -0,0 +1,195 @@
+//! Reader adapter used to decode the upcoming input of another reader.
+
+use super::Reader;
+use crate::{EncodingRules, Error, ErrorKind, Length, ReaderOptions};
+
+/// Maximum amount of input peeked using [`Reader::peek_into`], which covers
+/// the longest [`Header`][`crate::Header`] supported by this crate.
+const MAX_PEEK_LEN: usize = 16;
+
+/// [`Reader`] decoding the upcoming input of the wrapped reader without
+/// modifying its state.
+///
+/// Input is obtained using [`Reader::peek_into`], so readers which can't
+/// cheaply be cloned, e.g. streaming readers, can support peeking by
+/// overriding it. Only peeks going further than [`MAX_PEEK_LEN`] bytes, e.g.
+/// when computing BER indefinite lengths, fall back to cloning the reader.
+#[derive(Clone)]
+pub(crate) struct PeekReader<'a, R> {
+    /// Reader whose input is peeked.
+    inner: &'a R,
+
+    /// Amount of input read through this reader so far.
+    offset: Length,
+
+    /// Clone of the inner reader, created once more than [`MAX_PEEK_LEN`]
+    /// bytes are read.
+    fallback: Option<R>,
+}
+
+impl<'a, R> PeekReader<'a, R> {
+    /// Create a new reader peeking at the input of `inner`.
+    pub(crate) fn new(inner: &'a R) -> Self {
+        Self {
+            inner,
+            offset: Length::ZERO,
+            fallback: None,
+        }
+    }
+}
+
+impl<'r, R: Reader<'r>> Reader<'r> for PeekReader<'_, R> {
+    fn encoding_rules(&self) -> EncodingRules {
+        self.inner.encoding_rules()
+    }
+
+    fn options(&self) -> ReaderOptions {
+        self.inner.options()
+    }
+
+    fn input_len(&self) -> Length {
+        self.inner.input_len()
+    }
+
+    fn position(&self) -> Length {
+        match &self.fallback {
+            Some(fallback) => fallback.position(),
+            None => self.inner.position().saturating_add(self.offset),
+        }
+    }
+
+    fn read_nested<T, F, E>(&mut self, _len: Length, _f: F) -> Result<T, E>
+    where
+        E: From<Error>,
+        F: FnOnce(&mut Self) -> Result<T, E>,
+    {
+        // Headers and tags are decoded without nesting
+        Err(self.error(ErrorKind::Reader).into())
+    }
+
+    fn read_slice(&mut self, _len: Length) -> Result<&'r [u8], Error> {
+        // Peeked input is copied, so it can't be borrowed
+        Err(self.error(ErrorKind::Reader))
+    }
+
+    fn read_into<'o>(&mut self, buf: &'o mut [u8]) -> Result<&'o [u8], Error> {
+        if let Some(fallback) = &mut self.fallback {
+            return fallback.read_into(buf);
+        }
+
+        let start = usize::try_from(self.offset)?;
+        let end = start
+            .checked_add(buf.len())
+            .ok_or_else(|| self.error(ErrorKind::Overflow))?;
+
+        if end > MAX_PEEK_LEN {
+            let mut fallback = self.inner.clone();
+            fallback.drain(self.offset)?;
+            self.fallback = Some(fallback);
+            return self.read_into(buf);
+        }
+
+        let mut peeked = [0u8; MAX_PEEK_LEN];
+
+        // Report errors at the position of the data which couldn't be read
+        self.inner
+            .peek_into(&mut peeked[..end])
+            .map_err(|e| e.kind().at(self.position()))?;
+
+        buf.copy_from_slice(&peeked[start..end]);
+        self.offset = (self.offset + buf.len())?;
+        Ok(buf)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::PeekReader;
+    use crate::{
+        EncodingRules, Error, ErrorKind, Header, Length, Reader, ReaderOptions, SliceReader, Tag,
+    };
+    use hex_literal::hex;
+
+    /// Reader which can't be cloned to peek at its input, like a streaming
+    /// reader, but implements [`Reader::peek_into`] using a lookahead buffer.
+    struct LookaheadReader<'a> {
+        /// Underlying reader.
+        inner: SliceReader<'a>,
+    }
+
+    impl Clone for LookaheadReader<'_> {
+        fn clone(&self) -> Self {
+            // Peeking must go through `peek_into` rather than cloning the reader
+            unreachable!("LookaheadReader is never cloned by Header::peek or Tag::peek")
+        }
+    }
+
+    impl<'a> Reader<'a> for LookaheadReader<'a> {
+        fn encoding_rules(&self) -> EncodingRules {
+            self.inner.encoding_rules()
+        }
+
+        fn options(&self) -> ReaderOptions {
+            self.inner.options()
+        }
+
+        fn input_len(&self) -> Length {
+            self.inner.input_len()
+        }
+
+        fn position(&self) -> Length {
+            self.inner.position()
+        }
+
+        fn read_nested<T, F, E>(&mut self, _len: Length, _f: F) -> Result<T, E>
+        where
+            E: From<Error>,
+            F: FnOnce(&mut Self) -> Result<T, E>,
+        {
+            // Peeking only reads the header, so never descends into a value
+            unreachable!("peek_without_clone doesn't read nested values")
+        }
+
+        fn read_slice(&mut self, len: Length) -> Result<&'a [u8], Error> {
+            self.inner.read_slice(len)
+        }
+
+        fn peek_into(&self, buf: &mut [u8]) -> Result<(), Error> {
+            self.inner.peek_into(buf)
+        }
+    }
+
+    #[test]
+    fn peek_without_clone() {
+        let reader = LookaheadReader {
+            inner: SliceReader::new(&hex!("BF8FFFFFFF7F 03 020101")).unwrap(),
+        };
+
+        let header = Header::peek(&reader).unwrap();
+        assert_eq!(header.tag().number().value(), u32::MAX);
+        assert_eq!(header.length(), Length::new(3));
+        assert_eq!(Tag::peek(&reader).unwrap(), header.tag());
+        assert_eq!(reader.position(), Length::ZERO);
+    }
+
+    #[test]
+    fn peek_truncated() {
+        let reader = SliceReader::new(&hex!("3082")).unwrap();
+        let err = Header::peek(&reader).unwrap_err();
+        assert_eq!(err.position(), Some(Length::new(2)));
+        assert!(matches!(err.kind(), ErrorKind::Incomplete { .. }));
+    }
+
+    #[test]
+    fn peek_beyond_buffer() {
+        let reader = SliceReader::new(&[0u8; 20]).unwrap();
+        let mut peek_reader = PeekReader::new(&reader);
+        let mut buf = [0u8; 12];
+        peek_reader.read_into(&mut buf).unwrap();
+        peek_reader.read_into(&mut buf[..8]).unwrap();
+        assert_eq!(peek_reader.position(), Length::new(20));
+        assert!(peek_reader.read_byte().is_err());
+        assert_eq!(reader.position(), Length::ZERO);
+    }
+}
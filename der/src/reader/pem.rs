@@ -1,8 +1,14 @@
 //! Streaming PEM reader.
 
 use super::{Reader, position::Position};
-use crate::{EncodingRules, Error, ErrorKind, Length, Result};
-use pem_rfc7468::Decoder;
+use crate::{EncodingRules, Error, ErrorKind, Length, ReaderOptions, Result};
+use pem_rfc7468::{Decoder, Error as PemError};
+
+/// Pre-encapsulation boundary, which begins a PEM block.
+const PRE_ENCAPSULATION_BOUNDARY: &[u8] = b"-----BEGIN ";
+
+/// Post-encapsulation boundary, which ends a PEM block.
+const POST_ENCAPSULATION_BOUNDARY: &[u8] = b"-----END ";
 
 /// `Reader` type which decodes PEM on-the-fly.
 #[cfg(feature = "pem")]
@@ -14,6 +20,9 @@ pub struct PemReader<'i> {
     /// Encoding rules to apply when decoding the input.
     encoding_rules: EncodingRules,
 
+    /// Options limiting the accepted input.
+    options: ReaderOptions,
+
     /// Position tracker.
     position: Position,
 }
@@ -30,10 +39,35 @@ impl<'i> PemReader<'i> {
         Ok(Self {
             decoder,
             encoding_rules: EncodingRules::default(),
+            options: ReaderOptions::default(),
             position: Position::new(input_len),
         })
     }
 
+    /// Iterate over the encapsulated blocks of the given PEM input, e.g. a
+    /// bundle of certificates, yielding a reader for each of them.
+    ///
+    /// Blocks may have any type label, which callers can inspect using
+    /// [`PemReader::type_label`] to decide how to decode them, unless it's
+    /// restricted using [`PemBlocks::with_label`]. Any text before, between, or
+    /// after the blocks is ignored.
+    pub fn blocks(pem: &'i [u8]) -> PemBlocks<'i> {
+        PemBlocks {
+            remaining: pem,
+            label: None,
+        }
+    }
+
+    /// Apply the given [`ReaderOptions`] to this reader.
+    ///
+    /// Returns [`ErrorKind::LengthLimit`] if the decoded input is longer than
+    /// allowed.
+    pub fn with_options(mut self, options: ReaderOptions) -> Result<Self> {
+        options.check_input_len(self.input_len())?;
+        self.options = options;
+        Ok(self)
+    }
+
     /// Get the PEM label which will be used in the encapsulation boundaries
     /// for this document.
     pub fn type_label(&self) -> &'i str {
@@ -47,6 +81,10 @@ impl<'i> Reader<'i> for PemReader<'i> {
         self.encoding_rules
     }
 
+    fn options(&self) -> ReaderOptions {
+        self.options
+    }
+
     fn input_len(&self) -> Length {
         self.position.input_len()
     }
@@ -77,3 +115,107 @@ impl<'i> Reader<'i> for PemReader<'i> {
         Ok(buf)
     }
 }
+
+/// Iterator over the encapsulated blocks of a PEM input.
+///
+/// Created by [`PemReader::blocks`]:
+///
+/// ```
+/// use der::{PemReader, Reader, asn1::{Null, OctetString}};
+///
+/// let pem = "-----BEGIN NULL-----\nBQA=\n-----END NULL-----\n\
+///            -----BEGIN OCTETS-----\nBAIBAg==\n-----END OCTETS-----\n";
+///
+/// for block in PemReader::blocks(pem.as_bytes()) {
+///     let mut reader = block?;
+///
+///     match reader.type_label() {
+///         "NULL" => assert_eq!(reader.decode::<Null>()?, Null),
+///         "OCTETS" => assert_eq!(reader.decode::<OctetString>()?.as_bytes(), &[1, 2]),
+///         label => panic!("unexpected label: {label}"),
+///     }
+/// }
+/// # Ok::<(), der::Error>(())
+/// ```
+#[cfg(feature = "pem")]
+#[derive(Clone, Debug)]
+pub struct PemBlocks<'i> {
+    /// Input remaining after the previously yielded block.
+    remaining: &'i [u8],
+
+    /// Type label blocks are required to have, if any.
+    label: Option<&'static str>,
+}
+
+#[cfg(feature = "pem")]
+impl PemBlocks<'_> {
+    /// Require all blocks to have the given type label, returning an error
+    /// for any block with another label rather than yielding it.
+    pub fn with_label(mut self, label: &'static str) -> Self {
+        self.label = Some(label);
+        self
+    }
+
+    /// Get the input remaining after the previously yielded block.
+    pub fn remaining(&self) -> &[u8] {
+        self.remaining
+    }
+}
+
+#[cfg(feature = "pem")]
+impl<'i> Iterator for PemBlocks<'i> {
+    type Item = Result<PemReader<'i>>;
+
+    fn next(&mut self) -> Option<Result<PemReader<'i>>> {
+        // Trailing text is ignored, like the text before each block
+        find(self.remaining, PRE_ENCAPSULATION_BOUNDARY)?;
+
+        // Stop iterating after the first error, as blocks can't be delimited
+        // reliably anymore
+        let (block, remaining) = match split_block(self.remaining) {
+            Some(split) => split,
+            None => {
+                self.remaining = &[];
+                return Some(Err(PemError::PostEncapsulationBoundary.into()));
+            }
+        };
+        self.remaining = remaining;
+
+        let reader = PemReader::new(block);
+        Some(match (reader, self.label) {
+            (Ok(reader), Some(expected)) if reader.type_label() != expected => {
+                Err(PemError::UnexpectedTypeLabel { expected }.into())
+            }
+            (result, _) => result,
+        })
+    }
+}
+
+/// Split the given PEM input after the end of the line containing its first
+/// post-encapsulation boundary, i.e. after its first block.
+fn split_block(pem: &[u8]) -> Option<(&[u8], &[u8])> {
+    let boundary = find(pem, POST_ENCAPSULATION_BOUNDARY)?;
+    let line_len = pem
+        .get(boundary..)?
+        .iter()
+        .position(|&c| c == b'\r' || c == b'\n')
+        .unwrap_or(pem.len() - boundary);
+
+    let mut end = boundary + line_len;
+
+    // Include the line ending: CRLF, CR, or LF
+    match pem.get(end..) {
+        Some([b'\r', b'\n', ..]) => end += 2,
+        Some([b'\r' | b'\n', ..]) => end += 1,
+        _ => (),
+    }
+
+    Some(pem.split_at(end))
+}
+
+/// Find the position of the first occurrence of `needle` in `haystack`.
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
@@ -1,6 +1,6 @@
 //! Slice reader.
 
-use crate::{BytesRef, Decode, EncodingRules, Error, ErrorKind, Length, Reader};
+use crate::{BytesRef, Decode, EncodingRules, Error, ErrorKind, Length, Reader, ReaderOptions};
 
 /// [`Reader`] which consumes an input byte slice.
 #[derive(Clone, Debug)]
@@ -11,6 +11,9 @@ pub struct SliceReader<'a> {
     /// Encoding rules to apply when decoding the input.
     encoding_rules: EncodingRules,
 
+    /// Options limiting the accepted input.
+    options: ReaderOptions,
+
     /// Did the decoding operation fail?
     failed: bool,
 
@@ -32,11 +35,21 @@ impl<'a> SliceReader<'a> {
         Ok(Self {
             bytes: BytesRef::new(bytes)?,
             encoding_rules,
+            options: ReaderOptions::default(),
             failed: false,
             position: Length::ZERO,
         })
     }
 
+    /// Apply the given [`ReaderOptions`] to this reader.
+    ///
+    /// Returns [`ErrorKind::LengthLimit`] if the input is longer than allowed.
+    pub fn with_options(mut self, options: ReaderOptions) -> Result<Self, Error> {
+        options.check_input_len(self.input_len())?;
+        self.options = options;
+        Ok(self)
+    }
+
     /// Return an error with the given [`ErrorKind`], annotating it with
     /// context about where the error occurred.
     pub fn error(&mut self, kind: ErrorKind) -> Error {
@@ -75,6 +88,10 @@ impl<'a> Reader<'a> for SliceReader<'a> {
         self.encoding_rules
     }
 
+    fn options(&self) -> ReaderOptions {
+        self.options
+    }
+
     fn input_len(&self) -> Length {
         self.bytes.len()
     }
@@ -161,7 +178,7 @@ impl<'a> Reader<'a> for SliceReader<'a> {
 #[allow(clippy::unwrap_used, clippy::panic)]
 mod tests {
     use super::SliceReader;
-    use crate::{Decode, ErrorKind, Length, Reader};
+    use crate::{Decode, ErrorKind, Length, Reader, ReaderOptions};
     use hex_literal::hex;
 
     // INTEGER: 42
@@ -222,4 +239,87 @@ mod tests {
             err.kind()
         );
     }
+
+    #[test]
+    fn skip_tlv() {
+        // SEQUENCE { INTEGER 42, [0] { OCTET STRING "hi" }, BOOLEAN TRUE }
+        let mut reader = SliceReader::new(&hex!("300C 02012A A0040402 6869 0101FF")).unwrap();
+        let value = reader
+            .sequence(|seq| {
+                let value = u8::decode(seq)?;
+                seq.skip_remaining()?;
+                Ok::<_, crate::Error>(value)
+            })
+            .unwrap();
+
+        assert_eq!(value, 42);
+        assert!(reader.is_finished());
+
+        let mut reader = SliceReader::new(&hex!("A0040402 6869 0101FF")).unwrap();
+        let header = reader.skip_tlv().unwrap();
+        assert!(header.tag().is_context_specific());
+        assert_eq!(bool::decode(&mut reader), Ok(true));
+    }
+
+    #[test]
+    fn skip_truncated_tlv() {
+        let mut reader = SliceReader::new(&hex!("02012A 0405 0102")).unwrap();
+        let err = reader.skip_remaining().unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::Incomplete { .. }));
+    }
+
+    #[test]
+    fn max_input_len() {
+        let options = ReaderOptions::new().with_max_input_len(Length::new(3));
+        let err = SliceReader::new(EXAMPLE_MSG)
+            .unwrap()
+            .with_options(options)
+            .unwrap_err();
+
+        assert_eq!(
+            err.kind(),
+            ErrorKind::LengthLimit {
+                length: Length::new(4),
+                limit: Length::new(3)
+            }
+        );
+
+        let options = ReaderOptions::new().with_max_input_len(Length::new(4));
+        assert!(
+            SliceReader::new(EXAMPLE_MSG)
+                .unwrap()
+                .with_options(options)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn max_value_len() {
+        // SEQUENCE { INTEGER 42 }
+        let msg = hex!("3003 02012A");
+
+        let options = ReaderOptions::new().with_max_value_len(Length::new(3));
+        let mut reader = SliceReader::new(&msg)
+            .unwrap()
+            .with_options(options)
+            .unwrap();
+        let value = reader.sequence(|reader| reader.decode::<i8>()).unwrap();
+        assert_eq!(value, 42);
+
+        let options = ReaderOptions::new().with_max_value_len(Length::new(2));
+        let mut reader = SliceReader::new(&msg)
+            .unwrap()
+            .with_options(options)
+            .unwrap();
+        let err = reader.sequence(|reader| reader.decode::<i8>()).unwrap_err();
+        assert_eq!(Some(Length::new(2)), err.position());
+        assert_eq!(
+            err.kind(),
+            ErrorKind::LengthLimit {
+                length: Length::new(3),
+                limit: Length::new(2)
+            }
+        );
+        assert!(reader.is_failed());
+    }
 }
@@ -0,0 +1,256 @@
+//! Hook for the content a decoder can't place.
+
+use crate::{AnyRef, Decode, EncodingRules, Error, ErrorKind, Length, Reader, ReaderOptions};
+
+/// Hook invoked with the TLVs a decoder can't place, such as unknown
+/// `CONTEXT-SPECIFIC` extensions of an extensible `SEQUENCE`.
+///
+/// By default such TLVs are rejected, e.g. with [`ErrorKind::TrailingData`].
+/// Wrapping a reader in a [`HookedReader`] passes them to the hook instead,
+/// which can capture the raw TLV and let decoding continue by returning
+/// `Ok(())`, or abort it by returning an error. Along with the strictness of
+/// the [`EncodingRules`], this allows to tune how much unexpected input is
+/// tolerated when interoperating with other implementations.
+///
+/// The TLVs passed to the hook are the unknown extensions of extensible
+/// `SEQUENCE`s, handled by [`Reader::decode_unknown_extensions`], and the
+/// unknown alternatives of extensible `CHOICE`s, handled by
+/// [`Reader::decode_unknown_alternative`]. Without a hook, the latter are
+/// rejected with [`ErrorKind::TagUnexpected`].
+///
+/// The hook is called by shared reference, so it needs interior mutability
+/// to capture TLVs. It is implemented for closures:
+///
+/// ```
+/// use core::cell::Cell;
+/// use der::{AnyRef, Decode, HookedReader, Reader, SliceReader, Tag, Tagged};
+///
+/// /// Decode `SEQUENCE { version INTEGER, ... }`.
+/// fn decode_version<'a>(reader: &mut impl Reader<'a>) -> der::Result<u8> {
+///     reader.sequence(|reader| {
+///         let version = u8::decode(reader)?;
+///         reader.decode_unknown_extensions(None)?;
+///         Ok(version)
+///     })
+/// }
+///
+/// // SEQUENCE { INTEGER 1, [0] { NULL } }
+/// let der = [0x30, 0x07, 0x02, 0x01, 0x01, 0xA0, 0x02, 0x05, 0x00];
+/// assert!(decode_version(&mut SliceReader::new(&der)?).is_err());
+///
+/// let unknown = Cell::new(None);
+/// let hook = |tlv: AnyRef<'_>| {
+///     unknown.set(Some(tlv.tag()));
+///     Ok(())
+/// };
+///
+/// let mut reader = HookedReader::new(SliceReader::new(&der)?, &hook);
+/// assert_eq!(decode_version(&mut reader)?, 1);
+/// assert!(matches!(unknown.get(), Some(Tag::ContextSpecific { .. })));
+/// # Ok::<(), der::Error>(())
+/// ```
+///
+/// [`ErrorKind::TrailingData`]: crate::ErrorKind::TrailingData
+/// [`ErrorKind::TagUnexpected`]: crate::ErrorKind::TagUnexpected
+pub trait DecodeUnknown<'r> {
+    /// Handle a TLV which the decoder can't place.
+    ///
+    /// Returning `Ok(())` skips the TLV, and an error aborts decoding.
+    fn decode_unknown(&self, tlv: AnyRef<'r>) -> Result<(), Error>;
+}
+
+impl<'r, F: ?Sized> DecodeUnknown<'r> for F
+where
+    F: Fn(AnyRef<'r>) -> Result<(), Error>,
+{
+    fn decode_unknown(&self, tlv: AnyRef<'r>) -> Result<(), Error> {
+        self(tlv)
+    }
+}
+
+/// Reader passing the TLVs which a decoder can't place to a [`DecodeUnknown`]
+/// hook, rather than rejecting them.
+///
+/// The hook is also used by the readers of the nested values. The TLVs are
+/// passed as [`AnyRef`], so the wrapped reader must borrow from its input.
+pub struct HookedReader<'h, R, H: ?Sized> {
+    /// Wrapped reader.
+    inner: R,
+
+    /// Hook invoked with the TLVs the decoder can't place.
+    hook: &'h H,
+}
+
+impl<'h, R, H: ?Sized> HookedReader<'h, R, H> {
+    /// Wrap the given reader, passing the TLVs a decoder can't place to the
+    /// given hook.
+    pub fn new(reader: R, hook: &'h H) -> Self {
+        Self {
+            inner: reader,
+            hook,
+        }
+    }
+
+    /// Get the wrapped reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Clone, H: ?Sized> Clone for HookedReader<'_, R, H> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            hook: self.hook,
+        }
+    }
+}
+
+impl<R: core::fmt::Debug, H: ?Sized> core::fmt::Debug for HookedReader<'_, R, H> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("HookedReader")
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'r, R, H> Reader<'r> for HookedReader<'_, R, H>
+where
+    R: Reader<'r>,
+    H: DecodeUnknown<'r> + ?Sized,
+{
+    fn encoding_rules(&self) -> EncodingRules {
+        self.inner.encoding_rules()
+    }
+
+    fn options(&self) -> ReaderOptions {
+        self.inner.options()
+    }
+
+    fn input_len(&self) -> Length {
+        self.inner.input_len()
+    }
+
+    fn position(&self) -> Length {
+        self.inner.position()
+    }
+
+    fn read_nested<T, F, E>(&mut self, len: Length, f: F) -> Result<T, E>
+    where
+        E: From<Error>,
+        F: FnOnce(&mut Self) -> Result<T, E>,
+    {
+        let hook = self.hook;
+
+        self.inner.read_nested(len, |inner| {
+            let mut nested = HookedReader::new(inner.clone(), hook);
+            let ret = f(&mut nested);
+            *inner = nested.inner;
+            ret
+        })
+    }
+
+    fn read_slice(&mut self, len: Length) -> Result<&'r [u8], Error> {
+        self.inner.read_slice(len)
+    }
+
+    fn read_into<'o>(&mut self, buf: &'o mut [u8]) -> Result<&'o [u8], Error> {
+        self.inner.read_into(buf)
+    }
+
+    fn error(&mut self, kind: ErrorKind) -> Error {
+        self.inner.error(kind)
+    }
+
+    fn finish(self) -> Result<(), Error> {
+        self.inner.finish()
+    }
+
+    fn offset(&self) -> Length {
+        self.inner.offset()
+    }
+
+    fn remaining_len(&self) -> Length {
+        self.inner.remaining_len()
+    }
+
+    fn decode_unknown(&mut self) -> Result<bool, Error> {
+        let tlv = AnyRef::decode(self)?;
+        self.hook.decode_unknown(tlv)?;
+        Ok(true)
+    }
+
+    fn decode_unknown_alternative(&mut self, tlv: AnyRef<'r>) -> Result<bool, Error> {
+        self.hook.decode_unknown(tlv)?;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::HookedReader;
+    use crate::{AnyRef, Decode, Error, ErrorKind, Reader, SliceReader, Tag, TagNumber, Tagged};
+    use core::cell::Cell;
+    use hex_literal::hex;
+
+    // SEQUENCE { INTEGER 1, [0] { NULL }, [1] { NULL } }
+    const EXAMPLE_MSG: &[u8] = &hex!("300B020101A0020500A1020500");
+
+    /// Decode the version of `SEQUENCE { version INTEGER, ..., [1] NULL OPTIONAL }`,
+    /// stopping at the unknown extensions preceding `[1]`.
+    fn decode_version<'a>(reader: &mut impl Reader<'a>) -> crate::Result<u8> {
+        reader.sequence(|reader| {
+            let version = u8::decode(reader)?;
+            reader.decode_unknown_extensions(Some(Tag::ContextSpecific {
+                constructed: true,
+                number: TagNumber(1),
+            }))?;
+            reader.drain(reader.remaining_len())?;
+            Ok(version)
+        })
+    }
+
+    #[test]
+    fn unknown_rejected_by_default() {
+        let mut reader = SliceReader::new(&EXAMPLE_MSG[2..]).unwrap();
+        u8::decode(&mut reader).unwrap();
+        assert!(!reader.decode_unknown().unwrap());
+        assert!(matches!(
+            reader.finish().unwrap_err().kind(),
+            ErrorKind::TrailingData { .. }
+        ));
+    }
+
+    #[test]
+    fn unknown_extensions_before_next() {
+        let count = Cell::new(0);
+        let hook = |tlv: AnyRef<'_>| {
+            assert_eq!(tlv.tag().number(), TagNumber(0));
+            count.set(count.get() + 1);
+            Ok(())
+        };
+
+        let mut reader = HookedReader::new(SliceReader::new(EXAMPLE_MSG).unwrap(), &hook);
+        assert_eq!(decode_version(&mut reader).unwrap(), 1);
+        assert_eq!(count.get(), 1);
+        reader.finish().unwrap();
+    }
+
+    #[test]
+    fn unknown_rejected_by_hook() {
+        let hook = |tlv: AnyRef<'_>| Err(Error::from(tlv.tag().value_error()));
+
+        let mut reader = HookedReader::new(SliceReader::new(EXAMPLE_MSG).unwrap(), &hook);
+        let err = decode_version(&mut reader).unwrap_err();
+        assert_eq!(
+            err.kind(),
+            ErrorKind::Value {
+                tag: Tag::ContextSpecific {
+                    constructed: true,
+                    number: TagNumber(0)
+                }
+            }
+        );
+    }
+}
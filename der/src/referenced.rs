@@ -59,6 +59,51 @@ where
     }
 }
 
+/// Impl [`OwnedToRef`] and [`RefToOwned`] as the identity for types which
+/// don't borrow any data, so they can be used in structs with owned and
+/// borrowed forms.
+macro_rules! impl_identity {
+    ($($ty:ty),+) => {
+        $(
+            impl<'a> RefToOwned<'a> for $ty {
+                type Owned = $ty;
+
+                fn ref_to_owned(&self) -> Self::Owned {
+                    *self
+                }
+            }
+
+            impl OwnedToRef for $ty {
+                type Borrowed<'a> = $ty;
+
+                fn owned_to_ref(&self) -> Self::Borrowed<'_> {
+                    *self
+                }
+            }
+        )+
+    };
+}
+
+impl_identity!(
+    bool,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    crate::asn1::Null,
+    crate::asn1::GeneralizedTime,
+    crate::asn1::UtcTime
+);
+
+#[cfg(feature = "oid")]
+impl_identity!(crate::asn1::ObjectIdentifier);
+
 #[cfg(feature = "alloc")]
 mod allocating {
     use super::{OwnedToRef, RefToOwned};
@@ -0,0 +1,288 @@
+//! Generation of ASN.1 module skeletons from Rust types.
+//!
+//! When the `schema` crate feature is enabled, the custom derive macros for
+//! `Sequence`, `Choice`, `Enumerated`, and `BitString` additionally
+//! impl the [`Asn1Schema`] trait, which writes an approximation of the ASN.1
+//! type assignment the Rust type corresponds to, including component names,
+//! tags, and `OPTIONAL`/`DEFAULT` markers.
+//!
+//! These can be collected into a [`Module`] whose [`fmt::Display`] impl
+//! renders a textual ASN.1 module, which can then be diffed against the one
+//! published in a specification to mechanically spot mismatches:
+//!
+//! ```ignore
+//! use der::schema::{Asn1Schema, Module};
+//!
+//! let module = Module::new(
+//!     "PKIX1Explicit88",
+//!     &[
+//!         Certificate::write_definition,
+//!         TbsCertificate::write_definition,
+//!     ],
+//! );
+//!
+//! println!("{module}");
+//! ```
+//!
+//! The output is an approximation: `DEFAULT` values are rendered as the
+//! Rust path of the function supplying them, constraints aren't rendered, and
+//! components whose type doesn't impl [`Asn1Schema`] (e.g. because it has
+//! handwritten `Decode`/`Encode` impls) are rendered using the name of the
+//! Rust type.
+
+use crate::asn1::{
+    AnyRef, BitStringRef, Duration, GeneralStringRef, GeneralizedTime, Ia5StringRef, IntRef, LeU32,
+    LeU64, Null, NumericStringRef, OctetStringRef, PrintableStringRef, SequenceOf, SetOf,
+    TeletexStringRef, TimeOfDay, UintRef, UtcTime, Utf8StringRef, VideotexStringRef,
+};
+use crate::{DateTime, DerOrd};
+use core::{fmt, marker::PhantomData};
+
+#[cfg(feature = "alloc")]
+use {
+    crate::asn1::{
+        Any, BitString, BmpString, Ia5String, Int, OctetString, PrintableString, SetOfVec,
+        TeletexString, Uint,
+    },
+    alloc::{boxed::Box, string::String, vec::Vec},
+};
+
+#[cfg(feature = "oid")]
+use crate::asn1::ObjectIdentifier;
+
+/// Function writing the definition of a type, see [`Asn1Schema::write_definition`].
+pub type Definition = fn(&mut dyn fmt::Write) -> fmt::Result;
+
+/// Types which correspond to an ASN.1 type which can be written in ASN.1
+/// notation.
+pub trait Asn1Schema {
+    /// Write a reference to this type, e.g. `INTEGER`, `SEQUENCE OF Name`,
+    /// or the name of a user-defined type.
+    fn write_type(w: &mut dyn fmt::Write) -> fmt::Result;
+
+    /// Write the type assignment defining this type, e.g.
+    /// `Name ::= SEQUENCE { ... }`.
+    ///
+    /// Only user-defined types have a definition, so this writes nothing by
+    /// default.
+    fn write_definition(w: &mut dyn fmt::Write) -> fmt::Result {
+        let _ = w;
+        Ok(())
+    }
+}
+
+/// ASN.1 module skeleton consisting of the definitions of a set of types.
+#[derive(Clone, Copy, Debug)]
+pub struct Module<'a> {
+    /// Module name.
+    name: &'a str,
+
+    /// Definitions of the types in the module.
+    definitions: &'a [Definition],
+}
+
+impl<'a> Module<'a> {
+    /// Create a new [`Module`] with the given name and type definitions.
+    pub const fn new(name: &'a str, definitions: &'a [Definition]) -> Self {
+        Self { name, definitions }
+    }
+}
+
+impl fmt::Display for Module<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} DEFINITIONS ::= BEGIN", self.name)?;
+
+        for definition in self.definitions {
+            f.write_str("\n")?;
+            definition(f)?;
+            f.write_str("\n")?;
+        }
+
+        f.write_str("\nEND\n")
+    }
+}
+
+/// Impl [`Asn1Schema`] for types which correspond to a built-in ASN.1 type.
+macro_rules! impl_asn1_schema {
+    ($asn1:expr => $($ty:ty),+ $(,)?) => {
+        $(
+            impl Asn1Schema for $ty {
+                fn write_type(w: &mut dyn fmt::Write) -> fmt::Result {
+                    w.write_str($asn1)
+                }
+            }
+        )+
+    };
+}
+
+impl_asn1_schema!("BOOLEAN" => bool);
+impl_asn1_schema!("INTEGER" => i8, i16, i32, i64, i128, u8, u16, u32, u64, u128);
+impl_asn1_schema!("INTEGER" => IntRef<'_>, UintRef<'_>);
+impl_asn1_schema!("NULL" => (), Null);
+impl_asn1_schema!("ANY" => AnyRef<'_>);
+impl_asn1_schema!("BIT STRING" => BitStringRef<'_>);
+impl_asn1_schema!("OCTET STRING" => OctetStringRef, LeU32, LeU64);
+impl_asn1_schema!("UTF8String" => str, Utf8StringRef<'_>);
+impl_asn1_schema!("NumericString" => NumericStringRef<'_>);
+impl_asn1_schema!("PrintableString" => PrintableStringRef<'_>);
+impl_asn1_schema!("IA5String" => Ia5StringRef<'_>);
+impl_asn1_schema!("TeletexString" => TeletexStringRef<'_>);
+impl_asn1_schema!("VideotexString" => VideotexStringRef<'_>);
+impl_asn1_schema!("GeneralString" => GeneralStringRef<'_>);
+impl_asn1_schema!("UTCTime" => UtcTime);
+impl_asn1_schema!("GeneralizedTime" => GeneralizedTime, DateTime);
+impl_asn1_schema!("TIME-OF-DAY" => TimeOfDay);
+impl_asn1_schema!("DURATION" => Duration);
+
+#[cfg(feature = "alloc")]
+impl_asn1_schema!("INTEGER" => Int, Uint);
+#[cfg(feature = "alloc")]
+impl_asn1_schema!("ANY" => Any);
+#[cfg(feature = "alloc")]
+impl_asn1_schema!("BIT STRING" => BitString);
+#[cfg(feature = "alloc")]
+impl_asn1_schema!("OCTET STRING" => OctetString);
+#[cfg(feature = "alloc")]
+impl_asn1_schema!("UTF8String" => String);
+#[cfg(feature = "alloc")]
+impl_asn1_schema!("PrintableString" => PrintableString);
+#[cfg(feature = "alloc")]
+impl_asn1_schema!("IA5String" => Ia5String);
+#[cfg(feature = "alloc")]
+impl_asn1_schema!("TeletexString" => TeletexString);
+#[cfg(feature = "alloc")]
+impl_asn1_schema!("BMPString" => BmpString);
+
+#[cfg(feature = "oid")]
+impl_asn1_schema!("OBJECT IDENTIFIER" => ObjectIdentifier);
+
+/// `OPTIONAL` is a property of the enclosing component, so `Option<T>` is
+/// written as `T`.
+impl<T: Asn1Schema> Asn1Schema for Option<T> {
+    fn write_type(w: &mut dyn fmt::Write) -> fmt::Result {
+        T::write_type(w)
+    }
+}
+
+impl<T: Asn1Schema + ?Sized> Asn1Schema for &T {
+    fn write_type(w: &mut dyn fmt::Write) -> fmt::Result {
+        T::write_type(w)
+    }
+}
+
+impl<T: Asn1Schema, const N: usize> Asn1Schema for SequenceOf<T, N> {
+    fn write_type(w: &mut dyn fmt::Write) -> fmt::Result {
+        w.write_str("SEQUENCE OF ")?;
+        T::write_type(w)
+    }
+}
+
+impl<T: Asn1Schema, const N: usize> Asn1Schema for [T; N] {
+    fn write_type(w: &mut dyn fmt::Write) -> fmt::Result {
+        w.write_str("SEQUENCE OF ")?;
+        T::write_type(w)
+    }
+}
+
+impl<T: Asn1Schema + DerOrd, const N: usize> Asn1Schema for SetOf<T, N> {
+    fn write_type(w: &mut dyn fmt::Write) -> fmt::Result {
+        w.write_str("SET OF ")?;
+        T::write_type(w)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Asn1Schema + ?Sized> Asn1Schema for Box<T> {
+    fn write_type(w: &mut dyn fmt::Write) -> fmt::Result {
+        T::write_type(w)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Asn1Schema> Asn1Schema for Vec<T> {
+    fn write_type(w: &mut dyn fmt::Write) -> fmt::Result {
+        w.write_str("SEQUENCE OF ")?;
+        T::write_type(w)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Asn1Schema + DerOrd> Asn1Schema for SetOfVec<T> {
+    fn write_type(w: &mut dyn fmt::Write) -> fmt::Result {
+        w.write_str("SET OF ")?;
+        T::write_type(w)
+    }
+}
+
+/// Support for writing the types of components of derived types, falling
+/// back to the name of the Rust type for types which don't impl
+/// [`Asn1Schema`].
+///
+/// This uses "autoref specialization": `(&&Component::<T>::new()).write_type(w)`
+/// resolves to [`component::WriteAsn1Schema`] if `T: Asn1Schema`, and otherwise
+/// to [`component::WriteTypeName`].
+#[doc(hidden)]
+pub mod component {
+    use super::*;
+
+    /// Marker for the type of a component.
+    pub struct Component<T: ?Sized>(PhantomData<T>);
+
+    impl<T: ?Sized> Component<T> {
+        /// Create a new component marker.
+        pub const fn new() -> Self {
+            Self(PhantomData)
+        }
+    }
+
+    impl<T: ?Sized> Default for Component<T> {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    /// Write the type of a component which impls [`Asn1Schema`].
+    pub trait WriteAsn1Schema {
+        /// Write the type of the component.
+        fn write_type(&self, w: &mut dyn fmt::Write) -> fmt::Result;
+    }
+
+    impl<T: Asn1Schema + ?Sized> WriteAsn1Schema for &Component<T> {
+        fn write_type(&self, w: &mut dyn fmt::Write) -> fmt::Result {
+            T::write_type(w)
+        }
+    }
+
+    /// Write the type of a component using the name of its Rust type.
+    pub trait WriteTypeName {
+        /// Write the type of the component.
+        fn write_type(&self, w: &mut dyn fmt::Write) -> fmt::Result;
+    }
+
+    impl<T: ?Sized> WriteTypeName for Component<T> {
+        fn write_type(&self, w: &mut dyn fmt::Write) -> fmt::Result {
+            write_short_type_name(core::any::type_name::<T>(), w)
+        }
+    }
+
+    /// Write a Rust type name with module paths removed, e.g.
+    /// `alloc::vec::Vec<x509_cert::ext::Extension>` as `Vec<Extension>`.
+    fn write_short_type_name(mut name: &str, w: &mut dyn fmt::Write) -> fmt::Result {
+        while !name.is_empty() {
+            let end = name
+                .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == ':'))
+                .unwrap_or(name.len());
+
+            let (path, rest) = name.split_at(end);
+            w.write_str(path.rsplit("::").next().unwrap_or(path))?;
+
+            let mut rest = rest.chars();
+            if let Some(c) = rest.next() {
+                w.write_char(c)?;
+            }
+            name = rest.as_str();
+        }
+
+        Ok(())
+    }
+}
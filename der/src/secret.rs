@@ -0,0 +1,149 @@
+//! Wrapper for decoded values which contain secrets.
+
+use crate::{Decode, Encode, Length, Reader, Result, Tag, Tagged, Writer};
+use core::{
+    fmt,
+    ops::{Deref, DerefMut},
+};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// Wrapper which zeroizes the inner value when dropped.
+///
+/// Decoding and encoding are passed through to the inner value, so this type
+/// can be used in place of `T`, e.g. as the type of a [`Sequence`] field
+/// holding a private key:
+///
+/// ```
+/// # #[cfg(feature = "alloc")]
+/// # {
+/// use der::{Decode, asn1::OctetString, secret::Secret};
+///
+/// let key = Secret::<OctetString>::from_der(&[0x04, 0x02, 0x13, 0x37])?;
+/// assert_eq!(key.as_bytes(), &[0x13, 0x37]);
+/// # }
+/// # Ok::<(), der::Error>(())
+/// ```
+///
+/// Owned types such as [`Any`], [`OctetString`] and [`BitString`] impl
+/// [`Zeroize`] when the `zeroize` feature is enabled, but aren't zeroized on
+/// drop unless wrapped in this type.
+///
+/// Note that values are only zeroized once they have been decoded: buffers
+/// allocated by a decoder which then fails are freed without zeroizing them.
+///
+/// [`Sequence`]: crate::Sequence
+/// [`Any`]: crate::asn1::Any
+/// [`OctetString`]: crate::asn1::OctetString
+/// [`BitString`]: crate::asn1::BitString
+#[derive(Clone, Default)]
+pub struct Secret<T: Zeroize>(T);
+
+impl<T: Zeroize> Secret<T> {
+    /// Wrap the given value.
+    pub fn new(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T: Zeroize> Deref for Secret<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: Zeroize> DerefMut for Secret<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T: Zeroize> From<T> for Secret<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T: Zeroize> fmt::Debug for Secret<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Secret").finish_non_exhaustive()
+    }
+}
+
+impl<T: Zeroize> Drop for Secret<T> {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl<T: Zeroize> ZeroizeOnDrop for Secret<T> {}
+
+impl<'a, T> Decode<'a> for Secret<T>
+where
+    T: Decode<'a> + Zeroize,
+{
+    type Error = T::Error;
+
+    fn decode<R: Reader<'a>>(reader: &mut R) -> core::result::Result<Self, T::Error> {
+        T::decode(reader).map(Self)
+    }
+}
+
+impl<T: Encode + Zeroize> Encode for Secret<T> {
+    fn encoded_len(&self) -> Result<Length> {
+        self.0.encoded_len()
+    }
+
+    fn encode(&self, writer: &mut impl Writer) -> Result<()> {
+        self.0.encode(writer)
+    }
+}
+
+impl<T: Tagged + Zeroize> Tagged for Secret<T> {
+    fn tag(&self) -> Tag {
+        self.0.tag()
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::Secret;
+    use crate::{
+        Decode, Encode, Tag, Tagged,
+        asn1::{Any, BitString, OctetString},
+    };
+    use hex_literal::hex;
+    use zeroize::Zeroize;
+
+    #[test]
+    fn round_trip() {
+        let der = hex!("04 03 01 02 03");
+        let secret = Secret::<OctetString>::from_der(&der).unwrap();
+        assert_eq!(secret.as_bytes(), &[1, 2, 3]);
+        assert_eq!(secret.tag(), Tag::OctetString);
+        assert_eq!(secret.to_der().unwrap(), der);
+
+        let any = Secret::<Any>::from_der(&der).unwrap();
+        assert_eq!(any.value(), &[1, 2, 3]);
+    }
+
+    #[test]
+    fn debug_redacted() {
+        let secret = Secret::new(OctetString::new([0x42]).unwrap());
+        assert_eq!(alloc::format!("{secret:?}"), "Secret { .. }");
+    }
+
+    #[test]
+    fn zeroize() {
+        let mut octets = OctetString::new([1, 2, 3]).unwrap();
+        octets.zeroize();
+        assert_eq!(octets.as_bytes(), &[0, 0, 0]);
+
+        let mut bits = BitString::from_bytes(&[0xFF]).unwrap();
+        bits.zeroize();
+        assert_eq!(bits.raw_bytes(), &[]);
+        assert_eq!(bits.bit_len(), 0);
+    }
+}
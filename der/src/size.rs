@@ -0,0 +1,48 @@
+//! ASN.1 `SIZE` constraints.
+
+use crate::{Error, ErrorKind, FixedTag};
+use core::ops::RangeBounds;
+
+/// Types which have an ASN.1 `SIZE` which can be constrained, i.e. the
+/// number of octets of an `OCTET STRING`, the number of characters of a
+/// character string, or the number of elements of a `SEQUENCE OF`/`SET OF`.
+///
+/// Size constraints on fields of a derived `Sequence` can be expressed using
+/// the `#[asn1(size = "...")]` attribute, which checks them at decode time:
+///
+/// ```rust,ignore
+/// use der::{Sequence, asn1::OctetString};
+///
+/// /// ```asn1
+/// /// KeyIdentifier ::= SEQUENCE {
+/// ///     keyId  OCTET STRING (SIZE (1..64))
+/// /// }
+/// /// ```
+/// #[derive(Sequence)]
+/// struct KeyIdentifier {
+///     #[asn1(size = "1..=64")]
+///     key_id: OctetString,
+/// }
+/// ```
+pub trait Size {
+    /// Get the ASN.1 `SIZE` of this value.
+    fn size(&self) -> usize;
+
+    /// Returns an error if the size of this value is not in the given range.
+    fn check_size(&self, range: impl RangeBounds<usize>) -> Result<(), Error>
+    where
+        Self: FixedTag,
+    {
+        if range.contains(&self.size()) {
+            Ok(())
+        } else {
+            Err(ErrorKind::Length { tag: Self::TAG }.into())
+        }
+    }
+}
+
+impl<T: Size + ?Sized> Size for &T {
+    fn size(&self) -> usize {
+        T::size(self)
+    }
+}
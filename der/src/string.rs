@@ -101,6 +101,8 @@ pub(crate) mod allocating {
     };
     use alloc::{borrow::ToOwned, string::String};
     use core::{borrow::Borrow, ops::Deref, str};
+    #[cfg(feature = "zeroize")]
+    use zeroize::Zeroize;
 
     /// String newtype which respects the [`Length::max`] limit.
     #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord, Hash)]
@@ -188,6 +190,14 @@ pub(crate) mod allocating {
         }
     }
 
+    #[cfg(feature = "zeroize")]
+    impl Zeroize for StringOwned {
+        fn zeroize(&mut self) {
+            self.inner.zeroize();
+            self.length = Length::ZERO;
+        }
+    }
+
     impl<'a> DecodeValue<'a> for StringOwned {
         type Error = Error;
 
@@ -0,0 +1,366 @@
+//! Structural digests of DER documents excluding chosen subtrees.
+//!
+//! Comparing or deduplicating documents while ignoring some of their fields,
+//! e.g. certificates regardless of their signature, would otherwise require
+//! decoding them, removing the fields and re-encoding them, which can change
+//! the encoding of the remaining fields if the decoded types don't round-trip
+//! exactly.
+//!
+//! Instead, the functions of this module walk the TLVs of the document and
+//! skip the ones located at the excluded [`TlvPath`]s. The resulting input is
+//! the original encoding of the document with the excluded TLVs removed and
+//! the lengths of their ancestors adjusted accordingly, so it's well-defined
+//! and independent of any decoded representation.
+
+use crate::{Decode, Encode, Error, ErrorKind, Header, Length, Reader, Result, SliceReader};
+
+/// Maximum nesting depth of constructed values which will be walked.
+const MAX_DEPTH: usize = 64;
+
+/// Maximum length of an encoded [`Header`].
+const MAX_HEADER_LEN: usize = 16;
+
+/// Path of a TLV in a document, as the indices of the TLVs containing it.
+///
+/// The empty path is the document's outermost TLV, `&[0]` is its first
+/// element, `&[0, 2]` the third element of its first element, and so on.
+/// E.g. the `signatureValue` of an X.509 `Certificate ::= SEQUENCE {
+/// tbsCertificate, signatureAlgorithm, signatureValue }` is at `&[2]`, and
+/// the `serialNumber` of its `tbsCertificate` at `&[0, 1]`.
+///
+/// Paths which don't match any TLV of a document, including paths going
+/// through primitive TLVs, are ignored.
+pub type TlvPath<'a> = &'a [usize];
+
+/// Write the structural encoding of the given DER document to `sink`, which
+/// is called with consecutive chunks of it.
+///
+/// The TLVs located at the `excluded` paths are skipped, and the headers of
+/// the constructed TLVs containing them are rewritten with the length of
+/// their remaining contents. Other TLVs are written unchanged.
+///
+/// ```
+/// use der::structural::write_structural;
+///
+/// // SEQUENCE { INTEGER 1, NULL, INTEGER 2 }
+/// let der = [0x30, 0x08, 0x02, 0x01, 0x01, 0x05, 0x00, 0x02, 0x01, 0x02];
+///
+/// let mut encoding = Vec::new();
+/// write_structural(&der, &[&[1]], |chunk| encoding.extend_from_slice(chunk))?;
+/// assert_eq!(encoding, [0x30, 0x06, 0x02, 0x01, 0x01, 0x02, 0x01, 0x02]);
+/// # Ok::<(), der::Error>(())
+/// ```
+pub fn write_structural(
+    der: &[u8],
+    excluded: &[TlvPath<'_>],
+    mut sink: impl FnMut(&[u8]),
+) -> Result<()> {
+    let mut walker = Walker::new(excluded);
+    let (tlv, rest) = Tlv::split(der, Length::ZERO)?;
+
+    if !rest.is_empty() {
+        return Err(ErrorKind::TrailingData {
+            decoded: tlv.len()?,
+            remaining: Length::try_from(rest.len())?,
+        }
+        .at(tlv.len()?));
+    }
+
+    if !walker.is_excluded() {
+        walker.write_tlv(&tlv, &mut sink)?;
+    }
+
+    Ok(())
+}
+
+/// Compute the digest of the structural encoding of the given DER document,
+/// as written by [`write_structural`].
+///
+/// Documents which only differ in the TLVs located at the `excluded` paths
+/// have the same digest.
+#[cfg(feature = "digest")]
+pub fn structural_digest<D: digest::Digest>(
+    der: &[u8],
+    excluded: &[TlvPath<'_>],
+) -> Result<digest::Output<D>> {
+    let mut hasher = D::new();
+    write_structural(der, excluded, |chunk| hasher.update(chunk))?;
+    Ok(hasher.finalize())
+}
+
+/// Verify that the digest of the structural encoding of the given DER
+/// document, as computed by [`structural_digest`], is `expected`.
+#[cfg(feature = "digest")]
+pub fn verify_structural_digest<D: digest::Digest>(
+    der: &[u8],
+    excluded: &[TlvPath<'_>],
+    expected: &[u8],
+) -> Result<bool> {
+    Ok(structural_digest::<D>(der, excluded)?.as_slice() == expected)
+}
+
+/// TLV located in a document.
+struct Tlv<'a> {
+    /// Decoded header.
+    header: Header,
+
+    /// Encoding of the whole TLV.
+    bytes: &'a [u8],
+
+    /// Value of the TLV.
+    value: &'a [u8],
+
+    /// Offset of the value in the document, for reporting errors.
+    value_offset: Length,
+}
+
+impl<'a> Tlv<'a> {
+    /// Split the TLV at the start of `bytes`, which are located at `offset`
+    /// in the document, returning it along with the remaining bytes.
+    fn split(bytes: &'a [u8], offset: Length) -> Result<(Self, &'a [u8])> {
+        let mut reader = SliceReader::new(bytes)?;
+
+        let (header, value) = (|| {
+            let header = Header::decode(&mut reader)?;
+            let value = reader.read_slice(header.length())?;
+            Ok::<_, Error>((header, value))
+        })()
+        .map_err(|e| e.nested(offset))?;
+
+        let (bytes, rest) = bytes.split_at(usize::try_from(reader.position())?);
+        let value_offset = (offset + Length::try_from(bytes.len() - value.len())?)?;
+
+        let tlv = Self {
+            header,
+            bytes,
+            value,
+            value_offset,
+        };
+
+        Ok((tlv, rest))
+    }
+
+    /// Get the length of the whole TLV.
+    fn len(&self) -> Result<Length> {
+        Length::try_from(self.bytes.len())
+    }
+
+    /// Iterate over the TLVs contained in the value of this TLV, along with
+    /// their index.
+    fn children(&self) -> impl Iterator<Item = Result<(usize, Tlv<'a>)>> {
+        let mut rest = self.value;
+        let mut offset = self.value_offset;
+        let mut index = 0;
+
+        core::iter::from_fn(move || {
+            if rest.is_empty() {
+                return None;
+            }
+
+            let child = Tlv::split(rest, offset).and_then(|(child, remaining)| {
+                rest = remaining;
+                offset = (offset + child.len()?)?;
+                Ok((index, child))
+            });
+
+            if child.is_err() {
+                rest = &[];
+            }
+
+            index += 1;
+            Some(child)
+        })
+    }
+}
+
+/// Walker of the TLVs of a document, keeping track of the path of the TLV
+/// being visited.
+struct Walker<'e> {
+    /// Paths of the excluded TLVs.
+    excluded: &'e [TlvPath<'e>],
+
+    /// Path of the TLV being visited.
+    path: [usize; MAX_DEPTH],
+
+    /// Length of the path of the TLV being visited.
+    depth: usize,
+}
+
+impl<'e> Walker<'e> {
+    /// Create a new walker visiting the outermost TLV of a document.
+    fn new(excluded: &'e [TlvPath<'e>]) -> Self {
+        Self {
+            excluded,
+            path: [0; MAX_DEPTH],
+            depth: 0,
+        }
+    }
+
+    /// Get the path of the TLV being visited.
+    fn path(&self) -> &[usize] {
+        &self.path[..self.depth]
+    }
+
+    /// Is the TLV being visited excluded?
+    fn is_excluded(&self) -> bool {
+        self.excluded.contains(&self.path())
+    }
+
+    /// Does the given TLV, which is being visited, contain excluded TLVs?
+    fn is_pruned(&self, tlv: &Tlv<'_>) -> bool {
+        let path = self.path();
+
+        tlv.header.is_constructed()
+            && self
+                .excluded
+                .iter()
+                .any(|excluded| excluded.len() > path.len() && excluded.starts_with(path))
+    }
+
+    /// Visit the children of the given TLV, calling `f` with each one which
+    /// isn't excluded.
+    fn visit_children(
+        &mut self,
+        tlv: &Tlv<'_>,
+        mut f: impl FnMut(&mut Self, &Tlv<'_>) -> Result<()>,
+    ) -> Result<()> {
+        if self.depth == MAX_DEPTH {
+            return Err(ErrorKind::Overflow.at(tlv.value_offset));
+        }
+
+        for child in tlv.children() {
+            let (index, child) = child?;
+            self.path[self.depth] = index;
+            self.depth += 1;
+
+            let result = if self.is_excluded() {
+                Ok(())
+            } else {
+                f(self, &child)
+            };
+
+            self.depth -= 1;
+            result?;
+        }
+
+        Ok(())
+    }
+
+    /// Compute the length of the structural encoding of the contents of the
+    /// given TLV.
+    fn contents_len(&mut self, tlv: &Tlv<'_>) -> Result<Length> {
+        let mut len = Length::ZERO;
+
+        self.visit_children(tlv, |walker, child| {
+            len = (len + walker.tlv_len(child)?)?;
+            Ok(())
+        })?;
+
+        Ok(len)
+    }
+
+    /// Compute the length of the structural encoding of the given TLV.
+    fn tlv_len(&mut self, tlv: &Tlv<'_>) -> Result<Length> {
+        if !self.is_pruned(tlv) {
+            return tlv.len();
+        }
+
+        let contents_len = self.contents_len(tlv)?;
+        tlv.header.with_length(contents_len).encoded_len()? + contents_len
+    }
+
+    /// Write the structural encoding of the given TLV to `sink`.
+    fn write_tlv(&mut self, tlv: &Tlv<'_>, sink: &mut impl FnMut(&[u8])) -> Result<()> {
+        if !self.is_pruned(tlv) {
+            sink(tlv.bytes);
+            return Ok(());
+        }
+
+        let header = tlv.header.with_length(self.contents_len(tlv)?);
+        let mut buf = [0u8; MAX_HEADER_LEN];
+        sink(header.encode_to_slice(&mut buf)?);
+
+        self.visit_children(tlv, |walker, child| walker.write_tlv(child, sink))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::{TlvPath, write_structural};
+    use crate::ErrorKind;
+    use hex_literal::hex;
+
+    /// Certificate-like document: `SEQUENCE { SEQUENCE { INTEGER 1, INTEGER 2 },
+    /// NULL, BIT STRING 0xAA }`.
+    const EXAMPLE_MSG: &[u8] = &hex!("300E30060201010201020500030200AA");
+
+    /// Collect the structural encoding of `der` into `buf`, returning its length.
+    fn structural(der: &[u8], excluded: &[TlvPath<'_>], buf: &mut [u8]) -> crate::Result<usize> {
+        let mut len = 0;
+
+        write_structural(der, excluded, |chunk| {
+            buf[len..][..chunk.len()].copy_from_slice(chunk);
+            len += chunk.len();
+        })?;
+
+        Ok(len)
+    }
+
+    #[test]
+    fn nothing_excluded() {
+        let mut buf = [0u8; 32];
+        let len = structural(EXAMPLE_MSG, &[], &mut buf).unwrap();
+        assert_eq!(&buf[..len], EXAMPLE_MSG);
+    }
+
+    #[test]
+    fn exclude_element() {
+        let mut buf = [0u8; 32];
+        let len = structural(EXAMPLE_MSG, &[&[2]], &mut buf).unwrap();
+        assert_eq!(&buf[..len], &hex!("300A30060201010201020500"));
+    }
+
+    #[test]
+    fn exclude_nested_elements() {
+        let mut buf = [0u8; 32];
+        let len = structural(EXAMPLE_MSG, &[&[0, 0], &[1], &[2, 0]], &mut buf).unwrap();
+        assert_eq!(&buf[..len], &hex!("30093003020102030200AA"));
+    }
+
+    #[test]
+    fn exclude_root() {
+        let mut buf = [0u8; 32];
+        assert_eq!(structural(EXAMPLE_MSG, &[&[]], &mut buf).unwrap(), 0);
+    }
+
+    #[test]
+    fn reject_malformed() {
+        let mut buf = [0u8; 32];
+
+        // Truncated nested INTEGER
+        let err = structural(&hex!("30053003020501"), &[&[0, 1]], &mut buf).unwrap_err();
+        assert_eq!(err.position(), Some(6u8.into()));
+
+        let err = structural(&hex!("05000500"), &[], &mut buf).unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::TrailingData { .. }));
+    }
+
+    #[cfg(feature = "digest")]
+    #[test]
+    fn digest_ignores_excluded() {
+        use super::{structural_digest, verify_structural_digest};
+        use sha2::Sha256;
+
+        // Same document with a different BIT STRING
+        let other = hex!("300F300602010102010205000303000102");
+
+        let digest = structural_digest::<Sha256>(EXAMPLE_MSG, &[&[2]]).unwrap();
+        assert_eq!(
+            digest,
+            structural_digest::<Sha256>(&other, &[&[2]]).unwrap()
+        );
+        assert!(verify_structural_digest::<Sha256>(&other, &[&[2]], &digest).unwrap());
+        assert!(!verify_structural_digest::<Sha256>(&other, &[], &digest).unwrap());
+    }
+}
@@ -4,10 +4,19 @@
 mod class;
 mod mode;
 mod number;
-
-pub use self::{class::Class, mode::TagMode, number::TagNumber};
-
-use crate::{Decode, DerOrd, Encode, Error, ErrorKind, Length, Reader, Result, Writer};
+mod set;
+
+pub use self::{
+    class::Class,
+    mode::TagMode,
+    number::TagNumber,
+    set::{ContextSpecificRange, TagSet},
+};
+
+use crate::{
+    Decode, DerOrd, Encode, Error, ErrorKind, Length, Reader, Result, Writer,
+    reader::peek::PeekReader,
+};
 use core::{cmp::Ordering, fmt};
 
 /// Indicator bit for constructed form encoding (i.e. vs primitive form)
@@ -132,6 +141,18 @@ pub enum Tag {
     /// `BMPString` tag: `30`.
     BmpString,
 
+    /// `DATE` tag: `31`.
+    Date,
+
+    /// `TIME-OF-DAY` tag: `32`.
+    TimeOfDay,
+
+    /// `DATE-TIME` tag: `33`.
+    DateTime,
+
+    /// `DURATION` tag: `34`.
+    Duration,
+
     /// Application tag.
     Application {
         /// Is this tag constructed? (vs primitive).
@@ -222,7 +243,14 @@ impl Tag {
                 }
             }
             // universal tag in long form
-            0x1F => return Err(reader.error(ErrorKind::TagNumberInvalid)),
+            // (only the time types, whose numbers fit in a single subsequent octet)
+            0x1F => match reader.read_byte()? {
+                0x1F => Tag::Date,
+                0x20 => Tag::TimeOfDay,
+                0x21 => Tag::DateTime,
+                0x22 => Tag::Duration,
+                _ => return Err(reader.error(ErrorKind::TagNumberInvalid)),
+            },
             byte => return Err(reader.error(ErrorKind::TagUnknown { byte })),
         };
 
@@ -231,9 +259,10 @@ impl Tag {
 
     /// Peek at the next byte in the reader and attempt to decode it as a [`Tag`] value.
     ///
-    /// Does not modify the reader's state.
+    /// Does not modify the reader's state. The input is obtained using
+    /// [`Reader::peek_into`].
     pub fn peek<'a>(reader: &impl Reader<'a>) -> Result<Self> {
-        Self::decode(&mut reader.clone())
+        Self::decode(&mut PeekReader::new(reader))
     }
 
     /// Returns true if given context-specific (or any given class) tag number matches the peeked tag.
@@ -296,6 +325,10 @@ impl Tag {
             Tag::VisibleString => TagNumber(26),
             Tag::GeneralString => TagNumber(27),
             Tag::BmpString => TagNumber(30),
+            Tag::Date => TagNumber(31),
+            Tag::TimeOfDay => TagNumber(32),
+            Tag::DateTime => TagNumber(33),
+            Tag::Duration => TagNumber(34),
             Tag::Application { number, .. } => number,
             Tag::ContextSpecific { number, .. } => number,
             Tag::Private { number, .. } => number,
@@ -383,7 +416,11 @@ fn parse_parts<'a, R: Reader<'a>>(first_byte: u8, reader: &mut R) -> Result<(boo
         multi_byte_tag_number |= u32::from(byte & 0x7F);
 
         if byte & 0x80 == 0 {
-            if multi_byte_tag_number < u32::from(TagNumber::MASK) {
+            // Tag numbers below 31 must use the low tag number form, which is
+            // tolerated when decoding BER
+            if multi_byte_tag_number < u32::from(TagNumber::MASK)
+                && reader.encoding_rules().is_der()
+            {
                 return Err(reader.error(ErrorKind::TagNumberInvalid));
             }
 
@@ -481,6 +518,10 @@ impl fmt::Display for Tag {
             Tag::VisibleString => f.write_str("VisibleString"),
             Tag::GeneralString => f.write_str("GeneralString"),
             Tag::BmpString => f.write_str("BMPString"),
+            Tag::Date => f.write_str("DATE"),
+            Tag::TimeOfDay => f.write_str("TIME-OF-DAY"),
+            Tag::DateTime => f.write_str("DATE-TIME"),
+            Tag::Duration => f.write_str("DURATION"),
             Tag::Sequence => f.write_str("SEQUENCE"),
             Tag::Application {
                 constructed,
@@ -526,7 +567,7 @@ mod tests {
     use hex_literal::hex;
 
     use super::{Class, Tag, TagNumber};
-    use crate::{Decode, DerOrd, ErrorKind, Length, Reader, SliceReader};
+    use crate::{Decode, DerOrd, Encode, ErrorKind, Length, Reader, SliceReader};
 
     #[test]
     fn tag_class() {
@@ -642,6 +683,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn universal_long_form() {
+        let tags = [Tag::Date, Tag::TimeOfDay, Tag::DateTime, Tag::Duration];
+
+        for (tag, der) in
+            tags.into_iter()
+                .zip([hex!("1F1F"), hex!("1F20"), hex!("1F21"), hex!("1F22")])
+        {
+            assert_eq!(Tag::from_der(&der).expect("supported universal tag"), tag);
+            assert_eq!(tag.encoded_len().expect("encoded length"), Length::new(2));
+
+            let mut buf = [0u8; 2];
+            assert_eq!(tag.encode_to_slice(&mut buf).expect("encoded tag"), der);
+        }
+
+        assert_eq!(
+            ErrorKind::TagNumberInvalid,
+            Tag::from_der(&hex!("1F23"))
+                .expect_err("unsupported universal tag")
+                .kind()
+        );
+    }
+
     #[test]
     fn tag_order() {
         // T-REC-X.680-202102
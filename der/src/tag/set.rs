@@ -0,0 +1,151 @@
+//! Sets of ASN.1 tags.
+
+use super::{Tag, TagNumber};
+use core::ops::RangeInclusive;
+
+/// Range of `CONTEXT-SPECIFIC` tag numbers sharing the same form, i.e. which
+/// are either all primitive or all constructed.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub struct ContextSpecificRange {
+    /// Are the tags in this range constructed? (vs primitive).
+    constructed: bool,
+
+    /// First tag number in the range.
+    start: TagNumber,
+
+    /// Last tag number in the range (inclusive).
+    end: TagNumber,
+}
+
+impl ContextSpecificRange {
+    /// Create a new range of `CONTEXT-SPECIFIC` tags with the given form.
+    pub const fn new(constructed: bool, numbers: RangeInclusive<TagNumber>) -> Self {
+        Self {
+            constructed,
+            start: *numbers.start(),
+            end: *numbers.end(),
+        }
+    }
+
+    /// Are the tags in this range constructed? (vs primitive).
+    pub const fn is_constructed(&self) -> bool {
+        self.constructed
+    }
+
+    /// Get the tag numbers in this range.
+    pub const fn numbers(&self) -> RangeInclusive<TagNumber> {
+        RangeInclusive::new(self.start, self.end)
+    }
+
+    /// Is the given [`Tag`] in this range?
+    pub fn contains(&self, tag: Tag) -> bool {
+        match tag {
+            Tag::ContextSpecific {
+                constructed,
+                number,
+            } => constructed == self.constructed && self.numbers().contains(&number),
+            _ => false,
+        }
+    }
+}
+
+/// Set of [`Tag`]s, e.g. the tags of the alternatives of a `CHOICE`.
+///
+/// Sets consist of individual tags, along with ranges of `CONTEXT-SPECIFIC`
+/// tag numbers which are used to compactly describe alternatives numbered
+/// consecutively, as is typical for extensible `CHOICE` types. They are
+/// usable in `const` contexts, which allows composing the tag sets of
+/// several types when dispatching on the tag of a value:
+///
+/// ```
+/// use der::{ContextSpecificRange, Tag, TagNumber, TagSet};
+///
+/// const TAGS: TagSet<'static> = TagSet::new(&[Tag::Utf8String])
+///     .with_context_specific(&[ContextSpecificRange::new(false, TagNumber(0)..=TagNumber(3))]);
+///
+/// assert!(TAGS.contains(Tag::Utf8String));
+/// assert!(TAGS.contains(TagNumber(2).context_specific(false)));
+/// assert!(!TAGS.contains(TagNumber(2).context_specific(true)));
+/// assert!(!TAGS.contains(TagNumber(4).context_specific(false)));
+/// ```
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct TagSet<'a> {
+    /// Individual tags in this set.
+    tags: &'a [Tag],
+
+    /// Ranges of `CONTEXT-SPECIFIC` tags in this set.
+    context_specific: &'a [ContextSpecificRange],
+}
+
+impl<'a> TagSet<'a> {
+    /// Create a new set containing the given tags.
+    pub const fn new(tags: &'a [Tag]) -> Self {
+        Self {
+            tags,
+            context_specific: &[],
+        }
+    }
+
+    /// Add the given ranges of `CONTEXT-SPECIFIC` tags to this set, replacing
+    /// any previously added ranges.
+    pub const fn with_context_specific(mut self, ranges: &'a [ContextSpecificRange]) -> Self {
+        self.context_specific = ranges;
+        self
+    }
+
+    /// Get the individual tags in this set.
+    pub const fn tags(&self) -> &'a [Tag] {
+        self.tags
+    }
+
+    /// Get the ranges of `CONTEXT-SPECIFIC` tags in this set.
+    pub const fn context_specific(&self) -> &'a [ContextSpecificRange] {
+        self.context_specific
+    }
+
+    /// Is the given [`Tag`] in this set?
+    pub fn contains(&self, tag: Tag) -> bool {
+        self.tags.contains(&tag)
+            || self
+                .context_specific
+                .iter()
+                .any(|range| range.contains(tag))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ContextSpecificRange, TagSet};
+    use crate::{Tag, TagNumber};
+
+    #[test]
+    fn contains() {
+        const RANGES: &[ContextSpecificRange] = &[
+            ContextSpecificRange::new(false, TagNumber(0)..=TagNumber(2)),
+            ContextSpecificRange::new(true, TagNumber(5)..=TagNumber(6)),
+        ];
+        let tags = [Tag::Boolean, TagNumber(3).application(true)];
+        let set = TagSet::new(&tags).with_context_specific(RANGES);
+
+        assert!(set.contains(Tag::Boolean));
+        assert!(set.contains(TagNumber(3).application(true)));
+        assert!(!set.contains(TagNumber(3).application(false)));
+        assert!(!set.contains(Tag::Integer));
+
+        for n in 0..=2 {
+            assert!(set.contains(TagNumber(n).context_specific(false)));
+            assert!(!set.contains(TagNumber(n).context_specific(true)));
+        }
+
+        assert!(!set.contains(TagNumber(3).context_specific(false)));
+        assert!(set.contains(TagNumber(5).context_specific(true)));
+        assert!(set.contains(TagNumber(6).context_specific(true)));
+        assert!(!set.contains(TagNumber(7).context_specific(true)));
+        assert!(!set.contains(TagNumber(0).application(false)));
+    }
+
+    #[test]
+    fn empty() {
+        assert!(!TagSet::default().contains(Tag::Null));
+    }
+}
@@ -0,0 +1,177 @@
+//! Example encodings from [ITU-T X.690], for validating implementations.
+//!
+//! X.690 illustrates several encoding rules with examples of values and their
+//! encodings. The examples below are the ones which are valid DER, i.e. which
+//! this crate (and other DER implementations) must decode and re-encode
+//! identically.
+//!
+//! ```
+//! use der::{asn1::BitStringRef, test_vectors};
+//!
+//! let example = test_vectors::find("8.6.4.2").unwrap();
+//! let bit_string = example.check::<BitStringRef<'_>>()?;
+//! assert_eq!(bit_string.unused_bits(), 4);
+//! # Ok::<(), der::Error>(())
+//! ```
+//!
+//! [ITU-T X.690]: https://www.itu.int/rec/T-REC-X.690
+
+use crate::{AnyRef, Decode, Encode, ErrorKind, Result, SliceWriter, Tagged};
+
+/// Maximum length of the encoding of an example.
+const MAX_LEN: usize = 16;
+
+/// Example value and its encoding from X.690.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Example {
+    /// Clause of X.690 containing the example, e.g. `8.9.3`.
+    pub clause: &'static str,
+
+    /// ASN.1 type and value of the example.
+    pub description: &'static str,
+
+    /// DER encoding of the value.
+    pub der: &'static [u8],
+}
+
+impl Example {
+    /// Decode the example as `T`, and check that re-encoding the decoded value
+    /// produces the same DER.
+    ///
+    /// Returns the decoded value for further checks.
+    pub fn check<T>(&self) -> Result<T>
+    where
+        T: Decode<'static, Error = crate::Error> + Encode,
+    {
+        let value = T::from_der(self.der)?;
+
+        let mut buf = [0u8; MAX_LEN];
+        let mut writer = SliceWriter::new(&mut buf);
+        value.encode(&mut writer)?;
+
+        if writer.finish()? != self.der {
+            let tag = AnyRef::from_der(self.der)?.tag();
+            return Err(ErrorKind::Noncanonical { tag }.into());
+        }
+
+        Ok(value)
+    }
+}
+
+/// Examples of DER encodings given in X.690.
+pub const EXAMPLES: &[Example] = &[
+    Example {
+        clause: "8.6.4.2",
+        description: "BIT STRING '0A3B5F291CD'H",
+        der: &[0x03, 0x07, 0x04, 0x0A, 0x3B, 0x5F, 0x29, 0x1C, 0xD0],
+    },
+    Example {
+        clause: "8.9.3",
+        description: r#"SEQUENCE { name IA5String, ok BOOLEAN } { name "Smith", ok TRUE }"#,
+        der: &[
+            0x30, 0x0A, 0x16, 0x05, 0x53, 0x6D, 0x69, 0x74, 0x68, 0x01, 0x01, 0xFF,
+        ],
+    },
+    Example {
+        clause: "8.14.3",
+        description: r#"Type1 ::= VisibleString "Jones""#,
+        der: &[0x1A, 0x05, 0x4A, 0x6F, 0x6E, 0x65, 0x73],
+    },
+    Example {
+        clause: "8.14.3",
+        description: r#"Type2 ::= [APPLICATION 3] IMPLICIT Type1 "Jones""#,
+        der: &[0x43, 0x05, 0x4A, 0x6F, 0x6E, 0x65, 0x73],
+    },
+    Example {
+        clause: "8.14.3",
+        description: r#"Type3 ::= [2] Type2 "Jones""#,
+        der: &[0xA2, 0x07, 0x43, 0x05, 0x4A, 0x6F, 0x6E, 0x65, 0x73],
+    },
+    Example {
+        clause: "8.14.3",
+        description: r#"Type4 ::= [APPLICATION 7] IMPLICIT Type3 "Jones""#,
+        der: &[0x67, 0x07, 0x43, 0x05, 0x4A, 0x6F, 0x6E, 0x65, 0x73],
+    },
+    Example {
+        clause: "8.14.3",
+        description: r#"Type5 ::= [2] IMPLICIT Type2 "Jones""#,
+        der: &[0x82, 0x05, 0x4A, 0x6F, 0x6E, 0x65, 0x73],
+    },
+    Example {
+        clause: "8.19.5",
+        description: "OBJECT IDENTIFIER { 2 999 3 }",
+        der: &[0x06, 0x03, 0x88, 0x37, 0x03],
+    },
+    Example {
+        clause: "8.20.5",
+        description: "RELATIVE-OID { 8571 3 2 }",
+        der: &[0x0D, 0x04, 0xC2, 0x7B, 0x03, 0x02],
+    },
+];
+
+/// Find the first example from the given clause of X.690.
+pub fn find(clause: &str) -> Option<&'static Example> {
+    EXAMPLES.iter().find(|example| example.clause == clause)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::{EXAMPLES, Example, find};
+    use crate::{
+        AnyRef, Class, ErrorKind, Tag, TagNumber, Tagged,
+        asn1::{BitStringRef, Ia5StringRef},
+    };
+
+    #[test]
+    fn all_round_trip() {
+        for example in EXAMPLES {
+            example.check::<AnyRef<'_>>().unwrap();
+        }
+    }
+
+    #[test]
+    fn typed() {
+        let bit_string = find("8.6.4.2")
+            .unwrap()
+            .check::<BitStringRef<'_>>()
+            .unwrap();
+        assert_eq!(bit_string.bit_len(), 44);
+
+        let (name, ok) = find("8.9.3")
+            .unwrap()
+            .check::<(Ia5StringRef<'_>, bool)>()
+            .unwrap();
+        assert_eq!(name.as_str(), "Smith");
+        assert!(ok);
+
+        let mut tags = EXAMPLES
+            .iter()
+            .filter(|example| example.clause == "8.14.3")
+            .map(|example| example.check::<AnyRef<'_>>().unwrap().tag());
+        assert_eq!(tags.next().unwrap(), Tag::VisibleString);
+        assert_eq!(tags.next().unwrap().class(), Class::Application);
+        assert_eq!(tags.next().unwrap().number(), TagNumber(2));
+        assert!(tags.next().unwrap().is_constructed());
+        assert_eq!(tags.next().unwrap().class(), Class::ContextSpecific);
+        assert!(tags.next().is_none());
+    }
+
+    #[test]
+    fn noncanonical() {
+        // BOOLEAN TRUE must be encoded as `FF` in DER
+        let example = Example {
+            clause: "",
+            description: "BOOLEAN TRUE",
+            der: &[0x01, 0x01, 0x01],
+        };
+        assert!(example.check::<bool>().is_err());
+
+        // `VisibleString` isn't an `IA5String`
+        let err = find("8.14.3")
+            .unwrap()
+            .check::<Ia5StringRef<'_>>()
+            .unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::TagUnexpected { .. }));
+    }
+}
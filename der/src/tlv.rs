@@ -0,0 +1,219 @@
+//! Raw TLV productions preserving their original encoding.
+
+use crate::{Decode, Encode, Error, Header, Length, Reader, Result, SliceReader, Tag, Writer};
+
+/// Reference to a complete TLV production exactly as it was encoded in the
+/// input, split into its header and value.
+///
+/// Unlike [`AnyRef`][`crate::AnyRef`], which re-encodes its header from the
+/// decoded tag and length, a [`TlvRef`] is re-encoded byte-for-byte, even
+/// for BER forms which have no DER equivalent, e.g. indefinite lengths or
+/// lengths and tag numbers encoded with more octets than needed. This
+/// makes it possible to echo back exactly what a peer sent, e.g. to verify a
+/// signature computed over the original encoding:
+///
+/// ```
+/// # #[cfg(all(feature = "alloc", feature = "ber"))]
+/// # {
+/// use der::{Decode, Encode, EncodingRules, SliceReader, Tag, TlvRef};
+///
+/// // SEQUENCE { BOOLEAN TRUE } with an indefinite length
+/// let ber = [0x30, 0x80, 0x01, 0x01, 0xFF, 0x00, 0x00];
+///
+/// let mut reader = SliceReader::new_with_encoding_rules(&ber, EncodingRules::Ber)?;
+/// let tlv = TlvRef::decode(&mut reader)?;
+/// assert_eq!(tlv.tag(), Tag::Sequence);
+/// assert_eq!(tlv.header_bytes(), &[0x30, 0x80]);
+/// assert_eq!(tlv.value(), &[0x01, 0x01, 0xFF]);
+/// assert_eq!(tlv.to_der()?, ber);
+/// # }
+/// # Ok::<(), der::Error>(())
+/// ```
+///
+/// Decoding a [`TlvRef`] borrows from the input, so it requires a reader
+/// over a slice, e.g. [`SliceReader`][`crate::SliceReader`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct TlvRef<'a> {
+    /// Decoded header.
+    header: Header,
+
+    /// Entire encoding of the TLV.
+    bytes: &'a [u8],
+
+    /// Encoded header at the start of `bytes`.
+    header_bytes: &'a [u8],
+
+    /// Value following the header, excluding any end-of-contents marker.
+    value: &'a [u8],
+}
+
+impl<'a> TlvRef<'a> {
+    /// Get the decoded [`Header`] of the TLV.
+    ///
+    /// Its [`Length`] includes the end-of-contents marker of values with an
+    /// indefinite length.
+    pub fn header(&self) -> Header {
+        self.header
+    }
+
+    /// Get the [`Tag`] of the TLV.
+    pub fn tag(&self) -> Tag {
+        self.header.tag()
+    }
+
+    /// Get the encoded header, i.e. the tag and length octets, exactly as
+    /// they appeared in the input.
+    pub fn header_bytes(&self) -> &'a [u8] {
+        self.header_bytes
+    }
+
+    /// Get the value of the TLV, excluding the end-of-contents marker of
+    /// values with an indefinite length.
+    pub fn value(&self) -> &'a [u8] {
+        self.value
+    }
+
+    /// Get the entire TLV exactly as it appeared in the input.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+}
+
+impl<'a> Decode<'a> for TlvRef<'a> {
+    type Error = Error;
+
+    fn decode<R: Reader<'a>>(reader: &mut R) -> Result<Self> {
+        let (header, header_len) = Header::peek_with_len(reader)?;
+        let bytes = reader.read_slice((header_len + header.length())?)?;
+
+        #[cfg(feature = "ber")]
+        let value_len = header.length().sans_eoc();
+        #[cfg(not(feature = "ber"))]
+        let value_len = header.length();
+
+        let mut tlv = SliceReader::new(bytes)?;
+        let header_bytes = tlv.read_slice(header_len)?;
+        let value = tlv.read_slice(value_len)?;
+
+        Ok(Self {
+            header,
+            bytes,
+            header_bytes,
+            value,
+        })
+    }
+}
+
+impl Encode for TlvRef<'_> {
+    fn encoded_len(&self) -> Result<Length> {
+        Length::try_from(self.bytes.len())
+    }
+
+    fn encode(&self, writer: &mut impl Writer) -> Result<()> {
+        writer.write(self.bytes)
+    }
+}
+
+impl<'a> From<TlvRef<'a>> for &'a [u8] {
+    fn from(tlv: TlvRef<'a>) -> &'a [u8] {
+        tlv.bytes
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::TlvRef;
+    use crate::{Decode, Encode, Length, Reader, SliceReader, Tag, asn1::OctetStringRef};
+    use hex_literal::hex;
+
+    #[test]
+    fn decode_der() {
+        // OCTET STRING followed by a BOOLEAN
+        let der = hex!("0402ABCD 0101FF");
+        let mut reader = SliceReader::new(&der).unwrap();
+
+        let tlv = TlvRef::decode(&mut reader).unwrap();
+        assert_eq!(tlv.tag(), Tag::OctetString);
+        assert_eq!(tlv.header().length(), Length::new(2));
+        assert_eq!(tlv.header_bytes(), &hex!("0402"));
+        assert_eq!(tlv.value(), &hex!("ABCD"));
+        assert_eq!(tlv.as_bytes(), &hex!("0402ABCD"));
+        let mut buf = [0u8; 4];
+        assert_eq!(tlv.encode_to_slice(&mut buf).unwrap(), hex!("0402ABCD"));
+        assert_eq!(
+            <&OctetStringRef>::from_der(tlv.as_bytes())
+                .unwrap()
+                .as_bytes(),
+            tlv.value()
+        );
+
+        assert_eq!(reader.position(), Length::new(4));
+        assert!(bool::decode(&mut reader).unwrap());
+    }
+
+    #[test]
+    fn decode_truncated() {
+        assert!(TlvRef::from_der(&hex!("0403ABCD")).is_err());
+        assert!(TlvRef::from_der(&hex!("04")).is_err());
+    }
+
+    #[cfg(feature = "ber")]
+    #[test]
+    fn decode_indefinite_length() {
+        use crate::EncodingRules;
+
+        // SEQUENCE { OCTET STRING, 200 bytes } with an indefinite length,
+        // whose header would be 3 bytes long if re-encoded from its length
+        let mut ber = [0u8; 207];
+        ber[..5].copy_from_slice(&hex!("3080 0481C8"));
+
+        let mut reader = SliceReader::new_with_encoding_rules(&ber, EncodingRules::Ber).unwrap();
+        let tlv = TlvRef::decode(&mut reader).unwrap();
+        assert!(reader.is_finished());
+
+        assert_eq!(tlv.header_bytes(), &hex!("3080"));
+        assert_eq!(tlv.value().len(), 203);
+        let mut buf = [0u8; 207];
+        assert_eq!(tlv.encode_to_slice(&mut buf).unwrap(), ber);
+
+        // `Reader::tlv_bytes` is consistent with it
+        let mut reader = SliceReader::new_with_encoding_rules(&ber, EncodingRules::Ber).unwrap();
+        assert_eq!(reader.tlv_bytes().unwrap(), &ber);
+    }
+
+    #[cfg(feature = "ber")]
+    #[test]
+    fn decode_non_minimal() {
+        use crate::{EncodingRules, TagNumber};
+
+        // OCTET STRING with a long form length, followed by a BOOLEAN with a
+        // high tag number form for [5]
+        let ber = hex!("048105AABBCCDDEE 9F0501FF");
+        assert!(TlvRef::from_der(&ber[..8]).is_err());
+        assert!(TlvRef::from_der(&ber[8..]).is_err());
+
+        let mut reader = SliceReader::new_with_encoding_rules(&ber, EncodingRules::Ber).unwrap();
+
+        let tlv = TlvRef::decode(&mut reader).unwrap();
+        assert_eq!(tlv.tag(), Tag::OctetString);
+        assert_eq!(tlv.header().length(), Length::new(5));
+        assert_eq!(tlv.header_bytes(), &hex!("048105"));
+        assert_eq!(tlv.value(), &hex!("AABBCCDDEE"));
+        let mut buf = [0u8; 8];
+        assert_eq!(tlv.encode_to_slice(&mut buf).unwrap(), &ber[..8]);
+
+        let tlv = TlvRef::decode(&mut reader).unwrap();
+        assert_eq!(
+            tlv.tag(),
+            Tag::ContextSpecific {
+                constructed: false,
+                number: TagNumber(5)
+            }
+        );
+        assert_eq!(tlv.header_bytes(), &hex!("9F0501"));
+        let mut buf = [0u8; 4];
+        assert_eq!(tlv.encode_to_slice(&mut buf).unwrap(), &ber[8..]);
+        assert!(reader.is_finished());
+    }
+}
@@ -0,0 +1,83 @@
+//! Optional [`tracing`] instrumentation of decode/encode boundaries.
+//!
+//! When the `tracing` crate feature is enabled, each call to [`Decode::decode`]
+//! and [`Encode::encode`] made through the blanket impls (as well as the impls
+//! generated by the custom derive macros) is wrapped in a `TRACE`-level span
+//! named `der::decode` or `der::encode` carrying the following fields:
+//!
+//! - `type_name`: Rust type name of the value being decoded/encoded
+//! - `tag`: ASN.1 tag of the value
+//! - `length`: length of the value in bytes
+//!
+//! When the feature is disabled these helpers compile down to nothing.
+//!
+//! [`tracing`]: https://docs.rs/tracing/
+
+use crate::{Header, Length, Reader, Tag};
+
+#[cfg(doc)]
+use crate::{Decode, Encode};
+
+/// Guard which keeps a decode/encode span entered until it's dropped.
+#[doc(hidden)]
+#[derive(Debug)]
+#[must_use = "the span is exited when the guard is dropped"]
+pub struct SpanGuard {
+    #[cfg(feature = "tracing")]
+    _span: tracing::span::EnteredSpan,
+}
+
+/// Enter a `der::decode` span for a value of type `T` with the given header.
+#[doc(hidden)]
+#[inline(always)]
+#[allow(unused_variables)]
+pub fn decode_span<T: ?Sized>(header: &Header) -> SpanGuard {
+    SpanGuard {
+        #[cfg(feature = "tracing")]
+        _span: tracing::trace_span!(
+            "der::decode",
+            type_name = core::any::type_name::<T>(),
+            tag = %header.tag(),
+            length = u32::from(header.length()),
+        )
+        .entered(),
+    }
+}
+
+/// Enter a `der::encode` span for a value of type `T` with the given tag and
+/// length, where the length may be unknown when using deferred length encoding.
+#[doc(hidden)]
+#[inline(always)]
+#[allow(unused_variables)]
+pub fn encode_span<T: ?Sized>(tag: Tag, length: Option<Length>) -> SpanGuard {
+    SpanGuard {
+        #[cfg(feature = "tracing")]
+        _span: tracing::trace_span!(
+            "der::encode",
+            type_name = core::any::type_name::<T>(),
+            tag = %tag,
+            length = length.map(u32::from),
+        )
+        .entered(),
+    }
+}
+
+/// Enter a `der::decode` span for a value of type `T` using the header at the
+/// current position of the reader, without consuming it.
+///
+/// Used by impls of [`Decode`] which dispatch on the tag before decoding the
+/// header, e.g. `CHOICE` types.
+#[doc(hidden)]
+#[inline(always)]
+#[allow(unused_variables)]
+pub fn peek_decode_span<'a, T: ?Sized>(reader: &impl Reader<'a>) -> Option<SpanGuard> {
+    #[cfg(feature = "tracing")]
+    {
+        Header::peek(reader)
+            .ok()
+            .map(|header| decode_span::<T>(&header))
+    }
+
+    #[cfg(not(feature = "tracing"))]
+    None
+}
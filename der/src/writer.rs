@@ -4,7 +4,10 @@
 pub(crate) mod pem;
 pub(crate) mod slice;
 
-use crate::Result;
+use crate::{ErrorKind, Length, Result};
+
+#[cfg(doc)]
+use crate::EncodeValue;
 
 #[cfg(feature = "std")]
 use std::io;
@@ -18,6 +21,29 @@ pub trait Writer {
     fn write_byte(&mut self, byte: u8) -> Result<()> {
         self.write(&[byte])
     }
+
+    /// Begin writing a value whose length will be inserted after the value
+    /// itself has been written, returning the position at which it starts.
+    ///
+    /// Writers which support this avoid having to compute the length of each
+    /// value with [`EncodeValue::value_len`] before encoding it, which
+    /// otherwise requires traversing nested structures once per level of
+    /// nesting.
+    ///
+    /// Returns `None` (the default) if the writer doesn't support deferred
+    /// lengths, in which case they must be computed up front.
+    fn begin_deferred_length(&mut self) -> Option<Length> {
+        None
+    }
+
+    /// Write the length of the value written after the given position, which
+    /// holds a one-octet placeholder for the length.
+    ///
+    /// The value is moved to make room for the long form of the length if
+    /// needed.
+    fn finish_deferred_length(&mut self, start: Length) -> Result<()> {
+        Err(ErrorKind::Failed.at(start))
+    }
 }
 
 #[cfg(feature = "std")]
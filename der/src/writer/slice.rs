@@ -16,6 +16,9 @@ pub struct SliceWriter<'a> {
 
     /// Total number of bytes written to buffer so far
     position: Length,
+
+    /// Are lengths inserted after values are written?
+    deferred_length: bool,
 }
 
 impl<'a> SliceWriter<'a> {
@@ -25,6 +28,32 @@ impl<'a> SliceWriter<'a> {
             bytes,
             failed: false,
             position: Length::ZERO,
+            deferred_length: false,
+        }
+    }
+
+    /// Create a new encoder which inserts the length of each value after
+    /// writing it.
+    ///
+    /// By default, encoding a value first computes its length, which for
+    /// constructed types like `SEQUENCE` requires traversing all of the
+    /// values nested within it. Since this happens again at every level of
+    /// nesting, deeply nested structures are traversed many times.
+    ///
+    /// With deferred lengths, each value is written first, after a single
+    /// octet reserved for its length, which is filled in afterwards. This
+    /// produces the same output without computing any length up front.
+    /// However, values of 128 octets or more need a long form length, so
+    /// they're moved within the buffer to make room for it, once for each
+    /// enclosing value which is long enough itself. Large, deeply nested
+    /// structures are thus still copied several times.
+    ///
+    /// Note that this only applies to types which are encoded via impls of
+    /// [`EncodeValue`] and [`Tagged`].
+    pub fn new_deferred_length(bytes: &'a mut [u8]) -> Self {
+        Self {
+            deferred_length: true,
+            ..Self::new(bytes)
         }
     }
 
@@ -94,7 +123,9 @@ impl<'a> SliceWriter<'a> {
     {
         Header::new(Tag::Sequence, length).encode(self)?;
 
+        let deferred_length = self.deferred_length;
         let mut nested_encoder = SliceWriter::new(self.reserve(length)?);
+        nested_encoder.deferred_length = deferred_length;
         f(&mut nested_encoder)?;
 
         if nested_encoder.finish()?.len() == usize::try_from(length)? {
@@ -131,6 +162,34 @@ impl Writer for SliceWriter<'_> {
         self.reserve(slice.len())?.copy_from_slice(slice);
         Ok(())
     }
+
+    fn begin_deferred_length(&mut self) -> Option<Length> {
+        self.deferred_length.then_some(self.position)
+    }
+
+    fn finish_deferred_length(&mut self, start: Length) -> Result<()> {
+        let end = self.position;
+        let value_start = (start + Length::ONE).or_else(|e| self.error(e.kind()))?;
+        let value_len = (end - value_start).or_else(|e| self.error(e.kind()))?;
+        let length_len = value_len.encoded_len()?;
+
+        // Move the value to make room for a long form length
+        if length_len > Length::ONE {
+            self.reserve((length_len - Length::ONE)?)?;
+
+            let dest = usize::try_from((start + length_len)?)?;
+            self.bytes
+                .copy_within(usize::try_from(value_start)?..usize::try_from(end)?, dest);
+        }
+
+        let start = usize::try_from(start)?;
+        let length_bytes = self
+            .bytes
+            .get_mut(start..start + usize::try_from(length_len)?)
+            .ok_or_else(|| ErrorKind::Overlength.at(end))?;
+
+        value_len.encode(&mut SliceWriter::new(length_bytes))
+    }
 }
 
 #[cfg(test)]
@@ -139,6 +198,56 @@ mod tests {
     use super::SliceWriter;
     use crate::{Encode, ErrorKind, Length};
 
+    #[test]
+    fn deferred_length_long_length() {
+        use crate::asn1::{OctetStringRef, SequenceOf};
+
+        let bytes = [0xAA; 300];
+        let mut value = SequenceOf::<_, 2>::new();
+        value
+            .add(OctetStringRef::new(&bytes[..1]).unwrap())
+            .unwrap();
+        value.add(OctetStringRef::new(&bytes).unwrap()).unwrap();
+
+        let mut expected = [0u8; 512];
+        let expected = value.encode_to_slice(&mut expected).unwrap();
+
+        let mut buffer = [0u8; 512];
+        let actual = value.encode_to_slice_deferred_length(&mut buffer).unwrap();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn deferred_length_nested() {
+        use crate::asn1::{OctetStringRef, SequenceOf};
+
+        let bytes = [0xAA; 300];
+        let mut inner = SequenceOf::<_, 2>::new();
+        inner
+            .add(OctetStringRef::new(&bytes[..3]).unwrap())
+            .unwrap();
+        inner.add(OctetStringRef::new(&bytes).unwrap()).unwrap();
+
+        let mut outer = SequenceOf::<_, 2>::new();
+        outer.add(inner.clone()).unwrap();
+        outer.add(inner).unwrap();
+
+        let mut expected = [0u8; 1024];
+        let expected = outer.encode_to_slice(&mut expected).unwrap();
+
+        let mut buffer = [0u8; 1024];
+        let actual = outer.encode_to_slice_deferred_length(&mut buffer).unwrap();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn deferred_length_overlength_message() {
+        let mut buffer = [0u8; 2];
+        let mut writer = SliceWriter::new_deferred_length(&mut buffer);
+        let err = 1u8.encode(&mut writer).err().unwrap();
+        assert_eq!(err.kind(), ErrorKind::Overlength);
+    }
+
     #[test]
     fn overlength_message() {
         let mut buffer = [];
@@ -113,7 +113,7 @@ mod choice {
     mod implicit {
         use der::asn1::Null;
         use der::{
-            Choice, Decode, Encode, Sequence, SliceWriter,
+            Choice, ContextSpecificRange, Decode, Encode, Sequence, SliceWriter, Tag, TagNumber,
             asn1::{BitStringRef, GeneralizedTime, SequenceOf},
         };
         use hex_literal::hex;
@@ -202,6 +202,38 @@ mod choice {
             assert_eq!(decoded, obj);
         }
 
+        #[test]
+        fn tag_set() {
+            let tag_set = ImplicitChoice::TAG_SET;
+            assert_eq!(tag_set.tags(), &[TagNumber(3).context_specific(true)]);
+            assert_eq!(
+                tag_set.context_specific(),
+                &[ContextSpecificRange::new(
+                    false,
+                    TagNumber(0)..=TagNumber(2)
+                )]
+            );
+
+            for n in 0..=2 {
+                let tag = TagNumber(n).context_specific(false);
+                assert!(ImplicitChoice::can_decode(tag));
+                assert!(!ImplicitChoice::can_decode(
+                    TagNumber(n).context_specific(true)
+                ));
+            }
+
+            assert!(ImplicitChoice::can_decode(
+                TagNumber(3).context_specific(true)
+            ));
+            assert!(!ImplicitChoice::can_decode(
+                TagNumber(3).context_specific(false)
+            ));
+            assert!(!ImplicitChoice::can_decode(
+                TagNumber(4).context_specific(false)
+            ));
+            assert!(!ImplicitChoice::can_decode(Tag::BitString));
+        }
+
         /// Test case for `CHOICE` inside `[0]` `EXPLICIT` tag in `SEQUENCE`.
         #[derive(Sequence, Debug, Eq, PartialEq)]
         #[allow(dead_code)]
@@ -245,6 +277,20 @@ mod enumerated {
         Specified = 1,
     }
 
+    /// `PKIStatus` with a catch-all variant for unrecognized values.
+    #[derive(Enumerated, Copy, Clone, Debug, Eq, PartialEq)]
+    #[asn1(type = "INTEGER", other = "Unknown")]
+    #[repr(u32)]
+    pub enum PkiStatus {
+        Accepted = 0,
+        GrantedWithMods = 1,
+        Rejection = 2,
+        Unknown(u32),
+    }
+
+    const ACCEPTED_DER: &[u8] = &hex!("02 01 00");
+    const UNKNOWN_DER: &[u8] = &hex!("02 01 2a");
+
     #[test]
     fn decode() {
         let unspecified = CrlReason::from_der(UNSPECIFIED_DER).unwrap();
@@ -257,6 +303,26 @@ mod enumerated {
         assert_eq!(custom_error_enum, EnumWithCustomError::Unspecified);
     }
 
+    #[test]
+    fn decode_unrecognized() {
+        assert!(CrlReason::from_der(&hex!("0a 01 07")).is_err());
+    }
+
+    #[test]
+    fn decode_other() {
+        let accepted = PkiStatus::from_der(ACCEPTED_DER).unwrap();
+        assert_eq!(PkiStatus::Accepted, accepted);
+
+        let unknown = PkiStatus::from_der(UNKNOWN_DER).unwrap();
+        assert_eq!(PkiStatus::Unknown(42), unknown);
+    }
+
+    #[test]
+    fn encode_other() {
+        assert_eq!(PkiStatus::Accepted.to_der().unwrap(), ACCEPTED_DER);
+        assert_eq!(PkiStatus::Unknown(42).to_der().unwrap(), UNKNOWN_DER);
+    }
+
     #[test]
     fn encode() {
         let mut buf = [0u8; 128];
@@ -271,6 +337,89 @@ mod enumerated {
     }
 }
 
+/// Custom derive test cases for the `OidChoice` macro.
+#[cfg(feature = "oid")]
+mod oid_choice {
+    use der::{
+        Decode, Encode, ErrorKind, OidChoice,
+        asn1::{AnyRef, ObjectIdentifier, PrintableStringRef, Utf8StringRef},
+    };
+    use hex_literal::hex;
+
+    const COUNTRY_NAME: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.5.4.6");
+
+    /// X.501 `AttributeTypeAndValue` with a few attribute types.
+    #[derive(OidChoice, Clone, Debug, Eq, PartialEq)]
+    #[asn1(other = "Other")]
+    pub enum AttributeTypeAndValue<'a> {
+        #[asn1(oid = "2.5.4.3")]
+        CommonName(Utf8StringRef<'a>),
+
+        #[asn1(oid = COUNTRY_NAME)]
+        Country(PrintableStringRef<'a>),
+
+        Other(ObjectIdentifier, AnyRef<'a>),
+    }
+
+    /// OID-keyed choice without a catch-all variant.
+    #[derive(OidChoice, Clone, Debug, Eq, PartialEq)]
+    pub enum Flag {
+        #[asn1(oid = "1.2.3.4")]
+        Enabled(bool),
+    }
+
+    const COMMON_NAME_DER: &[u8] = &hex!("300c 0603550403 0c05 6865 6c6c 6f");
+    const COUNTRY_DER: &[u8] = &hex!("3009 0603550406 1302 5553");
+    const OTHER_DER: &[u8] = &hex!("3008 0603550407 0101 ff");
+
+    #[test]
+    fn decode() {
+        let cn = AttributeTypeAndValue::from_der(COMMON_NAME_DER).unwrap();
+        assert_eq!(
+            cn,
+            AttributeTypeAndValue::CommonName(Utf8StringRef::new("hello").unwrap())
+        );
+        assert_eq!(cn.oid(), ObjectIdentifier::new_unwrap("2.5.4.3"));
+
+        let country = AttributeTypeAndValue::from_der(COUNTRY_DER).unwrap();
+        assert_eq!(
+            country,
+            AttributeTypeAndValue::Country(PrintableStringRef::new("US").unwrap())
+        );
+        assert_eq!(country.oid(), COUNTRY_NAME);
+
+        let other = AttributeTypeAndValue::from_der(OTHER_DER).unwrap();
+        assert_eq!(other.oid(), ObjectIdentifier::new_unwrap("2.5.4.7"));
+        assert!(matches!(other, AttributeTypeAndValue::Other(_, any) if any.value() == [0xff]));
+    }
+
+    #[test]
+    fn decode_rejects() {
+        let err = Flag::from_der(&hex!("3008 0603550407 0101 ff")).unwrap_err();
+        assert_eq!(
+            err.kind(),
+            ErrorKind::OidUnknown {
+                oid: ObjectIdentifier::new_unwrap("2.5.4.7")
+            }
+        );
+
+        // The value must have the type selected by the OID
+        assert!(AttributeTypeAndValue::from_der(&hex!("3009 0603550403 0101 ff")).is_err());
+    }
+
+    #[test]
+    fn encode() {
+        for der in [COMMON_NAME_DER, COUNTRY_DER, OTHER_DER] {
+            let value = AttributeTypeAndValue::from_der(der).unwrap();
+            assert_eq!(value.to_der().unwrap(), der);
+        }
+
+        let flag = Flag::Enabled(true);
+        assert_eq!(flag.to_der().unwrap(), hex!("3008 06032a0304 0101 ff"));
+        assert_eq!(Flag::from_der(&flag.to_der().unwrap()).unwrap(), flag);
+    }
+}
+
 /// Custom derive test cases for the `Sequence` macro.
 #[cfg(feature = "oid")]
 mod sequence {
@@ -475,7 +624,7 @@ mod sequence {
         pub context_specific: bool,
 
         #[asn1(optional = "true")]
-        pub optional: Option<bool>,
+        pub optional: Option<u8>,
 
         #[asn1(type = "OCTET STRING", optional = "true")]
         pub optional_octet_string: Option<&'a [u8]>,
@@ -510,7 +659,7 @@ mod sequence {
     #[test]
     fn type_combinations_instance() {
         let obj = TypeCheckExpandedSequenceFieldAttributeCombinations {
-            optional: Some(true),
+            optional: Some(1),
             optional_octet_string: Some(&[0xAA, 0xBB]),
             optional_bit_string: Some(&[0xCC, 0xDD]),
             context_specific_optional: Some(true),
@@ -725,6 +874,387 @@ mod sequence {
     }
 }
 
+/// Custom derive test cases for the `size` attribute.
+mod size {
+    use der::{
+        Decode, Encode, ErrorKind, Sequence, Tag,
+        asn1::{OctetString, OctetStringRef},
+    };
+
+    #[derive(Sequence, Debug, Eq, PartialEq)]
+    pub struct SizeConstrained<'a> {
+        #[asn1(size = "1..=4")]
+        pub key_id: OctetString,
+
+        #[asn1(size = "2")]
+        pub tag: &'a OctetStringRef,
+
+        #[asn1(size = "..=3")]
+        pub name: String,
+
+        #[asn1(context_specific = "0", optional = "true", size = "1..")]
+        pub flags: Option<Vec<bool>>,
+    }
+
+    fn example(key_id: &[u8], name: &str, flags: Option<Vec<bool>>) -> SizeConstrained<'static> {
+        SizeConstrained {
+            key_id: OctetString::new(key_id).unwrap(),
+            tag: OctetStringRef::new(&[0xAB, 0xCD]).unwrap(),
+            name: name.into(),
+            flags,
+        }
+    }
+
+    fn round_trip(value: &SizeConstrained<'_>) -> der::Result<()> {
+        let der = value.to_der().unwrap();
+        assert_eq!(&SizeConstrained::from_der(&der)?, value);
+        Ok(())
+    }
+
+    fn length_error(value: &SizeConstrained<'_>) -> Option<Tag> {
+        match round_trip(value).unwrap_err().kind() {
+            ErrorKind::Length { tag } => Some(tag),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn decode_within_bounds() {
+        round_trip(&example(&[1], "", None)).unwrap();
+        round_trip(&example(&[1, 2, 3, 4], "äöü", Some(vec![true]))).unwrap();
+    }
+
+    #[test]
+    fn decode_octet_string_out_of_bounds() {
+        assert_eq!(
+            length_error(&example(&[], "", None)),
+            Some(Tag::OctetString)
+        );
+        assert_eq!(
+            length_error(&example(&[1, 2, 3, 4, 5], "", None)),
+            Some(Tag::OctetString)
+        );
+    }
+
+    #[test]
+    fn decode_utf8_string_out_of_bounds() {
+        assert_eq!(
+            length_error(&example(&[1], "abcd", None)),
+            Some(Tag::Utf8String)
+        );
+    }
+
+    #[test]
+    fn decode_sequence_of_out_of_bounds() {
+        assert_eq!(
+            length_error(&example(&[1], "", Some(vec![]))),
+            Some(Tag::Sequence)
+        );
+    }
+}
+
+/// Custom derive test cases for `Option` fields without the `optional` attribute.
+mod untagged_optional {
+    use der::{Decode, Encode, Sequence, asn1::ObjectIdentifier};
+    use hex_literal::hex;
+
+    #[derive(Sequence, Debug, Eq, PartialEq)]
+    pub struct Untagged<'a> {
+        pub version: Option<u8>,
+
+        pub oid: ObjectIdentifier,
+
+        #[asn1(type = "OCTET STRING")]
+        pub key_id: Option<&'a [u8]>,
+
+        pub critical: Option<bool>,
+
+        #[asn1(context_specific = "0")]
+        pub label: Option<String>,
+    }
+
+    const OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.3");
+
+    #[test]
+    fn all_present() {
+        let value = Untagged {
+            version: Some(1),
+            oid: OID,
+            key_id: Some(&[0xAB]),
+            critical: Some(true),
+            label: Some("a".into()),
+        };
+
+        let der = value.to_der().unwrap();
+        assert_eq!(der, hex!("3012 020101 06022A03 0401AB 0101FF A0030C0161"));
+        assert_eq!(Untagged::from_der(&der).unwrap(), value);
+    }
+
+    #[test]
+    fn absent_fields_are_skipped_by_tag() {
+        let value = Untagged {
+            version: None,
+            oid: OID,
+            key_id: None,
+            critical: Some(false),
+            label: None,
+        };
+
+        let der = value.to_der().unwrap();
+        assert_eq!(der, hex!("3007 06022A03 010100"));
+        assert_eq!(Untagged::from_der(&der).unwrap(), value);
+    }
+
+    #[test]
+    fn unexpected_tag() {
+        // `critical` given as an INTEGER is neither an optional field nor the end
+        assert!(Untagged::from_der(&hex!("3007 06022A03 020100")).is_err());
+    }
+}
+
+/// Custom derive test cases for unknown extensions of extensible sequences
+/// and unknown alternatives of extensible choices.
+mod unknown {
+    use core::cell::Cell;
+    use der::{
+        AnyRef, Choice, Decode, Encode, Error, ErrorKind, HookedReader, Reader, Sequence,
+        SliceReader, Tag, TagNumber, Tagged,
+    };
+    use hex_literal::hex;
+
+    #[derive(Sequence, Debug, Eq, PartialEq)]
+    pub struct Extensible {
+        pub version: u8,
+
+        #[asn1(context_specific = "1", extensible = "true", optional = "true")]
+        pub flag: Option<bool>,
+
+        #[asn1(context_specific = "3", extensible = "true", optional = "true")]
+        pub count: Option<u8>,
+    }
+
+    #[derive(Choice, Debug, Eq, PartialEq)]
+    pub enum ExtensibleChoice<'a> {
+        #[asn1(context_specific = "0", constructed = "true")]
+        Flag(bool),
+
+        #[asn1(context_specific = "1", constructed = "true")]
+        Count(u8),
+
+        #[asn1(extensible = "true")]
+        Unknown(AnyRef<'a>),
+    }
+
+    // SEQUENCE { INTEGER 1, [0] { NULL }, [1] { TRUE }, [2] { NULL }, [3] { INTEGER 7 }, [4] { NULL } }
+    const EXAMPLE_DER: &[u8] =
+        &hex!("3019 020101 A0020500 A1030101FF A2020500 A303020107 A4020500");
+
+    fn decode_with<'a>(
+        der: &'a [u8],
+        hook: &dyn Fn(AnyRef<'a>) -> der::Result<()>,
+    ) -> der::Result<Extensible> {
+        let mut reader = HookedReader::new(SliceReader::new(der)?, hook);
+        let value = Extensible::decode(&mut reader)?;
+        reader.finish()?;
+        Ok(value)
+    }
+
+    #[test]
+    fn unknown_rejected_by_default() {
+        assert!(Extensible::from_der(EXAMPLE_DER).is_err());
+
+        let known = hex!("300D 020101 A1030101FF A303020107");
+        assert_eq!(
+            Extensible::from_der(&known).unwrap(),
+            Extensible {
+                version: 1,
+                flag: Some(true),
+                count: Some(7),
+            }
+        );
+    }
+
+    #[test]
+    fn unknown_skipped_by_hook() {
+        let unknown = Cell::new(0u32);
+        let hook = |tlv: AnyRef<'_>| {
+            unknown.set(unknown.get() | 1 << tlv.tag().number().value());
+            Ok(())
+        };
+
+        assert_eq!(
+            decode_with(EXAMPLE_DER, &hook).unwrap(),
+            Extensible {
+                version: 1,
+                flag: Some(true),
+                count: Some(7),
+            }
+        );
+        assert_eq!(unknown.get(), 0b10101);
+    }
+
+    #[test]
+    fn unknown_rejected_by_hook() {
+        let hook = |tlv: AnyRef<'_>| match tlv.tag().number() {
+            TagNumber(2) => Err(Error::from(tlv.tag().value_error())),
+            _ => Ok(()),
+        };
+
+        let err = decode_with(EXAMPLE_DER, &hook).unwrap_err();
+        assert_eq!(
+            err.kind(),
+            ErrorKind::Value {
+                tag: Tag::ContextSpecific {
+                    constructed: true,
+                    number: TagNumber(2),
+                }
+            }
+        );
+    }
+
+    #[test]
+    fn unknown_alternative() {
+        // [2] { NULL }
+        let der = hex!("A2020500");
+
+        let err = ExtensibleChoice::from_der(&der).unwrap_err();
+        assert_eq!(
+            err.kind(),
+            ErrorKind::TagUnexpected {
+                expected: None,
+                actual: Tag::ContextSpecific {
+                    constructed: true,
+                    number: TagNumber(2),
+                }
+            }
+        );
+
+        let unknown = Cell::new(None);
+        let hook = |tlv: AnyRef<'_>| {
+            unknown.set(Some(tlv.tag()));
+            Ok(())
+        };
+
+        let mut reader = HookedReader::new(SliceReader::new(&der).unwrap(), &hook);
+        let choice = ExtensibleChoice::decode(&mut reader).unwrap();
+        reader.finish().unwrap();
+
+        assert_eq!(unknown.get(), Some(choice.tag()));
+        assert_eq!(
+            choice,
+            ExtensibleChoice::Unknown(AnyRef::new(choice.tag(), &hex!("0500")).unwrap())
+        );
+        assert_eq!(choice.to_der().unwrap(), der);
+
+        // Known alternatives aren't passed to the hook
+        let mut reader = HookedReader::new(SliceReader::new(&hex!("A1030201 07")).unwrap(), &hook);
+        assert_eq!(
+            ExtensibleChoice::decode(&mut reader).unwrap(),
+            ExtensibleChoice::Count(7)
+        );
+    }
+
+    #[test]
+    fn unknown_alternative_rejected_by_hook() {
+        let der = hex!("A2020500");
+        let hook = |tlv: AnyRef<'_>| Err(Error::from(tlv.tag().value_error()));
+
+        let mut reader = HookedReader::new(SliceReader::new(&der).unwrap(), &hook);
+        let err = ExtensibleChoice::decode(&mut reader).unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::Value { .. }));
+    }
+}
+
+/// Custom derive test cases for the `lazy` field attribute.
+#[cfg(feature = "alloc")]
+mod lazy {
+    use der::{
+        Any, AnyRef, Decode, Encode, Sequence, Tag, Tagged,
+        asn1::{ObjectIdentifier, Utf8StringRef},
+    };
+
+    #[derive(Sequence, Debug, Eq, PartialEq)]
+    pub struct Inner<'a> {
+        pub id: ObjectIdentifier,
+        pub name: Utf8StringRef<'a>,
+    }
+
+    #[derive(Sequence, Debug, Eq, PartialEq)]
+    pub struct Envelope<'a> {
+        pub version: u8,
+
+        #[asn1(lazy = "Inner<'a>")]
+        pub inner: AnyRef<'a>,
+
+        #[asn1(context_specific = "0", optional = "true", lazy = "Inner<'a>")]
+        pub extra: Option<AnyRef<'a>>,
+    }
+
+    #[derive(Sequence, Debug, Eq, PartialEq)]
+    pub struct OwnedEnvelope {
+        #[asn1(lazy = "ObjectIdentifier")]
+        pub id: Any,
+    }
+
+    fn inner() -> Inner<'static> {
+        Inner {
+            id: ObjectIdentifier::new_unwrap("1.2.840.10045.2.1"),
+            name: Utf8StringRef::new("lazy").unwrap(),
+        }
+    }
+
+    #[test]
+    fn decode_on_demand() {
+        let inner_der = inner().to_der().unwrap();
+        let envelope = Envelope {
+            version: 1,
+            inner: AnyRef::from_der(&inner_der).unwrap(),
+            extra: None,
+        };
+        let der = envelope.to_der().unwrap();
+
+        let decoded = Envelope::from_der(&der).unwrap();
+        assert_eq!(decoded, envelope);
+        assert_eq!(decoded.inner.tag(), Tag::Sequence);
+        assert_eq!(decoded.decode_inner().unwrap(), inner());
+        assert_eq!(decoded.decode_extra().unwrap(), None);
+
+        let envelope = Envelope {
+            extra: Some(AnyRef::from_der(&inner_der).unwrap()),
+            ..decoded
+        };
+        let der = envelope.to_der().unwrap();
+        let decoded = Envelope::from_der(&der).unwrap();
+        assert_eq!(decoded.decode_extra().unwrap(), Some(inner()));
+    }
+
+    #[test]
+    fn decode_on_demand_error() {
+        // The raw TLV is accepted as-is, and only rejected once decoded.
+        let envelope = Envelope {
+            version: 1,
+            inner: AnyRef::NULL,
+            extra: None,
+        };
+        let der = envelope.to_der().unwrap();
+
+        let decoded = Envelope::from_der(&der).unwrap();
+        assert!(decoded.decode_inner().is_err());
+    }
+
+    #[test]
+    fn decode_owned_on_demand() {
+        let id = ObjectIdentifier::new_unwrap("1.2.840.10045.2.1");
+        let envelope = OwnedEnvelope {
+            id: Any::encode_from(&id).unwrap(),
+        };
+        let der = envelope.to_der().unwrap();
+
+        let decoded = OwnedEnvelope::from_der(&der).unwrap();
+        assert_eq!(decoded.decode_id().unwrap(), id);
+    }
+}
+
 /// Custom derive test cases for the `EncodeValue` macro.
 mod encode_value {
     use der::{Encode, EncodeValue, FixedTag, Tag};
@@ -1234,7 +1764,7 @@ mod infer_default {
         #[asn1(default = "Default::default")]
         pub use_default_default: bool,
 
-        #[asn1(default = "something_true")]
+        #[asn1(context_specific = "0", default = "something_true")]
         pub use_custom: bool,
     }
 
@@ -1243,3 +1773,420 @@ mod infer_default {
         todo!()
     }
 }
+
+/// Custom derive test cases for the `#[asn1(self_test)]` attribute.
+///
+/// The attribute emits round-trip tests for these types, which are run along
+/// with the other tests in this file.
+mod self_test {
+    use der::{Choice, Sequence};
+
+    #[derive(Sequence, Debug, Default, Eq, PartialEq)]
+    #[asn1(self_test)]
+    pub struct Defaults {
+        pub version: u8,
+
+        #[asn1(context_specific = "0", optional = "true")]
+        pub label: Option<String>,
+
+        #[asn1(
+            context_specific = "1",
+            tag_mode = "IMPLICIT",
+            default = "u32::default"
+        )]
+        pub count: u32,
+    }
+
+    #[cfg(feature = "arbitrary")]
+    #[derive(Sequence, Debug, Eq, PartialEq, arbitrary::Arbitrary)]
+    #[asn1(self_test = "arbitrary")]
+    pub struct Borrowed<'a> {
+        pub id: u32,
+
+        #[asn1(context_specific = "0", optional = "true")]
+        pub bits: Option<der::asn1::BitStringRef<'a>>,
+
+        #[asn1(context_specific = "1", tag_mode = "IMPLICIT", optional = "true")]
+        pub data: Option<der::asn1::OctetString>,
+    }
+
+    #[cfg(feature = "arbitrary")]
+    #[derive(Choice, Debug, Eq, PartialEq, arbitrary::Arbitrary)]
+    #[asn1(self_test = "arbitrary")]
+    pub enum Either {
+        #[asn1(context_specific = "0", constructed = "true")]
+        Flag(bool),
+
+        #[asn1(context_specific = "1", tag_mode = "IMPLICIT")]
+        Number(i64),
+    }
+}
+
+/// Custom derive test cases for the `#[asn1(owned = "...")]` attribute.
+#[cfg(feature = "oid")]
+mod owned {
+    use der::{
+        Decode, Encode, Sequence,
+        asn1::{
+            Any, AnyRef, BitString, BitStringRef, ObjectIdentifier, OctetString, OctetStringRef,
+        },
+        referenced::{OwnedToRef, RefToOwned},
+    };
+    use hex_literal::hex;
+
+    #[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+    #[asn1(owned = "Key")]
+    pub struct KeyRef<'a> {
+        pub algorithm: ObjectIdentifier,
+        pub parameters: Option<AnyRef<'a>>,
+        pub key: &'a OctetStringRef,
+
+        #[asn1(context_specific = "0", optional = "true")]
+        pub public_key: Option<BitStringRef<'a>>,
+    }
+
+    #[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+    pub struct Key {
+        pub algorithm: ObjectIdentifier,
+        pub parameters: Option<Any>,
+        pub key: OctetString,
+
+        #[asn1(context_specific = "0", optional = "true")]
+        pub public_key: Option<BitString>,
+    }
+
+    const KEY_DER: &[u8] = &hex!("30 13 06 03 2B 65 70 05 00 04 04 01 02 03 04 A0 04 03 02 00 FF");
+
+    #[test]
+    fn ref_to_owned() {
+        let key_ref = KeyRef::from_der(KEY_DER).unwrap();
+        let key = key_ref.ref_to_owned();
+        assert_eq!(key, Key::from_der(KEY_DER).unwrap());
+        assert_eq!(key, Key::from(key_ref.clone()));
+        assert_eq!(key.to_der().unwrap(), KEY_DER);
+
+        assert_eq!(key.owned_to_ref(), key_ref);
+        assert_eq!(KeyRef::from(&key), key_ref);
+    }
+}
+
+#[cfg(feature = "oid")]
+mod encode_fixed {
+    use der::{Decode, EncodeFixed, Sequence, asn1::ObjectIdentifier};
+    use hex_literal::hex;
+
+    #[derive(Sequence, Debug, Eq, PartialEq)]
+    #[asn1(encode_fixed)]
+    pub struct Counter {
+        pub version: u8,
+
+        pub algorithm: ObjectIdentifier,
+
+        #[asn1(context_specific = "0", optional = "true")]
+        pub value: Option<u32>,
+
+        #[asn1(context_specific = "40", tag_mode = "IMPLICIT")]
+        pub enabled: bool,
+    }
+
+    #[derive(Sequence, Debug, Eq, PartialEq)]
+    #[asn1(encode_fixed)]
+    pub struct Counters {
+        pub counters: [Counter; 2],
+    }
+
+    const COUNTER: Counter = Counter {
+        version: 1,
+        algorithm: ObjectIdentifier::new_unwrap("1.2.3"),
+        value: Some(0x80000000),
+        enabled: true,
+    };
+
+    #[test]
+    fn max_encoded_len() {
+        // 4 (version) + 41 (algorithm) + 9 (value) + 4 (enabled)
+        assert_eq!(Counter::MAX_ENCODED_LEN, 2 + 58);
+        assert_eq!(Counters::MAX_ENCODED_LEN, 2 + 2 + 2 * 60);
+    }
+
+    #[test]
+    fn encode_to_array() {
+        let encoded = COUNTER
+            .encode_to_array::<{ Counter::MAX_ENCODED_LEN }>()
+            .unwrap();
+        assert_eq!(
+            encoded.as_bytes(),
+            hex!("3014 020101 06022A03 A00702050080000000 9F2801FF")
+        );
+        assert_eq!(Counter::from_der(encoded.as_bytes()).unwrap(), COUNTER);
+
+        let counters = Counters {
+            counters: [COUNTER, COUNTER],
+        };
+        let encoded = counters
+            .encode_to_array::<{ Counters::MAX_ENCODED_LEN }>()
+            .unwrap();
+        assert_eq!(Counters::from_der(encoded.as_bytes()).unwrap(), counters);
+    }
+}
+
+/// Custom error types surfacing domain-specific errors from nested decoding.
+mod custom_error {
+    use super::CustomError;
+    use der::{
+        BitString, Decode, DecodeValue, EncodeValue, FixedTag, Header, Length, Reader, Sequence,
+        Tag, Writer,
+    };
+    use hex_literal::hex;
+
+    /// Version number of which only `0` and `1` are supported.
+    #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+    pub struct Version(u8);
+
+    #[derive(Debug, Eq, PartialEq)]
+    pub enum VersionError {
+        Der(der::Error),
+        Unsupported(u8),
+    }
+
+    impl From<der::Error> for VersionError {
+        fn from(err: der::Error) -> Self {
+            Self::Der(err)
+        }
+    }
+
+    impl<'a> DecodeValue<'a> for Version {
+        type Error = VersionError;
+
+        fn decode_value<R: Reader<'a>>(
+            reader: &mut R,
+            header: Header,
+        ) -> Result<Self, Self::Error> {
+            match u8::decode_value(reader, header)? {
+                version @ 0..=1 => Ok(Self(version)),
+                version => Err(VersionError::Unsupported(version)),
+            }
+        }
+    }
+
+    impl EncodeValue for Version {
+        fn value_len(&self) -> der::Result<Length> {
+            self.0.value_len()
+        }
+
+        fn encode_value(&self, writer: &mut impl Writer) -> der::Result<()> {
+            self.0.encode_value(writer)
+        }
+    }
+
+    impl FixedTag for Version {
+        const TAG: Tag = Tag::Integer;
+    }
+
+    #[derive(Debug, Eq, PartialEq)]
+    pub enum MessageError {
+        Der(der::Error),
+        Version(VersionError),
+    }
+
+    impl From<der::Error> for MessageError {
+        fn from(err: der::Error) -> Self {
+            Self::Der(err)
+        }
+    }
+
+    impl From<VersionError> for MessageError {
+        fn from(err: VersionError) -> Self {
+            Self::Version(err)
+        }
+    }
+
+    #[derive(Sequence, Debug, Eq, PartialEq)]
+    #[asn1(error = "MessageError")]
+    pub struct Message {
+        pub version: Version,
+        pub flag: bool,
+    }
+
+    #[derive(BitString, Debug)]
+    #[asn1(error = "CustomError")]
+    pub struct Flags {
+        pub first: bool,
+        pub second: bool,
+    }
+
+    #[test]
+    fn nested_error() {
+        let message = Message::from_der(&hex!("3006 020101 0101FF")).unwrap();
+        assert_eq!(message.version, Version(1));
+
+        assert_eq!(
+            Message::from_der(&hex!("3006 020105 0101FF")),
+            Err(MessageError::Version(VersionError::Unsupported(5)))
+        );
+        assert!(matches!(
+            Message::from_der(&hex!("3006 020101 0101")),
+            Err(MessageError::Der(_))
+        ));
+    }
+
+    #[test]
+    fn bit_string() {
+        let flags = Flags::from_der(&hex!("03020640")).unwrap();
+        assert!(!flags.first);
+        assert!(flags.second);
+
+        let CustomError(err) = Flags::from_der(&hex!("030201")).unwrap_err();
+        assert_eq!(err.position(), Some(Length::new(3)));
+    }
+}
+
+/// Custom derive test cases for the `#[asn1(skip)]` attribute.
+mod skip {
+    use der::{Decode, DerOrd, Encode, Sequence, ValueOrd};
+    use hex_literal::hex;
+
+    #[derive(Sequence, ValueOrd, Debug, Eq, PartialEq)]
+    pub struct Counter {
+        pub value: u32,
+
+        #[asn1(skip)]
+        pub cache: Option<u32>,
+
+        pub enabled: bool,
+
+        #[asn1(skip, default = "default_label")]
+        pub label: &'static str,
+    }
+
+    fn default_label() -> &'static str {
+        "counter"
+    }
+
+    const COUNTER_DER: &[u8] = &hex!("3006 020105 0101FF");
+
+    #[test]
+    fn decode() {
+        let counter = Counter::from_der(COUNTER_DER).unwrap();
+        assert_eq!(counter.value, 5);
+        assert_eq!(counter.cache, None);
+        assert!(counter.enabled);
+        assert_eq!(counter.label, "counter");
+    }
+
+    #[test]
+    fn encode() {
+        let counter = Counter {
+            value: 5,
+            cache: Some(25),
+            enabled: true,
+            label: "cached",
+        };
+
+        let mut buf = [0u8; 8];
+        assert_eq!(counter.encode_to_slice(&mut buf).unwrap(), COUNTER_DER);
+    }
+
+    #[test]
+    fn value_ord_ignores_skipped_fields() {
+        let a = Counter::from_der(COUNTER_DER).unwrap();
+        let b = Counter {
+            cache: Some(25),
+            ..Counter::from_der(COUNTER_DER).unwrap()
+        };
+
+        assert_eq!(a.value_cmp(&b).unwrap(), core::cmp::Ordering::Equal);
+        assert_eq!(a.der_cmp(&b).unwrap(), core::cmp::Ordering::Equal);
+    }
+}
+
+mod versioned {
+    use der::{Decode, Encode, Enumerated, Sequence};
+    use hex_literal::hex;
+
+    #[derive(Sequence, Debug, Eq, PartialEq)]
+    pub struct Message {
+        #[asn1(version)]
+        pub version: u8,
+
+        pub enabled: bool,
+
+        #[asn1(context_specific = "0", min_version = "2")]
+        pub extra: Option<bool>,
+
+        #[asn1(min_version = "3", default = "default_limit")]
+        pub limit: u16,
+    }
+
+    fn default_limit() -> u16 {
+        5
+    }
+
+    const V1_DER: &[u8] = &hex!("3006 020101 0101FF");
+    const V2_DER: &[u8] = &hex!("300B 020102 0101FF A003010100");
+    const V3_DER: &[u8] = &hex!("300E 020103 0101FF A003010100 02010A");
+
+    fn message(version: u8) -> Message {
+        Message {
+            version,
+            enabled: true,
+            extra: Some(false),
+            limit: 10,
+        }
+    }
+
+    #[test]
+    fn encode_omits_newer_fields() {
+        assert_eq!(message(1).to_der().unwrap(), V1_DER);
+        assert_eq!(message(2).to_der().unwrap(), V2_DER);
+        assert_eq!(message(3).to_der().unwrap(), V3_DER);
+    }
+
+    #[test]
+    fn decode() {
+        let v1 = Message::from_der(V1_DER).unwrap();
+        assert_eq!(v1.extra, None);
+        assert_eq!(v1.limit, 5);
+
+        let v2 = Message::from_der(V2_DER).unwrap();
+        assert_eq!(v2.extra, Some(false));
+        assert_eq!(v2.limit, 5);
+
+        assert_eq!(Message::from_der(V3_DER).unwrap(), message(3));
+    }
+
+    #[test]
+    fn decode_rejects_newer_fields_in_older_version() {
+        assert!(Message::from_der(&hex!("300B 020101 0101FF A003010100")).is_err());
+    }
+
+    #[derive(Enumerated, Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+    #[repr(u8)]
+    pub enum Version {
+        V1 = 0,
+        V2 = 1,
+    }
+
+    #[derive(Sequence, Debug, Eq, PartialEq)]
+    pub struct Key {
+        #[asn1(version)]
+        pub version: Version,
+
+        #[asn1(context_specific = "1", min_version = "Version::V2")]
+        pub public_key: Option<bool>,
+    }
+
+    #[test]
+    fn enumerated_version() {
+        let mut key = Key {
+            version: Version::V1,
+            public_key: Some(true),
+        };
+        assert_eq!(key.to_der().unwrap(), hex!("3003 0A0100"));
+
+        key.version = Version::V2;
+        let der = key.to_der().unwrap();
+        assert_eq!(der, hex!("3008 0A0101 A1030101FF"));
+        assert_eq!(Key::from_der(&der).unwrap(), key);
+    }
+}
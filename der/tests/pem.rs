@@ -3,7 +3,8 @@
 #![cfg(all(feature = "derive", feature = "oid", feature = "pem"))]
 
 use der::{
-    Any, Decode, DecodePem, EncodePem, Sequence,
+    Any, Decode, DecodePem, EncodePem, ErrorKind, Length, PemReader, Reader, ReaderOptions,
+    Sequence,
     asn1::{BitString, ObjectIdentifier},
     pem::{LineEnding, PemLabel},
 };
@@ -79,3 +80,124 @@ fn read_zero_slices_from_pem() {
     let pem = spki.to_pem(LineEnding::LF).unwrap();
     SpkiOwned::from_pem(pem).unwrap();
 }
+
+#[test]
+fn pem_reader_options() {
+    let input_len = Length::try_from(SPKI_DER.len()).unwrap();
+
+    let options = ReaderOptions::new().with_max_input_len(input_len);
+    let mut reader = PemReader::new(SPKI_PEM.as_bytes())
+        .unwrap()
+        .with_options(options)
+        .unwrap();
+    assert!(reader.decode::<SpkiOwned>().is_ok());
+
+    let options = ReaderOptions::new().with_max_input_len((input_len - Length::ONE).unwrap());
+    let err = PemReader::new(SPKI_PEM.as_bytes())
+        .unwrap()
+        .with_options(options)
+        .err()
+        .unwrap();
+    assert!(matches!(err.kind(), ErrorKind::LengthLimit { .. }));
+
+    let options = ReaderOptions::new().with_max_value_len(Length::new(16));
+    let mut reader = PemReader::new(SPKI_PEM.as_bytes())
+        .unwrap()
+        .with_options(options)
+        .unwrap();
+    let err = reader.decode::<SpkiOwned>().unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::LengthLimit { .. }));
+}
+
+#[test]
+fn pem_reader_blocks() {
+    let null_pem = der::pem::encode_string("NULL", LineEnding::CRLF, &[0x05, 0x00]).unwrap();
+    let bundle = format!("preamble\n{SPKI_PEM}\nsubject=example\n{null_pem}{SPKI_PEM}trailer\n");
+
+    let mut blocks = PemReader::blocks(bundle.as_bytes());
+    let mut labels = Vec::new();
+
+    for block in &mut blocks {
+        let mut reader = block.unwrap();
+        labels.push(reader.type_label());
+
+        match reader.type_label() {
+            "PUBLIC KEY" => {
+                let spki = reader.decode::<SpkiOwned>().unwrap();
+                assert_eq!(spki, SpkiOwned::from_pem(SPKI_PEM).unwrap());
+            }
+            "NULL" => reader.decode::<der::asn1::Null>().map(drop).unwrap(),
+            label => panic!("unexpected label: {label}"),
+        }
+    }
+
+    assert_eq!(labels, ["PUBLIC KEY", "NULL", "PUBLIC KEY"]);
+    assert_eq!(blocks.remaining(), b"trailer\n");
+    assert_eq!(PemReader::blocks(b"").count(), 0);
+}
+
+#[test]
+fn pem_reader_blocks_with_label() {
+    let null_pem = der::pem::encode_string("NULL", LineEnding::LF, &[0x05, 0x00]).unwrap();
+    let bundle = format!("{SPKI_PEM}{null_pem}");
+
+    let results: Vec<_> = PemReader::blocks(bundle.as_bytes())
+        .with_label("PUBLIC KEY")
+        .map(|block| block.map(|reader| reader.type_label()))
+        .collect();
+
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].as_ref().ok(), Some(&"PUBLIC KEY"));
+    assert_eq!(
+        results[1].as_ref().unwrap_err().kind(),
+        ErrorKind::Pem(der::pem::Error::UnexpectedTypeLabel {
+            expected: "PUBLIC KEY"
+        })
+    );
+}
+
+#[test]
+fn pem_reader_blocks_truncated() {
+    let truncated = &SPKI_PEM[..SPKI_PEM.len() / 2];
+    let bundle = format!("{SPKI_PEM}{truncated}");
+
+    let mut blocks = PemReader::blocks(bundle.as_bytes());
+    assert!(blocks.next().unwrap().is_ok());
+    assert_eq!(
+        blocks.next().unwrap().err().unwrap().kind(),
+        ErrorKind::Pem(der::pem::Error::PostEncapsulationBoundary)
+    );
+    assert!(blocks.next().is_none());
+}
+
+/// Error type of a downstream document type.
+#[derive(Debug, Eq, PartialEq)]
+pub struct CustomError(ErrorKind);
+
+impl From<der::Error> for CustomError {
+    fn from(err: der::Error) -> Self {
+        Self(err.kind())
+    }
+}
+
+/// `SpkiOwned` with a custom decoding error type.
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+#[asn1(error = CustomError)]
+pub struct SpkiCustomError {
+    pub algorithm: AlgorithmIdentifier,
+    pub subject_public_key: BitString,
+}
+
+impl PemLabel for SpkiCustomError {
+    const PEM_LABEL: &'static str = "PUBLIC KEY";
+}
+
+#[test]
+fn from_pem_custom_error() {
+    let spki = SpkiCustomError::from_pem(SPKI_PEM).unwrap();
+    assert_eq!(spki.to_pem(LineEnding::LF).unwrap(), SPKI_PEM);
+
+    let pem = SPKI_PEM.replace("PUBLIC KEY", "PRIVATE KEY");
+    let err = SpkiCustomError::from_pem(pem).unwrap_err();
+    assert!(matches!(err, CustomError(ErrorKind::Pem(_))));
+}
@@ -0,0 +1,150 @@
+//! Tests for generating ASN.1 module skeletons from derived types.
+
+#![cfg(all(
+    feature = "schema",
+    feature = "derive",
+    feature = "alloc",
+    feature = "oid"
+))]
+
+use der::{
+    BitString, Choice, Enumerated, Sequence,
+    asn1::{
+        Any, BitString as BitStringOwned, ContextSpecific, GeneralizedTime, ObjectIdentifier,
+        UtcTime,
+    },
+    schema::{Asn1Schema, Module},
+};
+
+#[derive(Sequence)]
+pub struct AlgorithmIdentifier {
+    pub algorithm: ObjectIdentifier,
+    pub parameters: Option<Any>,
+}
+
+#[derive(Choice)]
+pub enum Time {
+    #[asn1(type = "UTCTime")]
+    UtcTime(UtcTime),
+
+    #[asn1(type = "GeneralizedTime")]
+    GeneralTime(GeneralizedTime),
+}
+
+#[derive(Enumerated, Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum Version {
+    V1 = 0,
+    V2 = 1,
+    V3 = 2,
+}
+
+#[derive(BitString)]
+pub struct KeyUsage {
+    pub digital_signature: bool,
+    pub non_repudiation: bool,
+}
+
+fn default_version() -> Version {
+    Version::V1
+}
+
+#[derive(Sequence)]
+#[asn1(tag_mode = "IMPLICIT")]
+pub struct Example {
+    #[asn1(
+        context_specific = "0",
+        tag_mode = "EXPLICIT",
+        default = "default_version"
+    )]
+    pub version: Version,
+    pub signature: AlgorithmIdentifier,
+    pub not_before: Time,
+    pub key_usage: KeyUsage,
+    #[asn1(context_specific = "1", optional = "true")]
+    pub issuer_unique_id: Option<BitStringOwned>,
+    #[asn1(context_specific = "2", optional = "true", extensible = "true")]
+    pub extensions: Option<Vec<AlgorithmIdentifier>>,
+}
+
+fn definition<T: Asn1Schema>() -> String {
+    let mut out = String::new();
+    T::write_definition(&mut out).unwrap();
+    out
+}
+
+#[test]
+fn sequence_definition() {
+    assert_eq!(
+        definition::<AlgorithmIdentifier>(),
+        "AlgorithmIdentifier ::= SEQUENCE {\n    \
+         algorithm OBJECT IDENTIFIER,\n    \
+         parameters ANY OPTIONAL\n}"
+    );
+
+    assert_eq!(
+        definition::<Example>(),
+        "Example ::= SEQUENCE {\n    \
+         version [0] EXPLICIT Version DEFAULT default_version,\n    \
+         signature AlgorithmIdentifier,\n    \
+         notBefore Time,\n    \
+         keyUsage KeyUsage,\n    \
+         issuerUniqueId [1] IMPLICIT BIT STRING OPTIONAL,\n    \
+         ...,\n    \
+         extensions [2] IMPLICIT SEQUENCE OF AlgorithmIdentifier OPTIONAL\n}"
+    );
+}
+
+/// Types without an `Asn1Schema` impl are written using their Rust type name.
+#[derive(Sequence)]
+pub struct Fallback {
+    pub other: ContextSpecific<Any>,
+}
+
+#[test]
+fn sequence_definition_fallback() {
+    assert_eq!(
+        definition::<Fallback>(),
+        "Fallback ::= SEQUENCE {\n    other ContextSpecific<Any>\n}"
+    );
+}
+
+#[test]
+fn choice_definition() {
+    assert_eq!(
+        definition::<Time>(),
+        "Time ::= CHOICE {\n    utcTime UTCTime,\n    generalTime GeneralizedTime\n}"
+    );
+}
+
+#[test]
+fn enumerated_definition() {
+    assert_eq!(
+        definition::<Version>(),
+        "Version ::= ENUMERATED {\n    v1(0),\n    v2(1),\n    v3(2)\n}"
+    );
+}
+
+#[test]
+fn bit_string_definition() {
+    assert_eq!(
+        definition::<KeyUsage>(),
+        "KeyUsage ::= BIT STRING {\n    digitalSignature(0),\n    nonRepudiation(1)\n}"
+    );
+}
+
+#[test]
+fn module() {
+    let module = Module::new(
+        "Example",
+        &[Time::write_definition, Version::write_definition],
+    );
+
+    assert_eq!(
+        module.to_string(),
+        "Example DEFINITIONS ::= BEGIN\n\n\
+         Time ::= CHOICE {\n    utcTime UTCTime,\n    generalTime GeneralizedTime\n}\n\n\
+         Version ::= ENUMERATED {\n    v1(0),\n    v2(1),\n    v3(2)\n}\n\n\
+         END\n"
+    );
+}
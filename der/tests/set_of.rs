@@ -26,7 +26,7 @@ proptest! {
 #[cfg(all(feature = "derive", feature = "oid"))]
 mod ordering {
     use der::{
-        Decode, Sequence, ValueOrd,
+        Decode, Encode, Sequence, ValueOrd,
         asn1::{AnyRef, ObjectIdentifier, SetOf, SetOfVec},
     };
     use hex_literal::hex;
@@ -41,17 +41,35 @@ mod ordering {
     const OUT_OF_ORDER_RDN_EXAMPLE: &[u8] =
         &hex!("311F301106035504030C0A4A4F484E20534D495448300A060355040A0C03313233");
 
+    /// `OUT_OF_ORDER_RDN_EXAMPLE` with its elements in DER order.
+    const SORTED_RDN_EXAMPLE: &[u8] =
+        &hex!("311F300A060355040A0C03313233301106035504030C0A4A4F484E20534D495448");
+
     /// For compatibility reasons, we allow non-canonical DER with out-of-order
     /// sets in order to match the behavior of other implementations.
     #[test]
     fn allow_out_of_order_setof() {
-        assert!(SetOf::<AttributeTypeAndValue<'_>, 2>::from_der(OUT_OF_ORDER_RDN_EXAMPLE).is_ok());
+        let set =
+            SetOf::<AttributeTypeAndValue<'_>, 2>::from_der(OUT_OF_ORDER_RDN_EXAMPLE).unwrap();
+        assert!(set.is_reordered());
+        assert_eq!(set.to_der().unwrap(), SORTED_RDN_EXAMPLE);
+
+        let sorted = SetOf::<AttributeTypeAndValue<'_>, 2>::from_der(SORTED_RDN_EXAMPLE).unwrap();
+        assert!(!sorted.is_reordered());
+        assert_eq!(set, sorted);
     }
 
     /// Same as above, with `SetOfVec` instead of `SetOf`.
     #[test]
     fn allow_out_of_order_setofvec() {
-        assert!(SetOfVec::<AttributeTypeAndValue<'_>>::from_der(OUT_OF_ORDER_RDN_EXAMPLE).is_ok());
+        let set =
+            SetOfVec::<AttributeTypeAndValue<'_>>::from_der(OUT_OF_ORDER_RDN_EXAMPLE).unwrap();
+        assert!(set.is_reordered());
+        assert_eq!(set.to_der().unwrap(), SORTED_RDN_EXAMPLE);
+
+        let sorted = SetOfVec::<AttributeTypeAndValue<'_>>::from_der(SORTED_RDN_EXAMPLE).unwrap();
+        assert!(!sorted.is_reordered());
+        assert_eq!(set, sorted);
     }
 
     /// Test to ensure ordering is handled correctly.
@@ -1,11 +1,11 @@
 //! Attribute-related types used by the proc macro
 
-use crate::{Asn1Type, Tag, TagMode, TagNumber};
+use crate::{Asn1Type, Tag, TagMode, TagNumber, self_test::SelfTest};
 use proc_macro2::{Span, TokenStream};
 use quote::{ToTokens, quote};
 use std::{fmt::Debug, str::FromStr};
 use syn::punctuated::Punctuated;
-use syn::{Attribute, Ident, LitStr, Path, Token, parse::Parse, parse::ParseStream};
+use syn::{Attribute, Expr, Ident, LitStr, Path, Token, Type, parse::Parse, parse::ParseStream};
 
 /// Error type used by the structure
 #[derive(Debug, Clone, Default, Eq, PartialEq)]
@@ -17,6 +17,21 @@ pub(crate) enum ErrorType {
     Custom(Path),
 }
 
+impl Parse for ErrorType {
+    /// Parse the value of an `error` attribute, which is either a path, e.g.
+    /// `#[asn1(error = MyError)]`, or a string containing one like the values
+    /// of other attributes, e.g. `#[asn1(error = "MyError")]`.
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let path = if input.peek(LitStr) {
+            input.parse::<LitStr>()?.parse()?
+        } else {
+            input.parse()?
+        };
+
+        Ok(Self::Custom(path))
+    }
+}
+
 impl ToTokens for ErrorType {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         match self {
@@ -41,6 +56,19 @@ pub(crate) struct TypeAttrs {
     /// The default value is `EXPLICIT`.
     pub tag_mode: TagMode,
     pub error: ErrorType,
+
+    /// Sources of the values checked by the round-trip tests emitted for
+    /// this type, supplied as `#[asn1(self_test)]` or
+    /// `#[asn1(self_test = "...")]`.
+    pub self_test: Option<SelfTest>,
+
+    /// Owned counterpart of this borrowed type, supplied as
+    /// `#[asn1(owned = "...")]`.
+    pub owned: Option<Path>,
+
+    /// Should `EncodeFixed` be impl'd for this type? Supplied as
+    /// `#[asn1(encode_fixed)]`.
+    pub encode_fixed: bool,
 }
 
 impl TypeAttrs {
@@ -48,6 +76,9 @@ impl TypeAttrs {
     pub fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
         let mut tag_mode = None;
         let mut error = None;
+        let mut self_test = None;
+        let mut owned = None;
+        let mut encode_fixed = false;
 
         attrs.iter().try_for_each(|attr| {
             if !attr.path().is_ident(ATTR_NAME) {
@@ -66,11 +97,36 @@ impl TypeAttrs {
                         abort!(attr, "duplicate ASN.1 `error` attribute");
                     }
 
-                    error = Some(ErrorType::Custom(meta.value()?.parse()?));
+                    error = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("self_test") {
+                    if self_test.is_some() {
+                        abort!(attr, "duplicate ASN.1 `self_test` attribute");
+                    }
+
+                    self_test = Some(if meta.input.peek(Token![=]) {
+                        SelfTest::parse(&meta.value()?.parse()?)?
+                    } else {
+                        SelfTest {
+                            default: true,
+                            arbitrary: false,
+                        }
+                    });
+                } else if meta.path.is_ident("owned") {
+                    if owned.is_some() {
+                        abort!(attr, "duplicate ASN.1 `owned` attribute");
+                    }
+
+                    owned = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+                } else if meta.path.is_ident("encode_fixed") {
+                    if encode_fixed {
+                        abort!(attr, "duplicate ASN.1 `encode_fixed` attribute");
+                    }
+
+                    encode_fixed = true;
                 } else {
                     return Err(syn::Error::new_spanned(
                         attr,
-                        "invalid `asn1` attribute (valid options are `tag_mode` and `error`)",
+                        "invalid `asn1` attribute (valid options are `tag_mode`, `error`, `self_test`, `owned` and `encode_fixed`)",
                     ));
                 }
 
@@ -81,6 +137,9 @@ impl TypeAttrs {
         Ok(Self {
             tag_mode: tag_mode.unwrap_or_default(),
             error: error.unwrap_or_default(),
+            self_test,
+            owned,
+            encode_fixed,
         })
     }
 }
@@ -110,22 +169,44 @@ pub(crate) struct FieldAttrs {
     /// Is this field "extensible", i.e. preceded by the `...` extensibility marker?
     pub extensible: bool,
 
+    /// Type the raw TLV stored in this field is decoded as on demand, supplied
+    /// as `#[asn1(lazy = "...")]`.
+    pub lazy: Option<Type>,
+
+    /// Lowest version in which this field can be present, compared with the
+    /// value of the `version` field of the struct. Supplied as
+    /// `#[asn1(min_version = "...")]`.
+    pub min_version: Option<Expr>,
+
     /// Is this field `OPTIONAL`?
     pub optional: bool,
 
+    /// Range of allowed sizes for this field, supplied as `#[asn1(size = "...")]`.
+    pub size: Option<TokenStream>,
+
+    /// Is this field absent from the encoding, i.e. populated using its `default`
+    /// function or `Default` impl when decoding and ignored when encoding?
+    /// Supplied as `#[asn1(skip)]`.
+    pub skip: bool,
+
     /// Tagging mode for this type: `EXPLICIT` or `IMPLICIT`, supplied as
     /// `#[asn1(tag_mode = "...")]`.
     ///
     /// Inherits from the type-level tagging mode if specified, or otherwise
     /// defaults to `EXPLICIT`.
     pub tag_mode: TagMode,
+
+    /// Is this field the version discriminator of the struct, which the
+    /// `min_version` of the other fields is compared with? Supplied as
+    /// `#[asn1(version)]`.
+    pub version: bool,
 }
 
 impl FieldAttrs {
     /// Return true when either an optional or default ASN.1 attribute is associated
     /// with a field. Default signifies optionality due to omission of default values in
     /// DER encodings.
-    fn is_optional(&self) -> bool {
+    pub fn is_optional(&self) -> bool {
         self.optional || self.default.is_some()
     }
 
@@ -137,8 +218,13 @@ impl FieldAttrs {
         let mut default = None;
         let mut should_deref = None;
         let mut extensible = None;
+        let mut lazy = None;
+        let mut min_version = None;
         let mut optional = None;
+        let mut size = None;
+        let mut skip = None;
         let mut tag_mode = None;
+        let mut version = None;
 
         let mut parsed_attrs = Vec::new();
         AttrNameValue::from_attributes(attrs, &mut parsed_attrs)?;
@@ -199,6 +285,30 @@ impl FieldAttrs {
                 }
 
                 extensible = Some(ext);
+            // `lazy` attribute
+            } else if attr.parse_value::<String>("lazy")?.is_some() {
+                if lazy.is_some() {
+                    abort!(attr.name, "duplicate ASN.1 `lazy` attribute");
+                }
+
+                lazy = Some(attr.value.parse().map_err(|e| {
+                    syn::Error::new_spanned(
+                        attr.value,
+                        format_args!("error parsing ASN.1 `lazy` attribute: {e}"),
+                    )
+                })?);
+            // `min_version` attribute
+            } else if attr.parse_value::<String>("min_version")?.is_some() {
+                if min_version.is_some() {
+                    abort!(attr.name, "duplicate ASN.1 `min_version` attribute");
+                }
+
+                min_version = Some(attr.value.parse().map_err(|e| {
+                    syn::Error::new_spanned(
+                        attr.value,
+                        format_args!("error parsing ASN.1 `min_version` attribute: {e}"),
+                    )
+                })?);
             // `optional` attribute
             } else if let Some(opt) = attr.parse_value("optional")? {
                 if optional.is_some() {
@@ -206,6 +316,20 @@ impl FieldAttrs {
                 }
 
                 optional = Some(opt);
+            // `size` attribute
+            } else if attr.parse_value::<String>("size")?.is_some() {
+                if size.is_some() {
+                    abort!(attr.name, "duplicate ASN.1 `size` attribute");
+                }
+
+                size = Some(parse_size(&attr.value)?);
+            // `skip` attribute
+            } else if let Some(sk) = attr.parse_value("skip")? {
+                if skip.is_some() {
+                    abort!(attr.name, "duplicate ASN.1 `skip` attribute");
+                }
+
+                skip = Some(sk);
             // `tag_mode` attribute
             } else if let Some(mode) = attr.parse_value("tag_mode")? {
                 if tag_mode.is_some() {
@@ -227,11 +351,18 @@ impl FieldAttrs {
                 }
 
                 constructed = Some(ty);
+            // `version` attribute
+            } else if let Some(ver) = attr.parse_value("version")? {
+                if version.is_some() {
+                    abort!(attr.name, "duplicate ASN.1 `version` attribute");
+                }
+
+                version = Some(ver);
             } else {
                 abort!(
                     attr.name,
                     "unknown field-level `asn1` attribute \
-                    (valid options are `application`, `constructed`, `context_specific`, `default`, `deref`, `extensible`, `optional`, `private`, `tag_mode`, `type`)",
+                    (valid options are `application`, `constructed`, `context_specific`, `default`, `deref`, `extensible`, `lazy`, `min_version`, `optional`, `private`, `size`, `skip`, `tag_mode`, `type`, `version`)",
                 );
             }
         }
@@ -243,8 +374,13 @@ impl FieldAttrs {
             default,
             should_deref: should_deref.unwrap_or_default(),
             extensible: extensible.unwrap_or_default(),
+            lazy,
+            min_version,
             optional: optional.unwrap_or_default(),
+            size,
+            skip: skip.unwrap_or_default(),
             tag_mode: tag_mode.unwrap_or(type_attrs.tag_mode),
+            version: version.unwrap_or_default(),
         })
     }
 
@@ -405,6 +541,60 @@ impl FieldAttrs {
     }
 }
 
+/// Parse the value of a `#[asn1(size = "...")]` attribute into a range expression.
+///
+/// Accepts a single size (`"16"`) or an inclusive range (`"1..=64"`, `"1.."`, `"..=64"`).
+/// Half-open ranges (`"1..64"`) are rejected since they'd be easily confused with the
+/// inclusive ASN.1 `SIZE (1..64)` notation.
+fn parse_size(value: &LitStr) -> syn::Result<TokenStream> {
+    let parse_bound = |bound: &str| -> syn::Result<Option<usize>> {
+        let bound = bound.trim();
+
+        if bound.is_empty() {
+            return Ok(None);
+        }
+
+        bound.parse().map(Some).map_err(|_| {
+            syn::Error::new_spanned(value, format_args!("invalid ASN.1 `size` bound: `{bound}`"))
+        })
+    };
+
+    let string = value.value();
+
+    let (min, max) = match string.split_once("..") {
+        Some((min, max)) => match max.strip_prefix('=') {
+            Some(max) => (parse_bound(min)?, parse_bound(max)?),
+            None if max.trim().is_empty() => (parse_bound(min)?, None),
+            None => {
+                return Err(syn::Error::new_spanned(
+                    value,
+                    "ASN.1 `size` ranges must be inclusive, e.g. `1..=64`",
+                ));
+            }
+        },
+        None => match parse_bound(&string)? {
+            Some(size) => (Some(size), Some(size)),
+            None => {
+                return Err(syn::Error::new_spanned(
+                    value,
+                    "empty ASN.1 `size` attribute",
+                ));
+            }
+        },
+    };
+
+    match (min, max) {
+        (Some(min), Some(max)) if min > max => Err(syn::Error::new_spanned(
+            value,
+            "ASN.1 `size` lower bound exceeds upper bound",
+        )),
+        (Some(min), Some(max)) => Ok(quote!(#min..=#max)),
+        (Some(min), None) => Ok(quote!(#min..)),
+        (None, Some(max)) => Ok(quote!(..=#max)),
+        (None, None) => Ok(quote!(..)),
+    }
+}
+
 /// Name/value pair attribute.
 pub(crate) struct AttrNameValue {
     /// Attribute name.
@@ -431,6 +621,15 @@ impl Parse for AttrNameValue {
                 }
             }
         };
+
+        // The bare `#[asn1(skip)]` and `#[asn1(version)]` flags are shorthand
+        // for e.g. `skip = "true"`, other attributes require a value
+        let is_flag = name.is_ident("skip") || name.is_ident("version");
+        if is_flag && !input.peek(Token![=]) {
+            let value = LitStr::new("true", Span::call_site());
+            return Ok(Self { name, value });
+        }
+
         input.parse::<Token![=]>()?;
         let value = input.parse()?;
         Ok(Self { name, value })
@@ -1,9 +1,9 @@
 //! Support for deriving the `BitString` trait on bool structs for the purposes of
 //! decoding/encoding ASN.1 `BITSTRING` types as mapped to struct fields.
 
-use crate::{TypeAttrs, default_lifetime};
+use crate::{ErrorType, TypeAttrs, default_lifetime, schema::SchemaWriter};
 use proc_macro2::TokenStream;
-use quote::quote;
+use quote::{ToTokens, quote};
 use syn::{DeriveInput, GenericParam, Generics, Ident, LifetimeParam};
 
 use self::field::BitStringField;
@@ -20,6 +20,9 @@ pub(crate) struct DeriveBitString {
 
     /// Fields of the struct.
     fields: Vec<BitStringField>,
+
+    /// Error type for `DecodeValue` implementation.
+    error: ErrorType,
 }
 
 impl DeriveBitString {
@@ -59,12 +62,14 @@ impl DeriveBitString {
             ident: input.ident,
             generics: input.generics.clone(),
             fields,
+            error: type_attrs.error.clone(),
         })
     }
 
     /// Lower the derived output into a [`TokenStream`].
     pub fn to_tokens(&self) -> TokenStream {
         let ident = &self.ident;
+        let error = self.error.to_token_stream();
         let mut generics = self.generics.clone();
 
         // Use the first lifetime parameter as lifetime for Decode/Encode lifetime
@@ -129,7 +134,15 @@ impl DeriveBitString {
             }));
         }
 
+        let mut schema = SchemaWriter::new(ident, &self.generics, "BIT STRING");
+        for (i, field) in self.fields.iter().enumerate() {
+            schema.named_number(&field.ident, i);
+        }
+        let schema = schema.into_tokens(&self.generics);
+
         quote! {
+            #schema
+
             impl ::der::FixedTag for #ident #ty_generics #where_clause {
                 const TAG: der::Tag = ::der::Tag::BitString;
             }
@@ -138,12 +151,12 @@ impl DeriveBitString {
             }
 
             impl #impl_generics ::der::DecodeValue<#lifetime> for #ident #ty_generics #where_clause {
-                type Error = ::der::Error;
+                type Error = #error;
 
                 fn decode_value<R: ::der::Reader<#lifetime>>(
                     reader: &mut R,
                     header: ::der::Header,
-                ) -> ::core::result::Result<Self, ::der::Error> {
+                ) -> ::core::result::Result<Self, #error> {
                     use ::der::{Decode as _, DecodeValue as _, Reader as _};
                     use ::der::AllowedLenBitString as _;
 
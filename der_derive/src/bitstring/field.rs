@@ -25,6 +25,17 @@ impl BitStringField {
 
         let attrs = FieldAttrs::parse(&field.attrs, type_attrs)?;
 
+        if attrs.skip {
+            abort!(&ident, "`skip` is not allowed on BIT STRING fields");
+        }
+
+        if attrs.version || attrs.min_version.is_some() {
+            abort!(
+                &ident,
+                "`version` and `min_version` are not allowed on BIT STRING fields"
+            );
+        }
+
         if attrs.asn1_type.is_some() && attrs.default.is_some() {
             return Err(syn::Error::new_spanned(
                 ident,
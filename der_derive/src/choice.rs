@@ -4,8 +4,11 @@
 
 mod variant;
 
-use self::variant::ChoiceVariant;
-use crate::{ErrorType, TypeAttrs, default_lifetime};
+use self::variant::{ChoiceVariant, ExtensionVariant, TagOrPath};
+use crate::{
+    ErrorType, Tag, TagNumber, TypeAttrs, default_lifetime, schema::SchemaWriter,
+    self_test::SelfTest,
+};
 use proc_macro2::TokenStream;
 use quote::{ToTokens, quote};
 use syn::{DeriveInput, GenericParam, Generics, Ident, LifetimeParam};
@@ -21,8 +24,14 @@ pub(crate) struct DeriveChoice {
     /// Variants of this `Choice`.
     variants: Vec<ChoiceVariant>,
 
+    /// Variant holding the unknown alternatives, if this `Choice` is extensible.
+    extension: Option<ExtensionVariant>,
+
     /// Error type for `DecodeValue` implementation.
     error: ErrorType,
+
+    /// Round-trip tests to emit, if any.
+    self_test: Option<SelfTest>,
 }
 
 impl DeriveChoice {
@@ -37,17 +46,44 @@ impl DeriveChoice {
         };
 
         let type_attrs = TypeAttrs::parse(&input.attrs)?;
-        let variants = data
-            .variants
-            .iter()
-            .map(|variant| ChoiceVariant::new(variant, &type_attrs))
-            .collect::<syn::Result<_>>()?;
+        let mut variants = Vec::new();
+        let mut extension = None;
+
+        for variant in &data.variants {
+            match ExtensionVariant::new(variant, &type_attrs)? {
+                Some(_) if extension.is_some() => {
+                    abort!(&variant.ident, "only one variant can be `extensible`")
+                }
+                Some(ext) => extension = Some(ext),
+                None => variants.push(ChoiceVariant::new(variant, &type_attrs)?),
+            }
+        }
+
+        if type_attrs.self_test.is_some() {
+            SelfTest::check_generics(&input.ident, &input.generics)?;
+        }
+
+        if type_attrs.owned.is_some() {
+            abort!(
+                input.ident,
+                "`owned` is only supported when deriving `Sequence`"
+            );
+        }
+
+        if type_attrs.encode_fixed {
+            abort!(
+                input.ident,
+                "`encode_fixed` is only supported when deriving `Sequence`"
+            );
+        }
 
         Ok(Self {
             ident: input.ident,
             generics: input.generics.clone(),
             variants,
+            extension,
             error: type_attrs.error.clone(),
+            self_test: type_attrs.self_test,
         })
     }
 
@@ -74,7 +110,6 @@ impl DeriveChoice {
         let (_, ty_generics, where_clause) = self.generics.split_for_impl();
         let (impl_generics, _, _) = generics.split_for_impl();
 
-        let mut can_decode_body = Vec::new();
         let mut decode_body = Vec::new();
         let mut decode_value_body = Vec::new();
         let mut encode_body = Vec::new();
@@ -82,7 +117,6 @@ impl DeriveChoice {
         let mut tagged_body = Vec::new();
 
         for variant in &self.variants {
-            can_decode_body.push(variant.tag.to_tokens());
             decode_body.push(variant.to_decode_tokens());
             decode_value_body.push(variant.to_decode_value_tokens());
             encode_body.push(variant.to_encode_value_tokens());
@@ -90,12 +124,34 @@ impl DeriveChoice {
             tagged_body.push(variant.to_tagged_tokens());
         }
 
+        let (decode_unknown, decode_value_unknown) = match &self.extension {
+            Some(extension) => {
+                encode_body.push(extension.to_encode_value_tokens());
+                value_len_body.push(extension.to_value_len_tokens());
+                tagged_body.push(extension.to_tagged_tokens());
+                (
+                    extension.to_decode_tokens(),
+                    extension.to_decode_value_tokens(),
+                )
+            }
+            None => (unknown_tokens(), unknown_tokens()),
+        };
+
         let error = self.error.to_token_stream();
+        let tag_set = self.to_tokens_tag_set();
+        let schema = self.to_tokens_schema();
+        let self_test = self
+            .self_test
+            .map(|self_test| self_test.to_tokens(&self.ident, &self.generics));
 
         quote! {
+            #schema
+            #self_test
+            #tag_set
+
             impl #impl_generics ::der::Choice<#lifetime> for #ident #ty_generics #where_clause {
                 fn can_decode(tag: ::der::Tag) -> bool {
-                    matches!(tag, #(#can_decode_body)|*)
+                    Self::TAG_SET.contains(tag)
                 }
             }
 
@@ -108,16 +164,10 @@ impl DeriveChoice {
 
                 fn decode<R: ::der::Reader<#lifetime>>(reader: &mut R) -> ::core::result::Result<Self, #error> {
                     use der::Reader as _;
+                    let _span = ::der::trace::peek_decode_span::<Self>(reader);
                     match ::der::Tag::peek(reader)? {
                         #(#decode_body)*
-                        actual => Err(::der::Error::new(
-                            ::der::ErrorKind::TagUnexpected {
-                                expected: None,
-                                actual
-                            },
-                            reader.position()
-                        ).into()
-                        ),
+                        #decode_unknown
                     }
                 }
             }
@@ -128,14 +178,7 @@ impl DeriveChoice {
                 fn decode_value<R: ::der::Reader<#lifetime>>(reader: &mut R, header: der::Header) -> ::core::result::Result<Self, #error> {
                     match header.tag() {
                         #(#decode_value_body)*
-                        actual => Err(::der::Error::new(
-                            ::der::ErrorKind::TagUnexpected {
-                                expected: None,
-                                actual
-                            },
-                            reader.position()
-                        ).into()
-                        ),
+                        #decode_value_unknown
                     }
                 }
             }
@@ -165,6 +208,98 @@ impl DeriveChoice {
     }
 }
 
+impl DeriveChoice {
+    /// Lower the derived output into a [`TokenStream`] for the inherent `TAG_SET` constant.
+    ///
+    /// Consecutively numbered `CONTEXT-SPECIFIC` tags of the same form are
+    /// collapsed into ranges, with all other tags being listed individually.
+    fn to_tokens_tag_set(&self) -> TokenStream {
+        let ident = &self.ident;
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+
+        let mut context_specific = Vec::new();
+        let mut tags = Vec::new();
+
+        for variant in &self.variants {
+            match variant.tag {
+                TagOrPath::Tag(Tag::ContextSpecific {
+                    constructed,
+                    number,
+                }) => context_specific.push((constructed, number)),
+                ref tag => tags.push(tag.to_tokens()),
+            }
+        }
+
+        context_specific.sort();
+        context_specific.dedup();
+
+        let mut ranges = Vec::new();
+
+        for run in context_specific
+            .chunk_by(|(c1, n1), (c2, n2)| c1 == c2 && n1.0.checked_add(1) == Some(n2.0))
+        {
+            let (constructed, start) = run[0];
+            let (_, end) = run[run.len() - 1];
+
+            if run.len() == 1 {
+                tags.push(
+                    Tag::ContextSpecific {
+                        constructed,
+                        number: start,
+                    }
+                    .to_tokens(),
+                );
+            } else {
+                ranges.push(range_tokens(constructed, start, end));
+            }
+        }
+
+        quote! {
+            impl #impl_generics #ident #ty_generics #where_clause {
+                /// Tags of the alternatives of this `CHOICE`.
+                pub const TAG_SET: ::der::TagSet<'static> = ::der::TagSet::new(&[#(#tags),*])
+                    .with_context_specific(&[#(#ranges),*]);
+            }
+        }
+    }
+
+    /// Lower the derived output into a [`TokenStream`] for the `Asn1Schema` trait impl.
+    fn to_tokens_schema(&self) -> TokenStream {
+        let mut schema = SchemaWriter::new(&self.ident, &self.generics, "CHOICE");
+
+        for variant in &self.variants {
+            schema.component(&variant.ident, &variant.attrs, &variant.field_type);
+        }
+
+        if self.extension.is_some() {
+            schema.extension_marker();
+        }
+
+        schema.into_tokens(&self.generics)
+    }
+}
+
+/// Lower the fallback match arm rejecting unknown alternatives into a [`TokenStream`].
+fn unknown_tokens() -> TokenStream {
+    quote! {
+        actual => Err(::der::Error::new(
+            ::der::ErrorKind::TagUnexpected {
+                expected: None,
+                actual
+            },
+            reader.position()
+        ).into()
+        ),
+    }
+}
+
+/// Lower a range of `CONTEXT-SPECIFIC` tag numbers into a [`TokenStream`].
+fn range_tokens(constructed: bool, start: TagNumber, end: TagNumber) -> TokenStream {
+    let start = start.to_tokens();
+    let end = end.to_tokens();
+    quote!(::der::ContextSpecificRange::new(#constructed, #start..=#end))
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 mod tests {
@@ -70,6 +70,9 @@ pub(super) struct ChoiceVariant {
 
     /// Tag for the ASN.1 type.
     pub(super) tag: TagOrPath,
+
+    /// Type of the variant's field.
+    pub(super) field_type: Type,
 }
 
 impl ChoiceVariant {
@@ -78,23 +81,43 @@ impl ChoiceVariant {
         let ident = input.ident.clone();
         let attrs = FieldAttrs::parse(&input.attrs, type_attrs)?;
 
-        if attrs.extensible {
-            abort!(&ident, "`extensible` is not allowed on CHOICE");
+        if attrs.size.is_some() {
+            abort!(&ident, "`size` is not allowed on CHOICE");
+        }
+
+        if attrs.lazy.is_some() {
+            abort!(&ident, "`lazy` is not allowed on CHOICE");
+        }
+
+        if attrs.skip {
+            abort!(&ident, "`skip` is not allowed on CHOICE");
+        }
+
+        if attrs.version || attrs.min_version.is_some() {
+            abort!(
+                &ident,
+                "`version` and `min_version` are not allowed on CHOICE"
+            );
         }
 
         // Validate that variant is a 1-element tuple struct
-        match &input.fields {
+        let field_type = match &input.fields {
             // TODO(tarcieri): handle 0 bindings for ASN.1 NULL
-            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => (),
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => fields.unnamed[0].ty.clone(),
             _ => abort!(&ident, "enum variant must be a 1-element tuple struct"),
-        }
+        };
 
         let tag = match attrs.tag()? {
             Some(x) => x.into(),
             None => input.try_into()?,
         };
 
-        Ok(Self { ident, attrs, tag })
+        Ok(Self {
+            ident,
+            attrs,
+            tag,
+            field_type,
+        })
     }
 
     /// Derive a match arm of the impl body for `der::Decode<'_>`.
@@ -173,6 +196,104 @@ impl ChoiceVariant {
     }
 }
 
+/// "IR" for the variant of an extensible `Choice` holding its unknown
+/// alternatives, marked with `#[asn1(extensible = "true")]`.
+pub(super) struct ExtensionVariant {
+    /// Variant name.
+    pub(super) ident: Ident,
+}
+
+impl ExtensionVariant {
+    /// Create a new [`ExtensionVariant`] from the input [`Variant`], if it's
+    /// marked as `extensible`.
+    pub(super) fn new(input: &Variant, type_attrs: &TypeAttrs) -> syn::Result<Option<Self>> {
+        let ident = input.ident.clone();
+        let attrs = FieldAttrs::parse(&input.attrs, type_attrs)?;
+
+        if !attrs.extensible {
+            return Ok(None);
+        }
+
+        if attrs.asn1_type.is_some()
+            || attrs.class_num.is_some()
+            || attrs.constructed
+            || attrs.should_deref
+            || attrs.is_optional()
+        {
+            abort!(
+                &ident,
+                "`extensible` variant holds the unknown alternatives, and can't be tagged, typed or optional"
+            );
+        }
+
+        match &input.fields {
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => Ok(Some(Self { ident })),
+            _ => abort!(&ident, "enum variant must be a 1-element tuple struct"),
+        }
+    }
+
+    /// Derive the fallback match arm of the impl body for `der::Decode<'_>`.
+    pub(super) fn to_decode_tokens(&self) -> TokenStream {
+        self.to_unknown_tokens(quote!(reader.decode()?))
+    }
+
+    /// Derive the fallback match arm of the impl body for `der::DecodeValue<'_>`.
+    pub(super) fn to_decode_value_tokens(&self) -> TokenStream {
+        self.to_unknown_tokens(quote!(
+            <::der::AnyRef<'_> as ::der::DecodeValue<'_>>::decode_value(reader, header)?
+        ))
+    }
+
+    /// Derive a fallback match arm passing the TLV read by `decoder` to
+    /// `Reader::decode_unknown_alternative`.
+    fn to_unknown_tokens(&self, decoder: TokenStream) -> TokenStream {
+        let ident = &self.ident;
+
+        quote! {
+            actual => {
+                let position = reader.position();
+                let tlv: ::der::AnyRef<'_> = #decoder;
+
+                if reader.decode_unknown_alternative(tlv)? {
+                    Ok(Self::#ident(tlv.into()))
+                } else {
+                    Err(::der::Error::new(
+                        ::der::ErrorKind::TagUnexpected {
+                            expected: None,
+                            actual
+                        },
+                        position
+                    ).into())
+                }
+            }
+        }
+    }
+
+    /// Derive a match arm for the impl body for `der::EncodeValue::encode_value`.
+    pub(super) fn to_encode_value_tokens(&self) -> TokenStream {
+        let ident = &self.ident;
+        quote! {
+            Self::#ident(variant) => variant.encode_value(encoder),
+        }
+    }
+
+    /// Derive a match arm for the impl body for `der::EncodeValue::value_len`.
+    pub(super) fn to_value_len_tokens(&self) -> TokenStream {
+        let ident = &self.ident;
+        quote! {
+            Self::#ident(variant) => variant.value_len(),
+        }
+    }
+
+    /// Derive a match arm for the impl body for `der::Tagged::tag`.
+    pub(super) fn to_tagged_tokens(&self) -> TokenStream {
+        let ident = &self.ident;
+        quote! {
+            Self::#ident(variant) => ::der::Tagged::tag(variant),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::ChoiceVariant;
@@ -182,14 +303,19 @@ mod tests {
     };
     use proc_macro2::Span;
     use quote::quote;
-    use syn::Ident;
+    use syn::{Ident, parse_quote};
 
     #[test]
     fn simple() {
         let ident = Ident::new("ExampleVariant", Span::call_site());
         let attrs = FieldAttrs::default();
         let tag = Tag::Universal(Asn1Type::Utf8String).into();
-        let variant = ChoiceVariant { ident, attrs, tag };
+        let variant = ChoiceVariant {
+            ident,
+            attrs,
+            tag,
+            field_type: parse_quote!(()),
+        };
 
         assert_eq!(
             variant.to_decode_tokens().to_string(),
@@ -244,7 +370,12 @@ mod tests {
             ..Default::default()
         };
         let tag = Tag::Universal(Asn1Type::Utf8String).into();
-        let variant = ChoiceVariant { ident, attrs, tag };
+        let variant = ChoiceVariant {
+            ident,
+            attrs,
+            tag,
+            field_type: parse_quote!(()),
+        };
 
         assert_eq!(
             variant.to_decode_tokens().to_string(),
@@ -299,7 +430,12 @@ mod tests {
                     number: TagNumber(tag_number),
                 });
 
-                let variant = ChoiceVariant { ident, attrs, tag };
+                let variant = ChoiceVariant {
+                    ident,
+                    attrs,
+                    tag,
+                    field_type: parse_quote!(()),
+                };
                 let tag_number = TagNumber(tag_number).to_tokens();
 
                 assert_eq!(
@@ -384,7 +520,12 @@ mod tests {
                     number: TagNumber(tag_number),
                 });
 
-                let variant = ChoiceVariant { ident, attrs, tag };
+                let variant = ChoiceVariant {
+                    ident,
+                    attrs,
+                    tag,
+                    field_type: parse_quote!(()),
+                };
                 let tag_number = TagNumber(tag_number).to_tokens();
 
                 assert_eq!(
@@ -2,10 +2,10 @@
 //! the purposes of decoding/encoding ASN.1 `ENUMERATED` types as mapped to
 //! enum variants.
 
-use crate::{ATTR_NAME, ErrorType, default_lifetime};
+use crate::{ATTR_NAME, ErrorType, default_lifetime, schema::SchemaWriter};
 use proc_macro2::TokenStream;
 use quote::{ToTokens, quote};
-use syn::{DeriveInput, Expr, ExprLit, Ident, Lit, LitInt, LitStr, Path, Variant};
+use syn::{DeriveInput, Expr, ExprLit, Fields, Ident, Lit, LitInt, LitStr, Type, Variant};
 
 /// Valid options for the `#[repr]` attribute on `Enumerated` types.
 const REPR_TYPES: &[&str] = &["u8", "u16", "u32"];
@@ -24,6 +24,10 @@ pub(crate) struct DeriveEnumerated {
     /// Variants of this enum.
     variants: Vec<EnumeratedVariant>,
 
+    /// Catch-all variant holding unrecognized values, supplied as
+    /// `#[asn1(other = "...")]`.
+    other: Option<Ident>,
+
     /// Error type for `DecodeValue` implementation.
     error: ErrorType,
 }
@@ -43,6 +47,7 @@ impl DeriveEnumerated {
         let mut repr: Option<Ident> = None;
         let mut integer = false;
         let mut error: Option<ErrorType> = None;
+        let mut other: Option<Ident> = None;
 
         for attr in &input.attrs {
             if attr.path().is_ident(ATTR_NAME) {
@@ -55,12 +60,18 @@ impl DeriveEnumerated {
                             s => abort!(value, format_args!("`type = \"{s}\"` is unsupported")),
                         }
                     } else if meta.path.is_ident("error") {
-                        let path: Path = meta.value()?.parse()?;
-                        error = Some(ErrorType::Custom(path));
+                        error = Some(meta.value()?.parse()?);
+                    } else if meta.path.is_ident("other") {
+                        if other.is_some() {
+                            abort!(&meta.path, "duplicate ASN.1 `other` attribute");
+                        }
+
+                        let value: LitStr = meta.value()?.parse()?;
+                        other = Some(value.parse()?);
                     } else {
                         return Err(syn::Error::new_spanned(
                             &meta.path,
-                            "invalid `asn1` attribute (valid options are `type` and `error`)",
+                            "invalid `asn1` attribute (valid options are `type`, `error`, and `other`)",
                         ));
                     }
 
@@ -90,22 +101,40 @@ impl DeriveEnumerated {
             }
         }
 
-        // Parse enum variants
-        let variants = data
-            .variants
-            .iter()
-            .map(EnumeratedVariant::new)
-            .collect::<syn::Result<_>>()?;
+        let repr = repr.ok_or_else(|| {
+            syn::Error::new_spanned(
+                &input.ident,
+                format_args!("no `#[repr]` attribute on enum: must be one of {REPR_TYPES:?}"),
+            )
+        })?;
+
+        // Parse enum variants, setting aside the catch-all variant (if any)
+        let mut variants = Vec::new();
+        let mut other_found = false;
+
+        for variant in &data.variants {
+            if other.as_ref() == Some(&variant.ident) {
+                validate_other_variant(variant, &repr)?;
+                other_found = true;
+            } else {
+                variants.push(EnumeratedVariant::new(variant)?);
+            }
+        }
+
+        if let Some(other) = &other {
+            if !other_found {
+                abort!(
+                    other,
+                    format_args!("no variant named `{other}` for `#[asn1(other = \"...\")]`"),
+                );
+            }
+        }
 
         Ok(Self {
             ident: input.ident.clone(),
-            repr: repr.ok_or_else(|| {
-                syn::Error::new_spanned(
-                    &input.ident,
-                    format_args!("no `#[repr]` attribute on enum: must be one of {REPR_TYPES:?}"),
-                )
-            })?,
+            repr,
             variants,
+            other,
             integer,
             error: error.unwrap_or_default(),
         })
@@ -128,7 +157,49 @@ impl DeriveEnumerated {
 
         let error = self.error.to_token_stream();
 
+        // Without a catch-all variant the enum is C-like and can be cast
+        let (to_repr, unknown_arm) = match &self.other {
+            Some(other) => {
+                let to_repr_body = self.variants.iter().map(|variant| {
+                    let ident = &variant.ident;
+                    let discriminant = &variant.discriminant;
+                    quote! { Self::#ident => #discriminant, }
+                });
+
+                (
+                    quote! {
+                        match *self {
+                            #(#to_repr_body)*
+                            Self::#other(n) => n,
+                        }
+                    },
+                    quote! { n => Ok(Self::#other(n)) },
+                )
+            }
+            None => (
+                quote! { *self as #repr },
+                quote! { _ => Err(#tag.value_error().to_error().into()) },
+            ),
+        };
+
+        let kind = if self.integer {
+            "INTEGER"
+        } else {
+            "ENUMERATED"
+        };
+        let mut schema = SchemaWriter::new(ident, &Default::default(), kind);
+        for variant in &self.variants {
+            schema.named_number(&variant.ident, &variant.discriminant);
+        }
+        // Unrecognized values are preserved, i.e. the enumeration is extensible
+        if self.other.is_some() && !self.integer {
+            schema.extension_marker();
+        }
+        let schema = schema.into_tokens(&Default::default());
+
         quote! {
+            #schema
+
             impl<#default_lifetime> ::der::DecodeValue<#default_lifetime> for #ident {
                 type Error = #error;
 
@@ -142,11 +213,13 @@ impl DeriveEnumerated {
 
             impl ::der::EncodeValue for #ident {
                 fn value_len(&self) -> ::der::Result<::der::Length> {
-                    ::der::EncodeValue::value_len(&(*self as #repr))
+                    let value: #repr = #to_repr;
+                    ::der::EncodeValue::value_len(&value)
                 }
 
                 fn encode_value(&self, encoder: &mut impl ::der::Writer) -> ::der::Result<()> {
-                    ::der::EncodeValue::encode_value(&(*self as #repr), encoder)
+                    let value: #repr = #to_repr;
+                    ::der::EncodeValue::encode_value(&value, encoder)
                 }
             }
 
@@ -160,7 +233,7 @@ impl DeriveEnumerated {
                 fn try_from(n: #repr) -> ::core::result::Result<Self, #error> {
                     match n {
                         #(#try_from_body)*
-                        _ => Err(#tag.value_error().to_error().into())
+                        #unknown_arm
                     }
                 }
             }
@@ -168,6 +241,35 @@ impl DeriveEnumerated {
     }
 }
 
+/// Ensure the catch-all variant named by `#[asn1(other = "...")]` is a
+/// 1-tuple variant wrapping the `#[repr]` type.
+fn validate_other_variant(variant: &Variant, repr: &Ident) -> syn::Result<()> {
+    if let Some((_, discriminant)) = &variant.discriminant {
+        abort!(
+            discriminant,
+            "`#[asn1(other = \"...\")]` variant must not have a discriminant"
+        );
+    }
+
+    let field_type = match &variant.fields {
+        Fields::Unnamed(fields) if fields.unnamed.len() == 1 => &fields.unnamed[0].ty,
+        _ => abort!(
+            variant,
+            format_args!("`#[asn1(other = \"...\")]` variant must be a 1-tuple wrapping `{repr}`"),
+        ),
+    };
+
+    match field_type {
+        Type::Path(path) if path.qself.is_none() && path.path.is_ident(repr) => Ok(()),
+        _ => abort!(
+            field_type,
+            format_args!(
+                "`#[asn1(other = \"...\")]` variant must wrap the `#[repr]` type `{repr}`"
+            ),
+        ),
+    }
+}
+
 /// "IR" for a variant of a derived `Enumerated`.
 pub struct EnumeratedVariant {
     /// Variant name.
@@ -257,4 +359,55 @@ mod tests {
         assert_eq!(key_compromise.ident, "CaCompromise");
         assert_eq!(key_compromise.discriminant.to_string(), "2");
     }
+
+    /// `PKIStatus` with a catch-all variant for unrecognized values.
+    #[test]
+    fn pkistatus_other_example() {
+        let input = parse_quote! {
+            #[asn1(type = "INTEGER", other = "Unknown")]
+            #[repr(u32)]
+            pub enum PkiStatus {
+                Accepted = 0,
+                GrantedWithMods = 1,
+                Rejection = 2,
+                Unknown(u32),
+            }
+        };
+
+        let ir = DeriveEnumerated::new(input).unwrap();
+        assert_eq!(ir.ident, "PkiStatus");
+        assert!(ir.integer);
+        assert_eq!(ir.variants.len(), 3);
+        assert_eq!(ir.other.unwrap(), "Unknown");
+    }
+
+    #[test]
+    fn other_must_exist() {
+        let input = parse_quote! {
+            #[asn1(other = "Missing")]
+            #[repr(u8)]
+            pub enum Example {
+                A = 0,
+            }
+        };
+
+        assert!(DeriveEnumerated::new(input).is_err());
+    }
+    #[test]
+    fn other_must_wrap_repr() {
+        let input = parse_quote! {
+            #[asn1(other = "Unknown")]
+            #[repr(u8)]
+            pub enum Example {
+                A = 0,
+                Unknown(u32),
+            }
+        };
+
+        let err = DeriveEnumerated::new(input).err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "`#[asn1(other = \"...\")]` variant must wrap the `#[repr]` type `u8`"
+        );
+    }
 }
@@ -50,7 +50,13 @@
 //! By default generated `Decode` / `DecodeValue` implementations generated by macros
 //! from this crate use `der::Error` as the generic `Error` parameter, but it's
 //! possible to use a custom error type that implements `From<der::Error>` by using
-//! this attribute.
+//! this attribute, e.g. `#[asn1(error = MyError)]` or `#[asn1(error = "MyError")]`.
+//! It's supported by the [`Sequence`], [`DecodeValue`], [`Choice`],
+//! [`Enumerated`] and [`BitString`] derives.
+//!
+//! Errors of nested types are converted into the custom error type with `From`,
+//! so a `SEQUENCE` whose fields have their own custom error types can surface
+//! them as long as its error type impls `From` for each of them.
 //!
 //! Note that [`Choice`] puts more restrictions on the error type: during decoding
 //! for each enum variant the type in its `#[asn1(type = "...")]` attribute (let's
@@ -60,6 +66,50 @@
 //! `From<<U as TryFrom<T>>::Error>`. Since `U` and `T` types are usually the same
 //! implementing `From<Infallible>` should do it.
 //!
+//! ### `#[asn1(self_test)]` attribute: generated round-trip tests
+//!
+//! This attribute emits a `#[cfg(test)]` module alongside the derived impls,
+//! with tests which encode values of the type, decode them back and check the
+//! result is equal to the original value. This catches mistakes such as
+//! mismatched tags or optionality between the encoder and decoder.
+//!
+//! The values which are checked are supplied by the type's `Default` impl for
+//! `#[asn1(self_test)]`, and can be chosen explicitly with
+//! `#[asn1(self_test = "default, arbitrary")]`, where `arbitrary` checks a few
+//! hundred values generated from deterministic pseudorandom data by the
+//! type's `arbitrary::Arbitrary` impl, skipping values which fail to encode.
+//! The crate must then depend on `arbitrary` (e.g. as a dev-dependency).
+//!
+//! The type must also impl `Debug` and `PartialEq`, and must not have generic
+//! type or const parameters.
+//!
+//! ### `#[asn1(owned = "...")]` attribute: borrowed and owned struct pairs
+//!
+//! This attribute can be added to a borrowed struct deriving [`Sequence`],
+//! e.g. `FooRef<'a>`, to generate conversions to and from its owned
+//! counterpart with identically named fields, e.g. `Foo`:
+//!
+//! - `der::referenced::RefToOwned` for `FooRef<'a>`
+//! - `der::referenced::OwnedToRef` for `Foo`
+//! - `From<FooRef<'a>> for Foo` and `From<&'a Foo> for FooRef<'a>`
+//!
+//! Each field is converted using the same traits, which are impl'd by the
+//! borrowed types in `der` along with types which don't borrow data, such as
+//! `ObjectIdentifier` and integers.
+//!
+//! The borrowed struct must have a single lifetime parameter and no other
+//! generic parameters.
+//!
+//! ### `#[asn1(encode_fixed)]` attribute: statically bounded encodings
+//!
+//! This attribute can be added to a struct deriving [`Sequence`] to impl
+//! `der::EncodeFixed` for it, computing its `MAX_ENCODED_LEN` from the ones
+//! of its fields at compile time. Values of the struct can then be encoded
+//! to stack-allocated arrays using `EncodeFixed::encode_to_array`.
+//!
+//! The type of every field must impl `der::EncodeFixed`, and `CONTEXT-SPECIFIC`
+//! and other tagged fields are accounted for according to their tagging mode.
+//!
 //! ## Field-level attributes
 //!
 //! The following attributes can be added to either the fields of a particular
@@ -83,10 +133,80 @@
 //! skip over unrecognized lower-numbered `CONTEXT-SPECIFIC` fields when
 //! looking for a particular field of a struct.
 //!
+//! Unknown extensions are rejected unless decoding with a `der::HookedReader`,
+//! which passes them to a `der::DecodeUnknown` hook.
+//!
+//! On a variant of an `enum` deriving [`Choice`], it marks the `CHOICE` as
+//! extensible. That variant must be untagged and hold an `AnyRef` or `Any`,
+//! which receives the unknown alternatives accepted by a `der::DecodeUnknown`
+//! hook. Without a hook, unknown alternatives are still rejected.
+//!
+//! ### `#[asn1(lazy = "...")]` attribute: on-demand decoding
+//!
+//! This attribute can be applied to the fields of `struct` types deriving
+//! [`Sequence`] whose type is `AnyRef` or `Any` (or an `Option` of them),
+//! which store the raw TLV of the field instead of decoding it up front.
+//!
+//! The value is the type the field is decoded as, e.g.
+//! `#[asn1(lazy = "Certificate")]`. An accessor named after the field,
+//! e.g. `decode_certificate`, is generated to decode it on demand. The
+//! field is written in the schema with this type.
+//!
+//! It can't be combined with `type`, `default`, `size` or `IMPLICIT` tagging.
+//!
+//! ### `#[asn1(min_version = "...")]` attribute: fields added in later versions
+//!
+//! This attribute can be applied to the fields of `struct` types deriving
+//! [`Sequence`] which were appended in a later version of a protocol, in
+//! combination with a version discriminator field marked `#[asn1(version)]`
+//! preceding them, e.g. the `version` field of a PKCS#8 `OneAsymmetricKey`.
+//!
+//! The value is an expression compared with the value of the version field
+//! using its `PartialOrd` impl, e.g. `#[asn1(min_version = "2")]` or
+//! `#[asn1(min_version = "Version::V2")]`. The field is omitted when encoding
+//! a version lower than this one regardless of its value, and isn't decoded
+//! from such a version, in which case it's rejected as trailing data.
+//!
+//! Fields with a `min_version` must be `OPTIONAL` or have a `default`, which
+//! is their value when decoding a lower version.
+//!
 //! ### `#[asn1(optional = "true")]` attribute: support for `OPTIONAL` fields
 //!
 //! This attribute explicitly annotates a field as `OPTIONAL`.
 //!
+//! Fields of `struct` types deriving [`Sequence`] whose type is an `Option`
+//! are `OPTIONAL` even without this attribute: when decoding, the field is
+//! present if the tag of the next element matches it, and absent otherwise.
+//! This requires the tags to be unambiguous, so an `OPTIONAL` or `DEFAULT`
+//! field is rejected if any of the following fields, up to and including the
+//! next mandatory one, has the same tag. Tagged fields are compared by class
+//! and number, and untagged ones by universal tag, e.g. `u8` and `UintRef`
+//! fields are both `INTEGER`s. The tags of untagged fields are determined from
+//! their `type` attribute or the name of their type, so fields of other types,
+//! e.g. `CHOICE`s, are only detected as ambiguous with fields of the same type.
+//!
+//! ### `#[asn1(size = "...")]` attribute: `SIZE` constraints
+//!
+//! This attribute can be applied to the fields of `struct` types deriving
+//! [`Sequence`] to constrain the size of e.g. an `OCTET STRING`, `UTF8String`,
+//! or `SEQUENCE OF`. Values whose size falls outside the given range are
+//! rejected at decode time with `ErrorKind::Length`.
+//!
+//! The value is either an exact size, e.g. `#[asn1(size = "16")]`, or an
+//! inclusive range, e.g. `#[asn1(size = "1..=64")]`, `#[asn1(size = "1..")]`.
+//! See `der::Size` for the types which support this attribute.
+//!
+//! ### `#[asn1(skip)]` attribute: fields absent from the encoding
+//!
+//! This attribute can be applied to the fields of `struct` types deriving
+//! [`Sequence`] which aren't part of the encoding, e.g. caches derived from
+//! the other fields. The field is ignored when encoding, and populated using
+//! its `Default` impl when decoding, or the function supplied with the
+//! `default` attribute, e.g. `#[asn1(skip, default = "Cache::empty")]`.
+//!
+//! Skipped fields are also ignored by [`ValueOrd`], and can't be combined with
+//! any other attribute.
+//!
 //! ### `#[asn1(type = "...")]` attribute: ASN.1 type declaration
 //!
 //! This attribute can be used to specify the ASN.1 type for a particular
@@ -154,6 +274,9 @@ mod attributes;
 mod bitstring;
 mod choice;
 mod enumerated;
+mod oid_choice;
+mod schema;
+mod self_test;
 mod sequence;
 mod tag;
 mod value_ord;
@@ -164,6 +287,7 @@ use crate::{
     bitstring::DeriveBitString,
     choice::DeriveChoice,
     enumerated::DeriveEnumerated,
+    oid_choice::DeriveOidChoice,
     sequence::DeriveSequence,
     tag::{Tag, TagMode, TagNumber},
     value_ord::DeriveValueOrd,
@@ -205,6 +329,14 @@ fn default_lifetime() -> Lifetime {
 /// }
 /// ```
 ///
+/// # Tag set
+///
+/// The tags of the alternatives are exposed as an inherent `TAG_SET`
+/// constant of type [`TagSet`][5], which `Choice::can_decode` is implemented
+/// in terms of. Consecutively numbered `CONTEXT-SPECIFIC` alternatives of the
+/// same form are collapsed into ranges. Wrapper types can use the constant to
+/// dispatch on the tag of a value without duplicating the list of tags.
+///
 /// # `#[asn1(type = "...")]` attribute
 ///
 /// See [toplevel documentation for the `der_derive` crate][4] for more
@@ -214,6 +346,7 @@ fn default_lifetime() -> Lifetime {
 /// [2]: https://docs.rs/der/latest/der/trait.Decode.html
 /// [3]: https://docs.rs/der/latest/der/trait.Encode.html
 /// [4]: https://docs.rs/der_derive/
+/// [5]: https://docs.rs/der/latest/der/struct.TagSet.html
 #[proc_macro_derive(Choice, attributes(asn1))]
 pub fn derive_choice(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
@@ -252,6 +385,27 @@ pub fn derive_choice(input: TokenStream) -> TokenStream {
 ///
 /// Note that the derive macro will write a `TryFrom<...>` impl for the
 /// provided `#[repr]`, which is used by the decoder.
+///
+/// # `#[asn1(other = "...")]` attribute
+///
+/// By default decoding fails on values which don't correspond to any variant.
+/// Protocols which need to preserve unrecognized values can name a catch-all
+/// 1-tuple variant wrapping the `#[repr]` type, which will receive the raw
+/// value when decoding and re-encode it as-is:
+///
+/// ```ignore
+/// use der::Enumerated;
+///
+/// #[derive(Enumerated, Copy, Clone, Debug, Eq, PartialEq)]
+/// #[asn1(type = "INTEGER", other = "Unknown")]
+/// #[repr(u32)]
+/// pub enum PkiStatus {
+///     Accepted = 0,
+///     GrantedWithMods = 1,
+///     Rejection = 2,
+///     Unknown(u32),
+/// }
+/// ```
 #[proc_macro_derive(Enumerated, attributes(asn1))]
 pub fn derive_enumerated(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
@@ -261,6 +415,53 @@ pub fn derive_enumerated(input: TokenStream) -> TokenStream {
     }
 }
 
+/// Derive the [`DecodeValue`][1], [`EncodeValue`][2], [`Sequence`][3] traits on an `enum`
+/// representing an `OBJECT IDENTIFIER` followed by a value whose type is selected by it.
+///
+/// Many structures have the form of X.501 `AttributeTypeAndValue`, i.e. a `SEQUENCE` of an
+/// OID and an `ANY DEFINED BY` it. Each variant of the enum is a 1-tuple wrapping the type
+/// of the value, and declares the OID selecting it with `#[asn1(oid = "...")]`, given either
+/// in dotted decimal notation or as the path to an `ObjectIdentifier` constant.
+///
+/// Values with an unrecognized OID are rejected with `ErrorKind::OidUnknown` unless a
+/// catch-all 2-tuple variant holding the OID and value is named with
+/// `#[asn1(other = "...")]`.
+///
+/// An inherent `oid` method returning the OID of a value is also generated.
+///
+/// # Usage
+///
+/// ```ignore
+/// use der::{
+///     OidChoice,
+///     asn1::{AnyRef, ObjectIdentifier, PrintableStringRef, Utf8StringRef},
+/// };
+///
+/// #[derive(OidChoice)]
+/// #[asn1(other = "Other")]
+/// pub enum AttributeTypeAndValue<'a> {
+///     #[asn1(oid = "2.5.4.3")]
+///     CommonName(Utf8StringRef<'a>),
+///
+///     #[asn1(oid = "2.5.4.6")]
+///     Country(PrintableStringRef<'a>),
+///
+///     Other(ObjectIdentifier, AnyRef<'a>),
+/// }
+/// ```
+///
+/// [1]: https://docs.rs/der/latest/der/trait.DecodeValue.html
+/// [2]: https://docs.rs/der/latest/der/trait.EncodeValue.html
+/// [3]: https://docs.rs/der/latest/der/trait.Sequence.html
+#[proc_macro_derive(OidChoice, attributes(asn1))]
+pub fn derive_oid_choice(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match DeriveOidChoice::new(input) {
+        Ok(t) => t.to_tokens().into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
 /// Derive the [`DecodeValue`][1], [`EncodeValue`][2], [`Sequence`][3] traits on a `struct`.
 ///
 /// This custom derive macro can be used to automatically impl the
@@ -0,0 +1,390 @@
+//! Support for deriving the `DecodeValue`, `EncodeValue` and `Sequence`
+//! traits on enums representing an `OBJECT IDENTIFIER` followed by a value
+//! whose type is selected by it, as in X.501 `AttributeTypeAndValue`.
+
+use crate::{ATTR_NAME, ErrorType, default_lifetime, schema::SchemaWriter};
+use proc_macro2::TokenStream;
+use quote::{ToTokens, quote};
+use syn::{
+    DeriveInput, Fields, GenericParam, Generics, Ident, Lifetime, LifetimeParam, LitStr, Path,
+    Variant,
+};
+
+/// Derive the `OidChoice` traits for an enum.
+pub(crate) struct DeriveOidChoice {
+    /// Name of the enum type.
+    ident: Ident,
+
+    /// Generics of the enum.
+    generics: Generics,
+
+    /// Variants of this enum selected by an OID.
+    variants: Vec<OidChoiceVariant>,
+
+    /// Catch-all 2-tuple variant holding the OID and value of unrecognized
+    /// alternatives, supplied as `#[asn1(other = "...")]`.
+    other: Option<Ident>,
+
+    /// Error type for `DecodeValue` implementation.
+    error: ErrorType,
+}
+
+impl DeriveOidChoice {
+    /// Parse [`DeriveInput`].
+    pub fn new(input: DeriveInput) -> syn::Result<Self> {
+        let data = match input.data {
+            syn::Data::Enum(data) => data,
+            _ => abort!(
+                input.ident,
+                "can't derive `OidChoice` on this type: only `enum` types are allowed",
+            ),
+        };
+
+        let mut error: Option<ErrorType> = None;
+        let mut other: Option<Ident> = None;
+
+        for attr in &input.attrs {
+            if attr.path().is_ident(ATTR_NAME) {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("error") {
+                        if error.is_some() {
+                            abort!(&meta.path, "duplicate ASN.1 `error` attribute");
+                        }
+
+                        error = Some(meta.value()?.parse()?);
+                    } else if meta.path.is_ident("other") {
+                        if other.is_some() {
+                            abort!(&meta.path, "duplicate ASN.1 `other` attribute");
+                        }
+
+                        let value: LitStr = meta.value()?.parse()?;
+                        other = Some(value.parse()?);
+                    } else {
+                        return Err(syn::Error::new_spanned(
+                            &meta.path,
+                            "invalid `asn1` attribute (valid options are `error` and `other`)",
+                        ));
+                    }
+
+                    Ok(())
+                })?;
+            }
+        }
+
+        // Parse enum variants, setting aside the catch-all variant (if any)
+        let mut variants = Vec::new();
+        let mut other_found = false;
+
+        for variant in &data.variants {
+            if other.as_ref() == Some(&variant.ident) {
+                validate_other_variant(variant)?;
+                other_found = true;
+            } else {
+                variants.push(OidChoiceVariant::new(variant)?);
+            }
+        }
+
+        if let Some(other) = &other {
+            if !other_found {
+                abort!(
+                    other,
+                    format_args!("no variant named `{other}` for `#[asn1(other = \"...\")]`"),
+                );
+            }
+        }
+
+        Ok(Self {
+            ident: input.ident,
+            generics: input.generics,
+            variants,
+            other,
+            error: error.unwrap_or_default(),
+        })
+    }
+
+    /// Use the first lifetime parameter as the lifetime of `DecodeValue`,
+    /// inserting one if the enum has none.
+    fn calc_lifetime(&self) -> (Generics, Lifetime) {
+        let mut generics = self.generics.clone();
+        let lifetime = generics
+            .lifetimes()
+            .next()
+            .map(|lt| lt.lifetime.clone())
+            .unwrap_or_else(|| {
+                let lt = default_lifetime();
+                generics
+                    .params
+                    .insert(0, GenericParam::Lifetime(LifetimeParam::new(lt.clone())));
+                lt
+            });
+
+        (generics, lifetime)
+    }
+
+    /// Lower the derived output into a [`TokenStream`].
+    pub fn to_tokens(&self) -> TokenStream {
+        let ident = &self.ident;
+        let (der_generics, lifetime) = self.calc_lifetime();
+        let (_, ty_generics, where_clause) = self.generics.split_for_impl();
+        let (impl_generics, _, _) = der_generics.split_for_impl();
+        let (encode_impl_generics, _, _) = self.generics.split_for_impl();
+        let error = self.error.to_token_stream();
+
+        let mut decode_body = Vec::new();
+        let mut oid_body = Vec::new();
+        let mut value_len_body = Vec::new();
+        let mut encode_body = Vec::new();
+
+        for variant in &self.variants {
+            let variant_ident = &variant.ident;
+            let oid = variant.oid.to_tokens();
+
+            decode_body.push(quote! {
+                if oid == #oid {
+                    return Ok(Self::#variant_ident(reader.decode()?));
+                }
+            });
+            oid_body.push(quote!(Self::#variant_ident(_) => #oid,));
+            value_len_body.push(quote! {
+                Self::#variant_ident(value) => #oid.encoded_len()? + value.encoded_len()?,
+            });
+            encode_body.push(quote! {
+                Self::#variant_ident(value) => {
+                    #oid.encode(writer)?;
+                    value.encode(writer)
+                }
+            });
+        }
+
+        let unknown = match &self.other {
+            Some(other) => {
+                oid_body.push(quote!(Self::#other(oid, _) => *oid,));
+                value_len_body.push(quote! {
+                    Self::#other(oid, value) => oid.encoded_len()? + value.encoded_len()?,
+                });
+                encode_body.push(quote! {
+                    Self::#other(oid, value) => {
+                        oid.encode(writer)?;
+                        value.encode(writer)
+                    }
+                });
+                quote!(Ok(Self::#other(oid, reader.decode()?)))
+            }
+            None => quote!(Err(reader
+                .error(::der::ErrorKind::OidUnknown { oid })
+                .into())),
+        };
+
+        let mut schema = SchemaWriter::new(ident, &self.generics, "SEQUENCE");
+        schema.text_component("type OBJECT IDENTIFIER");
+        schema.text_component("value ANY DEFINED BY type");
+        let schema = schema.into_tokens(&self.generics);
+
+        quote! {
+            impl #encode_impl_generics #ident #ty_generics #where_clause {
+                /// Get the `OBJECT IDENTIFIER` selecting the alternative of this value.
+                pub fn oid(&self) -> ::der::asn1::ObjectIdentifier {
+                    match self {
+                        #(#oid_body)*
+                    }
+                }
+            }
+
+            impl #impl_generics ::der::DecodeValue<#lifetime> for #ident #ty_generics #where_clause {
+                type Error = #error;
+
+                fn decode_value<R: ::der::Reader<#lifetime>>(
+                    reader: &mut R,
+                    _header: ::der::Header,
+                ) -> ::core::result::Result<Self, #error> {
+                    use ::der::Reader as _;
+                    let oid: ::der::asn1::ObjectIdentifier = reader.decode()?;
+                    #(#decode_body)*
+                    #unknown
+                }
+            }
+
+            impl #encode_impl_generics ::der::EncodeValue for #ident #ty_generics #where_clause {
+                fn value_len(&self) -> ::der::Result<::der::Length> {
+                    use ::der::Encode as _;
+                    match self {
+                        #(#value_len_body)*
+                    }
+                }
+
+                fn encode_value(&self, writer: &mut impl ::der::Writer) -> ::der::Result<()> {
+                    use ::der::Encode as _;
+                    match self {
+                        #(#encode_body)*
+                    }
+                }
+            }
+
+            impl #impl_generics ::der::Sequence<#lifetime> for #ident #ty_generics #where_clause {}
+
+            #schema
+        }
+    }
+}
+
+/// Ensure the catch-all variant named by `#[asn1(other = "...")]` is a
+/// 2-tuple variant without an `oid` attribute.
+fn validate_other_variant(variant: &Variant) -> syn::Result<()> {
+    if let Some(attr) = variant
+        .attrs
+        .iter()
+        .find(|attr| attr.path().is_ident(ATTR_NAME))
+    {
+        abort!(
+            attr,
+            "`asn1` attribute is not allowed on the `#[asn1(other = \"...\")]` variant"
+        );
+    }
+
+    match &variant.fields {
+        Fields::Unnamed(fields) if fields.unnamed.len() == 2 => Ok(()),
+        _ => abort!(
+            variant,
+            "`#[asn1(other = \"...\")]` variant must be a 2-tuple of an `ObjectIdentifier` and a value",
+        ),
+    }
+}
+
+/// OID selecting a variant, supplied as `#[asn1(oid = "...")]`.
+#[derive(Clone, Debug)]
+enum VariantOid {
+    /// Dotted decimal notation, e.g. `"2.5.4.3"`.
+    Literal(LitStr),
+
+    /// Path to an `ObjectIdentifier` constant, e.g. `db::rfc4519::CN`.
+    Path(Path),
+}
+
+impl VariantOid {
+    /// Lower into an expression evaluating to the `ObjectIdentifier`.
+    fn to_tokens(&self) -> TokenStream {
+        match self {
+            Self::Literal(lit) => {
+                quote!(const { ::der::asn1::ObjectIdentifier::new_unwrap(#lit) })
+            }
+            Self::Path(path) => quote!(#path),
+        }
+    }
+}
+
+/// "IR" for a variant of a derived `OidChoice`.
+struct OidChoiceVariant {
+    /// Variant name.
+    ident: Ident,
+
+    /// OID selecting this variant.
+    oid: VariantOid,
+}
+
+impl OidChoiceVariant {
+    /// Create a new [`OidChoiceVariant`] from the input [`Variant`].
+    fn new(input: &Variant) -> syn::Result<Self> {
+        let mut oid = None;
+
+        for attr in &input.attrs {
+            if attr.path().is_ident(ATTR_NAME) {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("oid") {
+                        if oid.is_some() {
+                            abort!(&meta.path, "duplicate ASN.1 `oid` attribute");
+                        }
+
+                        let value = meta.value()?;
+                        oid = Some(if value.peek(LitStr) {
+                            VariantOid::Literal(value.parse()?)
+                        } else {
+                            VariantOid::Path(value.parse()?)
+                        });
+                    } else {
+                        return Err(syn::Error::new_spanned(
+                            &meta.path,
+                            "invalid `asn1` attribute (valid option is `oid`)",
+                        ));
+                    }
+
+                    Ok(())
+                })?;
+            }
+        }
+
+        match &input.fields {
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => (),
+            _ => abort!(input, "`OidChoice` variant must be a 1-tuple"),
+        }
+
+        let oid = oid.ok_or_else(|| {
+            syn::Error::new_spanned(input, "`OidChoice` variant has no `#[asn1(oid = \"...\")]`")
+        })?;
+
+        Ok(Self {
+            ident: input.ident.clone(),
+            oid,
+        })
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::{DeriveOidChoice, VariantOid};
+    use syn::parse_quote;
+
+    /// X.501 `AttributeTypeAndValue` with a few attribute types.
+    #[test]
+    fn attribute_type_and_value_example() {
+        let input = parse_quote! {
+            #[asn1(other = "Other")]
+            pub enum AttributeTypeAndValue<'a> {
+                #[asn1(oid = "2.5.4.3")]
+                CommonName(Utf8StringRef<'a>),
+
+                #[asn1(oid = rfc4519::C)]
+                Country(PrintableStringRef<'a>),
+
+                Other(ObjectIdentifier, AnyRef<'a>),
+            }
+        };
+
+        let ir = DeriveOidChoice::new(input).unwrap();
+        assert_eq!(ir.ident, "AttributeTypeAndValue");
+        assert_eq!(ir.variants.len(), 2);
+        assert_eq!(ir.other.unwrap(), "Other");
+
+        assert_eq!(ir.variants[0].ident, "CommonName");
+        assert!(
+            matches!(&ir.variants[0].oid, VariantOid::Literal(lit) if lit.value() == "2.5.4.3")
+        );
+        assert_eq!(ir.variants[1].ident, "Country");
+        assert!(matches!(ir.variants[1].oid, VariantOid::Path(_)));
+    }
+
+    #[test]
+    fn oid_is_required() {
+        let input = parse_quote! {
+            pub enum Example {
+                A(bool),
+            }
+        };
+
+        assert!(DeriveOidChoice::new(input).is_err());
+    }
+
+    #[test]
+    fn other_must_be_2_tuple() {
+        let input = parse_quote! {
+            #[asn1(other = "Other")]
+            pub enum Example<'a> {
+                #[asn1(oid = "1.2.3")]
+                A(bool),
+                Other(AnyRef<'a>),
+            }
+        };
+
+        assert!(DeriveOidChoice::new(input).is_err());
+    }
+}
@@ -0,0 +1,222 @@
+//! Support for emitting `der::schema::Asn1Schema` impls when the `schema`
+//! feature is enabled.
+
+use crate::{FieldAttrs, TagMode, attributes::ClassNum};
+use proc_macro2::{TokenStream, TokenTree};
+use quote::{ToTokens, quote};
+use syn::{Generics, Ident, Type, WherePredicate, parse_quote};
+
+/// Builder for the body of a derived `Asn1Schema::write_definition`.
+pub(crate) struct SchemaWriter {
+    /// Name of the type being defined.
+    ident: Ident,
+
+    /// Statements writing the definition.
+    body: Vec<TokenStream>,
+
+    /// Bounds on the generic types of components referenced by the definition.
+    bounds: Vec<WherePredicate>,
+
+    /// Generic type parameters of the type being defined.
+    type_params: Vec<Ident>,
+
+    /// Number of components written so far.
+    components: usize,
+}
+
+impl SchemaWriter {
+    /// Begin the definition of the given type, e.g. `Name ::= SEQUENCE`.
+    pub fn new(ident: &Ident, generics: &Generics, kind: &str) -> Self {
+        let header = format!("{ident} ::= {kind} {{");
+
+        Self {
+            ident: ident.clone(),
+            body: vec![quote!(w.write_str(#header)?;)],
+            bounds: Vec::new(),
+            type_params: generics.type_params().map(|p| p.ident.clone()).collect(),
+            components: 0,
+        }
+    }
+
+    /// Write the delimiter preceding the next component.
+    fn delimiter(&mut self) {
+        let delimiter = if self.components == 0 {
+            "\n    "
+        } else {
+            ",\n    "
+        };
+        self.body.push(quote!(w.write_str(#delimiter)?;));
+        self.components += 1;
+    }
+
+    /// Write the `...` extensibility marker.
+    pub fn extension_marker(&mut self) {
+        self.delimiter();
+        self.body.push(quote!(w.write_str("...")?;));
+    }
+
+    /// Write a named number, e.g. `keyCompromise(1)`.
+    pub fn named_number(&mut self, ident: &Ident, number: impl ToString) {
+        self.delimiter();
+        let text = format!("{}({})", component_name(ident), number.to_string());
+        self.body.push(quote!(w.write_str(#text)?;));
+    }
+
+    /// Write a component given as text, e.g. `value ANY DEFINED BY type`.
+    pub fn text_component(&mut self, text: &str) {
+        self.delimiter();
+        self.body.push(quote!(w.write_str(#text)?;));
+    }
+
+    /// Write a component of a `SEQUENCE` or alternative of a `CHOICE` with the given type.
+    pub fn component(&mut self, ident: &Ident, attrs: &FieldAttrs, ty: &Type) {
+        self.delimiter();
+
+        let mut prefix = format!("{} ", component_name(ident));
+
+        if let Some(class_num) = &attrs.class_num {
+            let tag = match class_num {
+                ClassNum::ContextSpecific(number) => format!("[{number}]"),
+                ClassNum::Application(number) => format!("[APPLICATION {number}]"),
+                ClassNum::Private(number) => format!("[PRIVATE {number}]"),
+            };
+
+            let mode = match attrs.tag_mode {
+                TagMode::Explicit => "EXPLICIT",
+                TagMode::Implicit => "IMPLICIT",
+            };
+
+            prefix.push_str(&format!("{tag} {mode} "));
+        }
+
+        self.body.push(quote!(w.write_str(#prefix)?;));
+
+        match attrs.asn1_type {
+            Some(asn1_type) => {
+                let asn1_type = asn1_type.to_string();
+                self.body.push(quote!(w.write_str(#asn1_type)?;));
+            }
+            // Bound generic types so they're written using `Asn1Schema` impls. Concrete types
+            // can't be bounded, since types without an impl would then fail to compile, and
+            // bounding e.g. `Vec<Self>` would be cyclic.
+            None if mentions_any(ty.to_token_stream(), &self.type_params) => {
+                self.body
+                    .push(quote!(<#ty as ::der::schema::Asn1Schema>::write_type(w)?;));
+                self.bounds
+                    .push(parse_quote!(#ty: ::der::schema::Asn1Schema));
+            }
+            None => {
+                self.body.push(quote! {
+                    (&&::der::schema::component::Component::<#ty>::new()).write_type(w)?;
+                });
+            }
+        }
+
+        if let Some(default) = &attrs.default {
+            // Render the path without the spaces inserted between tokens
+            let path = default.to_token_stream().to_string().replace(' ', "");
+            let default = format!(" DEFAULT {path}");
+            self.body.push(quote!(w.write_str(#default)?;));
+        } else if attrs.optional || is_option(ty) {
+            self.body.push(quote!(w.write_str(" OPTIONAL")?;));
+        }
+    }
+
+    /// Finish the definition and lower it into an `Asn1Schema` impl.
+    pub fn into_tokens(mut self, generics: &Generics) -> TokenStream {
+        if !cfg!(feature = "schema") {
+            return TokenStream::new();
+        }
+
+        let ident = &self.ident;
+        let name = ident.to_string();
+        let footer = if self.components == 0 { "}" } else { "\n}" };
+        self.body.push(quote!(w.write_str(#footer)?;));
+
+        let mut generics = generics.clone();
+        generics
+            .make_where_clause()
+            .predicates
+            .extend(self.bounds.iter().cloned());
+
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+        let body = &self.body;
+
+        quote! {
+            impl #impl_generics ::der::schema::Asn1Schema for #ident #ty_generics #where_clause {
+                fn write_type(w: &mut dyn ::core::fmt::Write) -> ::core::fmt::Result {
+                    w.write_str(#name)
+                }
+
+                fn write_definition(w: &mut dyn ::core::fmt::Write) -> ::core::fmt::Result {
+                    #[allow(unused_imports)]
+                    use ::der::schema::component::{WriteAsn1Schema as _, WriteTypeName as _};
+                    #(#body)*
+                    Ok(())
+                }
+            }
+        }
+    }
+}
+
+/// Is the given type an `Option`, which makes a component `OPTIONAL` even without the
+/// `optional` attribute?
+pub(crate) fn is_option(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Option"),
+        _ => false,
+    }
+}
+
+/// Do the given tokens mention any of the given identifiers?
+fn mentions_any(tokens: TokenStream, idents: &[Ident]) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Ident(ident) => idents.contains(&ident),
+        TokenTree::Group(group) => mentions_any(group.stream(), idents),
+        _ => false,
+    })
+}
+
+/// Convert a Rust identifier into an ASN.1 component name, i.e. from
+/// `snake_case` or `UpperCamelCase` to `lowerCamelCase`.
+fn component_name(ident: &Ident) -> String {
+    let ident = ident.to_string();
+    let ident = ident.strip_prefix("r#").unwrap_or(&ident);
+    let mut name = String::with_capacity(ident.len());
+    let mut upper = false;
+
+    for c in ident.chars() {
+        if c == '_' {
+            upper = !name.is_empty();
+        } else if upper {
+            name.extend(c.to_uppercase());
+            upper = false;
+        } else if name.is_empty() {
+            name.extend(c.to_lowercase());
+        } else {
+            name.push(c);
+        }
+    }
+
+    name
+}
+
+#[cfg(test)]
+mod tests {
+    use super::component_name;
+    use proc_macro2::Span;
+    use syn::Ident;
+
+    #[test]
+    fn component_names() {
+        let name = |s| component_name(&Ident::new(s, Span::call_site()));
+        assert_eq!(name("tbs_certificate"), "tbsCertificate");
+        assert_eq!(name("UtcTime"), "utcTime");
+        assert_eq!(name("version"), "version");
+        assert_eq!(name("issuer_unique_id"), "issuerUniqueId");
+    }
+}
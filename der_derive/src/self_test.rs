@@ -0,0 +1,161 @@
+//! Support for the `#[asn1(self_test)]` attribute, which emits a test module
+//! checking that values of the derived type round-trip through DER.
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Generics, Ident, LitStr};
+
+/// Sources of the values checked by the generated tests.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(crate) struct SelfTest {
+    /// Check the `Default` value of the type.
+    pub default: bool,
+
+    /// Check values generated by the type's `arbitrary::Arbitrary` impl.
+    pub arbitrary: bool,
+}
+
+impl SelfTest {
+    /// Parse the comma-separated list of sources of a
+    /// `#[asn1(self_test = "...")]` attribute.
+    pub fn parse(lit: &LitStr) -> syn::Result<Self> {
+        let mut self_test = Self::default();
+
+        for source in lit.value().split(',').map(str::trim) {
+            match source {
+                "default" if !self_test.default => self_test.default = true,
+                "arbitrary" if !self_test.arbitrary => self_test.arbitrary = true,
+                _ => abort!(
+                    lit,
+                    "invalid `self_test` sources (valid sources are `default` and `arbitrary`)"
+                ),
+            }
+        }
+
+        Ok(self_test)
+    }
+
+    /// Ensure tests can be emitted for the given type, which must not have
+    /// generic type or const parameters.
+    pub fn check_generics(ident: &Ident, generics: &Generics) -> syn::Result<()> {
+        if generics.type_params().next().is_some() || generics.const_params().next().is_some() {
+            abort!(
+                ident,
+                "`self_test` is not supported on types with generic type or const parameters"
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Lower the test module for the given type into a [`TokenStream`].
+    pub fn to_tokens(self, ident: &Ident, generics: &Generics) -> TokenStream {
+        let lifetimes = generics.lifetimes().count();
+        let static_lifetimes = (0..lifetimes).map(|_| quote!('static));
+        let anonymous_lifetimes = (0..lifetimes).map(|_| quote!('_));
+        let (static_ty, anonymous_ty) = if lifetimes == 0 {
+            (quote!(#ident), quote!(#ident))
+        } else {
+            (
+                quote!(#ident<#(#static_lifetimes),*>),
+                quote!(#ident<#(#anonymous_lifetimes),*>),
+            )
+        };
+
+        let mut tests = Vec::new();
+
+        if self.default {
+            tests.push(quote! {
+                #[test]
+                fn default_round_trip() {
+                    let value = <#static_ty as ::core::default::Default>::default();
+                    let der = encode(&value).expect("failed to encode the default value");
+                    check(&value, &der);
+                }
+            });
+        }
+
+        if self.arbitrary {
+            tests.push(quote! {
+                #[test]
+                fn arbitrary_round_trip() {
+                    // Values may borrow from the data they're generated from
+                    let data: &'static mut [u8] =
+                        Box::leak(std::vec![0u8; ITERATIONS * CHUNK_LEN].into_boxed_slice());
+
+                    // xorshift64
+                    let mut state = 0x9E37_79B9_7F4A_7C15u64;
+
+                    for byte in data.iter_mut() {
+                        state ^= state << 13;
+                        state ^= state >> 7;
+                        state ^= state << 17;
+                        *byte = state.to_le_bytes()[0];
+                    }
+
+                    let mut checked = 0usize;
+
+                    for chunk in data.chunks(CHUNK_LEN) {
+                        let mut u = ::arbitrary::Unstructured::new(chunk);
+                        let Ok(value) =
+                            <#static_ty as ::arbitrary::Arbitrary<'static>>::arbitrary(&mut u)
+                        else {
+                            continue;
+                        };
+
+                        // Arbitrary values may violate the constraints of the type
+                        if let Some(der) = encode(&value) {
+                            check(&value, &der);
+                            checked += 1;
+                        }
+                    }
+
+                    assert!(checked > 0, "no encodable arbitrary values were generated");
+                }
+            });
+        }
+
+        let module = format_ident!("__der_self_test_{}", ident);
+
+        quote! {
+            #[cfg(test)]
+            #[doc(hidden)]
+            #[allow(non_snake_case, dead_code, unused_imports)]
+            mod #module {
+                extern crate std;
+
+                use super::*;
+                use std::{boxed::Box, vec::Vec};
+
+                /// Number of arbitrary values to generate.
+                const ITERATIONS: usize = 256;
+
+                /// Length of the data each arbitrary value is generated from.
+                const CHUNK_LEN: usize = 1024;
+
+                /// Encode the given value, returning `None` if it can't be encoded.
+                fn encode(value: &#static_ty) -> Option<Vec<u8>> {
+                    let len = ::der::Encode::encoded_len(value).ok()?;
+                    let mut buf = std::vec![0u8; usize::try_from(len).ok()?];
+                    ::der::Encode::encode_to_slice(value, &mut buf).ok()?;
+                    Some(buf)
+                }
+
+                /// Check that the given encoding of a value decodes back to it.
+                fn check(value: &#static_ty, der: &[u8]) {
+                    let decoded: #anonymous_ty = match ::der::Decode::from_der(der) {
+                        Ok(decoded) => decoded,
+                        Err(_) => panic!(
+                            "failed to decode {:?} from its encoding {:02X?}",
+                            value, der
+                        ),
+                    };
+
+                    assert_eq!(&decoded, value, "round trip mismatch for encoding {:02X?}", der);
+                }
+
+                #(#tests)*
+            }
+        }
+    }
+}
@@ -3,11 +3,11 @@
 
 mod field;
 
-use crate::{ErrorType, TypeAttrs, default_lifetime};
+use crate::{ErrorType, TypeAttrs, default_lifetime, schema::SchemaWriter, self_test::SelfTest};
 use field::SequenceField;
 use proc_macro2::TokenStream;
 use quote::{ToTokens, quote};
-use syn::{DeriveInput, GenericParam, Generics, Ident, Lifetime, LifetimeParam};
+use syn::{DeriveInput, GenericParam, Generics, Ident, Lifetime, LifetimeParam, Path};
 
 /// Derive the `Sequence` trait for a struct
 pub(crate) struct DeriveSequence {
@@ -20,8 +20,24 @@ pub(crate) struct DeriveSequence {
     /// Fields of the struct.
     fields: Vec<SequenceField>,
 
+    /// Fields of the struct which are absent from the encoding, supplied as
+    /// `#[asn1(skip)]`.
+    skipped: Vec<SequenceField>,
+
+    /// Name of the version discriminator field, supplied as `#[asn1(version)]`.
+    version: Option<Ident>,
+
     /// Error type for `DecodeValue` implementation.
     error: ErrorType,
+
+    /// Round-trip tests to emit, if any.
+    self_test: Option<SelfTest>,
+
+    /// Owned counterpart of this borrowed struct, if any.
+    owned: Option<Path>,
+
+    /// Should `EncodeFixed` be impl'd for this struct?
+    encode_fixed: bool,
 }
 
 impl DeriveSequence {
@@ -37,17 +53,87 @@ impl DeriveSequence {
 
         let type_attrs = TypeAttrs::parse(&input.attrs)?;
 
-        let fields = data
+        let (skipped, fields): (Vec<SequenceField>, Vec<SequenceField>) = data
             .fields
             .iter()
             .map(|field| SequenceField::new(field, &type_attrs))
-            .collect::<syn::Result<_>>()?;
+            .collect::<syn::Result<Vec<_>>>()?
+            .into_iter()
+            .partition(|field| field.attrs.skip);
+
+        // The presence of an `OPTIONAL` or `DEFAULT` field is determined by the
+        // tag of the next element, which can be any of the following fields up
+        // to the next mandatory one
+        for (index, field) in fields.iter().enumerate() {
+            if !field.attrs.is_optional() {
+                continue;
+            }
+
+            let tag = field.element_tag();
+
+            for next in &fields[index + 1..] {
+                if next.element_tag() == tag {
+                    abort!(
+                        &next.ident,
+                        format!(
+                            "the presence of `{}` can't be determined from the tag of the next element: `{}` has the same tag",
+                            field.ident, next.ident
+                        )
+                    );
+                }
+
+                if !next.attrs.is_optional() {
+                    break;
+                }
+            }
+        }
+
+        let mut version = None;
+
+        for (index, field) in fields.iter().enumerate() {
+            if field.attrs.version {
+                if version.is_some() {
+                    abort!(&field.ident, "duplicate `version` field");
+                }
+
+                version = Some(index);
+            }
+
+            if field.attrs.min_version.is_some() && version.is_none_or(|version| version >= index) {
+                abort!(
+                    &field.ident,
+                    "`min_version` fields must follow the field marked `#[asn1(version)]`"
+                );
+            }
+        }
+
+        let version = version.map(|index| fields[index].ident.clone());
+
+        if type_attrs.self_test.is_some() {
+            SelfTest::check_generics(&input.ident, &input.generics)?;
+        }
+
+        if type_attrs.owned.is_some()
+            && (input.generics.lifetimes().count() != 1
+                || input.generics.type_params().next().is_some()
+                || input.generics.const_params().next().is_some())
+        {
+            abort!(
+                input.ident,
+                "`owned` is only supported on structs with a single lifetime parameter and no other generic parameters"
+            );
+        }
 
         Ok(Self {
             ident: input.ident,
             generics: input.generics.clone(),
             fields,
+            skipped,
+            version,
             error: type_attrs.error.clone(),
+            self_test: type_attrs.self_test,
+            owned: type_attrs.owned,
+            encode_fixed: type_attrs.encode_fixed,
         })
     }
 
@@ -97,10 +183,23 @@ impl DeriveSequence {
         let mut decode_result = Vec::new();
 
         for field in &self.fields {
-            decode_body.push(field.to_decode_tokens());
+            decode_body.extend(field.to_unknown_extensions_tokens());
+            decode_body.push(self.to_field_decode_tokens(field));
             decode_result.push(&field.ident);
         }
 
+        // Unknown extensions following the last field
+        if self.fields.iter().any(|field| field.attrs.extensible) {
+            decode_body.push(quote!(reader.decode_unknown_extensions(None)?;));
+        }
+
+        for field in &self.skipped {
+            let ident = &field.ident;
+            let value = field.to_absent_value_tokens();
+            decode_body.push(quote!(let #ident = #value;));
+            decode_result.push(ident);
+        }
+
         let error = self.error.to_token_stream();
 
         quote! {
@@ -122,6 +221,30 @@ impl DeriveSequence {
         }
     }
 
+    /// Derive code for decoding a field, which is only present in the encoding
+    /// of the versions greater than or equal to its `min_version` if any.
+    fn to_field_decode_tokens(&self, field: &SequenceField) -> TokenStream {
+        let decoder = field.to_decode_tokens();
+        let version = &self.version;
+
+        match field.to_version_condition_tokens(quote!(#version)) {
+            Some(condition) => {
+                let ident = &field.ident;
+                let absent = field.to_absent_value_tokens();
+
+                quote! {
+                    let #ident = if #condition {
+                        #decoder
+                        #ident
+                    } else {
+                        #absent
+                    };
+                }
+            }
+            None => decoder,
+        }
+    }
+
     /// Lower the derived output into a [`TokenStream`] for EncodeValue trait impl.
     pub fn to_tokens_encode(&self) -> TokenStream {
         let ident = &self.ident;
@@ -133,9 +256,29 @@ impl DeriveSequence {
         let mut encode_fields = Vec::new();
 
         for field in &self.fields {
-            let field = field.to_encode_tokens();
-            sum_lengths.push(quote!(let len = (len + #field.encoded_len()?)?;));
-            encode_fields.push(quote!(#field.encode(writer)?;));
+            let encoder = field.to_encode_tokens();
+            let version = &self.version;
+
+            match field.to_version_condition_tokens(quote!(self.#version)) {
+                Some(condition) => {
+                    sum_lengths.push(quote! {
+                        let len = if #condition {
+                            (len + #encoder.encoded_len()?)?
+                        } else {
+                            len
+                        };
+                    });
+                    encode_fields.push(quote! {
+                        if #condition {
+                            #encoder.encode(writer)?;
+                        }
+                    });
+                }
+                None => {
+                    sum_lengths.push(quote!(let len = (len + #encoder.encoded_len()?)?;));
+                    encode_fields.push(quote!(#encoder.encode(writer)?;));
+                }
+            }
         }
 
         quote! {
@@ -156,6 +299,30 @@ impl DeriveSequence {
         }
     }
 
+    /// Lower the derived output into a [`TokenStream`] for the EncodeFixed
+    /// trait impl, if requested.
+    fn to_tokens_encode_fixed(&self) -> Option<TokenStream> {
+        if !self.encode_fixed {
+            return None;
+        }
+
+        let ident = &self.ident;
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+        let field_lens = self
+            .fields
+            .iter()
+            .map(|field| field.to_max_encoded_len_tokens());
+
+        Some(quote! {
+            impl #impl_generics ::der::EncodeFixed for #ident #ty_generics #where_clause {
+                const MAX_ENCODED_LEN: usize = ::der::encode_fixed::max_tlv_len(
+                    ::der::Tag::Sequence,
+                    0 #(+ #field_lens)*,
+                );
+            }
+        })
+    }
+
     /// Lower the derived output into a [`TokenStream`] for trait impls:
     /// - EncodeValue
     /// - DecodeValue
@@ -164,12 +331,119 @@ impl DeriveSequence {
         let decode_tokens = self.to_tokens_decode();
         let encode_tokens = self.to_tokens_encode();
         let sequence_trait_tokens = self.to_tokens_sequence_trait();
+        let schema_tokens = self.to_tokens_schema();
+        let self_test_tokens = self
+            .self_test
+            .map(|self_test| self_test.to_tokens(&self.ident, &self.generics));
+        let owned_tokens = self.to_tokens_owned();
+        let lazy_tokens = self.to_tokens_lazy();
+        let encode_fixed_tokens = self.to_tokens_encode_fixed();
 
         quote! {
             #decode_tokens
             #encode_tokens
             #sequence_trait_tokens
+            #schema_tokens
+            #self_test_tokens
+            #owned_tokens
+            #lazy_tokens
+            #encode_fixed_tokens
+        }
+    }
+
+    /// Lower the derived output into a [`TokenStream`] for the accessors decoding
+    /// `lazy` fields on demand, if any.
+    fn to_tokens_lazy(&self) -> Option<TokenStream> {
+        let accessors: Vec<_> = self
+            .fields
+            .iter()
+            .filter_map(|field| field.to_lazy_accessor_tokens(&self.error))
+            .collect();
+
+        if accessors.is_empty() {
+            return None;
         }
+
+        let ident = &self.ident;
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+
+        Some(quote! {
+            impl #impl_generics #ident #ty_generics #where_clause {
+                #(#accessors)*
+            }
+        })
+    }
+
+    /// Lower the derived output into a [`TokenStream`] for the conversions
+    /// between this borrowed struct and its owned counterpart, if any:
+    /// - RefToOwned
+    /// - OwnedToRef
+    /// - From
+    fn to_tokens_owned(&self) -> Option<TokenStream> {
+        let owned = self.owned.as_ref()?;
+        let ident = &self.ident;
+        let lifetime = &self.generics.lifetimes().next()?.lifetime;
+        let fields: Vec<_> = self.fields.iter().map(|field| &field.ident).collect();
+        let skipped: Vec<_> = self.skipped.iter().map(|field| &field.ident).collect();
+        let skipped_values: Vec<_> = self
+            .skipped
+            .iter()
+            .map(|field| field.to_absent_value_tokens())
+            .collect();
+
+        Some(quote! {
+            impl<#lifetime> ::der::referenced::RefToOwned<#lifetime> for #ident<#lifetime> {
+                type Owned = #owned;
+
+                fn ref_to_owned(&self) -> #owned {
+                    #owned {
+                        #(#fields: ::der::referenced::RefToOwned::ref_to_owned(&self.#fields),)*
+                        #(#skipped: #skipped_values,)*
+                    }
+                }
+            }
+
+            impl ::der::referenced::OwnedToRef for #owned {
+                type Borrowed<#lifetime> = #ident<#lifetime>;
+
+                fn owned_to_ref(&self) -> #ident<'_> {
+                    #ident {
+                        #(#fields: ::der::referenced::OwnedToRef::owned_to_ref(&self.#fields),)*
+                        #(#skipped: #skipped_values,)*
+                    }
+                }
+            }
+
+            impl<#lifetime> ::core::convert::From<#ident<#lifetime>> for #owned {
+                fn from(value: #ident<#lifetime>) -> #owned {
+                    ::der::referenced::RefToOwned::ref_to_owned(&value)
+                }
+            }
+
+            impl<#lifetime> ::core::convert::From<&#lifetime #owned> for #ident<#lifetime> {
+                fn from(value: &#lifetime #owned) -> #ident<#lifetime> {
+                    ::der::referenced::OwnedToRef::owned_to_ref(value)
+                }
+            }
+        })
+    }
+
+    /// Lower the derived output into a [`TokenStream`] for the `Asn1Schema` trait impl.
+    fn to_tokens_schema(&self) -> TokenStream {
+        let mut schema = SchemaWriter::new(&self.ident, &self.generics, "SEQUENCE");
+        let mut extensible = false;
+
+        for field in &self.fields {
+            // Fields following the first extensible one are all extensions
+            if field.attrs.extensible && !extensible {
+                schema.extension_marker();
+                extensible = true;
+            }
+
+            schema.component(&field.ident, &field.attrs, &field.schema_type());
+        }
+
+        schema.into_tokens(&self.generics)
     }
 }
 
@@ -178,7 +452,7 @@ impl DeriveSequence {
 #[allow(clippy::bool_assert_comparison)]
 mod tests {
     use super::DeriveSequence;
-    use crate::{Asn1Type, TagMode, attributes::ClassNum};
+    use crate::{Asn1Type, TagMode, attributes::ClassNum, self_test::SelfTest};
     use syn::parse_quote;
 
     /// X.509 SPKI `AlgorithmIdentifier`.
@@ -435,4 +709,335 @@ mod tests {
         );
         assert_eq!(private_ia5_string.attrs.tag_mode, TagMode::Implicit);
     }
+
+    #[test]
+    fn self_test_attribute() {
+        let input = parse_quote! {
+            #[derive(Sequence)]
+            #[asn1(self_test)]
+            pub struct Version {
+                pub version: u8,
+            }
+        };
+
+        let ir = DeriveSequence::new(input).unwrap();
+        assert_eq!(
+            ir.self_test,
+            Some(SelfTest {
+                default: true,
+                arbitrary: false
+            })
+        );
+
+        let input = parse_quote! {
+            #[derive(Sequence)]
+            #[asn1(self_test = "arbitrary, default")]
+            pub struct Name<'a> {
+                pub name: Utf8StringRef<'a>,
+            }
+        };
+
+        let ir = DeriveSequence::new(input).unwrap();
+        assert_eq!(
+            ir.self_test,
+            Some(SelfTest {
+                default: true,
+                arbitrary: true
+            })
+        );
+        assert!(
+            ir.to_tokens_all()
+                .to_string()
+                .contains("__der_self_test_Name")
+        );
+
+        let input = parse_quote! {
+            #[derive(Sequence)]
+            #[asn1(self_test = "proptest")]
+            pub struct Version {
+                pub version: u8,
+            }
+        };
+        assert!(DeriveSequence::new(input).is_err());
+
+        let input = parse_quote! {
+            #[derive(Sequence)]
+            #[asn1(self_test)]
+            pub struct Wrapper<T> {
+                pub inner: T,
+            }
+        };
+        assert!(DeriveSequence::new(input).is_err());
+    }
+
+    #[test]
+    fn owned_attribute() {
+        let input = parse_quote! {
+            #[derive(Sequence)]
+            #[asn1(owned = "Name")]
+            pub struct NameRef<'a> {
+                pub name: Utf8StringRef<'a>,
+            }
+        };
+
+        let ir = DeriveSequence::new(input).unwrap();
+        assert!(ir.owned.unwrap().is_ident("Name"));
+
+        let input = parse_quote! {
+            #[derive(Sequence)]
+            #[asn1(owned = "Version")]
+            pub struct Version {
+                pub version: u8,
+            }
+        };
+        assert!(DeriveSequence::new(input).is_err());
+    }
+
+    #[test]
+    fn lazy_attribute() {
+        let input = parse_quote! {
+            #[derive(Sequence)]
+            pub struct Envelope<'a> {
+                #[asn1(lazy = "Inner<'a>")]
+                pub inner: AnyRef<'a>,
+
+                #[asn1(context_specific = "0", optional = "true", lazy = "Inner<'a>")]
+                pub extra: Option<AnyRef<'a>>,
+            }
+        };
+
+        let ir = DeriveSequence::new(input).unwrap();
+        assert!(ir.fields[0].attrs.lazy.is_some());
+        assert!(ir.fields[1].attrs.lazy.is_some());
+
+        let accessors = ir.to_tokens_lazy().unwrap().to_string();
+        assert!(accessors.contains("fn decode_inner"));
+        assert!(accessors.contains("fn decode_extra"));
+
+        let input = parse_quote! {
+            #[derive(Sequence)]
+            pub struct Envelope<'a> {
+                #[asn1(lazy = "Inner<'a>", type = "OCTET STRING")]
+                pub inner: AnyRef<'a>,
+            }
+        };
+        assert!(DeriveSequence::new(input).is_err());
+
+        let input = parse_quote! {
+            #[derive(Sequence)]
+            pub struct Envelope<'a> {
+                #[asn1(context_specific = "0", tag_mode = "IMPLICIT", lazy = "Inner<'a>")]
+                pub inner: AnyRef<'a>,
+            }
+        };
+        assert!(DeriveSequence::new(input).is_err());
+    }
+
+    #[test]
+    fn skip_attribute() {
+        let input = parse_quote! {
+            #[derive(Sequence)]
+            pub struct Counter {
+                pub value: u32,
+
+                #[asn1(skip)]
+                pub cache: Option<u32>,
+
+                #[asn1(skip, default = "default_label")]
+                pub label: String,
+            }
+        };
+
+        let ir = DeriveSequence::new(input).unwrap();
+        assert_eq!(ir.fields.len(), 1);
+        assert_eq!(ir.skipped.len(), 2);
+        assert!(!ir.skipped[0].attrs.optional);
+        assert!(ir.skipped[1].attrs.default.is_some());
+
+        let decode = ir.to_tokens_decode().to_string();
+        assert!(decode.contains("let cache = :: core :: default :: Default :: default ()"));
+        assert!(decode.contains("let label = default_label ()"));
+        assert!(!ir.to_tokens_encode().to_string().contains("cache"));
+
+        let input = parse_quote! {
+            #[derive(Sequence)]
+            pub struct Counter {
+                #[asn1(skip, context_specific = "0")]
+                pub cache: Option<u32>,
+            }
+        };
+        assert!(DeriveSequence::new(input).is_err());
+
+        // Other attributes can't be used as bare flags
+        let input = parse_quote! {
+            #[derive(Sequence)]
+            pub struct Counter {
+                #[asn1(optional)]
+                pub value: Option<u32>,
+            }
+        };
+        assert!(DeriveSequence::new(input).is_err());
+    }
+
+    #[test]
+    fn version_attribute() {
+        let input = parse_quote! {
+            #[derive(Sequence)]
+            pub struct Message {
+                #[asn1(version)]
+                pub version: u8,
+
+                #[asn1(context_specific = "0", min_version = "2")]
+                pub extra: Option<bool>,
+            }
+        };
+
+        let ir = DeriveSequence::new(input).unwrap();
+        assert_eq!(ir.version.as_ref().unwrap(), "version");
+        assert!(ir.fields[1].attrs.min_version.is_some());
+        assert!(
+            ir.to_tokens_encode()
+                .to_string()
+                .contains("if self . version >= 2")
+        );
+        assert!(
+            ir.to_tokens_decode()
+                .to_string()
+                .contains("if version >= 2")
+        );
+
+        // `min_version` fields must follow the version field
+        let input = parse_quote! {
+            #[derive(Sequence)]
+            pub struct Message {
+                #[asn1(context_specific = "0", min_version = "2")]
+                pub extra: Option<bool>,
+
+                #[asn1(version)]
+                pub version: u8,
+            }
+        };
+        assert!(DeriveSequence::new(input).is_err());
+
+        // `min_version` fields must be `OPTIONAL` or have a `default`
+        let input = parse_quote! {
+            #[derive(Sequence)]
+            pub struct Message {
+                #[asn1(version)]
+                pub version: u8,
+
+                #[asn1(min_version = "2")]
+                pub extra: bool,
+            }
+        };
+        assert!(DeriveSequence::new(input).is_err());
+    }
+
+    #[test]
+    fn untagged_option_fields() {
+        let input = parse_quote! {
+            #[derive(Sequence)]
+            pub struct Extension<'a> {
+                pub critical: Option<bool>,
+
+                #[asn1(type = "OCTET STRING")]
+                pub value: Option<&'a [u8]>,
+            }
+        };
+
+        let ir = DeriveSequence::new(input).unwrap();
+        assert!(ir.fields.iter().all(|field| field.attrs.optional));
+
+        let input = parse_quote! {
+            #[derive(Sequence)]
+            pub struct Ambiguous {
+                pub first: Option<u8>,
+                pub second: u8,
+            }
+        };
+        assert!(DeriveSequence::new(input).is_err());
+
+        let input = parse_quote! {
+            #[derive(Sequence)]
+            pub struct Ambiguous {
+                #[asn1(context_specific = "0")]
+                pub first: Option<u8>,
+
+                #[asn1(context_specific = "0")]
+                pub second: Option<bool>,
+            }
+        };
+        assert!(DeriveSequence::new(input).is_err());
+    }
+
+    #[test]
+    fn ambiguous_fields() {
+        // Different types with the same tag
+        let input = parse_quote! {
+            #[derive(Sequence)]
+            pub struct Ambiguous<'a> {
+                pub first: Option<u8>,
+                pub second: UintRef<'a>,
+            }
+        };
+        assert!(DeriveSequence::new(input).is_err());
+
+        // Field following other `OPTIONAL` and `DEFAULT` fields
+        let input = parse_quote! {
+            #[derive(Sequence)]
+            pub struct Ambiguous {
+                pub first: Option<bool>,
+                pub second: Option<u32>,
+
+                #[asn1(default = "Default::default")]
+                pub third: ObjectIdentifier,
+
+                pub fourth: Box<bool>,
+            }
+        };
+        assert!(DeriveSequence::new(input).is_err());
+
+        // Field following the next mandatory field
+        let input = parse_quote! {
+            #[derive(Sequence)]
+            pub struct Unambiguous {
+                pub first: Option<bool>,
+                pub second: u32,
+                pub third: Option<u8>,
+                pub fourth: bool,
+            }
+        };
+        assert!(DeriveSequence::new(input).is_ok());
+
+        // Tag resolved from the `type` attribute
+        let input = parse_quote! {
+            #[derive(Sequence)]
+            pub struct Ambiguous<'a> {
+                #[asn1(type = "OCTET STRING")]
+                pub first: Option<&'a [u8]>,
+
+                pub second: &'a OctetStringRef,
+            }
+        };
+        assert!(DeriveSequence::new(input).is_err());
+
+        // Different tags, or types which can't be resolved to a tag
+        let input = parse_quote! {
+            #[derive(Sequence)]
+            pub struct Unambiguous<'a> {
+                pub first: Option<u8>,
+
+                #[asn1(type = "OCTET STRING")]
+                pub second: Option<&'a [u8]>,
+
+                #[asn1(context_specific = "0")]
+                pub third: Option<bool>,
+
+                pub fourth: Option<Time>,
+                pub fifth: Name,
+                pub sixth: bool,
+            }
+        };
+        assert!(DeriveSequence::new(input).is_ok());
+    }
 }
@@ -1,12 +1,13 @@
 //! Sequence field IR and lowerings
 
 use crate::{
-    Asn1Type, FieldAttrs, TagMode, TypeAttrs,
+    Asn1Type, ErrorType, FieldAttrs, TagMode, TypeAttrs,
     attributes::{ClassNum, ClassTokens},
+    schema::is_option,
 };
 use proc_macro2::TokenStream;
-use quote::quote;
-use syn::{Field, Ident, Path, Type};
+use quote::{format_ident, quote};
+use syn::{Field, Ident, Path, Type, Visibility, ext::IdentExt};
 
 /// "IR" for a field of a derived `Sequence`.
 pub(super) struct SequenceField {
@@ -18,6 +19,9 @@ pub(super) struct SequenceField {
 
     /// Field type
     pub(super) field_type: Type,
+
+    /// Field visibility, shared by the accessor of `lazy` fields.
+    pub(super) vis: Visibility,
 }
 
 impl SequenceField {
@@ -30,7 +34,33 @@ impl SequenceField {
             )
         })?;
 
-        let attrs = FieldAttrs::parse(&field.attrs, type_attrs)?;
+        let mut attrs = FieldAttrs::parse(&field.attrs, type_attrs)?;
+
+        if attrs.skip {
+            if attrs.asn1_type.is_some()
+                || attrs.class_num.is_some()
+                || attrs.constructed
+                || attrs.should_deref
+                || attrs.extensible
+                || attrs.lazy.is_some()
+                || attrs.min_version.is_some()
+                || attrs.optional
+                || attrs.size.is_some()
+                || attrs.version
+            {
+                return Err(syn::Error::new_spanned(
+                    ident,
+                    "`skip` can only be combined with the `default` qualifier",
+                ));
+            }
+
+            return Ok(Self {
+                ident,
+                attrs,
+                field_type: field.ty.clone(),
+                vis: field.vis.clone(),
+            });
+        }
 
         if attrs.asn1_type.is_some() && attrs.default.is_some() {
             return Err(syn::Error::new_spanned(
@@ -46,10 +76,138 @@ impl SequenceField {
             ));
         }
 
+        // Plain `Option<T>` fields are `OPTIONAL`: their presence is determined
+        // by peeking the tag of the next element.
+        if is_option(&field.ty) && attrs.default.is_none() {
+            attrs.optional = true;
+        }
+
+        if attrs.version && (attrs.optional || attrs.min_version.is_some()) {
+            return Err(syn::Error::new_spanned(
+                ident,
+                "the `version` field can't be `OPTIONAL` or have a `min_version`",
+            ));
+        }
+
+        if attrs.min_version.is_some() && !attrs.is_optional() {
+            return Err(syn::Error::new_spanned(
+                ident,
+                "`min_version` fields must be `OPTIONAL` or have a `default`",
+            ));
+        }
+
+        if attrs.lazy.is_some() {
+            if attrs.asn1_type.is_some() || attrs.default.is_some() || attrs.size.is_some() {
+                return Err(syn::Error::new_spanned(
+                    ident,
+                    "`lazy` can't be combined with the `type`, `default` or `size` qualifiers",
+                ));
+            }
+
+            if attrs.class_num.is_some() && attrs.tag_mode == TagMode::Implicit {
+                return Err(syn::Error::new_spanned(
+                    ident,
+                    "`lazy` fields can't be IMPLICIT tagged: the TLV stored in the field must be the one of the lazy type",
+                ));
+            }
+        }
+
         Ok(Self {
             ident,
             attrs,
             field_type: field.ty.clone(),
+            vis: field.vis.clone(),
+        })
+    }
+
+    /// Derive the expression computing the value of a field absent from the
+    /// encoding, i.e. a `skip` field or a field whose `min_version` is greater
+    /// than the version of the struct.
+    pub(super) fn to_absent_value_tokens(&self) -> TokenStream {
+        match &self.attrs.default {
+            Some(default) => quote!(#default()),
+            None => quote!(::core::default::Default::default()),
+        }
+    }
+
+    /// Type of this field written in the schema: its `lazy` type if any.
+    pub(super) fn schema_type(&self) -> Type {
+        match &self.attrs.lazy {
+            Some(lazy) if is_option(&self.field_type) => syn::parse_quote!(Option<#lazy>),
+            Some(lazy) => lazy.clone(),
+            None => self.field_type.clone(),
+        }
+    }
+
+    /// Tag identifying the element encoded by this field when decoding.
+    pub(super) fn element_tag(&self) -> ElementTag {
+        if let Some(class_num) = &self.attrs.class_num {
+            return ElementTag::Class(class_num.clone());
+        }
+
+        if let Some(asn1_type) = self.attrs.asn1_type {
+            return ElementTag::Universal(asn1_type.to_string());
+        }
+
+        let ty = self.schema_type();
+        let ty = element_type(option_inner(&ty).unwrap_or(&ty));
+
+        match universal_type_name(ty) {
+            Some(name) => ElementTag::Universal(name.into()),
+            None => ElementTag::Type(quote!(#ty).to_string()),
+        }
+    }
+
+    /// Derive the accessor decoding a `lazy` field on demand, if this field is one.
+    pub(super) fn to_lazy_accessor_tokens(&self, error: &ErrorType) -> Option<TokenStream> {
+        let lazy = self.attrs.lazy.as_ref()?;
+        let ident = &self.ident;
+        let vis = &self.vis;
+        let accessor = format_ident!("decode_{}", ident.unraw());
+        let doc = format!(
+            "Decode the `{}` field, which is stored undecoded.",
+            ident.unraw()
+        );
+
+        Some(if self.attrs.optional || is_option(&self.field_type) {
+            quote! {
+                #[doc = #doc]
+                #vis fn #accessor(&self) -> ::core::result::Result<Option<#lazy>, #error> {
+                    Ok(self.#ident.as_ref().map(|any| any.decode_as::<#lazy>()).transpose()?)
+                }
+            }
+        } else {
+            quote! {
+                #[doc = #doc]
+                #vis fn #accessor(&self) -> ::core::result::Result<#lazy, #error> {
+                    Ok(self.#ident.decode_as::<#lazy>()?)
+                }
+            }
+        })
+    }
+
+    /// Derive code handling the unknown extensions preceding this field, if it's
+    /// an extensible field with a class tag.
+    pub(super) fn to_unknown_extensions_tokens(&self) -> Option<TokenStream> {
+        if !self.attrs.extensible {
+            return None;
+        }
+
+        let ClassTokens {
+            tag_type,
+            tag_number,
+            ..
+        } = self
+            .attrs
+            .class_num
+            .as_ref()?
+            .to_tokens(quote!(_), self.attrs.tag_mode);
+
+        Some(quote! {
+            reader.decode_unknown_extensions(Some(#tag_type {
+                number: #tag_number,
+                constructed: false,
+            }))?;
         })
     }
 
@@ -74,7 +232,32 @@ impl SequenceField {
             }
         }
 
-        lowerer.into_tokens(&self.ident)
+        let mut tokens = lowerer.into_tokens(&self.ident);
+
+        if let Some(size) = &self.attrs.size {
+            let ident = &self.ident;
+
+            tokens.extend(if self.attrs.optional {
+                quote! {
+                    if let Some(value) = &#ident {
+                        ::der::Size::check_size(value, #size)?;
+                    }
+                }
+            } else {
+                quote! {
+                    ::der::Size::check_size(&#ident, #size)?;
+                }
+            });
+        }
+
+        tokens
+    }
+
+    /// Derive the condition for this field to be present in the encoding, if it
+    /// has a `min_version`, given the expression of the version of the struct.
+    pub(super) fn to_version_condition_tokens(&self, version: TokenStream) -> Option<TokenStream> {
+        let min_version = self.attrs.min_version.as_ref()?;
+        Some(quote!(#version >= #min_version))
     }
 
     /// Derive code for encoding a field of a sequence.
@@ -109,6 +292,112 @@ impl SequenceField {
 
         lowerer.into_tokens()
     }
+
+    /// Derive the maximum encoded length of this field, for `EncodeFixed` impls.
+    pub(super) fn to_max_encoded_len_tokens(&self) -> TokenStream {
+        let ty = &self.field_type;
+        let max_len = quote!(<#ty as ::der::EncodeFixed>::MAX_ENCODED_LEN);
+
+        match self.attrs.tag() {
+            Ok(Some(tag)) if self.attrs.class_num.is_some() => {
+                let tag = tag.to_tokens();
+
+                match self.attrs.tag_mode {
+                    TagMode::Explicit => {
+                        quote!(::der::encode_fixed::max_tlv_len(#tag, #max_len))
+                    }
+                    TagMode::Implicit => {
+                        quote!(::der::encode_fixed::max_implicit_len(#tag, #max_len))
+                    }
+                }
+            }
+            _ => max_len,
+        }
+    }
+}
+
+/// Tag identifying the element encoded by a field when decoding, as far as it
+/// can be determined from the definition of the field.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(super) enum ElementTag {
+    /// Class and number of a tagged field.
+    Class(ClassNum),
+
+    /// Universal type of an untagged field, e.g. `INTEGER`.
+    Universal(String),
+
+    /// Type of an untagged field whose tag can't be determined from its name,
+    /// e.g. a `CHOICE` or a type defined outside of `der`.
+    Type(String),
+}
+
+/// Get the type encoded by `ty`, without references and `Box`es.
+fn element_type(ty: &Type) -> &Type {
+    match ty {
+        Type::Reference(reference) => element_type(&reference.elem),
+        Type::Paren(paren) => element_type(&paren.elem),
+        Type::Path(path) => match path.path.segments.last() {
+            Some(segment) if segment.ident == "Box" => match &segment.arguments {
+                syn::PathArguments::AngleBracketed(args) => match args.args.first() {
+                    Some(syn::GenericArgument::Type(inner)) => element_type(inner),
+                    _ => ty,
+                },
+                _ => ty,
+            },
+            _ => ty,
+        },
+        _ => ty,
+    }
+}
+
+/// Get the name of the universal type which `ty` is encoded as, if it's one
+/// of the types for which `der` implements the encoding.
+fn universal_type_name(ty: &Type) -> Option<&'static str> {
+    let ident = match ty {
+        Type::Array(_) => return Some("SEQUENCE"),
+        Type::Tuple(tuple) if tuple.elems.is_empty() => return Some("NULL"),
+        Type::Path(path) => path.path.segments.last()?.ident.to_string(),
+        _ => return None,
+    };
+
+    Some(match ident.as_str() {
+        "bool" => "BOOLEAN",
+        "u8" | "u16" | "u32" | "u64" | "u128" | "i8" | "i16" | "i32" | "i64" | "i128" | "Int"
+        | "IntRef" | "Uint" | "UintRef" => "INTEGER",
+        "BitString" | "BitStringRef" => "BIT STRING",
+        "OctetString" | "OctetStringRef" => "OCTET STRING",
+        "Null" => "NULL",
+        "ObjectIdentifier" => "OBJECT IDENTIFIER",
+        "f64" => "REAL",
+        "String" | "str" | "Utf8StringRef" => "UTF8String",
+        "Vec" | "SequenceOf" => "SEQUENCE",
+        "SetOf" | "SetOfVec" | "BTreeSet" => "SET",
+        "PrintableString" | "PrintableStringRef" => "PrintableString",
+        "TeletexString" | "TeletexStringRef" => "TeletexString",
+        "VideotexString" | "VideotexStringRef" => "VideotexString",
+        "Ia5String" | "Ia5StringRef" => "IA5String",
+        "BmpString" => "BMPString",
+        "UtcTime" => "UTCTime",
+        "GeneralizedTime" => "GeneralizedTime",
+        _ => return None,
+    })
+}
+
+/// Get the `T` of an `Option<T>` type.
+fn option_inner(ty: &Type) -> Option<&Type> {
+    if !is_option(ty) {
+        return None;
+    }
+
+    let Type::Path(path) = ty else { return None };
+    let syn::PathArguments::AngleBracketed(args) = &path.path.segments.last()?.arguments else {
+        return None;
+    };
+
+    match args.args.first()? {
+        syn::GenericArgument::Type(inner) => Some(inner),
+        _ => None,
+    }
 }
 
 /// AST lowerer for field decoders.
@@ -259,7 +548,7 @@ mod tests {
     use crate::{FieldAttrs, TagMode, TagNumber, attributes::ClassNum};
     use proc_macro2::Span;
     use quote::quote;
-    use syn::{Ident, Path, PathSegment, Type, TypePath, punctuated::Punctuated};
+    use syn::{Ident, Path, PathSegment, Type, TypePath, Visibility, punctuated::Punctuated};
 
     /// Create a [`Type::Path`].
     pub fn type_path(ident: Ident) -> Type {
@@ -288,10 +577,15 @@ mod tests {
             class_num: None,
             default: None,
             extensible: false,
+            lazy: None,
+            min_version: None,
             optional: false,
+            size: None,
+            skip: false,
             tag_mode: TagMode::Explicit,
             constructed: false,
             should_deref: false,
+            version: false,
         };
 
         let field_type = Ident::new("String", span);
@@ -300,6 +594,7 @@ mod tests {
             ident,
             attrs,
             field_type: type_path(field_type),
+            vis: Visibility::Inherited,
         };
 
         assert_eq!(
@@ -329,10 +624,15 @@ mod tests {
             class_num: Some(ClassNum::ContextSpecific(TagNumber(0))),
             default: None,
             extensible: false,
+            lazy: None,
+            min_version: None,
             optional: false,
+            size: None,
+            skip: false,
             tag_mode: TagMode::Implicit,
             constructed: false,
             should_deref: false,
+            version: false,
         };
 
         let field_type = Ident::new("String", span);
@@ -341,6 +641,7 @@ mod tests {
             ident,
             attrs,
             field_type: type_path(field_type),
+            vis: Visibility::Inherited,
         };
 
         assert_eq!(
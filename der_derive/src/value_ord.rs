@@ -43,6 +43,7 @@ impl DeriveValueOrd {
                 data.fields
                     .into_iter()
                     .map(|field| ValueField::new_struct(field, &type_attrs))
+                    .filter(|field| !field.as_ref().is_ok_and(|field| field.attrs.skip))
                     .collect::<syn::Result<_>>()?,
                 InputType::Struct,
             ),
@@ -0,0 +1,71 @@
+//! Character set policy for the fields of an MCF hash.
+
+use crate::{Error, Result, fields::DELIMITER};
+
+/// Policy for the characters allowed in the fields of an MCF hash.
+///
+/// The [`Charset::STANDARD`] policy only allows characters in the regexp range
+/// `[A-Za-z0-9./+=,\-]`, and is the one used unless another policy is explicitly requested.
+///
+/// Some legacy systems store hashes with additional characters in their fields, e.g. `_` or `!`.
+/// Such hashes can be parsed under a relaxed policy which allows these characters too:
+///
+/// ```
+/// use mcf::{Charset, PasswordHashRef};
+///
+/// let hash = "$legacy$salt_1$hash!";
+/// assert!(PasswordHashRef::new(hash).is_err());
+///
+/// let relaxed = Charset::with_extra("_!")?;
+/// let hash = PasswordHashRef::new_with_charset(hash, relaxed)?;
+/// assert_eq!(hash.charset(), relaxed);
+/// # Ok::<(), mcf::Error>(())
+/// ```
+///
+/// The policy only applies to fields: the algorithm identifier is always validated strictly.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub struct Charset {
+    /// Characters allowed in addition to the standard ones.
+    extra: &'static str,
+}
+
+impl Charset {
+    /// Standard policy, which only allows characters in the regexp range `[A-Za-z0-9./+=,\-]`.
+    pub const STANDARD: Self = Self { extra: "" };
+
+    /// Create a relaxed policy which allows the given characters in addition to the standard
+    /// ones.
+    ///
+    /// # Errors
+    /// - If `extra` contains `$`, whitespace, or characters which aren't printable ASCII
+    pub fn with_extra(extra: &'static str) -> Result<Self> {
+        if extra
+            .chars()
+            .any(|c| c == DELIMITER || !c.is_ascii_graphic())
+        {
+            return Err(Error {});
+        }
+
+        Ok(Self { extra })
+    }
+
+    /// Get the characters allowed in addition to the standard ones.
+    pub fn extra(self) -> &'static str {
+        self.extra
+    }
+
+    /// Is this the [`Charset::STANDARD`] policy, i.e. doesn't it allow any extra characters?
+    pub fn is_standard(self) -> bool {
+        self.extra.chars().all(is_standard)
+    }
+
+    /// Is the given character allowed in a field under this policy?
+    pub fn allows(self, c: char) -> bool {
+        is_standard(c) || self.extra.contains(c)
+    }
+}
+
+/// Is the given character allowed in a field under the standard policy?
+fn is_standard(c: char) -> bool {
+    matches!(c, 'A'..='Z' | 'a'..='z' | '0'..='9' | '.' | '/' | '+' | '=' | ',' | '-')
+}
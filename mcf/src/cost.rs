@@ -0,0 +1,76 @@
+//! Cost parameters of well-known algorithms, for use by strength policies.
+
+use crate::{
+    Error, GOST_YESCRYPT_ID, PasswordHashRef, Result, RoundsRange, SCRYPT_ID, ScryptParams,
+    YESCRYPT_ID, YescryptParams, ids,
+};
+
+/// Algorithm identifiers of SHA-crypt: `5` (SHA-256) and `6` (SHA-512).
+const SHA_CRYPT_IDS: [&str; 2] = [ids::SHA256_CRYPT, ids::SHA512_CRYPT];
+
+/// Cost parameters of a password hash, decoded according to the positional field layout of its
+/// algorithm.
+///
+/// Obtained using [`PasswordHashRef::cost`]:
+///
+/// ```
+/// use mcf::{Cost, PasswordHashRef};
+///
+/// let hash = PasswordHashRef::new("$y$j9T$PaFbMK1M4/ZYmqo1ivQ2y/$2OW.5ejqNqSBgUt.YwMnvPgqNuoL3xJwwG/SSk4wAKA")?;
+/// let Some(Cost::Yescrypt(params)) = hash.cost()? else { unreachable!() };
+/// assert_eq!(params.n(), 4096);
+/// assert_eq!(params.r(), 32);
+/// # Ok::<(), mcf::Error>(())
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Cost {
+    /// Number of rounds of SHA-crypt (`$5$`/`$6$`), which is [`RoundsRange::default`] when the
+    /// `rounds=N` field is omitted.
+    Rounds(u32),
+
+    /// Parameters of scrypt (`$7$`).
+    Scrypt(ScryptParams),
+
+    /// Parameters of yescrypt (`$y$`) or of its GOST variant (`$gy$`).
+    Yescrypt(YescryptParams),
+}
+
+impl PasswordHashRef<'_> {
+    /// Decode the cost parameters of this hash, if its algorithm is one of the well-known ones
+    /// supported by [`Cost`].
+    ///
+    /// Returns `Ok(None)` for other algorithms.
+    ///
+    /// # Errors
+    /// - If the fields of the hash don't follow the layout of its algorithm
+    /// - If the cost parameters are malformed or out of range
+    pub fn cost(self) -> Result<Option<Cost>> {
+        let mut fields = self.fields();
+        let first = fields.next().ok_or(Error {});
+
+        let cost = match self.id() {
+            id if SHA_CRYPT_IDS.contains(&id) => {
+                let range = RoundsRange::SHA_CRYPT;
+                let rounds = range.parse(first?).transpose()?;
+                Cost::Rounds(rounds.unwrap_or(range.default()))
+            }
+            SCRYPT_ID => Cost::Scrypt(ScryptParams::parse(first?)?.0),
+            YESCRYPT_ID | GOST_YESCRYPT_ID => Cost::Yescrypt(YescryptParams::parse(first?)?),
+            _ => return Ok(None),
+        };
+
+        Ok(Some(cost))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl crate::PasswordHash {
+    /// Decode the cost parameters of this hash, if its algorithm is one of the well-known ones
+    /// supported by [`Cost`].
+    ///
+    /// See [`PasswordHashRef::cost`].
+    pub fn cost(&self) -> Result<Option<Cost>> {
+        self.as_mcf_hash_ref().cost()
+    }
+}
@@ -1,6 +1,6 @@
 //! Fields of an MCF password hash, delimited by `$`
 
-use crate::{Error, Result};
+use crate::{Charset, Error, Result};
 use core::fmt;
 
 #[cfg(feature = "base64")]
@@ -28,6 +28,14 @@ impl<'a> Fields<'a> {
 
         ret
     }
+
+    /// Create a new field iterator from the fields following the identifier of an MCF hash,
+    /// i.e. without the leading `$id$` prefix.
+    ///
+    /// NOTE: like [`Fields::new`], this method doesn't validate the fields are well-formed.
+    pub(crate) fn after_id(s: &'a str) -> Self {
+        Self(s)
+    }
 }
 
 impl<'a> Iterator for Fields<'a> {
@@ -54,15 +62,22 @@ impl<'a> Iterator for Fields<'a> {
 
 /// Individual field of an MCF hash, delimited by `$`.
 ///
-/// Fields are constrained to characters in the regexp range `[A-Za-z0-9./+=,\-]`.
+/// Fields are constrained to characters in the regexp range `[A-Za-z0-9./+=,\-]`, unless
+/// they belong to a hash parsed under a relaxed [`Charset`].
 #[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord)]
 pub struct Field<'a>(&'a str);
 
 impl<'a> Field<'a> {
     /// Create a new [`Field`], validating the provided characters are in the allowed range.
     pub fn new(s: &'a str) -> Result<Self> {
+        Self::new_with_charset(s, Charset::STANDARD)
+    }
+
+    /// Create a new [`Field`], validating the provided characters are allowed by the given
+    /// [`Charset`].
+    pub fn new_with_charset(s: &'a str, charset: Charset) -> Result<Self> {
         let field = Field(s);
-        field.validate()?;
+        field.validate(charset)?;
         Ok(field)
     }
 
@@ -84,18 +99,11 @@ impl<'a> Field<'a> {
     }
 
     /// Validate a field in the password hash is well-formed.
-    pub(crate) fn validate(self) -> Result<()> {
-        if self.0.is_empty() {
+    pub(crate) fn validate(self, charset: Charset) -> Result<()> {
+        if self.0.is_empty() || !self.0.chars().all(|c| charset.allows(c)) {
             return Err(Error {});
         }
 
-        for c in self.0.chars() {
-            match c {
-                'A'..='Z' | 'a'..='z' | '0'..='9' | '.' | '/' | '+' | '=' | ',' | '-' => (),
-                _ => return Err(Error {}),
-            }
-        }
-
         Ok(())
     }
 }
@@ -0,0 +1,144 @@
+//! Registry of well-known algorithm identifiers, and of the number of fields their hashes have.
+//!
+//! MCF has no central registry of identifiers, so this covers the identifiers used by
+//! `crypt(3)` implementations such as libxcrypt, and by the [PHC string format] as used in MCF.
+//!
+//! [PHC string format]: https://github.com/P-H-C/phc-string-format/blob/master/phc-sf-spec.md
+
+use crate::{Error, PasswordHashRef, Result};
+use core::ops::RangeInclusive;
+
+/// MD5-crypt: `1`.
+pub const MD5_CRYPT: &str = "1";
+
+/// bcrypt with the original (buggy) handling of non-ASCII passwords: `2a`.
+pub const BCRYPT_2A: &str = "2a";
+
+/// bcrypt: `2b`.
+pub const BCRYPT: &str = "2b";
+
+/// bcrypt as produced by `crypt_blowfish`: `2y`.
+pub const BCRYPT_2Y: &str = "2y";
+
+/// SHA-crypt with SHA-256: `5`.
+pub const SHA256_CRYPT: &str = "5";
+
+/// SHA-crypt with SHA-512: `6`.
+pub const SHA512_CRYPT: &str = "6";
+
+/// scrypt in the format introduced by libxcrypt: `7`.
+pub const SCRYPT: &str = crate::SCRYPT_ID;
+
+/// yescrypt: `y`.
+pub const YESCRYPT: &str = crate::YESCRYPT_ID;
+
+/// GOST R 34.11-2012 hashes of yescrypt hashes: `gy`.
+pub const GOST_YESCRYPT: &str = crate::GOST_YESCRYPT_ID;
+
+/// NetBSD's iterated HMAC-SHA1: `sha1`.
+pub const SHA1_CRYPT: &str = "sha1";
+
+/// Argon2d in the PHC string format: `argon2d`.
+pub const ARGON2D: &str = "argon2d";
+
+/// Argon2i in the PHC string format: `argon2i`.
+pub const ARGON2I: &str = "argon2i";
+
+/// Argon2id in the PHC string format: `argon2id`.
+pub const ARGON2ID: &str = "argon2id";
+
+/// scrypt in the PHC string format: `scrypt`.
+pub const SCRYPT_PHC: &str = "scrypt";
+
+/// PBKDF2-HMAC-SHA256 in the PHC string format: `pbkdf2-sha256`.
+pub const PBKDF2_SHA256: &str = "pbkdf2-sha256";
+
+/// PBKDF2-HMAC-SHA512 in the PHC string format: `pbkdf2-sha512`.
+pub const PBKDF2_SHA512: &str = "pbkdf2-sha512";
+
+/// Validation rules of the hashes of the well-known identifiers, in the order of the constants
+/// above.
+pub const RULES: &[Rule] = &[
+    Rule::new(MD5_CRYPT, 2, 2),
+    Rule::new(BCRYPT_2A, 2, 2),
+    Rule::new(BCRYPT, 2, 2),
+    Rule::new(BCRYPT_2Y, 2, 2),
+    Rule::new(SHA256_CRYPT, 2, 3),
+    Rule::new(SHA512_CRYPT, 2, 3),
+    Rule::new(SCRYPT, 2, 2),
+    Rule::new(YESCRYPT, 3, 3),
+    Rule::new(GOST_YESCRYPT, 3, 3),
+    Rule::new(SHA1_CRYPT, 3, 3),
+    Rule::new(ARGON2D, 3, 4),
+    Rule::new(ARGON2I, 3, 4),
+    Rule::new(ARGON2ID, 3, 4),
+    Rule::new(SCRYPT_PHC, 3, 3),
+    Rule::new(PBKDF2_SHA256, 3, 3),
+    Rule::new(PBKDF2_SHA512, 3, 3),
+];
+
+/// Get the validation rules of the given identifier, if it's a well-known one.
+pub fn rule(id: &str) -> Option<&'static Rule> {
+    RULES.iter().find(|rule| rule.id == id)
+}
+
+/// Validation rules of the hashes of a well-known algorithm identifier.
+///
+/// They are checked by [`PasswordHashRef::new_strict`], or can be used by the consumers of a
+/// specific algorithm to reject the hashes of other algorithms early:
+///
+/// ```
+/// use mcf::{PasswordHashRef, ids};
+///
+/// let bcrypt = PasswordHashRef::new("$2b$12$R9h/cIPz0gi.URNNX3kh2OPST9/PgBkqquzi.Ss7KIUgO2t0jWMUW")?;
+/// assert!(ids::rule(ids::BCRYPT).unwrap().check(bcrypt).is_ok());
+/// assert!(ids::rule(ids::SHA512_CRYPT).unwrap().check(bcrypt).is_err());
+/// # Ok::<(), mcf::Error>(())
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Rule {
+    /// Algorithm identifier.
+    id: &'static str,
+
+    /// Minimum number of fields following the identifier.
+    min_fields: usize,
+
+    /// Maximum number of fields following the identifier.
+    max_fields: usize,
+}
+
+impl Rule {
+    /// Create the rule of an identifier whose hashes have between `min_fields` and `max_fields`
+    /// fields following it.
+    const fn new(id: &'static str, min_fields: usize, max_fields: usize) -> Self {
+        Self {
+            id,
+            min_fields,
+            max_fields,
+        }
+    }
+
+    /// Get the algorithm identifier this rule applies to.
+    pub fn id(&self) -> &'static str {
+        self.id
+    }
+
+    /// Get the allowed number of fields following the identifier.
+    pub fn fields(&self) -> RangeInclusive<usize> {
+        self.min_fields..=self.max_fields
+    }
+
+    /// Check that the given hash has the identifier of this rule, and the expected number of
+    /// fields.
+    ///
+    /// # Errors
+    /// - If the hash has another identifier
+    /// - If the number of fields of the hash is outside [`Rule::fields`]
+    pub fn check(&self, hash: PasswordHashRef<'_>) -> Result<()> {
+        if hash.id() != self.id || !self.fields().contains(&hash.fields().count()) {
+            return Err(Error {});
+        }
+
+        Ok(())
+    }
+}
@@ -17,16 +17,50 @@
 extern crate alloc;
 
 mod base64;
+mod charset;
+mod cost;
 mod error;
 mod fields;
-
+pub mod ids;
+mod list;
+mod lossy;
+mod params;
+mod parts;
+#[cfg(feature = "password-hash")]
+mod phc;
+mod redacted;
+#[cfg(feature = "alloc")]
+mod rewrite;
+mod rounds;
+mod scrypt;
+#[cfg(feature = "serde")]
+mod serde;
+
+pub use charset::Charset;
+pub use cost::Cost;
 pub use error::{Error, Result};
 pub use fields::{Field, Fields};
+pub use list::{HashList, LIST_SEPARATOR};
+pub use lossy::InvalidHash;
+pub use params::{PAIR_DELIMITER, PARAMS_DELIMITER, Params};
+pub use parts::McfParts;
+pub use redacted::Redacted;
+pub use rounds::{ROUNDS_PREFIX, RoundsField, RoundsRange};
+pub use scrypt::{GOST_YESCRYPT_ID, SCRYPT_ID, ScryptParams, YESCRYPT_ID, YescryptParams};
 
-#[cfg(feature = "alloc")]
-pub use allocating::PasswordHash;
 #[cfg(feature = "base64")]
 pub use base64::Base64;
+#[cfg(feature = "alloc")]
+pub use {
+    allocating::PasswordHash,
+    lossy::sanitize_lossy,
+    rewrite::{Rewrite, RewriteOutcome},
+};
+
+use core::{cmp::Ordering, ops::Range, str};
+
+#[cfg(feature = "subtle")]
+use subtle::{Choice, ConstantTimeEq};
 
 /// Debug message used in panics when invariants aren't properly held.
 const INVARIANT_MSG: &str = "should be ensured valid by constructor";
@@ -34,15 +68,79 @@ const INVARIANT_MSG: &str = "should be ensured valid by constructor";
 /// Password hash reference type for hashes encoded in the Modular Crypt Format (MCF),
 /// e.g. `$<id>$...`.
 ///
+/// The end of the identifier is located once when the hash is parsed, so [`id`] and
+/// [`fields`] don't rescan the string, and [`id_range`] and [`fields_range`] expose these
+/// boundaries for slicing the hash directly.
+///
 /// For more information, see [`PasswordHash`].
-#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord)]
-pub struct PasswordHashRef<'a>(&'a str);
+///
+/// # Equality
+///
+/// Hashes are compared as strings, regardless of the [`Charset`] they were parsed with.
+///
+/// The [`PartialEq`] impl is not constant time. When comparing against a stored hash, e.g. in a
+/// password verifier, use `ConstantTimeEq::ct_eq` instead, which requires the `subtle` feature.
+///
+/// [`id`]: PasswordHashRef::id
+/// [`fields`]: PasswordHashRef::fields
+/// [`id_range`]: PasswordHashRef::id_range
+/// [`fields_range`]: PasswordHashRef::fields_range
+#[derive(Clone, Copy, Debug)]
+pub struct PasswordHashRef<'a>(&'a str, Charset, usize);
 
 impl<'a> PasswordHashRef<'a> {
     /// Parse the given input string, returning an [`PasswordHashRef`] if valid.
     pub fn new(s: &'a str) -> Result<Self> {
-        validate(s)?;
-        Ok(Self(s))
+        Self::new_with_charset(s, Charset::STANDARD)
+    }
+
+    /// Parse the given input string, allowing the characters of the given [`Charset`] in its
+    /// fields, and returning an [`PasswordHashRef`] if valid.
+    pub fn new_with_charset(s: &'a str, charset: Charset) -> Result<Self> {
+        let id_end = validate(s, charset)?;
+        Ok(Self(s, charset, id_end))
+    }
+
+    /// Parse the given input bytes, e.g. read from a legacy `/etc/shadow` file which may contain
+    /// invalid bytes, returning an [`PasswordHashRef`] if valid.
+    ///
+    /// Unlike [`PasswordHashRef::new`], the input doesn't need to be valid UTF-8, and errors
+    /// report the location of the first invalid byte. See [`InvalidHash`] for more information.
+    pub fn from_bytes(bytes: &'a [u8]) -> core::result::Result<Self, InvalidHash> {
+        Self::from_bytes_with_charset(bytes, Charset::STANDARD)
+    }
+
+    /// Parse the given input bytes like [`PasswordHashRef::from_bytes`], allowing the characters
+    /// of the given [`Charset`] in its fields.
+    pub fn from_bytes_with_charset(
+        bytes: &'a [u8],
+        charset: Charset,
+    ) -> core::result::Result<Self, InvalidHash> {
+        if let Some(err) = InvalidHash::locate(bytes, charset) {
+            return Err(err);
+        }
+
+        // All characters allowed in a well-formed hash are ASCII
+        let s = str::from_utf8(bytes).expect(INVARIANT_MSG);
+        Ok(Self::new_with_charset(s, charset).expect(INVARIANT_MSG))
+    }
+
+    /// Parse the given input string like [`PasswordHashRef::new`], additionally checking the
+    /// number of fields of hashes with a well-known identifier against its [`ids::Rule`].
+    ///
+    /// Hashes with other identifiers are accepted as long as they're well-formed.
+    ///
+    /// # Errors
+    /// - If the hash is malformed
+    /// - If the hash has a well-known identifier but an unexpected number of fields
+    pub fn new_strict(s: &'a str) -> Result<Self> {
+        let hash = Self::new(s)?;
+
+        if let Some(rule) = ids::rule(hash.id()) {
+            rule.check(hash)?;
+        }
+
+        Ok(hash)
     }
 
     /// Get the contained string as a `str`.
@@ -50,24 +148,50 @@ impl<'a> PasswordHashRef<'a> {
         self.0
     }
 
+    /// Get the [`Charset`] this hash was parsed under.
+    pub fn charset(self) -> Charset {
+        self.1
+    }
+
     /// Get the algorithm identifier for this MCF hash.
     pub fn id(self) -> &'a str {
-        Fields::new(self.as_str())
-            .next()
-            .expect(INVARIANT_MSG)
-            .as_str()
+        &self.0[self.id_range()]
     }
 
     /// Get an iterator over the parts of the password hash as delimited by `$`, excluding the
     /// initial identifier.
     pub fn fields(self) -> Fields<'a> {
-        let mut fields = Fields::new(self.as_str());
+        Fields::after_id(&self.0[self.fields_range()])
+    }
 
-        // Remove the leading identifier
-        let id = fields.next().expect(INVARIANT_MSG);
-        debug_assert_eq!(self.id(), id.as_str());
+    /// Get the byte range of the algorithm identifier within [`PasswordHashRef::as_str`],
+    /// excluding the delimiters around it.
+    pub fn id_range(self) -> Range<usize> {
+        1..self.2
+    }
 
-        fields
+    /// Get the byte range of the `$`-delimited fields following the identifier within
+    /// [`PasswordHashRef::as_str`], excluding the delimiter preceding them.
+    ///
+    /// The range is empty if the hash only consists of an identifier.
+    pub fn fields_range(self) -> Range<usize> {
+        (self.2 + 1).min(self.0.len())..self.0.len()
+    }
+
+    /// Split this hash into typed [`McfParts`] following the `$id$params$salt$hash` convention.
+    ///
+    /// # Errors
+    /// - If the hash doesn't have between 1 and 3 fields after the identifier
+    pub fn to_parts(self) -> Result<McfParts<'a>> {
+        McfParts::try_from(self)
+    }
+
+    /// Get a wrapper which renders this hash with its salt and hash output fields masked, e.g.
+    /// for logging.
+    ///
+    /// See [`Redacted`] for more information.
+    pub fn redacted(self) -> Redacted<'a> {
+        Redacted::new(self)
     }
 }
 
@@ -92,15 +216,51 @@ impl<'a> TryFrom<&'a str> for PasswordHashRef<'a> {
     }
 }
 
+impl PartialEq for PasswordHashRef<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl Eq for PasswordHashRef<'_> {}
+
+impl PartialOrd for PasswordHashRef<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for PasswordHashRef<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(other.0)
+    }
+}
+
+#[cfg(feature = "subtle")]
+impl ConstantTimeEq for PasswordHashRef<'_> {
+    /// Compare the complete hash strings in constant time.
+    ///
+    /// Only their lengths, which aren't secret, are compared in variable time.
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0.as_bytes().ct_eq(other.0.as_bytes())
+    }
+}
+
 #[cfg(feature = "alloc")]
 mod allocating {
-    use crate::{Error, Field, Fields, PasswordHashRef, Result, fields, validate, validate_id};
+    use crate::{
+        Charset, Error, Field, Fields, McfParts, PasswordHashRef, Redacted, Result, fields,
+        validate, validate_id,
+    };
     use alloc::string::String;
-    use core::{fmt, str};
+    use core::{cmp::Ordering, fmt, ops::Range, str};
 
     #[cfg(feature = "base64")]
     use crate::Base64;
 
+    #[cfg(feature = "subtle")]
+    use subtle::{Choice, ConstantTimeEq};
+
     /// Password hash encoded in the Modular Crypt Format (MCF). Owned form with builder
     /// functionality.
     ///
@@ -116,15 +276,40 @@ mod allocating {
     /// ```text
     /// $6$rounds=100000$exn6tVc2j/MZD8uG$BI1Xh8qQSK9J4m14uwy7abn.ctj/TIAzlaVCto0MQrOFIeTXsc1iwzH16XEWo/a7c7Y9eVJvufVzYAs4EsPOy0
     /// ```
-    #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
-    pub struct PasswordHash(String);
+    ///
+    /// Like [`PasswordHashRef`], the end of the identifier is located once, when the hash is
+    /// parsed or created.
+    ///
+    /// # Equality
+    ///
+    /// Hashes are compared as strings, regardless of the [`Charset`] they were parsed with.
+    ///
+    /// The [`PartialEq`] impl is not constant time. When comparing against a stored hash, e.g.
+    /// in a password verifier, use `ConstantTimeEq::ct_eq` instead, which requires the `subtle`
+    /// feature.
+    #[derive(Clone, Debug)]
+    pub struct PasswordHash(String, Charset, usize);
 
     impl PasswordHash {
         /// Parse the given input string, returning an [`PasswordHash`] if valid.
         pub fn new(s: impl Into<String>) -> Result<PasswordHash> {
+            Self::new_with_charset(s, Charset::STANDARD)
+        }
+
+        /// Parse the given input string, allowing the characters of the given [`Charset`] in
+        /// its fields, and returning an [`PasswordHash`] if valid.
+        pub fn new_with_charset(s: impl Into<String>, charset: Charset) -> Result<PasswordHash> {
             let s = s.into();
-            validate(&s)?;
-            Ok(Self(s))
+            let id_end = validate(&s, charset)?;
+            Ok(Self(s, charset, id_end))
+        }
+
+        /// Parse the given input string, checking the number of fields of hashes with a
+        /// well-known identifier as described in [`PasswordHashRef::new_strict`].
+        pub fn new_strict(s: impl Into<String>) -> Result<PasswordHash> {
+            let hash = Self::new(s)?;
+            PasswordHashRef::new_strict(hash.as_str())?;
+            Ok(hash)
         }
 
         /// Create an [`PasswordHash`] from an identifier.
@@ -142,7 +327,8 @@ mod allocating {
             let mut hash = String::with_capacity(1 + id.len());
             hash.push(fields::DELIMITER);
             hash.push_str(id);
-            Ok(Self(hash))
+            let id_end = hash.len();
+            Ok(Self(hash, Charset::STANDARD, id_end))
         }
 
         /// Get the contained string as a `str`.
@@ -150,9 +336,16 @@ mod allocating {
             &self.0
         }
 
+        /// Get the [`Charset`] this hash was parsed under.
+        ///
+        /// Fields pushed with [`PasswordHash::push_str`] are validated under this policy.
+        pub fn charset(&self) -> Charset {
+            self.1
+        }
+
         /// Get an [`PasswordHashRef`] which corresponds to this owned [`PasswordHash`].
         pub fn as_mcf_hash_ref(&self) -> PasswordHashRef<'_> {
-            PasswordHashRef(self.as_str())
+            PasswordHashRef(self.as_str(), self.1, self.2)
         }
 
         /// Get the algorithm identifier for this MCF hash.
@@ -166,6 +359,34 @@ mod allocating {
             self.as_mcf_hash_ref().fields()
         }
 
+        /// Get the byte range of the algorithm identifier within [`PasswordHash::as_str`],
+        /// excluding the delimiters around it.
+        pub fn id_range(&self) -> Range<usize> {
+            self.as_mcf_hash_ref().id_range()
+        }
+
+        /// Get the byte range of the `$`-delimited fields following the identifier within
+        /// [`PasswordHash::as_str`], excluding the delimiter preceding them.
+        ///
+        /// The range is empty if the hash only consists of an identifier.
+        pub fn fields_range(&self) -> Range<usize> {
+            self.as_mcf_hash_ref().fields_range()
+        }
+
+        /// Split this hash into typed [`McfParts`] following the `$id$params$salt$hash`
+        /// convention.
+        pub fn to_parts(&self) -> Result<McfParts<'_>> {
+            self.as_mcf_hash_ref().to_parts()
+        }
+
+        /// Get a wrapper which renders this hash with its salt and hash output fields masked,
+        /// e.g. for logging.
+        ///
+        /// See [`Redacted`] for more information.
+        pub fn redacted(&self) -> Redacted<'_> {
+            self.as_mcf_hash_ref().redacted()
+        }
+
         /// Encode the given data as the specified variant of Base64 and push it onto the password
         /// hash string, first adding a `$` delimiter.
         #[cfg(feature = "base64")]
@@ -175,9 +396,15 @@ mod allocating {
         }
 
         /// Push an additional field onto the password hash string, first adding a `$` delimiter.
-        pub fn push_field(&mut self, field: Field<'_>) {
+        ///
+        /// # Errors
+        /// - If the field contains characters outside the set allowed by
+        ///   [`PasswordHash::charset`], e.g. if it was created under a relaxed [`Charset`]
+        pub fn push_field(&mut self, field: Field<'_>) -> Result<()> {
+            field.validate(self.1)?;
             self.0.push(fields::DELIMITER);
             self.0.push_str(field.as_str());
+            Ok(())
         }
 
         /// Push a raw string onto the MCF hash, first adding a `$` delimiter and also ensuring it
@@ -185,12 +412,62 @@ mod allocating {
         ///
         /// # Errors
         /// - If the provided `str` fails to validate as a [`Field`] (i.e. contains characters
-        ///   outside the allowed set)
+        ///   outside the set allowed by [`PasswordHash::charset`])
         pub fn push_str(&mut self, s: &str) -> Result<()> {
-            let field = Field::new(s)?;
-            self.push_field(field);
+            self.push_field(Field::new_with_charset(s, self.1)?)
+        }
+
+        /// Replace the field at the given index, excluding the initial identifier (i.e. the
+        /// index of the field in [`PasswordHash::fields`]).
+        ///
+        /// # Errors
+        /// - If there's no field at `index`
+        /// - If the field contains characters outside the set allowed by
+        ///   [`PasswordHash::charset`]
+        pub fn set_field(&mut self, index: usize, field: Field<'_>) -> Result<()> {
+            field.validate(self.1)?;
+            let range = self.field_range(index).ok_or(Error {})?;
+            self.0.replace_range(range, field.as_str());
             Ok(())
         }
+
+        /// Remove the field at the given index, excluding the initial identifier, along with
+        /// its `$` delimiter.
+        ///
+        /// # Errors
+        /// - If there's no field at `index`
+        pub fn remove_field(&mut self, index: usize) -> Result<()> {
+            let range = self.field_range(index).ok_or(Error {})?;
+            self.0.replace_range((range.start - 1)..range.end, "");
+            Ok(())
+        }
+
+        /// Keep only the first `n` fields following the identifier, removing the rest.
+        ///
+        /// Has no effect if the hash has `n` or fewer fields.
+        pub fn truncate_fields(&mut self, n: usize) {
+            if let Some(range) = self.field_range(n) {
+                self.0.truncate(range.start - 1);
+            }
+        }
+
+        /// Get the byte range of the field at the given index, excluding the initial identifier
+        /// and the delimiter preceding the field.
+        fn field_range(&self, index: usize) -> Option<Range<usize>> {
+            // Skip the delimiter preceding the identifier
+            let start = self
+                .0
+                .match_indices(fields::DELIMITER)
+                .nth(index.checked_add(1)?)?
+                .0
+                + 1;
+
+            let end = self.0[start..]
+                .find(fields::DELIMITER)
+                .map_or(self.0.len(), |len| start + len);
+
+            Some(start..end)
+        }
     }
 
     impl<'a> AsRef<str> for PasswordHashRef<'a> {
@@ -240,10 +517,41 @@ mod allocating {
             Self::new(s)
         }
     }
+
+    impl PartialEq for PasswordHash {
+        fn eq(&self, other: &Self) -> bool {
+            self.0 == other.0
+        }
+    }
+
+    impl Eq for PasswordHash {}
+
+    impl PartialOrd for PasswordHash {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for PasswordHash {
+        fn cmp(&self, other: &Self) -> Ordering {
+            self.0.cmp(&other.0)
+        }
+    }
+
+    #[cfg(feature = "subtle")]
+    impl ConstantTimeEq for PasswordHash {
+        /// Compare the complete hash strings in constant time.
+        ///
+        /// Only their lengths, which aren't secret, are compared in variable time.
+        fn ct_eq(&self, other: &Self) -> Choice {
+            self.as_mcf_hash_ref().ct_eq(&other.as_mcf_hash_ref())
+        }
+    }
 }
 
-/// Perform validations that the given string is well-formed MCF.
-fn validate(s: &str) -> Result<()> {
+/// Perform validations that the given string is well-formed MCF, returning the byte offset of
+/// the end of the identifier, i.e. of the delimiter following it if any.
+fn validate(s: &str, charset: Charset) -> Result<usize> {
     // Require leading `$`
     if !s.starts_with(fields::DELIMITER) {
         return Err(Error {});
@@ -263,10 +571,10 @@ fn validate(s: &str) -> Result<()> {
 
     // Validate the remaining fields have an appropriate format
     for field in fields {
-        field.validate()?;
+        field.validate(charset)?;
     }
 
-    Ok(())
+    Ok(1 + id.as_str().len())
 }
 
 /// Validate the password hash identifier is well-formed.
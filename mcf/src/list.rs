@@ -0,0 +1,125 @@
+//! Lists of MCF hashes stored in a single string, e.g. `$y$...;$6$...`.
+
+use crate::{Error, Field, PasswordHashRef, Result, fields::DELIMITER};
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+/// Default separator between the hashes of a [`HashList`]: `;`.
+pub const LIST_SEPARATOR: char = ';';
+
+/// List of MCF hashes joined by a separator within a single string, e.g. `$y$...;$6$...`.
+///
+/// Some deployments (e.g. certain PAM stacks) store a primary hash followed by one or more
+/// fallback hashes in a single field, typically while migrating between algorithms.
+///
+/// Parsing is tolerant: whitespace around each hash and empty entries (e.g. from a trailing
+/// separator) are ignored, and each hash is validated individually as the list is iterated, so a
+/// single malformed entry doesn't prevent using the others.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct HashList<'a> {
+    /// Joined hashes.
+    s: &'a str,
+
+    /// Separator between hashes.
+    separator: char,
+}
+
+impl<'a> HashList<'a> {
+    /// Create a [`HashList`] from hashes separated by [`LIST_SEPARATOR`].
+    pub fn new(s: &'a str) -> Self {
+        Self {
+            s,
+            separator: LIST_SEPARATOR,
+        }
+    }
+
+    /// Create a [`HashList`] from hashes separated by the given character.
+    ///
+    /// # Errors
+    /// - If `separator` is `$` or is allowed within a [`Field`], since it would then be ambiguous
+    pub fn with_separator(s: &'a str, separator: char) -> Result<Self> {
+        validate_separator(separator)?;
+        Ok(Self { s, separator })
+    }
+
+    /// Borrow the joined hashes as a `str`.
+    pub fn as_str(self) -> &'a str {
+        self.s
+    }
+
+    /// Get the separator between hashes.
+    pub fn separator(self) -> char {
+        self.separator
+    }
+
+    /// Iterate over the hashes in the list, in order, validating each of them.
+    ///
+    /// Empty entries are skipped, and surrounding whitespace is trimmed from each entry.
+    pub fn iter(self) -> impl Iterator<Item = Result<PasswordHashRef<'a>>> {
+        self.s
+            .split(self.separator)
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .map(PasswordHashRef::new)
+    }
+
+    /// Iterate over the well-formed hashes in the list, in order, skipping malformed ones.
+    pub fn valid(self) -> impl Iterator<Item = PasswordHashRef<'a>> {
+        self.iter().filter_map(Result::ok)
+    }
+
+    /// Get the first well-formed hash in the list, which is conventionally the primary one.
+    pub fn primary(self) -> Option<PasswordHashRef<'a>> {
+        self.valid().next()
+    }
+
+    /// Ensure every entry of the list is a well-formed hash.
+    ///
+    /// # Errors
+    /// - If any non-empty entry isn't a well-formed MCF hash
+    pub fn validate(self) -> Result<()> {
+        self.iter().try_for_each(|hash| hash.map(|_| ()))
+    }
+
+    /// Join the given hashes into a single string, separated by `separator`.
+    ///
+    /// # Errors
+    /// - If `separator` is invalid (see [`HashList::with_separator`])
+    #[cfg(feature = "alloc")]
+    pub fn join<'h>(
+        hashes: impl IntoIterator<Item = PasswordHashRef<'h>>,
+        separator: char,
+    ) -> Result<String> {
+        validate_separator(separator)?;
+        let mut joined = String::new();
+
+        for (i, hash) in hashes.into_iter().enumerate() {
+            if i > 0 {
+                joined.push(separator);
+            }
+
+            joined.push_str(hash.as_str());
+        }
+
+        Ok(joined)
+    }
+
+    /// Re-serialize the well-formed hashes in this list in normalized form, i.e. without
+    /// whitespace, empty or malformed entries.
+    #[cfg(feature = "alloc")]
+    pub fn to_normalized_string(self) -> String {
+        Self::join(self.valid(), self.separator).expect(crate::INVARIANT_MSG)
+    }
+}
+
+/// Validate a list separator can't be confused with the contents of a hash.
+fn validate_separator(separator: char) -> Result<()> {
+    let mut buf = [0u8; 4];
+
+    if separator == DELIMITER || Field::new(separator.encode_utf8(&mut buf)).is_ok() {
+        return Err(Error {});
+    }
+
+    Ok(())
+}
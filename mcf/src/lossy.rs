@@ -0,0 +1,189 @@
+//! Diagnostics for hashes with invalid bytes, e.g. scraped from legacy `/etc/shadow` files.
+
+use crate::{Charset, Error, fields::DELIMITER};
+use core::fmt;
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+/// Location of the first invalid byte of a hash which failed to parse with
+/// [`PasswordHashRef::from_bytes`][`crate::PasswordHashRef::from_bytes`].
+///
+/// The invalid byte itself isn't retained, so this error can be logged without leaking any part
+/// of the hash:
+///
+/// ```
+/// use mcf::PasswordHashRef;
+///
+/// let err = PasswordHashRef::from_bytes(b"$6$salt$hash\xff").unwrap_err();
+/// assert_eq!(err.field(), Some(1));
+/// assert_eq!(err.offset(), 12);
+/// assert_eq!(err.to_string(), "invalid MCF hash: invalid byte at offset 12 in field 1");
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct InvalidHash {
+    /// Index of the field, excluding the initial identifier, or `None` for the identifier.
+    field: Option<usize>,
+
+    /// Byte offset within the input.
+    offset: usize,
+}
+
+impl InvalidHash {
+    /// Locate the first invalid byte of the given input, returning `None` if it's a well-formed
+    /// MCF hash under the given [`Charset`].
+    pub(crate) fn locate(bytes: &[u8], charset: Charset) -> Option<Self> {
+        // Require leading `$`
+        if bytes.first() != Some(&(DELIMITER as u8)) {
+            return Some(Self {
+                field: None,
+                offset: 0,
+            });
+        }
+
+        let mut field = None;
+        let mut start = 1;
+
+        loop {
+            let end = bytes[start..]
+                .iter()
+                .position(|&b| b == DELIMITER as u8)
+                .map_or(bytes.len(), |len| start + len);
+
+            let invalid = match field {
+                None => invalid_id_byte(&bytes[start..end]),
+                Some(_) => invalid_field_byte(&bytes[start..end], charset),
+            };
+
+            if let Some(pos) = invalid {
+                return Some(Self {
+                    field,
+                    offset: start + pos,
+                });
+            }
+
+            if end == bytes.len() {
+                return None;
+            }
+
+            field = Some(field.map_or(0, |index| index + 1));
+            start = end + 1;
+        }
+    }
+
+    /// Get the index of the field containing the invalid byte, excluding the initial identifier
+    /// (i.e. the index of the field in [`PasswordHashRef::fields`]).
+    ///
+    /// Returns `None` if the identifier, or the `$` preceding it, is invalid.
+    ///
+    /// [`PasswordHashRef::fields`]: crate::PasswordHashRef::fields
+    pub fn field(self) -> Option<usize> {
+        self.field
+    }
+
+    /// Get the byte offset of the invalid byte within the input.
+    ///
+    /// For empty fields, e.g. in `$6$$hash` or due to a trailing `$`, this is the offset where
+    /// the field would start.
+    pub fn offset(self) -> usize {
+        self.offset
+    }
+}
+
+impl From<InvalidHash> for Error {
+    fn from(_: InvalidHash) -> Error {
+        Error {}
+    }
+}
+
+impl core::error::Error for InvalidHash {}
+
+impl fmt::Display for InvalidHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid MCF hash: invalid byte at offset {} in ",
+            self.offset
+        )?;
+
+        match self.field {
+            Some(index) => write!(f, "field {index}"),
+            None => f.write_str("identifier"),
+        }
+    }
+}
+
+/// Copy the given input, replacing each byte sequence which isn't valid UTF-8 and each character
+/// which isn't allowed in an MCF hash under the given [`Charset`] with
+/// [`char::REPLACEMENT_CHARACTER`].
+///
+/// The result is intended for diagnostics, e.g. to display a malformed hash alongside its
+/// [`InvalidHash`] error: when anything was replaced, it no longer corresponds to the original
+/// hash and should not be stored or verified against.
+///
+/// ```
+/// use mcf::{Charset, sanitize_lossy};
+///
+/// assert_eq!(
+///     sanitize_lossy(b"$6$sa\xfflt$ha sh", Charset::STANDARD),
+///     "$6$sa\u{FFFD}lt$ha\u{FFFD}sh"
+/// );
+/// ```
+#[cfg(feature = "alloc")]
+pub fn sanitize_lossy(bytes: &[u8], charset: Charset) -> String {
+    let mut ret = String::with_capacity(bytes.len());
+
+    for chunk in bytes.utf8_chunks() {
+        for c in chunk.valid().chars() {
+            if c == DELIMITER || charset.allows(c) {
+                ret.push(c);
+            } else {
+                ret.push(char::REPLACEMENT_CHARACTER);
+            }
+        }
+
+        if !chunk.invalid().is_empty() {
+            ret.push(char::REPLACEMENT_CHARACTER);
+        }
+    }
+
+    ret
+}
+
+/// Find the offset of the first invalid byte of an identifier, if any.
+///
+/// Mirrors the rules of [`crate::validate_id`].
+fn invalid_id_byte(id: &[u8]) -> Option<usize> {
+    let (Some(first), Some(last)) = (id.first(), id.last()) else {
+        return Some(0);
+    };
+
+    if let Some(pos) = id
+        .iter()
+        .position(|b| !matches!(b, b'a'..=b'z' | b'0'..=b'9' | b'-'))
+    {
+        return Some(pos);
+    }
+
+    if *first == b'-' {
+        Some(0)
+    } else if *last == b'-' {
+        Some(id.len() - 1)
+    } else {
+        None
+    }
+}
+
+/// Find the offset of the first invalid byte of a field, if any.
+///
+/// All characters allowed by a [`Charset`] are ASCII, so the bytes can be checked individually
+/// without first validating the field is UTF-8.
+fn invalid_field_byte(field: &[u8], charset: Charset) -> Option<usize> {
+    if field.is_empty() {
+        return Some(0);
+    }
+
+    field
+        .iter()
+        .position(|&b| !b.is_ascii() || !charset.allows(b as char))
+}
@@ -0,0 +1,154 @@
+//! Algorithm parameters stored as `k=v,k2=v2` within a field of an MCF hash.
+
+use crate::{Error, Field, Result};
+use core::fmt;
+
+/// Delimiter between individual parameters: `,`.
+pub const PARAMS_DELIMITER: char = ',';
+
+/// Delimiter between a parameter's key and its value: `=`.
+pub const PAIR_DELIMITER: char = '=';
+
+/// Algorithm parameters in the conventional `k=v,k2=v2` form, e.g. `m=19456,t=2,p=1`.
+///
+/// Duplicate keys are rejected when parsing, since they're a sign of tampering or corruption which
+/// "last one wins" parsing would otherwise silently hide.
+///
+/// Keys are constrained to characters in the regexp range `[a-z0-9\-]`, and values to those
+/// allowed in a [`Field`] other than `,`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Params<'a>(&'a str);
+
+impl<'a> Params<'a> {
+    /// Parse [`Params`] from the given string.
+    ///
+    /// # Errors
+    /// - If `s` contains characters outside the allowed [`Field`] set
+    /// - If any parameter isn't of the form `k=v` with a non-empty key and value
+    /// - If any key appears more than once
+    pub fn new(s: &'a str) -> Result<Self> {
+        Field::new(s)?;
+        let params = Self(s);
+
+        for (i, (key, value)) in params.iter_unvalidated().enumerate() {
+            validate_key(key)?;
+
+            if value.is_empty() {
+                return Err(Error {});
+            }
+
+            if params.iter_unvalidated().take(i).any(|(k, _)| k == key) {
+                return Err(Error {});
+            }
+        }
+
+        Ok(params)
+    }
+
+    /// Borrow the parameters as a `str`.
+    pub fn as_str(self) -> &'a str {
+        self.0
+    }
+
+    /// Iterate over the `(key, value)` pairs of these parameters, in order.
+    pub fn iter(self) -> impl Iterator<Item = (&'a str, &'a str)> {
+        self.iter_unvalidated()
+    }
+
+    /// Get the value of the parameter with the given key, if present.
+    pub fn get(self, key: &str) -> Option<&'a str> {
+        self.iter().find(|&(k, _)| k == key).map(|(_, v)| v)
+    }
+
+    /// Find the first key which is out of order according to the given canonical ordering,
+    /// i.e. which appears after a key that `order` places after it.
+    ///
+    /// Keys which don't appear in `order` are ignored.
+    ///
+    /// This is the lenient counterpart to [`Params::check_order`] which merely reports the
+    /// offending key, for use when non-canonical hashes should be accepted but flagged
+    /// (e.g. for rehashing).
+    pub fn find_misordered(self, order: &[&str]) -> Option<&'a str> {
+        let mut prev = None;
+
+        for (key, _) in self.iter() {
+            let Some(pos) = order.iter().position(|&k| k == key) else {
+                continue;
+            };
+
+            if prev.is_some_and(|prev| pos < prev) {
+                return Some(key);
+            }
+
+            prev = Some(pos);
+        }
+
+        None
+    }
+
+    /// Ensure the keys of these parameters follow the given canonical ordering.
+    ///
+    /// Keys which don't appear in `order` are ignored.
+    ///
+    /// # Errors
+    /// - If any key is out of order (see [`Params::find_misordered`])
+    pub fn check_order(self, order: &[&str]) -> Result<()> {
+        match self.find_misordered(order) {
+            Some(_) => Err(Error {}),
+            None => Ok(()),
+        }
+    }
+
+    /// Iterate over `(key, value)` pairs without validating them.
+    fn iter_unvalidated(self) -> impl Iterator<Item = (&'a str, &'a str)> {
+        self.0
+            .split(PARAMS_DELIMITER)
+            .map(|param| param.split_once(PAIR_DELIMITER).unwrap_or((param, "")))
+    }
+}
+
+impl AsRef<str> for Params<'_> {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl fmt::Display for Params<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+impl<'a> TryFrom<Field<'a>> for Params<'a> {
+    type Error = Error;
+
+    fn try_from(field: Field<'a>) -> Result<Self> {
+        Self::new(field.as_str())
+    }
+}
+
+impl<'a> TryFrom<&'a str> for Params<'a> {
+    type Error = Error;
+
+    fn try_from(s: &'a str) -> Result<Self> {
+        Self::new(s)
+    }
+}
+
+/// Validate a parameter key is well-formed.
+///
+/// Allowed characters match the regex: `[a-z0-9\-]`.
+fn validate_key(key: &str) -> Result<()> {
+    if key.is_empty() {
+        return Err(Error {});
+    }
+
+    for c in key.chars() {
+        match c {
+            'a'..='z' | '0'..='9' | '-' => (),
+            _ => return Err(Error {}),
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,152 @@
+//! Structured representation of the conventional parts of an MCF hash.
+
+use crate::{Charset, Error, Field, PasswordHashRef, Result, fields::DELIMITER, validate_id};
+
+#[cfg(feature = "alloc")]
+use alloc::string::ToString;
+use core::fmt;
+
+#[cfg(feature = "alloc")]
+use crate::PasswordHash;
+
+/// Typed parts of a password hash following the common `$id$params$salt$hash` convention.
+///
+/// Many MCF algorithms serialize an optional parameter string (e.g. `rounds=100000`), followed by
+/// an optional salt, followed by the hash output itself. This type gives algorithm implementations
+/// a structured intermediate representation to build or inspect rather than manipulating
+/// positional fields directly.
+///
+/// Each part is validated when it's set, so any [`McfParts`] can be rendered into a well-formed
+/// MCF hash, either through its [`fmt::Display`] impl or [`McfParts::to_password_hash`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct McfParts<'a> {
+    /// Algorithm identifier.
+    id: &'a str,
+
+    /// Algorithm-specific parameters.
+    params: Option<Field<'a>>,
+
+    /// Salt.
+    salt: Option<Field<'a>>,
+
+    /// Hash output.
+    hash: Field<'a>,
+
+    /// Characters allowed in the fields.
+    charset: Charset,
+}
+
+impl<'a> McfParts<'a> {
+    /// Create new [`McfParts`] from an algorithm identifier and hash output field.
+    ///
+    /// # Errors
+    /// - If `id` is not a valid algorithm identifier
+    /// - If `hash` contains characters outside the allowed [`Field`] set
+    pub fn new(id: &'a str, hash: &'a str) -> Result<Self> {
+        validate_id(id)?;
+
+        Ok(Self {
+            id,
+            params: None,
+            salt: None,
+            hash: Field::new(hash)?,
+            charset: Charset::STANDARD,
+        })
+    }
+
+    /// Set the algorithm-specific parameters field.
+    pub fn with_params(mut self, params: &'a str) -> Result<Self> {
+        self.params = Some(Field::new_with_charset(params, self.charset)?);
+        Ok(self)
+    }
+
+    /// Set the salt field.
+    pub fn with_salt(mut self, salt: &'a str) -> Result<Self> {
+        self.salt = Some(Field::new_with_charset(salt, self.charset)?);
+        Ok(self)
+    }
+
+    /// Get the algorithm identifier.
+    pub fn id(&self) -> &'a str {
+        self.id
+    }
+
+    /// Get the algorithm-specific parameters field, if present.
+    pub fn params(&self) -> Option<Field<'a>> {
+        self.params
+    }
+
+    /// Get the salt field, if present.
+    pub fn salt(&self) -> Option<Field<'a>> {
+        self.salt
+    }
+
+    /// Get the hash output field.
+    pub fn hash(&self) -> Field<'a> {
+        self.hash
+    }
+
+    /// Get the [`Charset`] the fields are validated under.
+    ///
+    /// This is [`Charset::STANDARD`] unless these parts were split from a hash parsed under
+    /// another policy.
+    pub fn charset(&self) -> Charset {
+        self.charset
+    }
+
+    /// Render these parts as an owned [`PasswordHash`].
+    #[cfg(feature = "alloc")]
+    pub fn to_password_hash(&self) -> PasswordHash {
+        PasswordHash::new_with_charset(self.to_string(), self.charset).expect(crate::INVARIANT_MSG)
+    }
+
+    /// Iterate over the fields which follow the identifier, in order.
+    fn fields(&self) -> impl Iterator<Item = Field<'a>> {
+        [self.params, self.salt, Some(self.hash)]
+            .into_iter()
+            .flatten()
+    }
+}
+
+impl fmt::Display for McfParts<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{DELIMITER}{}", self.id)?;
+
+        for field in self.fields() {
+            write!(f, "{DELIMITER}{field}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> TryFrom<PasswordHashRef<'a>> for McfParts<'a> {
+    type Error = Error;
+
+    /// Interpret the fields of the given hash positionally:
+    ///
+    /// - `$id$hash`
+    /// - `$id$salt$hash`
+    /// - `$id$params$salt$hash`
+    fn try_from(hash: PasswordHashRef<'a>) -> Result<Self> {
+        let mut fields = hash.fields();
+        let (params, salt, output) = match (fields.next(), fields.next(), fields.next()) {
+            (Some(output), None, None) => (None, None, output),
+            (Some(salt), Some(output), None) => (None, Some(salt), output),
+            (Some(params), Some(salt), Some(output)) => (Some(params), Some(salt), output),
+            _ => return Err(Error {}),
+        };
+
+        if fields.next().is_some() {
+            return Err(Error {});
+        }
+
+        Ok(Self {
+            id: hash.id(),
+            params,
+            salt,
+            hash: output,
+            charset: hash.charset(),
+        })
+    }
+}
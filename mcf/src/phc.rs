@@ -0,0 +1,49 @@
+//! Interop with the [PHC string format] used by the [`password-hash`] crate, e.g.
+//! `$argon2id$v=19$m=19456,t=2,p=1$salt$hash`.
+//!
+//! PHC strings are a subset of MCF, so any PHC string can be represented as an MCF hash, whereas
+//! only MCF hashes which follow the PHC conventions (e.g. unpadded "B64" salt and hash fields)
+//! can be converted into a PHC [`password_hash::PasswordHash`].
+//!
+//! [PHC string format]: https://github.com/P-H-C/phc-string-format/blob/master/phc-sf-spec.md
+//! [`password-hash`]: https://docs.rs/password-hash
+
+use crate::{Error, PasswordHashRef, Result};
+
+#[cfg(feature = "alloc")]
+use {crate::PasswordHash, alloc::string::ToString};
+
+impl<'a> TryFrom<PasswordHashRef<'a>> for password_hash::PasswordHash<'a> {
+    type Error = Error;
+
+    fn try_from(hash: PasswordHashRef<'a>) -> Result<Self> {
+        password_hash::PasswordHash::new(hash.as_str()).map_err(|_| Error {})
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> TryFrom<&'a PasswordHash> for password_hash::PasswordHash<'a> {
+    type Error = Error;
+
+    fn try_from(hash: &'a PasswordHash) -> Result<Self> {
+        hash.as_mcf_hash_ref().try_into()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl TryFrom<&password_hash::PasswordHash<'_>> for PasswordHash {
+    type Error = Error;
+
+    fn try_from(hash: &password_hash::PasswordHash<'_>) -> Result<Self> {
+        PasswordHash::new(hash.to_string())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl TryFrom<password_hash::PasswordHash<'_>> for PasswordHash {
+    type Error = Error;
+
+    fn try_from(hash: password_hash::PasswordHash<'_>) -> Result<Self> {
+        Self::try_from(&hash)
+    }
+}
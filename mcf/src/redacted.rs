@@ -0,0 +1,63 @@
+//! Redacted rendering of password hashes, e.g. for logging.
+
+use crate::{PasswordHashRef, fields::DELIMITER};
+use core::fmt;
+
+/// Placeholder rendered in place of redacted fields.
+const REDACTED: &str = "<redacted>";
+
+/// Wrapper around a [`PasswordHashRef`] whose [`fmt::Display`] and [`fmt::Debug`] impls mask the
+/// salt and hash output fields, so a password hash can be logged without leaking verifier
+/// material:
+///
+/// ```
+/// use mcf::PasswordHashRef;
+///
+/// let hash = PasswordHashRef::new("$6$rounds=100000$exn6tVc2j/MZD8uG$BI1Xh8qQSK9J4m14")?;
+/// assert_eq!(
+///     hash.redacted().to_string(),
+///     "$6$rounds=100000$<redacted>$<redacted>"
+/// );
+/// # Ok::<(), mcf::Error>(())
+/// ```
+///
+/// Fields are interpreted positionally following the `$id$params$salt$hash` convention of
+/// [`McfParts`][`crate::McfParts`]: the last two fields are masked, and any fields preceding them
+/// are treated as parameters and rendered as-is. Hashes with fewer fields are masked entirely,
+/// which may mask parameters of algorithms which don't follow this convention, e.g. the cost of
+/// bcrypt.
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub struct Redacted<'a>(PasswordHashRef<'a>);
+
+impl<'a> Redacted<'a> {
+    /// Wrap the given hash.
+    pub fn new(hash: PasswordHashRef<'a>) -> Self {
+        Self(hash)
+    }
+}
+
+impl fmt::Display for Redacted<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{DELIMITER}{}", self.0.id())?;
+
+        let n_params = self.0.fields().count().saturating_sub(2);
+
+        for (i, field) in self.0.fields().enumerate() {
+            if i < n_params {
+                write!(f, "{DELIMITER}{field}")?;
+            } else {
+                write!(f, "{DELIMITER}{REDACTED}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Redacted<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Redacted")
+            .field(&format_args!("\"{self}\""))
+            .finish()
+    }
+}
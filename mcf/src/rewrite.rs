@@ -0,0 +1,181 @@
+//! Bulk rewriting of algorithm identifiers, e.g. normalizing `$2a$` bcrypt hashes to `$2b$`.
+
+use crate::{Charset, Error, Field, PasswordHash, PasswordHashRef, Result, fields, validate_id};
+use alloc::{boxed::Box, string::String};
+use core::fmt;
+
+/// Transformation applied to the fields of the hashes matched by a [`Rewrite`].
+type FieldMap<'a> = dyn Fn(usize, Field<'_>) -> Result<Option<String>> + 'a;
+
+/// Rule replacing the algorithm identifier of password hashes, optionally transforming their
+/// fields, while preserving everything else byte-for-byte.
+///
+/// Rules are applied to individual hashes with [`Rewrite::apply`], or to a stream of hashes,
+/// e.g. the records of a user database, with [`Rewrite::apply_all`], which reports a
+/// [`RewriteOutcome`] for every record:
+///
+/// ```
+/// use mcf::{Rewrite, RewriteOutcome};
+///
+/// let rewrite = Rewrite::new("2a", "2b")?;
+/// let records = [
+///     "$2a$12$R9h/cIPz0gi.URNNX3kh2OPST9/PgBkqquzi.Ss7KIUgO2t0jWMUW",
+///     "$6$exn6tVc2j/MZD8uG$BI1Xh8qQSK9J4m14uwy7abn.ctj/TIAzlaVCto0MQrOFIeTXsc1iwzH16XEWo/a7c7Y9eVJvufVzYAs4EsPOy0",
+/// ];
+///
+/// let mut outcomes = rewrite.apply_all(records);
+/// let Some(RewriteOutcome::Rewritten(hash)) = outcomes.next() else { unreachable!() };
+/// assert_eq!(hash.as_str(), "$2b$12$R9h/cIPz0gi.URNNX3kh2OPST9/PgBkqquzi.Ss7KIUgO2t0jWMUW");
+/// assert!(matches!(outcomes.next(), Some(RewriteOutcome::Unchanged(_))));
+/// # Ok::<(), mcf::Error>(())
+/// ```
+pub struct Rewrite<'a> {
+    /// Identifier of the hashes to rewrite.
+    from: &'a str,
+
+    /// Identifier replacing `from`.
+    to: &'a str,
+
+    /// Charset under which the hashes are parsed.
+    charset: Charset,
+
+    /// Transformation of the fields of the rewritten hashes.
+    field_map: Option<Box<FieldMap<'a>>>,
+}
+
+impl<'a> Rewrite<'a> {
+    /// Create a rule replacing the identifier `from` with `to`, leaving the fields unchanged.
+    ///
+    /// # Errors
+    /// - If either identifier is malformed (see [`PasswordHash::from_id`])
+    pub fn new(from: &'a str, to: &'a str) -> Result<Self> {
+        validate_id(from)?;
+        validate_id(to)?;
+
+        Ok(Self {
+            from,
+            to,
+            charset: Charset::STANDARD,
+            field_map: None,
+        })
+    }
+
+    /// Parse the hashes under the given [`Charset`] rather than [`Charset::STANDARD`].
+    pub fn with_charset(mut self, charset: Charset) -> Self {
+        self.charset = charset;
+        self
+    }
+
+    /// Transform the fields of the rewritten hashes with `f`, which is called with the index and
+    /// value of each field following the identifier.
+    ///
+    /// It returns `Ok(None)` to keep a field unchanged, `Ok(Some(field))` to replace it, or an
+    /// error to reject the hash, e.g. if it uses parameters the target algorithm doesn't support.
+    /// Replacement fields are validated under the [`Charset`] of this rule.
+    pub fn with_field_map(
+        mut self,
+        f: impl Fn(usize, Field<'_>) -> Result<Option<String>> + 'a,
+    ) -> Self {
+        self.field_map = Some(Box::new(f));
+        self
+    }
+
+    /// Get the identifier of the hashes rewritten by this rule.
+    pub fn from_id(&self) -> &'a str {
+        self.from
+    }
+
+    /// Get the identifier replacing [`Rewrite::from_id`].
+    pub fn to_id(&self) -> &'a str {
+        self.to
+    }
+
+    /// Apply this rule to a single hash.
+    pub fn apply<'h>(&self, hash: &'h str) -> RewriteOutcome<'h> {
+        let hash = match PasswordHashRef::new_with_charset(hash, self.charset) {
+            Ok(hash) => hash,
+            Err(err) => return RewriteOutcome::Invalid(err),
+        };
+
+        if hash.id() != self.from {
+            return RewriteOutcome::Unchanged(hash);
+        }
+
+        match self.rewrite(hash) {
+            Ok(rewritten) => RewriteOutcome::Rewritten(rewritten),
+            Err(err) => RewriteOutcome::Invalid(err),
+        }
+    }
+
+    /// Apply this rule to each hash of the given stream, in order.
+    pub fn apply_all<'h>(
+        &self,
+        hashes: impl IntoIterator<Item = &'h str>,
+    ) -> impl Iterator<Item = RewriteOutcome<'h>> {
+        hashes.into_iter().map(|hash| self.apply(hash))
+    }
+
+    /// Rewrite a hash whose identifier matches this rule.
+    fn rewrite(&self, hash: PasswordHashRef<'_>) -> Result<PasswordHash> {
+        let s = hash.as_str();
+        let mut rewritten = String::with_capacity(s.len() - self.from.len() + self.to.len());
+        rewritten.push(fields::DELIMITER);
+        rewritten.push_str(self.to);
+
+        match &self.field_map {
+            Some(field_map) => {
+                for (index, field) in hash.fields().enumerate() {
+                    rewritten.push(fields::DELIMITER);
+
+                    match field_map(index, field)? {
+                        Some(replacement) => rewritten.push_str(&replacement),
+                        None => rewritten.push_str(field.as_str()),
+                    }
+                }
+            }
+            None => rewritten.push_str(&s[hash.id_range().end..]),
+        }
+
+        PasswordHash::new_with_charset(rewritten, self.charset)
+    }
+}
+
+impl fmt::Debug for Rewrite<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Rewrite")
+            .field("from", &self.from)
+            .field("to", &self.to)
+            .field("charset", &self.charset)
+            .field("field_map", &self.field_map.is_some())
+            .finish()
+    }
+}
+
+/// Outcome of applying a [`Rewrite`] to a hash.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RewriteOutcome<'h> {
+    /// The hash matched the rule and was rewritten.
+    Rewritten(PasswordHash),
+
+    /// The hash has another identifier, and was left unchanged.
+    Unchanged(PasswordHashRef<'h>),
+
+    /// The hash is malformed, or was rejected by the field transformation.
+    Invalid(Error),
+}
+
+impl RewriteOutcome<'_> {
+    /// Get the hash to store after the rewrite, or `None` if it's invalid.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::Rewritten(hash) => Some(hash.as_str()),
+            Self::Unchanged(hash) => Some(hash.as_str()),
+            Self::Invalid(_) => None,
+        }
+    }
+
+    /// Was the hash rewritten?
+    pub fn is_rewritten(&self) -> bool {
+        matches!(self, Self::Rewritten(_))
+    }
+}
@@ -0,0 +1,153 @@
+//! Support for the `rounds=N` parameter convention used by the SHA-crypt family.
+
+use crate::{Error, Field, Result};
+use core::fmt;
+
+/// Prefix of a field following the `rounds=N` convention.
+pub const ROUNDS_PREFIX: &str = "rounds=";
+
+/// Legal range and default value of the `rounds=N` parameter of an algorithm.
+///
+/// Algorithms such as SHA-crypt (`$5$`/`$6$`) store their iteration count in an optional first
+/// field of the form `rounds=N`. The field is omitted when `N` equals the algorithm's default,
+/// and values outside the legal range are clamped rather than rejected by the reference
+/// implementation.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RoundsRange {
+    /// Minimum number of rounds.
+    min: u32,
+
+    /// Maximum number of rounds.
+    max: u32,
+
+    /// Number of rounds used when the `rounds=N` field is omitted.
+    default: u32,
+}
+
+impl RoundsRange {
+    /// Rounds range used by SHA-crypt, i.e. `$5$` (SHA-256) and `$6$` (SHA-512).
+    pub const SHA_CRYPT: Self = Self::new(1_000, 999_999_999, 5_000);
+
+    /// Create a new [`RoundsRange`].
+    ///
+    /// # Panics
+    /// - If `default` is outside of `min..=max`
+    pub const fn new(min: u32, max: u32, default: u32) -> Self {
+        assert!(min <= default && default <= max, "invalid rounds range");
+        Self { min, max, default }
+    }
+
+    /// Minimum number of rounds.
+    pub const fn min(self) -> u32 {
+        self.min
+    }
+
+    /// Maximum number of rounds.
+    pub const fn max(self) -> u32 {
+        self.max
+    }
+
+    /// Number of rounds used when the `rounds=N` field is omitted.
+    pub const fn default(self) -> u32 {
+        self.default
+    }
+
+    /// Clamp the given number of rounds to the legal range.
+    pub fn clamp(self, rounds: u64) -> u32 {
+        u32::try_from(rounds)
+            .unwrap_or(u32::MAX)
+            .clamp(self.min, self.max)
+    }
+
+    /// Ensure the given number of rounds is within the legal range.
+    ///
+    /// # Errors
+    /// - If `rounds` is outside of the legal range
+    pub fn check(self, rounds: u32) -> Result<u32> {
+        if (self.min..=self.max).contains(&rounds) {
+            Ok(rounds)
+        } else {
+            Err(Error {})
+        }
+    }
+
+    /// Is the given number of rounds the default, meaning the `rounds=N` field should be omitted
+    /// from the canonical encoding?
+    pub fn is_default(self, rounds: u32) -> bool {
+        rounds == self.default
+    }
+
+    /// Parse a field following the `rounds=N` convention, strictly rejecting values outside of
+    /// the legal range.
+    ///
+    /// Returns `None` if the field doesn't start with `rounds=`, e.g. if the `rounds=N` field was
+    /// omitted and `field` is the salt instead.
+    ///
+    /// # Errors
+    /// - If `N` is not a decimal number
+    /// - If `N` is outside of the legal range
+    pub fn parse(self, field: Field<'_>) -> Option<Result<u32>> {
+        let rounds = parse_rounds(field)?;
+        Some(rounds.and_then(|rounds| self.check(u32::try_from(rounds).map_err(|_| Error {})?)))
+    }
+
+    /// Parse a field following the `rounds=N` convention, clamping values outside of the legal
+    /// range as the reference implementation does.
+    ///
+    /// Returns `None` if the field doesn't start with `rounds=`.
+    ///
+    /// # Errors
+    /// - If `N` is not a decimal number
+    pub fn parse_clamped(self, field: Field<'_>) -> Option<Result<u32>> {
+        let rounds = parse_rounds(field)?;
+        Some(rounds.map(|rounds| self.clamp(rounds)))
+    }
+
+    /// Get the `rounds=N` field to include in the canonical encoding of a hash computed with the
+    /// given number of rounds, or `None` if it should be omitted because `rounds` is the default.
+    pub fn canonical(self, rounds: u32) -> Option<RoundsField> {
+        if self.is_default(rounds) {
+            None
+        } else {
+            Some(RoundsField(rounds))
+        }
+    }
+}
+
+/// `rounds=N` field, rendered through its [`fmt::Display`] impl.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RoundsField(u32);
+
+impl RoundsField {
+    /// Number of rounds.
+    pub fn rounds(self) -> u32 {
+        self.0
+    }
+}
+
+impl fmt::Display for RoundsField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{ROUNDS_PREFIX}{}", self.0)
+    }
+}
+
+/// Parse the `N` of a `rounds=N` field, saturating on overflow.
+fn parse_rounds(field: Field<'_>) -> Option<Result<u64>> {
+    let digits = field.as_str().strip_prefix(ROUNDS_PREFIX)?;
+
+    if digits.is_empty() {
+        return Some(Err(Error {}));
+    }
+
+    let mut rounds = 0u64;
+
+    for c in digits.chars() {
+        let Some(digit) = c.to_digit(10) else {
+            return Some(Err(Error {}));
+        };
+
+        rounds = rounds.saturating_mul(10).saturating_add(digit.into());
+    }
+
+    Some(Ok(rounds))
+}
@@ -0,0 +1,291 @@
+//! Support for the cost parameters of scrypt (`$7$`) and yescrypt (`$y$`/`$gy$`).
+//!
+//! Unlike most MCF algorithms, these formats don't store their parameters as `k=v` pairs, but as
+//! integers encoded with the `crypt(3)` Base64 alphabet (a.k.a. "itoa64"):
+//!
+//! ```text
+//! ./0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz
+//! ```
+
+use crate::{Error, Field, Result};
+
+/// Algorithm identifier of scrypt hashes in the format introduced by libxcrypt: `7`.
+pub const SCRYPT_ID: &str = "7";
+
+/// Algorithm identifier of yescrypt hashes: `y`.
+pub const YESCRYPT_ID: &str = "y";
+
+/// Algorithm identifier of GOST R 34.11-2012 hashes of yescrypt hashes: `gy`.
+pub const GOST_YESCRYPT_ID: &str = "gy";
+
+/// `crypt(3)` Base64 alphabet used to encode the parameters.
+const ITOA64: &[u8; 64] = b"./0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// Length of the parameters prefixing the salt of `$7$` hashes: `N` (1 character), `r` and `p`
+/// (5 characters each).
+const SCRYPT_PARAMS_LEN: usize = 11;
+
+/// Largest supported base 2 logarithm of `N`.
+const MAX_N_LOG2: u32 = 63;
+
+/// yescrypt `YESCRYPT_RW` flag, which is the smallest "flavor" encoding flags other than
+/// `YESCRYPT_WORM`.
+const YESCRYPT_RW: u32 = 0x002;
+
+/// Mask of the yescrypt flags which can be encoded in the flavor of `YESCRYPT_RW` hashes.
+const YESCRYPT_RW_FLAVOR_MASK: u32 = 0x3fc;
+
+/// Cost parameters of an scrypt hash in the `$7$` format, e.g. `$7$CU..../....salt$hash`.
+///
+/// The parameters prefix the salt in the first field following the identifier, without any
+/// delimiter: `N` is encoded as its base 2 logarithm in a single character, followed by `r` and
+/// `p` as 30-bit little-endian integers of 5 characters each.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ScryptParams {
+    /// Base 2 logarithm of the CPU/memory cost `N`.
+    n_log2: u32,
+
+    /// Block size.
+    r: u32,
+
+    /// Parallelization.
+    p: u32,
+}
+
+impl ScryptParams {
+    /// Parse the parameters prefixing the given field, returning them along with the salt which
+    /// follows them.
+    ///
+    /// # Errors
+    /// - If the field is shorter than the parameters
+    /// - If the parameters contain characters outside the itoa64 alphabet
+    /// - If `N` exceeds `2^63`
+    pub fn parse(field: Field<'_>) -> Result<(Self, &str)> {
+        let s = field.as_str();
+        let params = s.get(..SCRYPT_PARAMS_LEN).ok_or(Error {})?.as_bytes();
+        let salt = &s[SCRYPT_PARAMS_LEN..];
+
+        let n_log2 = decode_char(params[0])?;
+        if n_log2 > MAX_N_LOG2 {
+            return Err(Error {});
+        }
+
+        Ok((
+            Self {
+                n_log2,
+                r: decode_fixed(&params[1..6])?,
+                p: decode_fixed(&params[6..11])?,
+            },
+            salt,
+        ))
+    }
+
+    /// Base 2 logarithm of the CPU/memory cost `N`.
+    pub fn n_log2(self) -> u32 {
+        self.n_log2
+    }
+
+    /// CPU/memory cost `N`.
+    pub fn n(self) -> u64 {
+        1 << self.n_log2
+    }
+
+    /// Block size `r`.
+    pub fn r(self) -> u32 {
+        self.r
+    }
+
+    /// Parallelization `p`.
+    pub fn p(self) -> u32 {
+        self.p
+    }
+}
+
+/// Cost parameters of a yescrypt hash, e.g. `$y$j9T$salt$hash`.
+///
+/// The parameters are stored in the first field following the identifier, as a sequence of
+/// variable-length integers: the flags (encoded as a "flavor"), the base 2 logarithm of `N`, and
+/// `r`, optionally followed by a bitmask indicating which of `p`, `t`, `g`, and the base 2
+/// logarithm of `NROM` are present. Absent parameters take their default value.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct YescryptParams {
+    /// Flags selecting the yescrypt variant (e.g. `YESCRYPT_DEFAULTS`).
+    flags: u32,
+
+    /// Base 2 logarithm of the CPU/memory cost `N`.
+    n_log2: u32,
+
+    /// Block size.
+    r: u32,
+
+    /// Parallelization.
+    p: u32,
+
+    /// Additional time cost.
+    t: u32,
+
+    /// Number of hash upgrades.
+    g: u32,
+
+    /// Base 2 logarithm of the size of the ROM, or 0 if no ROM is used.
+    nrom_log2: u32,
+}
+
+impl YescryptParams {
+    /// Parse the parameters from the given field.
+    ///
+    /// # Errors
+    /// - If the field contains characters outside the itoa64 alphabet
+    /// - If the field is truncated, or contains trailing characters
+    /// - If the flags, `N`, or `NROM` are out of range
+    pub fn parse(field: Field<'_>) -> Result<Self> {
+        let mut src = field.as_str().as_bytes();
+
+        let flavor = decode_var(&mut src, 0)?;
+        let flags = if flavor < YESCRYPT_RW {
+            flavor
+        } else if flavor <= YESCRYPT_RW + (YESCRYPT_RW_FLAVOR_MASK >> 2) {
+            YESCRYPT_RW + ((flavor - YESCRYPT_RW) << 2)
+        } else {
+            return Err(Error {});
+        };
+
+        let n_log2 = decode_var(&mut src, 1)?;
+        if n_log2 > MAX_N_LOG2 {
+            return Err(Error {});
+        }
+
+        let mut params = Self {
+            flags,
+            n_log2,
+            r: decode_var(&mut src, 1)?,
+            p: 1,
+            t: 0,
+            g: 0,
+            nrom_log2: 0,
+        };
+
+        if !src.is_empty() {
+            let have = decode_var(&mut src, 1)?;
+            if have > 0xf {
+                return Err(Error {});
+            }
+
+            if have & 1 != 0 {
+                params.p = decode_var(&mut src, 2)?;
+            }
+
+            if have & 2 != 0 {
+                params.t = decode_var(&mut src, 1)?;
+            }
+
+            if have & 4 != 0 {
+                params.g = decode_var(&mut src, 1)?;
+            }
+
+            if have & 8 != 0 {
+                params.nrom_log2 = decode_var(&mut src, 1)?;
+                if params.nrom_log2 > MAX_N_LOG2 {
+                    return Err(Error {});
+                }
+            }
+        }
+
+        if !src.is_empty() {
+            return Err(Error {});
+        }
+
+        Ok(params)
+    }
+
+    /// Flags selecting the yescrypt variant.
+    pub fn flags(self) -> u32 {
+        self.flags
+    }
+
+    /// Base 2 logarithm of the CPU/memory cost `N`.
+    pub fn n_log2(self) -> u32 {
+        self.n_log2
+    }
+
+    /// CPU/memory cost `N`.
+    pub fn n(self) -> u64 {
+        1 << self.n_log2
+    }
+
+    /// Block size `r`.
+    pub fn r(self) -> u32 {
+        self.r
+    }
+
+    /// Parallelization `p`.
+    pub fn p(self) -> u32 {
+        self.p
+    }
+
+    /// Additional time cost `t`.
+    pub fn t(self) -> u32 {
+        self.t
+    }
+
+    /// Number of hash upgrades `g`.
+    pub fn g(self) -> u32 {
+        self.g
+    }
+
+    /// Size of the ROM `NROM`, if any.
+    pub fn nrom(self) -> Option<u64> {
+        (self.nrom_log2 != 0).then(|| 1 << self.nrom_log2)
+    }
+}
+
+/// Decode a single itoa64 character.
+fn decode_char(c: u8) -> Result<u32> {
+    ITOA64
+        .iter()
+        .position(|&a| a == c)
+        .map(|pos| pos as u32)
+        .ok_or(Error {})
+}
+
+/// Decode a 30-bit little-endian integer, as used by `$7$` hashes.
+fn decode_fixed(src: &[u8]) -> Result<u32> {
+    src.iter()
+        .rev()
+        .try_fold(0, |value, &c| Ok((value << 6) | decode_char(c)?))
+}
+
+/// Decode a variable-length integer no smaller than `min` from the beginning of `src`, as used
+/// by yescrypt hashes, advancing `src` past it.
+///
+/// The first character determines the length of the encoding: the 64 characters are split in
+/// ranges of decreasing size, the first one (48 characters) encoding values on a single
+/// character, the next one (8 characters) on two characters, and so on.
+fn decode_var(src: &mut &[u8], min: u32) -> Result<u32> {
+    let (&first, rest) = src.split_first().ok_or(Error {})?;
+    *src = rest;
+
+    let c = decode_char(first)?;
+    let (mut start, mut end, mut chars, mut bits) = (0u32, 47u32, 1u32, 0u32);
+    let mut value = u64::from(min);
+
+    while c > end {
+        value += u64::from(end + 1 - start) << bits;
+        start = end + 1;
+        end = start + (62 - end) / 2;
+        chars += 1;
+        bits += 6;
+    }
+
+    value += u64::from(c - start) << bits;
+
+    for _ in 1..chars {
+        let (&next, rest) = src.split_first().ok_or(Error {})?;
+        *src = rest;
+
+        bits -= 6;
+        value += u64::from(decode_char(next)?) << bits;
+    }
+
+    u32::try_from(value).map_err(|_| Error {})
+}
@@ -0,0 +1,38 @@
+//! [`serde`] support: hashes are serialized as strings, and validated when deserialized.
+//!
+//! [`PasswordHashRef`] borrows from the input, so it can only be deserialized from formats which
+//! don't need to unescape strings, and fields holding it need the `#[serde(borrow)]` attribute.
+
+use crate::PasswordHashRef;
+use ::serde::{Deserialize, Serialize, de, ser};
+
+#[cfg(feature = "alloc")]
+use {crate::PasswordHash, alloc::string::String};
+
+impl<'de: 'a, 'a> Deserialize<'de> for PasswordHashRef<'a> {
+    fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use de::Error;
+        PasswordHashRef::new(<&'de str>::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+impl Serialize for PasswordHashRef<'_> {
+    fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'de> Deserialize<'de> for PasswordHash {
+    fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use de::Error;
+        PasswordHash::new(String::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Serialize for PasswordHash {
+    fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
@@ -2,7 +2,11 @@
 
 #![cfg(feature = "alloc")]
 
-use mcf::PasswordHash;
+use core::cmp::Ordering;
+use mcf::{
+    Charset, Cost, Field, HashList, McfParts, Params, PasswordHash, PasswordHashRef, Rewrite,
+    RewriteOutcome, RoundsRange, ScryptParams, YescryptParams, ids,
+};
 
 #[cfg(feature = "base64")]
 use {hex_literal::hex, mcf::Base64};
@@ -72,6 +76,94 @@ fn parse_sha512_hash() {
     assert_eq!(None, fields.next());
 }
 
+#[test]
+fn id_and_fields_ranges() {
+    let hash = PasswordHashRef::new(SHA512_HASH).unwrap();
+    assert_eq!(hash.id_range(), 1..2);
+    assert_eq!(&SHA512_HASH[hash.id_range()], "6");
+    assert_eq!(
+        &SHA512_HASH[hash.fields_range()],
+        &SHA512_HASH["$6$".len()..]
+    );
+
+    let hash = PasswordHashRef::new("$6").unwrap();
+    assert_eq!(hash.id_range(), 1..2);
+    assert!(hash.fields_range().is_empty());
+    assert_eq!(hash.fields().next(), None);
+
+    // Boundaries are kept up-to-date as fields are modified
+    let mut hash = PasswordHash::from_id("sha512-crypt").unwrap();
+    assert_eq!(&hash.as_str()[hash.id_range()], "sha512-crypt");
+    assert!(hash.fields_range().is_empty());
+
+    hash.push_str("rounds=1000").unwrap();
+    assert_eq!(hash.id(), "sha512-crypt");
+    assert_eq!(&hash.as_str()[hash.fields_range()], "rounds=1000");
+    assert_eq!(hash.fields().next().unwrap().as_str(), "rounds=1000");
+
+    hash.truncate_fields(0);
+    assert_eq!(hash.as_str(), "$sha512-crypt");
+    assert!(hash.fields_range().is_empty());
+    assert_eq!(hash.fields().next(), None);
+}
+
+#[test]
+fn relaxed_charset() {
+    const LEGACY_HASH: &str = "$legacy$salt_1$hash!";
+    assert!(PasswordHashRef::new(LEGACY_HASH).is_err());
+    assert!(PasswordHash::new(LEGACY_HASH).is_err());
+
+    let relaxed = Charset::with_extra("_!").unwrap();
+    assert!(!relaxed.is_standard());
+    assert!(relaxed.allows('_') && !relaxed.allows('~'));
+
+    let hash_ref = PasswordHashRef::new_with_charset(LEGACY_HASH, relaxed).unwrap();
+    assert_eq!(hash_ref.charset(), relaxed);
+    assert_eq!(hash_ref.fields().last().unwrap().as_str(), "hash!");
+
+    let parts = hash_ref.to_parts().unwrap();
+    assert_eq!(parts.charset(), relaxed);
+    assert_eq!(parts.to_password_hash().as_str(), LEGACY_HASH);
+
+    let mut hash = PasswordHash::new_with_charset(LEGACY_HASH, relaxed).unwrap();
+    assert_eq!(hash.charset(), relaxed);
+    hash.push_str("more_").unwrap();
+    assert!(hash.push_str("more~").is_err());
+
+    // The identifier is always validated strictly
+    assert!(PasswordHashRef::new_with_charset("$leg_acy$hash", relaxed).is_err());
+    assert_eq!(
+        PasswordHashRef::new(SHA512_HASH).unwrap().charset(),
+        Charset::STANDARD
+    );
+}
+
+#[test]
+fn relaxed_charset_invalid() {
+    assert!(Charset::with_extra("$").is_err());
+    assert!(Charset::with_extra(" ").is_err());
+    assert!(Charset::with_extra("\u{e9}").is_err());
+    assert!(Charset::with_extra("./").unwrap().is_standard());
+}
+
+#[test]
+fn relaxed_charset_comparisons() {
+    let relaxed = Charset::with_extra("_").unwrap();
+
+    let hash_ref = PasswordHashRef::new(SHA512_HASH).unwrap();
+    let relaxed_ref = PasswordHashRef::new_with_charset(SHA512_HASH, relaxed).unwrap();
+    assert_eq!(hash_ref, relaxed_ref);
+    assert_eq!(hash_ref.cmp(&relaxed_ref), Ordering::Equal);
+
+    let hash = PasswordHash::new(SHA512_HASH).unwrap();
+    let relaxed_hash = PasswordHash::new_with_charset(SHA512_HASH, relaxed).unwrap();
+    assert_eq!(hash, relaxed_hash);
+    assert_eq!(hash.cmp(&relaxed_hash), Ordering::Equal);
+
+    let other = PasswordHashRef::new_with_charset("$6$rounds=1000$a_b", relaxed).unwrap();
+    assert!(other < hash_ref);
+}
+
 #[cfg(feature = "base64")]
 #[test]
 fn push_fields() {
@@ -80,3 +172,625 @@ fn push_fields() {
     hash.push_base64(EXAMPLE_HASH, Base64::ShaCrypt);
     assert_eq!(SHA512_HASH, hash.as_str());
 }
+
+#[test]
+fn set_field() {
+    let mut hash: PasswordHash = SHA512_HASH.parse().unwrap();
+    hash.set_field(0, Field::new("rounds=5000").unwrap())
+        .unwrap();
+    assert_eq!(
+        hash.as_str(),
+        SHA512_HASH.replace("rounds=100000", "rounds=5000")
+    );
+
+    hash.set_field(2, Field::new("hash").unwrap()).unwrap();
+    assert_eq!(hash.as_str(), "$6$rounds=5000$exn6tVc2j/MZD8uG$hash");
+
+    assert!(hash.set_field(3, Field::new("x").unwrap()).is_err());
+}
+
+#[test]
+fn fields_outside_charset() {
+    let relaxed = Charset::with_extra("_").unwrap();
+    let field = Field::new_with_charset("salt_1", relaxed).unwrap();
+
+    let mut hash = PasswordHash::new("$6$rounds=5000$salt").unwrap();
+    assert!(hash.push_field(field).is_err());
+    assert!(hash.set_field(1, field).is_err());
+    assert_eq!(hash.as_str(), "$6$rounds=5000$salt");
+    assert_eq!(hash.to_parts().unwrap().to_password_hash(), hash);
+
+    let mut hash = PasswordHash::new_with_charset("$6$rounds=5000$salt", relaxed).unwrap();
+    hash.set_field(1, field).unwrap();
+    hash.push_field(Field::new("hash").unwrap()).unwrap();
+    assert_eq!(hash.as_str(), "$6$rounds=5000$salt_1$hash");
+    assert_eq!(hash.to_parts().unwrap().to_password_hash(), hash);
+}
+
+#[test]
+fn remove_field() {
+    let mut hash: PasswordHash = SHA512_HASH.parse().unwrap();
+    hash.remove_field(0).unwrap();
+    assert_eq!(hash.fields().count(), 2);
+    assert!(hash.as_str().starts_with("$6$exn6tVc2j/MZD8uG$"));
+
+    hash.remove_field(1).unwrap();
+    assert_eq!(hash.as_str(), "$6$exn6tVc2j/MZD8uG");
+
+    assert!(hash.remove_field(1).is_err());
+    hash.remove_field(0).unwrap();
+    assert_eq!(hash.as_str(), "$6");
+    assert!(hash.remove_field(0).is_err());
+}
+
+#[test]
+fn truncate_fields() {
+    let mut hash: PasswordHash = SHA512_HASH.parse().unwrap();
+    hash.truncate_fields(3);
+    assert_eq!(hash.as_str(), SHA512_HASH);
+
+    // Strip the hash output for re-computation
+    hash.truncate_fields(2);
+    assert_eq!(hash.as_str(), "$6$rounds=100000$exn6tVc2j/MZD8uG");
+    assert!(PasswordHash::new(hash.as_str()).is_ok());
+
+    hash.truncate_fields(0);
+    assert_eq!(hash.as_str(), "$6");
+}
+
+#[test]
+fn parts_round_trip() {
+    let hash: PasswordHash = SHA512_HASH.parse().unwrap();
+    let parts = hash.to_parts().unwrap();
+    assert_eq!("6", parts.id());
+    assert_eq!("rounds=100000", parts.params().unwrap().as_str());
+    assert_eq!("exn6tVc2j/MZD8uG", parts.salt().unwrap().as_str());
+    assert_eq!(
+        "BI1Xh8qQSK9J4m14uwy7abn.ctj/TIAzlaVCto0MQrOFIeTXsc1iwzH16XEWo/a7c7Y9eVJvufVzYAs4EsPOy0",
+        parts.hash().as_str()
+    );
+
+    assert_eq!(SHA512_HASH, parts.to_string());
+    assert_eq!(hash, parts.to_password_hash());
+}
+
+#[test]
+fn parts_builder() {
+    let parts = McfParts::new("6", "hash")
+        .unwrap()
+        .with_salt("salt")
+        .unwrap();
+    assert_eq!(None, parts.params());
+    assert_eq!("$6$salt$hash", parts.to_string());
+
+    let parts = parts.with_params("rounds=5000").unwrap();
+    assert_eq!(
+        "$6$rounds=5000$salt$hash",
+        parts.to_password_hash().as_str()
+    );
+
+    assert!(McfParts::new("6$", "hash").is_err());
+    assert!(McfParts::new("6", "").is_err());
+    assert!(parts.with_salt("$alt").is_err());
+}
+
+#[test]
+fn parts_field_count() {
+    let hash = PasswordHash::new("$1$hash").unwrap();
+    let parts = hash.to_parts().unwrap();
+    assert_eq!(None, parts.salt());
+    assert_eq!("hash", parts.hash().as_str());
+
+    assert!(PasswordHash::new("$6").unwrap().to_parts().is_err());
+    assert!(PasswordHash::new("$6$a$b$c$d").unwrap().to_parts().is_err());
+}
+
+#[test]
+fn params_parse() {
+    let params = Params::new("m=19456,t=2,p=1").unwrap();
+    assert_eq!(
+        params.iter().collect::<Vec<_>>(),
+        [("m", "19456"), ("t", "2"), ("p", "1")]
+    );
+    assert_eq!(params.get("t"), Some("2"));
+    assert_eq!(params.get("x"), None);
+
+    let hash = PasswordHash::new(SHA512_HASH).unwrap();
+    let field = hash.fields().next().unwrap();
+    assert_eq!(
+        Params::try_from(field).unwrap().get("rounds"),
+        Some("100000")
+    );
+}
+
+#[test]
+fn params_malformed() {
+    assert!(Params::new("").is_err());
+    assert!(Params::new("m").is_err());
+    assert!(Params::new("m=").is_err());
+    assert!(Params::new("=1").is_err());
+    assert!(Params::new("m=1,,t=2").is_err());
+    assert!(Params::new("M=1").is_err());
+    assert!(Params::new("m=1$t=2").is_err());
+}
+
+#[test]
+fn params_duplicate() {
+    assert!(Params::new("m=1,t=2,m=3").is_err());
+    assert!(Params::new("m=1,m=1").is_err());
+}
+
+#[test]
+fn params_order() {
+    const ORDER: &[&str] = &["m", "t", "p"];
+
+    let params = Params::new("m=19456,t=2,p=1").unwrap();
+    assert_eq!(params.find_misordered(ORDER), None);
+    assert!(params.check_order(ORDER).is_ok());
+
+    let params = Params::new("t=2,m=19456,x=0,p=1").unwrap();
+    assert_eq!(params.find_misordered(ORDER), Some("m"));
+    assert!(params.check_order(ORDER).is_err());
+
+    // Unknown keys are ignored
+    let params = Params::new("m=19456,keyid=abc,p=1").unwrap();
+    assert!(params.check_order(ORDER).is_ok());
+}
+
+#[test]
+fn list_parse() {
+    let joined = format!(" {SHA512_HASH} ; $y$j9T$salt$hash;;$2b$; $foo-$bar ;");
+    let list = HashList::new(&joined);
+
+    let hashes: Vec<_> = list.iter().collect();
+    assert_eq!(hashes.len(), 4);
+    assert_eq!(hashes[0].unwrap().as_str(), SHA512_HASH);
+    assert_eq!(hashes[1].unwrap().id(), "y");
+    assert!(hashes[2].is_err());
+    assert!(hashes[3].is_err());
+
+    assert_eq!(list.valid().count(), 2);
+    assert_eq!(list.primary().unwrap().id(), "6");
+    assert!(list.validate().is_err());
+    assert_eq!(
+        list.to_normalized_string(),
+        format!("{SHA512_HASH};$y$j9T$salt$hash")
+    );
+}
+
+#[test]
+fn list_separator() {
+    let list = HashList::with_separator("$1$a$b\n$5$c$d", '\n').unwrap();
+    assert!(list.validate().is_ok());
+    assert_eq!(
+        list.valid().map(PasswordHashRef::id).collect::<Vec<_>>(),
+        ["1", "5"]
+    );
+
+    for separator in ['$', ',', '=', 'a', '-'] {
+        assert!(HashList::with_separator("", separator).is_err());
+    }
+}
+
+#[test]
+fn list_join() {
+    let hashes = [
+        PasswordHashRef::new("$y$j9T$salt$hash").unwrap(),
+        PasswordHashRef::new(SHA512_HASH).unwrap(),
+    ];
+
+    let joined = HashList::join(hashes, ';').unwrap();
+    assert_eq!(joined, format!("$y$j9T$salt$hash;{SHA512_HASH}"));
+    assert!(HashList::new(&joined).valid().eq(hashes));
+    assert!(HashList::join(hashes, ',').is_err());
+}
+
+#[test]
+fn rounds_parse() {
+    let hash: PasswordHash = SHA512_HASH.parse().unwrap();
+    let parts = hash.to_parts().unwrap();
+    let range = RoundsRange::SHA_CRYPT;
+
+    assert_eq!(range.parse(parts.params().unwrap()), Some(Ok(100_000)));
+    assert_eq!(range.parse(parts.salt().unwrap()), None);
+
+    let field = |s| Field::new(s).unwrap();
+    assert!(range.parse(field("rounds=")).unwrap().is_err());
+    assert!(range.parse(field("rounds=-1")).unwrap().is_err());
+    assert!(range.parse(field("rounds=1x")).unwrap().is_err());
+    assert!(range.parse(field("rounds=999")).unwrap().is_err());
+    assert!(range.parse(field("rounds=99999999999")).unwrap().is_err());
+}
+
+#[test]
+fn rounds_clamp() {
+    let range = RoundsRange::SHA_CRYPT;
+    let field = |s| Field::new(s).unwrap();
+
+    assert_eq!(range.parse_clamped(field("rounds=10")), Some(Ok(1_000)));
+    assert_eq!(
+        range.parse_clamped(field("rounds=99999999999999999999999")),
+        Some(Ok(999_999_999))
+    );
+    assert!(range.parse_clamped(field("rounds=x")).unwrap().is_err());
+    assert_eq!(range.clamp(5_000), 5_000);
+}
+
+#[test]
+fn rounds_canonical() {
+    let range = RoundsRange::SHA_CRYPT;
+
+    assert!(range.is_default(5_000));
+    assert_eq!(range.canonical(5_000), None);
+    assert_eq!(
+        range.canonical(100_000).unwrap().to_string(),
+        "rounds=100000"
+    );
+}
+
+#[test]
+fn cost_sha_crypt() {
+    let hash = PasswordHashRef::new(SHA512_HASH).unwrap();
+    assert_eq!(hash.cost(), Ok(Some(Cost::Rounds(100_000))));
+
+    let hash =
+        PasswordHashRef::new("$5$saltstring$5B8vYYiY.CVt1RlTTf8KbXBH3hsxY/GNooZF4Zf/a8A").unwrap();
+    assert_eq!(hash.cost(), Ok(Some(Cost::Rounds(5_000))));
+
+    let hash = PasswordHashRef::new("$6$rounds=10$saltstring$hash").unwrap();
+    assert!(hash.cost().is_err());
+}
+
+#[test]
+fn cost_scrypt() {
+    let hash: PasswordHash = "$7$CU..../....2Ncx3QOG./$1cR.yJsOc.sRD8XyKUZF9OQS0J8JUVX7A6X0ozMMwA6"
+        .parse()
+        .unwrap();
+
+    let Ok(Some(Cost::Scrypt(params))) = hash.cost() else {
+        panic!("unexpected cost: {:?}", hash.cost());
+    };
+    assert_eq!(params.n_log2(), 14);
+    assert_eq!(params.n(), 16384);
+    assert_eq!(params.r(), 32);
+    assert_eq!(params.p(), 1);
+
+    let field = |s| Field::new(s).unwrap();
+    let (_, salt) = ScryptParams::parse(hash.fields().next().unwrap()).unwrap();
+    assert_eq!(salt, "2Ncx3QOG./");
+    assert!(ScryptParams::parse(field("CU....")).is_err());
+    assert!(ScryptParams::parse(field("C+..../....salt")).is_err());
+}
+
+#[test]
+fn cost_yescrypt() {
+    let hash = PasswordHashRef::new(
+        "$y$j9T$PaFbMK1M4/ZYmqo1ivQ2y/$2OW.5ejqNqSBgUt.YwMnvPgqNuoL3xJwwG/SSk4wAKA",
+    )
+    .unwrap();
+
+    let Ok(Some(Cost::Yescrypt(params))) = hash.cost() else {
+        panic!("unexpected cost: {:?}", hash.cost());
+    };
+    assert_eq!(params.flags(), 0xb6);
+    assert_eq!(params.n(), 4096);
+    assert_eq!(params.r(), 32);
+    assert_eq!(
+        (params.p(), params.t(), params.g(), params.nrom()),
+        (1, 0, 0, None)
+    );
+
+    let hash = PasswordHashRef::new("$gy$j9T$salt$hash").unwrap();
+    assert_eq!(hash.cost(), Ok(Some(Cost::Yescrypt(params))));
+}
+
+#[test]
+fn cost_yescrypt_optional_params() {
+    let field = |s| Field::new(s).unwrap();
+
+    // p=4, t=2
+    let params = YescryptParams::parse(field("j9500/")).unwrap();
+    assert_eq!(
+        (params.r(), params.p(), params.t(), params.g()),
+        (8, 4, 2, 0)
+    );
+
+    // Two-character encoding of r=100, YESCRYPT_WORM
+    let params = YescryptParams::parse(field("/9kn")).unwrap();
+    assert_eq!((params.flags(), params.r()), (1, 100));
+
+    // NROM=2^20
+    let params = YescryptParams::parse(field("j955H")).unwrap();
+    assert_eq!(params.nrom(), Some(1 << 20));
+
+    for malformed in [
+        "j9", "j9T.", "j9T0", "j9Tz.", "jz5", "jkfT", "zzzzzz95", "j9T+",
+    ] {
+        assert!(
+            YescryptParams::parse(field(malformed)).is_err(),
+            "{malformed}"
+        );
+    }
+}
+
+#[test]
+fn cost_unknown() {
+    let hash = PasswordHashRef::new("$argon2id$v=19$m=65536,t=2,p=1$salt$hash").unwrap();
+    assert_eq!(hash.cost(), Ok(None));
+}
+
+#[cfg(feature = "password-hash")]
+#[test]
+fn phc_round_trip() {
+    const PHC_HASH: &str = "$argon2id$v=19$m=65536,t=2,p=1$gZiV/M1gPc22ElAH/Jh1Hw$CWOrkoo7oJBQ/iyh7uJ0LO2aLEfrHwTWllSAxT0zRno";
+
+    let phc = password_hash::PasswordHash::new(PHC_HASH).unwrap();
+    let mcf_hash = PasswordHash::try_from(&phc).unwrap();
+    assert_eq!(mcf_hash.as_str(), PHC_HASH);
+    assert_eq!(mcf_hash.id(), "argon2id");
+
+    let phc2: password_hash::PasswordHash<'_> = (&mcf_hash).try_into().unwrap();
+    assert_eq!(phc2, phc);
+}
+
+#[cfg(feature = "password-hash")]
+#[test]
+fn phc_reject_non_phc() {
+    // SHA-crypt uses a non-PHC Base64 alphabet
+    let mcf_hash = PasswordHash::new(SHA512_HASH).unwrap();
+    assert!(password_hash::PasswordHash::try_from(&mcf_hash).is_err());
+}
+
+#[test]
+fn rewrite_id() {
+    let rewrite = Rewrite::new("2a", "2b").unwrap();
+    let hash = "$2a$12$R9h/cIPz0gi.URNNX3kh2OPST9/PgBkqquzi.Ss7KIUgO2t0jWMUW";
+
+    let RewriteOutcome::Rewritten(rewritten) = rewrite.apply(hash) else {
+        panic!("hash wasn't rewritten");
+    };
+    assert_eq!(rewritten.id(), "2b");
+    assert_eq!(&rewritten.as_str()[3..], &hash[3..]);
+}
+
+#[test]
+fn rewrite_outcomes() {
+    let rewrite = Rewrite::new("2a", "2b").unwrap();
+    let records = ["$2a$10$abc", "$2y$10$abc", "not a hash", "$2a$10$ab c"];
+
+    let outcomes: Vec<_> = rewrite.apply_all(records).collect();
+    assert_eq!(outcomes[0].as_str(), Some("$2b$10$abc"));
+    assert!(outcomes[0].is_rewritten());
+    assert_eq!(
+        outcomes[1],
+        RewriteOutcome::Unchanged(PasswordHashRef::new("$2y$10$abc").unwrap())
+    );
+    assert!(matches!(outcomes[2], RewriteOutcome::Invalid(_)));
+    assert_eq!(outcomes[3].as_str(), None);
+}
+
+#[test]
+fn rewrite_fields() {
+    let rewrite = Rewrite::new("5", "6")
+        .unwrap()
+        .with_field_map(|index, field| match index {
+            0 if field.as_str() == "rounds=5000" => Ok(Some("rounds=10000".into())),
+            0 if field.as_str().starts_with("rounds=") => Err(Field::new(" ").unwrap_err()),
+            _ => Ok(None),
+        });
+
+    assert_eq!(
+        rewrite.apply("$5$rounds=5000$salt$hash").as_str(),
+        Some("$6$rounds=10000$salt$hash")
+    );
+    assert!(matches!(
+        rewrite.apply("$5$rounds=1000$salt$hash"),
+        RewriteOutcome::Invalid(_)
+    ));
+
+    // Replacement fields are validated
+    let rewrite = Rewrite::new("5", "6")
+        .unwrap()
+        .with_field_map(|_, _| Ok(Some("in valid".into())));
+    assert!(matches!(
+        rewrite.apply("$5$salt"),
+        RewriteOutcome::Invalid(_)
+    ));
+}
+
+#[test]
+fn rewrite_malformed_ids() {
+    assert!(Rewrite::new("2a", "$2b").is_err());
+    assert!(Rewrite::new("-", "2b").is_err());
+}
+
+#[test]
+fn parse_strict() {
+    let hash = PasswordHashRef::new_strict(SHA512_HASH).unwrap();
+    assert_eq!(hash.id(), ids::SHA512_CRYPT);
+    assert!(PasswordHash::new_strict(SHA512_HASH).is_ok());
+
+    // Unknown identifiers only need to be well-formed
+    assert!(PasswordHashRef::new_strict("$unknown$a$b$c$d$e").is_ok());
+
+    // SHA-crypt hashes have 2 or 3 fields, bcrypt hashes exactly 2
+    assert!(PasswordHashRef::new_strict("$6$exn6tVc2j/MZD8uG").is_err());
+    assert!(PasswordHash::new_strict("$2b$12$salt$hash").is_err());
+    assert!(PasswordHashRef::new_strict("$argon2id$v=19$m=65536,t=2,p=1$c2FsdA$aGFzaA").is_ok());
+}
+
+#[test]
+fn id_rules() {
+    let bcrypt =
+        PasswordHashRef::new("$2b$12$R9h/cIPz0gi.URNNX3kh2OPST9/PgBkqquzi.Ss7KIUgO2t0jWMUW")
+            .unwrap();
+    let rule = ids::rule(ids::BCRYPT).unwrap();
+    assert_eq!(rule.id(), "2b");
+    assert_eq!(rule.fields(), 2..=2);
+    assert!(rule.check(bcrypt).is_ok());
+
+    // A bcrypt hash passed to a SHA-crypt consumer is rejected early
+    assert!(ids::rule(ids::SHA512_CRYPT).unwrap().check(bcrypt).is_err());
+    assert!(ids::rule("unknown").is_none());
+    assert!(
+        ids::RULES
+            .iter()
+            .all(|rule| ids::rule(rule.id()) == Some(rule))
+    );
+}
+
+#[test]
+fn redacted() {
+    let hash = PasswordHash::new(SHA512_HASH).unwrap();
+    let redacted = "$6$rounds=100000$<redacted>$<redacted>";
+    assert_eq!(hash.redacted().to_string(), redacted);
+    assert_eq!(
+        format!("{:?}", hash.redacted()),
+        format!("Redacted(\"{redacted}\")")
+    );
+
+    // PHC hashes keep all of their parameters
+    let argon2 = PasswordHashRef::new(
+        "$argon2id$v=19$m=65536,t=2,p=1$gZiV/M1gPc22ElAH/Jh1Hw$CWOrkoo7oJBQ/iyh7uJ0LO2aLEfrHwTWllSAxT0zRno",
+    )
+    .unwrap();
+    assert_eq!(
+        argon2.redacted().to_string(),
+        "$argon2id$v=19$m=65536,t=2,p=1$<redacted>$<redacted>"
+    );
+
+    // Hashes without parameters are masked entirely
+    let md5 = PasswordHashRef::new("$1$saltsalt$hashhash").unwrap();
+    assert_eq!(md5.redacted().to_string(), "$1$<redacted>$<redacted>");
+    let id_only = PasswordHashRef::new("$7").unwrap();
+    assert_eq!(id_only.redacted().to_string(), "$7");
+}
+
+#[test]
+fn from_bytes() {
+    let hash = PasswordHashRef::from_bytes(SHA512_HASH.as_bytes()).unwrap();
+    assert_eq!(hash, PasswordHashRef::new(SHA512_HASH).unwrap());
+
+    let relaxed = Charset::with_extra("_").unwrap();
+    let hash = PasswordHashRef::from_bytes_with_charset(b"$legacy$salt_1", relaxed).unwrap();
+    assert_eq!(hash.charset(), relaxed);
+    assert_eq!(hash.fields().next().unwrap().as_str(), "salt_1");
+}
+
+#[test]
+fn from_bytes_invalid() {
+    fn locate(bytes: &[u8]) -> (Option<usize>, usize) {
+        let err = PasswordHashRef::from_bytes(bytes).unwrap_err();
+        (err.field(), err.offset())
+    }
+
+    // Invalid UTF-8
+    assert_eq!(locate(b"$6$rounds=5000$sa\xfflt$hash"), (Some(1), 17));
+    assert_eq!(locate(b"$6\xc3\xa9$salt"), (None, 2));
+    assert_eq!(locate(b"$6$salt$hash\xe2\x82"), (Some(1), 12));
+
+    // Disallowed characters
+    assert_eq!(locate(b"$6$salt$ha sh"), (Some(1), 10));
+    assert_eq!(locate(b"$6$salt$hash\n"), (Some(1), 12));
+    assert_eq!(locate(b"$legacy$salt_1"), (Some(0), 12));
+    assert_eq!(locate(b"$6$salt:"), (Some(0), 7));
+
+    // Malformed structure
+    assert_eq!(locate(b""), (None, 0));
+    assert_eq!(locate(b"6$salt"), (None, 0));
+    assert_eq!(locate(b"$"), (None, 1));
+    assert_eq!(locate(b"$$salt"), (None, 1));
+    assert_eq!(locate(b"$6$$hash"), (Some(0), 3));
+    assert_eq!(locate(b"$6$salt$"), (Some(1), 8));
+    assert_eq!(locate(b"$-6$salt"), (None, 1));
+    assert_eq!(locate(b"$6-$salt"), (None, 2));
+    assert_eq!(locate(b"$Six$salt"), (None, 1));
+}
+
+#[test]
+fn from_bytes_matches_new() {
+    let inputs = [
+        "Hello, world!",
+        "$",
+        "$$",
+        "$$foo",
+        "$foo$",
+        "$-$foo",
+        "$foo-$bar",
+        "$-foo$bar",
+        "$6",
+        "$6$salt_$hash",
+        "$a-b$c$d",
+        SHA512_HASH,
+    ];
+
+    for s in inputs {
+        assert_eq!(
+            PasswordHashRef::from_bytes(s.as_bytes()).is_ok(),
+            PasswordHashRef::new(s).is_ok(),
+            "{s}"
+        );
+    }
+}
+
+#[test]
+fn invalid_hash_display() {
+    let err = PasswordHashRef::from_bytes(b"$6$secret\xff").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid MCF hash: invalid byte at offset 9 in field 0"
+    );
+
+    let err = PasswordHashRef::from_bytes(b"$SECRET").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid MCF hash: invalid byte at offset 1 in identifier"
+    );
+}
+
+#[test]
+fn sanitize_lossy() {
+    assert_eq!(
+        mcf::sanitize_lossy(SHA512_HASH.as_bytes(), Charset::STANDARD),
+        SHA512_HASH
+    );
+    assert_eq!(
+        mcf::sanitize_lossy(b"$6$sa\xff\xfelt$ha sh\n", Charset::STANDARD),
+        "$6$sa\u{FFFD}\u{FFFD}lt$ha\u{FFFD}sh\u{FFFD}"
+    );
+
+    let relaxed = Charset::with_extra("_").unwrap();
+    assert_eq!(
+        mcf::sanitize_lossy(b"$legacy$salt_1\xc3", relaxed),
+        "$legacy$salt_1\u{FFFD}"
+    );
+}
+
+#[cfg(feature = "subtle")]
+#[test]
+fn ct_eq() {
+    use subtle::ConstantTimeEq;
+
+    let hash = PasswordHashRef::new(SHA512_HASH).unwrap();
+    assert!(bool::from(
+        hash.ct_eq(&PasswordHashRef::new(SHA512_HASH).unwrap())
+    ));
+
+    // Differs only in the last character of the hash output
+    let other = SHA512_HASH.replace("Oy0", "Oy1");
+    assert!(!bool::from(
+        hash.ct_eq(&PasswordHashRef::new(&other).unwrap())
+    ));
+
+    // Differs in length
+    let truncated = &SHA512_HASH[..SHA512_HASH.len() - 1];
+    assert!(!bool::from(
+        hash.ct_eq(&PasswordHashRef::new(truncated).unwrap())
+    ));
+
+    let owned = PasswordHash::new(SHA512_HASH).unwrap();
+    assert!(bool::from(
+        owned.ct_eq(&PasswordHash::new(SHA512_HASH).unwrap())
+    ));
+    assert!(!bool::from(owned.ct_eq(&PasswordHash::new(other).unwrap())));
+}
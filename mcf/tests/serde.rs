@@ -0,0 +1,61 @@
+//! `serde` support tests.
+
+#![cfg(all(feature = "alloc", feature = "serde"))]
+
+use mcf::{PasswordHash, PasswordHashRef};
+use serde::{Deserialize, Serialize};
+
+const SHA512_HASH: &str = "$6$rounds=100000$exn6tVc2j/MZD8uG$BI1Xh8qQSK9J4m14uwy7abn.ctj/TIAzlaVCto0MQrOFIeTXsc1iwzH16XEWo/a7c7Y9eVJvufVzYAs4EsPOy0";
+
+#[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
+struct Config {
+    user: String,
+    password_hash: PasswordHash,
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
+struct ConfigRef<'a> {
+    user: &'a str,
+    #[serde(borrow)]
+    password_hash: PasswordHashRef<'a>,
+}
+
+fn config() -> Config {
+    Config {
+        user: "alice".into(),
+        password_hash: PasswordHash::new(SHA512_HASH).unwrap(),
+    }
+}
+
+#[test]
+fn json_round_trip() {
+    let json = serde_json::to_string(&config()).unwrap();
+    assert_eq!(
+        json,
+        format!(r#"{{"user":"alice","password_hash":"{SHA512_HASH}"}}"#)
+    );
+    assert_eq!(serde_json::from_str::<Config>(&json).unwrap(), config());
+}
+
+#[test]
+fn json_round_trip_borrowed() {
+    let json = serde_json::to_string(&config()).unwrap();
+    let config_ref = serde_json::from_str::<ConfigRef<'_>>(&json).unwrap();
+    assert_eq!(config_ref.password_hash.as_str(), SHA512_HASH);
+    assert_eq!(serde_json::to_string(&config_ref).unwrap(), json);
+}
+
+#[test]
+fn toml_round_trip() {
+    let toml = toml::to_string(&config()).unwrap();
+    assert_eq!(toml::from_str::<Config>(&toml).unwrap(), config());
+}
+
+#[test]
+fn deserialize_invalid() {
+    let json = r#"{"user":"alice","password_hash":"6$rounds=100000$"}"#;
+    assert!(serde_json::from_str::<Config>(json).is_err());
+    assert!(serde_json::from_str::<ConfigRef<'_>>(json).is_err());
+
+    assert!(serde_json::from_str::<PasswordHash>(r#""$6$in valid""#).is_err());
+}
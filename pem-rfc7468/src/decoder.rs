@@ -11,14 +11,22 @@
 //! [RFC 4648]: https://datatracker.ietf.org/doc/html/rfc4648
 
 use crate::{
-    BASE64_WRAP_WIDTH, Base64Decoder, Error, POST_ENCAPSULATION_BOUNDARY,
-    PRE_ENCAPSULATION_BOUNDARY, Result, grammar,
+    BASE64_WRAP_WIDTH, Base64Decoder, ENCAPSULATION_BOUNDARY_DELIMITER, EncapsulatedHeaders, Error,
+    LineEnding, POST_ENCAPSULATION_BOUNDARY, PRE_ENCAPSULATION_BOUNDARY, Result, encoded_len,
+    grammar,
 };
-use core::str;
+use base64ct::{Base64, Encoding};
+use core::{ops::Range, str};
 
 #[cfg(feature = "alloc")]
 use alloc::vec::Vec;
 
+#[cfg(feature = "zeroize")]
+use {crate::BASE64_WRAP_WIDTH as LINE_WIDTH, zeroize::Zeroize};
+
+#[cfg(all(feature = "alloc", feature = "zeroize"))]
+use zeroize::Zeroizing;
+
 #[cfg(feature = "std")]
 use std::io;
 
@@ -55,6 +63,117 @@ pub fn decode_vec(pem: &[u8]) -> Result<(&str, Vec<u8>)> {
     Ok((type_label, buf))
 }
 
+/// Decode a PEM document according to RFC 7468's "Strict" grammar in place,
+/// i.e. decoding the Base64 encapsulated text into the buffer holding it.
+///
+/// This avoids allocating a second buffer for the decoded document, e.g. when
+/// the PEM text was read into a [`Vec`] which is no longer needed afterwards.
+///
+/// On success, returns the decoded label and the portion of `pem` containing
+/// the decoded message. The contents of the rest of `pem` are unspecified,
+/// including on failure.
+pub fn decode_in_place(pem: &mut [u8]) -> Result<(&str, &[u8])> {
+    let (label, text) = {
+        let encapsulation = Encapsulation::try_from(&*pem)?;
+        let (headers, _) = EncapsulatedHeaders::parse(encapsulation.encapsulated_text)?;
+
+        if !headers.is_empty() {
+            return Err(Error::HeaderDisallowed);
+        }
+
+        (
+            subslice_range(pem, encapsulation.label.as_bytes())?,
+            subslice_range(pem, encapsulation.encapsulated_text)?,
+        )
+    };
+
+    // The label precedes the encapsulated text, which is decoded in place
+    let (head, tail) = pem.split_at_mut(text.start);
+    let label = head
+        .get(label)
+        .and_then(|label| str::from_utf8(label).ok())
+        .ok_or(Error::Label)?;
+    let text = tail.get_mut(..text.len()).ok_or(Error::Length)?;
+
+    let len = remove_eols_in_place(text)?;
+    let base64 = text.get_mut(..len).ok_or(Error::Length)?;
+    let decoded = Base64::decode_in_place(base64).map_err(base64ct::Error::from)?;
+    Ok((label, decoded))
+}
+
+/// Decode a PEM document which must be in canonical form, i.e. byte-for-byte
+/// identical to the output of [`encode`][`crate::encode`] for the decoded
+/// label and data with one of the [`LineEnding`]s.
+///
+/// This is useful for systems which hash or sign PEM text directly, as any
+/// document accepted by this function has exactly one valid encoding for a
+/// given line ending. Beyond RFC 7468's "Strict" grammar, it rejects:
+/// - text before the pre-encapsulation boundary
+/// - Base64 lines which aren't exactly 64 characters, except for the last one
+/// - mixed line endings
+/// - missing or additional line endings, including after the post-encapsulation
+///   boundary
+///
+/// Returns [`Error::NonCanonical`] if the document isn't canonical.
+///
+/// NOTE: not constant time with respect to the input.
+pub fn decode_canonical<'i, 'o>(pem: &'i [u8], buf: &'o mut [u8]) -> Result<(&'i str, &'o [u8])> {
+    let (label, decoded) = decode(pem, buf)?;
+    check_canonical(pem, label, decoded)?;
+    Ok((label, decoded))
+}
+
+/// Decode a PEM document which must be in canonical form, returning the result
+/// as a [`Vec`] upon success.
+///
+/// See [`decode_canonical`] for more information.
+#[cfg(feature = "alloc")]
+pub fn decode_canonical_vec(pem: &[u8]) -> Result<(&str, Vec<u8>)> {
+    let (label, decoded) = decode_vec(pem)?;
+    check_canonical(pem, label, &decoded)?;
+    Ok((label, decoded))
+}
+
+/// Decode a PEM document containing secret data (e.g. a private key) according to RFC 7468's
+/// "Strict" grammar.
+///
+/// Unlike [`decode`], the encapsulated text is decoded line-by-line directly into `buf`
+/// without any intermediate buffering, with the Base64 decoding being constant-time with
+/// respect to the encapsulated text. Only the positions of line endings, which are fixed by the
+/// grammar, are inspected.
+///
+/// On failure, `buf` is zeroized.
+#[cfg(feature = "zeroize")]
+pub fn decode_secret<'i, 'o>(pem: &'i [u8], buf: &'o mut [u8]) -> Result<(&'i str, &'o [u8])> {
+    let encapsulation = Encapsulation::try_from(pem).map_err(|e| check_for_headers(pem, e))?;
+
+    match decode_secret_text(encapsulation.encapsulated_text, buf) {
+        Ok(len) => Ok((encapsulation.label(), &buf[..len])),
+        Err(e) => {
+            buf.zeroize();
+            Err(check_for_headers(pem, e))
+        }
+    }
+}
+
+/// Decode a PEM document containing secret data according to RFC 7468's "Strict" grammar,
+/// returning the result as a [`Zeroizing`] [`Vec`] upon success.
+///
+/// See [`decode_secret`] for more information.
+#[cfg(all(feature = "alloc", feature = "zeroize"))]
+pub fn decode_secret_vec(pem: &[u8]) -> Result<(&str, Zeroizing<Vec<u8>>)> {
+    let encapsulation = Encapsulation::try_from(pem).map_err(|e| check_for_headers(pem, e))?;
+    let text = encapsulation.encapsulated_text;
+
+    // Upper bound on the decoded length, as line endings are included in the text
+    let max_len = (text.len() / 4).checked_mul(3).ok_or(Error::Length)?;
+    let mut buf = Zeroizing::new(vec![0u8; max_len]);
+
+    let len = decode_secret_text(text, &mut buf).map_err(|e| check_for_headers(pem, e))?;
+    buf.truncate(len);
+    Ok((encapsulation.label(), buf))
+}
+
 /// Decode the encapsulation boundaries of a PEM document according to RFC 7468's "Strict" grammar.
 ///
 /// On success, returning the decoded label.
@@ -62,6 +181,17 @@ pub fn decode_label(pem: &[u8]) -> Result<&str> {
     Ok(Encapsulation::try_from(pem)?.label())
 }
 
+/// Decode the encapsulation boundaries of a PEM document along with any RFC 1421 headers
+/// (i.e. `Proc-Type` and `DEK-Info`) which precede the encapsulated text.
+///
+/// This is useful for detecting legacy encrypted PEM documents, which are otherwise rejected
+/// with [`Error::HeaderDisallowed`].
+pub fn decode_headers(pem: &[u8]) -> Result<(&str, EncapsulatedHeaders<'_>)> {
+    let encapsulation = Encapsulation::try_from(pem)?;
+    let (headers, _) = EncapsulatedHeaders::parse(encapsulation.encapsulated_text)?;
+    Ok((encapsulation.label(), headers))
+}
+
 /// Attempt to detect the Base64 line width for the given PEM document.
 ///
 /// NOTE: not constant time with respect to the input.
@@ -78,6 +208,9 @@ pub struct Decoder<'i> {
     /// PEM type label.
     type_label: &'i str,
 
+    /// RFC 1421 headers (only parsed by [`Decoder::new_with_headers`]).
+    headers: EncapsulatedHeaders<'i>,
+
     /// Buffered Base64 decoder.
     base64: Base64Decoder<'i>,
 }
@@ -96,7 +229,32 @@ impl<'i> Decoder<'i> {
         let type_label = encapsulation.label();
         let base64 = Base64Decoder::new_wrapped(encapsulation.encapsulated_text, line_width)?;
 
-        Ok(Self { type_label, base64 })
+        Ok(Self {
+            type_label,
+            headers: EncapsulatedHeaders::default(),
+            base64,
+        })
+    }
+
+    /// Create a new PEM [`Decoder`] which accepts RFC 1421 headers (e.g. `Proc-Type` and
+    /// `DEK-Info`) before the encapsulated text, rather than returning an error.
+    ///
+    /// The parsed headers can be obtained using [`Decoder::headers`]. Note that the encapsulated
+    /// text is decoded as-is: if the headers indicate it's encrypted, the decoded data will be
+    /// ciphertext.
+    ///
+    /// Uses the default 64-character line wrapping.
+    pub fn new_with_headers(pem: &'i [u8]) -> Result<Self> {
+        let encapsulation = Encapsulation::try_from(pem)?;
+        let type_label = encapsulation.label();
+        let (headers, text) = EncapsulatedHeaders::parse(encapsulation.encapsulated_text)?;
+        let base64 = Base64Decoder::new_wrapped(text, BASE64_WRAP_WIDTH)?;
+
+        Ok(Self {
+            type_label,
+            headers,
+            base64,
+        })
     }
 
     /// Create a new PEM [`Decoder`] which automatically detects the line width the input is wrapped
@@ -113,6 +271,14 @@ impl<'i> Decoder<'i> {
         self.type_label
     }
 
+    /// Get the RFC 1421 headers parsed from the input document.
+    ///
+    /// These are always empty unless the decoder was constructed with
+    /// [`Decoder::new_with_headers`].
+    pub fn headers(&self) -> EncapsulatedHeaders<'i> {
+        self.headers
+    }
+
     /// Decode data into the provided output buffer.
     ///
     /// There must be at least as much remaining Base64 input to be decoded
@@ -261,6 +427,115 @@ impl<'a> TryFrom<&'a [u8]> for Encapsulation<'a> {
     }
 }
 
+/// Decode the Base64 encapsulated text wrapped at [`BASE64_WRAP_WIDTH`] into `buf`, returning
+/// the decoded length.
+#[cfg(feature = "zeroize")]
+fn decode_secret_text(mut text: &[u8], buf: &mut [u8]) -> Result<usize> {
+    let mut pos = 0;
+
+    while text.len() > LINE_WIDTH {
+        let (line, rest) = text.split_at(LINE_WIDTH);
+        text = grammar::strip_leading_eol(rest).ok_or(base64ct::Error::InvalidEncoding)?;
+
+        // Padding is only allowed on the last line
+        let decoded = Base64::decode(line, buf.get_mut(pos..).ok_or(Error::Length)?)?;
+        if decoded.len() != LINE_WIDTH / 4 * 3 {
+            return Err(base64ct::Error::InvalidEncoding.into());
+        }
+
+        pos = pos.checked_add(decoded.len()).ok_or(Error::Length)?;
+    }
+
+    let decoded = Base64::decode(text, buf.get_mut(pos..).ok_or(Error::Length)?)?;
+    pos.checked_add(decoded.len()).ok_or(Error::Length)
+}
+
+/// Get the range of `pem` occupied by `bytes`, which must be a subslice of it.
+fn subslice_range(pem: &[u8], bytes: &[u8]) -> Result<Range<usize>> {
+    let start = (bytes.as_ptr() as usize)
+        .checked_sub(pem.as_ptr() as usize)
+        .ok_or(Error::Length)?;
+    let end = start.checked_add(bytes.len()).ok_or(Error::Length)?;
+    Ok(start..end)
+}
+
+/// Remove the line endings from the Base64 encapsulated text wrapped at [`BASE64_WRAP_WIDTH`],
+/// moving the Base64 to the start of `text` and returning its length.
+fn remove_eols_in_place(text: &mut [u8]) -> Result<usize> {
+    let mut len = 0usize;
+    let mut pos = 0usize;
+
+    while text.len().saturating_sub(pos) > BASE64_WRAP_WIDTH {
+        let line_end = pos.checked_add(BASE64_WRAP_WIDTH).ok_or(Error::Length)?;
+        text.copy_within(pos..line_end, len);
+        len = len.checked_add(BASE64_WRAP_WIDTH).ok_or(Error::Length)?;
+
+        let rest = text.get(line_end..).ok_or(Error::Length)?;
+        let eol_len = rest
+            .len()
+            .checked_sub(
+                grammar::strip_leading_eol(rest)
+                    .ok_or(base64ct::Error::InvalidEncoding)?
+                    .len(),
+            )
+            .ok_or(Error::Length)?;
+        pos = line_end.checked_add(eol_len).ok_or(Error::Length)?;
+    }
+
+    let last_len = text.len().saturating_sub(pos);
+    text.copy_within(pos.., len);
+    len.checked_add(last_len).ok_or(Error::Length)
+}
+
+/// Check that `pem` is byte-for-byte identical to the canonical encoding of
+/// the given `label` and `data`, with the line ending used by `pem`.
+fn check_canonical(pem: &[u8], label: &str, data: &[u8]) -> Result<()> {
+    fn strip<'a>(text: &'a [u8], prefix: &[u8]) -> Result<&'a [u8]> {
+        text.strip_prefix(prefix).ok_or(Error::NonCanonical)
+    }
+
+    let mut text = strip(pem, PRE_ENCAPSULATION_BOUNDARY)?;
+    text = strip(text, label.as_bytes())?;
+    text = strip(text, ENCAPSULATION_BOUNDARY_DELIMITER)?;
+
+    let line_ending = [LineEnding::CRLF, LineEnding::LF, LineEnding::CR]
+        .into_iter()
+        .find(|line_ending| text.starts_with(line_ending.as_bytes()))
+        .ok_or(Error::NonCanonical)?;
+    let eol = line_ending.as_bytes();
+
+    if encoded_len(label, line_ending, data)? != pem.len() {
+        return Err(Error::NonCanonical);
+    }
+
+    let mut line = [0u8; BASE64_WRAP_WIDTH];
+
+    for chunk in data.chunks(BASE64_WRAP_WIDTH / 4 * 3) {
+        text = strip(text, eol)?;
+        text = strip(text, Base64::encode(chunk, &mut line)?.as_bytes())?;
+    }
+
+    if data.is_empty() {
+        text = strip(text, eol)?;
+    }
+
+    for expected in [
+        eol,
+        POST_ENCAPSULATION_BOUNDARY,
+        label.as_bytes(),
+        ENCAPSULATION_BOUNDARY_DELIMITER,
+        eol,
+    ] {
+        text = strip(text, expected)?;
+    }
+
+    if text.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::NonCanonical)
+    }
+}
+
 /// Check for PEM headers in the input, as they are disallowed by RFC7468.
 ///
 /// Returns `Error::HeaderDisallowed` if headers are encountered.
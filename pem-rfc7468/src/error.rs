@@ -27,6 +27,9 @@ pub enum Error {
     /// Invalid length.
     Length,
 
+    /// Document isn't in canonical form.
+    NonCanonical,
+
     /// "Preamble" (text before pre-encapsulation boundary) contains invalid data.
     Preamble,
 
@@ -52,6 +55,7 @@ impl fmt::Display for Error {
             Error::HeaderDisallowed => f.write_str("PEM headers disallowed by RFC7468"),
             Error::Label => f.write_str("PEM type label invalid"),
             Error::Length => f.write_str("PEM length invalid"),
+            Error::NonCanonical => f.write_str("PEM document is not in canonical form"),
             Error::Preamble => f.write_str("PEM preamble contains invalid data (NUL byte)"),
             Error::PreEncapsulationBoundary => {
                 f.write_str("PEM error in pre-encapsulation boundary")
@@ -97,6 +101,7 @@ impl From<Error> for std::io::Error {
             Error::CharacterEncoding
             | Error::EncapsulatedText
             | Error::Label
+            | Error::NonCanonical
             | Error::Preamble
             | Error::PreEncapsulationBoundary
             | Error::PostEncapsulationBoundary => std::io::ErrorKind::InvalidData,
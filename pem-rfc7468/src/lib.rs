@@ -61,16 +61,28 @@ mod decoder;
 mod encoder;
 mod error;
 mod grammar;
+mod headers;
 
 pub use crate::{
-    decoder::{Decoder, decode, decode_label, detect_base64_line_width},
+    decoder::{
+        Decoder, decode, decode_canonical, decode_headers, decode_in_place, decode_label,
+        detect_base64_line_width,
+    },
     encoder::{Encoder, encapsulated_len, encapsulated_len_wrapped, encode, encoded_len},
     error::{Error, Result},
+    headers::{DekInfo, EncapsulatedHeaders, ProcType},
 };
 pub use base64ct::LineEnding;
 
+#[cfg(feature = "zeroize")]
+pub use crate::decoder::decode_secret;
+#[cfg(all(feature = "alloc", feature = "zeroize"))]
+pub use crate::decoder::decode_secret_vec;
 #[cfg(feature = "alloc")]
-pub use crate::{decoder::decode_vec, encoder::encode_string};
+pub use crate::{
+    decoder::{decode_canonical_vec, decode_vec},
+    encoder::encode_string,
+};
 
 /// The pre-encapsulation boundary appears before the encapsulated text.
 ///
@@ -110,8 +122,10 @@ pub trait PemLabel {
     const PEM_LABEL: &'static str;
 
     /// Validate that a given label matches the expected label.
+    ///
+    /// The comparison is constant-time with respect to the contents of the labels.
     fn validate_pem_label(actual: &str) -> Result<()> {
-        if Self::PEM_LABEL == actual {
+        if ct_eq(Self::PEM_LABEL.as_bytes(), actual.as_bytes()) {
             Ok(())
         } else {
             Err(Error::UnexpectedTypeLabel {
@@ -120,3 +134,9 @@ pub trait PemLabel {
         }
     }
 }
+
+/// Compare two byte slices in constant-time with respect to their contents (but not their
+/// lengths).
+fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
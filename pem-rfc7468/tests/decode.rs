@@ -39,6 +39,50 @@ fn pkcs1_enc_example() {
     assert_eq!(label, "RSA PRIVATE KEY");
 }
 
+#[test]
+fn pkcs1_enc_example_headers() {
+    let pem = include_bytes!("examples/ssh_rsa_pem_password.pem");
+    let (label, headers) = pem_rfc7468::decode_headers(pem).unwrap();
+    assert_eq!(label, "RSA PRIVATE KEY");
+    assert!(headers.is_encrypted());
+
+    let dek_info = headers.dek_info.unwrap();
+    assert_eq!(dek_info.algorithm, "AES-128-CBC");
+    assert_eq!(
+        dek_info.parameters,
+        Some("15670D76FD184D46C40C971733E0543F")
+    );
+
+    let mut decoder = pem_rfc7468::Decoder::new_with_headers(pem).unwrap();
+    assert_eq!(decoder.headers(), headers);
+
+    let mut buf = [0u8; 2048];
+    let len = decoder.remaining_len();
+    decoder.decode(&mut buf[..len]).unwrap();
+    assert!(decoder.is_finished());
+}
+
+#[test]
+fn pkcs1_example_without_headers() {
+    let pem = include_bytes!("examples/pkcs1.pem");
+    let (_, headers) = pem_rfc7468::decode_headers(pem).unwrap();
+    assert!(headers.is_empty());
+
+    let mut decoder = pem_rfc7468::Decoder::new_with_headers(pem).unwrap();
+    let mut buf = [0u8; 2048];
+    let len = decoder.remaining_len();
+    let decoded = decoder.decode(&mut buf[..len]).unwrap();
+    assert_eq!(decoded, include_bytes!("examples/pkcs1.der"));
+}
+
+#[test]
+fn chosen_header_example_headers() {
+    let pem = include_bytes!("examples/chosen_header.pem");
+    let (_, headers) = pem_rfc7468::decode_headers(pem).unwrap();
+    assert!(headers.is_encrypted());
+    assert_eq!(headers.dek_info.unwrap().algorithm, "AES-128-CBC");
+}
+
 #[test]
 #[cfg(feature = "alloc")]
 fn pkcs1_enc_example_with_vec() {
@@ -125,3 +169,182 @@ fn line_width_detection() {
         70
     );
 }
+
+#[test]
+#[cfg(feature = "zeroize")]
+fn pkcs1_example_secret() {
+    let pem = include_bytes!("examples/pkcs1.pem");
+    let mut buf = [0u8; 2048];
+    let (label, decoded) = pem_rfc7468::decode_secret(pem, &mut buf).unwrap();
+    assert_eq!(label, "RSA PRIVATE KEY");
+    assert_eq!(decoded, include_bytes!("examples/pkcs1.der"));
+}
+
+#[test]
+#[cfg(feature = "zeroize")]
+fn pkcs8_example_secret() {
+    let pem = include_bytes!("examples/pkcs8.pem");
+    let mut buf = [0u8; 2048];
+    let (label, decoded) = pem_rfc7468::decode_secret(pem, &mut buf).unwrap();
+    assert_eq!(label, "PRIVATE KEY");
+    assert_eq!(decoded, include_bytes!("examples/pkcs8.der"));
+}
+
+#[test]
+#[cfg(feature = "zeroize")]
+fn secret_rejects_invalid() {
+    let mut buf = [0xFFu8; 2048];
+
+    // Headers are disallowed
+    let pem = include_bytes!("examples/ssh_rsa_pem_password.pem");
+    let result = pem_rfc7468::decode_secret(pem, &mut buf);
+    assert_eq!(result, Err(pem_rfc7468::Error::HeaderDisallowed));
+    assert!(buf.iter().all(|&b| b == 0));
+
+    // Only the default line width is supported
+    let pem = include_bytes!("examples/ssh-id_ed25519.pem");
+    assert!(pem_rfc7468::decode_secret(pem, &mut buf).is_err());
+
+    // Output buffer too small
+    let pem = include_bytes!("examples/pkcs1.pem");
+    buf.fill(0xFF);
+    assert!(pem_rfc7468::decode_secret(pem, &mut buf[..64]).is_err());
+    assert!(buf[..64].iter().all(|&b| b == 0));
+}
+
+#[test]
+#[cfg(all(feature = "alloc", feature = "zeroize"))]
+fn pkcs8_enc_example_secret_vec() {
+    let pem = include_bytes!("examples/pkcs8-enc.pem");
+    let (label, decoded) = pem_rfc7468::decode_secret_vec(pem).unwrap();
+    assert_eq!(label, "ENCRYPTED PRIVATE KEY");
+    assert_eq!(decoded.as_slice(), include_bytes!("examples/pkcs8-enc.der"));
+
+    let pem = include_bytes!("examples/chosen_header.pem");
+    assert_eq!(
+        pem_rfc7468::decode_secret_vec(pem),
+        Err(pem_rfc7468::Error::HeaderDisallowed)
+    );
+}
+
+#[test]
+fn canonical_example() {
+    let pem = include_bytes!("examples/pkcs1.pem");
+    let mut buf = [0u8; 2048];
+    let (label, decoded) = pem_rfc7468::decode_canonical(pem, &mut buf).unwrap();
+    assert_eq!(label, "RSA PRIVATE KEY");
+    assert_eq!(decoded, include_bytes!("examples/pkcs1.der"));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn canonical_example_with_vec() {
+    let pem = include_bytes!("examples/pkcs1.pem");
+    let (label, decoded) = pem_rfc7468::decode_canonical_vec(pem).unwrap();
+    assert_eq!(label, "RSA PRIVATE KEY");
+    assert_eq!(decoded, include_bytes!("examples/pkcs1.der"));
+
+    let pem = include_bytes!("examples/pkcs1_with_preceding_junk.pem");
+    assert_eq!(
+        pem_rfc7468::decode_canonical_vec(pem),
+        Err(pem_rfc7468::Error::NonCanonical)
+    );
+}
+
+#[test]
+fn canonical_line_endings() {
+    let der = include_bytes!("examples/pkcs1.der");
+    let mut pem = [0u8; 2048];
+    let mut buf = [0u8; 2048];
+
+    for line_ending in [
+        pem_rfc7468::LineEnding::LF,
+        pem_rfc7468::LineEnding::CRLF,
+        pem_rfc7468::LineEnding::CR,
+    ] {
+        let encoded = pem_rfc7468::encode("RSA PRIVATE KEY", line_ending, der, &mut pem).unwrap();
+        let (_, decoded) = pem_rfc7468::decode_canonical(encoded.as_bytes(), &mut buf).unwrap();
+        assert_eq!(decoded, der);
+    }
+
+    // Single line documents
+    for data in [&[1, 2, 3][..], &[0xFF; 48]] {
+        let encoded = pem_rfc7468::encode("TEST", Default::default(), data, &mut pem).unwrap();
+        let (_, decoded) = pem_rfc7468::decode_canonical(encoded.as_bytes(), &mut buf).unwrap();
+        assert_eq!(decoded, data);
+    }
+}
+
+#[test]
+fn non_canonical_examples() {
+    let pem = include_str!("examples/pkcs1.pem");
+
+    let check = |pem: &str| {
+        let mut buf = [0u8; 2048];
+        assert!(pem_rfc7468::decode(pem.as_bytes(), &mut buf).is_ok());
+        assert_eq!(
+            pem_rfc7468::decode_canonical(pem.as_bytes(), &mut buf),
+            Err(pem_rfc7468::Error::NonCanonical)
+        );
+    };
+
+    // Preamble
+    check(include_str!("examples/pkcs1_with_preceding_junk.pem"));
+
+    // Missing trailing line ending
+    check(pem.trim_end());
+
+    // Mixed line endings
+    check(&pem.replacen('\n', "\r\n", 1));
+
+    // Mixed line ending at the end of the document
+    check(&format!("{}\r\n", pem.trim_end()));
+}
+
+#[test]
+fn decode_in_place() {
+    for pem in [
+        &include_bytes!("examples/pkcs1.pem")[..],
+        include_bytes!("examples/pkcs1_with_preceding_junk.pem"),
+    ] {
+        let mut buf = pem.to_vec();
+        let (label, decoded) = pem_rfc7468::decode_in_place(&mut buf).unwrap();
+        assert_eq!(label, "RSA PRIVATE KEY");
+        assert_eq!(decoded, include_bytes!("examples/pkcs1.der"));
+    }
+
+    // CRLF line endings
+    let pem = std::str::from_utf8(include_bytes!("examples/pkcs8.pem")).unwrap();
+    let mut buf = pem.replace('\n', "\r\n").into_bytes();
+    let (label, decoded) = pem_rfc7468::decode_in_place(&mut buf).unwrap();
+    assert_eq!(label, "PRIVATE KEY");
+    assert_eq!(decoded, include_bytes!("examples/pkcs8.der"));
+}
+
+#[test]
+fn decode_in_place_errors() {
+    let mut buf = include_bytes!("examples/ssh_rsa_pem_password.pem").to_vec();
+    assert_eq!(
+        pem_rfc7468::decode_in_place(&mut buf),
+        Err(pem_rfc7468::Error::HeaderDisallowed)
+    );
+
+    // Invalid Base64 character
+    let mut buf = include_bytes!("examples/pkcs1.pem").to_vec();
+    let pos = buf.iter().position(|&c| c == b'\n').unwrap() + 10;
+    buf[pos] = b'*';
+    assert_eq!(
+        pem_rfc7468::decode_in_place(&mut buf),
+        Err(pem_rfc7468::Error::Base64(base64ct::Error::InvalidEncoding))
+    );
+
+    // Lines wrapped at a width other than 64
+    let mut buf = include_bytes!("examples/pkcs1.pem").to_vec();
+    let first_eol = buf.iter().position(|&c| c == b'\n').unwrap();
+    assert_eq!(buf[first_eol + 65], b'\n');
+    buf.remove(first_eol + 65);
+    assert_eq!(
+        pem_rfc7468::decode_in_place(&mut buf),
+        Err(pem_rfc7468::Error::Base64(base64ct::Error::InvalidEncoding))
+    );
+}
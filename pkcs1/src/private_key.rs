@@ -67,6 +67,8 @@ pub struct RsaPrivateKey<'a> {
 
     /// Additional primes `r_3`, ..., `r_u`, in order, if this is a multi-prime
     /// RSA key (i.e. `version` is `multi`).
+    ///
+    /// When present, it must contain at least one `OtherPrimeInfo`.
     pub other_prime_infos: Option<OtherPrimeInfos<'a>>,
 }
 
@@ -115,12 +117,24 @@ impl<'a> DecodeValue<'a> for RsaPrivateKey<'a> {
             return Err(reader.error(der::ErrorKind::Value { tag: Tag::Integer }));
         }
 
+        // `OtherPrimeInfos` is `SIZE(1..MAX)`
+        #[cfg(feature = "alloc")]
+        if result.other_prime_infos.as_ref().is_some_and(Vec::is_empty) {
+            return Err(reader.error(der::ErrorKind::Length { tag: Tag::Sequence }));
+        }
+
         Ok(result)
     }
 }
 
 impl EncodeValue for RsaPrivateKey<'_> {
     fn value_len(&self) -> der::Result<Length> {
+        // `OtherPrimeInfos` is `SIZE(1..MAX)`
+        #[cfg(feature = "alloc")]
+        if self.other_prime_infos.as_ref().is_some_and(Vec::is_empty) {
+            return Err(der::ErrorKind::Length { tag: Tag::Sequence }.into());
+        }
+
         self.version().encoded_len()?
             + self.modulus.encoded_len()?
             + self.public_exponent.encoded_len()?
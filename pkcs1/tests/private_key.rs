@@ -202,6 +202,52 @@ fn decode_rsa2048_multi_prime_der() {
     );
 }
 
+#[cfg(feature = "alloc")]
+#[test]
+fn encode_rsa2048_multi_prime_der() {
+    use der::Encode;
+
+    let key = RsaPrivateKey::try_from(RSA_2048_MULTI_PRIME_DER_EXAMPLE).unwrap();
+    assert_eq!(key.to_der().unwrap(), RSA_2048_MULTI_PRIME_DER_EXAMPLE);
+
+    // Dropping the other primes makes it a two-prime key
+    let mut two_prime = key.clone();
+    two_prime.other_prime_infos = None;
+    let der = two_prime.to_der().unwrap();
+    assert_eq!(
+        RsaPrivateKey::try_from(der.as_slice()).unwrap().version(),
+        Version::TwoPrime
+    );
+
+    // `OtherPrimeInfos` must not be empty
+    let mut empty = key;
+    empty.other_prime_infos = Some(Vec::new());
+    assert!(empty.to_der().is_err());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn decode_rsa2048_multi_prime_der_empty_other_prime_infos() {
+    // Replace `otherPrimeInfos` with an empty `SEQUENCE OF`, adjusting the
+    // length of the outer `SEQUENCE` (encoded in 3 bytes following its tag)
+    use der::{Decode, Encode, ErrorKind, Tag};
+
+    let key = RsaPrivateKey::try_from(RSA_2048_MULTI_PRIME_DER_EXAMPLE).unwrap();
+    let other_prime_infos_len = key.other_prime_infos.encoded_len().unwrap();
+    let mut der = RSA_2048_MULTI_PRIME_DER_EXAMPLE[..RSA_2048_MULTI_PRIME_DER_EXAMPLE.len()
+        - usize::try_from(other_prime_infos_len).unwrap()]
+        .to_vec();
+    der.extend_from_slice(&[0x30, 0x00]);
+
+    let body_len = u16::try_from(der.len() - 4).unwrap();
+    der[2..4].copy_from_slice(&body_len.to_be_bytes());
+
+    assert_eq!(
+        RsaPrivateKey::from_der(&der).unwrap_err().kind(),
+        ErrorKind::Length { tag: Tag::Sequence }
+    );
+}
+
 #[test]
 fn private_key_to_public_key() {
     let private_key = RsaPrivateKey::try_from(RSA_2048_DER_EXAMPLE).unwrap();
@@ -0,0 +1,264 @@
+//! PFX builder
+
+use crate::{
+    EncryptionAlgorithm, Error, MacAlgorithm, MacData, PKCS_12_CERT_BAG_OID,
+    PKCS_12_PKCS8_KEY_BAG_OID, PKCS_12_X509_CERT_OID, Result, SafeBag,
+    cert_type::CertBag,
+    pbe_params::EncryptedPrivateKeyInfo,
+    pfx::{Pfx, Version},
+};
+use alloc::{vec, vec::Vec};
+use cms::{
+    content_info::{CmsVersion, ContentInfo},
+    encrypted_data::EncryptedData,
+    enveloped_data::EncryptedContentInfo,
+};
+use const_oid::db::{
+    rfc2985::{PKCS_9_AT_FRIENDLY_NAME, PKCS_9_AT_LOCAL_KEY_ID},
+    rfc5911::{ID_DATA, ID_ENCRYPTED_DATA},
+};
+use der::{
+    Any, Encode, EncodeValue, TagMode, TagNumber, Tagged,
+    asn1::{BmpString, ContextSpecificRef, OctetString, SetOfVec},
+};
+use rand_core::CryptoRng;
+use sha1::{Digest, Sha1};
+use x509_cert::{
+    Certificate,
+    attr::{Attribute, Attributes},
+};
+use zeroize::Zeroizing;
+
+/// Length of the generated MAC salt.
+const MAC_SALT_LEN: usize = 16;
+
+/// Builder for PFXs, i.e. `.p12` files, containing private keys and their
+/// certificate chains.
+///
+/// Certificates are stored in an `EncryptedData` content, and private keys
+/// in `pkcs8ShroudedKeyBag`s, both encrypted using the same password as the
+/// MAC, which matches the layout produced by `openssl pkcs12 -export`.
+///
+/// ```ignore
+/// let mut builder = PfxBuilder::new();
+/// builder
+///     .add_key(&private_key_der, &certificate, Some("my key"))?
+///     .add_certificate(&ca_certificate)?;
+///
+/// let pfx = builder.build("password", &mut rng)?;
+/// ```
+#[derive(Clone, Debug)]
+pub struct PfxBuilder {
+    /// Private key bags, i.e. DER-encoded PKCS#8 `PrivateKeyInfo`s with their
+    /// attributes.
+    keys: Vec<(Zeroizing<Vec<u8>>, Option<Attributes>)>,
+
+    /// Certificate bags.
+    certificates: Vec<SafeBag>,
+
+    /// Algorithm used to encrypt keys and certificates.
+    encryption: EncryptionAlgorithm,
+
+    /// Algorithm used to compute the MAC.
+    mac: MacAlgorithm,
+
+    /// Number of iterations of the key derivation functions.
+    iterations: u32,
+}
+
+impl PfxBuilder {
+    /// Default number of iterations of the key derivation functions, which
+    /// matches OpenSSL.
+    pub const DEFAULT_ITERATIONS: u32 = 2048;
+
+    /// Create a builder using PBES2 with AES-256-CBC for encryption and
+    /// HMAC-SHA256 for the MAC, which are supported by OpenSSL 3 and
+    /// Windows 10 (1709) and later.
+    pub fn new() -> Self {
+        Self {
+            keys: Vec::new(),
+            certificates: Vec::new(),
+            encryption: EncryptionAlgorithm::Pbes2Aes256Cbc,
+            mac: MacAlgorithm::Sha256,
+            iterations: Self::DEFAULT_ITERATIONS,
+        }
+    }
+
+    /// Create a builder using `pbeWithSHAAnd3-KeyTripleDES-CBC` for encryption
+    /// and HMAC-SHA1 for the MAC, for compatibility with OpenSSL 1.x and older
+    /// versions of Windows.
+    pub fn legacy() -> Self {
+        Self {
+            encryption: EncryptionAlgorithm::PbeWithShaAnd3KeyTripleDesCbc,
+            mac: MacAlgorithm::Sha1,
+            ..Self::new()
+        }
+    }
+
+    /// Set the algorithm used to encrypt keys and certificates.
+    pub fn encryption(&mut self, encryption: EncryptionAlgorithm) -> &mut Self {
+        self.encryption = encryption;
+        self
+    }
+
+    /// Set the algorithm used to compute the MAC.
+    pub fn mac(&mut self, mac: MacAlgorithm) -> &mut Self {
+        self.mac = mac;
+        self
+    }
+
+    /// Set the number of iterations of the key derivation functions.
+    pub fn iterations(&mut self, iterations: u32) -> &mut Self {
+        self.iterations = iterations;
+        self
+    }
+
+    /// Add a private key, given as a DER-encoded PKCS#8 `PrivateKeyInfo`, and
+    /// its certificate.
+    ///
+    /// Both are linked with a `localKeyId` attribute containing the SHA-1
+    /// hash of the certificate, and get the given `friendlyName`, if any.
+    pub fn add_key(
+        &mut self,
+        private_key: &[u8],
+        certificate: &Certificate,
+        friendly_name: Option<&str>,
+    ) -> Result<&mut Self> {
+        let certificate_der = certificate.to_der()?;
+        let local_key_id = Sha1::digest(&certificate_der);
+
+        let mut attributes = vec![Attribute {
+            oid: PKCS_9_AT_LOCAL_KEY_ID,
+            values: SetOfVec::try_from(vec![Any::encode_from(&OctetString::new(
+                local_key_id.as_slice(),
+            )?)?])?,
+        }];
+
+        if let Some(friendly_name) = friendly_name {
+            attributes.push(Attribute {
+                oid: PKCS_9_AT_FRIENDLY_NAME,
+                values: SetOfVec::try_from(vec![Any::encode_from(&BmpString::from_utf8(
+                    friendly_name,
+                )?)?])?,
+            });
+        }
+
+        let attributes = Some(SetOfVec::try_from(attributes)?);
+        self.keys
+            .push((Zeroizing::new(private_key.to_vec()), attributes.clone()));
+        self.certificates
+            .push(cert_bag(&certificate_der, attributes)?);
+        Ok(self)
+    }
+
+    /// Add a certificate without a private key, e.g. an intermediate or root
+    /// CA certificate of the chain.
+    pub fn add_certificate(&mut self, certificate: &Certificate) -> Result<&mut Self> {
+        self.certificates
+            .push(cert_bag(&certificate.to_der()?, None)?);
+        Ok(self)
+    }
+
+    /// Build the PFX, encrypting its contents and computing its MAC using the
+    /// given password, and generating salts and IVs using the given RNG.
+    pub fn build<R: CryptoRng + ?Sized>(&self, password: &str, rng: &mut R) -> Result<Pfx> {
+        let mut auth_safe = Vec::new();
+
+        if !self.certificates.is_empty() {
+            let plaintext = self.certificates.to_der()?;
+            let (content_enc_alg, ciphertext) =
+                self.encryption
+                    .encrypt(password, self.iterations, rng, &plaintext)?;
+
+            let encrypted_data = EncryptedData {
+                version: CmsVersion::V0,
+                enc_content_info: EncryptedContentInfo {
+                    content_type: ID_DATA,
+                    content_enc_alg,
+                    encrypted_content: Some(OctetString::new(ciphertext)?),
+                },
+                unprotected_attrs: None,
+            };
+
+            auth_safe.push(ContentInfo {
+                content_type: ID_ENCRYPTED_DATA,
+                content: Any::encode_from(&encrypted_data)?,
+            });
+        }
+
+        if !self.keys.is_empty() {
+            let mut key_bags = Vec::with_capacity(self.keys.len());
+
+            for (private_key, attributes) in &self.keys {
+                let (encryption_algorithm, ciphertext) =
+                    self.encryption
+                        .encrypt(password, self.iterations, rng, private_key)?;
+
+                let encrypted_private_key = EncryptedPrivateKeyInfo {
+                    encryption_algorithm,
+                    encrypted_data: OctetString::new(ciphertext)?,
+                };
+
+                key_bags.push(SafeBag {
+                    bag_id: PKCS_12_PKCS8_KEY_BAG_OID,
+                    bag_value: explicit(&encrypted_private_key)?,
+                    bag_attributes: attributes.clone(),
+                });
+            }
+
+            auth_safe.push(data_content_info(&key_bags.to_der()?)?);
+        }
+
+        let auth_safe_data = auth_safe.to_der()?;
+
+        let mut mac_salt = [0u8; MAC_SALT_LEN];
+        rng.fill_bytes(&mut mac_salt);
+        let iterations = i32::try_from(self.iterations).map_err(|_| Error::EncryptFailed)?;
+        let mac_data = MacData::new(self.mac, password, &mac_salt, iterations, &auth_safe_data)?;
+
+        Ok(Pfx {
+            version: Version::V3,
+            auth_safe: data_content_info(&auth_safe_data)?,
+            mac_data: Some(mac_data),
+        })
+    }
+}
+
+impl Default for PfxBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Create a `certBag` for the given DER-encoded X.509 certificate.
+fn cert_bag(certificate: &[u8], bag_attributes: Option<Attributes>) -> Result<SafeBag> {
+    let cert_bag = CertBag {
+        cert_id: PKCS_12_X509_CERT_OID,
+        cert_value: OctetString::new(certificate)?,
+    };
+
+    Ok(SafeBag {
+        bag_id: PKCS_12_CERT_BAG_OID,
+        bag_value: explicit(&cert_bag)?,
+        bag_attributes,
+    })
+}
+
+/// Encode the given value with the `[0] EXPLICIT` tag of `SafeBag`'s `bagValue`.
+fn explicit<T: EncodeValue + Tagged>(value: &T) -> Result<Vec<u8>> {
+    let value = ContextSpecificRef {
+        tag_number: TagNumber(0),
+        tag_mode: TagMode::Explicit,
+        value,
+    };
+
+    Ok(value.to_der()?)
+}
+
+/// Create a `data` `ContentInfo` with the given DER-encoded content.
+fn data_content_info(content: &[u8]) -> Result<ContentInfo> {
+    Ok(ContentInfo {
+        content_type: ID_DATA,
+        content: Any::encode_from(&OctetString::new(content)?)?,
+    })
+}
@@ -0,0 +1,213 @@
+//! Decrypted contents of a PFX.
+
+use crate::{
+    AuthenticatedSafe, CertBag, Error, PKCS_12_CERT_BAG_OID, PKCS_12_KEY_BAG_OID,
+    PKCS_12_PKCS8_KEY_BAG_OID, PKCS_12_SAFE_CONTENTS_BAG_OID, PKCS_12_X509_CERT_OID, Result,
+    SafeBag, encryption, pbe_params::EncryptedPrivateKeyInfo, safe_bag::SafeContents,
+};
+use alloc::{string::String, vec::Vec};
+use cms::{content_info::ContentInfo, encrypted_data::EncryptedData};
+use const_oid::db::{
+    rfc2985::{PKCS_9_AT_FRIENDLY_NAME, PKCS_9_AT_LOCAL_KEY_ID},
+    rfc5911::{ID_DATA, ID_ENCRYPTED_DATA},
+};
+use der::{
+    AnyRef, Decode, Encode, ErrorKind,
+    asn1::{BmpString, ContextSpecific, OctetString},
+};
+use x509_cert::{Certificate, attr::Attributes};
+use zeroize::Zeroizing;
+
+/// Maximum nesting depth of `safeContentsBag`s, which is only bounded by the
+/// size of the PFX otherwise.
+const MAX_DEPTH: usize = 8;
+
+/// Private keys and certificates contained in a PFX, see [`Pfx::decrypt`].
+///
+/// [`Pfx::decrypt`]: crate::Pfx::decrypt
+#[derive(Clone, Debug, Default)]
+pub struct PfxContents {
+    /// Private keys, from `keyBag`s and `pkcs8ShroudedKeyBag`s.
+    pub keys: Vec<KeyEntry>,
+
+    /// X.509 certificates, from `certBag`s.
+    pub certificates: Vec<CertificateEntry>,
+}
+
+impl PfxContents {
+    /// Find the certificate for the given private key, i.e. the one with the
+    /// same `localKeyId` attribute.
+    pub fn certificate_for(&self, key: &KeyEntry) -> Option<&CertificateEntry> {
+        let local_key_id = key.local_key_id.as_ref()?;
+
+        self.certificates
+            .iter()
+            .find(|cert| cert.local_key_id.as_ref() == Some(local_key_id))
+    }
+
+    /// Add the contents of the given `authSafe`, decrypting password-encrypted
+    /// `EncryptedData` content.
+    pub(crate) fn add_authenticated_safe(
+        &mut self,
+        auth_safe: &AuthenticatedSafe<'_>,
+        password: &str,
+    ) -> Result<()> {
+        for content_info in auth_safe {
+            self.add_content_info(content_info, password)?;
+        }
+
+        Ok(())
+    }
+
+    fn add_content_info(&mut self, content_info: &ContentInfo, password: &str) -> Result<()> {
+        match content_info.content_type {
+            ID_DATA => {
+                let data = content_info.content.decode_as::<OctetString>()?;
+                self.add_safe_contents(&SafeContents::from_der(data.as_bytes())?, password, 0)
+            }
+            ID_ENCRYPTED_DATA => {
+                let encrypted_data = content_info.content.decode_as::<EncryptedData>()?;
+                let info = &encrypted_data.enc_content_info;
+
+                if info.content_type != ID_DATA {
+                    return Err(Error::UnsupportedContentType {
+                        oid: info.content_type,
+                    });
+                }
+
+                let ciphertext = info
+                    .encrypted_content
+                    .as_ref()
+                    .ok_or(Error::DecryptFailed)?;
+                let plaintext =
+                    encryption::decrypt(&info.content_enc_alg, password, ciphertext.as_bytes())?;
+                self.add_safe_contents(&SafeContents::from_der(&plaintext)?, password, 0)
+            }
+            oid => Err(Error::UnsupportedContentType { oid }),
+        }
+    }
+
+    /// Add the bags of the given `SafeContents`, found within `depth` nested
+    /// `safeContentsBag`s.
+    fn add_safe_contents(
+        &mut self,
+        safe_contents: &[SafeBag],
+        password: &str,
+        depth: usize,
+    ) -> Result<()> {
+        if depth > MAX_DEPTH {
+            return Err(Error::Asn1(ErrorKind::Overflow.into()));
+        }
+
+        for safe_bag in safe_contents {
+            let attributes = BagAttributes::from_attributes(safe_bag.bag_attributes.as_ref())?;
+
+            match safe_bag.bag_id {
+                PKCS_12_KEY_BAG_OID => {
+                    let value = ContextSpecific::<AnyRef<'_>>::from_der(&safe_bag.bag_value)?;
+                    self.keys.push(KeyEntry {
+                        private_key: Zeroizing::new(value.value.to_der()?),
+                        local_key_id: attributes.local_key_id,
+                        friendly_name: attributes.friendly_name,
+                    });
+                }
+                PKCS_12_PKCS8_KEY_BAG_OID => {
+                    let value =
+                        ContextSpecific::<EncryptedPrivateKeyInfo>::from_der(&safe_bag.bag_value)?;
+                    let private_key = encryption::decrypt(
+                        &value.value.encryption_algorithm,
+                        password,
+                        value.value.encrypted_data.as_bytes(),
+                    )?;
+
+                    self.keys.push(KeyEntry {
+                        private_key,
+                        local_key_id: attributes.local_key_id,
+                        friendly_name: attributes.friendly_name,
+                    });
+                }
+                PKCS_12_CERT_BAG_OID => {
+                    let value = ContextSpecific::<CertBag>::from_der(&safe_bag.bag_value)?;
+
+                    // Skip other certificate types, e.g. SDSI certificates
+                    if value.value.cert_id == PKCS_12_X509_CERT_OID {
+                        self.certificates.push(CertificateEntry {
+                            certificate: Certificate::from_der(value.value.cert_value.as_bytes())?,
+                            local_key_id: attributes.local_key_id,
+                            friendly_name: attributes.friendly_name,
+                        });
+                    }
+                }
+                PKCS_12_SAFE_CONTENTS_BAG_OID => {
+                    let value = ContextSpecific::<SafeContents>::from_der(&safe_bag.bag_value)?;
+                    self.add_safe_contents(&value.value, password, depth + 1)?;
+                }
+                // Skip other bag types, e.g. CRLs and secrets
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Private key contained in a PFX.
+#[derive(Clone, Debug)]
+pub struct KeyEntry {
+    /// DER-encoded PKCS#8 `PrivateKeyInfo`.
+    pub private_key: Zeroizing<Vec<u8>>,
+
+    /// Value of the `localKeyId` attribute, which links the key to its
+    /// certificate.
+    pub local_key_id: Option<Vec<u8>>,
+
+    /// Value of the `friendlyName` attribute.
+    pub friendly_name: Option<String>,
+}
+
+/// Certificate contained in a PFX.
+#[derive(Clone, Debug)]
+pub struct CertificateEntry {
+    /// X.509 certificate.
+    pub certificate: Certificate,
+
+    /// Value of the `localKeyId` attribute, which links the certificate to its
+    /// private key.
+    pub local_key_id: Option<Vec<u8>>,
+
+    /// Value of the `friendlyName` attribute.
+    pub friendly_name: Option<String>,
+}
+
+/// Attributes of a `SafeBag` which are surfaced in [`PfxContents`].
+#[derive(Default)]
+struct BagAttributes {
+    local_key_id: Option<Vec<u8>>,
+    friendly_name: Option<String>,
+}
+
+impl BagAttributes {
+    fn from_attributes(attributes: Option<&Attributes>) -> Result<Self> {
+        let mut result = Self::default();
+
+        for attribute in attributes.into_iter().flat_map(|attrs| attrs.iter()) {
+            let Some(value) = attribute.values.iter().next() else {
+                continue;
+            };
+
+            match attribute.oid {
+                PKCS_9_AT_LOCAL_KEY_ID => {
+                    let value = value.decode_as::<OctetString>()?;
+                    result.local_key_id = Some(value.as_bytes().to_vec());
+                }
+                PKCS_9_AT_FRIENDLY_NAME => {
+                    let value = value.decode_as::<BmpString>()?;
+                    result.friendly_name = Some(value.chars().collect());
+                }
+                _ => {}
+            }
+        }
+
+        Ok(result)
+    }
+}
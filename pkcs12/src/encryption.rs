@@ -0,0 +1,172 @@
+//! Password-based encryption of PKCS#12 contents.
+
+use crate::{
+    Error, PKCS_12_PBE_WITH_SHAAND2_KEY_TRIPLE_DES_CBC,
+    PKCS_12_PBE_WITH_SHAAND3_KEY_TRIPLE_DES_CBC, PKCS_12_PBE_WITH_SHAAND128_BIT_RC2_CBC,
+    PKCS_12_PBEWITH_SHAAND40_BIT_RC2_CBC, Result,
+    kdf::{Pkcs12KeyType, derive_key_utf8},
+    pbe_params::Pkcs12PbeParams,
+};
+use alloc::vec::Vec;
+use cbc::cipher::{
+    BlockCipherDecrypt, BlockCipherEncrypt, BlockModeDecrypt, BlockModeEncrypt, InnerIvInit,
+    KeyInit, block_padding::Pkcs7,
+};
+use der::{Any, Decode, Encode, asn1::OctetString};
+use pkcs5::pbes2;
+use rand_core::CryptoRng;
+use spki::AlgorithmIdentifierOwned;
+use zeroize::Zeroizing;
+
+/// Length of the salts generated for encryption.
+const SALT_LEN: usize = 16;
+
+/// Password-based encryption algorithms which can be used to encrypt the
+/// contents of a PFX.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum EncryptionAlgorithm {
+    /// PBES2 with PBKDF2-HMAC-SHA256 and AES-256-CBC, as used by OpenSSL 3
+    /// and Windows 10 (1709) and later.
+    Pbes2Aes256Cbc,
+
+    /// `pbeWithSHAAnd3-KeyTripleDES-CBC`, as required by OpenSSL 1.x and older
+    /// versions of Windows.
+    ///
+    /// This uses the PKCS#12 KDF, which is deprecated for new usage.
+    PbeWithShaAnd3KeyTripleDesCbc,
+}
+
+impl EncryptionAlgorithm {
+    /// Encrypt `plaintext` using a key derived from `password` with the given
+    /// number of iterations and a random salt, returning the algorithm
+    /// identifier and the ciphertext.
+    pub(crate) fn encrypt<R: CryptoRng + ?Sized>(
+        self,
+        password: &str,
+        iterations: u32,
+        rng: &mut R,
+        plaintext: &[u8],
+    ) -> Result<(AlgorithmIdentifierOwned, Vec<u8>)> {
+        let mut salt = [0u8; SALT_LEN];
+        rng.fill_bytes(&mut salt);
+
+        match self {
+            Self::Pbes2Aes256Cbc => {
+                let mut iv = [0u8; 16];
+                rng.fill_bytes(&mut iv);
+
+                let params = pbes2::Parameters::pbkdf2_sha256_aes256cbc(iterations, &salt, iv)?;
+                let ciphertext = params.encrypt(password.as_bytes(), plaintext)?;
+                let scheme = pkcs5::EncryptionScheme::from(params);
+                let algorithm = AlgorithmIdentifierOwned::from_der(&scheme.to_der()?)?;
+                Ok((algorithm, ciphertext))
+            }
+            Self::PbeWithShaAnd3KeyTripleDesCbc => {
+                let params = Pkcs12PbeParams {
+                    salt: OctetString::new(salt)?,
+                    iterations: i32::try_from(iterations).map_err(|_| Error::EncryptFailed)?,
+                };
+
+                let (key, iv) = derive_key_and_iv(&params, password, 24)?;
+                let cipher =
+                    des::TdesEde3::new_from_slice(&key).map_err(|_| Error::EncryptFailed)?;
+                let ciphertext = cbc_encrypt(cipher, &iv, plaintext)?;
+
+                let algorithm = AlgorithmIdentifierOwned {
+                    oid: PKCS_12_PBE_WITH_SHAAND3_KEY_TRIPLE_DES_CBC,
+                    parameters: Some(Any::encode_from(&params)?),
+                };
+                Ok((algorithm, ciphertext))
+            }
+        }
+    }
+}
+
+/// Decrypt `ciphertext` which was encrypted with the given algorithm and a key
+/// derived from `password`.
+///
+/// Supports PBES2 (with the algorithms supported by the [`pkcs5`] crate) and
+/// the PKCS#12 password-based encryption schemes using Triple-DES or RC2.
+pub(crate) fn decrypt(
+    algorithm: &AlgorithmIdentifierOwned,
+    password: &str,
+    ciphertext: &[u8],
+) -> Result<Zeroizing<Vec<u8>>> {
+    match algorithm.oid {
+        pbes2::PBES2_OID => {
+            let scheme = pkcs5::EncryptionScheme::try_from(algorithm.to_der()?.as_slice())?;
+            let plaintext = scheme
+                .decrypt(password.as_bytes(), ciphertext)
+                .map_err(|_| Error::DecryptFailed)?;
+            Ok(Zeroizing::new(plaintext))
+        }
+        PKCS_12_PBE_WITH_SHAAND3_KEY_TRIPLE_DES_CBC => {
+            let (key, iv) = derive_key_and_iv(&pbe_params(algorithm)?, password, 24)?;
+            let cipher = des::TdesEde3::new_from_slice(&key).map_err(|_| Error::DecryptFailed)?;
+            cbc_decrypt(cipher, &iv, ciphertext)
+        }
+        PKCS_12_PBE_WITH_SHAAND2_KEY_TRIPLE_DES_CBC => {
+            let (key, iv) = derive_key_and_iv(&pbe_params(algorithm)?, password, 16)?;
+            let cipher = des::TdesEde2::new_from_slice(&key).map_err(|_| Error::DecryptFailed)?;
+            cbc_decrypt(cipher, &iv, ciphertext)
+        }
+        PKCS_12_PBE_WITH_SHAAND128_BIT_RC2_CBC => {
+            let (key, iv) = derive_key_and_iv(&pbe_params(algorithm)?, password, 16)?;
+            cbc_decrypt(rc2::Rc2::new_with_eff_key_len(&key, 128), &iv, ciphertext)
+        }
+        PKCS_12_PBEWITH_SHAAND40_BIT_RC2_CBC => {
+            let (key, iv) = derive_key_and_iv(&pbe_params(algorithm)?, password, 5)?;
+            cbc_decrypt(rc2::Rc2::new_with_eff_key_len(&key, 40), &iv, ciphertext)
+        }
+        oid => Err(Error::UnsupportedAlgorithm { oid }),
+    }
+}
+
+/// Decode the parameters of a PKCS#12 password-based encryption scheme.
+fn pbe_params(algorithm: &AlgorithmIdentifierOwned) -> Result<Pkcs12PbeParams> {
+    let params = algorithm.parameters.as_ref().ok_or(Error::Pkcs5(
+        pkcs5::Error::AlgorithmParametersInvalid { oid: algorithm.oid },
+    ))?;
+
+    Ok(params.decode_as()?)
+}
+
+/// Derive a key of the given length and an 8-byte IV for a PKCS#12
+/// password-based encryption scheme, which all use SHA-1.
+fn derive_key_and_iv(
+    params: &Pkcs12PbeParams,
+    password: &str,
+    key_len: usize,
+) -> Result<(Zeroizing<Vec<u8>>, Vec<u8>)> {
+    let salt = params.salt.as_bytes();
+    let key = derive_key_utf8::<sha1::Sha1>(
+        password,
+        salt,
+        Pkcs12KeyType::EncryptionKey,
+        params.iterations,
+        key_len,
+    )?;
+    let iv =
+        derive_key_utf8::<sha1::Sha1>(password, salt, Pkcs12KeyType::Iv, params.iterations, 8)?;
+
+    Ok((Zeroizing::new(key), iv))
+}
+
+fn cbc_encrypt<C: BlockCipherEncrypt>(cipher: C, iv: &[u8], plaintext: &[u8]) -> Result<Vec<u8>> {
+    Ok(cbc::Encryptor::<C>::inner_iv_slice_init(cipher, iv)
+        .map_err(|_| Error::EncryptFailed)?
+        .encrypt_padded_vec::<Pkcs7>(plaintext))
+}
+
+fn cbc_decrypt<C: BlockCipherDecrypt>(
+    cipher: C,
+    iv: &[u8],
+    ciphertext: &[u8],
+) -> Result<Zeroizing<Vec<u8>>> {
+    cbc::Decryptor::<C>::inner_iv_slice_init(cipher, iv)
+        .map_err(|_| Error::DecryptFailed)?
+        .decrypt_padded_vec::<Pkcs7>(ciphertext)
+        .map(Zeroizing::new)
+        .map_err(|_| Error::DecryptFailed)
+}
@@ -0,0 +1,75 @@
+//! Error types
+
+use const_oid::ObjectIdentifier;
+use core::fmt;
+
+/// Result type
+pub type Result<T> = core::result::Result<T, Error>;
+
+/// Error type
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Error {
+    /// ASN.1 DER-related errors.
+    Asn1(der::Error),
+
+    /// Errors of the PBES2 encryption scheme.
+    Pkcs5(pkcs5::Error),
+
+    /// Decryption failed, e.g. because of an incorrect password.
+    DecryptFailed,
+
+    /// Encryption failed.
+    EncryptFailed,
+
+    /// The MAC of the PFX did not verify, e.g. because of an incorrect password.
+    MacVerificationFailed,
+
+    /// The PFX has no `macData` to verify.
+    MissingMacData,
+
+    /// Algorithm is not supported.
+    UnsupportedAlgorithm {
+        /// OID of the unsupported algorithm.
+        oid: ObjectIdentifier,
+    },
+
+    /// Content type is not supported, e.g. public-key encrypted `EnvelopedData`.
+    UnsupportedContentType {
+        /// OID of the unsupported content type.
+        oid: ObjectIdentifier,
+    },
+}
+
+impl core::error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Asn1(err) => write!(f, "PKCS#12 ASN.1 error: {err}"),
+            Error::Pkcs5(err) => write!(f, "PKCS#12 {err}"),
+            Error::DecryptFailed => f.write_str("PKCS#12 decryption failed"),
+            Error::EncryptFailed => f.write_str("PKCS#12 encryption failed"),
+            Error::MacVerificationFailed => f.write_str("PKCS#12 MAC verification failed"),
+            Error::MissingMacData => f.write_str("PKCS#12 PFX has no MAC"),
+            Error::UnsupportedAlgorithm { oid } => {
+                write!(f, "PKCS#12 algorithm {oid} is unsupported")
+            }
+            Error::UnsupportedContentType { oid } => {
+                write!(f, "PKCS#12 content type {oid} is unsupported")
+            }
+        }
+    }
+}
+
+impl From<der::Error> for Error {
+    fn from(err: der::Error) -> Error {
+        Error::Asn1(err)
+    }
+}
+
+impl From<pkcs5::Error> for Error {
+    fn from(err: pkcs5::Error) -> Error {
+        Error::Pkcs5(err)
+    }
+}
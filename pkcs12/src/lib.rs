@@ -30,6 +30,15 @@ mod crl_type;
 mod digest_info;
 mod mac_data;
 
+#[cfg(feature = "encryption")]
+mod builder;
+#[cfg(feature = "encryption")]
+mod contents;
+#[cfg(feature = "encryption")]
+mod encryption;
+#[cfg(feature = "encryption")]
+mod error;
+
 pub use crate::{
     authenticated_safe::AuthenticatedSafe,
     bag_type::BagType,
@@ -42,6 +51,15 @@ pub use crate::{
 };
 pub use cms;
 
+#[cfg(feature = "encryption")]
+pub use crate::{
+    builder::PfxBuilder,
+    contents::{CertificateEntry, KeyEntry, PfxContents},
+    encryption::EncryptionAlgorithm,
+    error::{Error, Result},
+    mac_data::MacAlgorithm,
+};
+
 use const_oid::ObjectIdentifier;
 
 // pbe oids
@@ -103,9 +121,4 @@ pub const PKCS_12_X509_CERT_OID: ObjectIdentifier =
 pub const PKCS_12_SDSI_CERT_OID: ObjectIdentifier =
     ObjectIdentifier::new_unwrap("1.2.840.113549.1.9.22.2");
 
-// todo: return the friendly name if present? (minimally, defer until BMPString support is available)
 // todo: support separate mac and encryption passwords?
-// todo: add decryption support
-// todo: add more encryption tests
-// todo: add a builder
-// todo: add RC2 support
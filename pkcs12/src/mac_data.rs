@@ -3,6 +3,22 @@
 use crate::digest_info::DigestInfo;
 use der::{Sequence, ValueOrd, asn1::OctetString};
 
+#[cfg(feature = "encryption")]
+use {
+    crate::{
+        Error, Result,
+        kdf::{Pkcs12KeyType, derive_key_utf8},
+    },
+    const_oid::{
+        ObjectIdentifier,
+        db::rfc5912::{ID_SHA_1, ID_SHA_224, ID_SHA_256, ID_SHA_384, ID_SHA_512},
+    },
+    digest::{Digest, FixedOutputReset, OutputSizeUser, block_api::BlockSizeUser},
+    hmac::{EagerHash, Hmac, KeyInit, Mac},
+    spki::AlgorithmIdentifierOwned,
+    zeroize::Zeroizing,
+};
+
 /// The `MacData` type is defined in [RFC 7292 Section 4].
 ///
 /// ```text
@@ -32,3 +48,135 @@ pub struct MacData {
 fn default_one() -> i32 {
     1
 }
+
+/// Digest algorithms which can be used to compute the MAC of a PFX.
+#[cfg(feature = "encryption")]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum MacAlgorithm {
+    /// HMAC-SHA1, as required by OpenSSL 1.x and older versions of Windows.
+    Sha1,
+
+    /// HMAC-SHA256.
+    Sha256,
+
+    /// HMAC-SHA384.
+    Sha384,
+
+    /// HMAC-SHA512.
+    Sha512,
+}
+
+#[cfg(feature = "encryption")]
+impl MacAlgorithm {
+    /// Get the OID of the digest algorithm.
+    pub fn oid(self) -> ObjectIdentifier {
+        match self {
+            Self::Sha1 => ID_SHA_1,
+            Self::Sha256 => ID_SHA_256,
+            Self::Sha384 => ID_SHA_384,
+            Self::Sha512 => ID_SHA_512,
+        }
+    }
+}
+
+#[cfg(feature = "encryption")]
+impl MacData {
+    /// Compute the `MacData` of the given `authSafe` contents using a key
+    /// derived from `password` with the PKCS#12 KDF.
+    pub fn new(
+        algorithm: MacAlgorithm,
+        password: &str,
+        salt: &[u8],
+        iterations: i32,
+        data: &[u8],
+    ) -> Result<Self> {
+        let digest = match algorithm {
+            MacAlgorithm::Sha1 => mac::<sha1::Sha1>(password, salt, iterations, data)?
+                .finalize()
+                .into_bytes()
+                .to_vec(),
+            MacAlgorithm::Sha256 => mac::<sha2::Sha256>(password, salt, iterations, data)?
+                .finalize()
+                .into_bytes()
+                .to_vec(),
+            MacAlgorithm::Sha384 => mac::<sha2::Sha384>(password, salt, iterations, data)?
+                .finalize()
+                .into_bytes()
+                .to_vec(),
+            MacAlgorithm::Sha512 => mac::<sha2::Sha512>(password, salt, iterations, data)?
+                .finalize()
+                .into_bytes()
+                .to_vec(),
+        };
+
+        Ok(Self {
+            mac: DigestInfo {
+                algorithm: AlgorithmIdentifierOwned {
+                    oid: algorithm.oid(),
+                    parameters: None,
+                },
+                digest: OctetString::new(digest)?,
+            },
+            mac_salt: OctetString::new(salt)?,
+            iterations,
+        })
+    }
+
+    /// Verify this MAC of the given `authSafe` contents.
+    ///
+    /// Supports SHA-1 and the SHA-2 family as digest algorithms.
+    pub fn verify(&self, password: &str, data: &[u8]) -> Result<()> {
+        let salt = self.mac_salt.as_bytes();
+        let tag = self.mac.digest.as_bytes();
+
+        let verified = match self.mac.algorithm.oid {
+            ID_SHA_1 => mac::<sha1::Sha1>(password, salt, self.iterations, data)?
+                .verify_slice(tag)
+                .is_ok(),
+            ID_SHA_224 => mac::<sha2::Sha224>(password, salt, self.iterations, data)?
+                .verify_slice(tag)
+                .is_ok(),
+            ID_SHA_256 => mac::<sha2::Sha256>(password, salt, self.iterations, data)?
+                .verify_slice(tag)
+                .is_ok(),
+            ID_SHA_384 => mac::<sha2::Sha384>(password, salt, self.iterations, data)?
+                .verify_slice(tag)
+                .is_ok(),
+            ID_SHA_512 => mac::<sha2::Sha512>(password, salt, self.iterations, data)?
+                .verify_slice(tag)
+                .is_ok(),
+            oid => return Err(Error::UnsupportedAlgorithm { oid }),
+        };
+
+        if verified {
+            Ok(())
+        } else {
+            Err(Error::MacVerificationFailed)
+        }
+    }
+}
+
+#[cfg(feature = "encryption")]
+/// Compute the HMAC of `data` keyed with the PKCS#12 KDF, see
+/// [RFC 7292 Appendix B.4].
+///
+/// [RFC 7292 Appendix B.4]: https://www.rfc-editor.org/rfc/rfc7292#appendix-B.4
+fn mac<D>(password: &str, salt: &[u8], iterations: i32, data: &[u8]) -> Result<Hmac<D>>
+where
+    D: Digest + FixedOutputReset + BlockSizeUser + EagerHash,
+{
+    let key = Zeroizing::new(derive_key_utf8::<D>(
+        password,
+        salt,
+        Pkcs12KeyType::Mac,
+        iterations,
+        <D as OutputSizeUser>::output_size(),
+    )?);
+
+    // HMAC accepts keys of any length
+    let mut mac =
+        <Hmac<D> as KeyInit>::new_from_slice(&key).map_err(|_| Error::MacVerificationFailed)?;
+    mac.update(data);
+    Ok(mac)
+}
@@ -7,6 +7,13 @@ use der::{Enumerated, Sequence, ValueOrd};
 use crate::mac_data::MacData;
 use cms::content_info::ContentInfo;
 
+#[cfg(feature = "encryption")]
+use {
+    crate::{AuthenticatedSafe, Error, PfxContents, Result},
+    const_oid::db::rfc5911::ID_DATA,
+    der::{Decode, asn1::OctetString},
+};
+
 /// just the version v3
 #[derive(Clone, Copy, Debug, Enumerated, Eq, PartialEq, PartialOrd, Ord)]
 #[asn1(type = "INTEGER")]
@@ -45,3 +52,51 @@ pub struct Pfx {
     /// the message digest info
     pub mac_data: Option<MacData>,
 }
+
+#[cfg(feature = "encryption")]
+impl Pfx {
+    /// Get the contents of the `authSafe`, i.e. the DER-encoded
+    /// [`AuthenticatedSafe`] which is protected by the MAC.
+    ///
+    /// Only password integrity mode is supported, i.e. the `authSafe` must
+    /// have the `data` content type.
+    ///
+    /// [`AuthenticatedSafe`]: crate::AuthenticatedSafe
+    pub fn auth_safe_data(&self) -> Result<OctetString> {
+        match self.auth_safe.content_type {
+            ID_DATA => Ok(self.auth_safe.content.decode_as()?),
+            oid => Err(Error::UnsupportedContentType { oid }),
+        }
+    }
+
+    /// Verify the MAC of this PFX using the given password.
+    pub fn verify_mac(&self, password: &str) -> Result<()> {
+        let mac_data = self.mac_data.as_ref().ok_or(Error::MissingMacData)?;
+        mac_data.verify(password, self.auth_safe_data()?.as_bytes())
+    }
+
+    /// Verify the MAC of this PFX if present, then decrypt its contents using
+    /// the given password.
+    ///
+    /// PFXs without `macData` are accepted, as produced by e.g.
+    /// `openssl pkcs12 -nomac`: use [`Pfx::verify_mac`] to require one.
+    ///
+    /// Password-encrypted `EncryptedData` content and `pkcs8ShroudedKeyBag`s
+    /// are decrypted using PBES2 or the PKCS#12 password-based encryption
+    /// schemes using Triple-DES or RC2, using the same password as the MAC.
+    /// Bags other than keys and X.509 certificates are skipped, and
+    /// `safeContentsBag`s nested too deeply are rejected with
+    /// [`der::ErrorKind::Overflow`].
+    pub fn decrypt(&self, password: &str) -> Result<PfxContents> {
+        let data = self.auth_safe_data()?;
+
+        if let Some(mac_data) = &self.mac_data {
+            mac_data.verify(password, data.as_bytes())?;
+        }
+
+        let mut contents = PfxContents::default();
+        contents
+            .add_authenticated_safe(&AuthenticatedSafe::from_der(data.as_bytes())?, password)?;
+        Ok(contents)
+    }
+}
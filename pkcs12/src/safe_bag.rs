@@ -58,16 +58,11 @@ impl<'a> ::der::DecodeValue<'a> for SafeBag {
 }
 impl ::der::EncodeValue for SafeBag {
     fn value_len(&self) -> ::der::Result<::der::Length> {
-        let content = AnyRef::from_der(&self.bag_value)?;
         use ::der::Encode as _;
+        // `bag_value` holds the complete `[0] EXPLICIT` element
         [
             self.bag_id.encoded_len()?,
-            ::der::asn1::ContextSpecificRef {
-                tag_number: ::der::TagNumber(0),
-                tag_mode: ::der::TagMode::Explicit,
-                value: &content,
-            }
-            .encoded_len()?,
+            AnyRef::from_der(&self.bag_value)?.encoded_len()?,
             self.bag_attributes.encoded_len()?,
         ]
         .into_iter()
@@ -76,13 +71,7 @@ impl ::der::EncodeValue for SafeBag {
     fn encode_value(&self, writer: &mut impl ::der::Writer) -> ::der::Result<()> {
         use ::der::Encode as _;
         self.bag_id.encode(writer)?;
-        let content = AnyRef::from_der(&self.bag_value)?;
-        ::der::asn1::ContextSpecificRef {
-            tag_number: ::der::TagNumber(0),
-            tag_mode: ::der::TagMode::Explicit,
-            value: &content,
-        }
-        .encode(writer)?;
+        AnyRef::from_der(&self.bag_value)?.encode(writer)?;
         self.bag_attributes.encode(writer)?;
         Ok(())
     }
@@ -0,0 +1,190 @@
+#![cfg(feature = "encryption")]
+//! Tests for decrypting and building PFXs.
+
+use der::{Decode, Encode};
+use pkcs12::{EncryptionAlgorithm, Error, MacAlgorithm, PfxBuilder, PfxContents, pfx::Pfx};
+use rand_core::{OsRng, UnwrapErr};
+use x509_cert::Certificate;
+
+const KEY: &[u8] = include_bytes!("examples/key.der");
+const CERT: &[u8] = include_bytes!("examples/cert.der");
+const EE_KEY: &[u8] = include_bytes!("examples/ValidCertificatePathTest1EE.key");
+const EE_CERT: &[u8] = include_bytes!("examples/ValidCertificatePathTest1EE.crt");
+const CA_CERT: &[u8] = include_bytes!("examples/GoodCACert.der");
+
+fn assert_contents(contents: &PfxContents, key: &[u8], cert: &[u8]) {
+    assert_eq!(contents.keys.len(), 1);
+    assert_eq!(contents.keys[0].private_key.as_slice(), key);
+
+    let entry = contents.certificate_for(&contents.keys[0]).unwrap();
+    assert_eq!(entry.certificate.to_der().unwrap(), cert);
+}
+
+#[test]
+fn decrypt_openssl() {
+    for (der, password) in [
+        // Empty password
+        (&include_bytes!("examples/example.pfx")[..], ""),
+        // Unencrypted certificates
+        (include_bytes!("examples/example2.pfx"), "1234"),
+        // No MAC
+        (include_bytes!("examples/example5.pfx"), "1234"),
+        (include_bytes!("examples/example6.pfx"), "1234"),
+        (include_bytes!("examples/example8.pfx"), "1234"),
+        (include_bytes!("examples/example17.pfx"), "1234"),
+    ] {
+        let pfx = Pfx::from_der(der).unwrap();
+        assert_contents(&pfx.decrypt(password).unwrap(), KEY, CERT);
+    }
+}
+
+#[test]
+fn decrypt_legacy() {
+    let mut keys = Vec::new();
+
+    for der in [
+        // pbeWithSHAAnd3-KeyTripleDES-CBC
+        &include_bytes!("examples/ValidCertificatePathTest1EE.p12")[..],
+        include_bytes!("examples/ValidCertificatePathTest1EE_windows_tdes.p12.pfx"),
+        // pbeWithSHAAnd40BitRC2-CBC
+        include_bytes!("examples/ValidCertificatePathTest1EE_macos.p12"),
+        // PBES2
+        include_bytes!("examples/ValidCertificatePathTest1EE_windows_aes.p12.pfx"),
+    ] {
+        let pfx = Pfx::from_der(der).unwrap();
+        let contents = pfx.decrypt("password").unwrap();
+        assert_eq!(contents.keys.len(), 1);
+
+        let key = &contents.keys[0];
+        assert_eq!(
+            key.friendly_name.as_deref(),
+            Some("Valid Certificate Path Test1 EE")
+        );
+        let entry = contents.certificate_for(key).unwrap();
+        assert_eq!(entry.certificate.to_der().unwrap(), EE_CERT);
+
+        // The `PrivateKeyInfo` encodings differ between the tools
+        let private_key = pkcs8::PrivateKeyInfoRef::from_der(&key.private_key).unwrap();
+        keys.push(private_key.private_key.as_bytes().to_vec());
+    }
+
+    assert!(keys.windows(2).all(|pair| pair[0] == pair[1]));
+}
+
+#[test]
+fn decrypt_wrong_password() {
+    let pfx = Pfx::from_der(include_bytes!("examples/example3.pfx")).unwrap();
+    assert_eq!(pfx.verify_mac("4321"), Err(Error::MacVerificationFailed));
+    assert_eq!(
+        pfx.decrypt("4321").unwrap_err(),
+        Error::MacVerificationFailed
+    );
+    assert!(pfx.verify_mac("1234").is_ok());
+}
+
+#[test]
+fn decrypt_missing_mac() {
+    let pfx = Pfx::from_der(include_bytes!("examples/example5.pfx")).unwrap();
+    assert_eq!(pfx.verify_mac("1234"), Err(Error::MissingMacData));
+}
+
+#[test]
+fn decrypt_unsupported_mac() {
+    // HMAC-BLAKE2b-512
+    let pfx = Pfx::from_der(include_bytes!("examples/example11.pfx")).unwrap();
+    assert!(matches!(
+        pfx.decrypt("1234"),
+        Err(Error::UnsupportedAlgorithm { .. })
+    ));
+}
+
+#[test]
+fn build_round_trip() {
+    let cert = Certificate::from_der(EE_CERT).unwrap();
+    let ca_cert = Certificate::from_der(CA_CERT).unwrap();
+
+    for mut builder in [PfxBuilder::new(), PfxBuilder::legacy()] {
+        builder
+            .add_key(EE_KEY, &cert, Some("ee"))
+            .unwrap()
+            .add_certificate(&ca_cert)
+            .unwrap();
+
+        let pfx = builder.build("password", &mut UnwrapErr(OsRng)).unwrap();
+        let pfx = Pfx::from_der(&pfx.to_der().unwrap()).unwrap();
+
+        let contents = pfx.decrypt("password").unwrap();
+        assert_contents(&contents, EE_KEY, EE_CERT);
+        assert_eq!(contents.keys[0].friendly_name.as_deref(), Some("ee"));
+        assert_eq!(contents.certificates.len(), 2);
+        assert_eq!(
+            contents.certificates[1].certificate.to_der().unwrap(),
+            CA_CERT
+        );
+
+        assert_eq!(
+            pfx.decrypt("wrong").unwrap_err(),
+            Error::MacVerificationFailed
+        );
+    }
+}
+
+#[test]
+fn build_algorithms() {
+    let cert = Certificate::from_der(CERT).unwrap();
+    let mut builder = PfxBuilder::new();
+    builder
+        .encryption(EncryptionAlgorithm::PbeWithShaAnd3KeyTripleDesCbc)
+        .mac(MacAlgorithm::Sha512)
+        .iterations(1)
+        .add_key(KEY, &cert, None)
+        .unwrap();
+
+    let pfx = builder.build("", &mut UnwrapErr(OsRng)).unwrap();
+    assert_contents(&pfx.decrypt("").unwrap(), KEY, CERT);
+}
+
+#[test]
+fn decrypt_nested_too_deep() {
+    use der::{
+        Any, ErrorKind, TagMode, TagNumber,
+        asn1::{ContextSpecific, OctetString},
+    };
+    use pkcs12::{PKCS_12_SAFE_CONTENTS_BAG_OID, SafeBag, cms::content_info::ContentInfo, pfx};
+
+    const ID_DATA: der::asn1::ObjectIdentifier =
+        der::asn1::ObjectIdentifier::new_unwrap("1.2.840.113549.1.7.1");
+
+    let data = |der: Vec<u8>| ContentInfo {
+        content_type: ID_DATA,
+        content: Any::encode_from(&OctetString::new(der).unwrap()).unwrap(),
+    };
+
+    // `safeContentsBag`s nested far deeper than any legitimate PFX
+    let mut safe_contents: Vec<SafeBag> = Vec::new();
+    for _ in 0..32 {
+        let bag_value = ContextSpecific {
+            tag_number: TagNumber(0),
+            tag_mode: TagMode::Explicit,
+            value: safe_contents,
+        };
+
+        safe_contents = vec![SafeBag {
+            bag_id: PKCS_12_SAFE_CONTENTS_BAG_OID,
+            bag_value: bag_value.to_der().unwrap(),
+            bag_attributes: None,
+        }];
+    }
+
+    let auth_safe = vec![data(safe_contents.to_der().unwrap())];
+    let pfx = Pfx {
+        version: pfx::Version::V3,
+        auth_safe: data(auth_safe.to_der().unwrap()),
+        mac_data: None,
+    };
+
+    match pfx.decrypt("") {
+        Err(Error::Asn1(err)) => assert_eq!(err.kind(), ErrorKind::Overflow),
+        other => panic!("unexpected result: {other:?}"),
+    }
+}
@@ -5,7 +5,7 @@
 use crate::AlgorithmIdentifierRef;
 use der::{
     Decode, DecodeValue, Encode, EncodeValue, ErrorKind, Length, Reader, Sequence, Tag, Writer,
-    asn1::{AnyRef, ObjectIdentifier, OctetStringRef},
+    asn1::{AnyRef, ObjectIdentifier, OctetStringArray},
 };
 
 /// `pbeWithMD2AndDES-CBC` Object Identifier (OID).
@@ -136,11 +136,11 @@ impl<'a> DecodeValue<'a> for Parameters {
 
 impl EncodeValue for Parameters {
     fn value_len(&self) -> der::Result<Length> {
-        OctetStringRef::new(&self.salt)?.encoded_len()? + self.iteration_count.encoded_len()?
+        OctetStringArray(self.salt).encoded_len()? + self.iteration_count.encoded_len()?
     }
 
     fn encode_value(&self, writer: &mut impl Writer) -> der::Result<()> {
-        OctetStringRef::new(&self.salt)?.encode(writer)?;
+        OctetStringArray(self.salt).encode(writer)?;
         self.iteration_count.encode(writer)?;
         Ok(())
     }
@@ -154,10 +154,7 @@ impl TryFrom<AnyRef<'_>> for Parameters {
     fn try_from(any: AnyRef<'_>) -> der::Result<Parameters> {
         any.sequence(|reader| {
             Ok(Parameters {
-                salt: <&OctetStringRef>::decode(reader)?
-                    .as_bytes()
-                    .try_into()
-                    .map_err(|_| Tag::OctetString.value_error())?,
+                salt: OctetStringArray::decode(reader)?.into(),
                 iteration_count: reader.decode()?,
             })
         })
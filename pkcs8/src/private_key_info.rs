@@ -118,6 +118,34 @@ impl<Params, Key, PubKey> PrivateKeyInfo<Params, Key, PubKey> {
         }
     }
 
+    /// Set the public key of this [`PrivateKeyInfo`], making it a PKCS#8 v2
+    /// `OneAsymmetricKey` message.
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")]
+    /// # {
+    /// use der::asn1::{BitString, ObjectIdentifier, OctetString};
+    /// use pkcs8::{PrivateKeyInfoOwned, Version};
+    /// use spki::AlgorithmIdentifier;
+    ///
+    /// let algorithm = AlgorithmIdentifier {
+    ///     oid: ObjectIdentifier::new_unwrap("1.3.101.112"),
+    ///     parameters: None,
+    /// };
+    ///
+    /// let private_key = OctetString::new([0x04, 0x20].iter().chain(&[0x42; 32]).copied().collect::<Vec<u8>>())?;
+    /// let public_key = BitString::from_bytes(&[0x24; 32])?;
+    ///
+    /// let key = PrivateKeyInfoOwned::new(algorithm, private_key).with_public_key(public_key);
+    /// assert_eq!(key.version(), Version::V2);
+    /// # }
+    /// # Ok::<(), der::Error>(())
+    /// ```
+    pub fn with_public_key(mut self, public_key: PubKey) -> Self {
+        self.public_key = Some(public_key);
+        self
+    }
+
     /// Get the PKCS#8 [`Version`] for this structure.
     ///
     /// [`Version::V1`] if `public_key` is `None`, [`Version::V2`] if `Some`.
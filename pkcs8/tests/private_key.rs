@@ -5,7 +5,10 @@ use hex_literal::hex;
 use pkcs8::{PrivateKeyInfoRef, Version};
 
 #[cfg(feature = "alloc")]
-use der::Encode;
+use {
+    der::{Decode, Encode},
+    pkcs8::PrivateKeyInfoOwned,
+};
 
 #[cfg(feature = "pem")]
 use der::{EncodePem, pem::LineEnding};
@@ -204,6 +207,35 @@ fn encode_ed25519_der_v2() {
     );
 }
 
+#[test]
+#[cfg(feature = "alloc")]
+fn encode_ed25519_der_v2_with_public_key() {
+    // `ED25519_DER_V2_EXAMPLE` without its attributes, which aren't preserved
+    const EXPECTED_DER: [u8; 83] = hex!(
+        "3051020101300506032B657004220420D4EE72DBF913584AD5B6D8F1F769F8AD3AFE7C28CBF1D4FBE097A88F44755842"
+        "81210019BF44096984CDFE8541BAC167DC3B96C85086AA30B6B6CB0C5C38AD703166E1"
+    );
+
+    let pk = PrivateKeyInfoRef::try_from(ED25519_DER_V2_EXAMPLE).unwrap();
+    let v1 = PrivateKeyInfoRef::new(pk.algorithm, pk.private_key);
+    assert_eq!(v1.version(), Version::V1);
+
+    let v2 = v1.with_public_key(pk.public_key.unwrap());
+    assert_eq!(v2.version(), Version::V2);
+    assert_eq!(v2.to_der().unwrap(), EXPECTED_DER);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn encode_ed25519_der_v2_owned() {
+    let pk = PrivateKeyInfoOwned::try_from(ED25519_DER_V2_EXAMPLE).unwrap();
+    let public_key = pk.public_key.clone().unwrap();
+
+    let decoded = PrivateKeyInfoOwned::from_der(&pk.to_der().unwrap()).unwrap();
+    assert_eq!(decoded.version(), Version::V2);
+    assert_eq!(decoded.public_key, Some(public_key));
+}
+
 #[test]
 #[cfg(feature = "alloc")]
 fn encode_rsa_2048_der() {
@@ -100,17 +100,24 @@ pub type AlgorithmIdentifierOwned = AlgorithmIdentifier<Any>;
 
 impl<Params> AlgorithmIdentifier<Params> {
     /// Assert the `algorithm` OID is an expected value.
+    ///
+    /// Returns [`Error::OidUnexpected`] with both OIDs if it differs.
     pub fn assert_algorithm_oid(&self, expected_oid: ObjectIdentifier) -> Result<ObjectIdentifier> {
         if self.oid == expected_oid {
             Ok(expected_oid)
         } else {
-            Err(Error::OidUnknown { oid: self.oid })
+            Err(Error::OidUnexpected {
+                expected: expected_oid,
+                actual: self.oid,
+            })
         }
     }
 }
 
 impl<'a> AlgorithmIdentifierRef<'a> {
     /// Assert `parameters` is an OID and has the expected value.
+    ///
+    /// Returns [`Error::OidUnexpected`] with both OIDs if it differs.
     pub fn assert_parameters_oid(
         &self,
         expected_oid: ObjectIdentifier,
@@ -120,7 +127,10 @@ impl<'a> AlgorithmIdentifierRef<'a> {
         if actual_oid == expected_oid {
             Ok(actual_oid)
         } else {
-            Err(Error::OidUnknown { oid: actual_oid })
+            Err(Error::OidUnexpected {
+                expected: expected_oid,
+                actual: actual_oid,
+            })
         }
     }
 
@@ -30,6 +30,15 @@ pub enum Error {
         /// Unrecognized OID value found in e.g. a SPKI `AlgorithmIdentifier`.
         oid: ObjectIdentifier,
     },
+
+    /// Algorithm OID differs from the expected one.
+    OidUnexpected {
+        /// Expected OID value.
+        expected: ObjectIdentifier,
+
+        /// OID value found in e.g. a SPKI `AlgorithmIdentifier`.
+        actual: ObjectIdentifier,
+    },
 }
 
 impl fmt::Display for Error {
@@ -43,6 +52,12 @@ impl fmt::Display for Error {
             Error::OidUnknown { oid } => {
                 write!(f, "unknown/unsupported algorithm OID: {oid}")
             }
+            Error::OidUnexpected { expected, actual } => {
+                write!(
+                    f,
+                    "unexpected algorithm OID: expected {expected}, got {actual}"
+                )
+            }
         }
     }
 }
@@ -0,0 +1,130 @@
+//! `SubjectPublicKeyInfo` fingerprints.
+
+use core::fmt;
+use digest::{Output, OutputSizeUser};
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+/// Fingerprint of a [`SubjectPublicKeyInfo`], i.e. the digest of its DER
+/// encoding computed using the digest algorithm `D`.
+///
+/// Computed using [`SubjectPublicKeyInfo::fingerprint`], it can be rendered in
+/// the forms used by key pinning tools:
+///
+/// - lowercase or uppercase hexadecimal using the [`fmt::LowerHex`] and
+///   [`fmt::UpperHex`] impls, e.g. `55dd4c74...`
+/// - colon-separated uppercase hexadecimal, as shown by OpenSSL, using
+///   [`Fingerprint::to_colon_hex`], e.g. `55:DD:4C:74:...`
+/// - Base64 as used by HTTP Public Key Pinning ([RFC7469 § 2.1.1]) using
+///   [`Fingerprint::to_base64`], or without padding as used by OpenSSH using
+///   [`Fingerprint::to_base64_unpadded`]
+///
+/// [`SubjectPublicKeyInfo`]: crate::SubjectPublicKeyInfo
+/// [`SubjectPublicKeyInfo::fingerprint`]: crate::SubjectPublicKeyInfo::fingerprint
+/// [RFC7469 § 2.1.1]: https://datatracker.ietf.org/doc/html/rfc7469#section-2.1.1
+pub struct Fingerprint<D: OutputSizeUser> {
+    /// Digest of the DER encoding.
+    bytes: Output<D>,
+}
+
+impl<D: OutputSizeUser> Fingerprint<D> {
+    /// Create a [`Fingerprint`] from the digest of a DER encoding.
+    pub fn new(bytes: Output<D>) -> Self {
+        Self { bytes }
+    }
+
+    /// Get the raw bytes of this fingerprint.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Convert into the digest output.
+    pub fn into_output(self) -> Output<D> {
+        self.bytes
+    }
+
+    /// Encode this fingerprint as lowercase hexadecimal.
+    #[cfg(feature = "alloc")]
+    pub fn to_hex(&self) -> String {
+        alloc::format!("{self:x}")
+    }
+
+    /// Encode this fingerprint as colon-separated uppercase hexadecimal, e.g.
+    /// `55:DD:4C:74`.
+    #[cfg(feature = "alloc")]
+    pub fn to_colon_hex(&self) -> String {
+        use core::fmt::Write;
+        let mut hex = String::with_capacity(self.bytes.len() * 3);
+
+        for (i, byte) in self.bytes.iter().enumerate() {
+            if i > 0 {
+                hex.push(':');
+            }
+
+            write!(hex, "{byte:02X}").expect("writing to a String can't fail");
+        }
+
+        hex
+    }
+
+    /// Encode this fingerprint as padded Base64.
+    #[cfg(all(feature = "alloc", feature = "base64"))]
+    pub fn to_base64(&self) -> String {
+        use base64ct::{Base64, Encoding};
+        Base64::encode_string(&self.bytes)
+    }
+
+    /// Encode this fingerprint as unpadded Base64.
+    #[cfg(all(feature = "alloc", feature = "base64"))]
+    pub fn to_base64_unpadded(&self) -> String {
+        use base64ct::{Base64Unpadded, Encoding};
+        Base64Unpadded::encode_string(&self.bytes)
+    }
+}
+
+impl<D: OutputSizeUser> AsRef<[u8]> for Fingerprint<D> {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl<D: OutputSizeUser> Clone for Fingerprint<D> {
+    fn clone(&self) -> Self {
+        Self::new(self.bytes.clone())
+    }
+}
+
+impl<D: OutputSizeUser> fmt::Debug for Fingerprint<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Fingerprint({self:X})")
+    }
+}
+
+impl<D: OutputSizeUser> Eq for Fingerprint<D> {}
+
+impl<D: OutputSizeUser> PartialEq for Fingerprint<D> {
+    fn eq(&self, other: &Self) -> bool {
+        self.bytes == other.bytes
+    }
+}
+
+impl<D: OutputSizeUser> fmt::LowerHex for Fingerprint<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in self.bytes.iter() {
+            write!(f, "{byte:02x}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<D: OutputSizeUser> fmt::UpperHex for Fingerprint<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in self.bytes.iter() {
+            write!(f, "{byte:02X}")?;
+        }
+
+        Ok(())
+    }
+}
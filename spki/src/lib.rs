@@ -45,6 +45,8 @@ mod traits;
 
 #[cfg(feature = "digest")]
 mod digest;
+#[cfg(feature = "digest")]
+mod fingerprint;
 
 pub use crate::{
     algorithm::{AlgorithmIdentifier, AlgorithmIdentifierRef, AlgorithmIdentifierWithOid},
@@ -68,7 +70,7 @@ pub use {
 };
 
 #[cfg(feature = "digest")]
-pub use crate::digest::DigestWriter;
+pub use crate::{digest::DigestWriter, fingerprint::Fingerprint};
 
 /// Size of a SHA-256 SPKI fingerprint in bytes.
 #[cfg(feature = "fingerprint")]
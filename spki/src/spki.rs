@@ -14,13 +14,15 @@ use der::{
     asn1::{Any, BitString},
 };
 
-#[cfg(feature = "fingerprint")]
+#[cfg(feature = "digest")]
 use {
-    crate::{DigestWriter, FingerprintBytes},
+    crate::{DigestWriter, Fingerprint},
     digest::Digest,
-    sha2::Sha256,
 };
 
+#[cfg(feature = "fingerprint")]
+use {crate::FingerprintBytes, sha2::Sha256};
+
 #[cfg(feature = "pem")]
 use der::pem::PemLabel;
 
@@ -76,6 +78,18 @@ where
         Ok(Base64::encode_string(&self.fingerprint_bytes()?))
     }
 
+    /// Calculate the fingerprint of this [`SubjectPublicKeyInfo`] using the
+    /// digest algorithm `D`, i.e. the digest of its DER encoding.
+    ///
+    /// The resulting [`Fingerprint`] can be encoded in the usual textual
+    /// forms, such as hexadecimal and Base64.
+    #[cfg(feature = "digest")]
+    pub fn fingerprint<D: Digest>(&self) -> Result<Fingerprint<D>> {
+        let mut hash = D::new();
+        self.encode(&mut DigestWriter(&mut hash))?;
+        Ok(Fingerprint::new(hash.finalize()))
+    }
+
     /// Calculate the SHA-256 fingerprint of this [`SubjectPublicKeyInfo`] as
     /// a raw byte array.
     ///
@@ -84,9 +98,7 @@ where
     /// [RFC7469 § 2.1.1]: https://datatracker.ietf.org/doc/html/rfc7469#section-2.1.1
     #[cfg(feature = "fingerprint")]
     pub fn fingerprint_bytes(&self) -> Result<FingerprintBytes> {
-        let mut hash = Sha256::new();
-        self.encode(&mut DigestWriter(&mut hash))?;
-        Ok(hash.finalize().into())
+        Ok(self.fingerprint::<Sha256>()?.into_output().into())
     }
 }
 
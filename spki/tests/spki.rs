@@ -2,7 +2,7 @@
 
 use der::asn1::ObjectIdentifier;
 use hex_literal::hex;
-use spki::SubjectPublicKeyInfoRef;
+use spki::{Error, SubjectPublicKeyInfoRef};
 
 #[cfg(feature = "alloc")]
 use {
@@ -91,6 +91,48 @@ fn decode_ed25519_and_fingerprint_spki() {
     );
 }
 
+#[test]
+#[cfg(feature = "fingerprint")]
+fn fingerprint_generic_digest() {
+    let spki = SubjectPublicKeyInfoRef::try_from(ED25519_DER_EXAMPLE).unwrap();
+    let fingerprint = spki.fingerprint::<sha2::Sha256>().unwrap();
+    assert_eq!(fingerprint.as_bytes(), ED25519_SPKI_FINGERPRINT);
+    assert_eq!(
+        format!("{fingerprint:x}"),
+        "55dd4c74b0e48534e2f4e173ceceb50df8f27a7ac2aa8991cc7ae914e030bced"
+    );
+
+    // Fingerprints can use any digest algorithm
+    use sha2::Digest;
+    let sha384 = spki.fingerprint::<sha2::Sha384>().unwrap();
+    assert_eq!(
+        sha384.as_bytes(),
+        sha2::Sha384::digest(ED25519_DER_EXAMPLE).as_slice()
+    );
+}
+
+#[test]
+#[cfg(all(feature = "alloc", feature = "base64", feature = "fingerprint"))]
+fn fingerprint_encodings() {
+    let spki = SubjectPublicKeyInfoRef::try_from(ED25519_DER_EXAMPLE).unwrap();
+    let fingerprint = spki.fingerprint::<sha2::Sha256>().unwrap();
+
+    assert_eq!(
+        fingerprint.to_hex(),
+        "55dd4c74b0e48534e2f4e173ceceb50df8f27a7ac2aa8991cc7ae914e030bced"
+    );
+    assert_eq!(
+        fingerprint.to_colon_hex(),
+        "55:DD:4C:74:B0:E4:85:34:E2:F4:E1:73:CE:CE:B5:0D:\
+         F8:F2:7A:7A:C2:AA:89:91:CC:7A:E9:14:E0:30:BC:ED"
+    );
+    assert_eq!(fingerprint.to_base64(), ED25519_SPKI_FINGERPRINT_BASE64);
+    assert_eq!(
+        fingerprint.to_base64_unpadded(),
+        ED25519_SPKI_FINGERPRINT_BASE64.trim_end_matches('=')
+    );
+}
+
 #[test]
 #[cfg(all(feature = "alloc", feature = "base64", feature = "fingerprint"))]
 fn decode_ed25519_and_fingerprint_base64() {
@@ -111,6 +153,44 @@ fn decode_ed25519_and_fingerprint_base64() {
     );
 }
 
+#[test]
+fn assert_oids() {
+    const ID_EC_PUBLIC_KEY: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.2.1");
+    const SECP256R1: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.3.1.7");
+    const SECP384R1: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.132.0.34");
+    const ID_ED25519: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.101.112");
+
+    let algorithm = SubjectPublicKeyInfoRef::try_from(EC_P256_DER_EXAMPLE)
+        .unwrap()
+        .algorithm;
+
+    assert_eq!(algorithm.assert_oids(ID_EC_PUBLIC_KEY, SECP256R1), Ok(()));
+
+    let algorithm_err = algorithm.assert_algorithm_oid(ID_ED25519).unwrap_err();
+    let parameters_err = algorithm
+        .assert_oids(ID_EC_PUBLIC_KEY, SECP384R1)
+        .unwrap_err();
+
+    assert_eq!(
+        algorithm_err,
+        Error::OidUnexpected {
+            expected: ID_ED25519,
+            actual: ID_EC_PUBLIC_KEY,
+        }
+    );
+    assert_eq!(
+        algorithm_err.to_string(),
+        "unexpected algorithm OID: expected 1.3.101.112, got 1.2.840.10045.2.1"
+    );
+    assert_eq!(
+        parameters_err,
+        Error::OidUnexpected {
+            expected: SECP384R1,
+            actual: SECP256R1,
+        }
+    );
+}
+
 #[test]
 fn decode_rsa_2048_der() {
     let spki = SubjectPublicKeyInfoRef::try_from(RSA_2048_DER_EXAMPLE).unwrap();
@@ -3,7 +3,7 @@ use alloc::string::String;
 use alloc::string::ToString;
 use der::{
     Choice, ValueOrd,
-    asn1::{Any, BmpString, PrintableString, TeletexString},
+    asn1::{Any, BmpString, PrintableString, TeletexString, matching},
 };
 
 /// DirectoryString as defined in [RFC 5280 Section 4.2.1.4].
@@ -78,6 +78,14 @@ impl DirectoryString {
         }
     }
 
+    /// Does this string match `other` according to X.520 `caseIgnoreMatch`,
+    /// regardless of the string types they're encoded as?
+    ///
+    /// See [`der::asn1::matching`] for details.
+    pub fn case_ignore_eq(&self, other: &Self) -> bool {
+        matching::case_ignore_eq(&self.value(), &other.value())
+    }
+
     /// Returns `&str` for `PrintableString`, `TeletexString` and `Utf8String`
     ///
     /// Warning: Returns `""` empty string for [`DirectoryString::BmpString`] variant
@@ -2,7 +2,9 @@
 
 use core::{fmt, marker::PhantomData, str::FromStr, time::Duration};
 use der::asn1::{GeneralizedTime, UtcTime};
-use der::{Choice, DateTime, DecodeValue, Encode, Header, Length, Reader, Sequence, ValueOrd};
+use der::{
+    Choice, DateTime, DecodeValue, Encode, Header, Length, OutOfRange, Reader, Sequence, ValueOrd,
+};
 
 #[cfg(feature = "std")]
 use std::time::SystemTime;
@@ -80,6 +82,15 @@ impl Time {
         Ok(())
     }
 
+    /// Create a `Time` from the given number of seconds since `UNIX_EPOCH`, handling timestamps
+    /// before 1970 or after 9999 according to the given [`OutOfRange`] policy.
+    ///
+    /// As per RFC 5280: 4.1.2.5, dates through 2049 are expressed as UTCTime, and later dates as
+    /// GeneralizedTime.
+    pub fn from_unix_secs(secs: i64, out_of_range: OutOfRange) -> der::Result<Self> {
+        DateTime::from_unix_secs(secs, out_of_range).map(Self::from)
+    }
+
     /// Creates a `Time` from the current date.
     #[cfg(feature = "std")]
     pub fn now() -> der::Result<Self> {
@@ -264,4 +275,21 @@ mod tests {
         assert!(matches!(time, Time::GeneralTime(_)));
         assert_eq!(alloc::format!("{}", time), "2050-01-01T00:00:00Z");
     }
+
+    #[test]
+    fn from_unix_secs() {
+        let time = Time::from_unix_secs(2_524_607_999, OutOfRange::Reject).expect("in range");
+        assert!(matches!(time, Time::UtcTime(_)));
+        assert_eq!(alloc::format!("{}", time), "2049-12-31T23:59:59Z");
+
+        let time = Time::from_unix_secs(2_524_608_000, OutOfRange::Reject).expect("in range");
+        assert!(matches!(time, Time::GeneralTime(_)));
+        assert_eq!(alloc::format!("{}", time), "2050-01-01T00:00:00Z");
+
+        assert!(Time::from_unix_secs(-1, OutOfRange::Reject).is_err());
+        assert_eq!(
+            Time::from_unix_secs(i64::MAX, OutOfRange::Clamp).expect("clamped"),
+            Time::INFINITY
+        );
+    }
 }
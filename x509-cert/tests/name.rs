@@ -429,3 +429,18 @@ fn decode_given_name() {
     Name::from_str("GN=my_name,SN=my_sn").unwrap();
     Name::from_str("givenName=my_name,SN=my_sn").unwrap();
 }
+
+#[test]
+fn directory_string_case_ignore_eq() {
+    use der::asn1::{BmpString, PrintableString};
+    use x509_cert::ext::pkix::name::DirectoryString;
+
+    let printable = DirectoryString::PrintableString(PrintableString::new("Test  CA").unwrap());
+    let utf8 = DirectoryString::Utf8String("test ca ".into());
+    let bmp = DirectoryString::BmpString(BmpString::from_utf8("TEST CA").unwrap());
+
+    assert!(printable.case_ignore_eq(&utf8));
+    assert!(utf8.case_ignore_eq(&bmp));
+    assert!(!printable.case_ignore_eq(&DirectoryString::Utf8String("Test CB".into())));
+    assert_ne!(printable, utf8);
+}